@@ -10,8 +10,12 @@ fi
 
 pattern="thread_rng|rand::random|std::time::Instant::now|Instant::now"
 
-if grep -R -n -E "$pattern" "$target_dir" >/dev/null; then
+# wallclock.rs is the one place allowed to read the wall clock: it times
+# soak legs, bench runs and tick profiles, which never feed the simulation.
+allowlist="$target_dir/wallclock.rs"
+
+if matches=$(grep -R -n -E "$pattern" "$target_dir" | grep -v "^$allowlist:"); then
   echo "banned nondeterministic APIs found in $target_dir" >&2
-  grep -R -n -E "$pattern" "$target_dir"
+  echo "$matches"
   exit 1
 fi
@@ -5,17 +5,22 @@ use std::io::{BufWriter, Write};
 use std::num::ParseIntError;
 use std::path::PathBuf;
 
+use game::systems::economy::order::sorted_commodities;
 use game::systems::economy::{
     compute_price, load_rulepack, step_economy_day, BasisBp, CommodityId, EconState, EconStepScope,
     EconomyDay, HubId, MoneyCents, Pp, Rulepack,
 };
+use game::systems::trading::types::CommodityCatalog;
 
 const ECON_VERSION: u32 = 1;
 const RULEPACK_PATH: &str = concat!(
     env!("CARGO_MANIFEST_DIR"),
     "/../../assets/rulepacks/day_001.toml"
 );
-const BASE_PRICE_CENTS: i64 = 10_000;
+const COMMODITIES_PATH: &str = concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/../../assets/trading/commodities.toml"
+);
 
 fn main() {
     if let Err(err) = try_main() {
@@ -27,10 +32,12 @@ fn main() {
 fn try_main() -> Result<(), String> {
     let args = Args::parse()?;
     let rulepack = load_rulepack(RULEPACK_PATH).map_err(|err| err.to_string())?;
-    run_sim(&args, &rulepack).map_err(|err| err.to_string())
+    let catalog = CommodityCatalog::load_from_path(COMMODITIES_PATH.as_ref())
+        .map_err(|err| format!("{err:#}"))?;
+    run_sim(&args, &rulepack, &catalog).map_err(|err| err.to_string())
 }
 
-fn run_sim(args: &Args, rp: &Rulepack) -> Result<(), std::io::Error> {
+fn run_sim(args: &Args, rp: &Rulepack, catalog: &CommodityCatalog) -> Result<(), std::io::Error> {
     if let Some(parent) = args.out.parent() {
         if !parent.as_os_str().is_empty() {
             fs::create_dir_all(parent)?;
@@ -38,14 +45,21 @@ fn run_sim(args: &Args, rp: &Rulepack) -> Result<(), std::io::Error> {
     }
     let file = File::create(&args.out)?;
     let mut writer = BufWriter::new(file);
-    writeln!(
+    // Stock columns only appear when the rulepack runs NPC flow, so curves
+    // from rulepacks without it keep their original layout.
+    let stock_columns = rp.stock.replenish.is_active();
+    write!(
         writer,
         "day,hub,com,di_bp,basis_bp,price_cents,debt_cents,interest_cents,pp,rot_u16"
     )?;
+    if stock_columns {
+        write!(writer, ",stock_units,npc_replenished,npc_demand")?;
+    }
+    writeln!(writer)?;
 
     let (mut state, hubs) = seed_state(args, rp);
     for day in 0..args.days {
-        let mut interest_by_hub = Vec::with_capacity(hubs.len());
+        let mut deltas = Vec::with_capacity(hubs.len());
         let mut global_snapshot = None;
         for (idx, hub) in hubs.iter().enumerate() {
             let scope = if idx == 0 {
@@ -62,15 +76,14 @@ fn run_sim(args: &Args, rp: &Rulepack) -> Result<(), std::io::Error> {
                     rot_u16: state.rot_u16,
                 });
             }
-            interest_by_hub.push(delta.interest_delta);
             assert!(delta.day.0 <= day, "delta day monotonic");
+            deltas.push(delta);
         }
 
         let global_snapshot = global_snapshot.expect("at least one hub");
-        let mut commodities: Vec<_> = state.di_bp.keys().copied().collect();
-        commodities.sort_by_key(|c| c.0);
+        let commodities = sorted_commodities(&state.di_bp);
 
-        for (interest, hub_metadata) in interest_by_hub.into_iter().zip(hubs.iter()) {
+        for (delta, hub_metadata) in deltas.iter().zip(hubs.iter()) {
             let hub_id = hub_metadata.id;
             for commodity in &commodities {
                 let di_bp = state.di_bp.get(commodity).copied().unwrap_or(BasisBp(0));
@@ -79,9 +92,15 @@ fn run_sim(args: &Args, rp: &Rulepack) -> Result<(), std::io::Error> {
                     .get(&(hub_id, *commodity))
                     .copied()
                     .unwrap_or(BasisBp(0));
-                let price =
-                    compute_price(MoneyCents(BASE_PRICE_CENTS), di_bp, basis_bp, &rp.pricing);
-                writeln!(
+                let price = compute_price(
+                    catalog.base_price(*commodity),
+                    di_bp,
+                    basis_bp,
+                    &rp.pricing,
+                    rp.pricing.override_for(*commodity),
+                )
+                .price;
+                write!(
                     writer,
                     "{day},{},{},{},{},{},{},{},{},{}",
                     hub_id.0,
@@ -90,10 +109,25 @@ fn run_sim(args: &Args, rp: &Rulepack) -> Result<(), std::io::Error> {
                     basis_bp.0,
                     price.as_i64(),
                     global_snapshot.debt_cents.as_i64(),
-                    interest.as_i64(),
+                    delta.interest_delta.as_i64(),
                     global_snapshot.pp.0,
                     global_snapshot.rot_u16
                 )?;
+                if stock_columns {
+                    let (replenished, demand) = delta
+                        .stock
+                        .iter()
+                        .find(|flow| flow.commodity == *commodity)
+                        .map_or((0, 0), |flow| (flow.replenished, flow.demand));
+                    write!(
+                        writer,
+                        ",{},{},{}",
+                        state.hub_stock(hub_id, *commodity, &rp.stock.replenish),
+                        replenished,
+                        demand
+                    )?;
+                }
+                writeln!(writer)?;
             }
         }
     }
@@ -113,10 +147,14 @@ fn seed_state(args: &Args, rp: &Rulepack) -> (EconState, Vec<HubMetadata>) {
         di_overlay_bp: 0,
         basis_bp: HashMap::new(),
         basis_drivers: HashMap::new(),
+        closed_routes: HashMap::new(),
         pp: Pp(pp_value),
         rot_u16: 0,
         pending_planting: Vec::new(),
         debt_cents: MoneyCents(debt_value),
+        price_history: HashMap::new(),
+        trade_pressure: HashMap::new(),
+        ..EconState::default()
     };
     let hubs = (0..args.hubs)
         .map(|idx| HubMetadata { id: HubId(idx + 1) })
@@ -1,10 +1,15 @@
+use std::collections::BTreeMap;
+
 use bevy::prelude::Resource;
 use serde::{Deserialize, Serialize};
 
+use crate::systems::director::MissionHistory;
 use crate::systems::economy::state::RngCursor;
-use crate::systems::economy::{EconState, HubId, MoneyCents};
+use crate::systems::economy::{EconState, HubId, RouteId};
 use crate::systems::save::InventorySlot;
 use crate::systems::trading::inventory::Cargo;
+use crate::systems::trading::ledger::Wallet;
+use crate::world::closures::RouteClosures;
 
 #[derive(Debug, Clone, Resource, Serialize, Deserialize)]
 pub struct AppState {
@@ -15,7 +20,15 @@ pub struct AppState {
     pub inventory: Vec<InventorySlot>,
     pub cargo: Cargo,
     pub rng_cursors: Vec<RngCursor>,
-    pub wallet: MoneyCents,
+    pub wallet: Wallet,
+    #[serde(default)]
+    pub route_closures: RouteClosures,
+    /// Completed legs per route.
+    #[serde(default)]
+    pub visited_links: BTreeMap<RouteId, u32>,
+    /// Resolved mission outcomes of the most recent legs.
+    #[serde(default)]
+    pub mission_history: MissionHistory,
 }
 
 impl Default for AppState {
@@ -28,11 +41,39 @@ impl Default for AppState {
             inventory: Vec::new(),
             cargo: Cargo::default(),
             rng_cursors: Vec::new(),
-            wallet: MoneyCents::ZERO,
+            wallet: Wallet::default(),
+            route_closures: RouteClosures::default(),
+            visited_links: BTreeMap::new(),
+            mission_history: MissionHistory::default(),
         }
     }
 }
 
+impl AppState {
+    /// Counts one more completed leg on `route`.
+    pub fn record_visit(&mut self, route: RouteId) {
+        let count = self.visited_links.entry(route).or_insert(0);
+        *count = count.saturating_add(1);
+    }
+
+    /// Legs completed on `route`.
+    pub fn route_visit_count(&self, route: RouteId) -> u32 {
+        self.visited_links.get(&route).copied().unwrap_or(0)
+    }
+
+    /// The route with the most completed legs, the lowest id on a tie, or
+    /// `None` before any leg has finished.
+    pub fn most_visited(&self) -> Option<(RouteId, u32)> {
+        self.visited_links
+            .iter()
+            .filter(|(_, count)| **count > 0)
+            .fold(None, |best, (route, count)| match best {
+                Some((_, best_count)) if best_count >= *count => best,
+                _ => Some((*route, *count)),
+            })
+    }
+}
+
 impl PartialEq for AppState {
     fn eq(&self, other: &Self) -> bool {
         self.econ_version == other.econ_version
@@ -42,6 +83,9 @@ impl PartialEq for AppState {
             && self.cargo == other.cargo
             && self.rng_cursors == other.rng_cursors
             && self.wallet == other.wallet
+            && self.route_closures == other.route_closures
+            && self.visited_links == other.visited_links
+            && self.mission_history == other.mission_history
             && econ_eq(&self.econ, &other.econ)
     }
 }
@@ -57,4 +101,43 @@ fn econ_eq(a: &EconState, b: &EconState) -> bool {
         && a.rot_u16 == b.rot_u16
         && a.pending_planting == b.pending_planting
         && a.debt_cents == b.debt_cents
+        && a.price_history == b.price_history
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unvisited_routes_count_zero() {
+        let state = AppState::default();
+        assert_eq!(state.route_visit_count(RouteId(4)), 0);
+        assert_eq!(state.most_visited(), None);
+    }
+
+    #[test]
+    fn repeated_legs_accumulate() {
+        let mut state = AppState::default();
+        for route in [2, 5, 2, 2, 5] {
+            state.record_visit(RouteId(route));
+        }
+        assert_eq!(state.route_visit_count(RouteId(2)), 3);
+        assert_eq!(state.route_visit_count(RouteId(5)), 2);
+        assert_eq!(state.route_visit_count(RouteId(1)), 0);
+        assert_eq!(state.most_visited(), Some((RouteId(2), 3)));
+    }
+
+    #[test]
+    fn ties_go_to_the_lowest_route() {
+        let mut state = AppState::default();
+        for route in [7, 3, 9, 7, 9, 3] {
+            state.record_visit(RouteId(route));
+        }
+        assert_eq!(state.most_visited(), Some((RouteId(3), 2)));
+
+        // Stale zero entries, as a hand-edited save could carry, never win.
+        let mut state = AppState::default();
+        state.visited_links.insert(RouteId(1), 0);
+        assert_eq!(state.most_visited(), None);
+    }
 }
@@ -0,0 +1,155 @@
+//! Headless tick-loop benchmark: steps one leg at a fixed seed without
+//! building or writing a record and reports throughput, so regressions in
+//! the deterministic loop show up as numbers.
+
+use anyhow::Result;
+use serde::Serialize;
+
+use crate::cli::{CliOptions, Mode};
+use crate::logs::m2;
+use crate::systems::director::LegContext;
+use crate::systems::economy::log as econ_log;
+use crate::wallclock::Stopwatch;
+use crate::{leg_context_from_options, start_leg_app, step_leg_app};
+
+/// World seed every `--mode bench` run uses, so runs stay comparable.
+pub const BENCH_WORLD_SEED: u64 = 0xBE7C_2024_0000_0001;
+/// Measured ticks when `--ticks` is not given: two minutes at 30 Hz.
+pub const DEFAULT_BENCH_TICKS: u32 = 3_600;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct BenchReport {
+    pub world_seed: u64,
+    /// Ticks stepped before measuring started.
+    pub warmup_ticks: u32,
+    /// Measured ticks.
+    pub ticks: u32,
+    pub wall_nanos: u64,
+    pub ticks_per_sec: u64,
+    /// Commands emitted by the measured ticks.
+    pub commands: u64,
+    pub commands_per_sec: u64,
+    /// Most commands queued by a single measured tick.
+    pub peak_queue_len: usize,
+    /// Heap allocations during the measured ticks, process-wide; only with
+    /// the `count_allocations` feature.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub allocations: Option<u64>,
+}
+
+/// Measures `ticks` fixed steps of the leg `context` describes, with no
+/// warmup.
+pub fn bench_leg(context: LegContext, ticks: u32) -> Result<BenchReport> {
+    bench_leg_with(&CliOptions::for_mode(Mode::Bench), context, 0, ticks)
+}
+
+/// Steps `warmup` unmeasured ticks, then measures `ticks` more. Commands are
+/// counted and dropped; meters are not validated and nothing is written.
+pub fn bench_leg_with(
+    options: &CliOptions,
+    context: LegContext,
+    warmup: u32,
+    ticks: u32,
+) -> Result<BenchReport> {
+    let mut options = options.clone();
+    options.headless = true;
+    options.profile_ticks = false;
+    options.autosave_dir = None;
+
+    let mut app = start_leg_app(&options, context);
+    for _ in 0..warmup {
+        step_leg_app(&mut app, &options, None)?;
+    }
+
+    let allocations_before = allocation_count();
+    let started = Stopwatch::start();
+    let mut commands = 0u64;
+    let mut peak_queue_len = 0usize;
+    for _ in 0..ticks {
+        let drained = step_leg_app(&mut app, &options, None)?;
+        peak_queue_len = peak_queue_len.max(drained.len());
+        commands += drained.len() as u64;
+    }
+    let wall_nanos = started.elapsed().as_nanos().max(1);
+    let allocations = allocation_count()
+        .zip(allocations_before)
+        .map(|(after, before)| after - before);
+
+    Ok(BenchReport {
+        world_seed: context.world_seed,
+        warmup_ticks: warmup,
+        ticks,
+        wall_nanos: u64::try_from(wall_nanos).unwrap_or(u64::MAX),
+        ticks_per_sec: per_sec(u64::from(ticks), wall_nanos),
+        commands,
+        commands_per_sec: per_sec(commands, wall_nanos),
+        peak_queue_len,
+        allocations,
+    })
+}
+
+fn per_sec(count: u64, wall_nanos: u128) -> u64 {
+    let rate = u128::from(count) * 1_000_000_000 / wall_nanos;
+    u64::try_from(rate).unwrap_or(u64::MAX)
+}
+
+/// `--mode bench`: prints the report as JSON.
+pub(crate) fn run(options: CliOptions) -> Result<()> {
+    m2::set_enabled(false);
+    econ_log::set_enabled(false);
+    let mut context = leg_context_from_options(&options)?;
+    context.world_seed = BENCH_WORLD_SEED;
+    let ticks = options.ticks.unwrap_or(DEFAULT_BENCH_TICKS);
+    let report = bench_leg_with(&options, context, options.warmup, ticks)?;
+    println!("{}", serde_json::to_string(&report)?);
+    Ok(())
+}
+
+/// Heap allocations since startup, process-wide, when built with the
+/// `count_allocations` feature, which installs a counting global allocator.
+#[cfg(feature = "count_allocations")]
+pub fn allocation_count() -> Option<u64> {
+    Some(counter::ALLOCATIONS.load(std::sync::atomic::Ordering::Relaxed))
+}
+
+/// Always `None` without the `count_allocations` feature.
+#[cfg(not(feature = "count_allocations"))]
+pub fn allocation_count() -> Option<u64> {
+    None
+}
+
+#[cfg(feature = "count_allocations")]
+mod counter {
+    use std::alloc::{GlobalAlloc, Layout, System};
+    use std::sync::atomic::{AtomicU64, Ordering};
+
+    pub(super) static ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+
+    /// The system allocator, counting every allocation and reallocation.
+    struct Counting;
+
+    // SAFETY: every call is forwarded unchanged to `System`.
+    unsafe impl GlobalAlloc for Counting {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+            unsafe { System.alloc(layout) }
+        }
+
+        unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+            ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+            unsafe { System.alloc_zeroed(layout) }
+        }
+
+        unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+            ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+            unsafe { System.realloc(ptr, layout, new_size) }
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            unsafe { System.dealloc(ptr, layout) }
+        }
+    }
+
+    #[global_allocator]
+    static GLOBAL: Counting = Counting;
+}
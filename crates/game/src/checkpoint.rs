@@ -0,0 +1,237 @@
+//! Mid-leg checkpoints: the simulation resources as they stood before a
+//! chosen fixed step, enough to resume the leg and reproduce the rest of its
+//! command stream without re-running the steps before it.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{anyhow, Context, Result};
+use bevy::prelude::World;
+use repro::{canonical_json_bytes, Command, Record};
+use serde::{Deserialize, Serialize};
+
+use crate::app_state::AppState;
+use crate::cli::CliOptions;
+use crate::runtime::{drive_ticks, StopCondition};
+use crate::systems::command_queue::CommandQueue;
+use crate::systems::director::{
+    DirectorState, EconIntent, LegClock, LegContext, MissionHistory, MissionOutcomes,
+    MissionRuntime, PauseState, PhysicsCadence, SlowmoAccumulator, SpawnMemory, WheelState,
+};
+use crate::systems::save::{app_state_from_snapshot, snapshot_from_app_state, SaveV18};
+use crate::systems::trading::ledger::LedgerJournal;
+use crate::world::closures::RouteClosures;
+use crate::{
+    finish_leg_app, leg_context_from_record, leg_meters, leg_record, start_leg_app, step_leg_app,
+};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Checkpoint {
+    /// Fixed steps run before the snapshot was taken.
+    pub step: u32,
+    /// Fixed steps in the recorded run; a resumed replay stops here too.
+    pub ticks: u32,
+    /// Commands drained before `step`; the resumed tail starts at this index.
+    pub command_index: usize,
+    /// Commands queued but not yet drained; they come out with `step`.
+    pub queued: Vec<Command>,
+    pub director: DirectorState,
+    pub context: LegContext,
+    /// Spawn budgets plus the spawn seed and counter that drive type draws.
+    pub spawn_memory: SpawnMemory,
+    pub missions: MissionRuntime,
+    #[serde(default)]
+    pub mission_outcomes: MissionOutcomes,
+    pub econ_intent: EconIntent,
+    pub clock: LegClock,
+    pub wheel: WheelState,
+    pub pause: PauseState,
+    pub slowmo: SlowmoAccumulator,
+    pub(crate) physics: PhysicsCadence,
+    pub closures: RouteClosures,
+    #[serde(default)]
+    pub mission_history: MissionHistory,
+    /// Economy, cargo, wallet and economy RNG cursors, in save form.
+    pub save: SaveV18,
+}
+
+impl Checkpoint {
+    pub fn capture(world: &World, step: u32, ticks: u32, command_index: usize) -> Self {
+        Self {
+            step,
+            ticks,
+            command_index,
+            queued: world.resource::<CommandQueue>().buf.clone(),
+            director: world.resource::<DirectorState>().clone(),
+            context: *world.resource::<LegContext>(),
+            spawn_memory: world.resource::<SpawnMemory>().clone(),
+            missions: world.resource::<MissionRuntime>().clone(),
+            mission_outcomes: world.resource::<MissionOutcomes>().clone(),
+            econ_intent: *world.resource::<EconIntent>(),
+            clock: *world.resource::<LegClock>(),
+            wheel: *world.resource::<WheelState>(),
+            pause: *world.resource::<PauseState>(),
+            slowmo: *world.resource::<SlowmoAccumulator>(),
+            physics: *world.resource::<PhysicsCadence>(),
+            closures: world.resource::<RouteClosures>().clone(),
+            mission_history: world.resource::<MissionHistory>().clone(),
+            save: snapshot_from_app_state(world.resource::<AppState>()),
+        }
+    }
+
+    /// Overwrites the leg resources in `world` with the checkpoint's.
+    pub fn restore(&self, world: &mut World) {
+        world.insert_resource(self.director.clone());
+        world.insert_resource(self.context);
+        world.insert_resource(self.spawn_memory.clone());
+        world.insert_resource(self.missions.clone());
+        world.insert_resource(self.mission_outcomes.clone());
+        world.insert_resource(self.econ_intent);
+        world.insert_resource(self.clock);
+        world.insert_resource(self.wheel);
+        world.insert_resource(self.pause);
+        world.insert_resource(self.slowmo);
+        world.insert_resource(self.physics);
+        world.insert_resource(self.closures.clone());
+        world.insert_resource(self.mission_history.clone());
+        world.insert_resource(app_state_from_snapshot(self.save.clone()));
+        if let Some(mut journal) = world.get_resource_mut::<LedgerJournal>() {
+            journal.rebase();
+        }
+        world.resource_mut::<CommandQueue>().buf = self.queued.clone();
+    }
+}
+
+/// `leg.json` → `leg.checkpoint.json`, next to the record.
+pub fn checkpoint_path(record: &Path) -> PathBuf {
+    record.with_extension("checkpoint.json")
+}
+
+pub fn write_checkpoint(path: &Path, checkpoint: &Checkpoint) -> Result<()> {
+    let bytes = canonical_json_bytes(checkpoint)?;
+    fs::write(path, bytes).with_context(|| format!("writing checkpoint {}", path.display()))
+}
+
+pub fn read_checkpoint(path: &Path) -> Result<Checkpoint> {
+    let bytes = fs::read(path).with_context(|| format!("reading checkpoint {}", path.display()))?;
+    serde_json::from_slice(&bytes).with_context(|| format!("parsing checkpoint {}", path.display()))
+}
+
+/// Records a leg like [`record_leg`](crate::record_leg) and captures a
+/// checkpoint before fixed step `at_step`. The checkpoint's `ticks` is the
+/// steps the leg actually ran, so a resumed replay stops where it did.
+pub fn record_leg_with_checkpoint(
+    options: &CliOptions,
+    context: LegContext,
+    stop: impl Into<StopCondition>,
+    at_step: u32,
+) -> Result<(Record, DirectorState, Checkpoint)> {
+    let stop = stop.into();
+    if at_step > stop.max_ticks() {
+        return Err(anyhow!(
+            "checkpoint step {at_step} is past the leg's {} steps",
+            stop.max_ticks()
+        ));
+    }
+    crate::world::index::ensure_world_index()?;
+    let meters = leg_meters(options)?;
+    let mut app = start_leg_app(options, context);
+    let mut commands: Vec<Command> = Vec::new();
+    let head = drive_ticks(
+        &mut app,
+        options,
+        meters,
+        stop.capped(at_step),
+        false,
+        |_, drained| commands.extend(drained),
+    )?;
+    if head < at_step {
+        return Err(anyhow!(
+            "checkpoint step {at_step} is past the leg's {head} steps"
+        ));
+    }
+    let mut checkpoint = Checkpoint::capture(app.world(), at_step, at_step, commands.len());
+    let tail = drive_ticks(
+        &mut app,
+        options,
+        meters,
+        stop.after(head),
+        false,
+        |_, drained| commands.extend(drained),
+    )?;
+    checkpoint.ticks = head + tail;
+    let (state, _, mut annotations) = finish_leg_app(&app, options)?;
+    let record = leg_record(options, context, commands, &state, &mut annotations)?;
+    Ok((record, state, checkpoint))
+}
+
+/// The tail of a leg replayed from a checkpoint.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CheckpointReplay {
+    /// Index into the record's commands where the tail starts.
+    pub start: usize,
+    /// Commands the resumed leg emitted.
+    pub commands: Vec<Command>,
+    /// Record indices where the tail diverged. Without
+    /// `continue_after_mismatch` the replay stops after the first step that
+    /// diverges.
+    pub mismatches: Vec<usize>,
+}
+
+impl CheckpointReplay {
+    pub fn is_match(&self) -> bool {
+        self.mismatches.is_empty()
+    }
+}
+
+/// Restores `checkpoint` into a fresh app for the leg `record` describes and
+/// replays the remaining steps, comparing each command against the record.
+pub fn replay_from_checkpoint(
+    options: &CliOptions,
+    record: &Record,
+    checkpoint: &Checkpoint,
+    continue_after_mismatch: bool,
+) -> Result<CheckpointReplay> {
+    let start = checkpoint.command_index;
+    let expected = record.commands.get(start..).ok_or_else(|| {
+        anyhow!(
+            "checkpoint starts at command {start} but the record has {}",
+            record.commands.len()
+        )
+    })?;
+    let context = leg_context_from_record(&record.meta, options)?;
+    let mut options = options.clone();
+    options.rulepack = Some(record.meta.rulepack.clone());
+    crate::world::index::ensure_world_index()?;
+    let meters = leg_meters(&options)?;
+
+    let mut app = start_leg_app(&options, context);
+    checkpoint.restore(app.world_mut());
+
+    let mut replay = CheckpointReplay {
+        start,
+        ..CheckpointReplay::default()
+    };
+    for _ in checkpoint.step..checkpoint.ticks {
+        let drained = step_leg_app(&mut app, &options, meters)?;
+        for actual in drained {
+            let offset = replay.commands.len();
+            // Past the record's end only the first extra command counts.
+            let diverged = match expected.get(offset) {
+                Some(expected) => *expected != actual,
+                None => offset == expected.len(),
+            };
+            if diverged {
+                replay.mismatches.push(start + offset);
+            }
+            replay.commands.push(actual);
+        }
+        if !continue_after_mismatch && !replay.mismatches.is_empty() {
+            return Ok(replay);
+        }
+    }
+    if replay.commands.len() < expected.len() {
+        replay.mismatches.push(start + replay.commands.len());
+    }
+    Ok(replay)
+}
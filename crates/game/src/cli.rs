@@ -1,26 +1,126 @@
 use clap::{builder::BoolishValueParser, ArgAction, Parser, ValueEnum};
+use thiserror::Error;
 
+use repro::{fixed_dt_nanos, Annotation, CommandSchema};
+
+use crate::runtime::{StopCondition, DEFAULT_LEG_TICKS, DEFAULT_MAX_LEG_TICKS};
+use crate::systems::annotations::parse_annotation;
 use crate::systems::economy::Weather;
+use crate::ui::i18n::DEFAULT_LANG;
 
 const DEFAULT_WORLD_SEED: u64 = 0xD7E7_2024_0001_0001;
 const DEFAULT_LINK_ID: u16 = 11;
 const DEFAULT_DAY: u32 = 3;
 const DEFAULT_PP: u16 = 120;
-const DEFAULT_DENSITY_PER_10K: u32 = 5;
-const DEFAULT_CADENCE_PER_MIN: u32 = 3;
-const DEFAULT_MISSION_MINUTES: u32 = 8;
-const DEFAULT_PLAYER_RATING: u8 = 50;
+pub(crate) const DEFAULT_DENSITY_PER_10K: u32 = 5;
+pub(crate) const DEFAULT_CADENCE_PER_MIN: u32 = 3;
+pub(crate) const DEFAULT_MISSION_MINUTES: u32 = 8;
+pub(crate) const DEFAULT_PLAYER_RATING: u8 = 50;
+
+/// World seed for a memorable phrase such as `bluefox`: the first eight
+/// bytes of its blake3 hash, little-endian. The phrase is used as given, so
+/// case and spacing matter.
+pub fn seed_from_phrase(phrase: &str) -> u64 {
+    let hash = blake3::hash(phrase.as_bytes());
+    let mut bytes = [0u8; 8];
+    bytes.copy_from_slice(&hash.as_bytes()[..8]);
+    u64::from_le_bytes(bytes)
+}
+
+/// Hex (`0x…`) or decimal seeds parse as numbers, and a malformed one is an
+/// error rather than a phrase; anything else goes through
+/// [`seed_from_phrase`].
+fn parse_record_schema(value: &str) -> Result<CommandSchema, String> {
+    let number: u32 = value.trim().parse().map_err(|err| format!("{err}"))?;
+    CommandSchema::try_from(number)
+}
 
-fn parse_u64(value: &str) -> Result<u64, String> {
+fn parse_world_seed(value: &str) -> Result<u64, String> {
     let trimmed = value.trim();
+    if trimmed.is_empty() {
+        return Err("world seed is empty".to_owned());
+    }
     if let Some(hex) = trimmed
         .strip_prefix("0x")
         .or_else(|| trimmed.strip_prefix("0X"))
     {
         u64::from_str_radix(hex, 16).map_err(|err| err.to_string())
-    } else {
+    } else if trimmed.bytes().all(|byte| byte.is_ascii_digit()) {
         trimmed.parse::<u64>().map_err(|err| err.to_string())
+    } else {
+        Ok(seed_from_phrase(trimmed))
+    }
+}
+
+/// Rates whose step is not a whole number of nanoseconds but is still
+/// accepted everywhere: records pin the rounded step in `fixed_dt_ns`, and
+/// the goldens were recorded at them. A step matches a rate when both round
+/// to the same nanosecond, so a truncated `0.0333333333` counts as 30 Hz.
+pub const TOLERATED_RATES_HZ: [u32; 3] = [30, 60, 120];
+/// Rates `--list-safe-dts` considers.
+const COMMON_RATES_HZ: [u32; 14] = [20, 24, 25, 30, 40, 50, 60, 64, 100, 120, 125, 128, 200, 250];
+
+/// How a fixed step maps onto whole nanoseconds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FixedDtStatus {
+    /// The step is a whole number of nanoseconds.
+    Exact,
+    /// The period of one of [`TOLERATED_RATES_HZ`], to the nanosecond.
+    Tolerated { hz: u32 },
+    /// Any other step; its nanosecond rounding is not pinned anywhere.
+    Inexact,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FixedDtCheck {
+    /// The step rounded to the nearest nanosecond, as records store it.
+    pub nanos: u64,
+    pub status: FixedDtStatus,
+}
+
+#[derive(Debug, Error, PartialEq)]
+pub enum FixedDtError {
+    #[error("--fixed-dt must be a finite step of at least one nanosecond, got {0}")]
+    NotPositive(f64),
+    #[error(
+        "--fixed-dt {dt} is not a whole number of nanoseconds (rounds to {nanos} ns); \
+         deterministic builds need a step from --list-safe-dts"
+    )]
+    Inexact { dt: f64, nanos: u64 },
+}
+
+/// Classifies `dt` seconds without warning or failing on inexact steps.
+#[allow(clippy::float_arithmetic)]
+pub fn check_fixed_dt(dt: f64) -> Result<FixedDtCheck, FixedDtError> {
+    let nanos = fixed_dt_nanos(dt);
+    if nanos == 0 {
+        return Err(FixedDtError::NotPositive(dt));
     }
+    let status = if nanos as f64 / 1e9 == dt {
+        FixedDtStatus::Exact
+    } else if let Some(hz) = TOLERATED_RATES_HZ
+        .into_iter()
+        .find(|hz| nanos == fixed_dt_nanos(1.0 / f64::from(*hz)))
+    {
+        FixedDtStatus::Tolerated { hz }
+    } else {
+        FixedDtStatus::Inexact
+    };
+    Ok(FixedDtCheck { nanos, status })
+}
+
+/// Common rates whose step is exact or tolerated, with the `--fixed-dt`
+/// value for each, slowest first.
+#[allow(clippy::float_arithmetic)]
+pub fn safe_fixed_dts() -> Vec<(u32, f64, FixedDtCheck)> {
+    COMMON_RATES_HZ
+        .into_iter()
+        .filter_map(|hz| {
+            let dt = 1.0 / f64::from(hz);
+            let check = check_fixed_dt(dt).ok()?;
+            (check.status != FixedDtStatus::Inexact).then_some((hz, dt, check))
+        })
+        .collect()
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
@@ -47,6 +147,10 @@ pub enum Mode {
     Play,
     Record,
     Replay,
+    /// Records the leg twice and replays it, checking all three agree.
+    SelfTest,
+    /// Times the headless tick loop at a fixed seed and prints a JSON report.
+    Bench,
 }
 
 #[derive(Debug, Parser, Clone)]
@@ -63,6 +167,9 @@ pub struct CliOptions {
     pub io: Option<String>,
     #[arg(long = "fixed-dt")]
     pub fixed_dt: Option<f64>,
+    /// Prints the common fixed steps that are exact or tolerated and exits.
+    #[arg(long = "list-safe-dts")]
+    pub list_safe_dts: bool,
     #[arg(long)]
     pub headless: bool,
     #[arg(
@@ -76,7 +183,9 @@ pub struct CliOptions {
     pub continue_after_mismatch: bool,
     #[arg(long = "debug-logs")]
     pub debug_logs: bool,
-    #[arg(long = "world-seed", value_parser = parse_u64, default_value = "0xD7E7202400010001")]
+    #[arg(long = "autosave-dir")]
+    pub autosave_dir: Option<String>,
+    #[arg(long = "world-seed", value_parser = parse_world_seed, default_value = "0xD7E7202400010001")]
     world_seed: u64,
     #[arg(long = "link-id", default_value_t = DEFAULT_LINK_ID)]
     link_id: u16,
@@ -88,12 +197,79 @@ pub struct CliOptions {
     pp: u16,
     #[arg(long = "density-per-10k", default_value_t = DEFAULT_DENSITY_PER_10K)]
     density_per_10k: u32,
-    #[arg(long = "cadence-per-min", default_value_t = DEFAULT_CADENCE_PER_MIN)]
-    cadence_per_min: u32,
-    #[arg(long = "mission-minutes", default_value_t = DEFAULT_MISSION_MINUTES)]
-    mission_minutes: u32,
+    /// Overrides the record's cadence on replay when given.
+    #[arg(long = "cadence-per-min")]
+    cadence_per_min: Option<u32>,
+    /// Overrides the record's mission length on replay when given.
+    #[arg(long = "mission-minutes")]
+    mission_minutes: Option<u32>,
     #[arg(long = "player-rating", default_value_t = DEFAULT_PLAYER_RATING)]
     player_rating: u8,
+    /// Prints the world graph validation report and exits.
+    #[arg(long = "validate-world")]
+    pub validate_world: bool,
+    /// Steps the economy to `--day` without trading, prints every hub's
+    /// quotes and exits.
+    #[arg(long = "market-snapshot")]
+    pub market_snapshot: bool,
+    /// Re-simulates every case in the golden manifest (`--io`, or
+    /// `repro/records/manifest.toml`), prints each hash and exits non-zero on
+    /// an untolerated mismatch.
+    #[arg(long = "verify-goldens")]
+    pub verify_goldens: bool,
+    /// Rewrites every golden record, its `.hash` and the manifest hashes from
+    /// a fresh run.
+    #[arg(long = "update-goldens", conflicts_with = "verify_goldens")]
+    pub update_goldens: bool,
+    /// Shows the director debug overlay in the windowed build.
+    #[arg(long = "debug-overlay")]
+    pub debug_overlay: bool,
+    /// Notes runs of at least this many command-free ticks as idle spans in
+    /// the record meta.
+    #[arg(long = "coalesce-idle")]
+    pub coalesce_idle: Option<u32>,
+    /// Command layout for written records: 1 repeats the tick on every
+    /// command, 2 groups commands under their tick.
+    #[arg(long = "record-schema", value_parser = parse_record_schema, default_value = "1")]
+    pub record_schema: CommandSchema,
+    /// Replays records with missing or unknown fields, filling defaults.
+    #[arg(long)]
+    pub lenient: bool,
+    /// Times each director system set and writes `<record>.profile.json`
+    /// next to the record.
+    #[arg(long = "profile-ticks")]
+    pub profile_ticks: bool,
+    /// Runs every day on this rulepack instead of the day's entry in
+    /// `assets/rulepacks/schedule.toml`. Replays set it from the record.
+    #[arg(long)]
+    pub rulepack: Option<String>,
+    /// Writes `<record>.checkpoint.json` with the leg state before this
+    /// fixed step.
+    #[arg(long = "checkpoint-at")]
+    pub checkpoint_at: Option<u32>,
+    /// Replays only the steps after this checkpoint, checking the tail of
+    /// the record.
+    #[arg(long = "from-checkpoint")]
+    pub from_checkpoint: Option<String>,
+    /// UI language, loaded from `assets/i18n/<lang>.toml`.
+    #[arg(long, default_value = DEFAULT_LANG)]
+    pub lang: String,
+    /// Marks a tick in the written record: `t=123,label=foo[,note=...]`.
+    /// Repeatable.
+    #[arg(long = "annotate", value_parser = parse_annotation)]
+    pub annotate: Vec<Annotation>,
+    /// Fixed steps a played or recorded leg runs (120 by default), or the
+    /// cap on them with `--run-to-completion`. `--mode bench` measures this
+    /// many.
+    #[arg(long)]
+    pub ticks: Option<u32>,
+    /// Runs played and recorded legs until they complete, capped by
+    /// `--ticks` when given.
+    #[arg(long = "run-to-completion")]
+    pub run_to_completion: bool,
+    /// Fixed steps `--mode bench` runs before measuring.
+    #[arg(long, default_value_t = 0)]
+    pub warmup: u32,
 }
 
 impl CliOptions {
@@ -111,23 +287,64 @@ impl CliOptions {
         self.fixed_dt.unwrap_or(Self::DEFAULT_FIXED_DT)
     }
 
+    /// Checks the effective fixed step. An inexact step is logged as a
+    /// warning, or rejected in `deterministic` builds; tolerated rates such
+    /// as the default 30 Hz pass everywhere.
+    pub fn validate_fixed_dt(&self) -> Result<FixedDtCheck, FixedDtError> {
+        let dt = self.effective_fixed_dt();
+        let check = check_fixed_dt(dt)?;
+        if check.status == FixedDtStatus::Inexact {
+            if cfg!(feature = "deterministic") {
+                return Err(FixedDtError::Inexact {
+                    dt,
+                    nanos: check.nanos,
+                });
+            }
+            log::warn!(
+                "--fixed-dt {dt} is not a whole number of nanoseconds; \
+                 it runs as {} ns and may not replay identically elsewhere",
+                check.nanos
+            );
+        }
+        Ok(check)
+    }
+
     pub fn for_mode(mode: Mode) -> Self {
         Self {
             mode,
             io: None,
             fixed_dt: None,
+            list_safe_dts: false,
             headless: false,
             continue_after_mismatch: true,
             debug_logs: false,
+            autosave_dir: None,
             world_seed: DEFAULT_WORLD_SEED,
             link_id: DEFAULT_LINK_ID,
             day: DEFAULT_DAY,
             weather: WeatherArg::Clear,
             pp: DEFAULT_PP,
             density_per_10k: DEFAULT_DENSITY_PER_10K,
-            cadence_per_min: DEFAULT_CADENCE_PER_MIN,
-            mission_minutes: DEFAULT_MISSION_MINUTES,
+            cadence_per_min: None,
+            mission_minutes: None,
             player_rating: DEFAULT_PLAYER_RATING,
+            validate_world: false,
+            market_snapshot: false,
+            verify_goldens: false,
+            update_goldens: false,
+            debug_overlay: false,
+            coalesce_idle: None,
+            record_schema: CommandSchema::default(),
+            lenient: false,
+            profile_ticks: false,
+            rulepack: None,
+            checkpoint_at: None,
+            from_checkpoint: None,
+            lang: DEFAULT_LANG.to_string(),
+            annotate: Vec::new(),
+            ticks: None,
+            run_to_completion: false,
+            warmup: 0,
         }
     }
 
@@ -156,14 +373,36 @@ impl CliOptions {
     }
 
     pub fn cadence_per_min(&self) -> u32 {
+        self.cadence_per_min.unwrap_or(DEFAULT_CADENCE_PER_MIN)
+    }
+
+    /// The cadence passed on the command line, if any.
+    pub fn cadence_per_min_override(&self) -> Option<u32> {
         self.cadence_per_min
     }
 
     pub fn mission_minutes(&self) -> u32 {
+        self.mission_minutes.unwrap_or(DEFAULT_MISSION_MINUTES)
+    }
+
+    /// The mission length passed on the command line, if any.
+    pub fn mission_minutes_override(&self) -> Option<u32> {
         self.mission_minutes
     }
 
     pub fn player_rating(&self) -> u8 {
         self.player_rating
     }
+
+    /// How long played and recorded legs run: `--ticks` steps, or until the
+    /// leg completes with `--run-to-completion`.
+    pub fn stop_condition(&self) -> StopCondition {
+        if self.run_to_completion {
+            StopCondition::Completion {
+                max_ticks: self.ticks.unwrap_or(DEFAULT_MAX_LEG_TICKS),
+            }
+        } else {
+            StopCondition::Ticks(self.ticks.unwrap_or(DEFAULT_LEG_TICKS))
+        }
+    }
 }
@@ -4,6 +4,8 @@ use bevy::diagnostic::{DiagnosticsStore, FrameTimeDiagnosticsPlugin};
 use bevy::prelude::*;
 use bevy::text::{TextColor, TextFont};
 
+use crate::profiling::TickProfiler;
+
 pub struct DiagnosticsUiPlugin;
 impl Plugin for DiagnosticsUiPlugin {
     fn build(&self, app: &mut App) {
@@ -19,6 +21,8 @@ struct FpsText;
 struct MsText;
 #[derive(Component)]
 struct EntText;
+#[derive(Component)]
+struct ProfText;
 
 fn setup_ui(mut cmds: Commands, asset_server: Res<AssetServer>) {
     let font_handle = asset_server.load("fonts/inter-regular.ttf");
@@ -42,7 +46,8 @@ fn setup_ui(mut cmds: Commands, asset_server: Res<AssetServer>) {
         let color = TextColor(Color::WHITE);
         p.spawn((Text::new("FPS: --"), font.clone(), color, FpsText));
         p.spawn((Text::new("CPU ms: --"), font.clone(), color, MsText));
-        p.spawn((Text::new("Entities: --"), font, color, EntText));
+        p.spawn((Text::new("Entities: --"), font.clone(), color, EntText));
+        p.spawn((Text::new(""), font, color, ProfText));
     });
 }
 
@@ -51,11 +56,13 @@ type DiagnosticsQueryData = (
     Option<&'static FpsText>,
     Option<&'static MsText>,
     Option<&'static EntText>,
+    Option<&'static ProfText>,
 );
 
 fn update_ui(
     diagnostics: Res<DiagnosticsStore>,
     q_count: Query<Entity>,
+    profiler: Option<Res<TickProfiler>>,
     mut readouts: Query<DiagnosticsQueryData>,
 ) {
     let fps_label = diagnostics
@@ -67,8 +74,17 @@ fn update_ui(
         .and_then(|d| d.smoothed())
         .map(|avg| format!("CPU ms: {:.2}", avg * 1000.0));
     let ent_label = format!("Entities: {}", q_count.iter().count());
+    // Shown only when tick profiling is on.
+    let prof_label = profiler
+        .and_then(|profiler| {
+            let profile = profiler.profile();
+            profile
+                .slowest()
+                .map(|set| format!("Slowest set: {} ({} us/tick)", set.set, set.mean_us))
+        })
+        .unwrap_or_default();
 
-    for (mut text, is_fps, is_ms, is_ent) in &mut readouts {
+    for (mut text, is_fps, is_ms, is_ent, is_prof) in &mut readouts {
         if is_fps.is_some() {
             if let Some(label) = fps_label.as_ref() {
                 text.0 = label.clone();
@@ -83,6 +99,10 @@ fn update_ui(
         }
         if is_ent.is_some() {
             text.0 = ent_label.clone();
+            continue;
+        }
+        if is_prof.is_some() {
+            text.0 = prof_label.clone();
         }
     }
 }
@@ -1,34 +1,64 @@
 pub mod app_state;
+pub mod bench;
+pub mod checkpoint;
 pub mod cli;
 pub mod logs;
+pub mod plugins;
+pub mod profiling;
+pub mod provenance;
+pub mod runtime;
 pub mod scheduling;
+pub mod selftest;
+pub mod soak;
 pub mod systems;
 pub mod ui;
+pub mod wallclock;
 pub mod world;
 
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use anyhow::{anyhow, Context, Result};
 use bevy::app::App;
 use bevy::log::{tracing, LogPlugin};
 use bevy::prelude::*;
 use bevy::time::{Fixed, Time as BevyTime};
+use repro::strict::ParseMode;
 use repro::{
-    canonical_json_bytes, from_canonical_json_bytes, hash_record, Command, Record, RecordMeta,
+    canonical_json_bytes, fixed_dt_nanos, hash_record, Command, Record, RecordMeta, RECORD_SCHEMA,
 };
 
 use crate::app_state::AppState;
 use crate::logs::m2;
+use crate::profiling::{TickProfiler, TickProfilerPlugin};
+use crate::provenance::{AssetCheck, AssetHashes};
+use crate::runtime::{
+    drive_ticks, mismatch_context_path, MismatchContext, StopCondition, TickSnapshot,
+    MAX_MISMATCH_CONTEXTS,
+};
 use cli::{CliOptions, Mode};
 use std::sync::Once;
+use systems::annotations::{annotate_on_key, AnnotationQueue};
 use systems::command_queue::CommandQueue;
 #[cfg(feature = "deterministic")]
 use systems::director::director_cfg_path;
-use systems::director::{DirectorPlugin, DirectorState, LegContext, WheelState};
-use systems::economy::{load_rulepack, Pp, RouteId, Rulepack, Weather};
+use systems::director::{DirectorPlugin, DirectorState, LegContext, LegContextBuilder, WheelState};
+use systems::economy::log as econ_log;
+use systems::economy::rulepack::swap_rulepack_on_day_change;
+use systems::economy::snapshot::market_table;
+use systems::economy::{
+    load_market_events, load_rulepack, CommodityId, EconomyDay, MarketEvents, Pp, RouteId,
+    RulepackSchedule, RulepackSource, ScheduledRulepack, Weather,
+};
+use systems::rng_stream::RngRegistry;
+use systems::save::{AutosavePlugin, SaveSlotPlugin};
+use systems::telemetry::{meter_registry, MeterRegistry};
+use systems::trading::catalog::{CatalogHandle, CatalogWatchPlugin};
 use systems::trading::TradingPlugin;
+use ui::director_overlay::DirectorDebugOverlayPlugin;
 use ui::hub_trade::HubTradePlugin;
+use ui::i18n::Strings;
+use ui::post_leg_report::PostLegReportPlugin;
 use ui::route_planner::RoutePlannerPlugin;
 
 pub fn run() -> Result<()> {
@@ -40,18 +70,84 @@ pub fn run_with_options(options: CliOptions) -> Result<()> {
     init_logging();
     log_determinism_banner();
     m2::set_enabled(options.debug_logs || cfg!(feature = "m2_logs"));
+    econ_log::set_enabled(options.debug_logs || cfg!(feature = "econ_logs"));
+    if cfg!(any(debug_assertions, feature = "deterministic")) {
+        RngRegistry::default().install();
+    }
+    if options.list_safe_dts {
+        run_list_safe_dts();
+        return Ok(());
+    }
+    if options.validate_world {
+        return run_validate_world();
+    }
+    if options.market_snapshot {
+        return run_market_snapshot(&options);
+    }
+    if options.verify_goldens || options.update_goldens {
+        return runtime::run_goldens(&options);
+    }
+    options.validate_fixed_dt()?;
+    world::index::ensure_world_index()?;
     match options.mode() {
         Mode::Play => run_play(options),
         Mode::Record => run_record(options),
         Mode::Replay => run_replay(options),
+        Mode::SelfTest => selftest::run(options),
+        Mode::Bench => bench::run(options),
     }
 }
 
+fn run_list_safe_dts() {
+    for (hz, dt, check) in cli::safe_fixed_dts() {
+        let status = match check.status {
+            cli::FixedDtStatus::Exact => "exact",
+            _ => "tolerated",
+        };
+        println!("{hz:>4} Hz  --fixed-dt {dt}  {} ns  {status}", check.nanos);
+    }
+}
+
+fn run_validate_world() -> Result<()> {
+    let path = world::index::world_graph_path()?;
+    let data = world::index::load_world_graph_data(&path)?;
+    match world::graphcheck::validate_world_graph(&data) {
+        Ok(()) => {
+            println!("{}: ok ({} routes)", path.display(), data.routes.len());
+            Ok(())
+        }
+        Err(defects) => {
+            for defect in &defects {
+                println!("{}: {defect}", path.display());
+            }
+            Err(anyhow!("world graph has {} defect(s)", defects.len()))
+        }
+    }
+}
+
+fn run_market_snapshot(options: &CliOptions) -> Result<()> {
+    let graph = world::index::load_world_graph_data(&world::index::world_graph_path()?)?;
+    let hubs = graph.hub_ids();
+    let catalog = systems::trading::load_default_commodities()?;
+    let schedule = rulepack_schedule(options)?;
+    let commodities: Vec<CommodityId> = catalog.list().iter().map(|spec| spec.id).collect();
+    let econ = systems::economy::verify::resimulate_to_day(
+        options.world_seed(),
+        AppState::default().econ_version,
+        EconomyDay(options.day()),
+        &schedule,
+        &hubs,
+        &commodities,
+    );
+    let _scope = CatalogHandle::new(catalog).scope();
+    let table = market_table(&hubs, &econ, schedule.rulepack_for(econ.day));
+    println!("{table}");
+    Ok(())
+}
+
 fn run_play(options: CliOptions) -> Result<()> {
-    let context = leg_context_from_options(&options);
-    let (_commands, _state) = simulate_ticks(&options, simulation_ticks(), context)?;
-    let _ = _commands;
-    let _ = _state;
+    let context = leg_context_from_options(&options)?;
+    simulate_ticks(&options, options.stop_condition(), context)?;
     Ok(())
 }
 
@@ -61,15 +157,69 @@ fn run_record(options: CliOptions) -> Result<()> {
         .as_ref()
         .map(PathBuf::from)
         .ok_or_else(|| anyhow!("--io path required for record mode"))?;
-    let context = leg_context_from_options(&options);
-    let (commands, state) = simulate_ticks(&options, simulation_ticks(), context)?;
+    let context = leg_context_from_options(&options)?;
+    let record = match options.checkpoint_at {
+        Some(at_step) => {
+            let (record, _, checkpoint) = checkpoint::record_leg_with_checkpoint(
+                &options,
+                context,
+                options.stop_condition(),
+                at_step,
+            )?;
+            checkpoint::write_checkpoint(&checkpoint::checkpoint_path(&path), &checkpoint)?;
+            record
+        }
+        None => record_leg(&options, context, options.stop_condition())?.0,
+    };
 
-    let record = Record {
+    let bytes = canonical_json_bytes(&record)?;
+    if let Some(parent) = path.parent() {
+        if !parent.as_os_str().is_empty() {
+            fs::create_dir_all(parent).with_context(|| format!("creating {}", parent.display()))?;
+        }
+    }
+    fs::write(&path, &bytes).with_context(|| format!("writing record {}", path.display()))?;
+
+    let hash = hash_record(&record)?;
+    let mut hash_path = path.clone();
+    hash_path.set_extension("hash");
+    fs::write(&hash_path, format!("{}\n", hash))
+        .with_context(|| format!("writing record hash {}", hash_path.display()))?;
+    Ok(())
+}
+
+/// Simulates one headless leg until `stop` (a plain tick count runs that
+/// many fixed steps) and returns its record, stamped with the current asset
+/// hashes, along with the director state the leg ended in. Nothing is
+/// written to disk.
+pub fn record_leg(
+    options: &CliOptions,
+    context: LegContext,
+    stop: impl Into<StopCondition>,
+) -> Result<(Record, DirectorState)> {
+    world::index::ensure_world_index()?;
+    let (commands, state, mut annotations) = simulate_ticks(options, stop.into(), context)?;
+    let record = leg_record(options, context, commands, &state, &mut annotations)?;
+    Ok((record, state))
+}
+
+/// Wraps a leg's commands in a record whose meta describes the leg, stamped
+/// with the current asset hashes.
+fn leg_record(
+    options: &CliOptions,
+    context: LegContext,
+    commands: Vec<Command>,
+    state: &DirectorState,
+    annotations: &mut AnnotationQueue,
+) -> Result<Record> {
+    let rulepack = leg_rulepack(options, context.day)?;
+    let assets = AssetHashes::with_rulepack(&rulepack.path)?;
+    let mut record = Record {
         meta: RecordMeta {
-            schema: 1,
+            schema: RECORD_SCHEMA,
             world_seed: format!("0x{:016X}", state.world_seed),
             link_id: format!("{}", state.link_id.0),
-            rulepack: "assets/rulepacks/day_001.toml".into(),
+            rulepack: rulepack.file,
             weather: format!("{:?}", state.weather),
             rng_salt: format!(
                 "0x{:016X}",
@@ -84,25 +234,20 @@ fn run_record(options: CliOptions) -> Result<()> {
             mission_minutes: context.mission_minutes,
             player_rating: context.player_rating,
             prior_danger_score: context.prior_danger_score,
+            fixed_dt_ns: Some(fixed_dt_nanos(options.effective_fixed_dt())),
+            command_schema: options.record_schema,
+            ..RecordMeta::default()
         },
         commands,
         inputs: Vec::new(),
+        annotations: Vec::new(),
     };
-
-    let bytes = canonical_json_bytes(&record)?;
-    if let Some(parent) = path.parent() {
-        if !parent.as_os_str().is_empty() {
-            fs::create_dir_all(parent).with_context(|| format!("creating {}", parent.display()))?;
-        }
+    assets.stamp(&mut record.meta);
+    annotations.flush_into(&mut record);
+    if let Some(min_ticks) = options.coalesce_idle {
+        record.meta.idle_spans = runtime::leg_idle_spans(&record.commands, state, min_ticks);
     }
-    fs::write(&path, &bytes).with_context(|| format!("writing record {}", path.display()))?;
-
-    let hash = hash_record(&record)?;
-    let mut hash_path = path.clone();
-    hash_path.set_extension("hash");
-    fs::write(&hash_path, format!("{}\n", hash))
-        .with_context(|| format!("writing record hash {}", hash_path.display()))?;
-    Ok(())
+    Ok(record)
 }
 
 fn run_replay(options: CliOptions) -> Result<()> {
@@ -112,51 +257,202 @@ fn run_replay(options: CliOptions) -> Result<()> {
         .map(PathBuf::from)
         .ok_or_else(|| anyhow!("--io path required for replay mode"))?;
     let bytes = fs::read(&path).with_context(|| format!("reading record {}", path.display()))?;
-    let record: Record = from_canonical_json_bytes(&bytes)
+    let mode = if options.lenient {
+        ParseMode::Lenient
+    } else {
+        ParseMode::Strict
+    };
+    let record = Record::from_json_bytes(&bytes, mode)
         .with_context(|| format!("parsing record {}", path.display()))?;
+    if !record.idle_spans_consistent() {
+        return Err(anyhow!(
+            "record {} lists idle spans that overlap its commands",
+            path.display()
+        ));
+    }
+    let rulepack_path = workspace_asset(&record.meta.rulepack);
+    if AssetHashes::with_rulepack(&rulepack_path)?.verify(&record.meta)? == AssetCheck::Unpinned {
+        warn!(
+            "record {} predates asset hashes; replaying without an asset drift check",
+            path.display()
+        );
+    }
+
+    if let Some(checkpoint_file) = &options.from_checkpoint {
+        let checkpoint = checkpoint::read_checkpoint(Path::new(checkpoint_file))?;
+        return replay_tail(&options, &record, &checkpoint);
+    }
 
-    let context = leg_context_from_record(&record.meta, &options)?;
-    let (commands, _) = simulate_ticks(&options, simulation_ticks(), context)?;
+    let mut commands: Vec<Command> = Vec::new();
+    let mut snapshots: Vec<(usize, TickSnapshot)> = Vec::new();
+    replay_leg(&options, &record, true, |snapshot, drained| {
+        let start = commands.len();
+        commands.extend(drained);
+        for (idx, actual) in commands.iter().enumerate().skip(start) {
+            if snapshots.len() < MAX_MISMATCH_CONTEXTS && record.commands.get(idx) != Some(actual) {
+                snapshots.extend(snapshot.map(|snapshot| (idx, snapshot.clone())));
+            }
+        }
+    })?;
 
     let continue_after = options.continue_after_mismatch;
     let expected_len = record.commands.len();
     let actual_len = commands.len();
     let shared_len = expected_len.min(actual_len);
 
-    for (idx, (expected, actual)) in record.commands.iter().zip(&commands).enumerate() {
-        if expected != actual {
-            let _ = m2::log_replay_mismatch(idx as u32, Some(expected), Some(actual));
-            if !continue_after {
-                return Err(anyhow!(
-                    "replay mismatch at command {idx}: expected {:?}, got {:?}",
-                    expected,
-                    actual
-                ));
-            }
-        }
+    let mut mismatches: Vec<usize> = (0..shared_len)
+        .filter(|&idx| record.commands[idx] != commands[idx])
+        .collect();
+    if expected_len != actual_len {
+        mismatches.push(shared_len);
     }
+    let Some(&first) = mismatches.first() else {
+        return Ok(());
+    };
 
-    if expected_len != actual_len {
+    let limit = if continue_after {
+        MAX_MISMATCH_CONTEXTS
+    } else {
+        1
+    };
+    let contexts: Vec<MismatchContext> = mismatches
+        .iter()
+        .take(limit)
+        .map(|&idx| {
+            let snapshot = snapshots
+                .iter()
+                .find(|(at, _)| *at == idx)
+                .map(|(_, snapshot)| snapshot.clone());
+            MismatchContext::collect(&record.meta, &record.commands, &commands, idx, snapshot)
+        })
+        .collect();
+    let context_path = mismatch_context_path(&path);
+    let context_bytes = if continue_after {
+        canonical_json_bytes(&contexts)?
+    } else {
+        canonical_json_bytes(&contexts[0])?
+    };
+    fs::write(&context_path, &context_bytes)
+        .with_context(|| format!("writing mismatch context {}", context_path.display()))?;
+
+    let logged = if continue_after {
+        &mismatches[..]
+    } else {
+        &mismatches[..1]
+    };
+    for &idx in logged {
         let _ = m2::log_replay_mismatch(
-            shared_len as u32,
-            record.commands.get(shared_len),
-            commands.get(shared_len),
+            idx as u32,
+            record.commands.get(idx),
+            commands.get(idx),
+            &context_path,
+        );
+    }
+
+    if continue_after {
+        return Ok(());
+    }
+    if first < shared_len {
+        Err(anyhow!(
+            "replay mismatch at command {first}: expected {:?}, got {:?} (context in {})",
+            record.commands[first],
+            commands[first],
+            context_path.display()
+        ))
+    } else {
+        Err(anyhow!(
+            "replay length mismatch: expected_len={expected_len}, actual_len={actual_len} (context in {})",
+            context_path.display()
+        ))
+    }
+}
+
+/// `--from-checkpoint`: replays the record's tail and reports mismatches,
+/// failing on the first unless `--continue-after-mismatch` is set.
+fn replay_tail(
+    options: &CliOptions,
+    record: &Record,
+    checkpoint: &checkpoint::Checkpoint,
+) -> Result<()> {
+    let continue_after = options.continue_after_mismatch;
+    let tail = checkpoint::replay_from_checkpoint(options, record, checkpoint, continue_after)?;
+    for &idx in &tail.mismatches {
+        warn!(
+            "replay from step {} mismatch at command {idx}: expected {:?}, got {:?}",
+            checkpoint.step,
+            record.commands.get(idx),
+            tail.commands.get(idx - tail.start)
         );
-        if !continue_after {
-            return Err(anyhow!(
-                "replay length mismatch: expected_len={expected_len}, actual_len={actual_len}"
-            ));
-        }
     }
+    match tail.mismatches.first() {
+        Some(&first) if !continue_after => Err(anyhow!(
+            "replay from step {} mismatch at command {first}",
+            checkpoint.step
+        )),
+        _ => Ok(()),
+    }
+}
 
+/// Re-simulates `record` on the leg and rulepack its meta describes, through
+/// the tick of its last command (see [`StopCondition::for_record`]), handing
+/// each tick's commands to `on_commands` (with its snapshot when `snapshots`
+/// is set).
+fn replay_leg(
+    options: &CliOptions,
+    record: &Record,
+    snapshots: bool,
+    on_commands: impl FnMut(Option<&TickSnapshot>, Vec<Command>),
+) -> Result<()> {
+    let context = leg_context_from_record(&record.meta, options)?;
+    let mut options = options.clone();
+    options.rulepack = Some(record.meta.rulepack.clone());
+    run_leg_ticks(
+        &options,
+        StopCondition::for_record(record),
+        context,
+        snapshots,
+        on_commands,
+    )?;
     Ok(())
 }
 
 fn simulate_ticks(
     options: &CliOptions,
-    ticks: u32,
+    stop: StopCondition,
     context: LegContext,
-) -> Result<(Vec<Command>, DirectorState)> {
+) -> Result<(Vec<Command>, DirectorState, AnnotationQueue)> {
+    let mut commands = Vec::new();
+    let (state, _, annotations) = run_leg_ticks(options, stop, context, false, |_, drained| {
+        commands.extend(drained);
+    })?;
+    Ok((commands, state, annotations))
+}
+
+/// Builds the leg's app and drives it until `stop`; see [`drive_ticks`].
+fn run_leg_ticks(
+    options: &CliOptions,
+    stop: StopCondition,
+    context: LegContext,
+    snapshots: bool,
+    on_commands: impl FnMut(Option<&TickSnapshot>, Vec<Command>),
+) -> Result<(DirectorState, LegContext, AnnotationQueue)> {
+    let meters = leg_meters(options)?;
+    let mut app = start_leg_app(options, context);
+    drive_ticks(&mut app, options, meters, stop, snapshots, on_commands)?;
+    finish_leg_app(&app, options)
+}
+
+fn leg_meters(options: &CliOptions) -> Result<Option<&'static MeterRegistry>> {
+    if meter_validation_enabled(options) {
+        Ok(Some(meter_registry()?))
+    } else {
+        Ok(None)
+    }
+}
+
+/// Builds the leg's app and runs its startup, leaving the wheel primed the
+/// way every leg begins.
+fn start_leg_app(options: &CliOptions, context: LegContext) -> App {
     let mut app = build_app(options, context);
     app.finish();
     app.update();
@@ -168,33 +464,72 @@ fn simulate_ticks(
                 .unwrap_or(true);
             if allow_slowmo {
                 queue.begin_tick(0);
-                world
+                let on = world
                     .resource_mut::<WheelState>()
                     .set_slowmo(&mut queue, true);
-                world
+                let off = world
                     .resource_mut::<WheelState>()
                     .set_slowmo(&mut queue, false);
+                world.write_message_batch(on.into_iter().chain(off));
             }
         });
-    let mut commands = Vec::new();
-    for _ in 0..ticks {
-        let current_tick = {
-            let world = app.world();
-            world.resource::<DirectorState>().leg_tick
-        };
-        {
-            let world = app.world_mut();
-            {
-                let mut queue = world.resource_mut::<CommandQueue>();
-                queue.begin_tick(current_tick);
-            }
-            world.run_schedule(FixedUpdate);
+    app
+}
+
+/// Runs one fixed step and returns the commands it emitted.
+fn step_leg_app(
+    app: &mut App,
+    options: &CliOptions,
+    meters: Option<&MeterRegistry>,
+) -> Result<Vec<Command>> {
+    let current_tick = app.world().resource::<DirectorState>().leg_tick;
+    {
+        let world = app.world_mut();
+        world
+            .resource_mut::<CommandQueue>()
+            .begin_tick(current_tick);
+        world.run_schedule(FixedUpdate);
+    }
+    let drained = app.world_mut().resource_mut::<CommandQueue>().drain();
+    if let Some(registry) = meters {
+        registry.validate_tick(&drained)?;
+    }
+    if options.autosave_dir.is_some() {
+        // The tick's commands are already drained, so checkpoint systems
+        // in `Last` cannot leak into the recorded stream.
+        app.world_mut().run_schedule(Last);
+    }
+    Ok(drained)
+}
+
+/// Writes the tick profile when `--profile-ticks` records one and returns
+/// the state the leg ended in.
+fn finish_leg_app(
+    app: &App,
+    options: &CliOptions,
+) -> Result<(DirectorState, LegContext, AnnotationQueue)> {
+    if let (Some(profiler), Some(io)) = (app.world().get_resource::<TickProfiler>(), &options.io) {
+        if options.profile_ticks && options.mode() == Mode::Record {
+            profiling::write_profile(Path::new(io), &profiler.profile())?;
         }
-        let mut queue = app.world_mut().resource_mut::<CommandQueue>();
-        commands.extend(queue.drain());
     }
     let state = app.world().resource::<DirectorState>().clone();
-    Ok((commands, state))
+    let context = *app.world().resource::<LegContext>();
+    let annotations = app
+        .world()
+        .get_resource::<AnnotationQueue>()
+        .cloned()
+        .unwrap_or_default();
+    Ok((state, context, annotations))
+}
+
+/// Meter keys are checked against the manifest in debug and deterministic
+/// builds and whenever a record is written or self-tested, so typos fail at
+/// record time.
+fn meter_validation_enabled(options: &CliOptions) -> bool {
+    cfg!(debug_assertions)
+        || cfg!(feature = "deterministic")
+        || matches!(options.mode(), Mode::Record | Mode::SelfTest)
 }
 
 fn build_app(options: &CliOptions, context: LegContext) -> App {
@@ -206,46 +541,130 @@ fn build_app(options: &CliOptions, context: LegContext) -> App {
         let mut fixed = app.world_mut().resource_mut::<BevyTime<Fixed>>();
         *fixed = BevyTime::<Fixed>::from_seconds(dt);
     }
-    app.init_resource::<CommandQueue>();
+    // Record and replay streams are authoritative; only play may shed meters.
+    if matches!(options.mode(), Mode::Play) {
+        app.init_resource::<CommandQueue>();
+    } else {
+        app.insert_resource(CommandQueue::authoritative());
+    }
     app.init_resource::<AppState>();
     app.insert_resource(context);
-    app.insert_resource(load_default_rulepack());
+    app.insert_resource(AnnotationQueue::new(options.annotate.clone()));
+    if let Some(registry) = RngRegistry::installed() {
+        app.insert_resource(registry);
+    }
+    let rulepacks = rulepack_schedule(options).expect("failed to load rulepack schedule");
+    app.insert_resource(rulepacks.for_day(EconomyDay(context.day)).clone());
+    app.insert_resource(rulepacks);
+    app.insert_resource(market_events().expect("failed to load market events"));
+    app.add_systems(First, swap_rulepack_on_day_change);
     app.add_plugins(TradingPlugin);
     if !options.headless {
+        app.insert_resource(Strings::load(&options.lang).expect("failed to load UI strings"));
         if matches!(options.mode(), Mode::Play) {
             app.add_plugins(bevy::asset::AssetPlugin::default());
             app.add_plugins(bevy::text::TextPlugin);
             app.add_plugins(bevy::ui::UiPlugin);
-            app.add_plugins((HubTradePlugin, RoutePlannerPlugin));
+            app.add_plugins((
+                HubTradePlugin,
+                RoutePlannerPlugin,
+                PostLegReportPlugin,
+                SaveSlotPlugin::default(),
+            ));
+            #[cfg(feature = "audio")]
+            app.add_plugins(systems::audio_cues::AudioCuePlugin);
+            if options.debug_overlay || cfg!(feature = "dev") {
+                app.add_plugins(DirectorDebugOverlayPlugin::default());
+            }
+            if cfg!(feature = "dev") {
+                app.add_plugins(CatalogWatchPlugin {
+                    path: systems::trading::default_commodities_path(),
+                });
+                app.add_systems(Update, annotate_on_key);
+            }
         } else {
             app.add_plugins(HubTradePlugin);
         }
     }
     app.add_plugins(DirectorPlugin);
+    #[cfg(feature = "terrain")]
+    app.add_plugins(plugins::TerrainPlugin::default());
+    if let Some(dir) = &options.autosave_dir {
+        app.add_plugins(AutosavePlugin::new(dir));
+    }
+    if profiling_enabled(options) {
+        app.add_plugins(TickProfilerPlugin);
+    }
     app
 }
 
-fn load_default_rulepack() -> Rulepack {
-    let workspace_path = std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+/// Set timings are wall-clock and stay out of the command stream. The
+/// `profiling` feature keeps the profiler running for the overlay; only
+/// `--profile-ticks` writes the profile file.
+fn profiling_enabled(options: &CliOptions) -> bool {
+    options.profile_ticks || cfg!(feature = "profiling")
+}
+
+/// Workspace-relative directory rulepacks are recorded under.
+const RULEPACK_DIR: &str = "assets/rulepacks";
+
+/// Resolves a workspace-relative asset path: under the working directory
+/// when present, otherwise the workspace copy.
+pub(crate) fn workspace_asset(relative: &str) -> PathBuf {
+    let local = Path::new(relative);
+    if local.exists() {
+        return local.to_path_buf();
+    }
+    Path::new(env!("CARGO_MANIFEST_DIR"))
         .join("..")
         .join("..")
-        .join("assets/rulepacks/day_001.toml");
-    let search_paths = [
-        std::path::Path::new("assets/rulepacks/day_001.toml"),
-        workspace_path.as_path(),
-    ];
-    for path in search_paths {
-        if path.exists() {
-            let as_str = path
-                .to_str()
-                .expect("default rulepack path should be valid UTF-8");
-            return load_rulepack(as_str).expect("failed to load default rulepack asset");
+        .join(relative)
+}
+
+/// The rulepack legs run against when no schedule entry says otherwise.
+pub(crate) fn default_rulepack_path() -> PathBuf {
+    workspace_asset("assets/rulepacks/day_001.toml")
+}
+
+pub(crate) fn rulepack_schedule_path() -> PathBuf {
+    workspace_asset("assets/rulepacks/schedule.toml")
+}
+
+/// The schedule in effect: the day-keyed schedule, or a single rulepack
+/// when `--rulepack` pins one.
+fn rulepack_schedule(options: &CliOptions) -> Result<RulepackSchedule> {
+    match &options.rulepack {
+        Some(file) => {
+            let path = workspace_asset(file);
+            let rulepack = load_rulepack(&path.to_string_lossy())
+                .with_context(|| format!("loading rulepack {}", path.display()))?;
+            Ok(RulepackSchedule::fixed(file.clone(), path, rulepack))
+        }
+        None => {
+            let path = rulepack_schedule_path();
+            RulepackSchedule::load(&path)
+                .with_context(|| format!("loading rulepack schedule {}", path.display()))
         }
     }
-    panic!(
-        "missing default rulepack asset at assets/rulepacks/day_001.toml (searched workspace path {})",
-        workspace_path.display()
-    );
+}
+
+/// Scripted market events for the day rollover.
+fn market_events() -> Result<MarketEvents> {
+    let path = workspace_asset("assets/econ/events.toml");
+    let events = load_market_events(&path.to_string_lossy())
+        .with_context(|| format!("loading market events {}", path.display()))?;
+    Ok(MarketEvents(events))
+}
+
+/// The rulepack a leg on `day` runs against, with `file` in the
+/// workspace-relative form stored in [`RecordMeta::rulepack`].
+fn leg_rulepack(options: &CliOptions, day: u32) -> Result<ScheduledRulepack> {
+    let schedule = rulepack_schedule(options)?;
+    let mut entry = schedule.entry_for(EconomyDay(day)).clone();
+    if options.rulepack.is_none() {
+        entry.file = format!("{RULEPACK_DIR}/{}", entry.file);
+    }
+    Ok(entry)
 }
 
 /// Adds the core plugin groups for the simulation, taking the headless flag into account.
@@ -302,42 +721,47 @@ fn configure_task_pool(builder: bevy::app::PluginGroupBuilder) -> bevy::app::Plu
     builder
 }
 
-fn simulation_ticks() -> u32 {
-    120
-}
-
-fn leg_context_from_options(options: &CliOptions) -> LegContext {
-    LegContext {
-        world_seed: options.world_seed(),
-        link_id: RouteId(options.link_id()),
-        day: options.day(),
-        weather: options.weather(),
-        pp: Pp(options.pp()),
-        density_per_10k: options.density_per_10k(),
-        cadence_per_min: options.cadence_per_min(),
-        mission_minutes: options.mission_minutes(),
-        player_rating: options.player_rating(),
-        multiplayer: false,
-        prior_danger_score: None,
-        basis_overlay_bp_total: 0,
-    }
+fn leg_context_from_options(options: &CliOptions) -> Result<LegContext> {
+    Ok(leg_context_builder(options).build()?)
 }
 
 fn leg_context_from_record(meta: &RecordMeta, options: &CliOptions) -> Result<LegContext> {
-    let mut context = leg_context_from_options(options);
-    context.world_seed = parse_seed_string(&meta.world_seed)?;
-    context.link_id = RouteId(parse_u16_string(&meta.link_id)?);
-    context.weather = parse_weather_string(&meta.weather)?;
-    context.day = meta.day;
-    context.pp = Pp(meta.pp);
-    context.density_per_10k = meta.density_per_10k;
-    context.cadence_per_min = meta.cadence_per_min;
-    context.mission_minutes = meta.mission_minutes;
-    context.player_rating = meta.player_rating;
-    context.prior_danger_score = meta.prior_danger_score;
+    let context = leg_context_builder(options)
+        .world_seed(parse_seed_string(&meta.world_seed)?)
+        .link_id(RouteId(parse_u16_string(&meta.link_id)?))
+        .weather(parse_weather_string(&meta.weather)?)
+        .day(meta.day)
+        .pp(Pp(meta.pp))
+        .density_per_10k(meta.density_per_10k)
+        .cadence_per_min(
+            options
+                .cadence_per_min_override()
+                .unwrap_or(meta.cadence_per_min),
+        )
+        .mission_minutes(
+            options
+                .mission_minutes_override()
+                .unwrap_or(meta.mission_minutes),
+        )
+        .player_rating(meta.player_rating)
+        .prior_danger_score(meta.prior_danger_score)
+        .build()?;
     Ok(context)
 }
 
+fn leg_context_builder(options: &CliOptions) -> LegContextBuilder {
+    LegContext::builder()
+        .world_seed(options.world_seed())
+        .link_id(RouteId(options.link_id()))
+        .day(options.day())
+        .weather(options.weather())
+        .pp(Pp(options.pp()))
+        .density_per_10k(options.density_per_10k())
+        .cadence_per_min(options.cadence_per_min())
+        .mission_minutes(options.mission_minutes())
+        .player_rating(options.player_rating())
+}
+
 fn init_logging() {
     static LOG_INIT: Once = Once::new();
 
@@ -354,19 +778,18 @@ fn init_logging() {
 #[cfg(feature = "deterministic")]
 fn log_determinism_banner() {
     let features = determinism_feature_flags();
-    match director_config_hash() {
-        Ok(hash) => info!(
-            "=== Deterministic build active: features=[{}] director_cfg_hash={} ===",
-            features, hash
-        ),
-        Err(err) => {
-            info!(
-                "=== Deterministic build active: features=[{}] director_cfg_hash=unavailable ===",
-                features
-            );
-            warn!("failed to hash director config: {err:?}");
-        }
-    }
+    let director = director_config_hash().unwrap_or_else(|err| {
+        warn!("failed to hash director config: {err:?}");
+        "unavailable".to_string()
+    });
+    let archetypes = archetype_catalog_hash().unwrap_or_else(|err| {
+        warn!("failed to hash archetype catalog: {err:?}");
+        "unavailable".to_string()
+    });
+    info!(
+        "=== Deterministic build active: features=[{}] director_cfg_hash={} archetypes_hash={} ===",
+        features, director, archetypes
+    );
 }
 
 #[cfg(not(feature = "deterministic"))]
@@ -386,10 +809,12 @@ fn determinism_feature_flags() -> String {
 
 #[cfg(feature = "deterministic")]
 fn director_config_hash() -> Result<String> {
-    let path = director_cfg_path();
-    let bytes =
-        fs::read(&path).with_context(|| format!("reading director config {}", path.display()))?;
-    Ok(blake3::hash(&bytes).to_hex().to_string())
+    provenance::file_hash(&director_cfg_path())
+}
+
+#[cfg(feature = "deterministic")]
+fn archetype_catalog_hash() -> Result<String> {
+    provenance::file_hash(&systems::director::archetypes::archetypes_path())
 }
 
 fn parse_seed_string(value: &str) -> Result<u64> {
@@ -433,9 +858,13 @@ fn parse_weather_string(value: &str) -> Result<Weather> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use clap::Parser;
+    use repro::CommandKind;
+    use systems::director::LegBoard;
+    use world::board::Board;
 
     fn default_context(options: &CliOptions) -> LegContext {
-        leg_context_from_options(options)
+        leg_context_from_options(options).expect("context")
     }
 
     #[cfg(feature = "deterministic")]
@@ -485,6 +914,74 @@ mod tests {
         assert!(output.contains("Deterministic build active"));
         assert!(output.contains("features=[deterministic"));
         assert!(output.contains("director_cfg_hash="));
+        assert!(output.contains("archetypes_hash="));
+    }
+
+    fn record_meta(mission_minutes: u32, cadence_per_min: u32) -> RecordMeta {
+        RecordMeta {
+            schema: 1,
+            world_seed: "0xD7E7202400010001".into(),
+            link_id: "11".into(),
+            rulepack: "assets/rulepacks/day_001.toml".into(),
+            weather: "Clear".into(),
+            rng_salt: "0x0".into(),
+            day: 3,
+            pp: 120,
+            density_per_10k: 5,
+            cadence_per_min,
+            mission_minutes,
+            player_rating: 50,
+            prior_danger_score: None,
+            ..RecordMeta::default()
+        }
+    }
+
+    #[test]
+    fn pacing_flags_override_options_and_record() {
+        let options = CliOptions::try_parse_from([
+            "game",
+            "--mode",
+            "replay",
+            "--mission-minutes",
+            "2",
+            "--cadence-per-min",
+            "9",
+        ])
+        .expect("cli options");
+        let context = leg_context_from_options(&options).expect("context");
+        assert_eq!(context.mission_minutes, 2);
+        assert_eq!(context.cadence_per_min, 9);
+
+        let context = leg_context_from_record(&record_meta(12, 4), &options).expect("context");
+        assert_eq!(context.mission_minutes, 2);
+        assert_eq!(context.cadence_per_min, 9);
+    }
+
+    #[test]
+    fn record_pacing_is_kept_without_flags() {
+        let options =
+            CliOptions::try_parse_from(["game", "--mode", "replay"]).expect("cli options");
+        assert_eq!(options.mission_minutes_override(), None);
+
+        let context = leg_context_from_record(&record_meta(12, 4), &options).expect("context");
+        assert_eq!(context.mission_minutes, 12);
+        assert_eq!(context.cadence_per_min, 4);
+    }
+
+    #[test]
+    fn contexts_are_validated() {
+        let options =
+            CliOptions::try_parse_from(["game", "--mission-minutes", "0"]).expect("cli options");
+        let err = leg_context_from_options(&options).expect_err("zero minutes");
+        assert!(err.to_string().contains("mission_minutes"));
+
+        let options =
+            CliOptions::try_parse_from(["game", "--mode", "replay"]).expect("cli options");
+        let mut meta = record_meta(12, 4);
+        meta.player_rating = 230;
+        let context = leg_context_from_record(&meta, &options).expect("context");
+        assert_eq!(context.player_rating, 100);
+        assert!(leg_context_from_record(&record_meta(0, 4), &options).is_err());
     }
 
     #[test]
@@ -499,6 +996,46 @@ mod tests {
         assert!(!app.is_plugin_added::<WindowingPlaceholderPlugin>());
     }
 
+    fn leg_spawn_positions(options: &CliOptions, context: LegContext) -> (Board, Vec<[i32; 3]>) {
+        let mut app = start_leg_app(options, context);
+        let board = app.world().resource::<LegBoard>().0.clone();
+        let mut positions = Vec::new();
+        for _ in 0..120 {
+            let drained = step_leg_app(&mut app, options, None).expect("tick");
+            positions.extend(
+                drained
+                    .into_iter()
+                    .filter_map(|command| match command.kind {
+                        CommandKind::Spawn(spawn) => Some([spawn.x_mm, spawn.y_mm, spawn.z_mm]),
+                        _ => None,
+                    }),
+            );
+        }
+        (board, positions)
+    }
+
+    #[test]
+    fn leg_spawns_land_on_the_legs_generated_board() {
+        let mut options = CliOptions::for_mode(Mode::Play);
+        options.headless = true;
+        let context = default_context(&options);
+
+        let (board, positions) = leg_spawn_positions(&options, context);
+        assert_eq!(board, LegBoard::for_leg(&context).expect("valid board").0);
+        assert!(
+            positions.len() > board.spawns.enemy.len(),
+            "expected spawns to cycle, got {}",
+            positions.len()
+        );
+        for (n, position) in positions.iter().enumerate() {
+            let cell = board.spawns.enemy[n % board.spawns.enemy.len()];
+            assert!(board.is_walkable(cell), "spawn {n} on {cell:?}");
+            assert_eq!(Some(*position), board.enemy_spawn_mm(n as u64), "spawn {n}");
+        }
+
+        assert_eq!(leg_spawn_positions(&options, context), (board, positions));
+    }
+
     #[test]
     fn windowed_mode_registers_window_plugin() {
         let options = CliOptions::for_mode(Mode::Play);
@@ -70,6 +70,7 @@ pub fn log_post_leg_summary(
     applied_basis_overlay: i16,
     di_bp_after: i32,
     basis_bp_after: i32,
+    interest_accrued_cents: i64,
 ) -> anyhow::Result<()> {
     if !enabled() {
         return Ok(());
@@ -82,6 +83,7 @@ pub fn log_post_leg_summary(
         applied_basis_overlay: i16,
         di_bp_after: i32,
         basis_bp_after: i32,
+        interest_accrued_cents: i64,
     }
 
     let value = SummaryLog {
@@ -90,6 +92,7 @@ pub fn log_post_leg_summary(
         applied_basis_overlay,
         di_bp_after,
         basis_bp_after,
+        interest_accrued_cents,
     };
 
     append_jsonl("post_leg_summary.jsonl", &value)
@@ -155,6 +158,7 @@ pub fn log_replay_mismatch(
     tick: u32,
     expected: Option<&Command>,
     actual: Option<&Command>,
+    context_path: &Path,
 ) -> anyhow::Result<()> {
     if !enabled() {
         return Ok(());
@@ -165,12 +169,14 @@ pub fn log_replay_mismatch(
         tick: u32,
         expected: Option<&'a Command>,
         actual: Option<&'a Command>,
+        context_path: String,
     }
 
     let value = ReplayMismatch {
         tick,
         expected,
         actual,
+        context_path: context_path.display().to_string(),
     };
 
     append_jsonl("replay_mismatch.jsonl", &value)
@@ -1,3 +1,5 @@
+pub mod terrain;
 pub mod visuals;
 
+pub use terrain::TerrainPlugin;
 pub use visuals::VisualsPlugin;
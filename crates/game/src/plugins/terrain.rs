@@ -0,0 +1,220 @@
+use bevy::asset::RenderAssetUsages;
+use bevy::mesh::{Indices, Mesh, PrimitiveTopology};
+use bevy::pbr::{MeshMaterial3d, StandardMaterial};
+use bevy::prelude::*;
+use worldgen::{ChunkKey, GenParams, MeshData, WorldGen};
+
+use crate::systems::director::LegContext;
+
+const CHUNK_SIZE: u32 = 33;
+const CHUNK_SCALE: f32 = 1.0;
+const TERRAIN_HEIGHT: f32 = 4.0;
+
+/// Spawns an `N×N` grid of WorldGen chunks around the origin with static
+/// colliders. Render meshes are only attached when mesh assets exist, so
+/// headless runs still get the colliders.
+pub struct TerrainPlugin {
+    pub chunks_per_side: u32,
+}
+
+impl Default for TerrainPlugin {
+    fn default() -> Self {
+        Self { chunks_per_side: 3 }
+    }
+}
+
+impl Plugin for TerrainPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(TerrainLayout {
+            chunks_per_side: self.chunks_per_side,
+        })
+        .add_systems(Startup, spawn_terrain);
+    }
+}
+
+#[derive(Resource, Clone, Copy)]
+struct TerrainLayout {
+    chunks_per_side: u32,
+}
+
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TerrainChunk {
+    pub x: i32,
+    pub z: i32,
+}
+
+/// World-space bounds of a chunk's collider geometry.
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+pub struct TerrainAabb {
+    pub min: Vec3,
+    pub max: Vec3,
+}
+
+pub fn terrain_params(world_seed: u64) -> GenParams {
+    GenParams {
+        seed: world_seed,
+        chunk_size: CHUNK_SIZE,
+        scale: CHUNK_SCALE,
+        height: TERRAIN_HEIGHT,
+    }
+}
+
+/// Chunk keys for an `N×N` grid, row by row, centred on the origin chunk.
+pub fn terrain_chunk_keys(chunks_per_side: u32) -> Vec<ChunkKey> {
+    let n = chunks_per_side as i32;
+    let offset = n / 2;
+    (0..n)
+        .flat_map(|z| {
+            (0..n).map(move |x| ChunkKey {
+                x: x - offset,
+                z: z - offset,
+            })
+        })
+        .collect()
+}
+
+pub fn mesh_from_data(data: &MeshData) -> Mesh {
+    Mesh::new(
+        PrimitiveTopology::TriangleList,
+        RenderAssetUsages::default(),
+    )
+    .with_inserted_attribute(Mesh::ATTRIBUTE_POSITION, data.positions.clone())
+    .with_inserted_attribute(Mesh::ATTRIBUTE_NORMAL, data.normals.clone())
+    .with_inserted_attribute(Mesh::ATTRIBUTE_UV_0, data.uvs.clone())
+    .with_inserted_indices(Indices::U32(data.indices.clone()))
+}
+
+fn mesh_aabb(data: &MeshData) -> TerrainAabb {
+    data.positions.iter().map(|p| Vec3::from_array(*p)).fold(
+        TerrainAabb {
+            min: Vec3::INFINITY,
+            max: Vec3::NEG_INFINITY,
+        },
+        |aabb, p| TerrainAabb {
+            min: aabb.min.min(p),
+            max: aabb.max.max(p),
+        },
+    )
+}
+
+fn spawn_terrain(
+    mut commands: Commands,
+    layout: Res<TerrainLayout>,
+    context: Option<Res<LegContext>>,
+    meshes: Option<ResMut<Assets<Mesh>>>,
+    materials: Option<ResMut<Assets<StandardMaterial>>>,
+) {
+    let world_seed = context.map(|ctx| ctx.world_seed).unwrap_or_default();
+    let generator = WorldGen::new(terrain_params(world_seed));
+    let mut render = meshes.zip(materials).map(|(mut meshes, mut materials)| {
+        let material = materials.add(StandardMaterial {
+            base_color: Color::srgb(0.32, 0.38, 0.28),
+            perceptual_roughness: 0.9,
+            ..default()
+        });
+        move |data: &MeshData| {
+            (
+                Mesh3d(meshes.add(mesh_from_data(data))),
+                MeshMaterial3d(material.clone()),
+            )
+        }
+    });
+
+    let keys = terrain_chunk_keys(layout.chunks_per_side);
+    for (key, data) in generator.generate_chunks(&keys) {
+        let mut entity = commands.spawn((
+            TerrainChunk { x: key.x, z: key.z },
+            mesh_aabb(&data),
+            Transform::IDENTITY,
+            Name::new(format!("TerrainChunk_{}_{}", key.x, key.z)),
+        ));
+        #[cfg(feature = "avian_physics")]
+        {
+            use avian3d::prelude::{Collider, RigidBody};
+            let vertices = data
+                .positions
+                .iter()
+                .map(|p| Vec3::from_array(*p))
+                .collect();
+            let triangles = data
+                .indices
+                .chunks_exact(3)
+                .map(|tri| [tri[0], tri[1], tri[2]])
+                .collect();
+            entity.insert((RigidBody::Static, Collider::trimesh(vertices, triangles)));
+        }
+        if let Some(render) = render.as_mut() {
+            entity.insert(render(&data));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::MinimalPlugins;
+
+    fn terrain_app(world_seed: u64) -> App {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins);
+        app.insert_resource(LegContext {
+            world_seed,
+            ..default()
+        });
+        app.add_plugins(TerrainPlugin::default());
+        app.update();
+        app
+    }
+
+    fn chunk_bounds(world_seed: u64) -> Vec<(TerrainChunk, TerrainAabb)> {
+        let mut app = terrain_app(world_seed);
+        let world = app.world_mut();
+        let mut query = world.query::<(&TerrainChunk, &TerrainAabb)>();
+        let mut chunks: Vec<_> = query.iter(world).map(|(c, a)| (*c, *a)).collect();
+        chunks.sort_by_key(|(chunk, _)| (chunk.z, chunk.x));
+        chunks
+    }
+
+    #[test]
+    fn headless_terrain_bounds_are_stable_for_seed() {
+        let first = chunk_bounds(0xD7E7_2024_0001_0001);
+        let second = chunk_bounds(0xD7E7_2024_0001_0001);
+        assert_eq!(first.len(), 9);
+        assert_eq!(first, second);
+        for (_, aabb) in &first {
+            assert!(aabb.min.cmple(aabb.max).all());
+        }
+    }
+
+    #[cfg(feature = "avian_physics")]
+    fn collider_bounds(world_seed: u64) -> Vec<(TerrainChunk, Vec3, Vec3)> {
+        use avian3d::prelude::{Collider, SimpleCollider};
+
+        let mut app = terrain_app(world_seed);
+        let world = app.world_mut();
+        let mut query = world.query::<(&TerrainChunk, &Collider)>();
+        let mut chunks: Vec<_> = query
+            .iter(world)
+            .map(|(chunk, collider)| {
+                let aabb = collider.aabb(Vec3::ZERO, Quat::IDENTITY);
+                (*chunk, aabb.min, aabb.max)
+            })
+            .collect();
+        chunks.sort_by_key(|(chunk, _, _)| (chunk.z, chunk.x));
+        chunks
+    }
+
+    #[cfg(feature = "avian_physics")]
+    #[test]
+    fn collider_bounds_are_stable_and_match_terrain_aabb() {
+        let seed = 0xD7E7_2024_0001_0001;
+        let first = collider_bounds(seed);
+        assert_eq!(first.len(), 9);
+        assert_eq!(first, collider_bounds(seed));
+        for ((chunk, min, max), (bounds_chunk, aabb)) in first.iter().zip(chunk_bounds(seed)) {
+            assert_eq!(*chunk, bounds_chunk);
+            assert!(min.abs_diff_eq(aabb.min, 1e-4), "{chunk:?} min");
+            assert!(max.abs_diff_eq(aabb.max, 1e-4), "{chunk:?} max");
+        }
+    }
+}
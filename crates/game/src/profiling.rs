@@ -0,0 +1,170 @@
+//! Wall-clock timing of the director's `FixedUpdate` sets.
+//!
+//! Marker systems sit on the boundaries between the chained sets and charge
+//! the time since the previous marker to the set that just ran. Nothing here
+//! touches the [`CommandQueue`](crate::systems::command_queue::CommandQueue),
+//! so profiled and unprofiled runs record the same stream.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use bevy::prelude::*;
+use serde::Serialize;
+
+use crate::scheduling::sets;
+use crate::wallclock::Stopwatch;
+
+/// Profiled sets in schedule order.
+pub const PROFILED_SETS: [&str; 6] = [
+    "input",
+    "director",
+    "missions",
+    "spawns",
+    "physics_step",
+    "cleanup",
+];
+
+#[derive(Debug, Clone, Copy, Default)]
+struct SetTiming {
+    total: Duration,
+    max: Duration,
+}
+
+/// Per-set totals accumulated over the ticks run so far.
+#[derive(Resource, Debug, Clone, Default)]
+pub struct TickProfiler {
+    sets: [SetTiming; PROFILED_SETS.len()],
+    ticks: u32,
+    wall: Duration,
+    /// Started when the current tick's first set begins.
+    tick_clock: Option<Stopwatch>,
+    /// Time into the current tick at which the previous set ended.
+    mark: Duration,
+}
+
+impl TickProfiler {
+    fn begin_tick(&mut self) {
+        self.tick_clock = Some(Stopwatch::start());
+        self.mark = Duration::ZERO;
+    }
+
+    fn end_set(&mut self, index: usize) {
+        let Some(clock) = self.tick_clock else {
+            return;
+        };
+        let now = clock.elapsed();
+        let elapsed = now.saturating_sub(self.mark);
+        self.mark = now;
+        let timing = &mut self.sets[index];
+        timing.total += elapsed;
+        timing.max = timing.max.max(elapsed);
+        if index + 1 == PROFILED_SETS.len() {
+            self.wall += now;
+            self.tick_clock = None;
+            self.ticks += 1;
+        }
+    }
+
+    pub fn ticks(&self) -> u32 {
+        self.ticks
+    }
+
+    pub fn profile(&self) -> TickProfile {
+        let ticks = u128::from(self.ticks.max(1));
+        TickProfile {
+            ticks: self.ticks,
+            wall_us: micros(self.wall),
+            sets: PROFILED_SETS
+                .iter()
+                .zip(&self.sets)
+                .map(|(name, timing)| SetProfile {
+                    set: (*name).to_string(),
+                    total_us: micros(timing.total),
+                    max_us: micros(timing.max),
+                    mean_us: (timing.total.as_micros() / ticks) as u64,
+                })
+                .collect(),
+        }
+    }
+}
+
+fn micros(duration: Duration) -> u64 {
+    u64::try_from(duration.as_micros()).unwrap_or(u64::MAX)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SetProfile {
+    pub set: String,
+    pub total_us: u64,
+    pub max_us: u64,
+    pub mean_us: u64,
+}
+
+/// Serialized form of a [`TickProfiler`], written next to the record.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TickProfile {
+    pub ticks: u32,
+    /// Time from the first set's start to the last set's end, summed over
+    /// ticks.
+    pub wall_us: u64,
+    pub sets: Vec<SetProfile>,
+}
+
+impl TickProfile {
+    /// The set with the largest total, if any time was measured.
+    pub fn slowest(&self) -> Option<&SetProfile> {
+        self.sets
+            .iter()
+            .filter(|set| set.total_us > 0)
+            .max_by_key(|set| set.total_us)
+    }
+}
+
+pub fn profile_path(record: &Path) -> PathBuf {
+    record.with_extension("profile.json")
+}
+
+pub fn write_profile(record: &Path, profile: &TickProfile) -> Result<PathBuf> {
+    let path = profile_path(record);
+    let json = serde_json::to_vec_pretty(profile)?;
+    fs::write(&path, json).with_context(|| format!("writing tick profile {}", path.display()))?;
+    Ok(path)
+}
+
+fn end_set(index: usize) -> impl FnMut(ResMut<TickProfiler>) {
+    move |mut profiler: ResMut<TickProfiler>| profiler.end_set(index)
+}
+
+/// Installs a [`TickProfiler`] and its boundary markers.
+#[derive(Default)]
+pub struct TickProfilerPlugin;
+
+impl Plugin for TickProfilerPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<TickProfiler>().add_systems(
+            FixedUpdate,
+            (
+                (|mut profiler: ResMut<TickProfiler>| profiler.begin_tick())
+                    .before(sets::DETTEROT_Input),
+                end_set(0)
+                    .after(sets::DETTEROT_Input)
+                    .before(sets::DETTEROT_Director),
+                end_set(1)
+                    .after(sets::DETTEROT_Director)
+                    .before(sets::DETTEROT_Missions),
+                end_set(2)
+                    .after(sets::DETTEROT_Missions)
+                    .before(sets::DETTEROT_Spawns),
+                end_set(3)
+                    .after(sets::DETTEROT_Spawns)
+                    .before(sets::DETTEROT_PhysicsStep),
+                end_set(4)
+                    .after(sets::DETTEROT_PhysicsStep)
+                    .before(sets::DETTEROT_Cleanup),
+                end_set(5).after(sets::DETTEROT_Cleanup),
+            ),
+        );
+    }
+}
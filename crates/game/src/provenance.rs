@@ -0,0 +1,95 @@
+//! Content hashes of the assets a leg is simulated against, stored in
+//! [`RecordMeta`] so a replay can refuse to run on edited inputs.
+
+use std::fs;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use repro::RecordMeta;
+use thiserror::Error;
+
+use crate::systems::director::director_cfg_path;
+use crate::systems::trading::default_commodities_path;
+
+/// BLAKE3 of a file's raw bytes, hex encoded.
+pub fn file_hash(path: &Path) -> Result<String> {
+    let bytes = fs::read(path).with_context(|| format!("reading {}", path.display()))?;
+    Ok(blake3::hash(&bytes).to_hex().to_string())
+}
+
+/// Hashes of the default rulepack, director config and commodity catalog.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AssetHashes {
+    pub rulepack: String,
+    pub director_cfg: String,
+    pub commodities: String,
+}
+
+impl AssetHashes {
+    /// Hashes the files the simulation loads right now, with the default
+    /// rulepack.
+    pub fn current() -> Result<Self> {
+        Self::with_rulepack(&crate::default_rulepack_path())
+    }
+
+    /// Like [`AssetHashes::current`], for a leg that runs on `rulepack`.
+    pub fn with_rulepack(rulepack: &Path) -> Result<Self> {
+        Ok(Self {
+            rulepack: file_hash(rulepack)?,
+            director_cfg: file_hash(&director_cfg_path())?,
+            commodities: file_hash(&default_commodities_path())?,
+        })
+    }
+
+    pub fn stamp(&self, meta: &mut RecordMeta) {
+        meta.rulepack_hash = Some(self.rulepack.clone());
+        meta.director_cfg_hash = Some(self.director_cfg.clone());
+        meta.commodities_hash = Some(self.commodities.clone());
+    }
+
+    /// Compares against the hashes a record was made with. Records from
+    /// before asset hashes carry none and report [`AssetCheck::Unpinned`].
+    pub fn verify(&self, meta: &RecordMeta) -> Result<AssetCheck, AssetDrift> {
+        let pinned = [
+            ("rulepack", &meta.rulepack_hash, &self.rulepack),
+            (
+                "director config",
+                &meta.director_cfg_hash,
+                &self.director_cfg,
+            ),
+            ("commodities", &meta.commodities_hash, &self.commodities),
+        ];
+        if pinned.iter().all(|(_, recorded, _)| recorded.is_none()) {
+            return Ok(AssetCheck::Unpinned);
+        }
+        for (asset, recorded, current) in pinned {
+            if let Some(recorded) = recorded {
+                if recorded != current {
+                    return Err(AssetDrift {
+                        asset,
+                        recorded: recorded.clone(),
+                        current: current.clone(),
+                    });
+                }
+            }
+        }
+        Ok(AssetCheck::Verified)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AssetCheck {
+    Verified,
+    Unpinned,
+}
+
+/// An asset's content no longer matches the hash stored in the record.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error(
+    "asset drift: {asset} changed since the record was made (recorded {recorded}, now {current})"
+)]
+pub struct AssetDrift {
+    pub asset: &'static str,
+    pub recorded: String,
+    pub current: String,
+}
@@ -0,0 +1,722 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use bevy::prelude::{App, World};
+use repro::strict::ParseMode;
+use repro::{
+    canonical_json_bytes, hash_record, idle_spans, Command, CommandKind, IdleSpan, Record,
+    RecordMeta, RECORD_SCHEMA,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::app_state::AppState;
+use crate::cli::{CliOptions, Mode};
+use crate::systems::command_queue::CommandQueue;
+use crate::systems::director::{
+    DirectorState, EconIntent, LegContext, LegStatus, SpawnMemory, WheelState,
+};
+use crate::systems::economy::verify::{neutral_state, step_all_hubs};
+use crate::systems::economy::{CommodityId, EconomyDay, MoneyCents};
+use crate::systems::telemetry::{aggregate_meters, meter_registry, MeterRegistry};
+use crate::systems::trading::catalog::CatalogHandle;
+use crate::systems::trading::engine::{TradeKind, TradeTx};
+use crate::systems::trading::inventory::Cargo;
+use crate::systems::trading::ledger::{LedgerJournal, Wallet, WalletAccess};
+use crate::systems::trading::types::TradingConfig;
+use crate::systems::trading::{load_default_commodities, load_default_trading_config};
+use crate::ui::hub_trade::HubTradeActions;
+use crate::world::index::{load_world_graph_data, world_graph_path};
+use crate::{
+    finish_leg_app, leg_context_from_options, leg_meters, replay_leg, rulepack_schedule,
+    run_leg_ticks, start_leg_app, step_leg_app, workspace_asset, RULEPACK_DIR,
+};
+
+/// Totals gathered over a leg's command stream.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct LegSummary {
+    /// Sum of every `spawn_count` meter emitted during the leg.
+    pub spawn_count_sum: i64,
+    /// Number of spawn commands emitted.
+    pub spawns: u32,
+    /// Sum of mission `pp_delta` meters.
+    pub pp_delta: i32,
+    pub final_danger: i32,
+    /// Basis overlay total carried in the leg context once the run ends.
+    pub basis_total: i32,
+}
+
+impl LegSummary {
+    /// Folds in a batch of commands, usually one drained tick.
+    pub fn observe(&mut self, commands: &[Command]) {
+        let spawns = commands
+            .iter()
+            .filter(|command| matches!(command.kind, CommandKind::Spawn(_)))
+            .count();
+        self.spawns = self.spawns.saturating_add(spawns as u32);
+        let meters = aggregate_meters(commands);
+        if let Some(&count) = meters.get("spawn_count") {
+            self.spawn_count_sum += count;
+        }
+        if let Some(&delta) = meters.get("pp_delta") {
+            let delta = delta.clamp(i64::from(i32::MIN), i64::from(i32::MAX)) as i32;
+            self.pp_delta = self.pp_delta.saturating_add(delta);
+        }
+    }
+}
+
+/// Fixed steps a leg runs when `--ticks` is not given.
+pub const DEFAULT_LEG_TICKS: u32 = 120;
+/// Safety cap on the fixed steps of a leg run to completion, or of a replay,
+/// when `--ticks` is not given: two hours at 30 Hz.
+pub const DEFAULT_MAX_LEG_TICKS: u32 = 216_000;
+
+/// When a headless leg stops.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StopCondition {
+    /// Exactly this many fixed steps, whether or not the leg completes.
+    Ticks(u32),
+    /// Once the leg completes, or after `max_ticks` steps.
+    Completion { max_ticks: u32 },
+    /// After the step stamped `tick`, once the leg completes, or after
+    /// `max_ticks` steps, whichever comes first. Replays use it to cover a
+    /// record's commands without knowing how many steps made them.
+    ThroughTick { tick: u32, max_ticks: u32 },
+}
+
+impl StopCondition {
+    /// Covers every command in `record`: through its last command's tick,
+    /// or no steps at all for a record without commands.
+    pub fn for_record(record: &Record) -> Self {
+        match record.commands.last() {
+            Some(last) => Self::ThroughTick {
+                tick: last.t,
+                max_ticks: DEFAULT_MAX_LEG_TICKS,
+            },
+            None => Self::Ticks(0),
+        }
+    }
+
+    pub fn max_ticks(self) -> u32 {
+        match self {
+            Self::Ticks(ticks) => ticks,
+            Self::Completion { max_ticks } | Self::ThroughTick { max_ticks, .. } => max_ticks,
+        }
+    }
+
+    /// Whether the leg completing ends the run early.
+    pub fn stops_on_completion(self) -> bool {
+        !matches!(self, Self::Ticks(_))
+    }
+
+    /// The same condition with the cap lowered to `ticks` if it was higher.
+    pub fn capped(self, ticks: u32) -> Self {
+        match self {
+            Self::Ticks(max) => Self::Ticks(max.min(ticks)),
+            Self::Completion { max_ticks } => Self::Completion {
+                max_ticks: max_ticks.min(ticks),
+            },
+            Self::ThroughTick { tick, max_ticks } => Self::ThroughTick {
+                tick,
+                max_ticks: max_ticks.min(ticks),
+            },
+        }
+    }
+
+    /// What is left of the condition once `ran` steps have run.
+    pub fn after(self, ran: u32) -> Self {
+        match self {
+            Self::Ticks(ticks) => Self::Ticks(ticks.saturating_sub(ran)),
+            Self::Completion { max_ticks } => Self::Completion {
+                max_ticks: max_ticks.saturating_sub(ran),
+            },
+            Self::ThroughTick { tick, max_ticks } => Self::ThroughTick {
+                tick,
+                max_ticks: max_ticks.saturating_sub(ran),
+            },
+        }
+    }
+}
+
+impl From<u32> for StopCondition {
+    fn from(ticks: u32) -> Self {
+        Self::Ticks(ticks)
+    }
+}
+
+/// Steps `app` until `stop` says to, handing each tick's drained commands to
+/// `sink`, and returns the steps run. With `snapshots` set the sink also gets
+/// the director resources as they stood when the tick began; otherwise none
+/// are captured. Completion is checked before each step,
+/// so a run split across calls stops where a single call would; the
+/// director leaves its state untouched after completion, so the state left
+/// behind matches a run that kept stepping.
+pub(crate) fn drive_ticks(
+    app: &mut App,
+    options: &CliOptions,
+    meters: Option<&MeterRegistry>,
+    stop: StopCondition,
+    snapshots: bool,
+    mut sink: impl FnMut(Option<&TickSnapshot>, Vec<Command>),
+) -> Result<u32> {
+    let mut ran = 0;
+    while ran < stop.max_ticks() {
+        if stop.stops_on_completion() && leg_completed(app.world()) {
+            break;
+        }
+        let tick = app.world().resource::<DirectorState>().leg_tick;
+        let snapshot = snapshots.then(|| TickSnapshot::capture(app.world()));
+        let drained = step_leg_app(app, options, meters)?;
+        ran += 1;
+        sink(snapshot.as_ref(), drained);
+        if matches!(stop, StopCondition::ThroughTick { tick: last, .. } if tick >= last) {
+            break;
+        }
+    }
+    Ok(ran)
+}
+
+fn leg_completed(world: &World) -> bool {
+    matches!(
+        world.resource::<DirectorState>().status,
+        LegStatus::Completed(_)
+    )
+}
+
+/// Commands kept on each side of a replay mismatch.
+pub const MISMATCH_WINDOW: usize = 10;
+/// Most mismatch contexts written by `--continue-after-mismatch`.
+pub const MAX_MISMATCH_CONTEXTS: usize = 20;
+
+/// Director resources at the start of a tick, before its systems run.
+#[derive(Debug, Clone, Serialize)]
+pub struct TickSnapshot {
+    pub director: DirectorState,
+    pub spawn_memory: SpawnMemory,
+    pub econ_intent: EconIntent,
+    pub wheel: WheelState,
+}
+
+impl TickSnapshot {
+    pub fn capture(world: &World) -> Self {
+        Self {
+            director: world
+                .get_resource::<DirectorState>()
+                .cloned()
+                .unwrap_or_default(),
+            spawn_memory: world
+                .get_resource::<SpawnMemory>()
+                .cloned()
+                .unwrap_or_default(),
+            econ_intent: world
+                .get_resource::<EconIntent>()
+                .copied()
+                .unwrap_or_default(),
+            wheel: world
+                .get_resource::<WheelState>()
+                .copied()
+                .unwrap_or_default(),
+        }
+    }
+}
+
+/// What a replay saw around one diverging command. Both windows start at
+/// `window_start` and cover up to [`MISMATCH_WINDOW`] commands either side
+/// of `index`.
+#[derive(Debug, Clone, Serialize)]
+pub struct MismatchContext {
+    pub index: usize,
+    pub tick: u32,
+    pub meta: RecordMeta,
+    pub window_start: usize,
+    pub expected: Vec<Command>,
+    pub actual: Vec<Command>,
+    /// Live resources at the start of `tick`; absent when the replay ran
+    /// out of commands before reaching it.
+    pub snapshot: Option<TickSnapshot>,
+}
+
+impl MismatchContext {
+    pub fn collect(
+        meta: &RecordMeta,
+        expected: &[Command],
+        actual: &[Command],
+        index: usize,
+        snapshot: Option<TickSnapshot>,
+    ) -> Self {
+        let window_start = index.saturating_sub(MISMATCH_WINDOW);
+        let window = |commands: &[Command]| {
+            let end = (index + MISMATCH_WINDOW + 1).min(commands.len());
+            commands
+                .get(window_start..end)
+                .map(<[Command]>::to_vec)
+                .unwrap_or_default()
+        };
+        let tick = actual
+            .get(index)
+            .or_else(|| expected.get(index))
+            .map(|command| command.t)
+            .unwrap_or_default();
+        Self {
+            index,
+            tick,
+            meta: meta.clone(),
+            window_start,
+            expected: window(expected),
+            actual: window(actual),
+            snapshot,
+        }
+    }
+}
+
+/// `leg.json` → `leg.mismatch.json`, next to the record.
+pub fn mismatch_context_path(record: &Path) -> PathBuf {
+    record.with_extension("mismatch.json")
+}
+
+/// Runs the leg the same way record mode does and returns every command.
+pub fn record_leg(options: &CliOptions) -> Result<(Vec<Command>, DirectorState)> {
+    let mut commands = Vec::new();
+    let (state, _, _) = run_leg_ticks(
+        options,
+        options.stop_condition(),
+        leg_context_from_options(options)?,
+        false,
+        |_, drained| commands.extend(drained),
+    )?;
+    Ok((commands, state))
+}
+
+/// Idle stretches of a recorded leg: runs of at least `min_ticks` ticks that
+/// emitted nothing, up to and including the tick the leg completed on.
+pub fn leg_idle_spans(
+    commands: &[Command],
+    state: &DirectorState,
+    min_ticks: u32,
+) -> Vec<IdleSpan> {
+    let end_tick = match state.status {
+        LegStatus::Completed(_) => state.leg_tick.saturating_add(1),
+        _ => state.leg_tick,
+    };
+    idle_spans(commands, end_tick, min_ticks)
+}
+
+/// Runs the leg without keeping its commands, stopping early once the leg
+/// completes. The terminal state matches [`record_leg`] for the same options.
+pub fn fast_forward(options: &CliOptions) -> Result<(DirectorState, LegSummary)> {
+    let mut summary = LegSummary::default();
+    let stop = StopCondition::Completion {
+        max_ticks: options.stop_condition().max_ticks(),
+    };
+    let (state, context, _) = run_leg_ticks(
+        options,
+        stop,
+        leg_context_from_options(options)?,
+        false,
+        |_, drained| summary.observe(&drained),
+    )?;
+    summary.final_danger = state.current_danger_score;
+    summary.basis_total = context.basis_overlay_bp_total;
+    Ok((state, summary))
+}
+
+/// Wallet a trading session opens with.
+pub const TRADING_SESSION_WALLET: MoneyCents = MoneyCents(500_000);
+/// Cargo mass and volume capacity of a trading session's hold.
+pub const TRADING_SESSION_CAPACITY: u32 = 10_000;
+
+/// Runs `script` against the economy of `seed` over `days` economy days and
+/// returns everything it emitted as one record.
+///
+/// The economy starts neutral at day 0 on the default rulepack schedule and
+/// world graph. Trades run in script order, spread evenly over the days:
+/// trade `i` runs on day `i * days / script.len()`, all on day 0 when `days`
+/// is 0. Each day's trades go through the hub trade actions, metering
+/// `ui_click_buy`/`ui_click_sell`, then `trade_total_cents` for a filled
+/// trade or `trade_rejected` for one the wallet, hold or market refused.
+/// The day then steps every hub, metering `price_clamp_hits` per hub and
+/// `econ_day` with the new day. Commands are stamped with the day they were
+/// emitted on.
+pub fn record_trading_session(script: &[TradeTx], days: u32, seed: u64) -> Result<Record> {
+    let options = CliOptions::for_mode(Mode::Record);
+    let schedule = rulepack_schedule(&options)?;
+    let graph = load_world_graph_data(&world_graph_path()?)?;
+    let hubs = graph.hub_ids();
+    let catalog = load_default_commodities()?;
+    let commodities: Vec<CommodityId> = catalog.list().iter().map(|spec| spec.id).collect();
+    let _catalog = CatalogHandle::new(catalog).scope();
+    let _config = TradingConfig::scope(load_default_trading_config()?);
+    let meters = meter_registry()?;
+    let econ_version = AppState::default().econ_version;
+
+    let mut econ = neutral_state(&schedule, &commodities);
+    let mut cargo = Cargo {
+        capacity_mass_kg: TRADING_SESSION_CAPACITY,
+        capacity_volume_l: TRADING_SESSION_CAPACITY,
+        ..Cargo::default()
+    };
+    let mut wallet = Wallet::new(TRADING_SESSION_WALLET);
+    let mut journal = LedgerJournal::default();
+    let mut queue = CommandQueue::authoritative();
+    let mut commands = Vec::new();
+    let mut trades = script.iter().enumerate().peekable();
+    for day in 0..days.max(1) {
+        queue.begin_tick(day);
+        let rules = schedule.for_day(econ.day);
+        while let Some((_, tx)) =
+            trades.next_if(|&(idx, _)| trade_day(idx, script.len(), days) == day)
+        {
+            let mut access = WalletAccess::new(&mut wallet, &mut journal, day);
+            let result = match tx.kind {
+                TradeKind::Buy => {
+                    HubTradeActions::buy(&mut queue, *tx, &mut econ, &mut cargo, &mut access, rules)
+                }
+                TradeKind::Sell => HubTradeActions::sell(
+                    &mut queue,
+                    *tx,
+                    &mut econ,
+                    &mut cargo,
+                    &mut access,
+                    rules,
+                ),
+            };
+            match result {
+                Ok(result) => queue.meter_wide("trade_total_cents", result.total_cents.as_i64()),
+                Err(_) => queue.meter("trade_rejected", 1),
+            }
+        }
+        if days > 0 {
+            for delta in step_all_hubs(&schedule, seed, econ_version, &hubs, &mut econ) {
+                delta.meter_price_clamps(&mut queue);
+            }
+            queue.meter_units("econ_day", econ.day.0);
+        }
+        let drained = queue.drain();
+        meters.validate_tick(&drained)?;
+        commands.extend(drained);
+    }
+
+    let rulepack = schedule.entry_for(EconomyDay(0));
+    Ok(Record {
+        meta: RecordMeta {
+            schema: RECORD_SCHEMA,
+            world_seed: format!("0x{seed:016X}"),
+            rulepack: format!("{RULEPACK_DIR}/{}", rulepack.file),
+            day: econ.day.0,
+            pp: econ.pp.0,
+            ..RecordMeta::default()
+        },
+        commands,
+        ..Record::default()
+    })
+}
+
+/// The session day script entry `idx` of `len` runs on.
+fn trade_day(idx: usize, len: usize, days: u32) -> u32 {
+    if days == 0 {
+        return 0;
+    }
+    let day = idx as u64 * u64::from(days) / len as u64;
+    u32::try_from(day).unwrap_or(days - 1)
+}
+
+/// What one [`HeadlessRunner::step`] call produced.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StepOutcome {
+    /// Commands emitted by the ticks this call ran, in order.
+    pub commands: Vec<Command>,
+    /// Whether the leg had completed by the end of the call.
+    pub completed: bool,
+}
+
+/// A headless leg that can be paused between calls and resumed, for hosts
+/// that interleave the simulation with other work. Steps are never skipped
+/// once the leg completes, so running the same total ticks in any split
+/// emits the same commands as [`record_leg`].
+pub struct HeadlessRunner {
+    app: App,
+    options: CliOptions,
+    meters: Option<&'static MeterRegistry>,
+    ticks_run: u32,
+}
+
+impl HeadlessRunner {
+    /// Builds the leg's app and runs its startup; no fixed step runs until
+    /// the first [`step`](Self::step).
+    pub fn new(options: &CliOptions, context: LegContext) -> Result<Self> {
+        let meters = leg_meters(options)?;
+        Ok(Self {
+            app: start_leg_app(options, context),
+            options: options.clone(),
+            meters,
+            ticks_run: 0,
+        })
+    }
+
+    /// The leg `options` describe, as [`record_leg`] runs it.
+    pub fn from_options(options: &CliOptions) -> Result<Self> {
+        Self::new(options, leg_context_from_options(options)?)
+    }
+
+    /// Runs `budget_ticks` more fixed steps and returns their commands.
+    pub fn step(&mut self, budget_ticks: u32) -> Result<StepOutcome> {
+        let mut commands = Vec::new();
+        self.ticks_run += drive_ticks(
+            &mut self.app,
+            &self.options,
+            self.meters,
+            StopCondition::Ticks(budget_ticks),
+            false,
+            |_, drained| commands.extend(drained),
+        )?;
+        Ok(StepOutcome {
+            commands,
+            completed: self.is_completed(),
+        })
+    }
+
+    /// Fixed steps run so far across every [`step`](Self::step) call.
+    pub fn ticks_run(&self) -> u32 {
+        self.ticks_run
+    }
+
+    pub fn is_completed(&self) -> bool {
+        matches!(self.director().status, LegStatus::Completed(_))
+    }
+
+    pub fn director(&self) -> &DirectorState {
+        self.app.world().resource::<DirectorState>()
+    }
+
+    /// Ends the run, writing the tick profile as a finished leg would, and
+    /// returns the terminal director state and leg context.
+    pub fn finish(self) -> Result<(DirectorState, LegContext)> {
+        let (state, context, _) = finish_leg_app(&self.app, &self.options)?;
+        Ok((state, context))
+    }
+}
+
+/// Manifest of golden records, relative to the workspace.
+pub const GOLDEN_MANIFEST: &str = "repro/records/manifest.toml";
+
+const GOLDEN_MANIFEST_HEADER: &str = "\
+# Golden legs checked by `--verify-goldens` and rewritten by `--update-goldens`.
+# `record` is relative to this file; `expected_hash` is the record hash of the
+# leg re-simulated from the record's meta.
+";
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct GoldenManifest {
+    #[serde(default, rename = "case")]
+    pub cases: Vec<GoldenCase>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct GoldenCase {
+    pub name: String,
+    /// Record file, relative to the manifest.
+    pub record: PathBuf,
+    /// Unset until the first `--update-goldens`; such cases report
+    /// [`GoldenStatus::Unpinned`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expected_hash: Option<String>,
+    /// A known, accepted divergence: a mismatch is reported but does not
+    /// fail verification.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tolerated_mismatch: Option<bool>,
+    /// Part of the subset CI verifies on every run.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub ci: bool,
+}
+
+impl GoldenManifest {
+    pub fn load(path: &Path) -> Result<Self> {
+        let raw =
+            fs::read_to_string(path).with_context(|| format!("reading {}", path.display()))?;
+        toml::from_str(&raw).with_context(|| format!("parsing {}", path.display()))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum GoldenStatus {
+    Pass,
+    Mismatch {
+        expected: String,
+    },
+    /// A mismatch on a case marked `tolerated_mismatch`.
+    Tolerated {
+        expected: String,
+    },
+    /// The case has no `expected_hash` yet.
+    Unpinned,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct GoldenOutcome {
+    pub name: String,
+    #[serde(flatten)]
+    pub status: GoldenStatus,
+    pub actual_hash: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct GoldenReport {
+    pub cases: Vec<GoldenOutcome>,
+}
+
+impl GoldenReport {
+    /// True unless a case mismatched without being tolerated.
+    pub fn passed(&self) -> bool {
+        !self
+            .cases
+            .iter()
+            .any(|case| matches!(case.status, GoldenStatus::Mismatch { .. }))
+    }
+}
+
+/// Verifies every case in the manifest at `manifest_path`.
+pub fn verify_goldens(manifest_path: &Path) -> Result<GoldenReport> {
+    verify_golden_cases(manifest_path, |_| true)
+}
+
+/// Re-simulates each selected case from its record's meta and compares the
+/// resulting record hash with the case's `expected_hash`.
+pub fn verify_golden_cases(
+    manifest_path: &Path,
+    mut select: impl FnMut(&GoldenCase) -> bool,
+) -> Result<GoldenReport> {
+    crate::world::index::ensure_world_index()?;
+    let manifest = GoldenManifest::load(manifest_path)?;
+    let base = manifest_dir(manifest_path);
+    let mut report = GoldenReport::default();
+    for case in manifest.cases.iter().filter(|case| select(case)) {
+        let (_, actual_hash) = resimulate_golden(&base.join(&case.record))
+            .with_context(|| format!("golden case {}", case.name))?;
+        let status = match &case.expected_hash {
+            None => GoldenStatus::Unpinned,
+            Some(expected) if *expected == actual_hash => GoldenStatus::Pass,
+            Some(expected) if case.tolerated_mismatch == Some(true) => GoldenStatus::Tolerated {
+                expected: expected.clone(),
+            },
+            Some(expected) => GoldenStatus::Mismatch {
+                expected: expected.clone(),
+            },
+        };
+        report.cases.push(GoldenOutcome {
+            name: case.name.clone(),
+            status,
+            actual_hash,
+        });
+    }
+    Ok(report)
+}
+
+/// Re-simulates every case and rewrites its record, the `.hash` beside it and
+/// the manifest's `expected_hash`. Every file is written to a temporary
+/// sibling first and only renamed into place once all of them succeeded.
+pub fn update_goldens(manifest_path: &Path) -> Result<GoldenManifest> {
+    crate::world::index::ensure_world_index()?;
+    let mut manifest = GoldenManifest::load(manifest_path)?;
+    let base = manifest_dir(manifest_path);
+    let mut staged = Vec::new();
+    let result = (|| -> Result<()> {
+        for case in &mut manifest.cases {
+            let record_path = base.join(&case.record);
+            let (record, hash) = resimulate_golden(&record_path)
+                .with_context(|| format!("golden case {}", case.name))?;
+            staged.push(stage(&record_path, &canonical_json_bytes(&record)?)?);
+            staged.push(stage(
+                &record_path.with_extension("hash"),
+                format!("{hash}\n").as_bytes(),
+            )?);
+            case.expected_hash = Some(hash);
+        }
+        let manifest_text = format!(
+            "{GOLDEN_MANIFEST_HEADER}\n{}",
+            toml::to_string(&manifest).context("serializing golden manifest")?
+        );
+        staged.push(stage(manifest_path, manifest_text.as_bytes())?);
+        Ok(())
+    })();
+    if let Err(err) = result {
+        for (tmp, _) in &staged {
+            let _ = fs::remove_file(tmp);
+        }
+        return Err(err);
+    }
+    for (tmp, path) in staged {
+        fs::rename(&tmp, &path).with_context(|| format!("replacing {}", path.display()))?;
+    }
+    Ok(manifest)
+}
+
+fn manifest_dir(manifest_path: &Path) -> PathBuf {
+    manifest_path
+        .parent()
+        .map(Path::to_path_buf)
+        .unwrap_or_default()
+}
+
+/// Writes `bytes` next to `path` and returns `(temporary, final)` paths.
+fn stage(path: &Path, bytes: &[u8]) -> Result<(PathBuf, PathBuf)> {
+    let mut tmp = path.as_os_str().to_owned();
+    tmp.push(".tmp");
+    let tmp = PathBuf::from(tmp);
+    fs::write(&tmp, bytes).with_context(|| format!("writing {}", tmp.display()))?;
+    Ok((tmp, path.to_path_buf()))
+}
+
+/// Replays the leg a golden record describes and returns the record with its
+/// commands replaced by the fresh run, and that record's hash. Meta, inputs
+/// and annotations are kept as recorded.
+fn resimulate_golden(record_path: &Path) -> Result<(Record, String)> {
+    let bytes =
+        fs::read(record_path).with_context(|| format!("reading {}", record_path.display()))?;
+    let mut record = Record::from_json_bytes(&bytes, ParseMode::Strict)
+        .with_context(|| format!("parsing {}", record_path.display()))?;
+    let mut options = CliOptions::for_mode(Mode::Replay);
+    options.headless = true;
+    let mut commands = Vec::new();
+    replay_leg(&options, &record, false, |_, drained| {
+        commands.extend(drained)
+    })?;
+    record.commands = commands;
+    let hash = hash_record(&record)?;
+    Ok((record, hash))
+}
+
+/// `--verify-goldens` / `--update-goldens`: `--io` names the manifest,
+/// [`GOLDEN_MANIFEST`] otherwise.
+pub(crate) fn run_goldens(options: &CliOptions) -> Result<()> {
+    let manifest_path = options
+        .io
+        .as_ref()
+        .map_or_else(|| workspace_asset(GOLDEN_MANIFEST), PathBuf::from);
+    if options.update_goldens {
+        let manifest = update_goldens(&manifest_path)?;
+        println!(
+            "{}: rewrote {} golden case(s)",
+            manifest_path.display(),
+            manifest.cases.len()
+        );
+        return Ok(());
+    }
+    let report = verify_goldens(&manifest_path)?;
+    for case in &report.cases {
+        let status = match &case.status {
+            GoldenStatus::Pass => "PASS".to_owned(),
+            GoldenStatus::Mismatch { expected } => format!("FAIL (expected {expected})"),
+            GoldenStatus::Tolerated { expected } => format!("TOLERATED (expected {expected})"),
+            GoldenStatus::Unpinned => "UNPINNED".to_owned(),
+        };
+        println!("{} {} {status}", case.name, case.actual_hash);
+    }
+    if report.passed() {
+        Ok(())
+    } else {
+        Err(anyhow::anyhow!("golden verification failed"))
+    }
+}
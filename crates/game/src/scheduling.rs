@@ -1,7 +1,12 @@
+use std::collections::{HashMap, HashSet};
+
+use bevy::ecs::intern::Interned;
+use bevy::ecs::schedule::graph::Direction;
+use bevy::ecs::schedule::{NodeId, ScheduleGraph, SystemSet};
 use bevy::prelude::*;
 
 #[cfg(feature = "deterministic")]
-use bevy::ecs::schedule::ExecutorKind;
+use bevy::ecs::schedule::{ExecutorKind, LogLevel, ScheduleBuildSettings};
 
 pub mod sets {
     #![allow(non_camel_case_types)]
@@ -26,6 +31,27 @@ pub mod sets {
     pub struct DETTEROT_Cleanup;
 }
 
+/// Names of the `FixedUpdate` pipeline sets in the order they run.
+pub const PIPELINE: [&str; 6] = [
+    "DETTEROT_Input",
+    "DETTEROT_Director",
+    "DETTEROT_Missions",
+    "DETTEROT_Spawns",
+    "DETTEROT_PhysicsStep",
+    "DETTEROT_Cleanup",
+];
+
+fn pipeline_sets() -> [Interned<dyn SystemSet>; 6] {
+    [
+        sets::DETTEROT_Input.intern(),
+        sets::DETTEROT_Director.intern(),
+        sets::DETTEROT_Missions.intern(),
+        sets::DETTEROT_Spawns.intern(),
+        sets::DETTEROT_PhysicsStep.intern(),
+        sets::DETTEROT_Cleanup.intern(),
+    ]
+}
+
 pub fn configure(app: &mut App) {
     app.configure_sets(
         FixedUpdate,
@@ -40,10 +66,232 @@ pub fn configure(app: &mut App) {
             .chain(),
     );
 
+    // Ambiguous systems run in insertion order on one thread, which a new
+    // plugin can silently change; fail the schedule build instead.
     #[cfg(feature = "deterministic")]
     {
         app.edit_schedule(FixedUpdate, |schedule| {
             schedule.set_executor_kind(ExecutorKind::SingleThreaded);
+            schedule.set_build_settings(ScheduleBuildSettings {
+                ambiguity_detection: LogLevel::Error,
+                ..default()
+            });
         });
     }
 }
+
+/// A `FixedUpdate` system and the pipeline sets that contain it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScheduledSystem {
+    pub name: String,
+    /// Entries of [`PIPELINE`], in pipeline order.
+    pub sets: Vec<&'static str>,
+}
+
+struct Pipeline<'a> {
+    graph: &'a ScheduleGraph,
+    set_nodes: [Option<NodeId>; 6],
+    names: HashMap<NodeId, String>,
+}
+
+impl<'a> Pipeline<'a> {
+    fn new(schedule: &'a Schedule) -> Self {
+        let graph = schedule.graph();
+        let mut set_nodes = [None; 6];
+        for (key, set, _) in graph.system_sets.iter() {
+            if let Some(idx) = pipeline_sets().iter().position(|ours| **ours == *set) {
+                set_nodes[idx] = Some(NodeId::Set(key));
+            }
+        }
+        // Systems move into the executable schedule once it is built.
+        let mut names: HashMap<NodeId, String> = graph
+            .systems
+            .iter()
+            .map(|(key, system, _)| (NodeId::System(key), system.name().to_string()))
+            .collect();
+        if let Ok(built) = schedule.systems() {
+            names.extend(
+                built.map(|(key, system)| (NodeId::System(key), system.name().to_string())),
+            );
+        }
+        Self {
+            graph,
+            set_nodes,
+            names,
+        }
+    }
+
+    fn name(&self, node: NodeId) -> String {
+        match self.names.get(&node) {
+            Some(name) => name.clone(),
+            None => self.graph.get_node_name(&node),
+        }
+    }
+
+    /// Pipeline sets containing `node`, directly or through nested sets.
+    fn sets_of(&self, node: NodeId) -> Vec<usize> {
+        let hierarchy = self.graph.hierarchy().graph();
+        let mut seen = HashSet::new();
+        let mut stack = vec![node];
+        let mut found = Vec::new();
+        while let Some(next) = stack.pop() {
+            for parent in hierarchy.neighbors_directed(next, Direction::Incoming) {
+                if seen.insert(parent) {
+                    if let Some(idx) = self.set_nodes.iter().position(|set| *set == Some(parent)) {
+                        found.push(idx);
+                    }
+                    stack.push(parent);
+                }
+            }
+        }
+        found.sort_unstable();
+        found
+    }
+
+    /// Position of `node` in the pipeline: its own index for a pipeline
+    /// set, otherwise that of the single pipeline set containing it.
+    fn rank(&self, node: NodeId) -> Option<usize> {
+        if let Some(idx) = self.set_nodes.iter().position(|set| *set == Some(node)) {
+            return Some(idx);
+        }
+        match self.sets_of(node).as_slice() {
+            [idx] => Some(*idx),
+            _ => None,
+        }
+    }
+
+    fn runs_before(&self, from: NodeId, to: NodeId) -> bool {
+        let dependency = self.graph.dependency().graph();
+        let mut seen = HashSet::new();
+        let mut stack = vec![from];
+        while let Some(next) = stack.pop() {
+            if next == to {
+                return true;
+            }
+            stack.extend(
+                dependency
+                    .neighbors_directed(next, Direction::Outgoing)
+                    .filter(|node| seen.insert(*node)),
+            );
+        }
+        false
+    }
+
+    fn systems(&self) -> Vec<NodeId> {
+        let mut systems: Vec<NodeId> = self
+            .graph
+            .hierarchy()
+            .graph()
+            .nodes()
+            .filter(NodeId::is_system)
+            .collect();
+        systems.sort_by_key(|node| self.name(*node));
+        systems
+    }
+
+    fn violations(&self) -> Vec<String> {
+        let mut violations = Vec::new();
+        for (idx, node) in self.set_nodes.iter().enumerate() {
+            if node.is_none() {
+                violations.push(format!("`{}` is not configured", PIPELINE[idx]));
+            }
+        }
+        for (idx, pair) in self.set_nodes.windows(2).enumerate() {
+            if let [Some(first), Some(second)] = *pair {
+                if !self.runs_before(first, second) {
+                    violations.push(format!(
+                        "`{}` is not ordered before `{}`",
+                        PIPELINE[idx],
+                        PIPELINE[idx + 1]
+                    ));
+                }
+            }
+        }
+
+        let dependency = self.graph.dependency().graph();
+        for system in self.systems() {
+            let name = self.name(system);
+            let sets = self.sets_of(system);
+            if sets.len() > 1 {
+                let names: Vec<&str> = sets.iter().map(|idx| PIPELINE[*idx]).collect();
+                violations.push(format!(
+                    "`{name}` is in several pipeline sets: {}",
+                    names.join(", ")
+                ));
+                continue;
+            }
+            let ordered = sets.len() == 1
+                || [Direction::Incoming, Direction::Outgoing]
+                    .into_iter()
+                    .flat_map(|direction| dependency.neighbors_directed(system, direction))
+                    .any(|other| self.rank(other).is_some());
+            if !ordered {
+                violations.push(format!(
+                    "`{name}` is outside the pipeline sets and unordered against them"
+                ));
+            }
+        }
+        for (before, after) in dependency.all_edges() {
+            if let (Some(first), Some(second)) = (self.rank(before), self.rank(after)) {
+                if first > second {
+                    violations.push(format!(
+                        "`{}` ({}) is ordered before `{}` ({})",
+                        self.name(before),
+                        PIPELINE[first],
+                        self.name(after),
+                        PIPELINE[second]
+                    ));
+                }
+            }
+        }
+        violations
+    }
+}
+
+fn fixed_update(app: &App) -> Option<&Schedule> {
+    app.world().get_resource::<Schedules>()?.get(FixedUpdate)
+}
+
+/// Every `FixedUpdate` system with the pipeline sets containing it, sorted
+/// by name.
+pub fn scheduled_systems(app: &App) -> Vec<ScheduledSystem> {
+    let Some(schedule) = fixed_update(app) else {
+        return Vec::new();
+    };
+    let pipeline = Pipeline::new(schedule);
+    pipeline
+        .systems()
+        .into_iter()
+        .map(|system| ScheduledSystem {
+            name: pipeline.name(system),
+            sets: pipeline
+                .sets_of(system)
+                .into_iter()
+                .map(|idx| PIPELINE[idx])
+                .collect(),
+        })
+        .collect()
+}
+
+/// Ways `app`'s `FixedUpdate` schedule breaks the pipeline contract: the
+/// sets must run in [`PIPELINE`] order, each system must sit in at most one
+/// of them and be ordered against them, and no ordering may run a later
+/// set's work before an earlier one's. Empty when the contract holds.
+pub fn contract_violations(app: &App) -> Vec<String> {
+    match fixed_update(app) {
+        Some(schedule) => Pipeline::new(schedule).violations(),
+        None => vec!["no `FixedUpdate` schedule".to_string()],
+    }
+}
+
+/// Panics with every [`contract_violations`] entry unless there are none.
+/// Downstream plugins that touch the command queue from `FixedUpdate` can
+/// run this on their app in tests.
+pub fn assert_contract(app: &App) {
+    let violations = contract_violations(app);
+    assert!(
+        violations.is_empty(),
+        "FixedUpdate schedule contract violated:\n  {}",
+        violations.join("\n  ")
+    );
+}
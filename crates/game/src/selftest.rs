@@ -0,0 +1,100 @@
+//! Determinism self-test: records the configured leg twice in fresh apps and
+//! checks the hashes agree, then round-trips the first record through its
+//! canonical JSON and replays it, checking every command comes back.
+
+use std::fmt;
+
+use anyhow::{anyhow, Result};
+use repro::strict::ParseMode;
+use repro::{canonical_json_bytes, hash_record, Command, Record};
+
+use crate::cli::CliOptions;
+use crate::runtime::StopCondition;
+use crate::{leg_context_from_options, record_leg, replay_leg};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SelfTestReport {
+    pub first_hash: String,
+    pub second_hash: String,
+    /// Hash of the first record with its commands replaced by the replay's.
+    pub replay_hash: String,
+    /// First command index where the replay left the record, if any.
+    pub replay_mismatch: Option<usize>,
+}
+
+impl SelfTestReport {
+    pub fn is_pass(&self) -> bool {
+        self.first_hash == self.second_hash
+            && self.first_hash == self.replay_hash
+            && self.replay_mismatch.is_none()
+    }
+}
+
+impl fmt::Display for SelfTestReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "record 1: {}", self.first_hash)?;
+        writeln!(f, "record 2: {}", self.second_hash)?;
+        writeln!(f, "replay:   {}", self.replay_hash)?;
+        if self.first_hash != self.second_hash {
+            writeln!(f, "records differ between runs")?;
+        }
+        if let Some(index) = self.replay_mismatch {
+            writeln!(f, "replay diverged at command {index}")?;
+        }
+        write!(f, "{}", if self.is_pass() { "PASS" } else { "FAIL" })
+    }
+}
+
+/// Runs the self-test on the leg `options` describes, recording until `stop`
+/// and replaying through the record's last command. Simulation errors are
+/// returned; disagreements are reported.
+pub fn run_self_test(
+    options: &CliOptions,
+    stop: impl Into<StopCondition>,
+) -> Result<SelfTestReport> {
+    let stop = stop.into();
+    let mut options = options.clone();
+    options.headless = true;
+    let context = leg_context_from_options(&options)?;
+
+    let (first, _) = record_leg(&options, context, stop)?;
+    let (second, _) = record_leg(&options, context, stop)?;
+
+    let bytes = canonical_json_bytes(&first)?;
+    let parsed = Record::from_json_bytes(&bytes, ParseMode::Strict)?;
+    let mut replayed: Vec<Command> = Vec::new();
+    replay_leg(&options, &parsed, false, |_, drained| {
+        replayed.extend(drained);
+    })?;
+    let replay_mismatch = first_divergence(&first.commands, &replayed);
+    let replay = Record {
+        commands: replayed,
+        ..first.clone()
+    };
+
+    Ok(SelfTestReport {
+        first_hash: hash_record(&first)?,
+        second_hash: hash_record(&second)?,
+        replay_hash: hash_record(&replay)?,
+        replay_mismatch,
+    })
+}
+
+fn first_divergence(expected: &[Command], actual: &[Command]) -> Option<usize> {
+    expected
+        .iter()
+        .zip(actual)
+        .position(|(expected, actual)| expected != actual)
+        .or_else(|| (expected.len() != actual.len()).then(|| expected.len().min(actual.len())))
+}
+
+/// `--mode self-test`: prints the report and fails unless it passed.
+pub(crate) fn run(options: CliOptions) -> Result<()> {
+    let report = run_self_test(&options, options.stop_condition())?;
+    println!("{report}");
+    if report.is_pass() {
+        Ok(())
+    } else {
+        Err(anyhow!("determinism self-test failed"))
+    }
+}
@@ -0,0 +1,194 @@
+//! Determinism soak: every seed records its campaign twice in fresh apps and
+//! the leg hashes must agree. Seeds run on separate threads; each simulation
+//! stays on its own thread (and on a one-thread task pool under the
+//! `deterministic` feature).
+
+use std::fmt;
+use std::ops::Range;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
+use std::time::Duration;
+
+use anyhow::{anyhow, Result};
+use repro::hash_record;
+
+use crate::cli::{CliOptions, Mode};
+use crate::systems::director::campaign::next_leg_context;
+use crate::wallclock::Stopwatch;
+use crate::{leg_context_from_options, record_leg};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SoakConfig {
+    /// World seeds to soak; each is used as the campaign's world seed.
+    pub seeds: Range<u64>,
+    /// Consecutive legs per campaign, one day apart, each carrying the
+    /// previous leg's danger forward.
+    pub legs: u32,
+    /// Fixed steps simulated per leg.
+    pub ticks: u32,
+    /// Worker threads; zero uses the available parallelism.
+    pub threads: usize,
+}
+
+/// Both runs of one seed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SeedReport {
+    pub seed: u64,
+    pub first: Vec<String>,
+    pub second: Vec<String>,
+    /// Commands emitted by one run of the campaign.
+    pub commands: u64,
+    /// Wall-clock time of every leg, both runs included.
+    pub leg_timings: Vec<Duration>,
+}
+
+impl SeedReport {
+    pub fn is_stable(&self) -> bool {
+        self.first == self.second
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct SoakReport {
+    pub seeds: Vec<SeedReport>,
+}
+
+impl SoakReport {
+    pub fn is_stable(&self) -> bool {
+        self.seeds.iter().all(SeedReport::is_stable)
+    }
+
+    pub fn unstable_seeds(&self) -> Vec<u64> {
+        self.seeds
+            .iter()
+            .filter(|seed| !seed.is_stable())
+            .map(|seed| seed.seed)
+            .collect()
+    }
+
+    pub fn total_commands(&self) -> u64 {
+        self.seeds.iter().map(|seed| seed.commands).sum()
+    }
+
+    pub fn leg_timings(&self) -> Vec<Duration> {
+        self.seeds
+            .iter()
+            .flat_map(|seed| seed.leg_timings.iter().copied())
+            .collect()
+    }
+
+    /// Nearest-rank percentile of the leg timings; zero when none ran.
+    pub fn leg_percentile(&self, pct: usize) -> Duration {
+        let mut sorted = self.leg_timings();
+        sorted.sort_unstable();
+        if sorted.is_empty() {
+            return Duration::ZERO;
+        }
+        let rank = (sorted.len() * pct.min(100)).div_ceil(100).max(1);
+        sorted[rank - 1]
+    }
+}
+
+impl fmt::Display for SoakReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for seed in &self.seeds {
+            let verdict = if seed.is_stable() {
+                "stable"
+            } else {
+                "UNSTABLE"
+            };
+            writeln!(
+                f,
+                "seed {}: {verdict} ({} legs, {} commands)",
+                seed.seed,
+                seed.first.len(),
+                seed.commands
+            )?;
+        }
+        writeln!(
+            f,
+            "legs: {} timed, p50 {:?}, p95 {:?}",
+            self.leg_timings().len(),
+            self.leg_percentile(50),
+            self.leg_percentile(95)
+        )?;
+        writeln!(f, "commands: {}", self.total_commands())?;
+        let unstable = self.unstable_seeds();
+        if unstable.is_empty() {
+            write!(f, "all {} seeds stable", self.seeds.len())
+        } else {
+            write!(f, "unstable seeds: {unstable:?}")
+        }
+    }
+}
+
+/// Runs the soak described by `cfg`. Simulation errors abort the soak;
+/// hash disagreements are reported, not returned as errors.
+pub fn run_soak(cfg: &SoakConfig) -> Result<SoakReport> {
+    let seeds: Vec<u64> = cfg.seeds.clone().collect();
+    let threads = match cfg.threads {
+        0 => thread::available_parallelism().map_or(1, usize::from),
+        n => n,
+    }
+    .min(seeds.len().max(1));
+
+    let next = AtomicUsize::new(0);
+    let results: Mutex<Vec<Option<Result<SeedReport>>>> =
+        Mutex::new(seeds.iter().map(|_| None).collect());
+    thread::scope(|scope| {
+        for _ in 0..threads {
+            scope.spawn(|| loop {
+                let idx = next.fetch_add(1, Ordering::Relaxed);
+                let Some(&seed) = seeds.get(idx) else {
+                    break;
+                };
+                let outcome = soak_seed(seed, cfg.legs, cfg.ticks);
+                results.lock().expect("soak results")[idx] = Some(outcome);
+            });
+        }
+    });
+
+    let mut report = SoakReport::default();
+    for outcome in results.into_inner().expect("soak results") {
+        let seed = outcome.ok_or_else(|| anyhow!("soak worker exited early"))??;
+        report.seeds.push(seed);
+    }
+    Ok(report)
+}
+
+fn soak_seed(seed: u64, legs: u32, ticks: u32) -> Result<SeedReport> {
+    let (first, commands, mut leg_timings) = record_campaign(seed, legs, ticks)?;
+    let (second, _, second_timings) = record_campaign(seed, legs, ticks)?;
+    leg_timings.extend(second_timings);
+    Ok(SeedReport {
+        seed,
+        first,
+        second,
+        commands,
+        leg_timings,
+    })
+}
+
+/// Records `legs` consecutive legs for `seed`, each in a fresh app, and
+/// returns their record hashes, the command count and per-leg timings.
+fn record_campaign(seed: u64, legs: u32, ticks: u32) -> Result<(Vec<String>, u64, Vec<Duration>)> {
+    let mut options = CliOptions::for_mode(Mode::Record);
+    options.headless = true;
+    let mut context = leg_context_from_options(&options)?;
+    context.world_seed = seed;
+
+    let mut hashes = Vec::with_capacity(legs as usize);
+    let mut timings = Vec::with_capacity(legs as usize);
+    let mut commands = 0u64;
+    for _ in 0..legs {
+        let started = Stopwatch::start();
+        let (record, state) = record_leg(&options, context, ticks)?;
+        timings.push(started.elapsed());
+        commands += record.commands.len() as u64;
+        hashes.push(hash_record(&record)?);
+        context = next_leg_context(&state, &context);
+        context.day = context.day.saturating_add(1);
+    }
+    Ok((hashes, commands, timings))
+}
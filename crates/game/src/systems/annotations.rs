@@ -0,0 +1,130 @@
+//! Playtest markers for the written record. `--annotate` entries and the dev
+//! keybinding queue annotations here, and the recorder moves them into the
+//! record's unhashed `annotations` when it writes it. Nothing here touches
+//! the command stream.
+
+use bevy::prelude::*;
+use repro::{Annotation, Record};
+
+use crate::systems::director::DirectorState;
+
+/// Key that marks the current leg tick in the windowed dev build.
+pub const ANNOTATE_KEY: KeyCode = KeyCode::F8;
+
+/// Annotations waiting to be written with the leg's record.
+#[derive(Resource, Debug, Clone, Default)]
+pub struct AnnotationQueue {
+    pending: Vec<Annotation>,
+}
+
+impl AnnotationQueue {
+    pub fn new(pending: Vec<Annotation>) -> Self {
+        Self { pending }
+    }
+
+    pub fn push(&mut self, t: u32, label: impl Into<String>, note: impl Into<String>) {
+        self.pending.push(Annotation {
+            t,
+            label: label.into(),
+            note: note.into(),
+        });
+    }
+
+    pub fn pending(&self) -> &[Annotation] {
+        &self.pending
+    }
+
+    /// Moves every queued annotation into `record`.
+    pub fn flush_into(&mut self, record: &mut Record) {
+        for annotation in self.pending.drain(..) {
+            record.add_annotation(annotation.t, annotation.label, annotation.note);
+        }
+    }
+}
+
+/// Parses `t=123,label=foo` with an optional trailing `note=...`, which may
+/// itself contain commas.
+pub fn parse_annotation(value: &str) -> Result<Annotation, String> {
+    let mut t = None;
+    let mut label = None;
+    let mut note = String::new();
+    let mut rest = value.trim();
+    while !rest.is_empty() {
+        if let Some(text) = rest.strip_prefix("note=") {
+            note = text.to_string();
+            break;
+        }
+        let (field, tail) = rest.split_once(',').unwrap_or((rest, ""));
+        rest = tail;
+        match field.split_once('=') {
+            Some(("t", tick)) => {
+                t = Some(
+                    tick.trim()
+                        .parse::<u32>()
+                        .map_err(|err| format!("annotation tick `{tick}`: {err}"))?,
+                );
+            }
+            Some(("label", text)) if !text.is_empty() => label = Some(text.to_string()),
+            _ => return Err(format!("unexpected annotation field `{field}`")),
+        }
+    }
+    Ok(Annotation {
+        t: t.ok_or("annotation needs `t=<tick>`")?,
+        label: label.ok_or("annotation needs `label=<name>`")?,
+        note,
+    })
+}
+
+/// Marks the current leg tick as `mark<n>` when [`ANNOTATE_KEY`] is pressed.
+pub fn annotate_on_key(
+    keys: Option<Res<ButtonInput<KeyCode>>>,
+    director: Option<Res<DirectorState>>,
+    mut queue: ResMut<AnnotationQueue>,
+) {
+    if !keys.is_some_and(|keys| keys.just_pressed(ANNOTATE_KEY)) {
+        return;
+    }
+    let tick = director.map_or(0, |state| state.leg_tick);
+    let label = format!("mark{}", queue.pending.len() + 1);
+    info!("annotated tick {tick} as {label}");
+    queue.push(tick, label, "");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_cli_annotations() {
+        assert_eq!(
+            parse_annotation("t=123,label=foo").expect("annotation"),
+            Annotation {
+                t: 123,
+                label: "foo".into(),
+                note: String::new(),
+            }
+        );
+        assert_eq!(
+            parse_annotation("label=lag,t=7,note=felt wrong, twice")
+                .expect("annotation")
+                .note,
+            "felt wrong, twice"
+        );
+        assert!(parse_annotation("t=1").is_err());
+        assert!(parse_annotation("label=foo").is_err());
+        assert!(parse_annotation("t=x,label=foo").is_err());
+        assert!(parse_annotation("t=1,label=foo,colour=red").is_err());
+    }
+
+    #[test]
+    fn flush_moves_annotations_into_the_record_in_tick_order() {
+        let mut queue = AnnotationQueue::default();
+        queue.push(40, "late", "");
+        queue.push(5, "early", "note");
+        let mut record = Record::default();
+        queue.flush_into(&mut record);
+        let ticks: Vec<u32> = record.annotations.iter().map(|a| a.t).collect();
+        assert_eq!(ticks, [5, 40]);
+        assert!(queue.pending().is_empty());
+    }
+}
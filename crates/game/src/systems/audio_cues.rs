@@ -0,0 +1,290 @@
+//! Gameplay sound cues driven by the meter stream.
+//!
+//! [`AudioCueRouter`] reads the meters sitting in the [`CommandQueue`] each
+//! frame and plays the clip `assets/audio/cues.toml` maps them to. It only
+//! reads the queue and owns no simulation state, so audio on or off leaves
+//! the recorded command stream unchanged. Playback goes through the
+//! [`AudioBackend`] trait: Kira behind the `audio` feature, or a test
+//! double.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use bevy::prelude::*;
+use repro::{Command, CommandKind};
+use serde::Deserialize;
+use thiserror::Error;
+
+use crate::systems::command_queue::CommandQueue;
+use crate::systems::telemetry::{meter_registry, MeterRegistry};
+
+const DEFAULT_COOLDOWN_MS: u32 = 250;
+
+/// Plays one clip by asset path, relative to `assets/`.
+pub trait AudioBackend {
+    fn play(&mut self, sound: &str);
+}
+
+/// One `[[cue]]`: plays `sound` when `meter` is emitted with a value that
+/// passes every predicate given.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CueSpec {
+    pub meter: String,
+    pub sound: String,
+    #[serde(default)]
+    pub equals: Option<i32>,
+    /// Inclusive lower bound.
+    #[serde(default)]
+    pub min: Option<i32>,
+    /// Inclusive upper bound.
+    #[serde(default)]
+    pub max: Option<i32>,
+    /// Overrides the file's `default_cooldown_ms` for this cue.
+    #[serde(default)]
+    pub cooldown_ms: Option<u32>,
+}
+
+impl CueSpec {
+    pub fn matches(&self, value: i32) -> bool {
+        self.equals.is_none_or(|equals| value == equals)
+            && self.min.is_none_or(|min| value >= min)
+            && self.max.is_none_or(|max| value <= max)
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct CuesFile {
+    #[serde(default = "default_cooldown_ms")]
+    default_cooldown_ms: u32,
+    #[serde(default, rename = "cue")]
+    cues: Vec<CueSpec>,
+}
+
+fn default_cooldown_ms() -> u32 {
+    DEFAULT_COOLDOWN_MS
+}
+
+#[derive(Debug, Error)]
+pub enum CueTableError {
+    #[error("failed to read audio cues: {0}")]
+    Read(#[from] std::io::Error),
+    #[error("failed to parse audio cues: {0}")]
+    Parse(#[from] toml::de::Error),
+    #[error("cue {index}: `{field}` is empty")]
+    EmptyField { index: usize, field: &'static str },
+    #[error("cue {index}: meter `{meter}` is not in the meter manifest")]
+    UnknownMeter { index: usize, meter: String },
+    #[error("cue {index}: no value of `{meter}` passes its predicates")]
+    EmptyRange { index: usize, meter: String },
+    #[error("meter manifest unavailable: {0}")]
+    Manifest(String),
+}
+
+/// The parsed `cues.toml`. Cues for the same meter are tried in file order
+/// and the first match plays.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CueTable {
+    cues: Vec<CueSpec>,
+    default_cooldown: Duration,
+}
+
+impl CueTable {
+    /// Loads `path`, checking meter keys against the shipped manifest.
+    pub fn load(path: &Path) -> Result<Self, CueTableError> {
+        let raw = fs::read_to_string(path)?;
+        let meters = meter_registry().map_err(|err| CueTableError::Manifest(err.to_string()))?;
+        Self::parse(&raw, meters)
+    }
+
+    pub fn parse(raw: &str, meters: &MeterRegistry) -> Result<Self, CueTableError> {
+        let file: CuesFile = toml::from_str(raw)?;
+        for (index, cue) in file.cues.iter().enumerate() {
+            if cue.meter.is_empty() {
+                return Err(CueTableError::EmptyField {
+                    index,
+                    field: "meter",
+                });
+            }
+            if cue.sound.is_empty() {
+                return Err(CueTableError::EmptyField {
+                    index,
+                    field: "sound",
+                });
+            }
+            if meters.policy(&cue.meter).is_none() {
+                return Err(CueTableError::UnknownMeter {
+                    index,
+                    meter: cue.meter.clone(),
+                });
+            }
+            let low = cue.min.unwrap_or(i32::MIN);
+            let high = cue.max.unwrap_or(i32::MAX);
+            let empty = low > high || cue.equals.is_some_and(|v| v < low || v > high);
+            if empty {
+                return Err(CueTableError::EmptyRange {
+                    index,
+                    meter: cue.meter.clone(),
+                });
+            }
+        }
+        Ok(Self {
+            cues: file.cues,
+            default_cooldown: Duration::from_millis(u64::from(file.default_cooldown_ms)),
+        })
+    }
+
+    pub fn cues(&self) -> &[CueSpec] {
+        &self.cues
+    }
+
+    /// The first cue for `meter` whose predicates accept `value`.
+    pub fn cue_for(&self, meter: &str, value: i32) -> Option<&CueSpec> {
+        self.cues
+            .iter()
+            .find(|cue| cue.meter == meter && cue.matches(value))
+    }
+
+    fn cooldown(&self, cue: &CueSpec) -> Duration {
+        cue.cooldown_ms.map_or(self.default_cooldown, |ms| {
+            Duration::from_millis(u64::from(ms))
+        })
+    }
+}
+
+/// `assets/audio/cues.toml`, from the working directory or the workspace.
+pub fn cues_path() -> PathBuf {
+    crate::workspace_asset("assets/audio/cues.toml")
+}
+
+/// Turns meters into cue plays, at most one per meter key per cooldown.
+#[derive(Resource, Debug, Clone)]
+pub struct AudioCueRouter {
+    table: CueTable,
+    last_played: HashMap<String, Duration>,
+    /// Commands already routed from the front of the queue, and the first of
+    /// them, so a drained and refilled queue is noticed.
+    seen: usize,
+    first_seen: Option<Command>,
+}
+
+impl AudioCueRouter {
+    pub fn new(table: CueTable) -> Self {
+        Self {
+            table,
+            last_played: HashMap::new(),
+            seen: 0,
+            first_seen: None,
+        }
+    }
+
+    pub fn table(&self) -> &CueTable {
+        &self.table
+    }
+
+    /// Plays the cues `commands` trigger at wall-clock time `now`. A cue is
+    /// skipped while its meter key is still cooling down from the last play.
+    pub fn route(&mut self, commands: &[Command], now: Duration, backend: &mut dyn AudioBackend) {
+        for command in commands {
+            let CommandKind::Meter(meter) = &command.kind else {
+                continue;
+            };
+            let Some(cue) = self.table.cue_for(&meter.key, meter.value) else {
+                continue;
+            };
+            let cooldown = self.table.cooldown(cue);
+            if let Some(last) = self.last_played.get(meter.key.as_str()) {
+                if now.saturating_sub(*last) < cooldown {
+                    continue;
+                }
+            }
+            backend.play(&cue.sound);
+            self.last_played.insert(meter.key.to_string(), now);
+        }
+    }
+
+    /// Routes the commands queued since the last call. Meters the queue drops
+    /// under pressure before being seen never play.
+    pub fn route_queue(
+        &mut self,
+        queue: &CommandQueue,
+        now: Duration,
+        backend: &mut dyn AudioBackend,
+    ) {
+        let buf = &queue.buf;
+        if buf.len() < self.seen || buf.first() != self.first_seen.as_ref() {
+            self.seen = 0;
+        }
+        let fresh = &buf[self.seen..];
+        if !fresh.is_empty() {
+            self.route(fresh, now, backend);
+        }
+        self.seen = buf.len();
+        self.first_seen = buf.first().cloned();
+    }
+}
+
+/// Routes queued meters to backend `B`. Reads the queue only; the router
+/// and the backend are the only state it touches.
+pub fn route_audio_cues_to<B: AudioBackend + Resource>(
+    queue: Res<CommandQueue>,
+    time: Res<Time<Real>>,
+    mut router: ResMut<AudioCueRouter>,
+    mut backend: ResMut<B>,
+) {
+    let now = time.elapsed();
+    router.route_queue(&queue, now, backend.as_mut());
+}
+
+#[cfg(feature = "audio")]
+pub use kira::AudioCuePlugin;
+
+#[cfg(feature = "audio")]
+mod kira {
+    use bevy::prelude::*;
+    use bevy_kira_audio::prelude::{Audio, AudioControl};
+    use bevy_kira_audio::AudioPlugin;
+
+    use super::{cues_path, AudioBackend, AudioCueRouter, CueTable};
+    use crate::systems::command_queue::CommandQueue;
+
+    struct KiraBackend<'a> {
+        audio: &'a Audio,
+        assets: &'a AssetServer,
+    }
+
+    impl AudioBackend for KiraBackend<'_> {
+        fn play(&mut self, sound: &str) {
+            self.audio.play(self.assets.load(sound.to_owned()));
+        }
+    }
+
+    /// Plays `assets/audio/cues.toml` through Kira. Needs the asset plugin.
+    pub struct AudioCuePlugin;
+
+    impl Plugin for AudioCuePlugin {
+        fn build(&self, app: &mut App) {
+            let table = CueTable::load(&cues_path()).expect("failed to load audio cues");
+            app.add_plugins(AudioPlugin)
+                .insert_resource(AudioCueRouter::new(table))
+                .add_systems(Update, play_audio_cues);
+        }
+    }
+
+    fn play_audio_cues(
+        queue: Res<CommandQueue>,
+        time: Res<Time<Real>>,
+        mut router: ResMut<AudioCueRouter>,
+        audio: Res<Audio>,
+        assets: Res<AssetServer>,
+    ) {
+        let mut backend = KiraBackend {
+            audio: &audio,
+            assets: &assets,
+        };
+        router.route_queue(&queue, time.elapsed(), &mut backend);
+    }
+}
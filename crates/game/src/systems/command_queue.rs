@@ -1,24 +1,78 @@
 use bevy::prelude::Resource;
-use repro::{Command, CommandKind, MeterCommand, SpawnCommand};
+use repro::{Command, CommandKind, MeterCommand, MeterKey, SpawnCommand};
+
+/// Soft cap on buffered commands before meters start being dropped.
+pub const DEFAULT_MAX_BUFFERED: usize = 4096;
+
+/// Meter reporting how many meters the soft cap dropped since the last drain.
+const OVERFLOW_METER: &str = "command_queue_overflow";
 
 /// Buffer of deterministic commands emitted during gameplay. The queue is
 /// flushed when the record writer commits a new tick to disk.
-#[derive(Resource, Default)]
+///
+/// Past `max_buffered` the oldest meters are dropped to bound growth during
+/// a stall; spawns are always kept. Record and replay runs mark the queue
+/// authoritative, which disables dropping.
+#[derive(Resource)]
 pub struct CommandQueue {
     pub buf: Vec<Command>,
     current_tick: u32,
+    max_buffered: usize,
+    authoritative: bool,
+    overflow_reported: bool,
+}
+
+impl Default for CommandQueue {
+    fn default() -> Self {
+        Self {
+            buf: Vec::new(),
+            current_tick: 0,
+            max_buffered: DEFAULT_MAX_BUFFERED,
+            authoritative: false,
+            overflow_reported: false,
+        }
+    }
 }
 
 impl CommandQueue {
+    /// A queue that never drops commands, for recording paths.
+    pub fn authoritative() -> Self {
+        Self {
+            authoritative: true,
+            ..Self::default()
+        }
+    }
+
+    pub fn with_max_buffered(mut self, max_buffered: usize) -> Self {
+        self.max_buffered = max_buffered.max(1);
+        self
+    }
+
+    pub fn max_buffered(&self) -> usize {
+        self.max_buffered
+    }
+
+    /// Buffered commands as a fraction of `max_buffered`; above 1.0 once the
+    /// soft cap is exceeded.
+    #[allow(clippy::float_arithmetic)] // Reporting only; never feeds the simulation.
+    pub fn pressure(&self) -> f32 {
+        self.buf.len() as f32 / self.max_buffered as f32
+    }
+
     /// Set the active tick before running FixedUpdate systems.
     pub fn begin_tick(&mut self, tick: u32) {
         self.current_tick = tick;
     }
 
+    /// The tick commands are currently stamped with.
+    pub fn current_tick(&self) -> u32 {
+        self.current_tick
+    }
+
     /// Queue a spawn command. Positions are recorded in millimetres to avoid
     /// floating point drift in deterministic replays.
     pub fn spawn(&mut self, kind: &str, x_mm: i32, y_mm: i32, z_mm: i32) {
-        self.buf.push(Command {
+        self.push(Command {
             t: self.current_tick,
             kind: CommandKind::Spawn(SpawnCommand {
                 kind: kind.to_owned(),
@@ -29,12 +83,13 @@ impl CommandQueue {
         });
     }
 
-    /// Queue a metric update for downstream analytics.
-    pub fn meter(&mut self, key: &str, value: i32) {
-        self.buf.push(Command {
+    /// Queue a metric update for downstream analytics. Literal keys are
+    /// stored without allocating.
+    pub fn meter(&mut self, key: impl Into<MeterKey>, value: i32) {
+        self.push(Command {
             t: self.current_tick,
             kind: CommandKind::Meter(MeterCommand {
-                key: key.to_owned(),
+                key: key.into(),
                 value,
             }),
         });
@@ -42,13 +97,219 @@ impl CommandQueue {
 
     /// Convenience helper for recording unit counts without leaking u32 into
     /// the deterministic command stream format.
-    pub fn meter_units(&mut self, key: &str, units: u32) {
+    pub fn meter_units(&mut self, key: impl Into<MeterKey>, units: u32) {
         let clamped = units.min(i32::MAX as u32) as i32;
         self.meter(key, clamped);
     }
 
+    /// Records an i64 quantity such as a cent amount, saturating it into the
+    /// i32 meter range.
+    pub fn meter_wide(&mut self, key: impl Into<MeterKey>, value: i64) {
+        let clamped = value.clamp(i64::from(i32::MIN), i64::from(i32::MAX)) as i32;
+        self.meter(key, clamped);
+    }
+
     /// Drain the queue, returning all buffered commands.
     pub fn drain(&mut self) -> Vec<Command> {
+        self.overflow_reported = false;
         std::mem::take(&mut self.buf)
     }
+
+    fn push(&mut self, command: Command) {
+        self.buf.push(command);
+        if !self.authoritative && self.buf.len() > self.max_buffered {
+            self.relieve_pressure();
+        }
+    }
+
+    /// Drops the oldest meters until the buffer fits again, leaving room for
+    /// the overflow meter. One overflow meter is kept per drain: it is never
+    /// dropped itself, and later overflows add to its count in place.
+    fn relieve_pressure(&mut self) {
+        let reported = self.overflow_reported;
+        let room = usize::from(!reported);
+        let mut excess = (self.buf.len() + room).saturating_sub(self.max_buffered);
+        let mut dropped = 0u32;
+        self.buf.retain(|command| {
+            let CommandKind::Meter(meter) = &command.kind else {
+                return true;
+            };
+            if excess == 0 || (reported && meter.key.as_str() == OVERFLOW_METER) {
+                return true;
+            }
+            excess -= 1;
+            dropped += 1;
+            false
+        });
+        if dropped == 0 {
+            return;
+        }
+        let report = self
+            .buf
+            .iter_mut()
+            .find_map(|command| match &mut command.kind {
+                CommandKind::Meter(meter) if reported && meter.key.as_str() == OVERFLOW_METER => {
+                    Some(meter)
+                }
+                _ => None,
+            });
+        match report {
+            Some(meter) => {
+                meter.value = meter
+                    .value
+                    .saturating_add(dropped.min(i32::MAX as u32) as i32);
+            }
+            None => {
+                self.overflow_reported = true;
+                self.buf.push(Command {
+                    t: self.current_tick,
+                    kind: CommandKind::Meter(MeterCommand {
+                        key: MeterKey::from_static(OVERFLOW_METER),
+                        value: dropped.min(i32::MAX as u32) as i32,
+                    }),
+                });
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kinds(queue: &CommandQueue) -> Vec<String> {
+        queue
+            .buf
+            .iter()
+            .map(|command| match &command.kind {
+                CommandKind::Spawn(spawn) => format!("spawn:{}", spawn.kind),
+                CommandKind::Meter(meter) => format!("{}={}", meter.key, meter.value),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn overflow_drops_oldest_meters_and_keeps_spawns() {
+        let mut queue = CommandQueue::default().with_max_buffered(4);
+        queue.begin_tick(5);
+        let [a, b, c, d] = ["a", "b", "c", "d"];
+        queue.spawn("bandit", 0, 0, 0);
+        queue.meter(a, 1);
+        queue.meter(b, 2);
+        queue.spawn("scout", 0, 0, 0);
+        queue.meter(c, 3);
+        assert_eq!(
+            kinds(&queue),
+            vec![
+                "spawn:bandit",
+                "spawn:scout",
+                "c=3",
+                "command_queue_overflow=2"
+            ]
+        );
+
+        queue.meter(d, 4);
+        assert_eq!(
+            kinds(&queue),
+            vec![
+                "spawn:bandit",
+                "spawn:scout",
+                "command_queue_overflow=3",
+                "d=4"
+            ]
+        );
+
+        for idx in 0..6 {
+            queue.spawn(&format!("s{idx}"), 0, 0, 0);
+        }
+        let spawns = queue
+            .buf
+            .iter()
+            .filter(|command| matches!(command.kind, CommandKind::Spawn(_)))
+            .count();
+        assert_eq!(spawns, 8);
+        // Only the report survives among the meters, counting `d` too.
+        assert_eq!(queue.buf.len(), 9);
+        assert_eq!(kinds(&queue)[2], "command_queue_overflow=4");
+
+        queue.drain();
+        for key in ["e", "f", "g", "h", "i"] {
+            queue.meter(key, 0);
+        }
+        assert_eq!(
+            kinds(&queue),
+            vec!["g=0", "h=0", "i=0", "command_queue_overflow=2"]
+        );
+    }
+
+    #[test]
+    fn later_overflows_in_a_tick_update_the_report_in_place() {
+        let mut queue = CommandQueue::default().with_max_buffered(4);
+        queue.begin_tick(9);
+        queue.spawn("bandit", 0, 0, 0);
+        queue.spawn("scout", 0, 0, 0);
+        for key in ["a", "b", "c"] {
+            queue.meter(key, 1);
+        }
+        assert_eq!(
+            kinds(&queue),
+            vec![
+                "spawn:bandit",
+                "spawn:scout",
+                "c=1",
+                "command_queue_overflow=2"
+            ]
+        );
+
+        // The report is the oldest meter now, but the next drop takes `c`.
+        let [d, e] = ["d", "e"];
+        queue.meter(d, 1);
+        queue.meter(e, 1);
+        assert_eq!(
+            kinds(&queue),
+            vec![
+                "spawn:bandit",
+                "spawn:scout",
+                "command_queue_overflow=4",
+                "e=1"
+            ]
+        );
+
+        let drained = queue.drain();
+        let reports: Vec<_> = drained
+            .iter()
+            .filter(|command| {
+                matches!(&command.kind, CommandKind::Meter(meter) if meter.key.as_str() == OVERFLOW_METER)
+            })
+            .collect();
+        assert_eq!(reports.len(), 1);
+        assert_eq!(reports[0].t, 9);
+    }
+
+    #[test]
+    fn authoritative_queue_never_drops() {
+        let mut queue = CommandQueue::authoritative().with_max_buffered(2);
+        let key = "a";
+        for value in 0..10 {
+            queue.meter(key, value);
+        }
+        assert_eq!(queue.buf.len(), 10);
+        assert_eq!(queue.pressure(), 5.0);
+    }
+
+    #[test]
+    fn pressure_tracks_fill_level() {
+        let mut queue = CommandQueue::default().with_max_buffered(8);
+        assert_eq!(queue.pressure(), 0.0);
+        for key in ["a", "b"] {
+            queue.meter(key, 1);
+        }
+        assert_eq!(queue.pressure(), 0.25);
+        for _ in 0..6 {
+            queue.spawn("bandit", 0, 0, 0);
+        }
+        assert_eq!(queue.pressure(), 1.0);
+        queue.drain();
+        assert_eq!(queue.pressure(), 0.0);
+    }
 }
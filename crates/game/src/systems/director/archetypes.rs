@@ -0,0 +1,137 @@
+//! What each spawn kind is: hit points, speed and threat.
+//!
+//! Spawn commands only carry a kind string; the [`ArchetypeCatalog`] maps it
+//! to stats so combat, UI and analytics can reason about spawns. Every kind
+//! a spawn table can draw must have an archetype, so typos fail at load.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Context, Result};
+use bevy::prelude::Resource;
+use serde::Deserialize;
+
+use super::config::DirectorCfg;
+use super::spawn::DEFAULT_SPAWN_KIND;
+
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct Archetype {
+    pub hp: u32,
+    pub speed_mm_s: u32,
+    pub threat: u32,
+}
+
+#[derive(Resource, Debug, Default, Clone, PartialEq, Eq)]
+pub struct ArchetypeCatalog {
+    by_kind: HashMap<String, Archetype>,
+}
+
+impl ArchetypeCatalog {
+    pub fn from_toml(source: &str) -> Result<Self> {
+        let by_kind: HashMap<String, Archetype> = toml::from_str(source)?;
+        Ok(Self { by_kind })
+    }
+
+    pub fn load_from_path(path: &Path) -> Result<Self> {
+        let source = fs::read_to_string(path)
+            .with_context(|| format!("reading archetypes from {}", path.display()))?;
+        Self::from_toml(&source)
+            .with_context(|| format!("deserializing archetypes from {}", path.display()))
+    }
+
+    /// Stats for a spawn kind. Borrows the key, so lookups do not allocate.
+    pub fn get(&self, kind: &str) -> Option<&Archetype> {
+        self.by_kind.get(kind)
+    }
+
+    /// Threat of `kind`, zero when it has no archetype.
+    pub fn threat(&self, kind: &str) -> u32 {
+        self.get(kind).map_or(0, |archetype| archetype.threat)
+    }
+
+    /// Fails on the first spawn kind `cfg` can draw that has no archetype.
+    pub fn validate_spawn_kinds(&self, cfg: &DirectorCfg) -> Result<()> {
+        let mut kinds: Vec<&str> = vec![DEFAULT_SPAWN_KIND];
+        if let Some(types) = &cfg.types {
+            kinds.extend(types.keys().map(String::as_str));
+        }
+        if let Some(weather_types) = &cfg.weather_types {
+            for weights in weather_types.values() {
+                kinds.extend(weights.keys().map(String::as_str));
+            }
+        }
+        kinds.sort_unstable();
+        kinds.dedup();
+        let missing: Vec<&str> = kinds
+            .into_iter()
+            .filter(|kind| self.get(kind).is_none())
+            .collect();
+        if !missing.is_empty() {
+            bail!("spawn kinds without an archetype: {}", missing.join(", "));
+        }
+        Ok(())
+    }
+}
+
+pub fn archetypes_path() -> PathBuf {
+    let default = Path::new("assets/director/archetypes.toml");
+    if default.exists() {
+        return default.to_path_buf();
+    }
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("../../assets/director/archetypes.toml")
+}
+
+/// Loads the catalog and checks it covers every spawn kind in `cfg`.
+pub fn load_archetypes_for(cfg: &DirectorCfg) -> Result<ArchetypeCatalog> {
+    let path = archetypes_path();
+    let catalog = ArchetypeCatalog::load_from_path(&path)?;
+    catalog
+        .validate_spawn_kinds(cfg)
+        .with_context(|| format!("validating {}", path.display()))?;
+    Ok(catalog)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::systems::director::config::{load_director_cfg, SpawnWeight};
+    use crate::systems::director::director_cfg_path;
+
+    fn director_cfg() -> DirectorCfg {
+        load_director_cfg(director_cfg_path().to_str().expect("cfg path")).expect("director cfg")
+    }
+
+    #[test]
+    fn shipped_catalog_covers_shipped_spawn_tables() {
+        let catalog = load_archetypes_for(&director_cfg()).expect("archetypes");
+        let bandit = catalog.get("bandit").expect("bandit");
+        assert!(bandit.hp > 0);
+        assert_eq!(catalog.threat("bandit"), bandit.threat);
+        assert_eq!(catalog.threat("unknown"), 0);
+    }
+
+    #[test]
+    fn spawn_table_kind_without_archetype_fails() {
+        let mut cfg = director_cfg();
+        cfg.weather_types
+            .get_or_insert_with(HashMap::new)
+            .entry("Fog".to_string())
+            .or_default()
+            .insert("banditt".to_string(), SpawnWeight::Units(1000));
+        let catalog = ArchetypeCatalog::load_from_path(&archetypes_path()).expect("catalog");
+        let err = catalog.validate_spawn_kinds(&cfg).expect_err("typo fails");
+        assert!(err.to_string().contains("banditt"), "{err}");
+    }
+
+    #[test]
+    fn unknown_archetype_fields_are_rejected() {
+        let err = ArchetypeCatalog::from_toml(
+            "[bandit]\nhp = 1\nspeed_mm_s = 1\nthreat = 1\narmor = 2\n",
+        );
+        assert!(err.is_err());
+        let missing = ArchetypeCatalog::from_toml("[bandit]\nhp = 1\n");
+        assert!(missing.is_err());
+    }
+}
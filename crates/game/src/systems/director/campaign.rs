@@ -0,0 +1,49 @@
+//! Carry between consecutive legs of a campaign.
+//!
+//! A leg's director reads `prior_danger_score` and `basis_overlay_bp_total`
+//! from its [`LegContext`]; these helpers fill both from the leg before, so
+//! a campaign's danger and basis build on each other instead of resetting.
+
+use super::{DirectorState, LegContext};
+
+/// What one finished leg hands to the next.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CampaignState {
+    /// Legs finished so far.
+    pub leg_index: u32,
+    /// Danger the last leg ended on; `None` before the first leg.
+    pub carry_danger: Option<i32>,
+    pub carry_basis_overlay: i32,
+}
+
+impl CampaignState {
+    /// Folds a finished leg's director state into the carry.
+    pub fn finish_leg(&mut self, state: &DirectorState) {
+        self.leg_index = self.leg_index.saturating_add(1);
+        self.carry_danger = Some(state.current_danger_score);
+        self.carry_basis_overlay = state.basis_overlay_bp_total;
+    }
+
+    /// `base` with the carry applied. Before the first leg this is `base`
+    /// unchanged.
+    pub fn leg_context(&self, base: &LegContext) -> LegContext {
+        if self.leg_index == 0 {
+            return *base;
+        }
+        LegContext {
+            prior_danger_score: self.carry_danger,
+            basis_overlay_bp_total: self.carry_basis_overlay,
+            ..*base
+        }
+    }
+}
+
+/// Context for the leg after `prev`: `base` with its prior danger and basis
+/// overlay total seeded from where `prev` ended.
+pub fn next_leg_context(prev: &DirectorState, base: &LegContext) -> LegContext {
+    LegContext {
+        prior_danger_score: Some(prev.current_danger_score),
+        basis_overlay_bp_total: prev.basis_overlay_bp_total,
+        ..*base
+    }
+}
@@ -0,0 +1,151 @@
+use bevy::prelude::Resource;
+use serde::{Deserialize, Serialize};
+
+use crate::systems::economy::Weather;
+use crate::world::board_los::weather_los_m;
+
+use super::config::PhaseCfg;
+use super::spawn::SpawnBudget;
+
+/// Director ticks per mission minute; matches the leg target in `finalize_leg`.
+pub const TICKS_PER_MINUTE: u32 = 60;
+
+/// Time of day within a leg. Phases cycle Dawn → Day → Dusk → Night.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Phase {
+    Dawn = 0,
+    #[default]
+    Day = 1,
+    Dusk = 2,
+    Night = 3,
+}
+
+impl Phase {
+    const CYCLE: [Phase; 4] = [Phase::Dawn, Phase::Day, Phase::Dusk, Phase::Night];
+}
+
+/// Current phase of the running leg, recomputed from `leg_tick` every tick.
+#[derive(Resource, Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LegClock {
+    pub phase: Phase,
+}
+
+impl LegClock {
+    pub fn at_tick(leg_tick: u32, minutes_per_phase: u32) -> Self {
+        Self {
+            phase: phase_at_tick(leg_tick, minutes_per_phase),
+        }
+    }
+}
+
+/// Phase at `leg_tick`. Legs open at Dawn; with `minutes_per_phase` of zero
+/// the clock is disabled and every tick is Day.
+pub fn phase_at_tick(leg_tick: u32, minutes_per_phase: u32) -> Phase {
+    let ticks_per_phase = minutes_per_phase.saturating_mul(TICKS_PER_MINUTE);
+    if ticks_per_phase == 0 {
+        return Phase::Day;
+    }
+    Phase::CYCLE[((leg_tick / ticks_per_phase) % 4) as usize]
+}
+
+/// Sight range for `weather` scaled by the phase's LOS percent, rounded down.
+pub fn effective_los_m(weather: Weather, phase: Phase, cfg: &PhaseCfg) -> u32 {
+    let pct = match phase {
+        Phase::Dawn => cfg.los_pct.dawn,
+        Phase::Day => cfg.los_pct.day,
+        Phase::Dusk => cfg.los_pct.dusk,
+        Phase::Night => cfg.los_pct.night,
+    };
+    let scaled = u64::from(weather_los_m(weather)) * u64::from(pct) / 100;
+    u32::try_from(scaled).unwrap_or(u32::MAX)
+}
+
+/// Applies the opt-in night aggression bonus. Budgets pass through untouched
+/// unless `night_aggression` is set and the phase is Night; the bonus rounds
+/// down and is capped at `clamp_max`.
+pub fn apply_night_bonus(
+    budget: SpawnBudget,
+    phase: Phase,
+    cfg: &PhaseCfg,
+    clamp_max: u32,
+) -> SpawnBudget {
+    if !cfg.night_aggression || phase != Phase::Night {
+        return budget;
+    }
+    let bonus = u64::from(budget.enemies) * u64::from(cfg.night_enemy_bonus_pct) / 100;
+    let enemies = (u64::from(budget.enemies) + bonus).min(u64::from(clamp_max.max(budget.enemies)));
+    SpawnBudget {
+        enemies: enemies as u32,
+        ..budget
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::systems::director::config::PhaseLosCfg;
+
+    fn cfg() -> PhaseCfg {
+        PhaseCfg {
+            minutes_per_phase: 2,
+            los_pct: PhaseLosCfg {
+                dawn: 75,
+                day: 100,
+                dusk: 66,
+                night: 33,
+            },
+            night_aggression: false,
+            night_enemy_bonus_pct: 50,
+        }
+    }
+
+    #[test]
+    fn phase_boundaries_land_on_exact_ticks() {
+        assert_eq!(phase_at_tick(0, 2), Phase::Dawn);
+        assert_eq!(phase_at_tick(119, 2), Phase::Dawn);
+        assert_eq!(phase_at_tick(120, 2), Phase::Day);
+        assert_eq!(phase_at_tick(239, 2), Phase::Day);
+        assert_eq!(phase_at_tick(240, 2), Phase::Dusk);
+        assert_eq!(phase_at_tick(360, 2), Phase::Night);
+        assert_eq!(phase_at_tick(479, 2), Phase::Night);
+        assert_eq!(phase_at_tick(480, 2), Phase::Dawn);
+        assert_eq!(phase_at_tick(u32::MAX, 0), Phase::Day);
+        assert_eq!(LegClock::at_tick(130, 2).phase, Phase::Day);
+    }
+
+    #[test]
+    fn los_scaling_is_integer_exact() {
+        let cfg = cfg();
+        // Clear is 60 m, Fog 20 m.
+        assert_eq!(effective_los_m(Weather::Clear, Phase::Day, &cfg), 60);
+        assert_eq!(effective_los_m(Weather::Clear, Phase::Dawn, &cfg), 45);
+        assert_eq!(effective_los_m(Weather::Clear, Phase::Dusk, &cfg), 39);
+        assert_eq!(effective_los_m(Weather::Fog, Phase::Night, &cfg), 6);
+        assert_eq!(
+            effective_los_m(Weather::Rains, Phase::Night, &PhaseCfg::default()),
+            36
+        );
+    }
+
+    #[test]
+    fn night_bonus_is_opt_in_and_night_only() {
+        let budget = SpawnBudget::new(9, 2);
+        let mut cfg = cfg();
+        for phase in Phase::CYCLE {
+            assert_eq!(apply_night_bonus(budget, phase, &cfg, 40), budget);
+        }
+
+        cfg.night_aggression = true;
+        for phase in [Phase::Dawn, Phase::Day, Phase::Dusk] {
+            assert_eq!(apply_night_bonus(budget, phase, &cfg, 40), budget);
+        }
+        assert_eq!(
+            apply_night_bonus(budget, Phase::Night, &cfg, 40),
+            SpawnBudget::new(13, 2)
+        );
+        assert_eq!(
+            apply_night_bonus(budget, Phase::Night, &cfg, 10),
+            SpawnBudget::new(10, 2)
+        );
+    }
+}
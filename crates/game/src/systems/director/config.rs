@@ -3,7 +3,10 @@ use std::fs;
 use std::path::Path;
 
 use anyhow::Context;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
+
+use crate::systems::economy::Weather;
+use crate::systems::gameplay::rewards::RewardsCfg;
 
 #[derive(Debug, Deserialize, Clone)]
 #[serde(deny_unknown_fields)]
@@ -11,9 +14,165 @@ pub struct DirectorCfg {
     pub spawn: SpawnCfg,
     pub missions: HashMap<String, MissionCfg>,
     #[serde(default)]
-    pub types: Option<HashMap<String, f32>>,
+    pub types: Option<HashMap<String, SpawnWeight>>,
+    #[serde(default)]
+    pub weather_types: Option<HashMap<String, HashMap<String, SpawnWeight>>>,
+    /// Pity limits keyed by spawn type name; types without an entry are
+    /// drawn from the tables as usual.
+    #[serde(default)]
+    pub type_pity: HashMap<String, TypePityCfg>,
+    #[serde(default)]
+    pub travel: TravelCfg,
+    #[serde(default)]
+    pub closures: ClosureCfg,
+    #[serde(default)]
+    pub phases: PhaseCfg,
+    /// Leg completion rewards; empty unless configured.
+    #[serde(default)]
+    pub rewards: RewardsCfg,
+    /// Spawn easing or tightening after a run of failed or successful legs;
+    /// off unless configured.
     #[serde(default)]
-    pub weather_types: Option<HashMap<String, HashMap<String, f32>>>,
+    pub streak: StreakCfg,
+}
+
+/// Relative weight of a spawn type in `[types]` or `[weather_types.*]`.
+///
+/// Integer weights are used as given. Float weights are the deprecated form:
+/// they are scaled by 1000 and rounded, so `1.0` matches `1000`, and
+/// deterministic builds refuse to load them.
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq)]
+#[serde(untagged)]
+pub enum SpawnWeight {
+    Units(u32),
+    Legacy(f32),
+}
+
+impl SpawnWeight {
+    /// The integer weight the spawn tables draw with.
+    #[allow(clippy::float_arithmetic)]
+    pub fn units(self) -> u32 {
+        match self {
+            Self::Units(units) => units,
+            Self::Legacy(weight) if weight > 0.0 => (weight * 1000.0).round() as u32,
+            Self::Legacy(_) => 0,
+        }
+    }
+}
+
+/// Keeps a spawn type from clumping or vanishing for a whole leg. See
+/// [`super::spawn::choose_spawn_type_with_pity`].
+#[derive(Debug, Default, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct TypePityCfg {
+    /// Spawns after a pick during which table draws of this type are
+    /// redrawn; zero allows back-to-back picks.
+    #[serde(default)]
+    pub min_interval: u32,
+    /// Longest run of spawns without this type; the spawn that would make
+    /// the run longer is forced to it. Zero disables the cap.
+    #[serde(default)]
+    pub max_absence: u32,
+}
+
+/// Converts route distance into leg length for `start_leg_for_route`.
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct TravelCfg {
+    /// Distance covered per mission minute; zero keeps the default length.
+    pub km_per_minute: u32,
+    pub min_minutes: u32,
+    pub max_minutes: u32,
+}
+
+impl Default for TravelCfg {
+    fn default() -> Self {
+        Self {
+            km_per_minute: 0,
+            min_minutes: 1,
+            max_minutes: 60,
+        }
+    }
+}
+
+/// Closes a route after a leg whose danger rose sharply.
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct ClosureCfg {
+    /// Leg danger increase above which the traversed route closes.
+    pub danger_delta_threshold: i32,
+    /// Days the route stays closed; zero disables closures.
+    pub closed_days: u32,
+}
+
+impl Default for ClosureCfg {
+    fn default() -> Self {
+        Self {
+            danger_delta_threshold: i32::MAX,
+            closed_days: 0,
+        }
+    }
+}
+
+/// Director memory across legs. See [`super::history`].
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct StreakCfg {
+    /// Completed legs remembered in the mission history.
+    pub history_legs: u32,
+    /// Legs in a row with the same verdict before the modifier applies;
+    /// zero disables it.
+    pub streak_legs: u32,
+    /// Budget reduction after a failure streak, in basis points.
+    pub ease_bp: u32,
+    /// Budget increase after a success streak, in basis points.
+    pub tighten_bp: u32,
+}
+
+impl Default for StreakCfg {
+    fn default() -> Self {
+        Self {
+            history_legs: 8,
+            streak_legs: 3,
+            ease_bp: 0,
+            tighten_bp: 0,
+        }
+    }
+}
+
+/// Time-of-day phases within a leg. See [`super::clock`].
+#[derive(Debug, Default, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct PhaseCfg {
+    /// Length of each Dawn/Day/Dusk/Night phase; zero pins the leg to Day.
+    pub minutes_per_phase: u32,
+    pub los_pct: PhaseLosCfg,
+    /// Opt-in: adds `night_enemy_bonus_pct` to the spawn budget at Night.
+    #[serde(default)]
+    pub night_aggression: bool,
+    #[serde(default)]
+    pub night_enemy_bonus_pct: u32,
+}
+
+/// Sight range multiplier per phase, in integer percent of the weather range.
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct PhaseLosCfg {
+    pub dawn: u32,
+    pub day: u32,
+    pub dusk: u32,
+    pub night: u32,
+}
+
+impl Default for PhaseLosCfg {
+    fn default() -> Self {
+        Self {
+            dawn: 100,
+            day: 100,
+            dusk: 100,
+            night: 100,
+        }
+    }
 }
 
 #[derive(Debug, Deserialize, Clone)]
@@ -25,9 +184,72 @@ pub struct SpawnCfg {
     pub growth_cap_per_leg: u32,
     pub clamp_min: u32,
     pub clamp_max: u32,
+    #[serde(default)]
+    pub growth_curve: GrowthCurve,
+    /// Cull live enemies when a budget drops below the spawned count.
+    /// Off keeps the spawned count ratcheting upward.
+    #[serde(default)]
+    pub allow_cull: bool,
+    /// `[min, max]` overrides of `clamp_min`/`clamp_max`, keyed by weather
+    /// like `beta_weather`.
+    #[serde(default)]
+    pub weather_clamp: HashMap<String, (u32, u32)>,
 }
 
-#[derive(Debug, Deserialize, Clone, Default)]
+impl SpawnCfg {
+    /// Enemy bounds for `weather`: its override when configured, otherwise
+    /// the global clamp. A max below the min is raised to it.
+    pub fn clamp_for(&self, weather: Weather) -> (u32, u32) {
+        let (min, max) = self
+            .weather_clamp
+            .get(&format!("{weather:?}"))
+            .copied()
+            .unwrap_or((self.clamp_min, self.clamp_max));
+        (min, max.max(min))
+    }
+}
+
+/// Shapes how much of the gap between the prior and desired enemy count a
+/// leg may close, before the final clamp.
+#[derive(Debug, Default, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(tag = "kind", rename_all = "snake_case", deny_unknown_fields)]
+pub enum GrowthCurve {
+    /// At most `growth_cap_per_leg` per leg.
+    #[default]
+    Linear,
+    /// Gaps wider than `threshold` may close by up to `boosted_cap`; narrower
+    /// ones fall back to `growth_cap_per_leg`.
+    StepUp { threshold: u32, boosted_cap: u32 },
+    /// Closes `pct` percent of the gap, rounded up, ignoring
+    /// `growth_cap_per_leg`. Large gaps close fast and small ones taper.
+    Geometric { pct: u32 },
+}
+
+impl GrowthCurve {
+    /// Enemies added this leg for a gap of `increase`.
+    pub fn delta(self, increase: u32, growth_cap_per_leg: u32) -> u32 {
+        match self {
+            GrowthCurve::Linear => increase.min(growth_cap_per_leg),
+            GrowthCurve::StepUp {
+                threshold,
+                boosted_cap,
+            } => {
+                let cap = if increase > threshold {
+                    boosted_cap
+                } else {
+                    growth_cap_per_leg
+                };
+                increase.min(cap)
+            }
+            GrowthCurve::Geometric { pct } => {
+                let scaled = (u64::from(increase) * u64::from(pct.min(100))).div_ceil(100);
+                scaled as u32
+            }
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
 #[serde(deny_unknown_fields)]
 pub struct MissionCfg {
     #[serde(default)]
@@ -47,5 +269,59 @@ pub fn load_director_cfg(path: &str) -> anyhow::Result<DirectorCfg> {
         .with_context(|| format!("config {path} was not valid UTF-8"))?;
     let cfg: DirectorCfg = toml::from_str(cfg_str)
         .with_context(|| format!("deserializing director config from {path}"))?;
+    cfg.check_spawn_weights()
+        .with_context(|| format!("validating director config {path}"))?;
     Ok(cfg)
 }
+
+impl DirectorCfg {
+    /// Every spawn table with its config path, fallback first.
+    fn spawn_weight_tables(&self) -> Vec<(String, &HashMap<String, SpawnWeight>)> {
+        let mut tables: Vec<_> = self
+            .types
+            .iter()
+            .map(|weights| ("types".to_owned(), weights))
+            .collect();
+        if let Some(weather_types) = &self.weather_types {
+            let mut by_weather: Vec<_> = weather_types.iter().collect();
+            by_weather.sort_by_key(|(weather, _)| *weather);
+            tables.extend(
+                by_weather
+                    .into_iter()
+                    .map(|(weather, weights)| (format!("weather_types.{weather}"), weights)),
+            );
+        }
+        tables
+    }
+
+    /// Each spawn table's weights must total at most `u32::MAX`, and
+    /// deterministic builds reject the deprecated float weights.
+    fn check_spawn_weights(&self) -> anyhow::Result<()> {
+        for (table, weights) in self.spawn_weight_tables() {
+            let total: u64 = weights
+                .values()
+                .map(|weight| u64::from(weight.units()))
+                .sum();
+            anyhow::ensure!(
+                total <= u64::from(u32::MAX),
+                "spawn weights in `{table}` total {total}, more than {}",
+                u32::MAX
+            );
+            #[cfg(feature = "deterministic")]
+            {
+                let mut floats: Vec<_> = weights
+                    .iter()
+                    .filter(|(_, weight)| matches!(weight, SpawnWeight::Legacy(_)))
+                    .map(|(name, _)| name.as_str())
+                    .collect();
+                floats.sort_unstable();
+                if let Some(name) = floats.first() {
+                    anyhow::bail!(
+                        "spawn weight `{table}.{name}` is a float; deterministic builds need integer weights (1.0 = 1000)"
+                    );
+                }
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,138 @@
+//! Validated construction of a [`LegContext`].
+//!
+//! The CLI and record paths both go through [`LegContextBuilder`], so a leg
+//! cannot start with a zero-length mission or a rating outside the 0–100
+//! scale the director's spawn and pity tables are tuned for.
+
+use thiserror::Error;
+
+use super::LegContext;
+use crate::systems::economy::{Pp, RouteId, Weather};
+
+/// Highest player rating the director understands; larger values are
+/// clamped to it.
+pub const MAX_PLAYER_RATING: u8 = 100;
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum ContextError {
+    #[error("mission_minutes must be at least 1; a zero-minute leg never completes")]
+    ZeroMissionMinutes,
+}
+
+/// Builds a [`LegContext`]. Unset fields keep [`LegContext::default`], which
+/// leaves `mission_minutes` at zero, so forgetting it fails [`build`].
+///
+/// [`build`]: LegContextBuilder::build
+#[derive(Debug, Clone, Default)]
+pub struct LegContextBuilder {
+    context: LegContext,
+}
+
+impl LegContext {
+    pub fn builder() -> LegContextBuilder {
+        LegContextBuilder::default()
+    }
+}
+
+impl LegContextBuilder {
+    pub fn world_seed(mut self, world_seed: u64) -> Self {
+        self.context.world_seed = world_seed;
+        self
+    }
+
+    pub fn link_id(mut self, link_id: RouteId) -> Self {
+        self.context.link_id = link_id;
+        self
+    }
+
+    pub fn day(mut self, day: u32) -> Self {
+        self.context.day = day;
+        self
+    }
+
+    pub fn weather(mut self, weather: Weather) -> Self {
+        self.context.weather = weather;
+        self
+    }
+
+    pub fn pp(mut self, pp: Pp) -> Self {
+        self.context.pp = pp;
+        self
+    }
+
+    pub fn density_per_10k(mut self, density_per_10k: u32) -> Self {
+        self.context.density_per_10k = density_per_10k;
+        self
+    }
+
+    pub fn cadence_per_min(mut self, cadence_per_min: u32) -> Self {
+        self.context.cadence_per_min = cadence_per_min;
+        self
+    }
+
+    /// Rejected by [`build`](Self::build) when zero.
+    pub fn mission_minutes(mut self, mission_minutes: u32) -> Self {
+        self.context.mission_minutes = mission_minutes;
+        self
+    }
+
+    /// Clamped to [`MAX_PLAYER_RATING`].
+    pub fn player_rating(mut self, player_rating: u8) -> Self {
+        self.context.player_rating = player_rating.min(MAX_PLAYER_RATING);
+        self
+    }
+
+    pub fn multiplayer(mut self, multiplayer: bool) -> Self {
+        self.context.multiplayer = multiplayer;
+        self
+    }
+
+    pub fn prior_danger_score(mut self, prior_danger_score: Option<i32>) -> Self {
+        self.context.prior_danger_score = prior_danger_score;
+        self
+    }
+
+    pub fn basis_overlay_bp_total(mut self, basis_overlay_bp_total: i32) -> Self {
+        self.context.basis_overlay_bp_total = basis_overlay_bp_total;
+        self
+    }
+
+    pub fn build(self) -> Result<LegContext, ContextError> {
+        if self.context.mission_minutes == 0 {
+            return Err(ContextError::ZeroMissionMinutes);
+        }
+        Ok(self.context)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn out_of_range_rating_is_clamped() {
+        let context = LegContext::builder()
+            .mission_minutes(8)
+            .player_rating(230)
+            .build()
+            .expect("context");
+        assert_eq!(context.player_rating, MAX_PLAYER_RATING);
+
+        let context = LegContext::builder()
+            .mission_minutes(8)
+            .player_rating(62)
+            .build()
+            .expect("context");
+        assert_eq!(context.player_rating, 62);
+    }
+
+    #[test]
+    fn zero_mission_minutes_is_rejected() {
+        let err = LegContext::builder()
+            .player_rating(50)
+            .build()
+            .expect_err("zero minutes");
+        assert_eq!(err, ContextError::ZeroMissionMinutes);
+        assert!(err.to_string().contains("mission_minutes"));
+    }
+}
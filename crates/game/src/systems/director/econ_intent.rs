@@ -1,7 +1,8 @@
 use bevy::prelude::Resource;
+use serde::{Deserialize, Serialize};
 
 /// Accumulates pending economic deltas to be applied after a mission resolves.
-#[derive(Resource, Default, Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Resource, Default, Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub struct EconIntent {
     pub pending_pp_delta: i16,
     pub pending_basis_overlay_bp: i16,
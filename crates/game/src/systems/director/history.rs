@@ -0,0 +1,285 @@
+//! Director memory across legs: how the missions of the last few legs ended,
+//! and the spawn adjustment a run of failed or successful legs earns.
+
+use std::collections::VecDeque;
+
+use bevy::prelude::Resource;
+use serde::{Deserialize, Serialize};
+
+use super::config::{DirectorCfg, StreakCfg};
+use super::missions::MissionOutcomes;
+use super::Outcome;
+
+/// Legs remembered before `[streak] history_legs` is applied.
+pub const DEFAULT_HISTORY_LEGS: usize = 8;
+/// Largest easing or tightening, as a share of the budget in basis points.
+pub const MAX_STREAK_BP: i32 = 10_000;
+
+/// How one mission ended on a completed leg.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MissionHistoryEntry {
+    pub name: String,
+    pub outcome: Outcome,
+}
+
+/// Resolved mission outcomes of the most recent legs, oldest first. Older
+/// legs fall off once `capacity` legs are held.
+#[derive(Resource, Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MissionHistory {
+    capacity: usize,
+    legs: VecDeque<Vec<MissionHistoryEntry>>,
+}
+
+impl Default for MissionHistory {
+    fn default() -> Self {
+        Self::with_capacity(DEFAULT_HISTORY_LEGS)
+    }
+}
+
+impl MissionHistory {
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            capacity,
+            legs: VecDeque::with_capacity(capacity),
+        }
+    }
+
+    /// Rebuilds a history from saved legs, oldest first, keeping the newest
+    /// `capacity` of them.
+    pub fn from_legs(
+        capacity: usize,
+        legs: impl IntoIterator<Item = Vec<MissionHistoryEntry>>,
+    ) -> Self {
+        let mut history = Self::with_capacity(capacity);
+        for leg in legs {
+            history.push(leg);
+        }
+        history
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Changes how many legs are kept, dropping the oldest beyond it.
+    pub fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity;
+        self.trim();
+    }
+
+    pub fn len(&self) -> usize {
+        self.legs.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.legs.is_empty()
+    }
+
+    /// Remembered legs, oldest first.
+    pub fn legs(&self) -> impl DoubleEndedIterator<Item = &[MissionHistoryEntry]> {
+        self.legs.iter().map(Vec::as_slice)
+    }
+
+    /// Records a completed leg's resolved missions; unresolved ones are left
+    /// out.
+    pub fn record_leg(&mut self, outcomes: &MissionOutcomes) {
+        let leg = outcomes
+            .rows()
+            .iter()
+            .filter_map(|row| {
+                row.outcome.map(|outcome| MissionHistoryEntry {
+                    name: row.name.clone(),
+                    outcome,
+                })
+            })
+            .collect();
+        self.push(leg);
+    }
+
+    fn push(&mut self, leg: Vec<MissionHistoryEntry>) {
+        self.legs.push_back(leg);
+        self.trim();
+    }
+
+    fn trim(&mut self) {
+        while self.legs.len() > self.capacity {
+            self.legs.pop_front();
+        }
+    }
+
+    /// The outcome the most recent legs share and how many legs in a row
+    /// share it. A leg fails when any mission failed and succeeds when at
+    /// least one succeeded and none failed; a leg with nothing resolved
+    /// breaks the streak.
+    pub fn streak(&self) -> Option<(Outcome, u32)> {
+        let mut verdicts = self.legs.iter().rev().map(|leg| leg_verdict(leg));
+        let kind = verdicts.next()??;
+        let run = 1 + verdicts
+            .take_while(|verdict| *verdict == Some(kind))
+            .count();
+        Some((kind, run as u32))
+    }
+}
+
+fn leg_verdict(leg: &[MissionHistoryEntry]) -> Option<Outcome> {
+    if leg.iter().any(|entry| entry.outcome == Outcome::Failure) {
+        Some(Outcome::Failure)
+    } else if leg.is_empty() {
+        None
+    } else {
+        Some(Outcome::Success)
+    }
+}
+
+/// Spawn budget adjustment in basis points earned by the current streak:
+/// negative eases after `streak_legs` failed legs in a row, positive
+/// tightens after as many successful ones. Zero without a `[streak]`
+/// section.
+pub fn streak_modifier(history: &MissionHistory, cfg: &DirectorCfg) -> i32 {
+    let StreakCfg {
+        streak_legs,
+        ease_bp,
+        tighten_bp,
+        ..
+    } = cfg.streak;
+    match history.streak() {
+        Some((kind, run)) if streak_legs > 0 && run >= streak_legs => match kind {
+            Outcome::Failure => -(ease_bp.min(MAX_STREAK_BP as u32) as i32),
+            Outcome::Success => tighten_bp.min(MAX_STREAK_BP as u32) as i32,
+        },
+        _ => 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::systems::director::config::load_director_cfg;
+    use crate::systems::director::director_cfg_path;
+    use crate::systems::director::spawn::compute_spawn_budget_with_streak;
+    use crate::systems::economy::{Pp, Weather};
+
+    fn leg(outcomes: &[Outcome]) -> Vec<MissionHistoryEntry> {
+        outcomes
+            .iter()
+            .enumerate()
+            .map(|(idx, outcome)| MissionHistoryEntry {
+                name: format!("mission_{idx}"),
+                outcome: *outcome,
+            })
+            .collect()
+    }
+
+    fn base_cfg() -> DirectorCfg {
+        let path = director_cfg_path();
+        load_director_cfg(path.to_str().expect("cfg path")).expect("director config")
+    }
+
+    fn streak_cfg() -> DirectorCfg {
+        let mut cfg = base_cfg();
+        cfg.streak = StreakCfg {
+            history_legs: 5,
+            streak_legs: 3,
+            ease_bp: 2_000,
+            tighten_bp: 1_000,
+        };
+        cfg
+    }
+
+    #[test]
+    fn three_failed_legs_ease_the_budget() {
+        let cfg = streak_cfg();
+        let failed = leg(&[Outcome::Failure, Outcome::Success]);
+        let mut history = MissionHistory::from_legs(5, [leg(&[Outcome::Success]), failed.clone()]);
+        history.push(failed.clone());
+        assert_eq!(streak_modifier(&history, &cfg), 0);
+        history.push(failed);
+        assert_eq!(history.streak(), Some((Outcome::Failure, 3)));
+        assert_eq!(streak_modifier(&history, &cfg), -2_000);
+
+        let plain = compute_spawn_budget_with_streak(Pp(2_000), Weather::Clear, None, &cfg, 0);
+        let eased = compute_spawn_budget_with_streak(Pp(2_000), Weather::Clear, None, &cfg, -2_000);
+        assert!(eased.enemies < plain.enemies, "{eased:?} vs {plain:?}");
+    }
+
+    #[test]
+    fn successful_streak_tightens() {
+        let cfg = streak_cfg();
+        let history = MissionHistory::from_legs(5, (0..4).map(|_| leg(&[Outcome::Success])));
+        assert_eq!(streak_modifier(&history, &cfg), 1_000);
+    }
+
+    #[test]
+    fn mixed_outcomes_leave_the_budget_alone() {
+        let cfg = streak_cfg();
+        let history = MissionHistory::from_legs(
+            5,
+            [
+                leg(&[Outcome::Failure]),
+                leg(&[Outcome::Success]),
+                leg(&[Outcome::Failure]),
+                leg(&[Outcome::Success]),
+                leg(&[Outcome::Failure]),
+            ],
+        );
+        assert_eq!(streak_modifier(&history, &cfg), 0);
+
+        // A leg that resolved nothing breaks a run.
+        let history = MissionHistory::from_legs(
+            5,
+            [
+                leg(&[Outcome::Failure]),
+                leg(&[Outcome::Failure]),
+                leg(&[]),
+                leg(&[Outcome::Failure]),
+            ],
+        );
+        assert_eq!(streak_modifier(&history, &cfg), 0);
+    }
+
+    #[test]
+    fn absent_section_is_zero() {
+        let cfg = base_cfg();
+        assert_eq!(cfg.streak, StreakCfg::default());
+        let history = MissionHistory::from_legs(8, (0..8).map(|_| leg(&[Outcome::Failure])));
+        assert_eq!(streak_modifier(&history, &cfg), 0);
+    }
+
+    #[test]
+    fn clamps_still_bound_the_budget() {
+        let mut cfg = streak_cfg();
+        cfg.streak.ease_bp = 50_000;
+        cfg.streak.tighten_bp = 50_000;
+        let (clamp_min, clamp_max) = cfg.spawn.clamp_for(Weather::Clear);
+        let failing = MissionHistory::from_legs(5, (0..3).map(|_| leg(&[Outcome::Failure])));
+        let winning = MissionHistory::from_legs(5, (0..3).map(|_| leg(&[Outcome::Success])));
+        assert_eq!(streak_modifier(&failing, &cfg), -MAX_STREAK_BP);
+        assert_eq!(streak_modifier(&winning, &cfg), MAX_STREAK_BP);
+        for pp in [0, 1_000, 5_000, 10_000] {
+            for history in [&failing, &winning] {
+                let modifier = streak_modifier(history, &cfg);
+                let budget =
+                    compute_spawn_budget_with_streak(Pp(pp), Weather::Clear, None, &cfg, modifier);
+                assert!(
+                    (clamp_min..=clamp_max).contains(&budget.enemies),
+                    "pp {pp} modifier {modifier}: {budget:?}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn oldest_legs_fall_off() {
+        let mut history = MissionHistory::with_capacity(2);
+        for outcome in [Outcome::Success, Outcome::Failure, Outcome::Failure] {
+            history.push(leg(&[outcome]));
+        }
+        assert_eq!(history.len(), 2);
+        assert_eq!(history.streak(), Some((Outcome::Failure, 2)));
+        history.set_capacity(1);
+        assert_eq!(history.len(), 1);
+        history.set_capacity(0);
+        assert!(history.is_empty());
+        assert_eq!(history.streak(), None);
+    }
+}
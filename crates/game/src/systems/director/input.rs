@@ -3,7 +3,7 @@ use bevy::prelude::*;
 
 use crate::systems::command_queue::CommandQueue;
 
-use super::pause_wheel::{PauseState, Stance, ToolSlot, WheelState};
+use super::pause_wheel::{PauseEvent, PauseState, Stance, ToolSlot, WheelEvent, WheelState};
 use super::LegContext;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -35,6 +35,7 @@ impl WheelInputQueue {
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn apply_wheel_inputs(
     mut wheel: ResMut<WheelState>,
     mut pause: ResMut<PauseState>,
@@ -42,6 +43,8 @@ pub fn apply_wheel_inputs(
     mut input_queue: ResMut<WheelInputQueue>,
     context: Option<Res<LegContext>>,
     keyboard: Option<Res<ButtonInput<KeyCode>>>,
+    mut wheel_events: MessageWriter<WheelEvent>,
+    mut pause_events: MessageWriter<PauseEvent>,
 ) {
     let allow_slowmo = context.as_ref().map(|c| !c.multiplayer).unwrap_or(true);
     let allow_hard_pause = allow_slowmo;
@@ -49,25 +52,25 @@ pub fn apply_wheel_inputs(
     for action in input_queue.take() {
         match action {
             WheelInputAction::SetStance(stance) => {
-                wheel.set_stance(&mut command_queue, stance);
+                wheel_events.write_batch(wheel.set_stance(&mut command_queue, stance));
             }
             WheelInputAction::SetTool(tool) => {
-                wheel.set_tool(&mut command_queue, tool);
+                wheel_events.write_batch(wheel.set_tool(&mut command_queue, tool));
             }
             WheelInputAction::SetOverwatch(enabled) => {
-                wheel.set_overwatch(&mut command_queue, enabled);
+                wheel_events.write_batch(wheel.set_overwatch(&mut command_queue, enabled));
             }
             WheelInputAction::SetMoveMode(enabled) => {
-                wheel.set_move_mode(&mut command_queue, enabled);
+                wheel_events.write_batch(wheel.set_move_mode(&mut command_queue, enabled));
             }
             WheelInputAction::SetSlowmo(enabled) => {
                 if allow_slowmo {
-                    wheel.set_slowmo(&mut command_queue, enabled);
+                    wheel_events.write_batch(wheel.set_slowmo(&mut command_queue, enabled));
                 }
             }
             WheelInputAction::SetHardPause(enabled) => {
                 if allow_hard_pause {
-                    pause.set_hard_pause(&mut command_queue, enabled);
+                    pause_events.write_batch(pause.set_hard_pause(&mut command_queue, enabled));
                 }
             }
         }
@@ -82,7 +85,7 @@ pub fn apply_wheel_inputs(
             None
         };
         if let Some(stance) = stance {
-            wheel.set_stance(&mut command_queue, stance);
+            wheel_events.write_batch(wheel.set_stance(&mut command_queue, stance));
         }
 
         let tool = if keys.pressed(KeyCode::Digit4) {
@@ -93,34 +96,34 @@ pub fn apply_wheel_inputs(
             None
         };
         if let Some(tool) = tool {
-            wheel.set_tool(&mut command_queue, tool);
+            wheel_events.write_batch(wheel.set_tool(&mut command_queue, tool));
         }
 
         if keys.pressed(KeyCode::KeyO) {
-            wheel.set_overwatch(&mut command_queue, true);
+            wheel_events.write_batch(wheel.set_overwatch(&mut command_queue, true));
         } else if keys.just_released(KeyCode::KeyO) {
-            wheel.set_overwatch(&mut command_queue, false);
+            wheel_events.write_batch(wheel.set_overwatch(&mut command_queue, false));
         }
 
         if keys.pressed(KeyCode::KeyM) {
-            wheel.set_move_mode(&mut command_queue, true);
+            wheel_events.write_batch(wheel.set_move_mode(&mut command_queue, true));
         } else if keys.just_released(KeyCode::KeyM) {
-            wheel.set_move_mode(&mut command_queue, false);
+            wheel_events.write_batch(wheel.set_move_mode(&mut command_queue, false));
         }
 
         if allow_slowmo {
             if keys.pressed(KeyCode::KeyL) {
-                wheel.set_slowmo(&mut command_queue, true);
+                wheel_events.write_batch(wheel.set_slowmo(&mut command_queue, true));
             } else if keys.just_released(KeyCode::KeyL) {
-                wheel.set_slowmo(&mut command_queue, false);
+                wheel_events.write_batch(wheel.set_slowmo(&mut command_queue, false));
             }
         }
 
         if allow_hard_pause {
             if keys.pressed(KeyCode::Space) {
-                pause.set_hard_pause(&mut command_queue, true);
+                pause_events.write_batch(pause.set_hard_pause(&mut command_queue, true));
             } else if keys.just_released(KeyCode::Space) {
-                pause.set_hard_pause(&mut command_queue, false);
+                pause_events.write_batch(pause.set_hard_pause(&mut command_queue, false));
             }
         }
     }
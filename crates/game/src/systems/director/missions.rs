@@ -1,11 +1,14 @@
 use bevy::prelude::Resource;
+use serde::{Deserialize, Serialize};
 
 use super::config::MissionCfg;
 use super::econ_intent::EconIntent;
-use super::rng::{hash_mission_name, mission_seed, DetRng};
+use super::rng::{hash_mission_name, DetRng};
+use super::Outcome;
 use crate::logs::m2;
 use crate::systems::command_queue::CommandQueue;
 use crate::systems::economy::RouteId;
+use crate::systems::rng_stream::RngStream;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum MissionResult {
@@ -19,9 +22,112 @@ pub enum MissionResult {
     },
 }
 
+/// Read-only view of how far a mission has come.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MissionProgress {
+    pub elapsed: u32,
+    /// Value of `elapsed` at which the mission resolves or times out.
+    pub target: u32,
+    /// `None` while the mission is still running.
+    pub outcome: Option<Outcome>,
+}
+
+/// How one catalog mission ended this leg.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MissionOutcome {
+    pub name: String,
+    /// `None` while the mission is unresolved.
+    pub outcome: Option<Outcome>,
+    pub pp_delta: i16,
+    pub basis_bp_overlay: i16,
+    pub resolve_tick: Option<u32>,
+}
+
+/// Mission results for the current leg, one row per catalog mission in
+/// catalog order.
+#[derive(Resource, Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MissionOutcomes {
+    rows: Vec<MissionOutcome>,
+}
+
+impl MissionOutcomes {
+    /// Unresolved rows for every mission in `cfgs`.
+    pub fn for_catalog(cfgs: &[(String, MissionCfg)]) -> Self {
+        Self {
+            rows: cfgs
+                .iter()
+                .map(|(name, _)| MissionOutcome {
+                    name: name.clone(),
+                    outcome: None,
+                    pp_delta: 0,
+                    basis_bp_overlay: 0,
+                    resolve_tick: None,
+                })
+                .collect(),
+        }
+    }
+
+    pub fn rows(&self) -> &[MissionOutcome] {
+        &self.rows
+    }
+
+    pub fn get(&self, name: &str) -> Option<&MissionOutcome> {
+        self.rows.iter().find(|row| row.name == name)
+    }
+
+    /// Fills in `name`'s row, appending one when the catalog lacked it.
+    pub fn record(&mut self, name: &str, result: MissionResult, tick: u32) {
+        let (outcome, pp_delta, basis_bp_overlay) = match result {
+            MissionResult::Success {
+                pp_delta,
+                basis_bp_overlay,
+            } => (Outcome::Success, pp_delta, basis_bp_overlay),
+            MissionResult::Fail {
+                pp_delta,
+                basis_bp_overlay,
+            } => (Outcome::Failure, pp_delta, basis_bp_overlay),
+        };
+        let row = MissionOutcome {
+            name: name.to_string(),
+            outcome: Some(outcome),
+            pp_delta,
+            basis_bp_overlay,
+            resolve_tick: Some(tick),
+        };
+        match self.rows.iter_mut().find(|existing| existing.name == name) {
+            Some(existing) => *existing = row,
+            None => self.rows.push(row),
+        }
+    }
+
+    /// The econ intents every resolved mission queued this leg. The live
+    /// [`EconIntent`] is drained each tick, so this is the leg-wide total.
+    pub fn intent(&self) -> EconIntent {
+        self.rows.iter().filter(|row| row.outcome.is_some()).fold(
+            EconIntent::default(),
+            |mut intent, row| {
+                intent.pending_pp_delta = intent.pending_pp_delta.saturating_add(row.pp_delta);
+                intent.pending_basis_overlay_bp = intent
+                    .pending_basis_overlay_bp
+                    .saturating_add(row.basis_bp_overlay);
+                intent
+            },
+        )
+    }
+}
+
 pub trait Mission {
     fn init(&mut self, seed: u64, cfg: &MissionCfg);
     fn tick(&mut self, dt_ticks: u32) -> Option<MissionResult>;
+    fn progress(&self) -> MissionProgress;
+}
+
+fn resolved(done: bool, success: bool) -> Option<Outcome> {
+    match (done, success) {
+        (false, _) => None,
+        (true, true) => Some(Outcome::Success),
+        (true, false) => Some(Outcome::Failure),
+    }
 }
 
 fn success_result(cfg: &MissionCfg) -> MissionResult {
@@ -38,7 +144,7 @@ fn fail_result(cfg: &MissionCfg) -> MissionResult {
     }
 }
 
-#[derive(Default)]
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
 pub struct RainFlagUplink {
     cfg: MissionCfg,
     resolve_at: u32,
@@ -73,9 +179,17 @@ impl Mission for RainFlagUplink {
             None
         }
     }
+
+    fn progress(&self) -> MissionProgress {
+        MissionProgress {
+            elapsed: self.elapsed,
+            target: self.resolve_at,
+            outcome: resolved(self.done, self.success),
+        }
+    }
 }
 
-#[derive(Default)]
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
 pub struct SourvaultEvac {
     cfg: MissionCfg,
     hazard_budget: u32,
@@ -110,9 +224,17 @@ impl Mission for SourvaultEvac {
             None
         }
     }
+
+    fn progress(&self) -> MissionProgress {
+        MissionProgress {
+            elapsed: self.elapsed,
+            target: self.hazard_budget,
+            outcome: resolved(self.done, self.success),
+        }
+    }
 }
 
-#[derive(Default)]
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
 pub struct BreakTheChain {
     cfg: MissionCfg,
     targets: u32,
@@ -141,9 +263,17 @@ impl Mission for BreakTheChain {
             None
         }
     }
+
+    fn progress(&self) -> MissionProgress {
+        MissionProgress {
+            elapsed: self.destroyed,
+            target: self.targets,
+            outcome: resolved(self.done, true),
+        }
+    }
 }
 
-#[derive(Default)]
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
 pub struct WayleaveDefault {
     cfg: MissionCfg,
     checkpoints: u32,
@@ -182,9 +312,17 @@ impl Mission for WayleaveDefault {
             None
         }
     }
+
+    fn progress(&self) -> MissionProgress {
+        MissionProgress {
+            elapsed: self.elapsed,
+            target: self.deadline,
+            outcome: resolved(self.done, self.reached >= self.checkpoints),
+        }
+    }
 }
 
-#[derive(Default)]
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
 pub struct AnchorAudit {
     cfg: MissionCfg,
     scan_ticks: u32,
@@ -219,9 +357,17 @@ impl Mission for AnchorAudit {
             None
         }
     }
+
+    fn progress(&self) -> MissionProgress {
+        MissionProgress {
+            elapsed: self.elapsed,
+            target: self.scan_ticks,
+            outcome: resolved(self.done, self.success),
+        }
+    }
 }
 
-#[derive(Resource, Default)]
+#[derive(Resource, Debug, Default, Clone, Serialize, Deserialize)]
 pub struct MissionRuntime {
     pub rain_flag: RainFlagUplink,
     pub sourvault: SourvaultEvac,
@@ -239,8 +385,7 @@ impl MissionRuntime {
         cfgs: &[(String, MissionCfg)],
     ) {
         for (name, cfg) in cfgs.iter() {
-            let mission_id = hash_mission_name(name);
-            let seed = mission_seed(world_seed, link_id, day, mission_id);
+            let seed = RngStream::mission_seed(world_seed, link_id, day, name);
             match name.as_str() {
                 "rain_flag" => self.rain_flag.init(seed, cfg),
                 "sourvault" => self.sourvault.init(seed, cfg),
@@ -252,12 +397,24 @@ impl MissionRuntime {
         }
     }
 
+    /// Progress for every mission, in the order `tick_all` resolves them.
+    pub fn progress(&self) -> [(&'static str, MissionProgress); 5] {
+        [
+            ("rain_flag", self.rain_flag.progress()),
+            ("sourvault", self.sourvault.progress()),
+            ("break_chain", self.break_chain.progress()),
+            ("wayleave", self.wayleave.progress()),
+            ("anchor_audit", self.anchor_audit.progress()),
+        ]
+    }
+
     pub fn tick_all(
         &mut self,
         current_tick: u32,
         dt_ticks: u32,
         queue: &mut CommandQueue,
         econ: &mut EconIntent,
+        outcomes: &mut MissionOutcomes,
     ) {
         let missions = [
             ("rain_flag", self.rain_flag.tick(dt_ticks)),
@@ -268,6 +425,7 @@ impl MissionRuntime {
         ];
         for (name, result) in missions {
             if let Some(outcome) = result {
+                outcomes.record(name, outcome, current_tick);
                 let mission_hash = hash_mission_name(name);
                 let mission_key = (mission_hash & 0x7FFF_FFFF) as i32;
                 let (pp_delta, basis_bp_overlay, success_flag) = match outcome {
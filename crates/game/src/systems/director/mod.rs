@@ -1,8 +1,15 @@
+pub mod archetypes;
+pub mod campaign;
+pub mod clock;
+pub mod context;
 mod econ_intent;
+pub mod history;
 pub mod input;
 pub mod missions;
 pub mod pause_wheel;
 pub mod spawn;
+pub mod sweep;
+pub mod travel;
 
 pub mod config;
 pub mod rng;
@@ -16,6 +23,8 @@ use bevy::ecs::schedule::ExecutorKind;
 use bevy::ecs::schedule::{Schedule, ScheduleLabel};
 use bevy::prelude::*;
 use bevy::time::Fixed;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::path::{Path, PathBuf};
 use std::time::Duration;
 
@@ -42,10 +51,20 @@ mod physics_stub {
 #[cfg(not(feature = "avian_physics"))]
 pub use physics_stub::{Physics, SubstepCount};
 
+use crate::app_state::AppState;
 use crate::logs::m2;
 use crate::scheduling::sets;
 use crate::systems::command_queue::CommandQueue;
-use crate::systems::economy::{Pp, RouteId, Weather};
+use crate::systems::economy::{accrue_leg_interest, EconomyDay, Pp, RouteId, Rulepack, Weather};
+use crate::systems::gameplay::rewards;
+use crate::systems::rng_stream::RngStream;
+use crate::systems::trading::ledger::{LedgerJournal, WalletAccess};
+use crate::world::board::Board;
+use crate::world::board_styles::{BoardStyles, LEGACY_STYLE};
+use crate::world::boardcheck::BoardDefect;
+use crate::world::boardgen::generate_board;
+use crate::world::closures::RouteClosures;
+use crate::world::index::StaticWorldIndex;
 
 #[derive(Resource, Debug, Clone, Copy, PartialEq, Eq)]
 pub enum PhysicsBackend {
@@ -53,25 +72,33 @@ pub enum PhysicsBackend {
     Grid,
 }
 
+pub use archetypes::{Archetype, ArchetypeCatalog};
+pub use clock::{effective_los_m, LegClock, Phase};
+pub use context::{ContextError, LegContextBuilder, MAX_PLAYER_RATING};
 pub use econ_intent::EconIntent;
+pub use history::{streak_modifier, MissionHistory, MissionHistoryEntry};
 pub use input::{apply_wheel_inputs, WheelInputAction, WheelInputQueue};
-pub use missions::{MissionResult, MissionRuntime};
-pub use pause_wheel::{PauseState, Stance, ToolSlot, WheelState};
+pub use missions::{
+    MissionOutcome, MissionOutcomes, MissionProgress, MissionResult, MissionRuntime,
+};
+pub use pause_wheel::{
+    PauseEvent, PauseState, Stance, ToolSlot, WheelChange, WheelEvent, WheelState,
+};
 pub use spawn::{
-    choose_spawn_type, compute_spawn_budget, danger_diff_sign, danger_score, SpawnBudget,
-    SpawnTypeTables,
+    apply_streak_modifier, choose_spawn_type, choose_spawn_type_with_pity, compute_spawn_budget,
+    compute_spawn_budget_with_streak, cull_spawns, danger_diff_sign, danger_score, SpawnBudget,
+    SpawnPick, SpawnTypeTables,
 };
 
 use self::config::load_director_cfg;
-use self::rng::{hash_mission_name, mission_seed};
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Outcome {
     Success,
     Failure,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum LegStatus {
     Loading,
     Running,
@@ -79,7 +106,7 @@ pub enum LegStatus {
     Completed(Outcome),
 }
 
-#[derive(Resource, Debug, Clone)]
+#[derive(Resource, Debug, Clone, Serialize, Deserialize)]
 pub struct DirectorState {
     pub world_seed: u64,
     pub day: u32,
@@ -89,6 +116,16 @@ pub struct DirectorState {
     pub weather: Weather,
     pub prior_danger_score: i32,
     pub current_danger_score: i32,
+    /// Danger carried into the leg, kept so the post-leg bridge can judge the
+    /// whole leg's danger change.
+    pub leg_start_danger_score: i32,
+    /// Basis overlay total including this leg's contribution once it has
+    /// finalized.
+    #[serde(default)]
+    pub basis_overlay_bp_total: i32,
+    /// Ticks the leg spent hard-paused.
+    #[serde(default)]
+    pub paused_ticks: u32,
 }
 
 impl Default for DirectorState {
@@ -102,6 +139,9 @@ impl Default for DirectorState {
             weather: Weather::default(),
             prior_danger_score: 0,
             current_danger_score: 0,
+            leg_start_danger_score: 0,
+            basis_overlay_bp_total: 0,
+            paused_ticks: 0,
         }
     }
 }
@@ -112,7 +152,7 @@ pub struct DirectorConfigResource(pub config::DirectorCfg);
 #[derive(Resource, Default, Clone)]
 pub struct MissionCatalog(pub Vec<(String, config::MissionCfg)>);
 
-#[derive(Resource, Default, Clone, Copy)]
+#[derive(Resource, Debug, Default, Clone, Copy, Serialize, Deserialize)]
 pub struct LegContext {
     pub world_seed: u64,
     pub link_id: RouteId,
@@ -128,18 +168,83 @@ pub struct LegContext {
     pub basis_overlay_bp_total: i32,
 }
 
-#[derive(Resource, Default, Clone, Copy)]
+/// Board for the current leg. When present, spawns are placed on its enemy
+/// spawn cells instead of along the legacy spawn line.
+#[derive(Resource, Clone, Debug)]
+pub struct LegBoard(pub Board);
+
+impl LegBoard {
+    /// The generated board for a leg, seeded from its world seed, link and
+    /// day. Legs carry no board style yet, so every leg uses `legacy`.
+    pub fn for_leg(context: &LegContext) -> Result<Self, Vec<BoardDefect>> {
+        let seed =
+            RngStream::mission_seed(context.world_seed, context.link_id, context.day, "board");
+        generate_board(seed, LEGACY_STYLE, BoardStyles::shipped()).map(Self)
+    }
+}
+
+#[derive(Resource, Default, Debug, Clone, Serialize, Deserialize)]
 pub struct SpawnMemory {
     pub prior_enemies: Option<u32>,
     pub last_budget: Option<SpawnBudget>,
+    /// Enemies in the last budget before the night bonus. Growth is judged
+    /// against this so the bonus does not compound or outlast the Night.
+    pub base_enemies: Option<u32>,
     pub pending_budget: Option<SpawnBudget>,
     pub spawn_seed: u64,
     pub spawn_counter: u64,
     pub last_spawned_enemies: u32,
+    /// Spawn indices still alive this leg, in spawn order.
+    pub active_spawns: Vec<u64>,
+    /// Spawn index each type with a `type_pity` entry last appeared at this
+    /// leg.
+    #[serde(default)]
+    pub type_last_seen: BTreeMap<String, u64>,
+}
+
+/// Scales fixed steps by `num / den` while slow-mo is on. The division
+/// remainder is carried between ticks, so `n` scaled steps of `base` add up
+/// to exactly `floor(n * base * num / den)` nanoseconds.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SlowmoAccumulator {
+    pub num: u32,
+    pub den: u32,
+    pub remainder_ns: u64,
+    /// Scaled time advanced under slow-mo so far.
+    pub elapsed_ns: u64,
+}
+
+impl Default for SlowmoAccumulator {
+    fn default() -> Self {
+        Self {
+            num: SLOWMO_NUMERATOR,
+            den: SLOWMO_DENOMINATOR,
+            remainder_ns: 0,
+            elapsed_ns: 0,
+        }
+    }
 }
 
-#[derive(Resource, Default, Clone, Copy)]
-struct PhysicsCadence {
+impl SlowmoAccumulator {
+    /// Returns the scaled length of one `base` step and carries the
+    /// remainder into the next call.
+    pub fn scale(&mut self, base: Duration) -> Duration {
+        let den = u128::from(self.den.max(1));
+        let total = base
+            .as_nanos()
+            .saturating_mul(u128::from(self.num))
+            .saturating_add(u128::from(self.remainder_ns));
+        self.remainder_ns = (total % den) as u64;
+        let scaled = total / den;
+        self.elapsed_ns = self
+            .elapsed_ns
+            .saturating_add(u64::try_from(scaled).unwrap_or(u64::MAX));
+        duration_from_nanos(scaled)
+    }
+}
+
+#[derive(Resource, Debug, Default, Clone, Copy, Serialize, Deserialize)]
+pub(crate) struct PhysicsCadence {
     base_timestep: Option<Duration>,
     accumulator: Duration,
 }
@@ -155,6 +260,8 @@ impl Plugin for DirectorPlugin {
         let cfg = load_director_cfg(cfg_path.to_str().expect("cfg path"))
             .unwrap_or_else(|_| panic!("director config missing: {}", cfg_path.display()));
         let spawn_tables = SpawnTypeTables::from_cfg(&cfg);
+        let archetypes = archetypes::load_archetypes_for(&cfg)
+            .unwrap_or_else(|err| panic!("archetype catalog invalid: {err:#}"));
 
         let mut missions: Vec<(String, config::MissionCfg)> = cfg
             .missions
@@ -191,26 +298,48 @@ impl Plugin for DirectorPlugin {
         app.insert_resource(DirectorConfigResource(cfg))
             .insert_resource(catalog)
             .insert_resource(spawn_tables)
+            .insert_resource(archetypes)
             .init_resource::<DirectorState>()
             .init_resource::<MissionRuntime>()
+            .init_resource::<MissionOutcomes>()
             .init_resource::<EconIntent>()
             .init_resource::<WheelState>()
             .init_resource::<PauseState>()
+            .add_message::<WheelEvent>()
+            .add_message::<PauseEvent>()
             .init_resource::<WheelInputQueue>()
             .init_resource::<SpawnMemory>()
             .init_resource::<LegContext>()
             .init_resource::<PhysicsCadence>()
-            .add_systems(Startup, setup_director)
+            .init_resource::<SlowmoAccumulator>()
+            .init_resource::<RouteClosures>()
+            .init_resource::<MissionHistory>()
+            .init_resource::<LegClock>()
+            .init_resource::<LedgerJournal>()
+            .add_systems(
+                Startup,
+                (
+                    seed_route_closures,
+                    seed_mission_history,
+                    setup_director,
+                    build_leg_board,
+                    reset_leg_clock,
+                )
+                    .chain(),
+            )
             .add_systems(
                 FixedUpdate,
                 (
                     apply_wheel_inputs.in_set(sets::DETTEROT_Input),
-                    sync_pause_state.in_set(sets::DETTEROT_Director),
-                    drive_director.in_set(sets::DETTEROT_Director),
+                    (sync_pause_state, advance_leg_clock, drive_director)
+                        .chain()
+                        .in_set(sets::DETTEROT_Director),
                     run_mission_runtime.in_set(sets::DETTEROT_Missions),
                     dispatch_spawns.in_set(sets::DETTEROT_Spawns),
                     physics_step.in_set(sets::DETTEROT_PhysicsStep),
-                    finalize_leg.in_set(sets::DETTEROT_Cleanup),
+                    (finalize_leg, mirror_route_closures, mirror_mission_history)
+                        .chain()
+                        .in_set(sets::DETTEROT_Cleanup),
                 ),
             );
     }
@@ -224,12 +353,58 @@ pub(crate) fn director_cfg_path() -> PathBuf {
     Path::new(env!("CARGO_MANIFEST_DIR")).join("../../assets/director/m2.toml")
 }
 
+/// Loads persisted closures from the save-backed [`AppState`] when present.
+fn seed_route_closures(app_state: Option<Res<AppState>>, mut closures: ResMut<RouteClosures>) {
+    if let Some(app_state) = app_state {
+        if !app_state.route_closures.is_empty() {
+            *closures = app_state.route_closures.clone();
+        }
+    }
+}
+
+/// Copies closure changes back into [`AppState`] so autosaves persist them
+/// and the next economy step sees the closed routes.
+fn mirror_route_closures(closures: Res<RouteClosures>, app_state: Option<ResMut<AppState>>) {
+    if let Some(mut app_state) = app_state {
+        if closures.is_changed() && app_state.route_closures != *closures {
+            let day = app_state.econ.day;
+            app_state.econ.closed_routes = closures.closed_counts_by_hub::<StaticWorldIndex>(day);
+            app_state.route_closures = closures.clone();
+        }
+    }
+}
+
+/// Loads the persisted mission history from [`AppState`] when present, kept
+/// to the configured number of legs.
+fn seed_mission_history(
+    app_state: Option<Res<AppState>>,
+    cfg: Res<DirectorConfigResource>,
+    mut history: ResMut<MissionHistory>,
+) {
+    if let Some(app_state) = app_state {
+        *history = app_state.mission_history.clone();
+    }
+    history.set_capacity(cfg.0.streak.history_legs as usize);
+}
+
+/// Copies the history back into [`AppState`] after a leg is recorded so
+/// autosaves persist it.
+fn mirror_mission_history(history: Res<MissionHistory>, app_state: Option<ResMut<AppState>>) {
+    if let Some(mut app_state) = app_state {
+        if history.is_changed() && app_state.mission_history != *history {
+            app_state.mission_history = history.clone();
+        }
+    }
+}
+
 fn setup_director(
     mut state: ResMut<DirectorState>,
     catalog: Res<MissionCatalog>,
     mut runtime: ResMut<MissionRuntime>,
+    mut outcomes: ResMut<MissionOutcomes>,
     mut memory: ResMut<SpawnMemory>,
     context: Res<LegContext>,
+    mut closures: ResMut<RouteClosures>,
 ) {
     state.status = LegStatus::Running;
     state.link_id = context.link_id;
@@ -237,10 +412,40 @@ fn setup_director(
     state.world_seed = context.world_seed;
     state.day = context.day;
     state.prior_danger_score = context.prior_danger_score.unwrap_or_default();
+    state.leg_start_danger_score = state.prior_danger_score;
+    state.basis_overlay_bp_total = context.basis_overlay_bp_total;
+    state.paused_ticks = 0;
+    closures.expire(EconomyDay(context.day));
     runtime.init_all(context.world_seed, context.link_id, context.day, &catalog.0);
-    let spawn_id = hash_mission_name("spawn_types");
-    memory.spawn_seed = mission_seed(context.world_seed, context.link_id, context.day, spawn_id);
+    *outcomes = MissionOutcomes::for_catalog(&catalog.0);
+    memory.spawn_seed = RngStream::mission_seed(
+        context.world_seed,
+        context.link_id,
+        context.day,
+        "spawn_types",
+    );
     memory.spawn_counter = 0;
+    memory.active_spawns.clear();
+    memory.type_last_seen.clear();
+}
+
+/// Generates the leg's board unless one was supplied up front. A seed that
+/// never validates leaves the board out, so spawns fall back to the line.
+fn build_leg_board(mut commands: Commands, context: Res<LegContext>, board: Option<Res<LegBoard>>) {
+    if board.is_some() {
+        return;
+    }
+    match LegBoard::for_leg(&context) {
+        Ok(board) => commands.insert_resource(board),
+        Err(defects) => warn!(
+            "no valid board for link {} on day {}: {defects:?}",
+            context.link_id.0, context.day
+        ),
+    }
+}
+
+fn reset_leg_clock(cfg: Res<DirectorConfigResource>, mut clock: ResMut<LegClock>) {
+    *clock = LegClock::at_tick(0, cfg.0.phases.minutes_per_phase);
 }
 
 fn sync_pause_state(mut state: ResMut<DirectorState>, pause: Res<PauseState>) {
@@ -256,6 +461,31 @@ fn sync_pause_state(mut state: ResMut<DirectorState>, pause: Res<PauseState>) {
     }
 }
 
+/// Moves the leg clock to the phase for the current tick and reports
+/// transitions along with the sight range they imply.
+fn advance_leg_clock(
+    state: Res<DirectorState>,
+    cfg: Res<DirectorConfigResource>,
+    mut clock: ResMut<LegClock>,
+    mut queue: ResMut<CommandQueue>,
+    pause: Res<PauseState>,
+) {
+    if !matches!(state.status, LegStatus::Running | LegStatus::Paused) || pause.hard_paused_sp {
+        return;
+    }
+    let phases = &cfg.0.phases;
+    let next = LegClock::at_tick(state.leg_tick, phases.minutes_per_phase);
+    if next != *clock {
+        *clock = next;
+        queue.meter("phase_changed", next.phase as i32);
+        queue.meter(
+            "los_m",
+            effective_los_m(state.weather, next.phase, phases) as i32,
+        );
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
 fn drive_director(
     mut state: ResMut<DirectorState>,
     cfg: Res<DirectorConfigResource>,
@@ -263,6 +493,8 @@ fn drive_director(
     context: Res<LegContext>,
     mut queue: ResMut<CommandQueue>,
     pause: Res<PauseState>,
+    clock: Res<LegClock>,
+    history: Res<MissionHistory>,
 ) {
     if !matches!(state.status, LegStatus::Running | LegStatus::Paused) {
         return;
@@ -270,9 +502,26 @@ fn drive_director(
     if pause.hard_paused_sp {
         return;
     }
+    let streak = streak_modifier(&history, &cfg.0);
+    // Only a configured streak section can make this non-zero, so legs
+    // without one record exactly what they did before.
+    if state.leg_tick == 0 && streak != 0 {
+        queue.meter("streak_modifier", streak);
+    }
 
     let previous_budget = memory.last_budget;
-    let budget = compute_spawn_budget(context.pp, state.weather, memory.prior_enemies, &cfg.0);
+    let prior = if cfg.0.phases.night_aggression {
+        memory.base_enemies.or(memory.prior_enemies)
+    } else {
+        memory.prior_enemies
+    };
+    let base = compute_spawn_budget(context.pp, state.weather, prior, &cfg.0);
+    // The unadjusted budget feeds the next tick's growth, so the streak
+    // adjustment never compounds.
+    memory.base_enemies = Some(base.enemies);
+    let adjusted = apply_streak_modifier(base, state.weather, &cfg.0, streak);
+    let budget =
+        clock::apply_night_bonus(adjusted, clock.phase, &cfg.0.phases, cfg.0.spawn.clamp_max);
     let spawn_changed = previous_budget.map(|b| b != budget).unwrap_or(true);
     if spawn_changed {
         memory.pending_budget = Some(budget);
@@ -307,6 +556,7 @@ fn drive_director(
 
 fn run_mission_runtime(
     mut runtime: ResMut<MissionRuntime>,
+    mut outcomes: ResMut<MissionOutcomes>,
     mut queue: ResMut<CommandQueue>,
     mut econ: ResMut<EconIntent>,
     state: Res<DirectorState>,
@@ -318,15 +568,25 @@ fn run_mission_runtime(
     if pause.hard_paused_sp {
         return;
     }
-    runtime.tick_all(state.leg_tick, 1, queue.as_mut(), econ.as_mut());
+    runtime.tick_all(
+        state.leg_tick,
+        1,
+        queue.as_mut(),
+        econ.as_mut(),
+        outcomes.as_mut(),
+    );
 }
 
+#[allow(clippy::too_many_arguments)]
 fn dispatch_spawns(
     mut memory: ResMut<SpawnMemory>,
     mut queue: ResMut<CommandQueue>,
     tables: Res<SpawnTypeTables>,
+    archetypes: Res<ArchetypeCatalog>,
+    cfg: Res<DirectorConfigResource>,
     state: Res<DirectorState>,
     pause: Res<PauseState>,
+    board: Option<Res<LegBoard>>,
 ) {
     if !matches!(state.status, LegStatus::Running | LegStatus::Paused) {
         memory.pending_budget = None;
@@ -342,18 +602,46 @@ fn dispatch_spawns(
         let previous_spawned = memory.last_spawned_enemies;
         let desired_spawned = budget.enemies;
         let new_spawns = desired_spawned.saturating_sub(previous_spawned);
+        let mut threat_total = 0u32;
+        let mut forced = 0u32;
         for idx in 0..new_spawns {
             let offset_mm = (idx as i32) * 100;
-            let kind = choose_spawn_type(
+            let (seed, counter) = (memory.spawn_seed, memory.spawn_counter);
+            let pick = choose_spawn_type_with_pity(
                 &tables,
                 state.weather,
-                memory.spawn_seed,
-                memory.spawn_counter,
+                seed,
+                counter,
+                &cfg.0.type_pity,
+                &mut memory.type_last_seen,
             );
-            memory.spawn_counter = memory.spawn_counter.saturating_add(1);
-            queue.spawn(&kind, base_x + offset_mm, 0, 0);
+            forced += u32::from(pick.forced);
+            let kind = pick.kind;
+            let [x_mm, y_mm, z_mm] = board
+                .as_ref()
+                .and_then(|board| board.0.enemy_spawn_mm(memory.spawn_counter))
+                .unwrap_or([base_x + offset_mm, 0, 0]);
+            let spawn_index = memory.spawn_counter;
+            memory.active_spawns.push(spawn_index);
+            memory.spawn_counter = spawn_index.saturating_add(1);
+            threat_total = threat_total.saturating_add(archetypes.threat(&kind));
+            queue.spawn(&kind, x_mm, y_mm, z_mm);
+        }
+        if new_spawns > 0 {
+            queue.meter_units("spawn_threat_total", threat_total);
+        }
+        if forced > 0 {
+            queue.meter_units("spawn_pity_forced", forced);
+        }
+        if cfg.0.spawn.allow_cull && desired_spawned < previous_spawned {
+            let excess = previous_spawned - desired_spawned;
+            let (seed, nonce) = (memory.spawn_seed, memory.spawn_counter);
+            let culled = cull_spawns(&mut memory.active_spawns, excess, seed, nonce);
+            queue.meter_units("spawn_cull", culled.len() as u32);
+            memory.last_spawned_enemies = desired_spawned;
+        } else {
+            memory.last_spawned_enemies = previous_spawned.max(desired_spawned);
         }
-        memory.last_spawned_enemies = previous_spawned.max(desired_spawned);
         memory.prior_enemies = Some(memory.last_spawned_enemies);
     }
 }
@@ -361,19 +649,6 @@ fn dispatch_spawns(
 const SLOWMO_NUMERATOR: u32 = 4;
 const SLOWMO_DENOMINATOR: u32 = 5;
 
-fn scale_duration(duration: Duration, numerator: u32, denominator: u32) -> Duration {
-    if numerator == denominator {
-        return duration;
-    }
-
-    let total_nanos = duration.as_nanos();
-    let scaled = total_nanos
-        .saturating_mul(numerator as u128)
-        .saturating_div(denominator as u128);
-
-    duration_from_nanos(scaled)
-}
-
 fn duration_from_nanos(nanos: u128) -> Duration {
     Duration::new(
         (nanos / 1_000_000_000) as u64,
@@ -408,25 +683,28 @@ fn physics_step(world: &mut World) {
     }
 
     let current_fixed = world.resource::<Time<Fixed>>().timestep();
-    let (base_delta, scaled_delta, steps_to_run) = {
+    let base_delta = {
         let mut cadence = world.resource_mut::<PhysicsCadence>();
-        let base_delta = match cadence.base_timestep {
+        match cadence.base_timestep {
             Some(base) if base == current_fixed => base,
             _ => {
                 cadence.base_timestep = Some(current_fixed);
                 cadence.accumulator = Duration::default();
+                world.resource_mut::<SlowmoAccumulator>().remainder_ns = 0;
                 current_fixed
             }
-        };
-        let scaled_delta = if wheel.slowmo_enabled {
-            scale_duration(base_delta, SLOWMO_NUMERATOR, SLOWMO_DENOMINATOR)
-        } else {
-            base_delta
-        };
-
+        }
+    };
+    let scaled_delta = if wheel.slowmo_enabled {
+        world.resource_mut::<SlowmoAccumulator>().scale(base_delta)
+    } else {
+        base_delta
+    };
+    let steps_to_run = {
+        let mut cadence = world.resource_mut::<PhysicsCadence>();
         if base_delta.is_zero() {
             cadence.accumulator = Duration::default();
-            (base_delta, scaled_delta, 0_usize)
+            0_usize
         } else {
             let total = cadence
                 .accumulator
@@ -436,7 +714,7 @@ fn physics_step(world: &mut World) {
             let steps = (total / base_nanos) as usize;
             let remainder = total % base_nanos;
             cadence.accumulator = duration_from_nanos(remainder);
-            (base_delta, scaled_delta, steps)
+            steps
         }
     };
 
@@ -453,12 +731,21 @@ fn physics_step(world: &mut World) {
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 fn finalize_leg(
     mut state: ResMut<DirectorState>,
     mut econ: ResMut<EconIntent>,
     mut queue: ResMut<CommandQueue>,
     mut context: ResMut<LegContext>,
     pause: Res<PauseState>,
+    cfg: Res<DirectorConfigResource>,
+    mut closures: ResMut<RouteClosures>,
+    slowmo: Res<SlowmoAccumulator>,
+    mut app_state: Option<ResMut<AppState>>,
+    mut journal: ResMut<LedgerJournal>,
+    rulepack: Option<Res<Rulepack>>,
+    outcomes: Res<MissionOutcomes>,
+    mut history: ResMut<MissionHistory>,
 ) {
     if !matches!(state.status, LegStatus::Running | LegStatus::Paused) {
         econ.clear();
@@ -473,16 +760,10 @@ fn finalize_leg(
     let danger_delta = state.current_danger_score - state.prior_danger_score;
     let basis_delta = i32::from(econ.pending_basis_overlay_bp);
     let basis_total = context.basis_overlay_bp_total.saturating_add(basis_delta);
-    let _ = m2::log_post_leg_summary(
-        danger_delta,
-        econ.pending_pp_delta,
-        econ.pending_basis_overlay_bp,
-        state.current_danger_score,
-        basis_total,
-    );
     state.prior_danger_score = state.current_danger_score;
     context.prior_danger_score = Some(state.current_danger_score);
     context.basis_overlay_bp_total = basis_total;
+    state.basis_overlay_bp_total = basis_total;
     const LEG_DURATION_TOLERANCE_TICKS: u32 = 60;
     const DEFAULT_LEG_TARGET_TICKS: u32 = 600;
     let mission_minutes = context.mission_minutes;
@@ -540,6 +821,68 @@ fn finalize_leg(
             state.leg_tick = state.leg_tick.saturating_add(1);
         }
     }
+    if pause.hard_paused_sp && !matches!(state.status, LegStatus::Completed(_)) {
+        state.paused_ticks = state.paused_ticks.saturating_add(1);
+    }
+    if matches!(state.status, LegStatus::Completed(_)) {
+        let leg_danger_delta = state.current_danger_score - state.leg_start_danger_score;
+        if travel::close_route_after_leg(
+            &mut closures,
+            &cfg.0.closures,
+            state.link_id,
+            state.day,
+            leg_danger_delta,
+        ) {
+            queue.meter("route_closed", i32::from(state.link_id.0));
+        }
+        history.record_leg(&outcomes);
+        if slowmo.elapsed_ns > 0 {
+            // Split so long slow-mo stretches fit the i32 meter values.
+            let elapsed = Duration::from_nanos(slowmo.elapsed_ns);
+            let secs = elapsed.as_secs().min(i32::MAX as u64) as i32;
+            queue.meter("slowmo_elapsed_s", secs);
+            queue.meter("slowmo_elapsed_subsec_ns", elapsed.subsec_nanos() as i32);
+        }
+    }
+    let mut leg_interest = None;
+    if let (LegStatus::Completed(outcome), Some(app_state)) =
+        (state.status, app_state.as_deref_mut())
+    {
+        app_state.record_visit(state.link_id);
+        if let Some(rp) = rulepack.as_deref() {
+            let AppState {
+                econ: econ_state,
+                wallet,
+                ..
+            } = &mut *app_state;
+            let mut wallet = WalletAccess::new(wallet, &mut journal, queue.current_tick());
+            leg_interest =
+                accrue_leg_interest(econ_state, &rp.interest, state.link_id, &mut wallet);
+        }
+        if let Some(event) = leg_interest {
+            queue.meter_wide("interest_accrued_cents", event.interest_delta.as_i64());
+            if event.shortfall_cents.as_i64() > 0 {
+                queue.meter_wide("debt_from_shortfall", event.shortfall_cents.as_i64());
+            }
+        }
+        let seed = rewards::reward_seed(state.world_seed, state.link_id, state.day);
+        rewards::reward_leg(
+            &cfg.0.rewards,
+            outcome,
+            seed,
+            app_state,
+            &mut journal,
+            &mut queue,
+        );
+    }
+    let _ = m2::log_post_leg_summary(
+        danger_delta,
+        econ.pending_pp_delta,
+        econ.pending_basis_overlay_bp,
+        state.current_danger_score,
+        basis_total,
+        leg_interest.map_or(0, |event| event.interest_delta.as_i64()),
+    );
     econ.clear();
 }
 
@@ -548,6 +891,19 @@ mod tests {
     use super::*;
     use bevy::ecs::system::IntoSystem;
 
+    #[test]
+    fn slowmo_accumulator_carries_the_remainder() {
+        let base = Duration::from_nanos(33_333_333);
+        let mut slowmo = SlowmoAccumulator::default();
+        let steps: Vec<u128> = (0..5).map(|_| slowmo.scale(base).as_nanos()).collect();
+        assert_eq!(
+            steps,
+            vec![26_666_666, 26_666_666, 26_666_667, 26_666_666, 26_666_667]
+        );
+        assert_eq!(slowmo.remainder_ns, 0);
+        assert_eq!(slowmo.elapsed_ns, 133_333_332);
+    }
+
     #[test]
     fn finalize_leg_accumulates_basis_overlay_total() {
         m2::set_enabled(false);
@@ -571,6 +927,14 @@ mod tests {
             ..Default::default()
         });
         world.insert_resource(PauseState::default());
+        let cfg_path = director_cfg_path();
+        let cfg = load_director_cfg(cfg_path.to_str().expect("cfg path")).expect("director cfg");
+        world.insert_resource(DirectorConfigResource(cfg));
+        world.init_resource::<RouteClosures>();
+        world.init_resource::<MissionOutcomes>();
+        world.init_resource::<MissionHistory>();
+        world.init_resource::<SlowmoAccumulator>();
+        world.init_resource::<LedgerJournal>();
 
         let mut system = IntoSystem::into_system(finalize_leg);
         system.initialize(&mut world);
@@ -615,4 +979,303 @@ mod tests {
             .iter()
             .any(|command| matches!(command.kind, repro::CommandKind::Meter(ref meter) if meter.value == 10)));
     }
+
+    #[test]
+    fn dispatch_spawns_meters_threat_of_new_spawns() {
+        let mut world = World::new();
+        world.insert_resource(DirectorState {
+            status: LegStatus::Running,
+            weather: Weather::Clear,
+            ..Default::default()
+        });
+        world.insert_resource(SpawnMemory {
+            pending_budget: Some(SpawnBudget::new(5, 0)),
+            spawn_seed: 0xD7E7,
+            ..Default::default()
+        });
+        world.insert_resource(PauseState::default());
+        let cfg_path = director_cfg_path();
+        let cfg = load_director_cfg(cfg_path.to_str().expect("cfg path")).expect("director cfg");
+        world.insert_resource(SpawnTypeTables::from_cfg(&cfg));
+        world.insert_resource(DirectorConfigResource(cfg));
+        let catalog = ArchetypeCatalog::from_toml(
+            "[bandit]\nhp = 1\nspeed_mm_s = 1\nthreat = 10\n\n\
+             [scout]\nhp = 1\nspeed_mm_s = 1\nthreat = 1\n",
+        )
+        .expect("catalog");
+        world.insert_resource(catalog);
+        let mut queue = CommandQueue::default();
+        queue.begin_tick(0);
+        world.insert_resource(queue);
+
+        let mut system = IntoSystem::into_system(dispatch_spawns);
+        system.initialize(&mut world);
+
+        let expected_threat = |commands: &[repro::Command]| -> i32 {
+            commands
+                .iter()
+                .filter_map(|command| match &command.kind {
+                    repro::CommandKind::Spawn(spawn) if spawn.kind == "bandit" => Some(10),
+                    repro::CommandKind::Spawn(spawn) if spawn.kind == "scout" => Some(1),
+                    repro::CommandKind::Spawn(spawn) => panic!("unexpected kind {}", spawn.kind),
+                    repro::CommandKind::Meter(_) => None,
+                })
+                .sum()
+        };
+        let threat_meter = |commands: &[repro::Command]| -> Vec<i32> {
+            commands
+                .iter()
+                .filter_map(|command| match &command.kind {
+                    repro::CommandKind::Meter(meter) if meter.key == "spawn_threat_total" => {
+                        Some(meter.value)
+                    }
+                    _ => None,
+                })
+                .collect()
+        };
+
+        let _ = system.run((), &mut world);
+        let first = world.resource_mut::<CommandQueue>().drain();
+        assert_eq!(first.len(), 7, "spawn_count, five spawns, threat");
+        assert_eq!(threat_meter(&first), vec![expected_threat(&first)]);
+
+        // Growing the budget only meters the threat of the added spawns.
+        world.resource_mut::<SpawnMemory>().pending_budget = Some(SpawnBudget::new(7, 0));
+        let _ = system.run((), &mut world);
+        let second = world.resource_mut::<CommandQueue>().drain();
+        assert_eq!(second.len(), 4);
+        assert_eq!(threat_meter(&second), vec![expected_threat(&second)]);
+
+        // No new spawns, no threat meter.
+        world.resource_mut::<SpawnMemory>().pending_budget = Some(SpawnBudget::new(7, 0));
+        let _ = system.run((), &mut world);
+        let third = world.resource_mut::<CommandQueue>().drain();
+        assert!(threat_meter(&third).is_empty());
+    }
+
+    /// Runs `dispatch_spawns` over `budgets` in a fresh world and returns
+    /// each run's commands plus the final spawn memory.
+    fn dispatch_budgets(
+        allow_cull: bool,
+        budgets: &[u32],
+    ) -> (Vec<Vec<repro::Command>>, SpawnMemory) {
+        let mut world = World::new();
+        world.insert_resource(DirectorState {
+            status: LegStatus::Running,
+            weather: Weather::Clear,
+            ..Default::default()
+        });
+        world.insert_resource(SpawnMemory {
+            spawn_seed: 0xD7E7,
+            ..Default::default()
+        });
+        world.insert_resource(PauseState::default());
+        let cfg_path = director_cfg_path();
+        let mut cfg =
+            load_director_cfg(cfg_path.to_str().expect("cfg path")).expect("director cfg");
+        cfg.spawn.allow_cull = allow_cull;
+        world.insert_resource(SpawnTypeTables::from_cfg(&cfg));
+        world.insert_resource(DirectorConfigResource(cfg));
+        world.insert_resource(ArchetypeCatalog::default());
+        let mut queue = CommandQueue::default();
+        queue.begin_tick(0);
+        world.insert_resource(queue);
+
+        let mut system = IntoSystem::into_system(dispatch_spawns);
+        system.initialize(&mut world);
+        let runs = budgets
+            .iter()
+            .map(|&enemies| {
+                world.resource_mut::<SpawnMemory>().pending_budget =
+                    Some(SpawnBudget::new(enemies, 0));
+                let _ = system.run((), &mut world);
+                world.resource_mut::<CommandQueue>().drain()
+            })
+            .collect();
+        (runs, world.resource::<SpawnMemory>().clone())
+    }
+
+    fn cull_meters(commands: &[repro::Command]) -> Vec<i32> {
+        commands
+            .iter()
+            .filter_map(|command| match &command.kind {
+                repro::CommandKind::Meter(meter) if meter.key == "spawn_cull" => Some(meter.value),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn dropping_budget_culls_the_excess_deterministically() {
+        let (runs, memory) = dispatch_budgets(true, &[8, 3]);
+        assert_eq!(cull_meters(&runs[1]), vec![5]);
+        assert_eq!(memory.last_spawned_enemies, 3);
+        assert_eq!(memory.prior_enemies, Some(3));
+        assert_eq!(memory.active_spawns.len(), 3);
+
+        let (again, again_memory) = dispatch_budgets(true, &[8, 3]);
+        assert_eq!(runs, again);
+        assert_eq!(memory.active_spawns, again_memory.active_spawns);
+
+        // Regrowing spawns only the gap back to the budget.
+        let (regrow, regrow_memory) = dispatch_budgets(true, &[8, 3, 6]);
+        let spawned = regrow[2]
+            .iter()
+            .filter(|command| matches!(command.kind, repro::CommandKind::Spawn(_)))
+            .count();
+        assert_eq!(spawned, 3);
+        assert_eq!(regrow_memory.active_spawns.len(), 6);
+    }
+
+    #[test]
+    fn culling_off_keeps_the_spawn_ratchet() {
+        let (runs, memory) = dispatch_budgets(false, &[8, 3]);
+        assert!(runs.iter().all(|commands| cull_meters(commands).is_empty()));
+        assert_eq!(runs[1].len(), 1, "spawn_count only");
+        assert_eq!(memory.last_spawned_enemies, 8);
+        assert_eq!(memory.prior_enemies, Some(8));
+        assert_eq!(memory.active_spawns.len(), 8);
+    }
+
+    #[test]
+    fn completed_leg_grants_configured_reward() {
+        m2::set_enabled(false);
+
+        let mut world = World::new();
+        world.insert_resource(DirectorState {
+            status: LegStatus::Running,
+            world_seed: 7,
+            day: 2,
+            leg_tick: 60,
+            ..Default::default()
+        });
+        world.init_resource::<EconIntent>();
+        let mut queue = CommandQueue::default();
+        queue.begin_tick(60);
+        world.insert_resource(queue);
+        world.insert_resource(LegContext {
+            mission_minutes: 1,
+            ..Default::default()
+        });
+        world.insert_resource(PauseState::default());
+        let cfg_path = director_cfg_path();
+        let mut cfg =
+            load_director_cfg(cfg_path.to_str().expect("cfg path")).expect("director cfg");
+        cfg.rewards.success.entries = vec![rewards::RewardEntry {
+            weight: 1,
+            cents: 900,
+            commodity: None,
+            units: 0,
+        }];
+        world.insert_resource(DirectorConfigResource(cfg));
+        world.init_resource::<RouteClosures>();
+        world.init_resource::<MissionOutcomes>();
+        world.init_resource::<MissionHistory>();
+        world.init_resource::<AppState>();
+        world.init_resource::<SlowmoAccumulator>();
+        world.init_resource::<LedgerJournal>();
+
+        let mut system = IntoSystem::into_system(finalize_leg);
+        system.initialize(&mut world);
+        let _ = system.run((), &mut world);
+        system.apply_deferred(&mut world);
+
+        assert_eq!(
+            world.resource::<DirectorState>().status,
+            LegStatus::Completed(Outcome::Success)
+        );
+        assert_eq!(
+            world.resource::<AppState>().wallet.balance(),
+            crate::systems::economy::MoneyCents(900)
+        );
+        let queue = world.resource::<CommandQueue>();
+        assert!(queue.buf.iter().any(|command| matches!(
+            command.kind,
+            repro::CommandKind::Meter(ref meter) if meter.key == "reward_cents" && meter.value == 900
+        )));
+
+        // The leg is over; later ticks must not pay out or count it again.
+        let _ = system.run((), &mut world);
+        let app_state = world.resource::<AppState>();
+        assert_eq!(
+            app_state.wallet.balance(),
+            crate::systems::economy::MoneyCents(900)
+        );
+        assert_eq!(app_state.route_visit_count(RouteId::default()), 1);
+        assert_eq!(world.resource::<MissionHistory>().len(), 1);
+    }
+
+    #[test]
+    fn leg_cadence_interest_accrues_once_on_completion() {
+        use crate::systems::economy::{
+            accrue_interest_per_leg, load_rulepack, InterestAccrual, MoneyCents,
+        };
+
+        m2::set_enabled(false);
+
+        let mut world = World::new();
+        world.insert_resource(DirectorState {
+            status: LegStatus::Running,
+            leg_tick: 60,
+            ..Default::default()
+        });
+        world.init_resource::<EconIntent>();
+        let mut queue = CommandQueue::default();
+        queue.begin_tick(60);
+        world.insert_resource(queue);
+        world.insert_resource(LegContext {
+            mission_minutes: 1,
+            ..Default::default()
+        });
+        world.insert_resource(PauseState::default());
+        let mut cfg = load_director_cfg(director_cfg_path().to_str().expect("cfg path"))
+            .expect("director cfg");
+        cfg.rewards.success.entries.clear();
+        world.insert_resource(DirectorConfigResource(cfg));
+        world.init_resource::<RouteClosures>();
+        world.init_resource::<MissionOutcomes>();
+        world.init_resource::<MissionHistory>();
+        let mut app_state = AppState::default();
+        app_state.econ.debt_cents = MoneyCents(500_000);
+        world.insert_resource(app_state);
+        world.init_resource::<SlowmoAccumulator>();
+        world.init_resource::<LedgerJournal>();
+        let rp_path =
+            Path::new(env!("CARGO_MANIFEST_DIR")).join("../../assets/rulepacks/day_001.toml");
+        let mut rp = load_rulepack(rp_path.to_str().expect("rulepack path")).expect("rulepack");
+        rp.interest.accrue_on = InterestAccrual::Leg;
+        let (expected, _) = accrue_interest_per_leg(MoneyCents(500_000), &rp.interest);
+        world.insert_resource(rp);
+
+        let mut system = IntoSystem::into_system(finalize_leg);
+        system.initialize(&mut world);
+        let _ = system.run((), &mut world);
+        system.apply_deferred(&mut world);
+        let _ = system.run((), &mut world);
+
+        assert_eq!(
+            world.resource::<AppState>().econ.debt_cents,
+            MoneyCents(500_000).saturating_add(expected)
+        );
+        let queue = world.resource::<CommandQueue>();
+        let meters = |key: &str| -> Vec<i32> {
+            queue
+                .buf
+                .iter()
+                .filter_map(|command| match command.kind {
+                    repro::CommandKind::Meter(ref meter) if meter.key == key => Some(meter.value),
+                    _ => None,
+                })
+                .collect()
+        };
+        assert_eq!(
+            meters("interest_accrued_cents"),
+            vec![expected.as_i64() as i32]
+        );
+        // The default wallet is empty, so all of it is shortfall.
+        assert_eq!(
+            meters("debt_from_shortfall"),
+            vec![expected.as_i64() as i32]
+        );
+    }
 }
@@ -1,22 +1,23 @@
-use bevy::prelude::Resource;
+use bevy::prelude::{Message, Resource};
+use serde::{Deserialize, Serialize};
 
 use crate::systems::command_queue::CommandQueue;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
 pub enum Stance {
     #[default]
     Brace,
     Vault,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
 pub enum ToolSlot {
     #[default]
     A,
     B,
 }
 
-#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
 pub struct WheelState {
     pub stance: Stance,
     pub tool: ToolSlot,
@@ -26,64 +27,121 @@ pub struct WheelState {
 }
 
 impl WheelState {
-    pub fn set_stance(&mut self, queue: &mut CommandQueue, stance: Stance) {
-        if self.stance != stance {
-            self.stance = stance;
-            queue.meter(
+    pub fn set_stance(&mut self, queue: &mut CommandQueue, stance: Stance) -> Option<WheelEvent> {
+        let old = std::mem::replace(&mut self.stance, stance);
+        emit(queue, WheelChange::Stance { old, new: stance })
+    }
+
+    pub fn set_tool(&mut self, queue: &mut CommandQueue, tool: ToolSlot) -> Option<WheelEvent> {
+        let old = std::mem::replace(&mut self.tool, tool);
+        emit(queue, WheelChange::Tool { old, new: tool })
+    }
+
+    pub fn set_overwatch(&mut self, queue: &mut CommandQueue, enabled: bool) -> Option<WheelEvent> {
+        let old = std::mem::replace(&mut self.overwatch, enabled);
+        emit(queue, WheelChange::Overwatch { old, new: enabled })
+    }
+
+    pub fn set_move_mode(&mut self, queue: &mut CommandQueue, enabled: bool) -> Option<WheelEvent> {
+        let old = std::mem::replace(&mut self.move_mode, enabled);
+        emit(queue, WheelChange::MoveMode { old, new: enabled })
+    }
+
+    pub fn set_slowmo(&mut self, queue: &mut CommandQueue, enabled: bool) -> Option<WheelEvent> {
+        let old = std::mem::replace(&mut self.slowmo_enabled, enabled);
+        emit(queue, WheelChange::Slowmo { old, new: enabled })
+    }
+}
+
+/// One wheel setting changing from `old` to `new`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WheelChange {
+    Stance { old: Stance, new: Stance },
+    Tool { old: ToolSlot, new: ToolSlot },
+    Overwatch { old: bool, new: bool },
+    MoveMode { old: bool, new: bool },
+    Slowmo { old: bool, new: bool },
+}
+
+impl WheelChange {
+    fn is_change(&self) -> bool {
+        match *self {
+            Self::Stance { old, new } => old != new,
+            Self::Tool { old, new } => old != new,
+            Self::Overwatch { old, new }
+            | Self::MoveMode { old, new }
+            | Self::Slowmo { old, new } => old != new,
+        }
+    }
+
+    /// The meter key and value recorded for the new setting.
+    pub fn meter(&self) -> (&'static str, i32) {
+        match *self {
+            Self::Stance { new, .. } => (
                 "wheel_stance",
-                match stance {
+                match new {
                     Stance::Brace => 0,
                     Stance::Vault => 1,
                 },
-            );
-        }
-    }
-
-    pub fn set_tool(&mut self, queue: &mut CommandQueue, tool: ToolSlot) {
-        if self.tool != tool {
-            self.tool = tool;
-            queue.meter(
+            ),
+            Self::Tool { new, .. } => (
                 "wheel_tool",
-                match tool {
+                match new {
                     ToolSlot::A => 0,
                     ToolSlot::B => 1,
                 },
-            );
-        }
-    }
-
-    pub fn set_overwatch(&mut self, queue: &mut CommandQueue, enabled: bool) {
-        if self.overwatch != enabled {
-            self.overwatch = enabled;
-            queue.meter("wheel_overwatch", enabled as i32);
+            ),
+            Self::Overwatch { new, .. } => ("wheel_overwatch", new as i32),
+            Self::MoveMode { new, .. } => ("wheel_move", new as i32),
+            Self::Slowmo { new, .. } => ("wheel_slowmo", new as i32),
         }
     }
+}
 
-    pub fn set_move_mode(&mut self, queue: &mut CommandQueue, enabled: bool) {
-        if self.move_mode != enabled {
-            self.move_mode = enabled;
-            queue.meter("wheel_move", enabled as i32);
-        }
-    }
+/// A wheel transition, sent alongside its meter. The meter stays the
+/// recorded copy; these are for in-frame consumers and are never saved.
+#[derive(Message, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WheelEvent {
+    pub tick: u32,
+    pub change: WheelChange,
+}
 
-    pub fn set_slowmo(&mut self, queue: &mut CommandQueue, enabled: bool) {
-        if self.slowmo_enabled != enabled {
-            self.slowmo_enabled = enabled;
-            queue.meter("wheel_slowmo", enabled as i32);
-        }
+fn emit(queue: &mut CommandQueue, change: WheelChange) -> Option<WheelEvent> {
+    if !change.is_change() {
+        return None;
     }
+    let (key, value) = change.meter();
+    queue.meter(key, value);
+    Some(WheelEvent {
+        tick: queue.current_tick(),
+        change,
+    })
 }
 
-#[derive(Resource, Default, Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Resource, Default, Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub struct PauseState {
     pub hard_paused_sp: bool,
 }
 
 impl PauseState {
-    pub fn set_hard_pause(&mut self, queue: &mut CommandQueue, paused: bool) {
-        if self.hard_paused_sp != paused {
-            self.hard_paused_sp = paused;
-            queue.meter("wheel_hard_pause", paused as i32);
+    pub fn set_hard_pause(&mut self, queue: &mut CommandQueue, paused: bool) -> Option<PauseEvent> {
+        if self.hard_paused_sp == paused {
+            return None;
         }
+        let old = std::mem::replace(&mut self.hard_paused_sp, paused);
+        queue.meter("wheel_hard_pause", paused as i32);
+        Some(PauseEvent {
+            tick: queue.current_tick(),
+            old,
+            new: paused,
+        })
     }
 }
+
+/// A single-player hard pause toggling, sent alongside `wheel_hard_pause`.
+#[derive(Message, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PauseEvent {
+    pub tick: u32,
+    pub old: bool,
+    pub new: bool,
+}
@@ -13,6 +13,7 @@ pub fn hash_mission_name(name: &str) -> u64 {
 }
 
 #[inline]
+#[deprecated(note = "use `RngStream::mission_seed`")]
 pub fn mission_seed(world_seed: u64, link_id: RouteId, day: u32, mission_id: u64) -> u64 {
     let mut key = [0u8; 32];
     key[0..8].copy_from_slice(&world_seed.to_le_bytes());
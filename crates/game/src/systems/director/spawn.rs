@@ -1,13 +1,15 @@
+use std::collections::{BTreeMap, HashMap};
+
 use crate::systems::economy::{Pp, Weather};
 use bevy::prelude::Resource;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
-use super::config::DirectorCfg;
-use super::rng::{spawn_subseed, DetRng};
+use super::config::{DirectorCfg, SpawnWeight, TypePityCfg};
+use super::rng::{hash_mission_name, spawn_subseed, DetRng};
 
-const DEFAULT_SPAWN_KIND: &str = "bandit";
+pub(crate) const DEFAULT_SPAWN_KIND: &str = "bandit";
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub struct SpawnBudget {
     pub enemies: u32,
     pub obstacles: u32,
@@ -25,36 +27,44 @@ pub struct SpawnTypeEntry {
     cumulative_weight: u32,
 }
 
+/// Spawn types with their integer weights, sampled with one range draw.
+///
+/// A draw in `0..total_weight` picks the first entry whose cumulative weight
+/// passes it. The guide table splits the draws into one bucket per entry and
+/// stores where each bucket's search starts, so a pick checks about two
+/// entries however long the table is, and lands on exactly the entry a
+/// linear scan would.
 #[derive(Clone, Default)]
 pub struct SpawnTypeTable {
     entries: Vec<SpawnTypeEntry>,
     total_weight: u32,
+    guide: Vec<u32>,
 }
 
 impl SpawnTypeTable {
-    #[allow(clippy::float_arithmetic)]
-    fn from_weights(weights: &std::collections::HashMap<String, f32>) -> Self {
+    /// Builds the table in name order, dropping zero weights. The loader
+    /// keeps the total within `u32`; a larger one saturates.
+    fn from_weights(weights: &std::collections::HashMap<String, SpawnWeight>) -> Self {
         let mut entries = Vec::new();
         let mut total = 0u32;
         let mut sorted: Vec<_> = weights.iter().collect();
-        sorted.sort_by(|(a, _), (b, _)| a.cmp(b));
+        sorted.sort_by_key(|(name, _)| *name);
         for (name, weight) in sorted {
-            if *weight <= 0.0 {
-                continue;
-            }
-            let scaled = (*weight * 1000.0).round();
-            if scaled <= 0.0 {
+            let units = weight.units();
+            if units == 0 {
                 continue;
             }
-            total = total.saturating_add(scaled as u32);
+            total = total.saturating_add(units);
             entries.push(SpawnTypeEntry {
                 name: name.clone(),
                 cumulative_weight: total,
             });
         }
+        let guide = guide_table(&entries, total);
         Self {
             entries,
             total_weight: total,
+            guide,
         }
     }
 
@@ -63,16 +73,61 @@ impl SpawnTypeTable {
             return DEFAULT_SPAWN_KIND.to_owned();
         }
         let draw = rng.range_u32(0, self.total_weight.saturating_sub(1));
+        let bucket = u64::from(draw) * self.guide.len() as u64 / u64::from(self.total_weight);
+        let mut idx = self.guide[bucket as usize] as usize;
+        while self.entries[idx].cumulative_weight <= draw {
+            idx += 1;
+        }
+        self.entries[idx].name.clone()
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.entries.iter().any(|entry| entry.name == name)
+    }
+
+    /// Like [`Self::choose`] over the entries `excluded` rejects, keeping
+    /// their relative weights. `None` when every entry is excluded.
+    fn choose_excluding(
+        &self,
+        rng: &mut DetRng,
+        excluded: impl Fn(&str) -> bool,
+    ) -> Option<String> {
+        let mut previous = 0;
+        let mut kept: Vec<(&str, u32)> = Vec::with_capacity(self.entries.len());
+        let mut total = 0u32;
         for entry in &self.entries {
-            if draw < entry.cumulative_weight {
-                return entry.name.clone();
+            let weight = entry.cumulative_weight - previous;
+            previous = entry.cumulative_weight;
+            if !excluded(&entry.name) {
+                total = total.saturating_add(weight);
+                kept.push((&entry.name, total));
             }
         }
-        self.entries
-            .last()
-            .map(|entry| entry.name.clone())
-            .unwrap_or_else(|| DEFAULT_SPAWN_KIND.to_owned())
+        if total == 0 {
+            return None;
+        }
+        let draw = rng.range_u32(0, total - 1);
+        kept.iter()
+            .find(|(_, cumulative)| draw < *cumulative)
+            .map(|(name, _)| (*name).to_owned())
+    }
+}
+
+/// For each of `entries.len()` equal buckets of draws, the first entry a
+/// draw in that bucket can land on.
+fn guide_table(entries: &[SpawnTypeEntry], total: u32) -> Vec<u32> {
+    let buckets = entries.len() as u64;
+    let mut guide = Vec::with_capacity(entries.len());
+    let mut idx = 0;
+    for bucket in 0..buckets {
+        // Smallest draw in the bucket.
+        let start = (bucket * u64::from(total)).div_ceil(buckets);
+        while u64::from(entries[idx].cumulative_weight) <= start {
+            idx += 1;
+        }
+        guide.push(idx as u32);
     }
+    guide
 }
 
 #[derive(Clone, Default, Resource)]
@@ -140,13 +195,17 @@ pub fn compute_spawn_budget(
         cfg.spawn.base as i32 + cfg.spawn.alpha_pp_per_100 * pp_band + weather_delta;
     enemies_raw = enemies_raw.max(0);
     let desired = enemies_raw as u32;
-    let desired_clamped = desired.clamp(cfg.spawn.clamp_min, cfg.spawn.clamp_max);
+    let (clamp_min, clamp_max) = cfg.spawn.clamp_for(weather);
+    let desired_clamped = desired.clamp(clamp_min, clamp_max);
 
     let prior_enemies = prior.unwrap_or(desired_clamped);
-    let capped_prior = prior_enemies.clamp(cfg.spawn.clamp_min, cfg.spawn.clamp_max);
+    let capped_prior = prior_enemies.clamp(clamp_min, clamp_max);
     let increase = desired_clamped.saturating_sub(capped_prior);
-    let delta = increase.min(cfg.spawn.growth_cap_per_leg);
-    let enemies = (capped_prior + delta).clamp(cfg.spawn.clamp_min, cfg.spawn.clamp_max);
+    let delta = cfg
+        .spawn
+        .growth_curve
+        .delta(increase, cfg.spawn.growth_cap_per_leg);
+    let enemies = (capped_prior + delta).clamp(clamp_min, clamp_max);
 
     SpawnBudget {
         enemies,
@@ -154,6 +213,45 @@ pub fn compute_spawn_budget(
     }
 }
 
+/// [`compute_spawn_budget`] with a streak adjustment from
+/// [`super::history::streak_modifier`] added on top, in basis points of the
+/// budget. The result stays within the weather's clamps. `prior` should be
+/// an unadjusted budget so the adjustment does not compound.
+pub fn compute_spawn_budget_with_streak(
+    pp: Pp,
+    weather: Weather,
+    prior: Option<u32>,
+    cfg: &DirectorCfg,
+    modifier_bp: i32,
+) -> SpawnBudget {
+    apply_streak_modifier(
+        compute_spawn_budget(pp, weather, prior, cfg),
+        weather,
+        cfg,
+        modifier_bp,
+    )
+}
+
+/// Adds `modifier_bp` basis points of `budget.enemies` to it, truncating
+/// toward zero, and re-applies the weather's clamps.
+pub fn apply_streak_modifier(
+    budget: SpawnBudget,
+    weather: Weather,
+    cfg: &DirectorCfg,
+    modifier_bp: i32,
+) -> SpawnBudget {
+    if modifier_bp == 0 {
+        return budget;
+    }
+    let (clamp_min, clamp_max) = cfg.spawn.clamp_for(weather);
+    let enemies = i64::from(budget.enemies);
+    let adjusted = enemies + enemies * i64::from(modifier_bp) / 10_000;
+    SpawnBudget {
+        enemies: adjusted.clamp(i64::from(clamp_min), i64::from(clamp_max)) as u32,
+        ..budget
+    }
+}
+
 pub fn choose_spawn_type(
     tables: &SpawnTypeTables,
     weather: Weather,
@@ -164,6 +262,100 @@ pub fn choose_spawn_type(
     tables.table_for(weather).choose(&mut rng)
 }
 
+/// A spawn type pick and whether a pity cap forced it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SpawnPick {
+    pub kind: String,
+    pub forced: bool,
+}
+
+/// [`choose_spawn_type`] with per-type [`TypePityCfg`] limits applied.
+/// `last_seen` holds the spawn index each pity type last appeared at this
+/// leg and is updated with the pick.
+///
+/// A table type whose `max_absence` would be exceeded by this spawn is
+/// forced; when several are due at once the spawn's own draw picks one, so
+/// caps can slip when they are tighter than the number of due types. A
+/// drawn type still within its `min_interval` is redrawn among the types
+/// that are not. Every choice is made from the spawn seed and index, so a
+/// replay forces the same spawns.
+pub fn choose_spawn_type_with_pity(
+    tables: &SpawnTypeTables,
+    weather: Weather,
+    seed: u64,
+    spawn_index: u64,
+    pity: &HashMap<String, TypePityCfg>,
+    last_seen: &mut BTreeMap<String, u64>,
+) -> SpawnPick {
+    let table = tables.table_for(weather);
+    let mut rng = DetRng::from_seed(spawn_subseed(seed, spawn_index));
+    // Spawns since `name` last appeared, or since the leg began.
+    let absence = |name: &str| match last_seen.get(name) {
+        Some(seen) => spawn_index.saturating_sub(seen.saturating_add(1)),
+        None => spawn_index,
+    };
+
+    let mut due: Vec<&str> = pity
+        .iter()
+        .filter(|(name, cfg)| {
+            cfg.max_absence > 0
+                && table.contains(name)
+                && absence(name).saturating_add(1) >= u64::from(cfg.max_absence)
+        })
+        .map(|(name, _)| name.as_str())
+        .collect();
+    due.sort_unstable();
+
+    let pick = if due.is_empty() {
+        let drawn = table.choose(&mut rng);
+        let cooling = |name: &str| {
+            pity.get(name).is_some_and(|cfg| {
+                last_seen.contains_key(name) && absence(name) < u64::from(cfg.min_interval)
+            })
+        };
+        let kind = if cooling(&drawn) {
+            table.choose_excluding(&mut rng, cooling).unwrap_or(drawn)
+        } else {
+            drawn
+        };
+        SpawnPick {
+            kind,
+            forced: false,
+        }
+    } else {
+        let last = u32::try_from(due.len() - 1).unwrap_or(u32::MAX);
+        SpawnPick {
+            kind: due[rng.range_u32(0, last) as usize].to_owned(),
+            forced: true,
+        }
+    };
+    if pity.contains_key(&pick.kind) {
+        last_seen.insert(pick.kind.clone(), spawn_index);
+    }
+    pick
+}
+
+/// Removes `count` ids from `active` and returns them in ascending order.
+/// The picks are drawn from `seed` and `nonce`, so the same live set always
+/// loses the same spawns.
+pub fn cull_spawns(active: &mut Vec<u64>, count: u32, seed: u64, nonce: u64) -> Vec<u64> {
+    let salt = hash_mission_name("spawn_cull");
+    let mut rng = DetRng::from_seed(spawn_subseed(seed ^ salt, nonce));
+    let count = (count as usize).min(active.len());
+    let mut culled = Vec::with_capacity(count);
+    for _ in 0..count {
+        let last = u32::try_from(active.len() - 1).unwrap_or(u32::MAX);
+        let pick = rng.range_u32(0, last) as usize;
+        culled.push(active.remove(pick));
+    }
+    culled.sort_unstable();
+    culled
+}
+
+/// `1000·enemies + 400·density + 300·cadence + 50·minutes`, scaled by
+/// `(250 + rating - 50) / 250` and rounded half up. The sum and product are
+/// taken in `i128`, which holds them for any `u32` inputs, so nothing wraps
+/// on the way; a score past `i32::MAX` saturates there.
 pub fn danger_score(
     budget: &SpawnBudget,
     mission_minutes: u32,
@@ -171,16 +363,16 @@ pub fn danger_score(
     cadence_per_min: u32,
     player_rating_0_100: u8,
 ) -> i32 {
-    let enemies = budget.enemies as i32;
-    let density = density_per_10k as i32;
-    let cadence = cadence_per_min as i32;
-    let minutes = mission_minutes as i32;
+    let enemies = i128::from(budget.enemies);
+    let density = i128::from(density_per_10k);
+    let cadence = i128::from(cadence_per_min);
+    let minutes = i128::from(mission_minutes);
 
     let danger_raw = 1000 * enemies + 400 * density + 300 * cadence + 50 * minutes;
-    let rating = i32::from(player_rating_0_100.clamp(0, 100));
+    let rating = i128::from(player_rating_0_100.clamp(0, 100));
     let delta = rating - 50;
-    let numerator = danger_raw as i64 * (250 + i64::from(delta));
-    ((numerator + 125) / 250) as i32
+    let numerator = danger_raw * (250 + delta);
+    i32::try_from((numerator + 125) / 250).unwrap_or(i32::MAX)
 }
 
 pub fn danger_diff_sign(current: i32, prior: i32) -> i32 {
@@ -194,14 +386,13 @@ pub fn danger_diff_sign(current: i32, prior: i32) -> i32 {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::systems::director::config::{DirectorCfg, SpawnCfg};
-    use std::collections::HashMap;
+    use crate::systems::director::config::{DirectorCfg, GrowthCurve, SpawnCfg};
 
     #[test]
     fn spawn_table_prefers_higher_weights() {
         let mut weights = HashMap::new();
-        weights.insert("alpha".to_string(), 1.0);
-        weights.insert("beta".to_string(), 3.0);
+        weights.insert("alpha".to_string(), SpawnWeight::Units(1));
+        weights.insert("beta".to_string(), SpawnWeight::Units(3));
         let table = SpawnTypeTable::from_weights(&weights);
         let mut alpha_hits = 0;
         let mut beta_hits = 0;
@@ -216,6 +407,178 @@ mod tests {
         assert!(beta_hits > alpha_hits);
     }
 
+    fn table(weights: &[(&str, SpawnWeight)]) -> SpawnTypeTable {
+        let weights = weights
+            .iter()
+            .map(|(name, weight)| (name.to_string(), *weight))
+            .collect();
+        SpawnTypeTable::from_weights(&weights)
+    }
+
+    /// The cumulative scan `choose` ran before the guide table.
+    fn linear_pick(table: &SpawnTypeTable, rng: &mut DetRng) -> String {
+        let draw = rng.range_u32(0, table.total_weight.saturating_sub(1));
+        table
+            .entries
+            .iter()
+            .find(|entry| draw < entry.cumulative_weight)
+            .map(|entry| entry.name.clone())
+            .expect("draw below the total")
+    }
+
+    #[test]
+    fn distribution_follows_integer_weights() {
+        let table = table(&[
+            ("alpha", SpawnWeight::Units(1)),
+            ("beta", SpawnWeight::Units(3)),
+            ("gamma", SpawnWeight::Units(6)),
+            ("never", SpawnWeight::Units(0)),
+            ("legacy_zero", SpawnWeight::Legacy(0.0004)),
+        ]);
+        assert!(!table.contains("never"));
+        assert!(!table.contains("legacy_zero"));
+
+        let mut hits: HashMap<String, u32> = HashMap::new();
+        for idx in 0..10_000 {
+            let pick = table.choose(&mut DetRng::from_seed(spawn_subseed(0x0D15_7000, idx)));
+            *hits.entry(pick).or_default() += 1;
+        }
+        assert_eq!(hits.len(), 3, "{hits:?}");
+        // Expected counts 1000/3000/6000; 250 is about five standard
+        // deviations of the widest.
+        for (name, expected) in [("alpha", 1_000), ("beta", 3_000), ("gamma", 6_000)] {
+            let got = hits[name];
+            assert!(got.abs_diff(expected) <= 250, "{name}: {got} of {expected}");
+        }
+    }
+
+    #[test]
+    fn guide_table_picks_match_the_linear_scan() {
+        let legacy = table(&[
+            ("bandit", SpawnWeight::Legacy(1.0)),
+            ("scout", SpawnWeight::Legacy(0.5)),
+            ("warlord", SpawnWeight::Legacy(0.02)),
+        ]);
+        let integer = table(&[
+            ("bandit", SpawnWeight::Units(1000)),
+            ("scout", SpawnWeight::Units(500)),
+            ("warlord", SpawnWeight::Units(20)),
+        ]);
+        let names: Vec<String> = (0..300).map(|idx| format!("type_{idx:03}")).collect();
+        let many: Vec<_> = names
+            .iter()
+            .enumerate()
+            .map(|(idx, name)| (name.as_str(), SpawnWeight::Units(idx as u32 * 37 % 11)))
+            .collect();
+        let many = table(&many);
+
+        for (new, old) in [(&integer, &legacy), (&many, &many)] {
+            for idx in 0..10_000 {
+                let seed = spawn_subseed(0x5EED_0042, idx);
+                let mut guided = DetRng::from_seed(seed);
+                let mut linear = DetRng::from_seed(seed);
+                assert_eq!(new.choose(&mut guided), linear_pick(old, &mut linear));
+                // One range draw each, so the streams stay in step.
+                assert_eq!(guided.next_u32(), linear.next_u32());
+            }
+        }
+    }
+
+    fn rare_table() -> SpawnTypeTables {
+        let mut cfg = growth_cfg(GrowthCurve::Linear);
+        cfg.types = Some(HashMap::from([
+            ("bandit".to_string(), SpawnWeight::Units(1000)),
+            ("scout".to_string(), SpawnWeight::Units(500)),
+            ("warlord".to_string(), SpawnWeight::Units(20)),
+        ]));
+        SpawnTypeTables::from_cfg(&cfg)
+    }
+
+    fn picks(
+        tables: &SpawnTypeTables,
+        pity: &HashMap<String, TypePityCfg>,
+        count: u64,
+    ) -> Vec<SpawnPick> {
+        let mut last_seen = BTreeMap::new();
+        (0..count)
+            .map(|idx| {
+                choose_spawn_type_with_pity(
+                    tables,
+                    Weather::Clear,
+                    0x5EED_0042,
+                    idx,
+                    pity,
+                    &mut last_seen,
+                )
+            })
+            .collect()
+    }
+
+    fn longest_absence(picks: &[SpawnPick], kind: &str) -> usize {
+        picks
+            .split(|pick| pick.kind == kind)
+            .map(<[SpawnPick]>::len)
+            .max()
+            .unwrap_or(0)
+    }
+
+    #[test]
+    fn max_absence_forces_rare_types_in_every_window() {
+        let tables = rare_table();
+        let unpitied = picks(&tables, &HashMap::new(), 200);
+        assert!(longest_absence(&unpitied, "warlord") > 10);
+        for idx in 0..200 {
+            assert_eq!(
+                unpitied[idx as usize].kind,
+                choose_spawn_type(&tables, Weather::Clear, 0x5EED_0042, idx)
+            );
+        }
+
+        let pity = HashMap::from([(
+            "warlord".to_string(),
+            TypePityCfg {
+                min_interval: 0,
+                max_absence: 10,
+            },
+        )]);
+        let pitied = picks(&tables, &pity, 200);
+        for window in pitied.windows(10) {
+            assert!(window.iter().any(|pick| pick.kind == "warlord"));
+        }
+        assert!(pitied.iter().any(|pick| pick.forced));
+        assert!(pitied
+            .iter()
+            .filter(|pick| pick.forced)
+            .all(|pick| pick.kind == "warlord"));
+        assert_eq!(pitied, picks(&tables, &pity, 200));
+    }
+
+    #[test]
+    fn min_interval_spaces_out_repeat_picks() {
+        let tables = rare_table();
+        let pity = HashMap::from([(
+            "bandit".to_string(),
+            TypePityCfg {
+                min_interval: 2,
+                max_absence: 0,
+            },
+        )]);
+        let picks = picks(&tables, &pity, 200);
+        let bandits: Vec<usize> = picks
+            .iter()
+            .enumerate()
+            .filter(|(_, pick)| pick.kind == "bandit")
+            .map(|(idx, _)| idx)
+            .collect();
+        assert!(bandits.len() > 10);
+        assert!(bandits.windows(2).all(|pair| pair[1] - pair[0] > 2));
+        assert!(picks.iter().all(|pick| !pick.forced));
+
+        let parsed: TypePityCfg = toml::from_str("max_absence = 10\n").expect("pity cfg");
+        assert_eq!(parsed.min_interval, 0);
+        assert_eq!(parsed.max_absence, 10);
+    }
+
     #[test]
     fn tables_fall_back_to_default() {
         let cfg = DirectorCfg {
@@ -226,13 +589,150 @@ mod tests {
                 growth_cap_per_leg: 1,
                 clamp_min: 1,
                 clamp_max: 1,
+                growth_curve: GrowthCurve::Linear,
+                allow_cull: false,
+                weather_clamp: HashMap::new(),
             },
             missions: HashMap::new(),
             types: None,
             weather_types: None,
+            type_pity: HashMap::new(),
+            travel: Default::default(),
+            closures: Default::default(),
+            phases: Default::default(),
+            rewards: Default::default(),
+            streak: Default::default(),
         };
         let tables = SpawnTypeTables::from_cfg(&cfg);
         let pick = choose_spawn_type(&tables, Weather::Clear, 0xDEAD_BEEF, 0);
         assert_eq!(pick, DEFAULT_SPAWN_KIND);
     }
+
+    fn growth_cfg(curve: GrowthCurve) -> DirectorCfg {
+        let mut beta_weather = HashMap::new();
+        beta_weather.insert("Fog".to_string(), 6);
+        DirectorCfg {
+            spawn: SpawnCfg {
+                base: 8,
+                alpha_pp_per_100: 5,
+                beta_weather,
+                growth_cap_per_leg: 8,
+                clamp_min: 2,
+                clamp_max: 40,
+                growth_curve: curve,
+                allow_cull: false,
+                weather_clamp: HashMap::new(),
+            },
+            missions: HashMap::new(),
+            types: None,
+            weather_types: None,
+            type_pity: HashMap::new(),
+            travel: Default::default(),
+            closures: Default::default(),
+            phases: Default::default(),
+            rewards: Default::default(),
+            streak: Default::default(),
+        }
+    }
+
+    fn enemies(cfg: &DirectorCfg, pp: u16, prior: Option<u32>) -> u32 {
+        compute_spawn_budget(Pp(pp), Weather::Fog, prior, cfg).enemies
+    }
+
+    #[test]
+    fn linear_growth_matches_flat_cap() {
+        let parsed: SpawnCfg = toml::from_str(
+            "base = 8\nalpha_pp_per_100 = 5\ngrowth_cap_per_leg = 8\nclamp_min = 2\nclamp_max = 40\n[beta_weather]\n",
+        )
+        .expect("spawn cfg");
+        assert_eq!(parsed.growth_curve, GrowthCurve::Linear);
+        assert!(!parsed.allow_cull);
+        let step_up: SpawnCfg = toml::from_str(
+            "base = 8\nalpha_pp_per_100 = 5\ngrowth_cap_per_leg = 8\nclamp_min = 2\nclamp_max = 40\n[beta_weather]\n[growth_curve]\nkind = \"step_up\"\nthreshold = 12\nboosted_cap = 16\n",
+        )
+        .expect("step-up cfg");
+        assert_eq!(
+            step_up.growth_curve,
+            GrowthCurve::StepUp {
+                threshold: 12,
+                boosted_cap: 16
+            }
+        );
+
+        let cfg = growth_cfg(GrowthCurve::Linear);
+        // Desired at pp 400 in fog: 8 + 5 * 4 + 6 = 34.
+        assert_eq!(enemies(&cfg, 400, None), 34);
+        assert_eq!(enemies(&cfg, 400, Some(10)), 18);
+        assert_eq!(enemies(&cfg, 400, Some(30)), 34);
+        assert_eq!(enemies(&cfg, 0, Some(30)), 30);
+    }
+
+    #[test]
+    fn step_up_boosts_wide_gaps_only() {
+        let cfg = growth_cfg(GrowthCurve::StepUp {
+            threshold: 12,
+            boosted_cap: 16,
+        });
+        assert_eq!(enemies(&cfg, 400, Some(10)), 26);
+        assert_eq!(enemies(&cfg, 400, Some(22)), 30);
+        assert_eq!(enemies(&cfg, 400, Some(30)), 34);
+    }
+
+    #[test]
+    fn geometric_tapers_and_respects_clamp_max() {
+        let cfg = growth_cfg(GrowthCurve::Geometric { pct: 50 });
+        assert_eq!(enemies(&cfg, 400, Some(10)), 22);
+        assert_eq!(enemies(&cfg, 400, Some(22)), 28);
+        assert_eq!(enemies(&cfg, 400, Some(33)), 34);
+
+        for pct in [1, 50, 100, 250, u32::MAX] {
+            let cfg = growth_cfg(GrowthCurve::Geometric { pct });
+            for prior in [0, 2, 20, 39, 40, 500] {
+                for pp in [0, 400, 1200, u16::MAX] {
+                    let got = enemies(&cfg, pp, Some(prior));
+                    assert!(got <= cfg.spawn.clamp_max, "pct {pct} prior {prior}: {got}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn weather_clamp_overrides_the_global_bounds() {
+        let parsed: SpawnCfg = toml::from_str(
+            "base = 8\nalpha_pp_per_100 = 5\ngrowth_cap_per_leg = 8\nclamp_min = 2\nclamp_max = 40\n[beta_weather]\n[weather_clamp]\nFog = [4, 60]\nWindy = [2, 12]\n",
+        )
+        .expect("weather clamp cfg");
+        assert_eq!(parsed.clamp_for(Weather::Fog), (4, 60));
+        assert_eq!(parsed.clamp_for(Weather::Clear), (2, 40));
+
+        let mut cfg = growth_cfg(GrowthCurve::Linear);
+        // Desired at pp 1200 in fog: 8 + 5 * 12 + 6 = 74.
+        assert_eq!(enemies(&cfg, 1200, Some(40)), 40);
+        cfg.spawn.weather_clamp = parsed.weather_clamp;
+        assert_eq!(enemies(&cfg, 1200, Some(40)), 48);
+        assert_eq!(enemies(&cfg, 1200, Some(58)), 60);
+        // Priors above the fog max are pulled down to it before growing.
+        assert_eq!(enemies(&cfg, 1200, Some(500)), 60);
+
+        let windy = compute_spawn_budget(Pp(1200), Weather::Windy, Some(10), &cfg);
+        assert_eq!(windy.enemies, 12);
+        let clear = compute_spawn_budget(Pp(1200), Weather::Clear, Some(40), &cfg);
+        assert_eq!(clear.enemies, 40);
+    }
+
+    #[test]
+    fn culls_pick_the_same_spawns_for_the_same_seed() {
+        let mut first: Vec<u64> = (0..8).collect();
+        let mut second = first.clone();
+        let culled = cull_spawns(&mut first, 5, 0xD7E7, 8);
+        assert_eq!(culled, cull_spawns(&mut second, 5, 0xD7E7, 8));
+        assert_eq!(culled.len(), 5);
+        assert_eq!(first, second);
+        assert_eq!(first.len(), 3);
+        assert!(culled.iter().all(|id| !first.contains(id)));
+
+        let mut short = vec![4, 9];
+        assert_eq!(cull_spawns(&mut short, 5, 1, 0), vec![4, 9]);
+        assert!(short.is_empty());
+    }
 }
@@ -0,0 +1,167 @@
+use std::ops::RangeInclusive;
+
+use serde::Serialize;
+
+use super::config::DirectorCfg;
+use super::rng::{hash_mission_name, spawn_subseed, DetRng};
+use super::spawn::{compute_spawn_budget, danger_score};
+use crate::systems::economy::{Pp, Weather};
+
+/// PP step between sweep samples; one spawn-budget band.
+pub const PP_STEP: u16 = 100;
+
+/// Leg parameters drawn per seed. Ranges bracket the CLI defaults.
+const MISSION_MINUTES: (u32, u32) = (4, 12);
+const DENSITY_PER_10K: (u32, u32) = (3, 10);
+const CADENCE_PER_MIN: (u32, u32) = (2, 6);
+const PLAYER_RATING: (u32, u32) = (30, 70);
+
+/// Danger statistics for one weather. Quantiles use the nearest lower rank,
+/// so every value is an actual sample.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DangerStats {
+    pub weather: String,
+    pub samples: u32,
+    pub min: i32,
+    pub p10: i32,
+    pub p25: i32,
+    pub median: i32,
+    pub p75: i32,
+    pub p90: i32,
+    pub max: i32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SweepReport {
+    pub pp_min: u16,
+    pub pp_max: u16,
+    pub seeds: u32,
+    pub weathers: Vec<DangerStats>,
+}
+
+impl SweepReport {
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string_pretty(self)
+    }
+
+    pub fn for_weather(&self, weather: Weather) -> Option<&DangerStats> {
+        let key = format!("{weather:?}");
+        self.weathers.iter().find(|stats| stats.weather == key)
+    }
+}
+
+/// Scores a fresh leg for every PP step in `pp_range` × `seeds`, per weather.
+/// Each seed draws its own mission length, density, cadence and player
+/// rating, so the spread reflects leg variety rather than a single setup.
+pub fn danger_distribution(
+    pp_range: RangeInclusive<u16>,
+    weathers: &[Weather],
+    seeds: &[u64],
+    cfg: &DirectorCfg,
+) -> SweepReport {
+    let stream = hash_mission_name("danger_sweep");
+    let legs: Vec<(u32, u32, u32, u8)> = seeds
+        .iter()
+        .map(|&seed| {
+            let mut rng = DetRng::from_seed(spawn_subseed(seed, stream));
+            let mut draw = |(low, high): (u32, u32)| rng.range_u32(low, high);
+            (
+                draw(MISSION_MINUTES),
+                draw(DENSITY_PER_10K),
+                draw(CADENCE_PER_MIN),
+                draw(PLAYER_RATING) as u8,
+            )
+        })
+        .collect();
+    let pps: Vec<u16> = pp_range.clone().step_by(usize::from(PP_STEP)).collect();
+
+    let stats = weathers
+        .iter()
+        .map(|&weather| {
+            let mut dangers = Vec::with_capacity(pps.len() * legs.len());
+            for &pp in &pps {
+                let budget = compute_spawn_budget(Pp(pp), weather, None, cfg);
+                for &(minutes, density, cadence, rating) in &legs {
+                    dangers.push(danger_score(&budget, minutes, density, cadence, rating));
+                }
+            }
+            summarize(weather, dangers)
+        })
+        .collect();
+
+    SweepReport {
+        pp_min: *pp_range.start(),
+        pp_max: *pp_range.end(),
+        seeds: seeds.len() as u32,
+        weathers: stats,
+    }
+}
+
+fn summarize(weather: Weather, mut dangers: Vec<i32>) -> DangerStats {
+    dangers.sort_unstable();
+    let rank = |pct: usize| -> i32 {
+        if dangers.is_empty() {
+            0
+        } else {
+            dangers[(dangers.len() - 1) * pct / 100]
+        }
+    };
+    DangerStats {
+        weather: format!("{weather:?}"),
+        samples: dangers.len() as u32,
+        min: rank(0),
+        p10: rank(10),
+        p25: rank(25),
+        median: rank(50),
+        p75: rank(75),
+        p90: rank(90),
+        max: rank(100),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::systems::director::config::load_director_cfg;
+    use crate::systems::director::director_cfg_path;
+
+    const WEATHERS: [Weather; 4] = [Weather::Clear, Weather::Rains, Weather::Fog, Weather::Windy];
+
+    fn cfg() -> DirectorCfg {
+        let path = director_cfg_path();
+        load_director_cfg(path.to_str().expect("cfg path")).expect("director cfg")
+    }
+
+    fn seeds() -> Vec<u64> {
+        (0..32u64).map(|i| 0xD7E7_2024_0000_0000 ^ i).collect()
+    }
+
+    #[test]
+    fn fixed_sweep_is_stable() {
+        let cfg = cfg();
+        let first = danger_distribution(0..=800, &WEATHERS, &seeds(), &cfg);
+        let second = danger_distribution(0..=800, &WEATHERS, &seeds(), &cfg);
+        assert_eq!(first, second);
+        assert_eq!(first.to_json().unwrap(), second.to_json().unwrap());
+
+        assert_eq!(first.weathers.len(), WEATHERS.len());
+        for stats in &first.weathers {
+            assert_eq!(stats.samples, 9 * 32);
+            assert!(stats.min <= stats.p10 && stats.p10 <= stats.p25);
+            assert!(stats.p25 <= stats.median && stats.median <= stats.p75);
+            assert!(stats.p75 <= stats.p90 && stats.p90 <= stats.max);
+        }
+    }
+
+    #[test]
+    fn higher_pp_raises_the_median() {
+        let cfg = cfg();
+        let low = danger_distribution(0..=0, &WEATHERS, &seeds(), &cfg);
+        let high = danger_distribution(400..=400, &WEATHERS, &seeds(), &cfg);
+        for weather in WEATHERS {
+            let low = low.for_weather(weather).expect("low stats");
+            let high = high.for_weather(weather).expect("high stats");
+            assert!(high.median > low.median, "{weather:?}: {low:?} vs {high:?}");
+        }
+    }
+}
@@ -0,0 +1,282 @@
+use std::collections::HashSet;
+
+use thiserror::Error;
+
+use super::config::{ClosureCfg, DirectorCfg, TravelCfg};
+use super::LegContext;
+use crate::app_state::AppState;
+use crate::cli::{
+    DEFAULT_CADENCE_PER_MIN, DEFAULT_DENSITY_PER_10K, DEFAULT_MISSION_MINUTES,
+    DEFAULT_PLAYER_RATING,
+};
+use crate::systems::command_queue::CommandQueue;
+use crate::systems::economy::{EconomyDay, HubId, MoneyCents, RouteId};
+use crate::systems::trading::ledger::{LedgerJournal, LedgerSource, WalletAccess};
+use crate::world::closures::RouteClosures;
+use crate::world::index::{route_destination, StaticWorldIndex, WorldIndex};
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum TravelError {
+    #[error("route {0:?} does not leave hub {1:?}")]
+    NotAdjacent(RouteId, HubId),
+    #[error("route {0:?} is closed")]
+    RouteClosed(RouteId),
+    #[error("toll of {toll:?} exceeds wallet balance {wallet:?}")]
+    InsufficientFunds {
+        toll: MoneyCents,
+        wallet: MoneyCents,
+    },
+}
+
+/// Leg length for a route of `distance_km`, rounded up to whole minutes and
+/// clamped to the configured window. Routes without a distance, or configs
+/// without a rate, keep the default mission length.
+pub fn mission_minutes_for_distance(distance_km: u32, cfg: &TravelCfg) -> u32 {
+    if distance_km == 0 || cfg.km_per_minute == 0 {
+        return DEFAULT_MISSION_MINUTES;
+    }
+    distance_km
+        .div_ceil(cfg.km_per_minute)
+        .clamp(cfg.min_minutes, cfg.max_minutes.max(cfg.min_minutes))
+}
+
+/// Charges the route toll and builds the leg context for travelling along
+/// `route` from the current hub. Nothing is mutated when the route is closed,
+/// does not touch the current hub, or the toll is unaffordable.
+pub fn start_leg_for_route(
+    route: RouteId,
+    app_state: &mut AppState,
+    cfg: &DirectorCfg,
+    closed_routes: &HashSet<RouteId>,
+    journal: &mut LedgerJournal,
+    queue: &mut CommandQueue,
+) -> Result<LegContext, TravelError> {
+    if route_destination::<StaticWorldIndex>(route, app_state.last_hub).is_none() {
+        return Err(TravelError::NotAdjacent(route, app_state.last_hub));
+    }
+    if closed_routes.contains(&route) {
+        return Err(TravelError::RouteClosed(route));
+    }
+    let toll = StaticWorldIndex::route_toll(route);
+    let balance = app_state.wallet.balance();
+    if toll > balance {
+        return Err(TravelError::InsufficientFunds {
+            toll,
+            wallet: balance,
+        });
+    }
+
+    WalletAccess::new(&mut app_state.wallet, journal, queue.current_tick())
+        .debit(LedgerSource::Toll, toll);
+    queue.meter(
+        "route_toll_cents",
+        i32::try_from(toll.as_i64()).unwrap_or(i32::MAX),
+    );
+
+    Ok(LegContext {
+        world_seed: app_state.world_seed,
+        link_id: route,
+        day: app_state.econ.day.0,
+        weather: StaticWorldIndex::route_weather(route),
+        pp: app_state.econ.pp,
+        density_per_10k: DEFAULT_DENSITY_PER_10K,
+        cadence_per_min: DEFAULT_CADENCE_PER_MIN,
+        mission_minutes: mission_minutes_for_distance(
+            StaticWorldIndex::route_distance(route),
+            &cfg.travel,
+        ),
+        player_rating: DEFAULT_PLAYER_RATING,
+        multiplayer: false,
+        prior_danger_score: None,
+        basis_overlay_bp_total: 0,
+    })
+}
+
+/// Closes `route` for `cfg.closed_days` days from `day` when the leg's
+/// `danger_delta` exceeds the configured threshold. Returns `true` when the
+/// closure was created or extended.
+pub fn close_route_after_leg(
+    closures: &mut RouteClosures,
+    cfg: &ClosureCfg,
+    route: RouteId,
+    day: u32,
+    danger_delta: i32,
+) -> bool {
+    if cfg.closed_days == 0 || danger_delta <= cfg.danger_delta_threshold {
+        return false;
+    }
+    closures.close(route, EconomyDay(day.saturating_add(cfg.closed_days)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::systems::director::config::load_director_cfg;
+    use crate::systems::director::director_cfg_path;
+    use crate::systems::economy::Weather;
+    use crate::systems::trading::ledger::Wallet;
+    use repro::CommandKind;
+
+    fn cfg() -> DirectorCfg {
+        let path = director_cfg_path();
+        load_director_cfg(path.to_str().expect("cfg path")).expect("director config")
+    }
+
+    fn state_at_hub_2(wallet: i64) -> AppState {
+        AppState {
+            world_seed: 0xD7E7_2024_0001_0001,
+            last_hub: HubId(2),
+            wallet: Wallet::new(MoneyCents(wallet)),
+            ..AppState::default()
+        }
+    }
+
+    #[test]
+    fn tolled_route_charges_wallet_exactly() {
+        let cfg = cfg();
+        let mut state = state_at_hub_2(10_000);
+        let mut journal = LedgerJournal::default();
+        let mut queue = CommandQueue::default();
+        queue.begin_tick(0);
+
+        let context = start_leg_for_route(
+            RouteId(4),
+            &mut state,
+            &cfg,
+            &HashSet::new(),
+            &mut journal,
+            &mut queue,
+        )
+        .expect("leg starts");
+
+        assert_eq!(StaticWorldIndex::route_toll(RouteId(4)), MoneyCents(2_500));
+        assert_eq!(state.wallet.balance(), MoneyCents(7_500));
+        assert_eq!(journal.net_for(LedgerSource::Toll), -2_500);
+        assert_eq!(context.link_id, RouteId(4));
+        assert_eq!(context.weather, Weather::Rains);
+        assert_eq!(context.mission_minutes, 7);
+        let meters: Vec<_> = queue
+            .drain()
+            .into_iter()
+            .filter_map(|command| match command.kind {
+                CommandKind::Meter(meter) => Some((meter.key.to_string(), meter.value)),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(meters, vec![("route_toll_cents".to_string(), 2_500)]);
+    }
+
+    #[test]
+    fn unaffordable_or_closed_route_leaves_state_untouched() {
+        let cfg = cfg();
+        let mut journal = LedgerJournal::default();
+        let mut queue = CommandQueue::default();
+
+        let mut state = state_at_hub_2(2_499);
+        let err = start_leg_for_route(
+            RouteId(4),
+            &mut state,
+            &cfg,
+            &HashSet::new(),
+            &mut journal,
+            &mut queue,
+        )
+        .expect_err("toll is unaffordable");
+        assert_eq!(
+            err,
+            TravelError::InsufficientFunds {
+                toll: MoneyCents(2_500),
+                wallet: MoneyCents(2_499),
+            }
+        );
+        assert_eq!(state.wallet.balance(), MoneyCents(2_499));
+
+        let mut state = state_at_hub_2(10_000);
+        let closed = HashSet::from([RouteId(4)]);
+        let err = start_leg_for_route(
+            RouteId(4),
+            &mut state,
+            &cfg,
+            &closed,
+            &mut journal,
+            &mut queue,
+        )
+        .expect_err("route is closed");
+        assert_eq!(err, TravelError::RouteClosed(RouteId(4)));
+        assert_eq!(state.wallet.balance(), MoneyCents(10_000));
+
+        let err = start_leg_for_route(
+            RouteId(3),
+            &mut state,
+            &cfg,
+            &HashSet::new(),
+            &mut journal,
+            &mut queue,
+        )
+        .expect_err("route 3 does not touch hub 2");
+        assert_eq!(err, TravelError::NotAdjacent(RouteId(3), HubId(2)));
+        assert!(queue.drain().is_empty());
+        assert!(journal.entries().is_empty());
+    }
+
+    #[test]
+    fn distance_to_minutes_rounds_up_and_clamps() {
+        let travel = TravelCfg {
+            km_per_minute: 20,
+            min_minutes: 2,
+            max_minutes: 10,
+        };
+        assert_eq!(
+            mission_minutes_for_distance(0, &travel),
+            DEFAULT_MISSION_MINUTES
+        );
+        assert_eq!(mission_minutes_for_distance(1, &travel), 2);
+        assert_eq!(mission_minutes_for_distance(60, &travel), 3);
+        assert_eq!(mission_minutes_for_distance(61, &travel), 4);
+        assert_eq!(mission_minutes_for_distance(10_000, &travel), 10);
+        assert_eq!(mission_minutes_for_distance(u32::MAX, &travel), 10);
+        for km in 1..400 {
+            assert!(
+                mission_minutes_for_distance(km, &travel)
+                    <= mission_minutes_for_distance(km + 1, &travel)
+            );
+        }
+    }
+
+    #[test]
+    fn dangerous_leg_closes_route_for_configured_days() {
+        let cfg = ClosureCfg {
+            danger_delta_threshold: 100,
+            closed_days: 3,
+        };
+        let mut closures = RouteClosures::default();
+
+        assert!(!close_route_after_leg(
+            &mut closures,
+            &cfg,
+            RouteId(4),
+            7,
+            100
+        ));
+        assert!(closures.is_empty());
+
+        assert!(close_route_after_leg(
+            &mut closures,
+            &cfg,
+            RouteId(4),
+            7,
+            101
+        ));
+        assert!(closures.is_closed(RouteId(4), EconomyDay(9)));
+        assert!(!closures.is_closed(RouteId(4), EconomyDay(10)));
+
+        let disabled = ClosureCfg::default();
+        assert!(!close_route_after_leg(
+            &mut closures,
+            &disabled,
+            RouteId(5),
+            7,
+            i32::MAX
+        ));
+    }
+}
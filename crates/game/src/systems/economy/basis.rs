@@ -1,5 +1,6 @@
 #![allow(dead_code)]
 
+use super::rng::sample_noise_bp;
 use super::{BasisBp, DetRng, Pp, Rulepack, Weather};
 
 #[derive(Debug, Clone, Copy)]
@@ -15,6 +16,18 @@ pub fn update_basis(
     drivers: &BasisDrivers,
     rp: &Rulepack,
     rng: &mut DetRng,
+) -> BasisBp {
+    update_basis_with_overlay(current, drivers, 0, rp, rng)
+}
+
+/// Like [`update_basis`], adding `overlay_bp` to the daily move ahead of the
+/// clamps.
+pub fn update_basis_with_overlay(
+    current: BasisBp,
+    drivers: &BasisDrivers,
+    overlay_bp: i32,
+    rp: &Rulepack,
+    rng: &mut DetRng,
 ) -> BasisBp {
     let cfg = &rp.basis;
 
@@ -22,12 +35,11 @@ pub fn update_basis(
     let weather_term = weather_impact(cfg, drivers.weather);
     let routes_term = i32::from(drivers.closed_routes) * cfg.beta_routes_bp;
     let stock_term = drivers.stock_dev * cfg.beta_stock_bp;
-    let noise = rng
-        .norm_bounded_bp(0, cfg.noise_sigma_bp, cfg.noise_clamp_bp)
-        .0;
+    let noise = sample_noise_bp(rng, cfg.noise_sigma_bp, cfg.noise_clamp_bp);
 
-    let mut next =
-        current.0 as i64 + (pp_term + weather_term + routes_term + stock_term + noise) as i64;
+    let mut next = current.0 as i64
+        + (pp_term + weather_term + routes_term + stock_term + noise) as i64
+        + overlay_bp as i64;
 
     let delta_cap = cfg.per_day_clamp_bp as i64;
     if delta_cap > 0 {
@@ -2,6 +2,8 @@
 
 use std::collections::HashMap;
 
+use super::order::sorted_commodities;
+use super::rng::sample_noise_bp;
 use super::{rulepack::DiCfg, BasisBp, CommodityId, DetRng, EconomyDay, Rulepack};
 
 #[derive(Debug, Clone, Default)]
@@ -20,18 +22,29 @@ impl DiState {
 }
 
 pub fn step_di(day: EconomyDay, state: &mut DiState, rp: &Rulepack, rng: &mut DetRng) {
+    step_di_with_events(day, state, &HashMap::new(), rp, rng);
+}
+
+/// Like [`step_di`], adding a per-commodity event overlay ahead of the clamps.
+pub fn step_di_with_events(
+    day: EconomyDay,
+    state: &mut DiState,
+    event_bp: &HashMap<CommodityId, i32>,
+    rp: &Rulepack,
+    rng: &mut DetRng,
+) {
     let _ = day;
     let cfg = &rp.di;
-    let mut keys: Vec<CommodityId> = state.per_com.keys().copied().collect();
-    keys.sort_by_key(|id| id.0);
-
-    for commodity in keys {
+    for commodity in sorted_commodities(&state.per_com) {
         let current = state
             .per_com
             .get(&commodity)
             .copied()
             .unwrap_or(BasisBp(cfg.long_run_mean_bp));
-        let next = advance_value(current, state.overlay_bp, cfg, rng);
+        let overlay_bp = state
+            .overlay_bp
+            .saturating_add(event_bp.get(&commodity).copied().unwrap_or(0));
+        let next = advance_value(current, overlay_bp, cfg, rng);
         state.per_com.insert(commodity, BasisBp(next));
     }
 
@@ -43,9 +56,9 @@ fn advance_value(current: BasisBp, overlay_bp: i32, cfg: &DiCfg, rng: &mut DetRn
     let retention = cfg.retention_bp as i64;
     let deviation = current.0 as i64 - mean;
     let retained = deviation * retention / 10_000;
-    let noise = rng.norm_bounded_bp(0, cfg.noise_sigma_bp, cfg.noise_clamp_bp);
+    let noise = sample_noise_bp(rng, cfg.noise_sigma_bp, cfg.noise_clamp_bp);
 
-    let mut next = mean + retained + overlay_bp as i64 + noise.0 as i64;
+    let mut next = mean + retained + overlay_bp as i64 + noise as i64;
     let per_day = cfg.per_day_clamp_bp as i64;
     let current_i64 = current.0 as i64;
     if per_day > 0 {
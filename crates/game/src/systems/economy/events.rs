@@ -0,0 +1,106 @@
+use std::collections::HashMap;
+use std::fs;
+
+use bevy::prelude::Resource;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use super::{CommodityId, EconomyDay, HubId};
+
+/// Scripted market shock applied on a single economy day.
+///
+/// `di_overlay_bp` moves the global daily index and is only valid on global
+/// events (`hub` unset); `basis_overlay_bp` moves the basis of every matching
+/// hub. Both are added before the per-day and absolute clamps run.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct MarketEvent {
+    pub day: EconomyDay,
+    #[serde(default)]
+    pub hub: Option<HubId>,
+    pub commodity: CommodityId,
+    #[serde(default)]
+    pub di_overlay_bp: i32,
+    #[serde(default)]
+    pub basis_overlay_bp: i32,
+}
+
+/// The scripted events the game's day rollover applies, loaded from
+/// `assets/econ/events.toml` next to the rulepack schedule.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Resource)]
+pub struct MarketEvents(pub Vec<MarketEvent>);
+
+impl MarketEvents {
+    pub fn events(&self) -> &[MarketEvent] {
+        &self.0
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct MarketEventsFile {
+    #[serde(default)]
+    events: Vec<MarketEvent>,
+}
+
+#[derive(Debug, Error)]
+pub enum MarketEventError {
+    #[error("failed to read market events: {0}")]
+    Read(#[from] std::io::Error),
+    #[error("failed to parse market events: {0}")]
+    Parse(#[from] toml::de::Error),
+    #[error("market event on day {day} for hub {hub}: di_overlay_bp requires a global event")]
+    HubScopedDi { day: u32, hub: u16 },
+}
+
+pub fn load_market_events(path: &str) -> Result<Vec<MarketEvent>, MarketEventError> {
+    let raw = fs::read_to_string(path)?;
+    parse_market_events(&raw)
+}
+
+pub fn parse_market_events(raw: &str) -> Result<Vec<MarketEvent>, MarketEventError> {
+    let file: MarketEventsFile = toml::from_str(raw)?;
+    for event in &file.events {
+        if let Some(hub) = event.hub {
+            if event.di_overlay_bp != 0 {
+                return Err(MarketEventError::HubScopedDi {
+                    day: event.day.0,
+                    hub: hub.0,
+                });
+            }
+        }
+    }
+    Ok(file.events)
+}
+
+/// Summed DI overlays per commodity for events firing on `day`.
+pub fn di_overlays_for_day(events: &[MarketEvent], day: EconomyDay) -> HashMap<CommodityId, i32> {
+    let mut overlays = HashMap::new();
+    for event in events
+        .iter()
+        .filter(|event| event.day == day && event.hub.is_none())
+    {
+        let entry = overlays.entry(event.commodity).or_insert(0i32);
+        *entry = entry.saturating_add(event.di_overlay_bp);
+    }
+    overlays
+}
+
+/// Summed basis overlay for `commodity` at `hub` from events firing on `day`.
+pub fn basis_overlay_for(
+    events: &[MarketEvent],
+    day: EconomyDay,
+    hub: HubId,
+    commodity: CommodityId,
+) -> i32 {
+    events
+        .iter()
+        .filter(|event| {
+            event.day == day
+                && event.commodity == commodity
+                && event.hub.is_none_or(|target| target == hub)
+        })
+        .fold(0i32, |acc, event| {
+            acc.saturating_add(event.basis_overlay_bp)
+        })
+}
@@ -1,138 +1,115 @@
-#[cfg(feature = "econ_logs")]
-use std::collections::HashMap;
-#[cfg(feature = "econ_logs")]
+//! Economy delta log. Each [`EconDelta`] becomes one canonical-JSON line in
+//! `logs/econ/day_<NNNN>.jsonl`, keyed by the delta's economy day, so the
+//! analytics tooling reads it with the same parser as the m2 logs.
+
 use std::env;
-#[cfg(feature = "econ_logs")]
-use std::fs::{self, OpenOptions};
-#[cfg(feature = "econ_logs")]
-use std::io::{Error as IoError, Write};
-#[cfg(feature = "econ_logs")]
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufRead, BufReader, Lines, Write};
 use std::path::{Path, PathBuf};
-
-#[cfg(feature = "econ_logs")]
-use serde_json::json;
+use std::sync::atomic::{AtomicBool, Ordering};
 
 use super::state::EconDelta;
+use super::EconomyDay;
+
+/// Overrides the log directory, e.g. for sandboxed CI runs.
+pub const LOG_DIR_ENV: &str = "DETTEROT_ECON_LOG_DIR";
+const DEFAULT_LOG_DIR: &str = "logs/econ";
 
-#[cfg(feature = "econ_logs")]
-use super::{compute_price, rulepack::PricingCfg, BasisBp, CommodityId, MoneyCents, Weather};
+static LOGS_ENABLED: AtomicBool = AtomicBool::new(cfg!(feature = "econ_logs"));
 
-#[cfg(feature = "econ_logs")]
-const LOG_BASE_PRICE: MoneyCents = MoneyCents(10_000);
+pub fn set_enabled(enabled: bool) {
+    LOGS_ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+fn enabled() -> bool {
+    LOGS_ENABLED.load(Ordering::Relaxed)
+}
 
-#[cfg(feature = "econ_logs")]
-pub fn log_econ_tick(delta: &EconDelta, pricing: &PricingCfg) {
-    if delta.di.is_empty() {
+/// Directory the economy log is written to: [`LOG_DIR_ENV`] when set,
+/// `logs/econ` otherwise.
+pub fn log_dir() -> PathBuf {
+    env::var_os(LOG_DIR_ENV).map_or_else(|| PathBuf::from(DEFAULT_LOG_DIR), PathBuf::from)
+}
+
+/// Appends `delta` to its day's log when economy logging is on. A failed
+/// write is reported and dropped; it never stops the simulation.
+pub fn log_econ_tick(delta: &EconDelta) {
+    log_econ_tick_in(&log_dir(), delta);
+}
+
+pub(crate) fn log_econ_tick_in(dir: &Path, delta: &EconDelta) {
+    if !enabled() {
         return;
     }
-
-    if let Err(err) = append_entries(delta, pricing) {
-        eprintln!("econ log error: {err}");
+    if let Err(err) = EconLogWriter::new(dir).write(delta) {
+        ::log::warn!("econ log write failed: {err}");
     }
 }
 
-#[cfg(feature = "econ_logs")]
-fn append_entries(delta: &EconDelta, pricing: &PricingCfg) -> std::io::Result<()> {
-    let mut basis_lookup: HashMap<CommodityId, BasisBp> = HashMap::new();
-    for entry in &delta.basis {
-        basis_lookup.insert(entry.commodity, entry.value);
+/// Writes deltas into per-day files under one directory.
+#[derive(Debug, Clone)]
+pub struct EconLogWriter {
+    dir: PathBuf,
+}
+
+impl EconLogWriter {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
     }
 
-    let path = resolve_log_path();
-    if let Some(parent) = path.parent() {
-        if !parent.as_os_str().is_empty() {
-            fs::create_dir_all(parent)?;
-        }
+    pub fn path_for(&self, day: EconomyDay) -> PathBuf {
+        day_log_path(&self.dir, day)
     }
 
-    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
-    for entry in &delta.di {
-        let basis_bp = basis_lookup
-            .get(&entry.commodity)
-            .copied()
-            .unwrap_or(BasisBp(0));
-        let price = compute_price(LOG_BASE_PRICE, entry.value, basis_bp, pricing);
-        let record = json!({
-            "day": delta.day.0,
-            "hub": delta.hub.0,
-            "com": entry.commodity.0,
-            "di_bp": entry.value.0,
-            "basis_bp": basis_bp.0,
-            "drivers": {
-                "pp": delta.pp_after.0,
-                "weather": format!("{:?}", Weather::Clear),
-                "routes": 0,
-                "stock": 0
-            },
-            "clamps_applied": delta.clamps_hit.clone(),
-            "price_cents": price.as_i64()
-        });
-        let line = serde_json::to_string(&record).map_err(json_err)?;
-        writeln!(file, "{line}")?;
+    pub fn write(&self, delta: &EconDelta) -> io::Result<()> {
+        fs::create_dir_all(&self.dir)?;
+        let line = repro::canonical_json_bytes(delta).map_err(io::Error::other)?;
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(self.path_for(delta.day))?;
+        file.write_all(&line)
     }
+}
 
-    Ok(())
+/// `<dir>/day_<NNNN>.jsonl`.
+pub fn day_log_path(dir: &Path, day: EconomyDay) -> PathBuf {
+    dir.join(format!("day_{:04}.jsonl", day.0))
 }
 
-#[cfg(feature = "econ_logs")]
-fn resolve_log_path() -> PathBuf {
-    if let Ok(dir) = env::var("DETTEROT_ECON_LOG_DIR") {
-        PathBuf::from(dir).join("econ_tick.jsonl")
-    } else {
-        Path::new("logs/econ/econ_tick.jsonl").to_path_buf()
-    }
+/// Iterates the deltas in one day's log file, in write order.
+pub struct EconLogReader {
+    lines: Lines<BufReader<File>>,
 }
 
-#[cfg(feature = "econ_logs")]
-fn json_err(err: serde_json::Error) -> IoError {
-    IoError::other(err)
+impl EconLogReader {
+    pub fn open(path: &Path) -> io::Result<Self> {
+        let file = File::open(path)?;
+        Ok(Self {
+            lines: BufReader::new(file).lines(),
+        })
+    }
+
+    pub fn open_day(dir: &Path, day: EconomyDay) -> io::Result<Self> {
+        Self::open(&day_log_path(dir, day))
+    }
 }
 
-#[cfg(not(feature = "econ_logs"))]
-pub fn log_econ_tick(_delta: &EconDelta, _pricing: &super::rulepack::PricingCfg) {}
-
-#[cfg(all(test, feature = "econ_logs"))]
-mod tests {
-    use super::*;
-    use crate::systems::economy::state::CommodityDelta;
-    use crate::systems::economy::{
-        rulepack::PricingCfg, BasisBp, CommodityId, EconomyDay, HubId, MoneyCents, Pp,
-    };
-    use tempfile::tempdir;
-
-    #[test]
-    fn log_shape_smoke() {
-        let dir = tempdir().expect("dir");
-        env::set_var("DETTEROT_ECON_LOG_DIR", dir.path());
-        let delta = EconDelta {
-            day: EconomyDay(0),
-            hub: HubId(1),
-            di: vec![CommodityDelta {
-                commodity: CommodityId(1),
-                value: BasisBp(10),
-            }],
-            basis: vec![CommodityDelta {
-                commodity: CommodityId(1),
-                value: BasisBp(5),
-            }],
-            pp_before: Pp(5000),
-            pp_after: Pp(5000),
-            rot_before: 0,
-            rot_after: 0,
-            debt_before: MoneyCents(0),
-            interest_delta: MoneyCents(0),
-            debt_after: MoneyCents(0),
-            clamps_hit: vec![],
-            rng_cursors: vec![],
-        };
-        let pricing = PricingCfg {
-            min_multiplier_bp: -3_000,
-            max_multiplier_bp: 4_000,
-        };
-        log_econ_tick(&delta, &pricing);
-        let log_path = dir.path().join("econ_tick.jsonl");
-        let data = fs::read_to_string(log_path).expect("log file");
-        assert!(data.contains("\"price_cents\""));
-        env::remove_var("DETTEROT_ECON_LOG_DIR");
+impl Iterator for EconLogReader {
+    type Item = io::Result<EconDelta>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let line = match self.lines.next()? {
+                Ok(line) => line,
+                Err(err) => return Some(Err(err)),
+            };
+            if line.trim().is_empty() {
+                continue;
+            }
+            return Some(
+                repro::from_canonical_json_bytes(line.as_bytes()).map_err(io::Error::other),
+            );
+        }
     }
 }
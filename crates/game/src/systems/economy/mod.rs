@@ -1,21 +1,28 @@
 pub mod basis;
 pub mod di;
+pub mod events;
 pub mod interest;
 pub mod log;
 pub mod money;
+pub mod order;
 pub mod planting;
 pub mod pricing;
 pub mod rng;
 pub mod rot;
 pub mod rounding;
 pub mod rulepack;
+pub mod snapshot;
 pub mod state;
+pub mod stock;
 pub mod types;
+pub mod verify;
 
 #[allow(unused_imports)]
-pub use basis::{update_basis, BasisDrivers};
+pub use basis::{update_basis, update_basis_with_overlay, BasisDrivers};
 #[allow(unused_imports)]
-pub use di::{step_di, DiState};
+pub use di::{step_di, step_di_with_events, DiState};
+#[allow(unused_imports)]
+pub use events::{load_market_events, MarketEvent, MarketEventError, MarketEvents};
 #[allow(unused_imports)]
 pub use interest::accrue_interest_per_leg;
 #[allow(unused_imports)]
@@ -23,20 +30,26 @@ pub use money::MoneyCents;
 #[allow(unused_imports)]
 pub use planting::{apply_planting_pull, schedule_planting, PendingPlanting};
 #[allow(unused_imports)]
-pub use pricing::compute_price;
+pub use pricing::{compute_price, price_clamp, ClampSide, PriceComputation};
 #[allow(unused_imports)]
 pub use rng::DetRng;
 #[allow(unused_imports)]
-pub use rot::convert_rot_to_debt;
+pub use rot::{accrue_spoilage_rot, convert_rot_to_debt};
 #[allow(unused_imports)]
 pub use rounding::{bankers_round_cents, round_down_to_cents};
 #[allow(unused_imports)]
 pub use rulepack::{
-    load_rulepack, BasisCfg, BasisWeatherCfg, DiCfg, InterestCfg, PpCfg, PricingCfg, RotCfg,
-    Rulepack, RulepackError,
+    load_rulepack, BasisCfg, BasisWeatherCfg, DiCfg, InterestAccrual, InterestCfg, PpCfg,
+    PressureCfg, PricingCfg, PricingOverride, ReplenishCfg, RotCfg, Rulepack, RulepackError,
+    RulepackSchedule, RulepackSource, ScheduledRulepack, StockCfg,
+};
+#[allow(unused_imports)]
+pub use state::{
+    accrue_leg_interest, step_economy_day, step_economy_day_with_events, EconDelta, EconState,
+    EconStepScope, LegInterestEvent, TradePressure,
 };
 #[allow(unused_imports)]
-pub use state::{step_economy_day, EconDelta, EconState, EconStepScope};
+pub use stock::{step_npc_flow, StockDelta};
 #[allow(unused_imports)]
 pub use types::{BasisBp, CommodityId, EconomyDay, HubId, Pp, RouteId, Weather};
 
@@ -0,0 +1,23 @@
+//! Canonical iteration order for the economy's hash maps.
+//!
+//! `HashMap` iteration order varies between instances, so anything that
+//! feeds an RNG stream, an [`super::EconDelta`] or a save walks keys through
+//! these helpers instead of iterating the map directly.
+
+use std::collections::HashMap;
+
+use super::{CommodityId, HubId};
+
+/// Keys of a per-commodity map, ascending by commodity id.
+pub fn sorted_commodities<V>(map: &HashMap<CommodityId, V>) -> Vec<CommodityId> {
+    let mut keys: Vec<CommodityId> = map.keys().copied().collect();
+    keys.sort_unstable_by_key(|commodity| commodity.0);
+    keys
+}
+
+/// Keys of a per-(hub, commodity) map, ascending by hub then commodity id.
+pub fn sorted_basis<V>(map: &HashMap<(HubId, CommodityId), V>) -> Vec<(HubId, CommodityId)> {
+    let mut keys: Vec<(HubId, CommodityId)> = map.keys().copied().collect();
+    keys.sort_unstable_by_key(|(hub, commodity)| (hub.0, commodity.0));
+    keys
+}
@@ -1,19 +1,70 @@
 #![allow(dead_code)]
 
-use super::{bankers_round_cents, round_down_to_cents, rulepack::PricingCfg, BasisBp, MoneyCents};
+use serde::Serialize;
+
+use super::{
+    bankers_round_cents, round_down_to_cents,
+    rulepack::{PricingCfg, PricingOverride},
+    BasisBp, MoneyCents,
+};
 
 const BASIS_SCALE: i64 = 10_000;
 const MILLI_CENT_SCALE: i128 = 10;
 
+/// Which multiplier bound a quote was pinned to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum ClampSide {
+    Min,
+    Max,
+}
+
+/// A quote and whether the multiplier clamp changed it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PriceComputation {
+    pub price: MoneyCents,
+    pub clamped: Option<ClampSide>,
+}
+
+/// Multiplier bounds in bp: the override when given, else the global ones.
+fn multiplier_bounds(pricing: &PricingCfg, overrides: Option<&PricingOverride>) -> (i64, i64) {
+    let (min, max) = overrides.map_or(
+        (pricing.min_multiplier_bp, pricing.max_multiplier_bp),
+        |bounds| (bounds.min_multiplier_bp, bounds.max_multiplier_bp),
+    );
+    (i64::from(min), i64::from(max))
+}
+
+/// The bound `di + basis` falls outside of, if any. Sitting exactly on a
+/// bound does not count as a clamp.
+pub fn price_clamp(
+    di: BasisBp,
+    basis: BasisBp,
+    pricing: &PricingCfg,
+    overrides: Option<&PricingOverride>,
+) -> Option<ClampSide> {
+    let drivers_bp = i64::from(di.0).saturating_add(i64::from(basis.0));
+    let (min_multiplier_bp, max_multiplier_bp) = multiplier_bounds(pricing, overrides);
+    if drivers_bp < min_multiplier_bp {
+        Some(ClampSide::Min)
+    } else if drivers_bp > max_multiplier_bp {
+        Some(ClampSide::Max)
+    } else {
+        None
+    }
+}
+
+/// Prices `base` under `di + basis`, clamped to the commodity's bounds:
+/// `overrides` when the rulepack has one for it, else the global
+/// [`PricingCfg`] bounds.
 pub fn compute_price(
     base: MoneyCents,
     di: BasisBp,
     basis: BasisBp,
     pricing: &PricingCfg,
-) -> MoneyCents {
+    overrides: Option<&PricingOverride>,
+) -> PriceComputation {
     let drivers_bp = i64::from(di.0).saturating_add(i64::from(basis.0));
-    let min_multiplier_bp = i64::from(pricing.min_multiplier_bp);
-    let max_multiplier_bp = i64::from(pricing.max_multiplier_bp);
+    let (min_multiplier_bp, max_multiplier_bp) = multiplier_bounds(pricing, overrides);
     let clamped_drivers_bp = drivers_bp.clamp(min_multiplier_bp, max_multiplier_bp);
 
     let multiplier = BASIS_SCALE.saturating_add(clamped_drivers_bp);
@@ -36,5 +87,9 @@ pub fn compute_price(
 
     // Final floor ensures we never carry residuals beyond a cent even if
     // future changes tweak the rounding scheme.
-    round_down_to_cents(i128::from(rounded.as_i64()) * MILLI_CENT_SCALE)
+    let price = round_down_to_cents(i128::from(rounded.as_i64()) * MILLI_CENT_SCALE);
+    PriceComputation {
+        price,
+        clamped: price_clamp(di, basis, pricing, overrides),
+    }
 }
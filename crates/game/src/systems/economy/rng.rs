@@ -13,6 +13,7 @@ pub struct DetRng {
 }
 
 impl DetRng {
+    #[deprecated(note = "use `RngStream::new`, or `RngStream::econ_day` for existing econ seeds")]
     pub fn from_seed(
         world_seed: u64,
         econ_version: u32,
@@ -20,14 +21,17 @@ impl DetRng {
         day: EconomyDay,
         tag: u32,
     ) -> Self {
-        Self::from_seed_inner(world_seed, econ_version, Some(hub), day, tag)
+        Self::from_legacy_parts(world_seed, econ_version, Some(hub), day, tag)
     }
 
+    #[deprecated(note = "use `RngStream::new`, or `RngStream::econ_day` for existing econ seeds")]
     pub fn from_seed_global(world_seed: u64, econ_version: u32, day: EconomyDay, tag: u32) -> Self {
-        Self::from_seed_inner(world_seed, econ_version, None, day, tag)
+        Self::from_legacy_parts(world_seed, econ_version, None, day, tag)
     }
 
-    fn from_seed_inner(
+    /// The `det_rng_v1` derivation every econ stream used before
+    /// [`RngStream`](crate::systems::rng_stream::RngStream).
+    pub(crate) fn from_legacy_parts(
         world_seed: u64,
         econ_version: u32,
         hub: Option<HubId>,
@@ -43,11 +47,12 @@ impl DetRng {
         }
         hasher.update(&day.0.to_le_bytes());
         hasher.update(&tag.to_le_bytes());
-        let hash = hasher.finalize();
-        let mut seed = [0u8; 32];
-        seed.copy_from_slice(hash.as_bytes());
+        Self::from_hash(hasher.finalize())
+    }
+
+    pub(crate) fn from_hash(hash: blake3::Hash) -> Self {
         Self {
-            rng: Xoshiro256PlusPlus::from_seed(seed),
+            rng: Xoshiro256PlusPlus::from_seed(*hash.as_bytes()),
             draws: 0,
         }
     }
@@ -58,6 +63,16 @@ impl DetRng {
     }
 
     pub fn norm_bounded_bp(&mut self, mu_bp: i32, sigma_bp: u32, clamp_bp: i32) -> BasisBp {
+        let value = mu_bp as i64 + self.scaled_irwin_hall(sigma_bp);
+        let clamp = clamp_bp.unsigned_abs() as i64;
+        let clamped = value.clamp(-clamp, clamp);
+
+        BasisBp(clamped as i32)
+    }
+
+    /// Sum of six centred 16-bit uniforms scaled to a standard deviation of
+    /// roughly `sigma_bp`. Always consumes six draws.
+    fn scaled_irwin_hall(&mut self, sigma_bp: u32) -> i64 {
         const SAMPLE_COUNT: usize = 6;
         const SAMPLE_MASK: u32 = 0xFFFF;
         const SAMPLE_HALF: i64 = (SAMPLE_MASK as i64 + 1) / 2; // 32768
@@ -69,20 +84,25 @@ impl DetRng {
             acc += draw;
         }
 
-        let scaled = if sigma_bp == 0 {
+        if sigma_bp == 0 {
             0
         } else {
             (acc * sigma_bp as i64) / NORMALIZER
-        };
-
-        let value = mu_bp as i64 + scaled;
-        let clamp = clamp_bp.unsigned_abs() as i64;
-        let clamped = value.clamp(-clamp, clamp);
-
-        BasisBp(clamped as i32)
+        }
     }
 
     pub fn cursor(&self) -> u32 {
         self.draws
     }
 }
+
+/// Zero-mean noise shock shared by the DI and basis steps.
+///
+/// Sums six 16-bit uniform draws centred on zero (Irwin-Hall), scales the
+/// total so its standard deviation is roughly `sigma_bp`, then clamps to
+/// `±clamp_bp`. Integer-only, and always consumes exactly six draws so the
+/// RNG cursor does not depend on the configuration.
+pub fn sample_noise_bp(rng: &mut DetRng, sigma_bp: u32, clamp_bp: i32) -> i32 {
+    let clamp = i64::from(clamp_bp.unsigned_abs());
+    rng.scaled_irwin_hall(sigma_bp).clamp(-clamp, clamp) as i32
+}
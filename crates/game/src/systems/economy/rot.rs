@@ -1,6 +1,6 @@
 #![allow(dead_code)]
 
-use super::{MoneyCents, RotCfg};
+use super::{CommodityId, MoneyCents, RotCfg};
 
 pub fn convert_rot_to_debt(rot_u16: u16, cfg: &RotCfg) -> (u16, MoneyCents) {
     let clamped = rot_u16.clamp(cfg.rot_floor, cfg.rot_ceiling);
@@ -22,3 +22,11 @@ pub fn convert_rot_to_debt(rot_u16: u16, cfg: &RotCfg) -> (u16, MoneyCents) {
         MoneyCents::from_i128_clamped(i128::from(cfg.debt_per_chunk_cents) * i128::from(chunks));
     (rot_after, debt_delta)
 }
+
+/// Adds one raw ROT unit per spoiled cargo unit, clamped to the ceiling.
+pub fn accrue_spoilage_rot(rot_u16: u16, spoiled: &[(CommodityId, u32)], cfg: &RotCfg) -> u16 {
+    let lost: u64 = spoiled.iter().map(|(_, units)| u64::from(*units)).sum();
+    let total = u64::from(rot_u16).saturating_add(lost);
+    let clamped = total.min(u64::from(cfg.rot_ceiling.max(rot_u16)));
+    u16::try_from(clamped).unwrap_or(u16::MAX)
+}
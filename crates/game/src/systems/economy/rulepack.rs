@@ -1,13 +1,18 @@
 #![allow(dead_code)]
 
+use std::collections::BTreeMap;
 use std::fs;
+use std::path::{Path, PathBuf};
 
-use bevy::prelude::Resource;
+use bevy::prelude::*;
 use blake3::Hasher;
 use log::info;
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
+use super::{CommodityId, EconomyDay};
+use crate::app_state::AppState;
+
 /// Collection of economy tuning parameters loaded from a TOML rulepack.
 ///
 /// Each sub-structure focuses on one subsystem (daily index, basis, player
@@ -30,6 +35,12 @@ pub struct Rulepack {
     pub pp: PpCfg,
     /// Pricing multiplier bounds expressed in basis points.
     pub pricing: PricingCfg,
+    /// Trade pressure decay and its weight in the basis stock driver.
+    #[serde(default)]
+    pub pressure: PressureCfg,
+    /// NPC supply and demand that moves hub stock each day.
+    #[serde(default)]
+    pub stock: StockCfg,
 }
 
 /// Configuration for the Daily Index (DI) that anchors commodity price levels.
@@ -115,6 +126,20 @@ pub struct InterestCfg {
     pub convex_gamma_q16: u32,
     /// Maximum total spread per leg after combining all terms, in bp.
     pub per_leg_cap_bp: i32,
+    /// When interest accrues; econ days unless the rulepack opts into legs.
+    #[serde(default)]
+    pub accrue_on: InterestAccrual,
+}
+
+/// Cadence at which [`InterestCfg`] spreads are charged against debt.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum InterestAccrual {
+    /// Once per simulated econ day, inside `step_economy_day`.
+    #[default]
+    Day,
+    /// Once per completed leg, whatever its outcome.
+    Leg,
 }
 
 /// Rotation (ROT) tracking that converts surplus production into debt relief.
@@ -163,6 +188,105 @@ pub struct PricingCfg {
     pub min_multiplier_bp: i32,
     /// Maximum allowed multiplier (e.g., 4000 bp = 40% premium).
     pub max_multiplier_bp: i32,
+    /// Tighter bounds for individual commodities, keyed by commodity id.
+    #[serde(default, deserialize_with = "deserialize_overrides")]
+    pub overrides: BTreeMap<CommodityId, PricingOverride>,
+}
+
+/// TOML table keys are always strings, so parse the commodity ids here.
+fn deserialize_overrides<'de, D>(
+    deserializer: D,
+) -> Result<BTreeMap<CommodityId, PricingOverride>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let raw = BTreeMap::<String, PricingOverride>::deserialize(deserializer)?;
+    raw.into_iter()
+        .map(|(key, bounds)| {
+            key.parse::<u16>()
+                .map(|id| (CommodityId(id), bounds))
+                .map_err(|_| {
+                    serde::de::Error::custom(format!(
+                        "pricing override key {key:?} is not a commodity id"
+                    ))
+                })
+        })
+        .collect()
+}
+
+/// Per-commodity multiplier bounds; must sit inside the global
+/// [`PricingCfg`] bounds with `min_multiplier_bp < max_multiplier_bp`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PricingOverride {
+    pub min_multiplier_bp: i32,
+    pub max_multiplier_bp: i32,
+}
+
+impl PricingCfg {
+    /// The override for `com`, if the rulepack sets one.
+    pub fn override_for(&self, com: CommodityId) -> Option<&PricingOverride> {
+        self.overrides.get(&com)
+    }
+
+    fn validate(&self) -> Result<(), RulepackError> {
+        for (com, bounds) in &self.overrides {
+            if bounds.min_multiplier_bp >= bounds.max_multiplier_bp
+                || bounds.min_multiplier_bp < self.min_multiplier_bp
+                || bounds.max_multiplier_bp > self.max_multiplier_bp
+            {
+                return Err(RulepackError::PricingOverride {
+                    commodity: com.0,
+                    min: bounds.min_multiplier_bp,
+                    max: bounds.max_multiplier_bp,
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Demand pressure left by trades. Buying pushes a hub's pressure up and
+/// selling pulls it down; the pressure feeds the basis `stock_dev` driver
+/// and decays toward zero each day. The default disables both.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PressureCfg {
+    /// Share of the remaining pressure shed per day (bp; 10_000 = all).
+    pub decay_bp: i32,
+    /// Traded units that move `stock_dev` by one point; zero disables the
+    /// basis effect.
+    pub units_per_stock_pct: u32,
+}
+
+/// Hub stock tuning. Only the NPC flow is configurable for now.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct StockCfg {
+    #[serde(default)]
+    pub replenish: ReplenishCfg,
+}
+
+/// Daily NPC flow at each hub: stock drifts back toward `equilibrium_units`
+/// and NPC buyers draw some of it down. The default disables both.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ReplenishCfg {
+    /// Units of each commodity a hub holds when left alone.
+    pub equilibrium_units: u32,
+    /// Share of the gap to equilibrium closed per day (bp; 10_000 = all).
+    pub rate_bp: i32,
+    /// Cap on the units the drift moves per day, in either direction.
+    pub max_units_per_day: u32,
+    /// Most units NPCs buy of each commodity per hub and day; zero disables
+    /// demand.
+    pub demand_max_units: u32,
+}
+
+impl ReplenishCfg {
+    pub fn is_active(&self) -> bool {
+        (self.rate_bp > 0 && self.max_units_per_day > 0) || self.demand_max_units > 0
+    }
 }
 
 #[derive(Debug, Error)]
@@ -173,11 +297,22 @@ pub enum RulepackError {
     Parse(#[from] toml::de::Error),
     #[error("failed to hash rulepack: {0}")]
     Hash(#[from] serde_json::Error),
+    #[error("rulepack schedule lists no rulepacks")]
+    EmptySchedule,
+    #[error("rulepack schedule must start at day 0, not day {0}")]
+    ScheduleStart(u32),
+    #[error("rulepack schedule days must increase: day {day} follows day {previous}")]
+    ScheduleOrder { day: u32, previous: u32 },
+    #[error(
+        "pricing override for commodity {commodity} ({min}..{max} bp) must have min < max inside the global bounds"
+    )]
+    PricingOverride { commodity: u16, min: i32, max: i32 },
 }
 
 pub fn load_rulepack(path: &str) -> Result<Rulepack, RulepackError> {
     let raw = fs::read_to_string(path)?;
     let rulepack: Rulepack = toml::from_str(&raw)?;
+    rulepack.pricing.validate()?;
     log_schema_hash(&rulepack)?;
     Ok(rulepack)
 }
@@ -190,3 +325,140 @@ fn log_schema_hash(rulepack: &Rulepack) -> Result<(), RulepackError> {
     info!("rulepack_schema_hash={}", hash.to_hex());
     Ok(())
 }
+
+/// Where the economy step reads its tuning for a given day: a single
+/// [`Rulepack`] for every day, or a [`RulepackSchedule`].
+pub trait RulepackSource {
+    fn rulepack_for(&self, day: EconomyDay) -> &Rulepack;
+}
+
+impl RulepackSource for Rulepack {
+    fn rulepack_for(&self, _day: EconomyDay) -> &Rulepack {
+        self
+    }
+}
+
+/// One schedule entry, in effect from `from_day` until the next entry.
+#[derive(Debug, Clone)]
+pub struct ScheduledRulepack {
+    pub from_day: EconomyDay,
+    /// File name as written in the schedule, relative to it.
+    pub file: String,
+    pub path: PathBuf,
+    pub rulepack: Rulepack,
+}
+
+/// Rulepacks by economy day, loaded from `assets/rulepacks/schedule.toml`.
+#[derive(Debug, Clone, Resource)]
+pub struct RulepackSchedule {
+    entries: Vec<ScheduledRulepack>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ScheduleFile {
+    rulepack: Vec<ScheduleEntry>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ScheduleEntry {
+    from_day: u32,
+    file: String,
+}
+
+impl RulepackSchedule {
+    /// Loads the schedule and every rulepack it names. Entries must start at
+    /// day 0 and be listed in increasing day order.
+    pub fn load(path: &Path) -> Result<Self, RulepackError> {
+        let raw = fs::read_to_string(path)?;
+        let file: ScheduleFile = toml::from_str(&raw)?;
+        let dir = path.parent().unwrap_or_else(|| Path::new(""));
+        let mut entries: Vec<ScheduledRulepack> = Vec::with_capacity(file.rulepack.len());
+        for entry in file.rulepack {
+            match entries.last() {
+                None if entry.from_day != 0 => {
+                    return Err(RulepackError::ScheduleStart(entry.from_day));
+                }
+                Some(previous) if entry.from_day <= previous.from_day.0 => {
+                    return Err(RulepackError::ScheduleOrder {
+                        day: entry.from_day,
+                        previous: previous.from_day.0,
+                    });
+                }
+                _ => {}
+            }
+            let rulepack_path = dir.join(&entry.file);
+            let rulepack = load_rulepack(&rulepack_path.to_string_lossy())?;
+            entries.push(ScheduledRulepack {
+                from_day: EconomyDay(entry.from_day),
+                file: entry.file,
+                path: rulepack_path,
+                rulepack,
+            });
+        }
+        if entries.is_empty() {
+            return Err(RulepackError::EmptySchedule);
+        }
+        Ok(Self { entries })
+    }
+
+    /// A schedule that applies `rulepack` to every day.
+    pub fn fixed(file: impl Into<String>, path: PathBuf, rulepack: Rulepack) -> Self {
+        Self {
+            entries: vec![ScheduledRulepack {
+                from_day: EconomyDay(0),
+                file: file.into(),
+                path,
+                rulepack,
+            }],
+        }
+    }
+
+    pub fn entry_for(&self, day: EconomyDay) -> &ScheduledRulepack {
+        let idx = self
+            .entries
+            .partition_point(|entry| entry.from_day <= day)
+            .saturating_sub(1);
+        &self.entries[idx]
+    }
+
+    pub fn for_day(&self, day: EconomyDay) -> &Rulepack {
+        &self.entry_for(day).rulepack
+    }
+
+    pub fn entries(&self) -> &[ScheduledRulepack] {
+        &self.entries
+    }
+}
+
+impl RulepackSource for RulepackSchedule {
+    fn rulepack_for(&self, day: EconomyDay) -> &Rulepack {
+        self.for_day(day)
+    }
+}
+
+/// Replaces the [`Rulepack`] resource when the economy day moves into
+/// another schedule entry.
+pub fn swap_rulepack_on_day_change(
+    schedule: Res<RulepackSchedule>,
+    app_state: Option<Res<AppState>>,
+    mut last_day: Local<Option<EconomyDay>>,
+    mut rulepack: ResMut<Rulepack>,
+) {
+    let Some(app_state) = app_state else {
+        return;
+    };
+    let day = app_state.econ.day;
+    let Some(previous) = last_day.replace(day) else {
+        return;
+    };
+    if previous == day {
+        return;
+    }
+    let entry = schedule.entry_for(day);
+    if entry.from_day != schedule.entry_for(previous).from_day {
+        info!("economy day {} switches rulepack to {}", day.0, entry.file);
+        *rulepack = entry.rulepack.clone();
+    }
+}
@@ -0,0 +1,81 @@
+//! Read-only market dump: every (hub, commodity) quote at the current day.
+
+use std::fmt;
+
+use serde::Serialize;
+
+use super::order::sorted_commodities;
+use super::{BasisBp, ClampSide, CommodityId, EconState, EconomyDay, HubId, MoneyCents, Rulepack};
+use crate::systems::trading::pricing_vm::price_view;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct MarketRow {
+    pub hub: HubId,
+    pub commodity: CommodityId,
+    pub di_bp: BasisBp,
+    pub basis_bp: BasisBp,
+    pub price_cents: MoneyCents,
+    pub clamped: Option<ClampSide>,
+}
+
+/// Quotes for every requested hub, sorted by hub then commodity, with a
+/// blake3 hash of the rows for comparing dumps.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MarketTable {
+    pub day: EconomyDay,
+    pub rows: Vec<MarketRow>,
+    pub hash: String,
+}
+
+/// Prices every commodity `econ` tracks at each of `hubs`, the same way the
+/// hub trade screen quotes them. Duplicate hubs are listed once.
+pub fn market_table(hubs: &[HubId], econ: &EconState, rp: &Rulepack) -> MarketTable {
+    let mut hubs = hubs.to_vec();
+    hubs.sort_unstable_by_key(|hub| hub.0);
+    hubs.dedup();
+    let commodities = sorted_commodities(&econ.di_bp);
+    let mut rows = Vec::with_capacity(hubs.len() * commodities.len());
+    for hub in hubs {
+        for &commodity in &commodities {
+            let view = price_view(hub, commodity, econ, rp);
+            rows.push(MarketRow {
+                hub,
+                commodity,
+                di_bp: view.di_bp,
+                basis_bp: view.basis_bp,
+                price_cents: view.price_cents,
+                clamped: view.clamped,
+            });
+        }
+    }
+    let bytes = serde_json::to_vec(&rows).expect("market rows serialize");
+    MarketTable {
+        day: econ.day,
+        rows,
+        hash: blake3::hash(&bytes).to_hex().to_string(),
+    }
+}
+
+impl fmt::Display for MarketTable {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "day,hub,com,di_bp,basis_bp,price_cents,clamped")?;
+        for row in &self.rows {
+            let clamped = match row.clamped {
+                Some(ClampSide::Min) => "min",
+                Some(ClampSide::Max) => "max",
+                None => "",
+            };
+            writeln!(
+                f,
+                "{},{},{},{},{},{},{clamped}",
+                self.day.0,
+                row.hub.0,
+                row.commodity.0,
+                row.di_bp.0,
+                row.basis_bp.0,
+                row.price_cents.as_i64()
+            )?;
+        }
+        write!(f, "# blake3 {}", self.hash)
+    }
+}
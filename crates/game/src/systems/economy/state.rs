@@ -1,24 +1,40 @@
 #![allow(dead_code)]
 
 use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
 
 use serde::{Deserialize, Serialize};
 
 use super::{
-    basis::{update_basis, BasisDrivers},
-    di::{step_di, DiState},
+    basis::{update_basis_with_overlay, BasisDrivers},
+    di::{step_di_with_events, DiState},
+    events::{basis_overlay_for, di_overlays_for_day, MarketEvent},
     interest::accrue_interest_per_leg,
     log,
+    order::sorted_commodities,
     planting::apply_planting_pull,
+    pricing::price_clamp,
     rot::convert_rot_to_debt,
-    BasisBp, CommodityId, DetRng, EconomyDay, HubId, MoneyCents, Pp, Rulepack, Weather,
+    rulepack::{InterestAccrual, InterestCfg, PressureCfg, ReplenishCfg, RulepackSource},
+    stock::{step_npc_flow, StockDelta},
+    BasisBp, CommodityId, EconomyDay, HubId, MoneyCents, Pp, RouteId, Weather,
 };
 
 use super::planting::PendingPlanting;
+use crate::systems::command_queue::CommandQueue;
+use crate::systems::rng_stream::RngStream;
+use crate::systems::trading::ledger::{LedgerSource, WalletAccess};
 
 const RNG_TAG_DI: u32 = 0;
 const RNG_TAG_BASIS: u32 = 1;
 
+/// Number of daily price samples kept per (hub, commodity) for charting.
+pub const PRICE_HISTORY_DAYS: usize = 14;
+
+/// Bound on the net traded units a hub/commodity pair can accumulate as
+/// pressure, in either direction.
+pub const TRADE_PRESSURE_LIMIT: i32 = 10_000;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EconState {
     pub day: EconomyDay,
@@ -27,10 +43,165 @@ pub struct EconState {
     pub basis_bp: HashMap<(HubId, CommodityId), BasisBp>,
     #[serde(skip)]
     pub basis_drivers: HashMap<HubId, BasisDrivers>,
+    /// Closed routes touching each hub, set by the caller before stepping.
+    /// Feeds the basis `closed_routes` driver.
+    #[serde(skip)]
+    pub closed_routes: HashMap<HubId, u8>,
     pub pp: Pp,
     pub rot_u16: u16,
     pub pending_planting: Vec<PendingPlanting>,
     pub debt_cents: MoneyCents,
+    /// Newest-last `di + basis` samples, one per stepped day, bounded to
+    /// [`PRICE_HISTORY_DAYS`].
+    pub price_history: HashMap<(HubId, CommodityId), Vec<BasisBp>>,
+    /// Demand pressure per hub and commodity. Settled entries are dropped.
+    #[serde(default)]
+    pub trade_pressure: HashMap<(HubId, CommodityId), TradePressure>,
+    /// Units each hub holds above (positive) or below its equilibrium stock,
+    /// moved by trades and the daily NPC flow. Pairs at equilibrium are
+    /// dropped.
+    #[serde(default)]
+    pub stock_offset: HashMap<(HubId, CommodityId), i32>,
+    /// Changes whenever prices may have moved: every economy step and any
+    /// overlay applied outside one. Values come from a process-wide counter,
+    /// so separately built or loaded states never share one. Not saved.
+    #[serde(skip, default = "fresh_revision")]
+    pub revision: u64,
+}
+
+static NEXT_REVISION: AtomicU64 = AtomicU64::new(1);
+
+fn fresh_revision() -> u64 {
+    NEXT_REVISION.fetch_add(1, Ordering::Relaxed)
+}
+
+/// Net units bought (positive) or sold (negative) and not yet decayed, plus
+/// the level already reflected in the basis. Both are bounded to
+/// [`TRADE_PRESSURE_LIMIT`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TradePressure {
+    pub units: i32,
+    pub priced: i32,
+}
+
+impl TradePressure {
+    fn is_settled(&self) -> bool {
+        self.units == 0 && self.priced == 0
+    }
+
+    fn stock_dev(&self, cfg: &PressureCfg) -> i32 {
+        match cfg.units_per_stock_pct {
+            0 => 0,
+            per_pct => {
+                let per_pct = per_pct.min(i32::MAX as u32) as i32;
+                self.units / per_pct - self.priced / per_pct
+            }
+        }
+    }
+
+    /// Sheds `decay_bp` of the pressure, moving at least one unit toward
+    /// zero so entries always drain.
+    fn decay(&mut self, cfg: &PressureCfg) {
+        if cfg.decay_bp <= 0 {
+            return;
+        }
+        let current = i64::from(self.units);
+        let mut shed = current * i64::from(cfg.decay_bp.min(10_000)) / 10_000;
+        if shed == 0 {
+            shed = current.signum();
+        }
+        self.units = (current - shed) as i32;
+    }
+}
+
+impl EconState {
+    /// Marks prices as changed. Call after mutating DI, basis or overlays
+    /// outside [`step_economy_day`].
+    pub fn bump_revision(&mut self) {
+        self.revision = fresh_revision();
+    }
+
+    /// Adds a trade of `signed_units` (positive for buys) to the pressure on
+    /// `com` at `hub` and moves the hub's stock the other way. Prices only
+    /// move on the next economy step.
+    pub fn apply_trade_pressure(&mut self, hub: HubId, com: CommodityId, signed_units: i32) {
+        self.add_trade_pressure(hub, com, signed_units);
+        self.shift_stock(hub, com, signed_units.saturating_neg());
+    }
+
+    pub(super) fn add_trade_pressure(&mut self, hub: HubId, com: CommodityId, signed_units: i32) {
+        let pressure = self.trade_pressure.entry((hub, com)).or_default();
+        pressure.units = pressure
+            .units
+            .saturating_add(signed_units)
+            .clamp(-TRADE_PRESSURE_LIMIT, TRADE_PRESSURE_LIMIT);
+        if pressure.is_settled() {
+            self.trade_pressure.remove(&(hub, com));
+        }
+    }
+
+    pub fn trade_pressure(&self, hub: HubId, com: CommodityId) -> i32 {
+        self.trade_pressure
+            .get(&(hub, com))
+            .map_or(0, |pressure| pressure.units)
+    }
+
+    /// Units of `com` at `hub` above (positive) or below equilibrium.
+    pub fn stock_offset(&self, hub: HubId, com: CommodityId) -> i32 {
+        self.stock_offset.get(&(hub, com)).copied().unwrap_or(0)
+    }
+
+    /// Units of `com` on hand at `hub` under `cfg`'s equilibrium.
+    pub fn hub_stock(&self, hub: HubId, com: CommodityId, cfg: &ReplenishCfg) -> u32 {
+        let units = i64::from(cfg.equilibrium_units) + i64::from(self.stock_offset(hub, com));
+        units.clamp(0, i64::from(u32::MAX)) as u32
+    }
+
+    /// Moves the stock offset of `com` at `hub` by `units`, bounded to
+    /// [`TRADE_PRESSURE_LIMIT`] either way.
+    pub(super) fn shift_stock(&mut self, hub: HubId, com: CommodityId, units: i32) {
+        let offset = self
+            .stock_offset(hub, com)
+            .saturating_add(units)
+            .clamp(-TRADE_PRESSURE_LIMIT, TRADE_PRESSURE_LIMIT);
+        if offset == 0 {
+            self.stock_offset.remove(&(hub, com));
+        } else {
+            self.stock_offset.insert((hub, com), offset);
+        }
+    }
+
+    /// Returns the `stock_dev` move for `com` at `hub` since the last step,
+    /// marks the current pressure as priced in and then sheds `decay_bp` of
+    /// it. Feeding only the change keeps the basis effect proportional to the
+    /// outstanding pressure, so it fades as the pressure drains.
+    fn settle_trade_pressure(&mut self, hub: HubId, com: CommodityId, cfg: &PressureCfg) -> i32 {
+        let Some(pressure) = self.trade_pressure.get_mut(&(hub, com)) else {
+            return 0;
+        };
+        let stock_dev = pressure.stock_dev(cfg);
+        pressure.priced = pressure.units;
+        pressure.decay(cfg);
+        if pressure.is_settled() {
+            self.trade_pressure.remove(&(hub, com));
+        }
+        stock_dev
+    }
+
+    /// Recent combined price drivers for `commodity` at `hub`, oldest first.
+    pub fn price_history(&self, hub: HubId, commodity: CommodityId) -> &[BasisBp] {
+        self.price_history
+            .get(&(hub, commodity))
+            .map(Vec::as_slice)
+            .unwrap_or(&[])
+    }
+
+    fn record_price_sample(&mut self, hub: HubId, commodity: CommodityId, sample: BasisBp) {
+        let samples = self.price_history.entry((hub, commodity)).or_default();
+        samples.push(sample);
+        let excess = samples.len().saturating_sub(PRICE_HISTORY_DAYS);
+        samples.drain(..excess);
+    }
 }
 
 impl Default for EconState {
@@ -41,15 +212,20 @@ impl Default for EconState {
             di_overlay_bp: 0,
             basis_bp: HashMap::new(),
             basis_drivers: HashMap::new(),
+            closed_routes: HashMap::new(),
             pp: Pp(0),
             rot_u16: 0,
             pending_planting: Vec::new(),
             debt_cents: MoneyCents::ZERO,
+            price_history: HashMap::new(),
+            trade_pressure: HashMap::new(),
+            stock_offset: HashMap::new(),
+            revision: fresh_revision(),
         }
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq)]
 pub struct EconDelta {
     pub day: EconomyDay,
     pub hub: HubId,
@@ -63,10 +239,18 @@ pub struct EconDelta {
     pub interest_delta: MoneyCents,
     pub debt_after: MoneyCents,
     pub clamps_hit: Vec<String>,
+    /// Commodities at this hub whose quote multiplier is clamped after the
+    /// step.
+    #[serde(default)]
+    pub price_clamp_hits: u32,
+    /// NPC supply and demand at this hub, run after its basis step. Empty
+    /// when the rulepack disables the flow, and then left out of the JSON.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub stock: Vec<StockDelta>,
     pub rng_cursors: Vec<RngCursor>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct CommodityDelta {
     pub commodity: CommodityId,
     pub value: BasisBp,
@@ -79,6 +263,11 @@ pub struct RngCursor {
 }
 
 impl EconDelta {
+    /// Meters `price_clamp_hits` for this step.
+    pub fn meter_price_clamps(&self, queue: &mut CommandQueue) {
+        queue.meter_units("price_clamp_hits", self.price_clamp_hits);
+    }
+
     fn new(day: EconomyDay, hub: HubId) -> Self {
         Self {
             day,
@@ -88,6 +277,57 @@ impl EconDelta {
     }
 }
 
+/// Interest charged when a leg completes under [`InterestAccrual::Leg`]; the
+/// leg-cadence counterpart of [`EconDelta::interest_delta`].
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub struct LegInterestEvent {
+    pub day: EconomyDay,
+    pub route: RouteId,
+    pub debt_before: MoneyCents,
+    pub interest_delta: MoneyCents,
+    /// Part of `interest_delta` paid out of the wallet.
+    pub paid_cents: MoneyCents,
+    /// Part of `interest_delta` the wallet could not cover, added to debt.
+    pub shortfall_cents: MoneyCents,
+    pub debt_after: MoneyCents,
+}
+
+/// Accrues one leg of interest on `state.debt_cents` when `cfg` accrues per
+/// leg. Returns `None` in day mode, where [`step_economy_day`] owns accrual.
+///
+/// The interest is billed to `wallet` as [`LedgerSource::Interest`] up to its
+/// balance; the wallet never goes negative, and the shortfall is added to
+/// `state.debt_cents` instead.
+pub fn accrue_leg_interest(
+    state: &mut EconState,
+    cfg: &InterestCfg,
+    route: RouteId,
+    wallet: &mut WalletAccess<'_>,
+) -> Option<LegInterestEvent> {
+    if cfg.accrue_on != InterestAccrual::Leg {
+        return None;
+    }
+    let debt_before = state.debt_cents;
+    let (interest_delta, _) = accrue_interest_per_leg(debt_before, cfg);
+    let paid_cents = MoneyCents(
+        interest_delta
+            .as_i64()
+            .min(wallet.balance().as_i64().max(0)),
+    );
+    wallet.debit(LedgerSource::Interest, paid_cents);
+    let shortfall_cents = interest_delta.saturating_sub(paid_cents);
+    state.debt_cents = debt_before.saturating_add(shortfall_cents);
+    Some(LegInterestEvent {
+        day: state.day,
+        route,
+        debt_before,
+        interest_delta,
+        paid_cents,
+        shortfall_cents,
+        debt_after: state.debt_cents,
+    })
+}
+
 impl RngCursor {
     fn new(label: &'static str, draws: u32) -> Self {
         Self {
@@ -108,20 +348,41 @@ pub enum EconStepScope {
     HubOnly,
 }
 
-pub fn step_economy_day(
-    rp: &Rulepack,
+/// Steps one economy day for `hub`. `rules` is consulted for the stepped
+/// day, so a [`RulepackSchedule`](super::rulepack::RulepackSchedule) switches
+/// tuning at its day boundaries.
+pub fn step_economy_day<R: RulepackSource + ?Sized>(
+    rules: &R,
     world_seed: u64,
     econ_version: u32,
     hub: HubId,
     state: &mut EconState,
 
     scope: EconStepScope,
+) -> EconDelta {
+    step_economy_day_with_events(rules, world_seed, econ_version, hub, state, scope, &[])
+}
+
+/// Steps the economy like [`step_economy_day`], applying any [`MarketEvent`]
+/// scheduled for the stepped day. Global DI overlays only fire on the
+/// [`EconStepScope::GlobalAndHub`] pass; basis overlays fire for every hub
+/// the event targets.
+pub fn step_economy_day_with_events<R: RulepackSource + ?Sized>(
+    rules: &R,
+    world_seed: u64,
+    econ_version: u32,
+    hub: HubId,
+    state: &mut EconState,
+    scope: EconStepScope,
+    events: &[MarketEvent],
 ) -> EconDelta {
     let day = match scope {
         EconStepScope::GlobalAndHub => state.day,
         EconStepScope::HubOnly => EconomyDay(state.day.0.saturating_sub(1)),
     };
+    let rp = rules.rulepack_for(day);
     let mut delta = EconDelta::new(day, hub);
+    state.bump_revision();
 
     if matches!(scope, EconStepScope::GlobalAndHub) {
         // 1. DI step
@@ -130,24 +391,21 @@ pub fn step_economy_day(
             overlay_bp: state.di_overlay_bp,
         };
         let prev_di = di_state.per_com.clone();
-        let mut rng_di = DetRng::from_seed_global(world_seed, econ_version, day, RNG_TAG_DI);
-        step_di(day, &mut di_state, rp, &mut rng_di);
+        let mut rng_di = RngStream::econ_day(world_seed, econ_version, None, day, RNG_TAG_DI);
+        let di_events = di_overlays_for_day(events, day);
+        step_di_with_events(day, &mut di_state, &di_events, rp, &mut rng_di);
         state.di_bp = di_state.per_com;
         state.di_overlay_bp = di_state.overlay_bp;
-        let mut di_entries: Vec<_> = state.di_bp.iter().collect();
-        di_entries.sort_by_key(|(commodity, _)| commodity.0);
-        for (commodity, value) in di_entries {
-            delta.di.push(CommodityDelta {
-                commodity: *commodity,
-                value: *value,
-            });
-            if let Some(previous) = prev_di.get(commodity) {
+        for commodity in sorted_commodities(&state.di_bp) {
+            let value = state.di_bp[&commodity];
+            delta.di.push(CommodityDelta { commodity, value });
+            if let Some(previous) = prev_di.get(&commodity) {
                 note_clamps(
                     &mut delta.clamps_hit,
                     "di",
-                    *commodity,
+                    commodity,
                     previous,
-                    value,
+                    &value,
                     rp.di.per_day_clamp_bp,
                     rp.di.absolute_min_bp,
                     rp.di.absolute_max_bp,
@@ -170,10 +428,12 @@ pub fn step_economy_day(
         delta.rot_after = rot_after;
         delta.debt_before = state.debt_cents;
         state.debt_cents = state.debt_cents.saturating_add(debt_delta);
-        let (interest_delta, debt_with_interest) =
-            accrue_interest_per_leg(state.debt_cents, &rp.interest);
-        state.debt_cents = debt_with_interest;
-        delta.interest_delta = interest_delta;
+        if rp.interest.accrue_on == InterestAccrual::Day {
+            let (interest_delta, debt_with_interest) =
+                accrue_interest_per_leg(state.debt_cents, &rp.interest);
+            state.debt_cents = debt_with_interest;
+            delta.interest_delta = interest_delta;
+        }
         delta.debt_after = state.debt_cents;
 
         // 4. Advance day
@@ -189,20 +449,26 @@ pub fn step_economy_day(
     }
 
     // Basis updates for this hub
-    let mut commodities: Vec<_> = state.di_bp.keys().copied().collect();
-    commodities.sort_by_key(|c| c.0);
-    let mut rng_basis = DetRng::from_seed(world_seed, econ_version, hub, day, RNG_TAG_BASIS);
+    let commodities = sorted_commodities(&state.di_bp);
+    let mut rng_basis =
+        RngStream::econ_day(world_seed, econ_version, Some(hub), day, RNG_TAG_BASIS);
     let drivers = BasisDrivers {
         pp: state.pp,
         weather: Weather::Clear,
-        closed_routes: 0,
+        closed_routes: state.closed_routes.get(&hub).copied().unwrap_or(0),
         stock_dev: 0,
     };
     state.basis_drivers.insert(hub, drivers);
     for commodity in commodities {
         let key = (hub, commodity);
         let current = state.basis_bp.get(&key).copied().unwrap_or(BasisBp(0));
-        let updated = update_basis(current, &drivers, rp, &mut rng_basis);
+        let overlay_bp = basis_overlay_for(events, day, hub, commodity);
+        let commodity_drivers = BasisDrivers {
+            stock_dev: state.settle_trade_pressure(hub, commodity, &rp.pressure),
+            ..drivers
+        };
+        let updated =
+            update_basis_with_overlay(current, &commodity_drivers, overlay_bp, rp, &mut rng_basis);
         note_clamps(
             &mut delta.clamps_hit,
             "basis",
@@ -214,6 +480,11 @@ pub fn step_economy_day(
             rp.basis.absolute_max_bp,
         );
         state.basis_bp.insert(key, updated);
+        let di = state.di_bp.get(&commodity).copied().unwrap_or(BasisBp(0));
+        state.record_price_sample(hub, commodity, BasisBp(di.0.saturating_add(updated.0)));
+        if price_clamp(di, updated, &rp.pricing, rp.pricing.override_for(commodity)).is_some() {
+            delta.price_clamp_hits += 1;
+        }
         delta.basis.push(CommodityDelta {
             commodity,
             value: updated,
@@ -223,10 +494,24 @@ pub fn step_economy_day(
         .rng_cursors
         .push(RngCursor::new("basis", rng_basis.cursor()));
 
-    if matches!(scope, EconStepScope::GlobalAndHub) {
-        log::log_econ_tick(&delta, &rp.pricing);
+    // NPC supply and demand, priced in from the next step
+    let (stock, demand_draws) = step_npc_flow(
+        world_seed,
+        econ_version,
+        hub,
+        day,
+        state,
+        &rp.stock.replenish,
+    );
+    if !stock.is_empty() {
+        delta.stock = stock;
+        delta
+            .rng_cursors
+            .push(RngCursor::new("npc_demand", demand_draws));
     }
 
+    log::log_econ_tick(&delta);
+
     delta
 }
 
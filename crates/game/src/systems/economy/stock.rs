@@ -0,0 +1,95 @@
+use serde::{Deserialize, Serialize};
+
+use super::order::sorted_commodities;
+use super::rulepack::ReplenishCfg;
+use super::state::EconState;
+use super::{CommodityId, DetRng, EconomyDay, HubId};
+use crate::systems::rng_stream::RngStream;
+
+/// Stream the NPC demand draws come from. It is separate from the
+/// `det_rng_v1` DI and basis streams, so enabling demand never shifts their
+/// draws or cursors.
+const NPC_DEMAND_DOMAIN: &str = "econ_npc_demand_v1";
+
+/// One commodity's NPC flow at a hub for one economy day.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub struct StockDelta {
+    pub commodity: CommodityId,
+    /// Units the drift toward equilibrium added; negative when it drained an
+    /// overstocked hub.
+    pub replenished: i32,
+    /// Units NPC buyers took after the drift.
+    pub demand: u32,
+    /// Stock on hand once both moved.
+    pub stock_after: u32,
+}
+
+/// Runs one day of NPC supply and demand for every commodity at `hub`.
+///
+/// Stock first drifts toward `cfg.equilibrium_units` by `cfg.rate_bp` of the
+/// gap, at least one unit and at most `cfg.max_units_per_day`. NPCs then buy
+/// up to `cfg.demand_max_units` of what is on hand, one draw per commodity
+/// from the `econ_npc_demand_v1` stream for this hub and day. Both moves go
+/// into the trade pressure against the stock offset, as a trade does, so the
+/// basis `stock_dev` driver prices scarcity and refill from the next step.
+///
+/// Returns the movements and the number of demand draws. Nothing moves and
+/// no stream is opened when `cfg` is inactive.
+pub fn step_npc_flow(
+    world_seed: u64,
+    econ_version: u32,
+    hub: HubId,
+    day: EconomyDay,
+    state: &mut EconState,
+    cfg: &ReplenishCfg,
+) -> (Vec<StockDelta>, u32) {
+    if !cfg.is_active() {
+        return (Vec::new(), 0);
+    }
+    let mut rng = RngStream::new(
+        world_seed,
+        econ_version,
+        NPC_DEMAND_DOMAIN,
+        &[u64::from(hub.0), u64::from(day.0)],
+    );
+    let equilibrium = i64::from(cfg.equilibrium_units);
+    let mut flows = Vec::new();
+    for commodity in sorted_commodities(&state.di_bp) {
+        let offset = i64::from(state.stock_offset(hub, commodity));
+        let replenished = drift(-offset, cfg);
+        let on_hand = (equilibrium + offset + replenished).max(0);
+        let demand = demand_draw(&mut rng, cfg).min(on_hand);
+        let moved = (replenished - demand) as i32;
+        state.shift_stock(hub, commodity, moved);
+        state.add_trade_pressure(hub, commodity, -moved);
+        flows.push(StockDelta {
+            commodity,
+            replenished: replenished as i32,
+            demand: demand as u32,
+            stock_after: state.hub_stock(hub, commodity, cfg),
+        });
+    }
+    (flows, rng.cursor())
+}
+
+/// Units the drift moves toward equilibrium given the `gap` to it.
+fn drift(gap: i64, cfg: &ReplenishCfg) -> i64 {
+    if cfg.rate_bp <= 0 || gap == 0 {
+        return 0;
+    }
+    let mut step = gap * i64::from(cfg.rate_bp.min(10_000)) / 10_000;
+    if step == 0 {
+        step = gap.signum();
+    }
+    let cap = i64::from(cfg.max_units_per_day);
+    step.clamp(-cap, cap)
+}
+
+/// Uniform draw in `0..=cfg.demand_max_units`.
+fn demand_draw(rng: &mut DetRng, cfg: &ReplenishCfg) -> i64 {
+    if cfg.demand_max_units == 0 {
+        return 0;
+    }
+    let span = u64::from(cfg.demand_max_units) + 1;
+    ((u64::from(rng.u32()) * span) >> 32) as i64
+}
@@ -4,6 +4,7 @@ use crate::systems::economy::{
     load_rulepack, update_basis, BasisBp, BasisDrivers, DetRng, EconomyDay, HubId, Pp, Rulepack,
     Weather,
 };
+use crate::systems::rng_stream::RngStream;
 
 fn workspace_path(relative: &str) -> PathBuf {
     let manifest_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
@@ -20,7 +21,7 @@ fn load_fixture_pack() -> Rulepack {
 }
 
 fn seeded_rng(tag: u32) -> DetRng {
-    DetRng::from_seed(900, 1, HubId(2), EconomyDay(0), tag)
+    RngStream::econ_day(900, 1, Some(HubId(2)), EconomyDay(0), tag)
 }
 
 #[test]
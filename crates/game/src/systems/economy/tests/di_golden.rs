@@ -2,8 +2,9 @@ use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 
 use crate::systems::economy::{
-    load_rulepack, step_di, BasisBp, CommodityId, DetRng, DiState, EconomyDay, HubId, Rulepack,
+    load_rulepack, step_di, BasisBp, CommodityId, DiState, EconomyDay, HubId, Rulepack,
 };
+use crate::systems::rng_stream::RngStream;
 
 fn workspace_path(relative: &str) -> PathBuf {
     let manifest_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
@@ -29,7 +30,7 @@ fn di_sequence_matches_golden() {
         per_com,
         overlay_bp: 120,
     };
-    let mut rng = DetRng::from_seed(777, 1, HubId(3), EconomyDay(0), 0);
+    let mut rng = RngStream::econ_day(777, 1, Some(HubId(3)), EconomyDay(0), 0);
 
     let mut samples = Vec::new();
     for day in 0..30u32 {
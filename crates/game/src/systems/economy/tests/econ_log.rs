@@ -0,0 +1,106 @@
+use std::fs;
+use std::path::Path;
+
+use tempfile::tempdir;
+
+use crate::systems::economy::log::{
+    day_log_path, log_econ_tick_in, set_enabled, EconLogReader, EconLogWriter,
+};
+use crate::systems::economy::{
+    load_rulepack, step_economy_day, EconDelta, EconState, EconStepScope, EconomyDay, HubId, Pp,
+};
+
+const HUBS: [u16; 3] = [1, 2, 3];
+
+/// Two economy days across three hubs: one global step plus two hub-only
+/// steps per day.
+fn two_days() -> Vec<EconDelta> {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("../../assets/rulepacks/day_001.toml");
+    let rp = load_rulepack(path.to_str().expect("utf-8 path")).expect("rulepack");
+    let mut state = EconState {
+        pp: Pp(420),
+        ..EconState::default()
+    };
+    let mut deltas = Vec::new();
+    for _ in 0..2 {
+        for (idx, hub) in HUBS.iter().enumerate() {
+            let scope = if idx == 0 {
+                EconStepScope::GlobalAndHub
+            } else {
+                EconStepScope::HubOnly
+            };
+            deltas.push(step_economy_day(&rp, 77, 1, HubId(*hub), &mut state, scope));
+        }
+    }
+    deltas
+}
+
+fn read_day(dir: &Path, day: EconomyDay) -> Vec<EconDelta> {
+    EconLogReader::open_day(dir, day)
+        .expect("day log")
+        .collect::<Result<_, _>>()
+        .expect("parse deltas")
+}
+
+#[test]
+fn each_economy_day_rotates_into_its_own_file() {
+    let dir = tempdir().expect("tempdir");
+    let writer = EconLogWriter::new(dir.path());
+    let deltas = two_days();
+    for delta in &deltas {
+        writer.write(delta).expect("write delta");
+    }
+
+    let mut files: Vec<String> = fs::read_dir(dir.path())
+        .expect("log dir")
+        .map(|entry| {
+            entry
+                .expect("entry")
+                .file_name()
+                .to_string_lossy()
+                .into_owned()
+        })
+        .collect();
+    files.sort();
+    assert_eq!(files, ["day_0000.jsonl", "day_0001.jsonl"]);
+    for day in [EconomyDay(0), EconomyDay(1)] {
+        let text = fs::read_to_string(day_log_path(dir.path(), day)).expect("day log");
+        assert_eq!(text.lines().count(), HUBS.len());
+    }
+}
+
+#[test]
+fn reader_round_trips_the_deltas() {
+    let dir = tempdir().expect("tempdir");
+    let writer = EconLogWriter::new(dir.path());
+    let deltas = two_days();
+    for delta in &deltas {
+        writer.write(delta).expect("write delta");
+    }
+
+    let mut read = read_day(dir.path(), EconomyDay(0));
+    read.extend(read_day(dir.path(), EconomyDay(1)));
+    assert_eq!(read, deltas);
+    assert!(read.iter().all(|delta| !delta.rng_cursors.is_empty()));
+    let line = fs::read(day_log_path(dir.path(), EconomyDay(0))).expect("day log");
+    let first = line
+        .split(|byte| *byte == b'\n')
+        .next()
+        .expect("first line");
+    let mut expected = repro::canonical_json_bytes(&deltas[0]).expect("canonical");
+    expected.pop();
+    assert_eq!(first, expected.as_slice());
+}
+
+#[test]
+fn disabled_logging_writes_nothing_and_leaves_deltas_alone() {
+    let dir = tempdir().expect("tempdir");
+    set_enabled(false);
+    let deltas = two_days();
+    for delta in &deltas {
+        log_econ_tick_in(dir.path(), delta);
+    }
+
+    assert_eq!(fs::read_dir(dir.path()).expect("log dir").count(), 0);
+    assert_eq!(two_days(), deltas);
+}
@@ -55,6 +55,7 @@ fn interest_rounds_half_cent_boundaries() {
         convex_leg_bp: 0,
         convex_gamma_q16: 0,
         per_leg_cap_bp: i32::MAX,
+        accrue_on: Default::default(),
     };
 
     cfg.base_leg_bp = 51;
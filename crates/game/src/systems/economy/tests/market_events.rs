@@ -0,0 +1,244 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use crate::systems::economy::events::parse_market_events;
+use crate::systems::economy::{
+    load_market_events, load_rulepack, step_economy_day, step_economy_day_with_events, BasisBp,
+    CommodityId, EconState, EconStepScope, EconomyDay, HubId, MarketEvent, Rulepack,
+};
+
+fn workspace_path(relative: &str) -> PathBuf {
+    let manifest_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let root = manifest_dir
+        .parent()
+        .and_then(|p| p.parent())
+        .expect("workspace root");
+    root.join(relative)
+}
+
+fn rulepack() -> Rulepack {
+    load_rulepack(
+        workspace_path("assets/rulepacks/day_001.toml")
+            .to_str()
+            .unwrap(),
+    )
+    .expect("rulepack")
+}
+
+fn initial_state() -> EconState {
+    EconState {
+        di_bp: HashMap::from([(CommodityId(1), BasisBp(0)), (CommodityId(2), BasisBp(-50))]),
+        ..Default::default()
+    }
+}
+
+type DaySnapshot = (
+    EconomyDay,
+    HashMap<CommodityId, BasisBp>,
+    Vec<((u16, u16), i32)>,
+);
+
+/// Steps hub 1 globally and hub 2 as a follow-up, returning per-day
+/// snapshots of DI and basis.
+fn run(rp: &Rulepack, events: &[MarketEvent], days: u32) -> Vec<DaySnapshot> {
+    let mut state = initial_state();
+    let mut out = Vec::new();
+    for _ in 0..days {
+        let day = state.day;
+        step_economy_day_with_events(
+            rp,
+            11,
+            1,
+            HubId(1),
+            &mut state,
+            EconStepScope::GlobalAndHub,
+            events,
+        );
+        step_economy_day_with_events(
+            rp,
+            11,
+            1,
+            HubId(2),
+            &mut state,
+            EconStepScope::HubOnly,
+            events,
+        );
+        let mut basis: Vec<_> = state
+            .basis_bp
+            .iter()
+            .map(|((hub, com), value)| ((hub.0, com.0), value.0))
+            .collect();
+        basis.sort();
+        out.push((day, state.di_bp.clone(), basis));
+    }
+    out
+}
+
+fn basis_at(snapshot: &[((u16, u16), i32)], hub: u16, commodity: u16) -> i32 {
+    snapshot
+        .iter()
+        .find(|(key, _)| *key == (hub, commodity))
+        .map(|(_, value)| *value)
+        .expect("basis entry")
+}
+
+#[test]
+fn event_fires_only_on_its_day() {
+    let rp = rulepack();
+    let events = vec![MarketEvent {
+        day: EconomyDay(3),
+        hub: Some(HubId(2)),
+        commodity: CommodityId(1),
+        di_overlay_bp: 0,
+        basis_overlay_bp: 200,
+    }];
+    let baseline = run(&rp, &[], 6);
+    let shocked = run(&rp, &events, 6);
+
+    for day in 0..3 {
+        assert_eq!(baseline[day], shocked[day], "day {day} should be untouched");
+    }
+    let before = basis_at(&baseline[3].2, 2, 1);
+    let after = basis_at(&shocked[3].2, 2, 1);
+    assert_eq!(after - before, 200);
+    assert_eq!(
+        basis_at(&baseline[3].2, 1, 1),
+        basis_at(&shocked[3].2, 1, 1)
+    );
+    assert_eq!(
+        basis_at(&baseline[3].2, 2, 2),
+        basis_at(&shocked[3].2, 2, 2)
+    );
+    assert_eq!(
+        baseline[3].1, shocked[3].1,
+        "hub-scoped events leave DI alone"
+    );
+}
+
+#[test]
+fn global_event_moves_di_and_all_hubs() {
+    let rp = rulepack();
+    let events = vec![MarketEvent {
+        day: EconomyDay(1),
+        hub: None,
+        commodity: CommodityId(2),
+        di_overlay_bp: 100,
+        basis_overlay_bp: 50,
+    }];
+    let baseline = run(&rp, &[], 2);
+    let shocked = run(&rp, &events, 2);
+
+    let di_before = baseline[1].1[&CommodityId(2)].0;
+    let di_after = shocked[1].1[&CommodityId(2)].0;
+    assert_eq!(di_after - di_before, 100);
+    assert_eq!(
+        baseline[1].1[&CommodityId(1)],
+        shocked[1].1[&CommodityId(1)]
+    );
+    for hub in [1, 2] {
+        assert_eq!(
+            basis_at(&shocked[1].2, hub, 2) - basis_at(&baseline[1].2, hub, 2),
+            50
+        );
+    }
+}
+
+#[test]
+fn overlays_respect_clamps() {
+    let rp = rulepack();
+    let events = vec![MarketEvent {
+        day: EconomyDay(0),
+        hub: None,
+        commodity: CommodityId(1),
+        di_overlay_bp: 50_000,
+        basis_overlay_bp: 50_000,
+    }];
+    let shocked = run(&rp, &events, 1);
+    let di = shocked[0].1[&CommodityId(1)].0;
+    assert!(di <= rp.di.per_day_clamp_bp && di <= rp.di.absolute_max_bp);
+    let basis = basis_at(&shocked[0].2, 1, 1);
+    assert_eq!(
+        basis,
+        rp.basis.per_day_clamp_bp.min(rp.basis.absolute_max_bp)
+    );
+
+    let mut state = EconState {
+        basis_bp: HashMap::from([(
+            (HubId(1), CommodityId(1)),
+            BasisBp(rp.basis.absolute_max_bp - 10),
+        )]),
+        ..initial_state()
+    };
+    step_economy_day_with_events(
+        &rp,
+        11,
+        1,
+        HubId(1),
+        &mut state,
+        EconStepScope::GlobalAndHub,
+        &events,
+    );
+    assert_eq!(
+        state.basis_bp[&(HubId(1), CommodityId(1))].0,
+        rp.basis.absolute_max_bp
+    );
+}
+
+#[test]
+fn unmatched_events_reproduce_plain_step() {
+    let rp = rulepack();
+    let events = vec![MarketEvent {
+        day: EconomyDay(500),
+        hub: None,
+        commodity: CommodityId(1),
+        di_overlay_bp: 300,
+        basis_overlay_bp: 300,
+    }];
+    let mut plain = initial_state();
+    let mut with_events = initial_state();
+    for _ in 0..10 {
+        let a = step_economy_day(
+            &rp,
+            11,
+            1,
+            HubId(1),
+            &mut plain,
+            EconStepScope::GlobalAndHub,
+        );
+        let b = step_economy_day_with_events(
+            &rp,
+            11,
+            1,
+            HubId(1),
+            &mut with_events,
+            EconStepScope::GlobalAndHub,
+            &events,
+        );
+        assert_eq!(
+            serde_json::to_string(&a).unwrap(),
+            serde_json::to_string(&b).unwrap()
+        );
+    }
+}
+
+#[test]
+fn loads_events_asset() {
+    let path = workspace_path("assets/econ/events.toml");
+    let events = load_market_events(path.to_str().unwrap()).expect("events");
+    assert_eq!(events.len(), 2);
+    assert_eq!(events[0].hub, Some(HubId(2)));
+    assert_eq!(events[1].hub, None);
+}
+
+#[test]
+fn rejects_hub_scoped_di_overlay() {
+    let raw = r#"
+[[events]]
+day = 1
+hub = 3
+commodity = 1
+di_overlay_bp = 10
+"#;
+    assert!(parse_market_events(raw).is_err());
+    assert!(parse_market_events("[[events]]\nday = 1\ncommodity = 1\nextra = 2\n").is_err());
+}
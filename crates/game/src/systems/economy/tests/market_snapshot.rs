@@ -0,0 +1,90 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use crate::systems::economy::snapshot::market_table;
+use crate::systems::economy::{
+    load_rulepack, BasisBp, CommodityId, EconState, EconomyDay, HubId, Pp,
+};
+use crate::systems::trading::catalog::CatalogHandle;
+use crate::systems::trading::types::CommodityCatalog;
+
+fn workspace_path(relative: &str) -> PathBuf {
+    let manifest_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let root = manifest_dir
+        .parent()
+        .and_then(|p| p.parent())
+        .expect("workspace root");
+    root.join(relative)
+}
+
+fn fixture_state() -> EconState {
+    EconState {
+        day: EconomyDay(6),
+        di_bp: HashMap::from([
+            (CommodityId(3), BasisBp(134)),
+            (CommodityId(1), BasisBp(-20)),
+            (CommodityId(2), BasisBp(75)),
+        ]),
+        basis_bp: HashMap::from([
+            ((HubId(2), CommodityId(1)), BasisBp(40)),
+            ((HubId(1), CommodityId(3)), BasisBp(-65)),
+            ((HubId(2), CommodityId(3)), BasisBp(210)),
+        ]),
+        pp: Pp(5_000),
+        ..EconState::default()
+    }
+}
+
+#[test]
+fn snapshot_is_sorted_and_deterministic() {
+    let catalog =
+        CommodityCatalog::load_from_path(&workspace_path("assets/trading/commodities.toml"))
+            .expect("catalog");
+    let _scope = CatalogHandle::new(catalog).scope();
+    let rp = load_rulepack(
+        workspace_path("assets/rulepacks/day_001.toml")
+            .to_str()
+            .unwrap(),
+    )
+    .expect("rulepack");
+    let econ = fixture_state();
+
+    let table = market_table(&[HubId(2), HubId(1), HubId(2)], &econ, &rp);
+    let again = market_table(&[HubId(1), HubId(2)], &econ.clone(), &rp);
+    assert_eq!(table, again);
+    assert_eq!(table.day, EconomyDay(6));
+    let keys: Vec<(u16, u16)> = table
+        .rows
+        .iter()
+        .map(|row| (row.hub.0, row.commodity.0))
+        .collect();
+    assert_eq!(keys, vec![(1, 1), (1, 2), (1, 3), (2, 1), (2, 2), (2, 3)]);
+    assert_eq!(table.rows[2].basis_bp, BasisBp(-65));
+    assert_eq!(table.rows[4].basis_bp, BasisBp(0));
+    assert_eq!(table.hash.len(), 64);
+}
+
+#[test]
+fn snapshot_hash_tracks_basis_changes() {
+    let catalog =
+        CommodityCatalog::load_from_path(&workspace_path("assets/trading/commodities.toml"))
+            .expect("catalog");
+    let _scope = CatalogHandle::new(catalog).scope();
+    let rp = load_rulepack(
+        workspace_path("assets/rulepacks/day_001.toml")
+            .to_str()
+            .unwrap(),
+    )
+    .expect("rulepack");
+    let econ = fixture_state();
+    let mut nudged = econ.clone();
+    nudged
+        .basis_bp
+        .insert((HubId(2), CommodityId(1)), BasisBp(41));
+
+    let hubs = [HubId(1), HubId(2)];
+    let before = market_table(&hubs, &econ, &rp);
+    let after = market_table(&hubs, &nudged, &rp);
+    assert_ne!(before.hash, after.hash);
+    assert_eq!(before.rows[..3], after.rows[..3]);
+}
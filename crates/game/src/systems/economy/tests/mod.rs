@@ -1,9 +1,18 @@
 mod basis_dynamics_golden;
 mod di_golden;
+mod econ_log;
 mod interest_piecewise_golden;
+mod market_events;
+mod market_snapshot;
+mod order;
 mod planting_pull;
+mod price_history;
 mod pricing_rounding_golden;
 mod rng_discipline;
 mod rot_convert;
 mod rulepack_load;
+mod rulepack_schedule;
 mod state_step;
+mod stock_replenish;
+mod trade_pressure;
+mod verify;
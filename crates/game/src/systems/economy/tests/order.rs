@@ -0,0 +1,84 @@
+use std::collections::HashMap;
+use std::path::Path;
+
+use crate::systems::economy::order::{sorted_basis, sorted_commodities};
+use crate::systems::economy::{
+    load_rulepack, step_economy_day, BasisBp, CommodityId, EconState, EconStepScope, HubId, Pp,
+};
+
+const COMMODITIES: [u16; 6] = [9, 2, 14, 5, 1, 7];
+const HUBS: [u16; 3] = [3, 1, 2];
+
+fn state(di_order: &[u16], basis_order: &[(u16, u16)]) -> EconState {
+    let mut di_bp = HashMap::new();
+    for &com in di_order {
+        di_bp.insert(CommodityId(com), BasisBp(i32::from(com) * 37 - 200));
+    }
+    let mut basis_bp = HashMap::new();
+    for &(hub, com) in basis_order {
+        basis_bp.insert(
+            (HubId(hub), CommodityId(com)),
+            BasisBp(i32::from(hub) * 11 - i32::from(com) * 5),
+        );
+    }
+    EconState {
+        di_bp,
+        di_overlay_bp: 80,
+        basis_bp,
+        pp: Pp(420),
+        rot_u16: 150,
+        ..EconState::default()
+    }
+}
+
+fn run(mut state: EconState) -> (String, String) {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("../../assets/rulepacks/day_001.toml");
+    let rp = load_rulepack(path.to_str().unwrap()).expect("rulepack");
+    let mut deltas = Vec::new();
+    for _ in 0..5 {
+        for (idx, hub) in HUBS.iter().enumerate() {
+            let scope = if idx == 0 {
+                EconStepScope::GlobalAndHub
+            } else {
+                EconStepScope::HubOnly
+            };
+            deltas.push(step_economy_day(&rp, 77, 1, HubId(*hub), &mut state, scope));
+        }
+    }
+    let json = serde_json::to_string(&deltas).expect("serialize deltas");
+    let hash = blake3::hash(json.as_bytes()).to_hex().to_string();
+    (json, hash)
+}
+
+#[test]
+fn shuffled_insertion_yields_identical_deltas() {
+    let basis: Vec<(u16, u16)> = HUBS
+        .iter()
+        .flat_map(|&hub| COMMODITIES.iter().map(move |&com| (hub, com)))
+        .collect();
+    let mut reversed_di = COMMODITIES;
+    reversed_di.reverse();
+    let mut shuffled_basis = basis.clone();
+    shuffled_basis.rotate_left(7);
+    shuffled_basis.reverse();
+
+    let (first_json, first_hash) = run(state(&COMMODITIES, &basis));
+    let (second_json, second_hash) = run(state(&reversed_di, &shuffled_basis));
+    assert_eq!(first_json, second_json);
+    assert_eq!(first_hash, second_hash);
+}
+
+#[test]
+fn helpers_sort_by_id() {
+    let econ = state(&COMMODITIES, &[(2, 9), (1, 14), (2, 1), (1, 2)]);
+    let commodities: Vec<u16> = sorted_commodities(&econ.di_bp)
+        .iter()
+        .map(|c| c.0)
+        .collect();
+    assert_eq!(commodities, vec![1, 2, 5, 7, 9, 14]);
+    let basis: Vec<(u16, u16)> = sorted_basis(&econ.basis_bp)
+        .iter()
+        .map(|(hub, com)| (hub.0, com.0))
+        .collect();
+    assert_eq!(basis, vec![(1, 2), (1, 14), (2, 1), (2, 9)]);
+}
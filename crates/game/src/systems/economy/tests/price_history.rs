@@ -0,0 +1,72 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use crate::systems::economy::state::PRICE_HISTORY_DAYS;
+use crate::systems::economy::{
+    load_rulepack, step_economy_day, BasisBp, CommodityId, EconState, EconStepScope, HubId,
+};
+
+fn workspace_path(relative: &str) -> PathBuf {
+    let manifest_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let root = manifest_dir
+        .parent()
+        .and_then(|p| p.parent())
+        .expect("workspace root");
+    root.join(relative)
+}
+
+fn run_days(days: u32, seed: u64) -> EconState {
+    let rp = load_rulepack(
+        workspace_path("assets/rulepacks/day_001.toml")
+            .to_str()
+            .unwrap(),
+    )
+    .expect("rulepack");
+    let mut state = EconState {
+        di_bp: HashMap::from([(CommodityId(1), BasisBp(0)), (CommodityId(2), BasisBp(-50))]),
+        ..Default::default()
+    };
+    for _ in 0..days {
+        step_economy_day(
+            &rp,
+            seed,
+            1,
+            HubId(1),
+            &mut state,
+            EconStepScope::GlobalAndHub,
+        );
+        step_economy_day(&rp, seed, 1, HubId(2), &mut state, EconStepScope::HubOnly);
+    }
+    state
+}
+
+#[test]
+fn history_keeps_only_latest_samples() {
+    let state = run_days(20, 9);
+    for hub in [HubId(1), HubId(2)] {
+        for commodity in [CommodityId(1), CommodityId(2)] {
+            let history = state.price_history(hub, commodity);
+            assert_eq!(history.len(), PRICE_HISTORY_DAYS);
+            let di = state.di_bp[&commodity];
+            let basis = state.basis_bp[&(hub, commodity)];
+            assert_eq!(history.last(), Some(&BasisBp(di.0 + basis.0)));
+        }
+    }
+    assert!(state.price_history(HubId(3), CommodityId(1)).is_empty());
+}
+
+#[test]
+fn history_tail_matches_shorter_run() {
+    let long = run_days(20, 9);
+    let short = run_days(16, 9);
+    let long_history = long.price_history(HubId(1), CommodityId(1));
+    let short_history = short.price_history(HubId(1), CommodityId(1));
+    assert_eq!(&long_history[..PRICE_HISTORY_DAYS - 4], &short_history[4..]);
+}
+
+#[test]
+fn history_is_deterministic_for_seed() {
+    let a = run_days(20, 42);
+    let b = run_days(20, 42);
+    assert_eq!(a.price_history, b.price_history);
+}
@@ -1,12 +1,13 @@
 use crate::systems::economy::{
-    bankers_round_cents, compute_price, round_down_to_cents, rulepack::PricingCfg, BasisBp,
-    MoneyCents,
+    bankers_round_cents, compute_price, price_clamp, round_down_to_cents, rulepack::PricingCfg,
+    BasisBp, ClampSide, MoneyCents, PricingOverride,
 };
 
 fn unlimited_pricing_cfg() -> PricingCfg {
     PricingCfg {
         min_multiplier_bp: i32::MIN,
         max_multiplier_bp: i32::MAX,
+        overrides: Default::default(),
     }
 }
 
@@ -46,23 +47,23 @@ fn compute_price_handles_half_cent_ties() {
     let di = BasisBp(-2000);
     let basis = BasisBp(-500);
     let pricing = unlimited_pricing_cfg();
-    let rounded_up = compute_price(base, di, basis, &pricing);
+    let rounded_up = compute_price(base, di, basis, &pricing, None).price;
     assert_eq!(rounded_up, MoneyCents(2));
 
     let base = MoneyCents(5);
     let di = BasisBp(-500);
     let basis = BasisBp(-500);
-    let rounded_down = compute_price(base, di, basis, &pricing);
+    let rounded_down = compute_price(base, di, basis, &pricing, None).price;
     assert_eq!(rounded_down, MoneyCents(4));
 
     let base = MoneyCents(100);
     let di = BasisBp(51);
     let basis = BasisBp(0);
-    let just_above_half_cent = compute_price(base, di, basis, &pricing);
+    let just_above_half_cent = compute_price(base, di, basis, &pricing, None).price;
     assert_eq!(just_above_half_cent, MoneyCents(101));
 
     let di = BasisBp(49);
-    let just_below_half_cent = compute_price(base, di, basis, &pricing);
+    let just_below_half_cent = compute_price(base, di, basis, &pricing, None).price;
     assert_eq!(just_below_half_cent, MoneyCents(100));
 }
 
@@ -70,16 +71,16 @@ fn compute_price_handles_half_cent_ties() {
 fn compute_price_monotonic_in_drivers() {
     let base = MoneyCents(10_000);
     let pricing = unlimited_pricing_cfg();
-    let mut previous = compute_price(base, BasisBp(-1_000), BasisBp(-1_000), &pricing);
+    let mut previous = compute_price(base, BasisBp(-1_000), BasisBp(-1_000), &pricing, None).price;
     for delta in (-1_000..=1_000).step_by(250) {
-        let price = compute_price(base, BasisBp(delta), BasisBp(-1_000), &pricing);
+        let price = compute_price(base, BasisBp(delta), BasisBp(-1_000), &pricing, None).price;
         assert!(price.0 >= previous.0);
         previous = price;
     }
 
-    let mut previous = compute_price(base, BasisBp(0), BasisBp(-1_000), &pricing);
+    let mut previous = compute_price(base, BasisBp(0), BasisBp(-1_000), &pricing, None).price;
     for delta in (-1_000..=1_000).step_by(250) {
-        let price = compute_price(base, BasisBp(0), BasisBp(delta), &pricing);
+        let price = compute_price(base, BasisBp(0), BasisBp(delta), &pricing, None).price;
         assert!(price.0 >= previous.0);
         previous = price;
     }
@@ -91,13 +92,13 @@ fn compute_price_saturates_on_extreme_inputs() {
     let base = MoneyCents(i64::MAX);
     let di = BasisBp(500_000);
     let basis = BasisBp(500_000);
-    let price = compute_price(base, di, basis, &pricing);
+    let price = compute_price(base, di, basis, &pricing, None).price;
     assert_eq!(price, MoneyCents(i64::MAX));
 
     let base = MoneyCents(i64::MAX);
     let di = BasisBp(i32::MIN / 2);
     let basis = BasisBp(i32::MIN / 2);
-    let price = compute_price(base, di, basis, &pricing);
+    let price = compute_price(base, di, basis, &pricing, None).price;
     assert_eq!(price, MoneyCents(i64::MIN));
 }
 
@@ -106,12 +107,55 @@ fn compute_price_respects_pricing_bounds() {
     let pricing = PricingCfg {
         min_multiplier_bp: -1_000,
         max_multiplier_bp: 1_000,
+        overrides: Default::default(),
     };
     let base = MoneyCents(100);
 
-    let price_high = compute_price(base, BasisBp(2_500), BasisBp(0), &pricing);
+    let price_high = compute_price(base, BasisBp(2_500), BasisBp(0), &pricing, None).price;
     assert_eq!(price_high, MoneyCents(110));
 
-    let price_low = compute_price(base, BasisBp(-5_000), BasisBp(0), &pricing);
+    let price_low = compute_price(base, BasisBp(-5_000), BasisBp(0), &pricing, None).price;
     assert_eq!(price_low, MoneyCents(90));
 }
+
+#[test]
+fn override_bounds_replace_the_global_clamp() {
+    let pricing = PricingCfg {
+        min_multiplier_bp: -1_000,
+        max_multiplier_bp: 1_000,
+        overrides: Default::default(),
+    };
+    let tight = PricingOverride {
+        min_multiplier_bp: -500,
+        max_multiplier_bp: 500,
+    };
+    let base = MoneyCents(100);
+
+    let global = compute_price(base, BasisBp(800), BasisBp(0), &pricing, None);
+    assert_eq!(global.price, MoneyCents(108));
+    assert_eq!(global.clamped, None);
+
+    let overridden = compute_price(base, BasisBp(800), BasisBp(0), &pricing, Some(&tight));
+    assert_eq!(overridden.price, MoneyCents(105));
+    assert_eq!(overridden.clamped, Some(ClampSide::Max));
+
+    let low = compute_price(base, BasisBp(-400), BasisBp(-400), &pricing, Some(&tight));
+    assert_eq!(low.price, MoneyCents(95));
+    assert_eq!(low.clamped, Some(ClampSide::Min));
+}
+
+#[test]
+fn prices_on_a_bound_are_not_reported_as_clamped() {
+    let pricing = PricingCfg {
+        min_multiplier_bp: -1_000,
+        max_multiplier_bp: 1_000,
+        overrides: Default::default(),
+    };
+    let at_max = compute_price(MoneyCents(100), BasisBp(600), BasisBp(400), &pricing, None);
+    assert_eq!(at_max.price, MoneyCents(110));
+    assert_eq!(at_max.clamped, None);
+    assert_eq!(
+        price_clamp(BasisBp(600), BasisBp(401), &pricing, None),
+        Some(ClampSide::Max)
+    );
+}
@@ -1,12 +1,14 @@
-use crate::systems::economy::{DetRng, EconomyDay, HubId};
+use crate::systems::economy::rng::sample_noise_bp;
+use crate::systems::economy::{EconomyDay, HubId};
+use crate::systems::rng_stream::RngStream;
 
 #[test]
 fn deterministic_seed_reproducible() {
     let hub = HubId(7);
     let day = EconomyDay(3);
-    let mut rng_a = DetRng::from_seed(42, 1, hub, day, 99);
-    let mut rng_b = DetRng::from_seed(42, 1, hub, day, 99);
-    let mut rng_c = DetRng::from_seed(42, 1, hub, EconomyDay(4), 99);
+    let mut rng_a = RngStream::econ_day(42, 1, Some(hub), day, 99);
+    let mut rng_b = RngStream::econ_day(42, 1, Some(hub), day, 99);
+    let mut rng_c = RngStream::econ_day(42, 1, Some(hub), EconomyDay(4), 99);
 
     let seq_a: Vec<u32> = (0..4).map(|_| rng_a.u32()).collect();
     let seq_b: Vec<u32> = (0..4).map(|_| rng_b.u32()).collect();
@@ -18,15 +20,52 @@ fn deterministic_seed_reproducible() {
 
 #[test]
 fn norm_samples_clamped_and_stable() {
-    let mut rng = DetRng::from_seed(7, 2, HubId(3), EconomyDay(55), 0);
+    let mut rng = RngStream::econ_day(7, 2, Some(HubId(3)), EconomyDay(55), 0);
     let samples: Vec<i32> = (0..5)
         .map(|_| rng.norm_bounded_bp(50, 400, 900).0)
         .collect();
     assert_eq!(samples, vec![-83, -703, 4, 260, 6]);
 
-    let mut rng = DetRng::from_seed(9, 1, HubId(1), EconomyDay(1), 1);
+    let mut rng = RngStream::econ_day(9, 1, Some(HubId(1)), EconomyDay(1), 1);
     for _ in 0..256 {
         let sample = rng.norm_bounded_bp(-25, 1200, 600);
         assert!(sample.0 >= -600 && sample.0 <= 600);
     }
 }
+
+#[test]
+fn noise_sequence_is_pinned() {
+    let mut rng = RngStream::econ_day(0xD7E7_2024, 1, Some(HubId(2)), EconomyDay(10), 0x44);
+    let di: Vec<i32> = (0..8).map(|_| sample_noise_bp(&mut rng, 60, 180)).collect();
+    assert_eq!(di, vec![-21, -37, -24, 39, -46, -18, -81, 6]);
+
+    let mut rng = RngStream::econ_day(0xD7E7_2024, 1, Some(HubId(2)), EconomyDay(10), 0x45);
+    let basis: Vec<i32> = (0..8).map(|_| sample_noise_bp(&mut rng, 45, 150)).collect();
+    assert_eq!(basis, vec![-19, 66, -7, -34, -26, 0, 32, -20]);
+
+    let mut rng = RngStream::econ_day(0xD7E7_2024, 1, Some(HubId(2)), EconomyDay(10), 0x46);
+    let wide: Vec<i32> = (0..8)
+        .map(|_| sample_noise_bp(&mut rng, 5000, 400))
+        .collect();
+    assert_eq!(wide, vec![-400, 400, -400, 400, 400, 400, -400, 400]);
+    assert_eq!(rng.cursor(), 48);
+}
+
+#[test]
+fn noise_matches_zero_mean_norm_samples() {
+    let mut shared = RngStream::econ_day(11, 3, Some(HubId(4)), EconomyDay(21), 7);
+    let mut direct = shared.clone();
+    for _ in 0..64 {
+        assert_eq!(
+            sample_noise_bp(&mut shared, 120, 300),
+            direct.norm_bounded_bp(0, 120, 300).0
+        );
+    }
+}
+
+#[test]
+fn zero_sigma_noise_still_consumes_draws() {
+    let mut rng = RngStream::econ_day(5, 1, Some(HubId(1)), EconomyDay(1), 2);
+    assert_eq!(sample_noise_bp(&mut rng, 0, 100), 0);
+    assert_eq!(rng.cursor(), 6);
+}
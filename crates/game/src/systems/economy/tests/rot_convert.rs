@@ -1,6 +1,8 @@
 use std::path::{Path, PathBuf};
 
-use crate::systems::economy::{convert_rot_to_debt, load_rulepack, MoneyCents};
+use crate::systems::economy::{
+    accrue_spoilage_rot, convert_rot_to_debt, load_rulepack, CommodityId, MoneyCents,
+};
 
 fn workspace_path(relative: &str) -> PathBuf {
     let manifest_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
@@ -50,3 +52,20 @@ fn decay_applies_before_conversion() {
     assert_eq!(rot_after, cfg.rot_floor);
     assert_eq!(delta, MoneyCents::ZERO);
 }
+
+#[test]
+fn spoilage_accrues_rot_up_to_ceiling() {
+    let cfg = cfg();
+    let spoiled = [(CommodityId(1), 3), (CommodityId(3), 4)];
+    assert_eq!(
+        accrue_spoilage_rot(cfg.rot_floor, &spoiled, &cfg),
+        cfg.rot_floor + 7
+    );
+    assert_eq!(accrue_spoilage_rot(cfg.rot_floor, &[], &cfg), cfg.rot_floor);
+
+    let flood = [(CommodityId(1), u32::MAX)];
+    assert_eq!(
+        accrue_spoilage_rot(cfg.rot_floor, &flood, &cfg),
+        cfg.rot_ceiling
+    );
+}
@@ -4,7 +4,7 @@ use std::path::{Path, PathBuf};
 
 use tempfile::NamedTempFile;
 
-use crate::systems::economy::load_rulepack;
+use crate::systems::economy::{load_rulepack, CommodityId, PricingOverride, RulepackError};
 
 fn workspace_path(relative: &str) -> PathBuf {
     let manifest_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
@@ -58,3 +58,43 @@ fn rejects_missing_sections() {
     let msg = err.to_string();
     assert!(msg.contains("missing field"), "unexpected error: {}", msg);
 }
+
+fn day_001_with_pricing_overrides(overrides: &str) -> NamedTempFile {
+    let base =
+        fs::read_to_string(workspace_path("assets/rulepacks/day_001.toml")).expect("fixture");
+    let mut tmp = NamedTempFile::new().expect("tmp file");
+    write!(tmp, "{base}\n[pricing.overrides]\n{overrides}").expect("write tmp");
+    tmp
+}
+
+#[test]
+fn parses_pricing_overrides_by_commodity() {
+    let tmp = day_001_with_pricing_overrides(
+        "3 = { min_multiplier_bp = -1000, max_multiplier_bp = 1500 }\n",
+    );
+    let pack = load_rulepack(tmp.path().to_str().unwrap()).expect("load rulepack");
+    assert_eq!(
+        pack.pricing.override_for(CommodityId(3)),
+        Some(&PricingOverride {
+            min_multiplier_bp: -1_000,
+            max_multiplier_bp: 1_500,
+        })
+    );
+    assert_eq!(pack.pricing.override_for(CommodityId(1)), None);
+}
+
+#[test]
+fn rejects_pricing_overrides_outside_the_global_bounds() {
+    for bounds in [
+        "min_multiplier_bp = 500, max_multiplier_bp = 500",
+        "min_multiplier_bp = -3500, max_multiplier_bp = 1000",
+        "min_multiplier_bp = -1000, max_multiplier_bp = 4500",
+    ] {
+        let tmp = day_001_with_pricing_overrides(&format!("2 = {{ {bounds} }}\n"));
+        let err = load_rulepack(tmp.path().to_str().unwrap()).expect_err(bounds);
+        assert!(
+            matches!(err, RulepackError::PricingOverride { commodity: 2, .. }),
+            "{bounds}: {err}"
+        );
+    }
+}
@@ -0,0 +1,163 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use bevy::prelude::*;
+
+use crate::app_state::AppState;
+use crate::systems::economy::rulepack::swap_rulepack_on_day_change;
+use crate::systems::economy::{
+    step_economy_day, EconState, EconStepScope, EconomyDay, HubId, Rulepack, RulepackError,
+    RulepackSchedule,
+};
+
+fn workspace_path(relative: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("../..")
+        .join(relative)
+}
+
+fn shipped_schedule() -> RulepackSchedule {
+    RulepackSchedule::load(&workspace_path("assets/rulepacks/schedule.toml")).expect("schedule")
+}
+
+fn write_schedule(dir: &Path, body: &str) -> PathBuf {
+    for file in ["day_001.toml", "day_010.toml"] {
+        fs::copy(
+            workspace_path(&format!("assets/rulepacks/{file}")),
+            dir.join(file),
+        )
+        .expect("copy rulepack");
+    }
+    let path = dir.join("schedule.toml");
+    fs::write(&path, body).expect("write schedule");
+    path
+}
+
+#[test]
+fn days_resolve_to_their_scheduled_rulepack() {
+    let schedule = shipped_schedule();
+    assert_eq!(schedule.entry_for(EconomyDay(0)).file, "day_001.toml");
+    assert_eq!(schedule.entry_for(EconomyDay(5)).file, "day_001.toml");
+    assert_eq!(schedule.entry_for(EconomyDay(10)).file, "day_010.toml");
+    assert_eq!(schedule.entry_for(EconomyDay(15)).file, "day_010.toml");
+    assert_eq!(schedule.for_day(EconomyDay(5)).di.long_run_mean_bp, 75);
+    assert_eq!(schedule.for_day(EconomyDay(15)).di.long_run_mean_bp, 120);
+}
+
+#[test]
+fn step_consults_the_rulepack_for_the_stepped_day() {
+    let schedule = shipped_schedule();
+    for day in [5, 15] {
+        let start = EconState {
+            day: EconomyDay(day),
+            debt_cents: crate::systems::economy::MoneyCents(5_000_000),
+            ..EconState::default()
+        };
+        let mut scheduled = start.clone();
+        let mut pinned = start;
+        let via_schedule = step_economy_day(
+            &schedule,
+            7,
+            1,
+            HubId(1),
+            &mut scheduled,
+            EconStepScope::GlobalAndHub,
+        );
+        let rulepack: &Rulepack = schedule.for_day(EconomyDay(day));
+        let via_rulepack = step_economy_day(
+            rulepack,
+            7,
+            1,
+            HubId(1),
+            &mut pinned,
+            EconStepScope::GlobalAndHub,
+        );
+        assert_eq!(
+            serde_json::to_string(&via_schedule).unwrap(),
+            serde_json::to_string(&via_rulepack).unwrap()
+        );
+    }
+
+    let mut early = EconState {
+        day: EconomyDay(5),
+        debt_cents: crate::systems::economy::MoneyCents(5_000_000),
+        ..EconState::default()
+    };
+    let mut late = EconState {
+        day: EconomyDay(15),
+        ..early.clone()
+    };
+    let early = step_economy_day(
+        &schedule,
+        7,
+        1,
+        HubId(1),
+        &mut early,
+        EconStepScope::GlobalAndHub,
+    );
+    let late = step_economy_day(
+        &schedule,
+        7,
+        1,
+        HubId(1),
+        &mut late,
+        EconStepScope::GlobalAndHub,
+    );
+    assert_ne!(early.interest_delta, late.interest_delta);
+}
+
+#[test]
+fn schedule_must_start_at_day_zero_and_increase() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let late_start = write_schedule(
+        dir.path(),
+        "[[rulepack]]\nfrom_day = 3\nfile = \"day_001.toml\"\n",
+    );
+    assert!(matches!(
+        RulepackSchedule::load(&late_start),
+        Err(RulepackError::ScheduleStart(3))
+    ));
+
+    let unordered = write_schedule(
+        dir.path(),
+        "[[rulepack]]\nfrom_day = 0\nfile = \"day_001.toml\"\n\n\
+         [[rulepack]]\nfrom_day = 0\nfile = \"day_010.toml\"\n",
+    );
+    assert!(matches!(
+        RulepackSchedule::load(&unordered),
+        Err(RulepackError::ScheduleOrder {
+            day: 0,
+            previous: 0
+        })
+    ));
+
+    let empty = write_schedule(dir.path(), "rulepack = []\n");
+    assert!(matches!(
+        RulepackSchedule::load(&empty),
+        Err(RulepackError::EmptySchedule)
+    ));
+}
+
+#[test]
+fn rulepack_resource_swaps_at_the_scheduled_day() {
+    let schedule = shipped_schedule();
+    let mut world = World::new();
+    let mut app_state = AppState::default();
+    app_state.econ.day = EconomyDay(8);
+    world.insert_resource(app_state);
+    world.insert_resource(schedule.for_day(EconomyDay(8)).clone());
+    world.insert_resource(schedule);
+
+    let mut system = IntoSystem::into_system(swap_rulepack_on_day_change);
+    system.initialize(&mut world);
+    let mut step_to = |world: &mut World, day: u32| {
+        world.resource_mut::<AppState>().econ.day = EconomyDay(day);
+        let _ = system.run((), world);
+        world.resource::<Rulepack>().di.long_run_mean_bp
+    };
+
+    assert_eq!(step_to(&mut world, 8), 75);
+    assert_eq!(step_to(&mut world, 9), 75);
+    assert_eq!(step_to(&mut world, 10), 120);
+    assert_eq!(step_to(&mut world, 11), 120);
+}
@@ -3,9 +3,11 @@ use std::fs;
 use std::path::{Path, PathBuf};
 
 use crate::systems::economy::{
-    load_rulepack, step_economy_day, BasisBp, CommodityId, EconState, EconStepScope, EconomyDay,
-    HubId, MoneyCents, PendingPlanting, Pp,
+    accrue_interest_per_leg, accrue_leg_interest, load_rulepack, step_economy_day, BasisBp,
+    CommodityId, EconState, EconStepScope, EconomyDay, HubId, InterestAccrual, MoneyCents,
+    PendingPlanting, Pp, RouteId,
 };
+use crate::systems::trading::ledger::{LedgerJournal, LedgerSource, Wallet, WalletAccess};
 
 fn workspace_path(relative: &str) -> PathBuf {
     let manifest_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
@@ -31,6 +33,7 @@ fn state_step_matches_golden() {
         di_overlay_bp: 120,
         basis_bp: HashMap::new(),
         basis_drivers: HashMap::new(),
+        closed_routes: HashMap::new(),
         pp: Pp(rp.pp.neutral_pp),
         rot_u16: 200,
         pending_planting: vec![PendingPlanting {
@@ -39,6 +42,9 @@ fn state_step_matches_golden() {
             age_days: 0,
         }],
         debt_cents: MoneyCents(10_000),
+        price_history: HashMap::new(),
+        trade_pressure: HashMap::new(),
+        ..EconState::default()
     };
 
     let mut history = Vec::new();
@@ -77,10 +83,14 @@ fn hub_only_scope_skips_global_progression() {
         di_overlay_bp: 0,
         basis_bp: HashMap::new(),
         basis_drivers: HashMap::new(),
+        closed_routes: HashMap::new(),
         pp: Pp(rp.pp.neutral_pp),
         rot_u16: 0,
         pending_planting: Vec::new(),
         debt_cents: MoneyCents(1_000),
+        price_history: HashMap::new(),
+        trade_pressure: HashMap::new(),
+        ..EconState::default()
     };
 
     let first_delta =
@@ -110,6 +120,172 @@ fn hub_only_scope_skips_global_progression() {
     assert_eq!(second_delta.interest_delta, MoneyCents::ZERO);
 }
 
+#[test]
+fn closed_routes_feed_the_basis_driver() {
+    let rp = load_rulepack(
+        workspace_path("assets/rulepacks/day_001.toml")
+            .to_str()
+            .unwrap(),
+    )
+    .expect("rulepack");
+
+    let base = EconState {
+        di_bp: HashMap::from([(CommodityId(1), BasisBp(0))]),
+        pp: Pp(rp.pp.neutral_pp),
+        ..EconState::default()
+    };
+    let mut open = base.clone();
+    let mut closed = EconState {
+        closed_routes: HashMap::from([(HubId(1), 2)]),
+        ..base
+    };
+
+    step_economy_day(&rp, 9, 1, HubId(1), &mut open, EconStepScope::GlobalAndHub);
+    step_economy_day(
+        &rp,
+        9,
+        1,
+        HubId(1),
+        &mut closed,
+        EconStepScope::GlobalAndHub,
+    );
+
+    assert_eq!(open.basis_drivers[&HubId(1)].closed_routes, 0);
+    assert_eq!(closed.basis_drivers[&HubId(1)].closed_routes, 2);
+    let key = (HubId(1), CommodityId(1));
+    assert_eq!(
+        closed.basis_bp[&key].0 - open.basis_bp[&key].0,
+        2 * rp.basis.beta_routes_bp
+    );
+}
+
+#[test]
+fn leg_cadence_accrues_once_per_completed_leg() {
+    let mut rp = load_rulepack(
+        workspace_path("assets/rulepacks/day_001.toml")
+            .to_str()
+            .unwrap(),
+    )
+    .expect("rulepack");
+    rp.interest.accrue_on = InterestAccrual::Leg;
+
+    let mut state = EconState {
+        di_bp: HashMap::from([(CommodityId(1), BasisBp(0))]),
+        pp: Pp(rp.pp.neutral_pp),
+        debt_cents: MoneyCents(500_000),
+        ..EconState::default()
+    };
+
+    for _ in 0..3 {
+        let delta = step_economy_day(&rp, 9, 1, HubId(1), &mut state, EconStepScope::GlobalAndHub);
+        assert_eq!(delta.interest_delta, MoneyCents::ZERO);
+    }
+    assert_eq!(state.debt_cents, MoneyCents(500_000));
+
+    let (expected, _) = accrue_interest_per_leg(MoneyCents(500_000), &rp.interest);
+    let mut wallet = Wallet::default();
+    let mut journal = LedgerJournal::default();
+    let event = accrue_leg_interest(
+        &mut state,
+        &rp.interest,
+        RouteId(4),
+        &mut WalletAccess::new(&mut wallet, &mut journal, 0),
+    )
+    .expect("leg mode");
+    assert_eq!(event.route, RouteId(4));
+    assert_eq!(event.day, EconomyDay(3));
+    assert_eq!(event.debt_before, MoneyCents(500_000));
+    assert_eq!(event.interest_delta, expected);
+    assert_eq!(event.shortfall_cents, expected);
+    assert_eq!(state.debt_cents, event.debt_after);
+    assert_eq!(
+        state.debt_cents,
+        MoneyCents(500_000).saturating_add(expected)
+    );
+}
+
+#[test]
+fn leg_interest_shortfall_goes_to_debt_not_the_wallet() {
+    let mut rp = load_rulepack(
+        workspace_path("assets/rulepacks/day_001.toml")
+            .to_str()
+            .unwrap(),
+    )
+    .expect("rulepack");
+    rp.interest.accrue_on = InterestAccrual::Leg;
+    let (interest, _) = accrue_interest_per_leg(MoneyCents(500_000), &rp.interest);
+    assert!(interest.as_i64() > 100, "fixture should charge interest");
+
+    let mut state = EconState {
+        debt_cents: MoneyCents(500_000),
+        ..EconState::default()
+    };
+    let mut wallet = Wallet::new(interest.saturating_sub(MoneyCents(100)));
+    let mut journal = LedgerJournal::default();
+    let event = accrue_leg_interest(
+        &mut state,
+        &rp.interest,
+        RouteId(4),
+        &mut WalletAccess::new(&mut wallet, &mut journal, 0),
+    )
+    .expect("leg mode");
+
+    assert_eq!(wallet.balance(), MoneyCents::ZERO);
+    assert_eq!(event.paid_cents, interest.saturating_sub(MoneyCents(100)));
+    assert_eq!(event.shortfall_cents, MoneyCents(100));
+    assert_eq!(state.debt_cents, MoneyCents(500_100));
+    assert_eq!(
+        journal.net_for(LedgerSource::Interest),
+        -event.paid_cents.as_i64()
+    );
+
+    // Already overdrawn wallets are left alone; all of it becomes debt.
+    let mut overdrawn = Wallet::new(MoneyCents(-50));
+    let event = accrue_leg_interest(
+        &mut state,
+        &rp.interest,
+        RouteId(4),
+        &mut WalletAccess::new(&mut overdrawn, &mut journal, 1),
+    )
+    .expect("leg mode");
+    assert_eq!(overdrawn.balance(), MoneyCents(-50));
+    assert_eq!(event.paid_cents, MoneyCents::ZERO);
+    assert_eq!(event.shortfall_cents, event.interest_delta);
+    assert_eq!(
+        state.debt_cents,
+        MoneyCents(500_100).saturating_add(event.interest_delta)
+    );
+}
+
+#[test]
+fn day_cadence_leaves_leg_completion_alone() {
+    let rp = load_rulepack(
+        workspace_path("assets/rulepacks/day_001.toml")
+            .to_str()
+            .unwrap(),
+    )
+    .expect("rulepack");
+    assert_eq!(rp.interest.accrue_on, InterestAccrual::Day);
+
+    let mut state = EconState {
+        debt_cents: MoneyCents(500_000),
+        ..EconState::default()
+    };
+    let mut wallet = Wallet::new(MoneyCents(10_000));
+    let mut journal = LedgerJournal::default();
+    assert_eq!(
+        accrue_leg_interest(
+            &mut state,
+            &rp.interest,
+            RouteId(4),
+            &mut WalletAccess::new(&mut wallet, &mut journal, 0),
+        ),
+        None
+    );
+    assert_eq!(wallet.balance(), MoneyCents(10_000));
+    assert_eq!(state.debt_cents, MoneyCents(500_000));
+}
+
 fn maybe_update_state_golden(path: &Path, contents: &str) {
     if std::env::var_os("UPDATE_ECON_GOLDENS").is_none() {
         return;
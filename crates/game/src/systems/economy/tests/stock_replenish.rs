@@ -0,0 +1,183 @@
+use std::fs;
+use std::path::Path;
+
+use crate::systems::economy::rulepack::load_rulepack;
+use crate::systems::economy::{
+    step_economy_day, BasisBp, CommodityId, EconDelta, EconState, EconStepScope, HubId,
+    ReplenishCfg, Rulepack,
+};
+
+const HUB: HubId = HubId(1);
+const COM: CommodityId = CommodityId(1);
+
+/// The shipped rulepack with NPC flow turned on; the shipped packs leave it
+/// off.
+fn rulepack() -> Rulepack {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("../../assets/rulepacks/day_001.toml");
+    let mut rp = load_rulepack(path.to_str().expect("utf-8 path")).expect("rulepack");
+    rp.stock.replenish = ReplenishCfg {
+        equilibrium_units: 200,
+        rate_bp: 1_500,
+        max_units_per_day: 40,
+        demand_max_units: 6,
+    };
+    rp
+}
+
+fn start_state() -> EconState {
+    let mut state = EconState::default();
+    state.di_bp.insert(COM, BasisBp(0));
+    state.di_bp.insert(CommodityId(2), BasisBp(0));
+    state
+}
+
+fn step_days(rp: &Rulepack, state: &mut EconState, days: u32) -> Vec<EconDelta> {
+    (0..days)
+        .map(|_| step_economy_day(rp, 7, 1, HUB, state, EconStepScope::GlobalAndHub))
+        .collect()
+}
+
+#[test]
+fn stock_converges_toward_equilibrium_without_trades() {
+    let rp = rulepack();
+    let cfg = &rp.stock.replenish;
+    let mut state = start_state();
+    state.stock_offset.insert((HUB, COM), -150);
+
+    let deltas = step_days(&rp, &mut state, 40);
+    let stock: Vec<u32> = deltas
+        .iter()
+        .map(|delta| delta.stock[0].stock_after)
+        .collect();
+    assert!(stock[0] > 50, "stock {stock:?}");
+    assert!(stock[9] > stock[0], "stock {stock:?}");
+    // Past this gap the drift outpaces any NPC demand, so stock settles
+    // inside it, just under equilibrium.
+    let settled_gap = cfg.demand_max_units * 10_000 / cfg.rate_bp as u32;
+    for (day, &units) in stock.iter().enumerate().skip(20) {
+        assert!(units <= cfg.equilibrium_units, "day {day} stock {stock:?}");
+        assert!(
+            cfg.equilibrium_units - units <= settled_gap,
+            "day {day} stock {stock:?}"
+        );
+    }
+    for delta in &deltas {
+        for flow in &delta.stock {
+            assert!(flow.demand <= cfg.demand_max_units);
+        }
+    }
+}
+
+#[test]
+fn drift_closes_the_configured_share_of_the_gap() {
+    let mut rp = rulepack();
+    rp.stock.replenish = ReplenishCfg {
+        equilibrium_units: 500,
+        rate_bp: 1_500,
+        max_units_per_day: 1_000,
+        demand_max_units: 0,
+    };
+    let mut state = start_state();
+    state.stock_offset.insert((HUB, COM), -400);
+    state.stock_offset.insert((HUB, CommodityId(2)), 300);
+
+    let mut gap = [-400_i32, 300];
+    for delta in step_days(&rp, &mut state, 12) {
+        for (flow, gap) in delta.stock.iter().zip(gap.iter_mut()) {
+            let expected = match -*gap * 1_500 / 10_000 {
+                0 => -gap.signum(),
+                step => step,
+            };
+            assert_eq!(flow.replenished, expected, "gap {gap}");
+            assert_eq!(flow.demand, 0);
+            *gap += flow.replenished;
+            assert_eq!(i64::from(flow.stock_after), 500 + i64::from(*gap));
+        }
+    }
+    assert_eq!(state.stock_offset(HUB, COM), gap[0]);
+
+    // The daily cap bounds the drift in both directions.
+    rp.stock.replenish.max_units_per_day = 20;
+    let mut capped = start_state();
+    capped.stock_offset.insert((HUB, COM), -400);
+    capped.stock_offset.insert((HUB, CommodityId(2)), 300);
+    let delta = step_days(&rp, &mut capped, 1).remove(0);
+    let moved: Vec<i32> = delta.stock.iter().map(|flow| flow.replenished).collect();
+    assert_eq!(moved, [20, -20]);
+}
+
+#[test]
+fn trades_move_stock_the_other_way() {
+    let rp = rulepack();
+    let mut state = start_state();
+    state.apply_trade_pressure(HUB, COM, 30);
+    assert_eq!(state.hub_stock(HUB, COM, &rp.stock.replenish), 170);
+    state.apply_trade_pressure(HUB, COM, -30);
+    assert!(state.stock_offset.is_empty());
+}
+
+#[test]
+fn npc_flow_leaves_di_and_basis_draws_alone() {
+    let rp = rulepack();
+    let mut quiet_rp = rp.clone();
+    quiet_rp.stock.replenish = ReplenishCfg::default();
+
+    let mut with_flow = start_state();
+    let mut without_flow = start_state();
+    let flowing = step_days(&rp, &mut with_flow, 10);
+    let quiet = step_days(&quiet_rp, &mut without_flow, 10);
+
+    for (flow, quiet) in flowing.iter().zip(&quiet) {
+        assert_eq!(flow.di, quiet.di);
+        assert_eq!(flow.rng_cursors[..2], quiet.rng_cursors[..]);
+        assert_eq!(flow.rng_cursors[2].label, "npc_demand");
+        assert_eq!(flow.rng_cursors[2].draws, 2);
+        assert!(quiet.stock.is_empty());
+    }
+    assert_eq!(with_flow.pp, without_flow.pp);
+    assert_eq!(with_flow.debt_cents, without_flow.debt_cents);
+}
+
+#[test]
+fn shipped_rulepacks_leave_npc_flow_off() {
+    for name in ["day_001.toml", "day_010.toml"] {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("../../assets/rulepacks")
+            .join(name);
+        let rp = load_rulepack(path.to_str().expect("utf-8 path")).expect("rulepack");
+        assert!(!rp.stock.replenish.is_active(), "{name}");
+    }
+}
+
+#[test]
+fn npc_flow_feeds_the_trade_pressure() {
+    let mut rp = rulepack();
+    rp.stock.replenish.demand_max_units = 0;
+    let mut state = start_state();
+    state.stock_offset.insert((HUB, COM), -150);
+
+    let flow = step_days(&rp, &mut state, 1).remove(0).stock[0];
+    assert!(flow.replenished > 0);
+    // The refill lands after the day's basis update, so it is priced in as
+    // falling demand on the next step.
+    assert_eq!(state.trade_pressure(HUB, COM), -flow.replenished);
+    assert_eq!(state.stock_offset(HUB, COM), -150 + flow.replenished);
+}
+
+#[test]
+fn npc_flow_steps_match_golden() {
+    let rp = rulepack();
+    let mut state = start_state();
+    state.stock_offset.insert((HUB, COM), -150);
+    state.stock_offset.insert((HUB, CommodityId(2)), 80);
+
+    let history = step_days(&rp, &mut state, 10);
+    let actual = serde_json::to_string_pretty(&history).expect("serialize");
+    let golden_path = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("src/systems/economy/tests/stock_replenish_golden.json");
+    if std::env::var_os("UPDATE_ECON_GOLDENS").is_some() {
+        fs::write(&golden_path, format!("{actual}\n")).expect("write golden");
+    }
+    let golden = fs::read_to_string(&golden_path).expect("read golden");
+    assert_eq!(actual, golden.trim());
+}
@@ -0,0 +1,97 @@
+use std::path::Path;
+
+use crate::systems::economy::rulepack::load_rulepack;
+use crate::systems::economy::state::TRADE_PRESSURE_LIMIT;
+use crate::systems::economy::{
+    step_economy_day, BasisBp, CommodityId, EconState, EconStepScope, EconomyDay, HubId, Rulepack,
+};
+
+const HUB: HubId = HubId(1);
+const COM: CommodityId = CommodityId(1);
+
+fn rulepack() -> Rulepack {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("../../assets/rulepacks/day_001.toml");
+    load_rulepack(path.to_str().expect("utf-8 path")).expect("rulepack")
+}
+
+fn start_state() -> EconState {
+    let mut state = EconState::default();
+    state.di_bp.insert(COM, BasisBp(0));
+    state.di_bp.insert(CommodityId(2), BasisBp(0));
+    state
+}
+
+/// Steps `state` for `days` and returns the hub basis of [`COM`] after each.
+fn basis_series(rp: &Rulepack, state: &mut EconState, days: u32) -> Vec<i32> {
+    (0..days)
+        .map(|day| {
+            state.day = EconomyDay(day);
+            step_economy_day(rp, 7, 1, HUB, state, EconStepScope::GlobalAndHub);
+            state.basis_bp[&(HUB, COM)].0
+        })
+        .collect()
+}
+
+fn pressure_gaps(days: u32) -> (Vec<i32>, EconState) {
+    let rp = rulepack();
+    let mut baseline = start_state();
+    let mut hoarded = start_state();
+    hoarded.apply_trade_pressure(HUB, COM, 150);
+
+    let quiet = basis_series(&rp, &mut baseline, days);
+    let pressed = basis_series(&rp, &mut hoarded, days);
+    let gaps = pressed.iter().zip(&quiet).map(|(p, q)| p - q).collect();
+    (gaps, hoarded)
+}
+
+#[test]
+fn heavy_buying_raises_the_next_basis() {
+    let (gaps, _) = pressure_gaps(1);
+    assert!(gaps[0] > 0, "gap {gaps:?}");
+}
+
+#[test]
+fn pressure_decays_back_toward_baseline() {
+    let (gaps, hoarded) = pressure_gaps(20);
+    assert!(gaps[0] > 0, "gaps {gaps:?}");
+    assert!(
+        gaps.windows(2).all(|pair| pair[1] <= pair[0]),
+        "gaps {gaps:?}"
+    );
+    assert!(gaps[4] * 2 < gaps[0], "gaps {gaps:?}");
+    assert_eq!(gaps[19], 0, "gaps {gaps:?}");
+    assert!(hoarded.trade_pressure.is_empty());
+}
+
+#[test]
+fn pressure_only_touches_its_own_commodity() {
+    let rp = rulepack();
+    let mut baseline = start_state();
+    let mut hoarded = start_state();
+    hoarded.apply_trade_pressure(HUB, COM, 150);
+    basis_series(&rp, &mut baseline, 3);
+    basis_series(&rp, &mut hoarded, 3);
+    assert_eq!(
+        baseline.basis_bp[&(HUB, CommodityId(2))],
+        hoarded.basis_bp[&(HUB, CommodityId(2))]
+    );
+}
+
+#[test]
+fn pressure_replays_identically() {
+    let (first, first_state) = pressure_gaps(12);
+    let (second, second_state) = pressure_gaps(12);
+    assert_eq!(first, second);
+    assert_eq!(first_state.trade_pressure, second_state.trade_pressure);
+    assert_eq!(first_state.basis_bp, second_state.basis_bp);
+}
+
+#[test]
+fn pressure_is_bounded() {
+    let mut state = start_state();
+    state.apply_trade_pressure(HUB, COM, i32::MAX);
+    state.apply_trade_pressure(HUB, COM, 5);
+    assert_eq!(state.trade_pressure(HUB, COM), TRADE_PRESSURE_LIMIT);
+    state.apply_trade_pressure(HUB, COM, -TRADE_PRESSURE_LIMIT);
+    assert!(state.trade_pressure.is_empty());
+}
@@ -0,0 +1,116 @@
+use std::path::{Path, PathBuf};
+
+use crate::app_state::AppState;
+use crate::systems::economy::verify::{resimulate_to_day, verify_save, EconMismatch};
+use crate::systems::economy::{
+    load_rulepack, BasisBp, CommodityId, EconomyDay, HubId, MoneyCents, Rulepack,
+};
+use crate::systems::save::{snapshot_from_app_state, CommoditySave, SaveV18};
+
+const WORLD_SEED: u64 = 0xD7E7_2024_0001_0001;
+const HUBS: [HubId; 3] = [HubId(1), HubId(2), HubId(3)];
+const COMMODITIES: [CommodityId; 2] = [CommodityId(1), CommodityId(2)];
+
+fn workspace_path(relative: &str) -> PathBuf {
+    let manifest_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let root = manifest_dir
+        .parent()
+        .and_then(|p| p.parent())
+        .expect("workspace root");
+    root.join(relative)
+}
+
+fn rulepack() -> Rulepack {
+    load_rulepack(
+        workspace_path("assets/rulepacks/day_001.toml")
+            .to_str()
+            .unwrap(),
+    )
+    .expect("rulepack")
+}
+
+fn fresh_save(rp: &Rulepack, day: u32) -> SaveV18 {
+    let state = AppState {
+        world_seed: WORLD_SEED,
+        ..AppState::default()
+    };
+    let econ = resimulate_to_day(
+        WORLD_SEED,
+        state.econ_version,
+        EconomyDay(day),
+        rp,
+        &HUBS,
+        &COMMODITIES,
+    );
+    assert_eq!(econ.day, EconomyDay(day));
+    let snapshot = snapshot_from_app_state(&AppState { econ, ..state });
+    // Through JSON, the way saves reach disk and come back.
+    serde_json::from_str(&serde_json::to_string(&snapshot).expect("save json")).expect("load")
+}
+
+#[test]
+fn freshly_simulated_save_verifies() {
+    let rp = rulepack();
+    for day in [0, 1, 6] {
+        let save = fresh_save(&rp, day);
+        assert_eq!(save.basis.len(), if day == 0 { 0 } else { 6 });
+        verify_save(&save, &rp).expect("untouched economy verifies");
+    }
+}
+
+#[test]
+fn tampered_save_reports_first_mismatched_field() {
+    let rp = rulepack();
+    let save = fresh_save(&rp, 6);
+
+    let mut tampered = save.clone();
+    let entry = tampered
+        .basis
+        .iter_mut()
+        .find(|entry| entry.hub == HubId(2) && entry.commodity == CommodityId(1))
+        .expect("basis entry");
+    let original = entry.value;
+    entry.value = BasisBp(original.0 + 1);
+    tampered.pp.0 += 1;
+    assert_eq!(
+        verify_save(&tampered, &rp),
+        Err(EconMismatch {
+            field: "basis[2/1]".into(),
+            saved: (original.0 + 1).to_string(),
+            expected: original.0.to_string(),
+        })
+    );
+
+    let mut tampered = save.clone();
+    tampered.pp.0 += 1;
+    let err = verify_save(&tampered, &rp).expect_err("pp tampered");
+    assert_eq!(err.field, "pp");
+
+    let mut tampered = save.clone();
+    tampered.debt_cents = tampered.debt_cents.saturating_add(MoneyCents(5));
+    assert_eq!(
+        verify_save(&tampered, &rp).expect_err("debt").field,
+        "debt_cents"
+    );
+
+    // An entry only the re-simulation has shows as `none` on the save side.
+    let mut tampered = save.clone();
+    tampered
+        .basis
+        .retain(|entry| entry.commodity != CommodityId(2) || entry.hub != HubId(1));
+    let err = verify_save(&tampered, &rp).expect_err("dropped basis entry");
+    assert_eq!(err.field, "basis[1/2]");
+    assert_eq!(err.saved, "none");
+
+    let mut tampered = save;
+    tampered.di[1] = CommoditySave {
+        commodity: CommodityId(2),
+        value: BasisBp(tampered.di[1].value.0 - 10),
+    };
+    let err = verify_save(&tampered, &rp).expect_err("di tampered");
+    assert_eq!(err.field, "di[2]");
+    assert!(
+        err.to_string().contains("save economy diverges at di[2]"),
+        "{err}"
+    );
+}
@@ -0,0 +1,197 @@
+//! Re-derives an economy from its seed and checks a save against it.
+//!
+//! The economy is a pure function of the world seed, econ version, rulepack
+//! and day until the player touches it, so a save whose economy was only
+//! ever stepped can be rebuilt from a neutral day 0 and compared. Trades,
+//! plantings, route closures and market events all move the economy off
+//! that path, and a save carrying any of them reports a mismatch.
+
+use thiserror::Error;
+
+use super::order::{sorted_basis, sorted_commodities};
+use super::rulepack::RulepackSource;
+use super::{
+    step_economy_day, BasisBp, CommodityId, EconDelta, EconState, EconStepScope, EconomyDay, HubId,
+    Pp,
+};
+use crate::systems::save::SaveV18;
+
+/// The first di, basis, PP or debt value where a save and its
+/// re-simulation disagree. A value missing on one side shows as `none`.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("save economy diverges at {field}: save has {saved}, re-simulation gives {expected}")]
+pub struct EconMismatch {
+    pub field: String,
+    pub saved: String,
+    pub expected: String,
+}
+
+/// Day 0 with every commodity's DI at zero, no basis and PP at the
+/// rulepack's neutral level.
+pub fn neutral_state<R: RulepackSource + ?Sized>(
+    rules: &R,
+    commodities: &[CommodityId],
+) -> EconState {
+    EconState {
+        di_bp: commodities
+            .iter()
+            .map(|&commodity| (commodity, BasisBp(0)))
+            .collect(),
+        pp: Pp(rules.rulepack_for(EconomyDay(0)).pp.neutral_pp),
+        ..EconState::default()
+    }
+}
+
+/// Steps a [`neutral_state`] up to `day`, one [`step_all_hubs`] per day.
+pub fn resimulate_to_day<R: RulepackSource + ?Sized>(
+    world_seed: u64,
+    econ_version: u32,
+    day: EconomyDay,
+    rules: &R,
+    hubs: &[HubId],
+    commodities: &[CommodityId],
+) -> EconState {
+    let mut state = neutral_state(rules, commodities);
+    if hubs.is_empty() {
+        return state;
+    }
+    for _ in 0..day.0 {
+        step_all_hubs(rules, world_seed, econ_version, hubs, &mut state);
+    }
+    state
+}
+
+/// Advances `state` one day: the global step with the first hub, then a
+/// hub-only step for every other hub, in `hubs` order. Returns each hub's
+/// delta in that order; nothing happens without hubs.
+pub fn step_all_hubs<R: RulepackSource + ?Sized>(
+    rules: &R,
+    world_seed: u64,
+    econ_version: u32,
+    hubs: &[HubId],
+    state: &mut EconState,
+) -> Vec<EconDelta> {
+    hubs.iter()
+        .enumerate()
+        .map(|(idx, &hub)| {
+            let scope = if idx == 0 {
+                EconStepScope::GlobalAndHub
+            } else {
+                EconStepScope::HubOnly
+            };
+            step_economy_day(rules, world_seed, econ_version, hub, state, scope)
+        })
+        .collect()
+}
+
+/// Re-simulates the save's economy to its day over the hubs and
+/// commodities it holds, and compares di, basis, PP and debt in that order.
+pub fn verify_save<R: RulepackSource + ?Sized>(
+    save: &SaveV18,
+    rules: &R,
+) -> Result<(), EconMismatch> {
+    let mut commodities: Vec<CommodityId> = save.di.iter().map(|entry| entry.commodity).collect();
+    commodities.sort_unstable();
+    commodities.dedup();
+    let mut hubs: Vec<HubId> = save.basis.iter().map(|entry| entry.hub).collect();
+    hubs.sort_unstable();
+    hubs.dedup();
+    let expected = resimulate_to_day(
+        save.world_seed,
+        save.econ_version,
+        save.day,
+        rules,
+        &hubs,
+        &commodities,
+    );
+
+    let mut saved_di: Vec<(CommodityId, BasisBp)> = save
+        .di
+        .iter()
+        .map(|entry| (entry.commodity, entry.value))
+        .collect();
+    saved_di.sort_unstable_by_key(|(commodity, _)| *commodity);
+    let expected_di = sorted_commodities(&expected.di_bp)
+        .into_iter()
+        .map(|commodity| (commodity, expected.di_bp[&commodity]));
+    compare_entries(saved_di, expected_di, |commodity| {
+        format!("di[{}]", commodity.0)
+    })?;
+
+    let mut saved_basis: Vec<((HubId, CommodityId), BasisBp)> = save
+        .basis
+        .iter()
+        .map(|entry| ((entry.hub, entry.commodity), entry.value))
+        .collect();
+    saved_basis.sort_unstable_by_key(|(key, _)| *key);
+    let expected_basis = sorted_basis(&expected.basis_bp)
+        .into_iter()
+        .map(|key| (key, expected.basis_bp[&key]));
+    compare_entries(saved_basis, expected_basis, |(hub, commodity)| {
+        format!("basis[{}/{}]", hub.0, commodity.0)
+    })?;
+
+    compare_value("pp", save.pp.0, expected.pp.0)?;
+    compare_value(
+        "debt_cents",
+        save.debt_cents.as_i64(),
+        expected.debt_cents.as_i64(),
+    )
+}
+
+fn compare_value<T: PartialEq + ToString>(
+    field: &str,
+    saved: T,
+    expected: T,
+) -> Result<(), EconMismatch> {
+    if saved == expected {
+        return Ok(());
+    }
+    Err(EconMismatch {
+        field: field.to_owned(),
+        saved: saved.to_string(),
+        expected: expected.to_string(),
+    })
+}
+
+/// Walks two key-sorted lists together and reports the first key whose
+/// values differ or that only one side has.
+fn compare_entries<K: Copy + Ord>(
+    saved: Vec<(K, BasisBp)>,
+    expected: impl IntoIterator<Item = (K, BasisBp)>,
+    name: impl Fn(K) -> String,
+) -> Result<(), EconMismatch> {
+    use std::cmp::Ordering;
+
+    let show =
+        |value: Option<BasisBp>| value.map_or_else(|| "none".to_owned(), |v| v.0.to_string());
+    let mismatch = |key: K, saved: Option<BasisBp>, expected: Option<BasisBp>| EconMismatch {
+        field: name(key),
+        saved: show(saved),
+        expected: show(expected),
+    };
+    let mut saved = saved.into_iter().peekable();
+    let mut expected = expected.into_iter().peekable();
+    loop {
+        match (saved.peek().copied(), expected.peek().copied()) {
+            (None, None) => return Ok(()),
+            (Some((key, value)), None) => return Err(mismatch(key, Some(value), None)),
+            (None, Some((key, value))) => return Err(mismatch(key, None, Some(value))),
+            (Some((saved_key, saved_value)), Some((expected_key, expected_value))) => {
+                match saved_key.cmp(&expected_key) {
+                    Ordering::Less => return Err(mismatch(saved_key, Some(saved_value), None)),
+                    Ordering::Greater => {
+                        return Err(mismatch(expected_key, None, Some(expected_value)))
+                    }
+                    Ordering::Equal if saved_value != expected_value => {
+                        return Err(mismatch(saved_key, Some(saved_value), Some(expected_value)))
+                    }
+                    Ordering::Equal => {
+                        saved.next();
+                        expected.next();
+                    }
+                }
+            }
+        }
+    }
+}
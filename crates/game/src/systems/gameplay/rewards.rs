@@ -0,0 +1,300 @@
+//! Material rewards drawn when a leg completes.
+//!
+//! The draw is seeded from the leg (world seed, link, day) like a mission, so
+//! replays reproduce it, and the granted amounts are emitted as meters so
+//! they land in the record.
+
+use serde::Deserialize;
+
+use crate::app_state::AppState;
+use crate::systems::command_queue::CommandQueue;
+use crate::systems::director::rng::DetRng;
+use crate::systems::director::Outcome;
+use crate::systems::economy::{CommodityId, EconomyDay, MoneyCents, RouteId};
+use crate::systems::rng_stream::RngStream;
+use crate::systems::trading::engine::max_capacity_units;
+use crate::systems::trading::inventory::Cargo;
+use crate::systems::trading::ledger::{LedgerJournal, LedgerSource, WalletAccess};
+
+/// Reward tables per leg outcome. Both default to empty, which grants and
+/// emits nothing.
+#[derive(Debug, Default, Deserialize, Clone, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct RewardsCfg {
+    #[serde(default)]
+    pub success: RewardTable,
+    #[serde(default)]
+    pub failure: RewardTable,
+}
+
+impl RewardsCfg {
+    pub fn table(&self, outcome: Outcome) -> &RewardTable {
+        match outcome {
+            Outcome::Success => &self.success,
+            Outcome::Failure => &self.failure,
+        }
+    }
+}
+
+#[derive(Debug, Default, Deserialize, Clone, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct RewardTable {
+    #[serde(default)]
+    pub entries: Vec<RewardEntry>,
+}
+
+/// One weighted outcome: cents, commodity units, or both.
+#[derive(Debug, Deserialize, Clone, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct RewardEntry {
+    pub weight: u32,
+    #[serde(default)]
+    pub cents: i64,
+    #[serde(default)]
+    pub commodity: Option<u16>,
+    #[serde(default)]
+    pub units: u32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Reward {
+    pub cents: MoneyCents,
+    pub commodity: Option<CommodityId>,
+    pub units: u32,
+}
+
+/// What actually reached the player; units that did not fit are `dropped`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GrantedReward {
+    pub cents: MoneyCents,
+    pub commodity: Option<CommodityId>,
+    pub units: u32,
+    pub dropped: u32,
+}
+
+impl RewardTable {
+    /// Picks an entry by weight with a draw seeded by `seed`. Empty tables
+    /// and tables whose weights sum to zero yield nothing.
+    pub fn draw(&self, seed: u64) -> Option<Reward> {
+        let total: u64 = self
+            .entries
+            .iter()
+            .map(|entry| u64::from(entry.weight))
+            .sum();
+        let total = u32::try_from(total).unwrap_or(u32::MAX);
+        if total == 0 {
+            return None;
+        }
+        let mut roll = DetRng::from_seed(seed).range_u32(0, total - 1);
+        let entry = self.entries.iter().find(|entry| {
+            if roll < entry.weight {
+                true
+            } else {
+                roll -= entry.weight;
+                false
+            }
+        })?;
+        Some(Reward {
+            cents: MoneyCents(entry.cents),
+            commodity: entry.commodity.map(CommodityId),
+            units: entry.units,
+        })
+    }
+}
+
+/// Seed for a leg's reward draw.
+pub fn reward_seed(world_seed: u64, link_id: RouteId, day: u32) -> u64 {
+    RngStream::mission_seed(world_seed, link_id, day, "leg_reward")
+}
+
+/// Credits the wallet and loads as many units as the hold can take.
+pub fn grant_reward(
+    reward: &Reward,
+    wallet: &mut WalletAccess<'_>,
+    cargo: &mut Cargo,
+    day: EconomyDay,
+) -> GrantedReward {
+    wallet.credit(LedgerSource::ContractReward, reward.cents);
+    let mut units = 0;
+    if let Some(com) = reward.commodity {
+        let free = max_capacity_units(cargo, com).unwrap_or(0);
+        units = reward.units.min(free);
+        if units > 0 && cargo.load(com, units, day).is_none() {
+            units = 0;
+        }
+    }
+    GrantedReward {
+        cents: reward.cents,
+        commodity: reward.commodity,
+        units,
+        dropped: reward.units - units,
+    }
+}
+
+pub fn emit_reward(queue: &mut CommandQueue, granted: &GrantedReward) {
+    let cents = granted
+        .cents
+        .as_i64()
+        .clamp(i64::from(i32::MIN), i64::from(i32::MAX)) as i32;
+    queue.meter("reward_cents", cents);
+    if let Some(com) = granted.commodity {
+        queue.meter("reward_commodity", i32::from(com.0));
+        queue.meter_units("reward_units", granted.units);
+        if granted.dropped > 0 {
+            queue.meter_units("reward_units_dropped", granted.dropped);
+        }
+    }
+}
+
+/// Draws the leg's reward for `outcome`, applies it and records it.
+pub fn reward_leg(
+    cfg: &RewardsCfg,
+    outcome: Outcome,
+    seed: u64,
+    app_state: &mut AppState,
+    journal: &mut LedgerJournal,
+    queue: &mut CommandQueue,
+) -> Option<GrantedReward> {
+    let reward = cfg.table(outcome).draw(seed)?;
+    let day = app_state.econ.day;
+    let AppState { wallet, cargo, .. } = app_state;
+    let mut wallet = WalletAccess::new(wallet, journal, queue.current_tick());
+    let granted = grant_reward(&reward, &mut wallet, cargo, day);
+    emit_reward(queue, &granted);
+    Some(granted)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::systems::trading::default_commodities_path;
+    use crate::systems::trading::types::{CommodityCatalog, TradingConfig};
+
+    fn install_catalog() {
+        let catalog =
+            CommodityCatalog::load_from_path(&default_commodities_path()).expect("catalog");
+        CommodityCatalog::install_global(catalog);
+        TradingConfig::install_global(TradingConfig {
+            fee_bp: 75,
+            ..TradingConfig::default()
+        });
+    }
+
+    fn first_commodity() -> CommodityId {
+        CommodityCatalog::global().list()[0].id
+    }
+
+    fn cfg(com: CommodityId) -> RewardsCfg {
+        RewardsCfg {
+            success: RewardTable {
+                entries: vec![
+                    RewardEntry {
+                        weight: 3,
+                        cents: 1_500,
+                        commodity: None,
+                        units: 0,
+                    },
+                    RewardEntry {
+                        weight: 1,
+                        cents: 250,
+                        commodity: Some(com.0),
+                        units: 40,
+                    },
+                ],
+            },
+            failure: RewardTable {
+                entries: vec![RewardEntry {
+                    weight: 1,
+                    cents: 50,
+                    commodity: None,
+                    units: 0,
+                }],
+            },
+        }
+    }
+
+    #[test]
+    fn draws_are_deterministic_per_seed() {
+        install_catalog();
+        let cfg = cfg(first_commodity());
+        let mut picks = Vec::new();
+        for day in 0..64 {
+            let seed = reward_seed(0xD7E7_2024, RouteId(1), day);
+            let first = cfg.success.draw(seed);
+            assert_eq!(first, cfg.success.draw(seed));
+            picks.push(first.expect("reward").cents);
+        }
+        assert!(picks.contains(&MoneyCents(1_500)));
+        assert!(picks.contains(&MoneyCents(250)));
+        assert_eq!(RewardTable::default().draw(7), None);
+    }
+
+    #[test]
+    fn units_are_capped_by_cargo_capacity() {
+        install_catalog();
+        let com = first_commodity();
+        let spec = CommodityCatalog::global().get(com).cloned().expect("spec");
+        let mut app_state = AppState::default();
+        app_state.cargo.capacity_mass_kg = u32::from(spec.mass_kg.max(1)) * 5;
+        app_state.cargo.capacity_volume_l = u32::from(spec.volume_l.max(1)) * 5;
+        let reward = Reward {
+            cents: MoneyCents(250),
+            commodity: Some(com),
+            units: 40,
+        };
+        let mut journal = LedgerJournal::default();
+        let AppState { wallet, cargo, .. } = &mut app_state;
+        let mut wallet = WalletAccess::new(wallet, &mut journal, 9);
+        let granted = grant_reward(&reward, &mut wallet, cargo, EconomyDay(3));
+        assert_eq!(granted.units, 5);
+        assert_eq!(granted.dropped, 35);
+        assert_eq!(app_state.cargo.units(com), 5);
+        assert_eq!(app_state.wallet.balance(), MoneyCents(250));
+        assert_eq!(journal.net_for(LedgerSource::ContractReward), 250);
+
+        let mut queue = CommandQueue::default();
+        queue.begin_tick(9);
+        emit_reward(&mut queue, &granted);
+        let meters: Vec<(String, i32)> = queue
+            .drain()
+            .into_iter()
+            .filter_map(|command| match command.kind {
+                repro::CommandKind::Meter(meter) => Some((meter.key.to_string(), meter.value)),
+                repro::CommandKind::Spawn(_) => None,
+            })
+            .collect();
+        assert_eq!(
+            meters,
+            vec![
+                ("reward_cents".to_string(), 250),
+                ("reward_commodity".to_string(), i32::from(com.0)),
+                ("reward_units".to_string(), 5),
+                ("reward_units_dropped".to_string(), 35),
+            ]
+        );
+    }
+
+    #[test]
+    fn failed_leg_draws_from_failure_table() {
+        install_catalog();
+        let cfg = cfg(first_commodity());
+        let mut app_state = AppState::default();
+        app_state.econ.day = EconomyDay(4);
+        let mut journal = LedgerJournal::default();
+        let mut queue = CommandQueue::default();
+        let seed = reward_seed(11, RouteId(2), 4);
+        let granted = reward_leg(
+            &cfg,
+            Outcome::Failure,
+            seed,
+            &mut app_state,
+            &mut journal,
+            &mut queue,
+        )
+        .expect("failure reward");
+        assert_eq!(granted.cents, MoneyCents(50));
+        assert_eq!(granted.commodity, None);
+        assert_eq!(app_state.wallet.balance(), MoneyCents(50));
+        assert_eq!(queue.drain().len(), 1);
+    }
+}
@@ -3,7 +3,12 @@
 use serde_json::Value;
 use thiserror::Error;
 
-use crate::systems::save::{v1_1::migrate_v1_to_v11, SaveV11};
+use crate::systems::save::{
+    v1_1::migrate_v1_to_v11, v1_2::migrate_v11_to_v12, v1_3::migrate_v12_to_v13,
+    v1_4::migrate_v13_to_v14, v1_5::migrate_v14_to_v15, v1_6::migrate_v15_to_v16,
+    v1_7::migrate_v16_to_v17, v1_8::migrate_v17_to_v18, SaveV11, SaveV12, SaveV13, SaveV14,
+    SaveV15, SaveV16, SaveV17, SaveV18, SchemaVersion,
+};
 
 pub mod v1;
 
@@ -13,11 +18,55 @@ pub enum MigrateError {
     Serde(#[from] serde_json::Error),
 }
 
-pub fn migrate_to_latest(value: Value) -> Result<SaveV11, MigrateError> {
-    if value.get("cargo").is_some() || value.get("last_hub").is_some() {
-        return serde_json::from_value(value).map_err(MigrateError::from);
+pub fn detect_schema(value: &Value) -> SchemaVersion {
+    if value.get("cargo_lots").is_some() {
+        SchemaVersion::V18
+    } else if value.get("stock_offsets").is_some() {
+        SchemaVersion::V17
+    } else if value.get("mission_history").is_some() {
+        SchemaVersion::V16
+    } else if value.get("visited_links").is_some() {
+        SchemaVersion::V15
+    } else if value.get("trade_pressure").is_some() {
+        SchemaVersion::V14
+    } else if value.get("route_closures").is_some() {
+        SchemaVersion::V13
+    } else if value.get("price_history").is_some() {
+        SchemaVersion::V12
+    } else if value.get("cargo").is_some() || value.get("last_hub").is_some() {
+        SchemaVersion::V11
+    } else {
+        SchemaVersion::V1
     }
+}
+
+pub fn migrate_to_latest(value: Value) -> Result<SaveV18, MigrateError> {
+    let v17: SaveV17 = match detect_schema(&value) {
+        SchemaVersion::V18 => return serde_json::from_value(value).map_err(MigrateError::from),
+        SchemaVersion::V17 => serde_json::from_value(value)?,
+        SchemaVersion::V16 => migrate_v16_to_v17(serde_json::from_value::<SaveV16>(value)?),
+        SchemaVersion::V15 => migrate_v16_to_v17(migrate_v15_to_v16(serde_json::from_value::<
+            SaveV15,
+        >(value)?)),
+        SchemaVersion::V14 => migrate_v16_to_v17(migrate_v15_to_v16(migrate_v14_to_v15(
+            serde_json::from_value::<SaveV14>(value)?,
+        ))),
+        SchemaVersion::V13 => migrate_up_from_v13(serde_json::from_value(value)?),
+        SchemaVersion::V12 => migrate_up_from_v13(migrate_v12_to_v13(serde_json::from_value::<
+            SaveV12,
+        >(value)?)),
+        SchemaVersion::V11 => migrate_up_from_v13(migrate_v12_to_v13(migrate_v11_to_v12(
+            serde_json::from_value::<SaveV11>(value)?,
+        ))),
+        SchemaVersion::V1 => migrate_up_from_v13(migrate_v12_to_v13(migrate_v11_to_v12(
+            migrate_v1_to_v11(v1::from_value(value)?),
+        ))),
+    };
+    Ok(migrate_v17_to_v18(v17))
+}
 
-    let v1 = v1::from_value(value)?;
-    Ok(migrate_v1_to_v11(v1))
+fn migrate_up_from_v13(v13: SaveV13) -> SaveV17 {
+    migrate_v16_to_v17(migrate_v15_to_v16(migrate_v14_to_v15(migrate_v13_to_v14(
+        v13,
+    ))))
 }
@@ -1,6 +1,11 @@
+pub mod annotations;
+pub mod audio_cues;
 pub mod command_queue;
 pub mod director;
 pub mod economy;
+pub mod gameplay;
 pub mod migrations;
+pub mod rng_stream;
 pub mod save;
+pub mod telemetry;
 pub mod trading;
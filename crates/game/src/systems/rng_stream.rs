@@ -0,0 +1,266 @@
+//! Named deterministic RNG streams and the registry that keeps them apart.
+//!
+//! [`RngStream::new`] hashes a domain string and key parts into the seed, so
+//! two systems only share draws if they ask for the same named stream. The
+//! `econ_day` and `mission_seed` shims keep the seeds older call sites
+//! derived by hand, which existing records and econ curves depend on.
+//!
+//! In debug and deterministic builds an active [`RngRegistry`] remembers
+//! which call site asked for each stream and panics when a different call
+//! site asks for the same one.
+
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::panic::Location;
+use std::sync::{Arc, Mutex, RwLock};
+
+use bevy::prelude::Resource;
+
+use crate::systems::director::rng::hash_mission_name;
+use crate::systems::economy::{DetRng, EconomyDay, HubId, RouteId};
+
+/// Registry domain for streams seeded through [`RngStream::econ_day`].
+const ECON_DAY_DOMAIN: &str = "det_rng_v1";
+/// Registry domain for seeds derived through [`RngStream::mission_seed`].
+const MISSION_SEED_DOMAIN: &str = "mission_seed_v1";
+
+static INSTALLED_REGISTRY: RwLock<Option<RngRegistry>> = RwLock::new(None);
+
+thread_local! {
+    static SCOPED_REGISTRY: RefCell<Option<RngRegistry>> = const { RefCell::new(None) };
+}
+
+/// Constructors for every deterministic stream the game draws from.
+pub struct RngStream;
+
+impl RngStream {
+    /// The stream named `domain` and `key_parts` for this world and econ
+    /// version. Distinct domains never share a seed.
+    #[allow(clippy::new_ret_no_self)] // `RngStream` only names the constructors.
+    #[track_caller]
+    pub fn new(
+        world_seed: u64,
+        econ_version: u32,
+        domain: &'static str,
+        key_parts: &[u64],
+    ) -> DetRng {
+        record(domain, world_seed, econ_version, key_parts);
+        let mut hasher = blake3::Hasher::new();
+        hasher.update(b"rng_stream_v1");
+        hasher.update(&world_seed.to_le_bytes());
+        hasher.update(&econ_version.to_le_bytes());
+        hasher.update(&(domain.len() as u64).to_le_bytes());
+        hasher.update(domain.as_bytes());
+        for part in key_parts {
+            hasher.update(&part.to_le_bytes());
+        }
+        DetRng::from_hash(hasher.finalize())
+    }
+
+    /// An economy day stream seeded exactly as `DetRng::from_seed` (with a
+    /// hub) or `DetRng::from_seed_global` (without) used to.
+    #[track_caller]
+    pub fn econ_day(
+        world_seed: u64,
+        econ_version: u32,
+        hub: Option<HubId>,
+        day: EconomyDay,
+        tag: u32,
+    ) -> DetRng {
+        let hub_part = hub.map_or(u64::MAX, |hub| u64::from(hub.0));
+        record(
+            ECON_DAY_DOMAIN,
+            world_seed,
+            econ_version,
+            &[hub_part, u64::from(day.0), u64::from(tag)],
+        );
+        DetRng::from_legacy_parts(world_seed, econ_version, hub, day, tag)
+    }
+
+    /// The director's per-leg seed for `name`, derived exactly as the old
+    /// `mission_seed(world_seed, link_id, day, hash_mission_name(name))`.
+    #[track_caller]
+    pub fn mission_seed(world_seed: u64, link_id: RouteId, day: u32, name: &str) -> u64 {
+        let mission_id = hash_mission_name(name);
+        record(
+            MISSION_SEED_DOMAIN,
+            world_seed,
+            0,
+            &[u64::from(link_id.0), u64::from(day), mission_id],
+        );
+        #[allow(deprecated)]
+        crate::systems::director::rng::mission_seed(world_seed, link_id, day, mission_id)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+struct StreamKey {
+    domain: &'static str,
+    parts: Vec<u64>,
+}
+
+/// Every stream requested during a run, with the call site that first asked
+/// for it. Clones share the same record.
+#[derive(Resource, Debug, Clone, Default)]
+pub struct RngRegistry {
+    streams: Arc<Mutex<BTreeMap<StreamKey, &'static Location<'static>>>>,
+}
+
+impl RngRegistry {
+    /// Makes this registry the active one for every thread without a scoped
+    /// registry, replacing any earlier install.
+    pub fn install(&self) {
+        *INSTALLED_REGISTRY
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner()) = Some(self.clone());
+    }
+
+    /// The registry [`install`](Self::install) last made active, if any.
+    pub fn installed() -> Option<Self> {
+        INSTALLED_REGISTRY
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .clone()
+    }
+
+    /// Makes this registry the active one on the current thread until the
+    /// guard drops.
+    pub fn scope(&self) -> RngRegistryScope {
+        let previous = SCOPED_REGISTRY.with(|scoped| scoped.replace(Some(self.clone())));
+        RngRegistryScope { previous }
+    }
+
+    /// Number of distinct streams recorded.
+    pub fn len(&self) -> usize {
+        self.lock().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Notes that `owner` asked for the stream `domain`/`parts`.
+    ///
+    /// # Panics
+    /// When a different call site already asked for the same stream.
+    pub fn record(&self, domain: &'static str, parts: &[u64], owner: &'static Location<'static>) {
+        let key = StreamKey {
+            domain,
+            parts: parts.to_vec(),
+        };
+        let first = *self.lock().entry(key).or_insert(owner);
+        if first != owner {
+            panic!(
+                "rng stream {domain} {parts:?} requested at {owner} was already taken at {first}"
+            );
+        }
+    }
+
+    fn active() -> Option<Self> {
+        SCOPED_REGISTRY
+            .with(|scoped| scoped.borrow().clone())
+            .or_else(Self::installed)
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, BTreeMap<StreamKey, &'static Location<'static>>> {
+        self.streams
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+/// Restores the previously scoped registry on drop.
+#[must_use = "the scope ends when the guard is dropped"]
+pub struct RngRegistryScope {
+    previous: Option<RngRegistry>,
+}
+
+impl Drop for RngRegistryScope {
+    fn drop(&mut self) {
+        let previous = self.previous.take();
+        SCOPED_REGISTRY.with(|scoped| *scoped.borrow_mut() = previous);
+    }
+}
+
+#[track_caller]
+fn record(domain: &'static str, world_seed: u64, econ_version: u32, key_parts: &[u64]) {
+    if !cfg!(any(debug_assertions, feature = "deterministic")) {
+        return;
+    }
+    let Some(registry) = RngRegistry::active() else {
+        return;
+    };
+    let mut parts = Vec::with_capacity(key_parts.len() + 2);
+    parts.push(world_seed);
+    parts.push(u64::from(econ_version));
+    parts.extend_from_slice(key_parts);
+    registry.record(domain, &parts, Location::caller());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::systems::director::rng;
+
+    fn draws(mut rng: DetRng) -> Vec<u32> {
+        (0..8).map(|_| rng.u32()).collect()
+    }
+
+    #[test]
+    fn domains_separate_streams_with_equal_keys() {
+        let _scope = RngRegistry::default().scope();
+        let di = RngStream::new(7, 1, "econ.di", &[3, 4]);
+        let basis = RngStream::new(7, 1, "econ.basis", &[3, 4]);
+        let shifted = RngStream::new(7, 1, "econ.di", &[3, 5]);
+        assert_ne!(draws(di.clone()), draws(basis));
+        assert_ne!(draws(di), draws(shifted));
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn shims_reproduce_the_previous_seeds() {
+        let _scope = RngRegistry::default().scope();
+        let hub = HubId(2);
+        let day = EconomyDay(10);
+        assert_eq!(
+            draws(RngStream::econ_day(0xD7E7_2024, 1, Some(hub), day, 1)),
+            draws(DetRng::from_seed(0xD7E7_2024, 1, hub, day, 1))
+        );
+        assert_eq!(
+            draws(RngStream::econ_day(0xD7E7_2024, 1, None, day, 0)),
+            draws(DetRng::from_seed_global(0xD7E7_2024, 1, day, 0))
+        );
+        for name in ["spawn_types", "leg_reward", "rain_flag"] {
+            assert_eq!(
+                RngStream::mission_seed(42, RouteId(3), 7, name),
+                rng::mission_seed(42, RouteId(3), 7, hash_mission_name(name))
+            );
+        }
+    }
+
+    #[test]
+    fn repeated_requests_from_one_call_site_are_allowed() {
+        let registry = RngRegistry::default();
+        let _scope = registry.scope();
+        for _ in 0..3 {
+            RngStream::new(1, 1, "econ.di", &[0]);
+        }
+        assert_eq!(registry.len(), 1);
+    }
+
+    #[test]
+    #[cfg(any(debug_assertions, feature = "deterministic"))]
+    fn duplicate_streams_from_another_call_site_panic() {
+        let registry = RngRegistry::default();
+        let _scope = registry.scope();
+        RngStream::mission_seed(9, RouteId(1), 2, "spawn_types");
+        let err = std::panic::catch_unwind(|| {
+            let _scope = registry.scope();
+            RngStream::mission_seed(9, RouteId(1), 2, "spawn_types");
+        })
+        .expect_err("duplicate stream");
+        let message = err.downcast_ref::<String>().cloned().unwrap_or_default();
+        assert!(message.contains(MISSION_SEED_DOMAIN), "{message}");
+        assert!(message.contains("rng_stream.rs"), "{message}");
+    }
+}
@@ -0,0 +1,169 @@
+use std::path::PathBuf;
+
+use bevy::prelude::*;
+
+use crate::app_state::AppState;
+use crate::systems::director::{DirectorState, LegStatus};
+use crate::systems::economy::EconomyDay;
+
+use super::SaveSlotManager;
+
+pub const DEFAULT_AUTOSAVE_SLOT: &str = "autosave";
+
+/// Why an autosave was written.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AutosaveTrigger {
+    DayRollover,
+    LegCompleted,
+}
+
+#[derive(Debug, Clone, Resource)]
+pub struct AutosaveConfig {
+    pub manager: SaveSlotManager,
+    pub slot: String,
+}
+
+/// Checkpoint bookkeeping; `writes` counts successful autosaves.
+#[derive(Debug, Clone, Default, Resource)]
+pub struct AutosaveTracker {
+    pub last_day: Option<EconomyDay>,
+    pub leg_completed: bool,
+    pub writes: u32,
+    pub last_trigger: Option<AutosaveTrigger>,
+}
+
+/// Writes the `AppState` snapshot to an autosave slot whenever the economy
+/// day advances or the leg completes. Runs in [`Last`] so it never observes a
+/// half-emitted tick and cannot feed back into the command stream.
+pub struct AutosavePlugin {
+    pub dir: PathBuf,
+    pub slot: String,
+}
+
+impl AutosavePlugin {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self {
+            dir: dir.into(),
+            slot: DEFAULT_AUTOSAVE_SLOT.to_string(),
+        }
+    }
+}
+
+impl Plugin for AutosavePlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(AutosaveConfig {
+            manager: SaveSlotManager::new(self.dir.clone()),
+            slot: self.slot.clone(),
+        });
+        app.init_resource::<AutosaveTracker>();
+        app.add_systems(Last, autosave_checkpoints);
+    }
+}
+
+pub fn autosave_checkpoints(
+    config: Res<AutosaveConfig>,
+    mut tracker: ResMut<AutosaveTracker>,
+    app_state: Option<Res<AppState>>,
+    director: Option<Res<DirectorState>>,
+) {
+    let Some(app_state) = app_state else {
+        return;
+    };
+
+    let day = app_state.econ.day;
+    let rolled_over = tracker.last_day.is_some_and(|last| day > last);
+    tracker.last_day = Some(day);
+
+    let completed = director
+        .as_ref()
+        .is_some_and(|state| matches!(state.status, LegStatus::Completed(_)));
+    let newly_completed = completed && !tracker.leg_completed;
+    tracker.leg_completed = completed;
+
+    let trigger = if newly_completed {
+        AutosaveTrigger::LegCompleted
+    } else if rolled_over {
+        AutosaveTrigger::DayRollover
+    } else {
+        return;
+    };
+
+    match config.manager.save_to_slot(&config.slot, &app_state) {
+        Ok(()) => {
+            tracker.writes = tracker.writes.saturating_add(1);
+            tracker.last_trigger = Some(trigger);
+        }
+        Err(err) => log::error!(
+            "autosave ({trigger:?}) to {} failed: {err}",
+            config.manager.root().display()
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::systems::director::Outcome;
+    use crate::systems::economy::MoneyCents;
+    use crate::systems::save::{load, snapshot_from_app_state};
+    use crate::systems::trading::ledger::Wallet;
+
+    fn world_with_autosave(dir: &std::path::Path) -> World {
+        let mut world = World::new();
+        world.insert_resource(AutosaveConfig {
+            manager: SaveSlotManager::new(dir),
+            slot: DEFAULT_AUTOSAVE_SLOT.to_string(),
+        });
+        world.init_resource::<AutosaveTracker>();
+        world.insert_resource(AppState::default());
+        world.insert_resource(DirectorState {
+            status: LegStatus::Running,
+            ..Default::default()
+        });
+        world
+    }
+
+    fn run(world: &mut World) {
+        let mut system = IntoSystem::into_system(autosave_checkpoints);
+        system.initialize(world);
+        let _ = system.run((), world);
+    }
+
+    #[test]
+    fn writes_once_per_trigger() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let mut world = world_with_autosave(dir.path());
+        run(&mut world);
+        assert_eq!(world.resource::<AutosaveTracker>().writes, 0);
+
+        world.resource_mut::<AppState>().wallet = Wallet::new(MoneyCents(4_321));
+        world.resource_mut::<DirectorState>().status = LegStatus::Completed(Outcome::Success);
+        run(&mut world);
+        run(&mut world);
+        let tracker = world.resource::<AutosaveTracker>();
+        assert_eq!(tracker.writes, 1);
+        assert_eq!(tracker.last_trigger, Some(AutosaveTrigger::LegCompleted));
+
+        let path = dir.path().join("autosave.json");
+        let expected = snapshot_from_app_state(world.resource::<AppState>());
+        assert_eq!(load(&path).expect("autosave"), expected);
+
+        world.resource_mut::<AppState>().econ.day = EconomyDay(1);
+        run(&mut world);
+        let tracker = world.resource::<AutosaveTracker>();
+        assert_eq!(tracker.writes, 2);
+        assert_eq!(tracker.last_trigger, Some(AutosaveTrigger::DayRollover));
+    }
+
+    #[test]
+    fn write_failures_do_not_panic() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let blocker = dir.path().join("file");
+        std::fs::write(&blocker, "not a dir").expect("write blocker");
+        let mut world = world_with_autosave(&blocker);
+        run(&mut world);
+        world.resource_mut::<DirectorState>().status = LegStatus::Completed(Outcome::Failure);
+        run(&mut world);
+        assert_eq!(world.resource::<AutosaveTracker>().writes, 0);
+    }
+}
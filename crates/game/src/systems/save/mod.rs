@@ -2,22 +2,48 @@
 
 use std::collections::HashMap;
 use std::fs;
-use std::path::Path;
+use std::io::Write;
+use std::path::{Path, PathBuf};
 
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
 use crate::app_state::AppState;
+use crate::systems::director::history::DEFAULT_HISTORY_LEGS;
+use crate::systems::director::MissionHistory;
+use crate::systems::economy::order::{sorted_basis, sorted_commodities};
 use crate::systems::economy::state::RngCursor;
 use crate::systems::economy::{
     BasisBp, CommodityId, EconState, EconomyDay, HubId, MoneyCents, PendingPlanting, Pp,
+    TradePressure,
 };
-use crate::systems::migrations::{migrate_to_latest, MigrateError};
-use crate::systems::trading::inventory::Cargo;
+use crate::systems::migrations::{detect_schema, migrate_to_latest, MigrateError};
+use crate::systems::trading::inventory::{Cargo, CargoLot};
+use crate::systems::trading::ledger::Wallet;
+use crate::world::closures::RouteClosures;
+use crate::world::index::StaticWorldIndex;
 
+pub mod autosave;
+pub mod slots;
 pub mod v1_1;
+pub mod v1_2;
+pub mod v1_3;
+pub mod v1_4;
+pub mod v1_5;
+pub mod v1_6;
+pub mod v1_7;
+pub mod v1_8;
 
+pub use autosave::{AutosavePlugin, AutosaveTracker, AutosaveTrigger};
+pub use slots::{SaveSlotManager, SaveSlotPlugin, SlotInfo};
 pub use v1_1::{CargoItemSave, CargoSave, SaveV11};
+pub use v1_2::{PriceHistorySave, SaveV12};
+pub use v1_3::{RouteClosureSave, SaveV13};
+pub use v1_4::{SaveV14, TradePressureSave};
+pub use v1_5::{RouteVisitSave, SaveV15};
+pub use v1_6::{MissionHistoryLegSave, MissionResultSave, SaveV16};
+pub use v1_7::{SaveV17, StockOffsetSave};
+pub use v1_8::{CargoLotSave, SaveV18};
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(deny_unknown_fields)]
@@ -68,16 +94,103 @@ pub enum SaveError {
     Serde(#[from] serde_json::Error),
     #[error(transparent)]
     Migrate(#[from] MigrateError),
+    #[error("invalid save slot id {0:?}")]
+    InvalidSlot(String),
 }
 
-pub fn save(path: &Path, snapshot: &SaveV11) -> Result<(), SaveError> {
+/// Schema revision detected on disk by [`verify`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SchemaVersion {
+    V1,
+    V11,
+    V12,
+    V13,
+    V14,
+    V15,
+    V16,
+    V17,
+    V18,
+}
+
+/// Knobs for [`save_with_options`]. `backup_depth` is the number of previous
+/// saves kept next to the primary file (`.bak`, `.bak2`, ...); zero disables
+/// backups entirely.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SaveOptions {
+    pub backup_depth: usize,
+}
+
+impl Default for SaveOptions {
+    fn default() -> Self {
+        Self { backup_depth: 1 }
+    }
+}
+
+/// Result of [`load_report`]: the migrated snapshot plus the backup it was
+/// recovered from when the primary file could not be parsed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LoadReport {
+    pub snapshot: SaveV18,
+    pub recovered_from: Option<PathBuf>,
+}
+
+/// Path of the `index`-th backup for `path`: `.bak` for index zero, then
+/// `.bak2`, `.bak3`, ...
+pub fn backup_path(path: &Path, index: usize) -> PathBuf {
+    let suffix = if index == 0 {
+        "bak".to_string()
+    } else {
+        format!("bak{}", index + 1)
+    };
+    with_extra_extension(path, &suffix)
+}
+
+pub(crate) fn tmp_path(path: &Path) -> PathBuf {
+    with_extra_extension(path, "tmp")
+}
+
+fn with_extra_extension(path: &Path, suffix: &str) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(".");
+    name.push(suffix);
+    PathBuf::from(name)
+}
+
+pub fn save(path: &Path, snapshot: &SaveV18) -> Result<(), SaveError> {
+    save_with_options(path, snapshot, &SaveOptions::default())
+}
+
+/// Writes `snapshot` to `<path>.tmp`, fsyncs it, renames it over `path` and
+/// fsyncs the directory so the rename itself is durable. The file being
+/// replaced is first rotated into the backup chain so a crash at any point
+/// leaves either the old or the new save readable; a `.tmp` left behind by a
+/// crash is never read and is overwritten by the next save.
+pub fn save_with_options(
+    path: &Path,
+    snapshot: &SaveV18,
+    options: &SaveOptions,
+) -> Result<(), SaveError> {
     let mut normalized = snapshot.clone();
     normalized.di.sort_by_key(|entry| entry.commodity.0);
     normalized
         .basis
-        .sort_by(|a, b| (a.hub.0, a.commodity.0).cmp(&(b.hub.0, b.commodity.0)));
+        .sort_by_key(|entry| (entry.hub.0, entry.commodity.0));
     normalized.inventory.sort_by_key(|slot| slot.commodity.0);
     normalized.cargo.items.sort_by_key(|item| item.commodity.0);
+    normalized
+        .price_history
+        .sort_by_key(|entry| (entry.hub.0, entry.commodity.0));
+    normalized.route_closures.sort_by_key(|entry| entry.route.0);
+    normalized
+        .trade_pressure
+        .sort_by_key(|entry| (entry.hub.0, entry.commodity.0));
+    normalized.visited_links.sort_by_key(|entry| entry.route.0);
+    for entry in &mut normalized.price_history {
+        entry.truncate_to_bound();
+    }
+    for entry in &mut normalized.trade_pressure {
+        entry.clamp_to_bound();
+    }
     let mut json = serde_json::to_string_pretty(&normalized)?;
     if !json.ends_with('\n') {
         json.push('\n');
@@ -87,16 +200,137 @@ pub fn save(path: &Path, snapshot: &SaveV11) -> Result<(), SaveError> {
             fs::create_dir_all(parent)?;
         }
     }
-    fs::write(path, json)?;
+
+    let tmp = tmp_path(path);
+    {
+        let mut file = fs::File::create(&tmp)?;
+        file.write_all(json.as_bytes())?;
+        file.sync_all()?;
+    }
+    if options.backup_depth > 0 && path.exists() {
+        rotate_backups(path, options.backup_depth)?;
+    }
+    fs::rename(&tmp, path)?;
+    sync_parent_dir(path)?;
     Ok(())
 }
 
-pub fn load(path: &Path) -> Result<SaveV11, SaveError> {
+/// Flushes the directory entries of `path`'s parent, committing renames.
+#[cfg(unix)]
+fn sync_parent_dir(path: &Path) -> Result<(), SaveError> {
+    let parent = match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    };
+    fs::File::open(parent)?.sync_all()?;
+    Ok(())
+}
+
+/// Directories cannot be opened for syncing here; the rename is as durable as
+/// the platform makes it.
+#[cfg(not(unix))]
+fn sync_parent_dir(_path: &Path) -> Result<(), SaveError> {
+    Ok(())
+}
+
+fn rotate_backups(path: &Path, depth: usize) -> Result<(), SaveError> {
+    for index in (1..depth).rev() {
+        let older = backup_path(path, index - 1);
+        if older.exists() {
+            fs::rename(&older, backup_path(path, index))?;
+        }
+    }
+    // Copy rather than rename so the primary file never disappears.
+    fs::copy(path, backup_path(path, 0))?;
+    Ok(())
+}
+
+pub fn load(path: &Path) -> Result<SaveV18, SaveError> {
+    let report = load_report(path)?;
+    if let Some(backup) = &report.recovered_from {
+        log::warn!(
+            "save {} was unreadable; recovered from {}",
+            path.display(),
+            backup.display()
+        );
+    }
+    Ok(report.snapshot)
+}
+
+/// Loads `path`, falling back to the backup chain when the primary file fails
+/// to parse. If no backup parses either, the primary's error is returned.
+pub fn load_report(path: &Path) -> Result<LoadReport, SaveError> {
+    let primary_err = match load_file(path) {
+        Ok(snapshot) => {
+            return Ok(LoadReport {
+                snapshot,
+                recovered_from: None,
+            })
+        }
+        Err(err @ SaveError::Io(_)) => return Err(err),
+        Err(err) => err,
+    };
+
+    let mut index = 0;
+    loop {
+        let backup = backup_path(path, index);
+        if !backup.exists() {
+            return Err(primary_err);
+        }
+        if let Ok(snapshot) = load_file(&backup) {
+            return Ok(LoadReport {
+                snapshot,
+                recovered_from: Some(backup),
+            });
+        }
+        index += 1;
+    }
+}
+
+fn load_file(path: &Path) -> Result<SaveV18, SaveError> {
     let raw = fs::read_to_string(path)?;
     let value: serde_json::Value = serde_json::from_str(&raw)?;
     Ok(migrate_to_latest(value)?)
 }
 
+/// Parses the save at `path` against its own schema without migrating it or
+/// building an `AppState`.
+pub fn verify(path: &Path) -> Result<SchemaVersion, SaveError> {
+    let raw = fs::read_to_string(path)?;
+    let value: serde_json::Value = serde_json::from_str(&raw)?;
+    let version = detect_schema(&value);
+    match version {
+        SchemaVersion::V1 => {
+            serde_json::from_value::<SaveV1>(value)?;
+        }
+        SchemaVersion::V11 => {
+            serde_json::from_value::<SaveV11>(value)?;
+        }
+        SchemaVersion::V12 => {
+            serde_json::from_value::<SaveV12>(value)?;
+        }
+        SchemaVersion::V13 => {
+            serde_json::from_value::<SaveV13>(value)?;
+        }
+        SchemaVersion::V14 => {
+            serde_json::from_value::<SaveV14>(value)?;
+        }
+        SchemaVersion::V15 => {
+            serde_json::from_value::<SaveV15>(value)?;
+        }
+        SchemaVersion::V16 => {
+            serde_json::from_value::<SaveV16>(value)?;
+        }
+        SchemaVersion::V17 => {
+            serde_json::from_value::<SaveV17>(value)?;
+        }
+        SchemaVersion::V18 => {
+            serde_json::from_value::<SaveV18>(value)?;
+        }
+    }
+    Ok(version)
+}
+
 pub fn save_app_state(path: &Path, state: &AppState) -> Result<(), SaveError> {
     let snapshot = snapshot_from_app_state(state);
     save(path, &snapshot)
@@ -107,31 +341,82 @@ pub fn load_app_state(path: &Path) -> Result<AppState, SaveError> {
     Ok(app_state_from_snapshot(snapshot))
 }
 
-pub fn snapshot_from_app_state(state: &AppState) -> SaveV11 {
-    let mut di: Vec<CommoditySave> = state
-        .econ
-        .di_bp
-        .iter()
-        .map(|(commodity, value)| CommoditySave {
-            commodity: *commodity,
-            value: *value,
+pub fn snapshot_from_app_state(state: &AppState) -> SaveV18 {
+    let econ = &state.econ;
+    let di: Vec<CommoditySave> = sorted_commodities(&econ.di_bp)
+        .into_iter()
+        .map(|commodity| CommoditySave {
+            commodity,
+            value: econ.di_bp[&commodity],
+        })
+        .collect();
+
+    let basis: Vec<BasisSave> = sorted_basis(&econ.basis_bp)
+        .into_iter()
+        .map(|(hub, commodity)| BasisSave {
+            hub,
+            commodity,
+            value: econ.basis_bp[&(hub, commodity)],
         })
         .collect();
-    di.sort_by_key(|entry| entry.commodity.0);
 
-    let mut basis: Vec<BasisSave> = state
-        .econ
-        .basis_bp
+    let mut price_history: Vec<PriceHistorySave> = sorted_basis(&econ.price_history)
+        .into_iter()
+        .map(|(hub, commodity)| PriceHistorySave {
+            hub,
+            commodity,
+            samples: econ.price_history[&(hub, commodity)].clone(),
+        })
+        .collect();
+    for entry in &mut price_history {
+        entry.truncate_to_bound();
+    }
+
+    let route_closures = state
+        .route_closures
+        .entries()
+        .map(|(route, until_day)| RouteClosureSave { route, until_day })
+        .collect();
+
+    let mut trade_pressure: Vec<TradePressureSave> = sorted_basis(&econ.trade_pressure)
+        .into_iter()
+        .map(|(hub, commodity)| {
+            let pressure = econ.trade_pressure[&(hub, commodity)];
+            TradePressureSave {
+                hub,
+                commodity,
+                units: pressure.units,
+                priced: pressure.priced,
+            }
+        })
+        .collect();
+    for entry in &mut trade_pressure {
+        entry.clamp_to_bound();
+    }
+
+    let mut stock_offsets: Vec<StockOffsetSave> = sorted_basis(&econ.stock_offset)
+        .into_iter()
+        .map(|(hub, commodity)| StockOffsetSave {
+            hub,
+            commodity,
+            units: econ.stock_offset[&(hub, commodity)],
+        })
+        .collect();
+    for entry in &mut stock_offsets {
+        entry.clamp_to_bound();
+    }
+
+    let visited_links = state
+        .visited_links
         .iter()
-        .map(|((hub, commodity), value)| BasisSave {
-            hub: *hub,
-            commodity: *commodity,
-            value: *value,
+        .filter(|(_, count)| **count > 0)
+        .map(|(route, count)| RouteVisitSave {
+            route: *route,
+            count: *count,
         })
         .collect();
-    basis.sort_by(|a, b| (a.hub.0, a.commodity.0).cmp(&(b.hub.0, b.commodity.0)));
 
-    SaveV11 {
+    SaveV18 {
         econ_version: state.econ_version,
         world_seed: state.world_seed,
         day: state.econ.day,
@@ -143,14 +428,25 @@ pub fn snapshot_from_app_state(state: &AppState) -> SaveV11 {
         rot: state.econ.rot_u16,
         debt_cents: state.econ.debt_cents,
         inventory: state.inventory.clone(),
-        wallet_cents: state.wallet,
+        wallet_cents: state.wallet.balance(),
         cargo: cargo_to_save(&state.cargo),
         pending_planting: state.econ.pending_planting.clone(),
         rng_cursors: state.rng_cursors.clone(),
+        price_history,
+        route_closures,
+        trade_pressure,
+        visited_links,
+        mission_history: state
+            .mission_history
+            .legs()
+            .map(MissionHistoryLegSave::from)
+            .collect(),
+        stock_offsets,
+        cargo_lots: cargo_lots_to_save(&state.cargo),
     }
 }
 
-pub fn app_state_from_snapshot(snapshot: SaveV11) -> AppState {
+pub fn app_state_from_snapshot(snapshot: SaveV18) -> AppState {
     let di_bp = snapshot
         .di
         .iter()
@@ -161,7 +457,51 @@ pub fn app_state_from_snapshot(snapshot: SaveV11) -> AppState {
         .iter()
         .map(|entry| ((entry.hub, entry.commodity), entry.value))
         .collect();
+    let price_history = snapshot
+        .price_history
+        .iter()
+        .map(|entry| {
+            let mut entry = entry.clone();
+            entry.truncate_to_bound();
+            ((entry.hub, entry.commodity), entry.samples)
+        })
+        .collect();
+    let trade_pressure = snapshot
+        .trade_pressure
+        .iter()
+        .filter_map(|entry| {
+            let mut entry = entry.clone();
+            entry.clamp_to_bound();
+            let pressure = TradePressure {
+                units: entry.units,
+                priced: entry.priced,
+            };
+            (pressure != TradePressure::default())
+                .then_some(((entry.hub, entry.commodity), pressure))
+        })
+        .collect();
+
+    let stock_offset = snapshot
+        .stock_offsets
+        .iter()
+        .filter_map(|entry| {
+            let mut entry = entry.clone();
+            entry.clamp_to_bound();
+            (entry.units != 0).then_some(((entry.hub, entry.commodity), entry.units))
+        })
+        .collect();
+
+    let visited_links = snapshot
+        .visited_links
+        .iter()
+        .filter(|entry| entry.count > 0)
+        .map(|entry| (entry.route, entry.count))
+        .collect();
 
+    let mut route_closures = RouteClosures::default();
+    for entry in &snapshot.route_closures {
+        route_closures.close(entry.route, entry.until_day);
+    }
     let econ = EconState {
         day: snapshot.day,
         di_bp,
@@ -171,6 +511,10 @@ pub fn app_state_from_snapshot(snapshot: SaveV11) -> AppState {
         rot_u16: snapshot.rot,
         pending_planting: snapshot.pending_planting.clone(),
         debt_cents: snapshot.debt_cents,
+        price_history,
+        trade_pressure,
+        stock_offset,
+        closed_routes: route_closures.closed_counts_by_hub::<StaticWorldIndex>(snapshot.day),
         ..Default::default()
     };
 
@@ -180,9 +524,15 @@ pub fn app_state_from_snapshot(snapshot: SaveV11) -> AppState {
         econ,
         last_hub: snapshot.last_hub,
         inventory: snapshot.inventory,
-        cargo: cargo_from_save(snapshot.cargo),
+        cargo: cargo_from_save(snapshot.cargo, snapshot.cargo_lots),
         rng_cursors: snapshot.rng_cursors,
-        wallet: snapshot.wallet_cents,
+        wallet: Wallet::new(snapshot.wallet_cents),
+        route_closures,
+        visited_links,
+        mission_history: MissionHistory::from_legs(
+            DEFAULT_HISTORY_LEGS.max(snapshot.mission_history.len()),
+            snapshot.mission_history.into_iter().map(Vec::from),
+        ),
     }
 }
 
@@ -190,6 +540,7 @@ fn cargo_to_save(cargo: &Cargo) -> CargoSave {
     let mut items: Vec<CargoItemSave> = cargo
         .items
         .iter()
+        .filter(|(_, units)| **units > 0)
         .map(|(commodity, units)| CargoItemSave {
             commodity: *commodity,
             units: *units,
@@ -203,14 +554,46 @@ fn cargo_to_save(cargo: &Cargo) -> CargoSave {
     }
 }
 
-fn cargo_from_save(save: CargoSave) -> Cargo {
-    let mut items: HashMap<CommodityId, u32> = HashMap::new();
-    for item in save.items {
-        items.insert(item.commodity, item.units);
+fn cargo_lots_to_save(cargo: &Cargo) -> Vec<CargoLotSave> {
+    let mut lots: Vec<CargoLotSave> = cargo
+        .lots
+        .iter()
+        .filter(|(commodity, _)| cargo.units(**commodity) > 0)
+        .flat_map(|(commodity, lots)| {
+            lots.iter().map(|lot| CargoLotSave {
+                commodity: *commodity,
+                loaded_day: lot.loaded_day,
+                units: lot.units,
+            })
+        })
+        .filter(|lot| lot.units > 0)
+        .collect();
+    lots.sort_by_key(|lot| (lot.commodity.0, lot.loaded_day.0));
+    lots
+}
+
+fn cargo_from_save(save: CargoSave, saved_lots: Vec<CargoLotSave>) -> Cargo {
+    let items: HashMap<CommodityId, u32> = save
+        .items
+        .into_iter()
+        .map(|item| (item.commodity, item.units))
+        .collect();
+    let mut lots: HashMap<CommodityId, Vec<CargoLot>> = HashMap::new();
+    for lot in saved_lots {
+        lots.entry(lot.commodity).or_default().push(CargoLot {
+            loaded_day: lot.loaded_day,
+            units: lot.units,
+        });
     }
-    Cargo {
+    for entries in lots.values_mut() {
+        entries.sort_by_key(|lot| lot.loaded_day.0);
+    }
+    let mut cargo = Cargo {
         capacity_mass_kg: save.capacity_mass_kg,
         capacity_volume_l: save.capacity_volume_l,
         items,
-    }
+        lots,
+    };
+    cargo.compact();
+    cargo
 }
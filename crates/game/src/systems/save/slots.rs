@@ -0,0 +1,183 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use bevy::prelude::*;
+use serde_json::Value;
+
+use crate::app_state::AppState;
+use crate::systems::economy::{EconomyDay, HubId, MoneyCents};
+use crate::systems::migrations::detect_schema;
+use crate::world::index::{StaticWorldIndex, WorldIndex};
+
+use super::{backup_path, load_app_state, save_app_state, tmp_path, SaveError, SchemaVersion};
+
+const SLOT_EXTENSION: &str = "json";
+const MAX_SLOT_LEN: usize = 32;
+const DEFAULT_SLOT_ROOT: &str = "saves";
+
+/// Display metadata for one save slot, read from stable top-level fields only.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SlotInfo {
+    pub slot: String,
+    pub schema: SchemaVersion,
+    pub day: EconomyDay,
+    pub wallet_cents: MoneyCents,
+    pub last_hub: HubId,
+    pub last_hub_name: String,
+    pub modified: Option<SystemTime>,
+}
+
+/// Manages `<root>/<slot>.json` save files and their backups.
+#[derive(Debug, Clone, Resource)]
+pub struct SaveSlotManager {
+    root: PathBuf,
+}
+
+impl SaveSlotManager {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Metadata for every valid slot under the root, sorted by slot id.
+    /// Files that cannot be read are skipped with a warning.
+    pub fn list(&self) -> Result<Vec<SlotInfo>, SaveError> {
+        let entries = match fs::read_dir(&self.root) {
+            Ok(entries) => entries,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(err) => return Err(err.into()),
+        };
+
+        let mut slots = Vec::new();
+        for entry in entries {
+            let path = entry?.path();
+            if path.extension().and_then(|ext| ext.to_str()) != Some(SLOT_EXTENSION) {
+                continue;
+            }
+            let Some(slot) = path.file_stem().and_then(|stem| stem.to_str()) else {
+                continue;
+            };
+            if validate_slot_id(slot).is_err() {
+                continue;
+            }
+            match read_slot_info(slot, &path) {
+                Ok(info) => slots.push(info),
+                Err(err) => log::warn!("skipping save slot {}: {err}", path.display()),
+            }
+        }
+        slots.sort_by(|a, b| a.slot.cmp(&b.slot));
+        Ok(slots)
+    }
+
+    pub fn slot_path(&self, slot: &str) -> Result<PathBuf, SaveError> {
+        validate_slot_id(slot)?;
+        Ok(self.root.join(format!("{slot}.{SLOT_EXTENSION}")))
+    }
+
+    pub fn save_to_slot(&self, slot: &str, state: &AppState) -> Result<(), SaveError> {
+        save_app_state(&self.slot_path(slot)?, state)
+    }
+
+    pub fn load_slot(&self, slot: &str) -> Result<AppState, SaveError> {
+        load_app_state(&self.slot_path(slot)?)
+    }
+
+    /// Removes the slot's primary file together with any backups and a
+    /// leftover temp file.
+    pub fn delete_slot(&self, slot: &str) -> Result<(), SaveError> {
+        let path = self.slot_path(slot)?;
+        remove_if_exists(&path)?;
+        remove_if_exists(&tmp_path(&path))?;
+        let mut index = 0;
+        loop {
+            let backup = backup_path(&path, index);
+            if !backup.exists() {
+                break;
+            }
+            fs::remove_file(backup)?;
+            index += 1;
+        }
+        Ok(())
+    }
+}
+
+impl Default for SaveSlotManager {
+    fn default() -> Self {
+        Self::new(DEFAULT_SLOT_ROOT)
+    }
+}
+
+/// Accepts slot ids matching `[a-z0-9_-]{1,32}` so ids can never escape the
+/// slot root.
+pub fn validate_slot_id(slot: &str) -> Result<(), SaveError> {
+    let valid_chars = slot
+        .bytes()
+        .all(|b| b.is_ascii_lowercase() || b.is_ascii_digit() || b == b'_' || b == b'-');
+    if slot.is_empty() || slot.len() > MAX_SLOT_LEN || !valid_chars {
+        return Err(SaveError::InvalidSlot(slot.to_string()));
+    }
+    Ok(())
+}
+
+/// The hub's name from the world asset, or `Hub <id>` for hubs it does not
+/// name.
+pub fn hub_display_name(hub: HubId) -> String {
+    StaticWorldIndex::hub_name(hub).map_or_else(|| format!("Hub {}", hub.0), str::to_owned)
+}
+
+fn read_slot_info(slot: &str, path: &Path) -> Result<SlotInfo, SaveError> {
+    let raw = fs::read_to_string(path)?;
+    let value: Value = serde_json::from_str(&raw)?;
+    let day = field::<EconomyDay>(&value, "day")?.unwrap_or(EconomyDay(0));
+    let wallet_cents = field::<MoneyCents>(&value, "wallet_cents")?.unwrap_or(MoneyCents::ZERO);
+    let last_hub = field::<HubId>(&value, "last_hub")?.unwrap_or_default();
+    let modified = fs::metadata(path).and_then(|meta| meta.modified()).ok();
+    Ok(SlotInfo {
+        slot: slot.to_string(),
+        schema: detect_schema(&value),
+        day,
+        wallet_cents,
+        last_hub,
+        last_hub_name: hub_display_name(last_hub),
+        modified,
+    })
+}
+
+fn field<T: serde::de::DeserializeOwned>(value: &Value, key: &str) -> Result<Option<T>, SaveError> {
+    value
+        .get(key)
+        .map(|raw| serde_json::from_value(raw.clone()))
+        .transpose()
+        .map_err(SaveError::from)
+}
+
+fn remove_if_exists(path: &Path) -> Result<(), SaveError> {
+    match fs::remove_file(path) {
+        Ok(()) => Ok(()),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(()),
+        Err(err) => Err(err.into()),
+    }
+}
+
+/// Exposes a [`SaveSlotManager`] rooted at `root` as a resource.
+pub struct SaveSlotPlugin {
+    pub root: PathBuf,
+}
+
+impl Default for SaveSlotPlugin {
+    fn default() -> Self {
+        Self {
+            root: PathBuf::from(DEFAULT_SLOT_ROOT),
+        }
+    }
+}
+
+impl Plugin for SaveSlotPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(SaveSlotManager::new(self.root.clone()));
+    }
+}
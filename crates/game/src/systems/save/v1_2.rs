@@ -0,0 +1,76 @@
+use serde::{Deserialize, Serialize};
+
+use crate::systems::economy::state::{RngCursor, PRICE_HISTORY_DAYS};
+use crate::systems::economy::{
+    BasisBp, CommodityId, EconomyDay, HubId, MoneyCents, PendingPlanting, Pp,
+};
+
+use super::{BasisSave, CargoSave, CommoditySave, InventorySlot, SaveV11};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SaveV12 {
+    pub econ_version: u32,
+    pub world_seed: u64,
+    pub day: EconomyDay,
+    #[serde(default)]
+    pub last_hub: HubId,
+    pub di: Vec<CommoditySave>,
+    #[serde(default)]
+    pub di_overlay_bp: i32,
+    pub basis: Vec<BasisSave>,
+    pub pp: Pp,
+    pub rot: u16,
+    #[serde(default)]
+    pub debt_cents: MoneyCents,
+    pub inventory: Vec<InventorySlot>,
+    #[serde(default)]
+    pub wallet_cents: MoneyCents,
+    pub cargo: CargoSave,
+    pub pending_planting: Vec<PendingPlanting>,
+    pub rng_cursors: Vec<RngCursor>,
+    pub price_history: Vec<PriceHistorySave>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PriceHistorySave {
+    pub hub: HubId,
+    pub commodity: CommodityId,
+    pub samples: Vec<BasisBp>,
+}
+
+impl PriceHistorySave {
+    /// Drops all but the newest [`PRICE_HISTORY_DAYS`] samples.
+    pub fn truncate_to_bound(&mut self) {
+        let excess = self.samples.len().saturating_sub(PRICE_HISTORY_DAYS);
+        self.samples.drain(..excess);
+    }
+}
+
+impl From<SaveV11> for SaveV12 {
+    fn from(v11: SaveV11) -> Self {
+        SaveV12 {
+            econ_version: v11.econ_version,
+            world_seed: v11.world_seed,
+            day: v11.day,
+            last_hub: v11.last_hub,
+            di: v11.di,
+            di_overlay_bp: v11.di_overlay_bp,
+            basis: v11.basis,
+            pp: v11.pp,
+            rot: v11.rot,
+            debt_cents: v11.debt_cents,
+            inventory: v11.inventory,
+            wallet_cents: v11.wallet_cents,
+            cargo: v11.cargo,
+            pending_planting: v11.pending_planting,
+            rng_cursors: v11.rng_cursors,
+            price_history: Vec::new(),
+        }
+    }
+}
+
+pub fn migrate_v11_to_v12(v11: SaveV11) -> SaveV12 {
+    SaveV12::from(v11)
+}
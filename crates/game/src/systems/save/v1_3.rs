@@ -0,0 +1,67 @@
+use serde::{Deserialize, Serialize};
+
+use crate::systems::economy::state::RngCursor;
+use crate::systems::economy::{EconomyDay, HubId, MoneyCents, PendingPlanting, Pp, RouteId};
+
+use super::{BasisSave, CargoSave, CommoditySave, InventorySlot, PriceHistorySave, SaveV12};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SaveV13 {
+    pub econ_version: u32,
+    pub world_seed: u64,
+    pub day: EconomyDay,
+    #[serde(default)]
+    pub last_hub: HubId,
+    pub di: Vec<CommoditySave>,
+    #[serde(default)]
+    pub di_overlay_bp: i32,
+    pub basis: Vec<BasisSave>,
+    pub pp: Pp,
+    pub rot: u16,
+    #[serde(default)]
+    pub debt_cents: MoneyCents,
+    pub inventory: Vec<InventorySlot>,
+    #[serde(default)]
+    pub wallet_cents: MoneyCents,
+    pub cargo: CargoSave,
+    pub pending_planting: Vec<PendingPlanting>,
+    pub rng_cursors: Vec<RngCursor>,
+    pub price_history: Vec<PriceHistorySave>,
+    pub route_closures: Vec<RouteClosureSave>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RouteClosureSave {
+    pub route: RouteId,
+    pub until_day: EconomyDay,
+}
+
+impl From<SaveV12> for SaveV13 {
+    fn from(v12: SaveV12) -> Self {
+        SaveV13 {
+            econ_version: v12.econ_version,
+            world_seed: v12.world_seed,
+            day: v12.day,
+            last_hub: v12.last_hub,
+            di: v12.di,
+            di_overlay_bp: v12.di_overlay_bp,
+            basis: v12.basis,
+            pp: v12.pp,
+            rot: v12.rot,
+            debt_cents: v12.debt_cents,
+            inventory: v12.inventory,
+            wallet_cents: v12.wallet_cents,
+            cargo: v12.cargo,
+            pending_planting: v12.pending_planting,
+            rng_cursors: v12.rng_cursors,
+            price_history: v12.price_history,
+            route_closures: Vec::new(),
+        }
+    }
+}
+
+pub fn migrate_v12_to_v13(v12: SaveV12) -> SaveV13 {
+    SaveV13::from(v12)
+}
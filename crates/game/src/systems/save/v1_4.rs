@@ -0,0 +1,87 @@
+use serde::{Deserialize, Serialize};
+
+use crate::systems::economy::state::{RngCursor, TRADE_PRESSURE_LIMIT};
+use crate::systems::economy::{CommodityId, EconomyDay, HubId, MoneyCents, PendingPlanting, Pp};
+
+use super::{
+    BasisSave, CargoSave, CommoditySave, InventorySlot, PriceHistorySave, RouteClosureSave, SaveV13,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SaveV14 {
+    pub econ_version: u32,
+    pub world_seed: u64,
+    pub day: EconomyDay,
+    #[serde(default)]
+    pub last_hub: HubId,
+    pub di: Vec<CommoditySave>,
+    #[serde(default)]
+    pub di_overlay_bp: i32,
+    pub basis: Vec<BasisSave>,
+    pub pp: Pp,
+    pub rot: u16,
+    #[serde(default)]
+    pub debt_cents: MoneyCents,
+    pub inventory: Vec<InventorySlot>,
+    #[serde(default)]
+    pub wallet_cents: MoneyCents,
+    pub cargo: CargoSave,
+    pub pending_planting: Vec<PendingPlanting>,
+    pub rng_cursors: Vec<RngCursor>,
+    pub price_history: Vec<PriceHistorySave>,
+    pub route_closures: Vec<RouteClosureSave>,
+    pub trade_pressure: Vec<TradePressureSave>,
+}
+
+/// Demand pressure at a hub, see
+/// [`TradePressure`](crate::systems::economy::TradePressure).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TradePressureSave {
+    pub hub: HubId,
+    pub commodity: CommodityId,
+    pub units: i32,
+    pub priced: i32,
+}
+
+impl TradePressureSave {
+    /// Clamps both levels to the range the economy keeps in memory.
+    pub fn clamp_to_bound(&mut self) {
+        self.units = self
+            .units
+            .clamp(-TRADE_PRESSURE_LIMIT, TRADE_PRESSURE_LIMIT);
+        self.priced = self
+            .priced
+            .clamp(-TRADE_PRESSURE_LIMIT, TRADE_PRESSURE_LIMIT);
+    }
+}
+
+impl From<SaveV13> for SaveV14 {
+    fn from(v13: SaveV13) -> Self {
+        SaveV14 {
+            econ_version: v13.econ_version,
+            world_seed: v13.world_seed,
+            day: v13.day,
+            last_hub: v13.last_hub,
+            di: v13.di,
+            di_overlay_bp: v13.di_overlay_bp,
+            basis: v13.basis,
+            pp: v13.pp,
+            rot: v13.rot,
+            debt_cents: v13.debt_cents,
+            inventory: v13.inventory,
+            wallet_cents: v13.wallet_cents,
+            cargo: v13.cargo,
+            pending_planting: v13.pending_planting,
+            rng_cursors: v13.rng_cursors,
+            price_history: v13.price_history,
+            route_closures: v13.route_closures,
+            trade_pressure: Vec::new(),
+        }
+    }
+}
+
+pub fn migrate_v13_to_v14(v13: SaveV13) -> SaveV14 {
+    SaveV14::from(v13)
+}
@@ -0,0 +1,75 @@
+use serde::{Deserialize, Serialize};
+
+use crate::systems::economy::state::RngCursor;
+use crate::systems::economy::{EconomyDay, HubId, MoneyCents, PendingPlanting, Pp, RouteId};
+
+use super::{
+    BasisSave, CargoSave, CommoditySave, InventorySlot, PriceHistorySave, RouteClosureSave,
+    SaveV14, TradePressureSave,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SaveV15 {
+    pub econ_version: u32,
+    pub world_seed: u64,
+    pub day: EconomyDay,
+    #[serde(default)]
+    pub last_hub: HubId,
+    pub di: Vec<CommoditySave>,
+    #[serde(default)]
+    pub di_overlay_bp: i32,
+    pub basis: Vec<BasisSave>,
+    pub pp: Pp,
+    pub rot: u16,
+    #[serde(default)]
+    pub debt_cents: MoneyCents,
+    pub inventory: Vec<InventorySlot>,
+    #[serde(default)]
+    pub wallet_cents: MoneyCents,
+    pub cargo: CargoSave,
+    pub pending_planting: Vec<PendingPlanting>,
+    pub rng_cursors: Vec<RngCursor>,
+    pub price_history: Vec<PriceHistorySave>,
+    pub route_closures: Vec<RouteClosureSave>,
+    pub trade_pressure: Vec<TradePressureSave>,
+    pub visited_links: Vec<RouteVisitSave>,
+}
+
+/// Completed legs on one route.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RouteVisitSave {
+    pub route: RouteId,
+    pub count: u32,
+}
+
+impl From<SaveV14> for SaveV15 {
+    fn from(v14: SaveV14) -> Self {
+        SaveV15 {
+            econ_version: v14.econ_version,
+            world_seed: v14.world_seed,
+            day: v14.day,
+            last_hub: v14.last_hub,
+            di: v14.di,
+            di_overlay_bp: v14.di_overlay_bp,
+            basis: v14.basis,
+            pp: v14.pp,
+            rot: v14.rot,
+            debt_cents: v14.debt_cents,
+            inventory: v14.inventory,
+            wallet_cents: v14.wallet_cents,
+            cargo: v14.cargo,
+            pending_planting: v14.pending_planting,
+            rng_cursors: v14.rng_cursors,
+            price_history: v14.price_history,
+            route_closures: v14.route_closures,
+            trade_pressure: v14.trade_pressure,
+            visited_links: Vec::new(),
+        }
+    }
+}
+
+pub fn migrate_v14_to_v15(v14: SaveV14) -> SaveV15 {
+    SaveV15::from(v14)
+}
@@ -0,0 +1,111 @@
+use serde::{Deserialize, Serialize};
+
+use crate::systems::director::{MissionHistoryEntry, Outcome};
+use crate::systems::economy::state::RngCursor;
+use crate::systems::economy::{EconomyDay, HubId, MoneyCents, PendingPlanting, Pp};
+
+use super::{
+    BasisSave, CargoSave, CommoditySave, InventorySlot, PriceHistorySave, RouteClosureSave,
+    RouteVisitSave, SaveV15, TradePressureSave,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SaveV16 {
+    pub econ_version: u32,
+    pub world_seed: u64,
+    pub day: EconomyDay,
+    #[serde(default)]
+    pub last_hub: HubId,
+    pub di: Vec<CommoditySave>,
+    #[serde(default)]
+    pub di_overlay_bp: i32,
+    pub basis: Vec<BasisSave>,
+    pub pp: Pp,
+    pub rot: u16,
+    #[serde(default)]
+    pub debt_cents: MoneyCents,
+    pub inventory: Vec<InventorySlot>,
+    #[serde(default)]
+    pub wallet_cents: MoneyCents,
+    pub cargo: CargoSave,
+    pub pending_planting: Vec<PendingPlanting>,
+    pub rng_cursors: Vec<RngCursor>,
+    pub price_history: Vec<PriceHistorySave>,
+    pub route_closures: Vec<RouteClosureSave>,
+    pub trade_pressure: Vec<TradePressureSave>,
+    pub visited_links: Vec<RouteVisitSave>,
+    /// Most recent legs last.
+    pub mission_history: Vec<MissionHistoryLegSave>,
+}
+
+/// The resolved missions of one completed leg, in catalog order.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct MissionHistoryLegSave {
+    pub missions: Vec<MissionResultSave>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct MissionResultSave {
+    pub name: String,
+    pub outcome: Outcome,
+}
+
+impl From<&[MissionHistoryEntry]> for MissionHistoryLegSave {
+    fn from(leg: &[MissionHistoryEntry]) -> Self {
+        Self {
+            missions: leg
+                .iter()
+                .map(|entry| MissionResultSave {
+                    name: entry.name.clone(),
+                    outcome: entry.outcome,
+                })
+                .collect(),
+        }
+    }
+}
+
+impl From<MissionHistoryLegSave> for Vec<MissionHistoryEntry> {
+    fn from(leg: MissionHistoryLegSave) -> Self {
+        leg.missions
+            .into_iter()
+            .map(|mission| MissionHistoryEntry {
+                name: mission.name,
+                outcome: mission.outcome,
+            })
+            .collect()
+    }
+}
+
+impl From<SaveV15> for SaveV16 {
+    fn from(v15: SaveV15) -> Self {
+        SaveV16 {
+            econ_version: v15.econ_version,
+            world_seed: v15.world_seed,
+            day: v15.day,
+            last_hub: v15.last_hub,
+            di: v15.di,
+            di_overlay_bp: v15.di_overlay_bp,
+            basis: v15.basis,
+            pp: v15.pp,
+            rot: v15.rot,
+            debt_cents: v15.debt_cents,
+            inventory: v15.inventory,
+            wallet_cents: v15.wallet_cents,
+            cargo: v15.cargo,
+            pending_planting: v15.pending_planting,
+            rng_cursors: v15.rng_cursors,
+            price_history: v15.price_history,
+            route_closures: v15.route_closures,
+            trade_pressure: v15.trade_pressure,
+            visited_links: v15.visited_links,
+            mission_history: Vec::new(),
+        }
+    }
+}
+
+pub fn migrate_v15_to_v16(v15: SaveV15) -> SaveV16 {
+    SaveV16::from(v15)
+}
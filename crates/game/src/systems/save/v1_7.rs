@@ -0,0 +1,92 @@
+use serde::{Deserialize, Serialize};
+
+use crate::systems::economy::state::{RngCursor, TRADE_PRESSURE_LIMIT};
+use crate::systems::economy::{CommodityId, EconomyDay, HubId, MoneyCents, PendingPlanting, Pp};
+
+use super::{
+    BasisSave, CargoSave, CommoditySave, InventorySlot, MissionHistoryLegSave, PriceHistorySave,
+    RouteClosureSave, RouteVisitSave, SaveV16, TradePressureSave,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SaveV17 {
+    pub econ_version: u32,
+    pub world_seed: u64,
+    pub day: EconomyDay,
+    #[serde(default)]
+    pub last_hub: HubId,
+    pub di: Vec<CommoditySave>,
+    #[serde(default)]
+    pub di_overlay_bp: i32,
+    pub basis: Vec<BasisSave>,
+    pub pp: Pp,
+    pub rot: u16,
+    #[serde(default)]
+    pub debt_cents: MoneyCents,
+    pub inventory: Vec<InventorySlot>,
+    #[serde(default)]
+    pub wallet_cents: MoneyCents,
+    pub cargo: CargoSave,
+    pub pending_planting: Vec<PendingPlanting>,
+    pub rng_cursors: Vec<RngCursor>,
+    pub price_history: Vec<PriceHistorySave>,
+    pub route_closures: Vec<RouteClosureSave>,
+    pub trade_pressure: Vec<TradePressureSave>,
+    pub visited_links: Vec<RouteVisitSave>,
+    /// Most recent legs last.
+    pub mission_history: Vec<MissionHistoryLegSave>,
+    /// Hub stock away from equilibrium, sorted by hub then commodity.
+    pub stock_offsets: Vec<StockOffsetSave>,
+}
+
+/// Units of a commodity a hub holds above (positive) or below equilibrium,
+/// see [`EconState::stock_offset`](crate::systems::economy::EconState::stock_offset).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct StockOffsetSave {
+    pub hub: HubId,
+    pub commodity: CommodityId,
+    pub units: i32,
+}
+
+impl StockOffsetSave {
+    /// Clamps `units` to the range trades and NPC flow can reach.
+    pub fn clamp_to_bound(&mut self) {
+        self.units = self
+            .units
+            .clamp(-TRADE_PRESSURE_LIMIT, TRADE_PRESSURE_LIMIT);
+    }
+}
+
+impl From<SaveV16> for SaveV17 {
+    fn from(v16: SaveV16) -> Self {
+        SaveV17 {
+            econ_version: v16.econ_version,
+            world_seed: v16.world_seed,
+            day: v16.day,
+            last_hub: v16.last_hub,
+            di: v16.di,
+            di_overlay_bp: v16.di_overlay_bp,
+            basis: v16.basis,
+            pp: v16.pp,
+            rot: v16.rot,
+            debt_cents: v16.debt_cents,
+            inventory: v16.inventory,
+            wallet_cents: v16.wallet_cents,
+            cargo: v16.cargo,
+            pending_planting: v16.pending_planting,
+            rng_cursors: v16.rng_cursors,
+            price_history: v16.price_history,
+            route_closures: v16.route_closures,
+            trade_pressure: v16.trade_pressure,
+            visited_links: v16.visited_links,
+            mission_history: v16.mission_history,
+            stock_offsets: Vec::new(),
+        }
+    }
+}
+
+pub fn migrate_v16_to_v17(v16: SaveV16) -> SaveV17 {
+    SaveV17::from(v16)
+}
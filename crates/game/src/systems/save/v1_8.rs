@@ -0,0 +1,86 @@
+use serde::{Deserialize, Serialize};
+
+use crate::systems::economy::state::RngCursor;
+use crate::systems::economy::{CommodityId, EconomyDay, HubId, MoneyCents, PendingPlanting, Pp};
+
+use super::{
+    BasisSave, CargoSave, CommoditySave, InventorySlot, MissionHistoryLegSave, PriceHistorySave,
+    RouteClosureSave, RouteVisitSave, SaveV17, StockOffsetSave, TradePressureSave,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SaveV18 {
+    pub econ_version: u32,
+    pub world_seed: u64,
+    pub day: EconomyDay,
+    #[serde(default)]
+    pub last_hub: HubId,
+    pub di: Vec<CommoditySave>,
+    #[serde(default)]
+    pub di_overlay_bp: i32,
+    pub basis: Vec<BasisSave>,
+    pub pp: Pp,
+    pub rot: u16,
+    #[serde(default)]
+    pub debt_cents: MoneyCents,
+    pub inventory: Vec<InventorySlot>,
+    #[serde(default)]
+    pub wallet_cents: MoneyCents,
+    pub cargo: CargoSave,
+    pub pending_planting: Vec<PendingPlanting>,
+    pub rng_cursors: Vec<RngCursor>,
+    pub price_history: Vec<PriceHistorySave>,
+    pub route_closures: Vec<RouteClosureSave>,
+    pub trade_pressure: Vec<TradePressureSave>,
+    pub visited_links: Vec<RouteVisitSave>,
+    /// Most recent legs last.
+    pub mission_history: Vec<MissionHistoryLegSave>,
+    /// Hub stock away from equilibrium, sorted by hub then commodity.
+    pub stock_offsets: Vec<StockOffsetSave>,
+    /// Cargo units by the day they were loaded, sorted by commodity then
+    /// day.
+    pub cargo_lots: Vec<CargoLotSave>,
+}
+
+/// Units of one commodity loaded on `loaded_day`, used for shelf-life ageing.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CargoLotSave {
+    pub commodity: CommodityId,
+    pub loaded_day: EconomyDay,
+    pub units: u32,
+}
+
+impl From<SaveV17> for SaveV18 {
+    fn from(v17: SaveV17) -> Self {
+        SaveV18 {
+            econ_version: v17.econ_version,
+            world_seed: v17.world_seed,
+            day: v17.day,
+            last_hub: v17.last_hub,
+            di: v17.di,
+            di_overlay_bp: v17.di_overlay_bp,
+            basis: v17.basis,
+            pp: v17.pp,
+            rot: v17.rot,
+            debt_cents: v17.debt_cents,
+            inventory: v17.inventory,
+            wallet_cents: v17.wallet_cents,
+            cargo: v17.cargo,
+            pending_planting: v17.pending_planting,
+            rng_cursors: v17.rng_cursors,
+            price_history: v17.price_history,
+            route_closures: v17.route_closures,
+            trade_pressure: v17.trade_pressure,
+            visited_links: v17.visited_links,
+            mission_history: v17.mission_history,
+            stock_offsets: v17.stock_offsets,
+            cargo_lots: Vec::new(),
+        }
+    }
+}
+
+pub fn migrate_v17_to_v18(v17: SaveV17) -> SaveV18 {
+    SaveV18::from(v17)
+}
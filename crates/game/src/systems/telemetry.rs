@@ -0,0 +1,238 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+use repro::{Command, CommandKind};
+use serde::Deserialize;
+use thiserror::Error;
+
+static REGISTRY: OnceLock<Result<MeterRegistry, String>> = OnceLock::new();
+
+/// How repeated emissions of a meter key combine.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MeterPolicy {
+    /// Values add up, within a tick and across ticks.
+    Sum,
+    /// The latest value wins.
+    Last,
+    /// At most one emission per tick; the latest value wins across ticks.
+    Unique,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct MetersFile {
+    meters: BTreeMap<String, MeterPolicy>,
+}
+
+#[derive(Debug, Error)]
+pub enum MeterRegistryError {
+    #[error("failed to read meter manifest: {0}")]
+    Read(#[from] std::io::Error),
+    #[error("failed to parse meter manifest: {0}")]
+    Parse(#[from] toml::de::Error),
+}
+
+/// Meter keys known to the game, from `assets/telemetry/meters.toml`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MeterRegistry {
+    policies: BTreeMap<String, MeterPolicy>,
+}
+
+impl MeterRegistry {
+    pub fn load(path: &Path) -> Result<Self, MeterRegistryError> {
+        let raw = fs::read_to_string(path)?;
+        Self::parse(&raw)
+    }
+
+    pub fn parse(raw: &str) -> Result<Self, MeterRegistryError> {
+        let file: MetersFile = toml::from_str(raw)?;
+        Ok(Self {
+            policies: file.meters,
+        })
+    }
+
+    pub fn policy(&self, key: &str) -> Option<MeterPolicy> {
+        self.policies.get(key).copied()
+    }
+
+    pub fn keys(&self) -> impl Iterator<Item = &str> {
+        self.policies.keys().map(String::as_str)
+    }
+
+    /// Checks one tick's drained commands: every meter key must be listed and
+    /// `Unique` keys may appear at most once.
+    pub fn validate_tick(&self, commands: &[Command]) -> Result<(), MeterViolations> {
+        let mut unknown = BTreeSet::new();
+        let mut repeated = BTreeSet::new();
+        let mut seen = BTreeSet::new();
+        let mut tick = 0;
+        for command in commands {
+            let CommandKind::Meter(meter) = &command.kind else {
+                continue;
+            };
+            tick = command.t;
+            match self.policy(&meter.key) {
+                None => {
+                    unknown.insert(meter.key.clone());
+                }
+                Some(MeterPolicy::Unique) => {
+                    if !seen.insert(meter.key.as_str()) {
+                        repeated.insert(meter.key.clone());
+                    }
+                }
+                Some(_) => {}
+            }
+        }
+        if unknown.is_empty() && repeated.is_empty() {
+            Ok(())
+        } else {
+            Err(MeterViolations {
+                tick,
+                unknown: unknown.iter().map(ToString::to_string).collect(),
+                repeated: repeated.iter().map(ToString::to_string).collect(),
+            })
+        }
+    }
+
+    /// Folds meters into one value per key using each key's policy. Keys
+    /// missing from the manifest are skipped; validation reports them.
+    pub fn aggregate(&self, commands: &[Command]) -> BTreeMap<String, i64> {
+        let mut totals = BTreeMap::new();
+        for command in commands {
+            let CommandKind::Meter(meter) = &command.kind else {
+                continue;
+            };
+            let Some(policy) = self.policy(&meter.key) else {
+                continue;
+            };
+            let value = i64::from(meter.value);
+            match policy {
+                MeterPolicy::Sum => {
+                    let total: &mut i64 = totals.entry(meter.key.to_string()).or_default();
+                    *total = total.saturating_add(value);
+                }
+                MeterPolicy::Last | MeterPolicy::Unique => {
+                    totals.insert(meter.key.to_string(), value);
+                }
+            }
+        }
+        totals
+    }
+}
+
+/// Meter keys in one tick that break the manifest.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub struct MeterViolations {
+    pub tick: u32,
+    pub unknown: Vec<String>,
+    pub repeated: Vec<String>,
+}
+
+impl fmt::Display for MeterViolations {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "meter validation failed at tick {}", self.tick)?;
+        if !self.unknown.is_empty() {
+            write!(f, "; unknown keys: {}", self.unknown.join(", "))?;
+        }
+        if !self.repeated.is_empty() {
+            write!(
+                f,
+                "; unique keys emitted more than once: {}",
+                self.repeated.join(", ")
+            )?;
+        }
+        Ok(())
+    }
+}
+
+pub fn meter_manifest_path() -> PathBuf {
+    let default = Path::new("assets/telemetry/meters.toml");
+    if default.exists() {
+        return default.to_path_buf();
+    }
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("../../assets/telemetry/meters.toml")
+}
+
+/// The registry loaded from [`meter_manifest_path`], parsed once.
+pub fn meter_registry() -> anyhow::Result<&'static MeterRegistry> {
+    REGISTRY
+        .get_or_init(|| MeterRegistry::load(&meter_manifest_path()).map_err(|err| err.to_string()))
+        .as_ref()
+        .map_err(|err| anyhow::anyhow!("{err}"))
+}
+
+/// [`MeterRegistry::aggregate`] with the shipped manifest.
+pub fn aggregate_meters(commands: &[Command]) -> BTreeMap<String, i64> {
+    meter_registry()
+        .map(|registry| registry.aggregate(commands))
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MANIFEST: &str = r#"
+        [meters]
+        pp_delta = "sum"
+        danger_score = "last"
+        route_closed = "unique"
+    "#;
+
+    #[test]
+    fn unknown_and_repeated_unique_keys_trip_validation() {
+        let registry = MeterRegistry::parse(MANIFEST).expect("manifest");
+        let commands = vec![
+            Command::meter_at(7, "pp_delta", 2),
+            Command::meter_at(7, "pp_delta", 3),
+            Command::meter_at(7, "danger_scroe", 1),
+            Command::meter_at(7, "route_closed", 4),
+            Command::meter_at(7, "route_closed", 4),
+        ];
+        let err = registry.validate_tick(&commands).unwrap_err();
+        assert_eq!(err.tick, 7);
+        assert_eq!(err.unknown, vec!["danger_scroe".to_string()]);
+        assert_eq!(err.repeated, vec!["route_closed".to_string()]);
+        let message = err.to_string();
+        assert!(message.contains("danger_scroe"), "{message}");
+        assert!(message.contains("route_closed"), "{message}");
+
+        assert!(registry.validate_tick(&commands[..2]).is_ok());
+    }
+
+    #[test]
+    fn sum_and_last_policies_aggregate() {
+        let registry = MeterRegistry::parse(MANIFEST).expect("manifest");
+        let commands = vec![
+            Command::meter_at(1, "pp_delta", 2),
+            Command::meter_at(1, "danger_score", 10),
+            Command::meter_at(1, "pp_delta", -5),
+            Command::spawn_at(1, "bandit", 0, 0, 0),
+            Command::meter_at(2, "danger_score", 12),
+            Command::meter_at(2, "route_closed", 3),
+            Command::meter_at(2, "typo", 99),
+        ];
+        let totals = registry.aggregate(&commands);
+        assert_eq!(totals.get("pp_delta"), Some(&-3));
+        assert_eq!(totals.get("danger_score"), Some(&12));
+        assert_eq!(totals.get("route_closed"), Some(&3));
+        assert!(!totals.contains_key("typo"));
+    }
+
+    #[test]
+    fn manifest_rejects_unknown_policies_and_fields() {
+        assert!(MeterRegistry::parse("[meters]\nx = \"max\"\n").is_err());
+        assert!(MeterRegistry::parse("[meters]\nx = \"sum\"\n[extra]\n").is_err());
+    }
+
+    #[test]
+    fn shipped_manifest_loads() {
+        let registry = meter_registry().expect("shipped manifest");
+        assert_eq!(registry.policy("pp_delta"), Some(MeterPolicy::Sum));
+        assert_eq!(registry.policy("danger_score"), Some(MeterPolicy::Last));
+    }
+}
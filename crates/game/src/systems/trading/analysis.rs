@@ -0,0 +1,79 @@
+use anyhow::ensure;
+
+use crate::systems::economy::{CommodityId, EconState, HubId, MoneyCents, RouteId, Rulepack};
+use crate::systems::trading::engine::max_capacity_units;
+use crate::systems::trading::inventory::Cargo;
+use crate::systems::trading::pricing_vm::price_view_with;
+use crate::systems::trading::types::{CommodityCatalog, TradingConfig};
+use crate::world::index::{route_destination, StaticWorldIndex, WorldIndex};
+
+/// Best single-commodity haul along one route out of the current hub.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RouteProfit {
+    pub route: RouteId,
+    pub destination: HubId,
+    /// `None` when no commodity turns a profit or nothing fits in the hold.
+    pub commodity: Option<CommodityId>,
+    pub units: u32,
+    /// Net cents after buying here and selling at `destination`, fees included.
+    pub margin_cents: MoneyCents,
+}
+
+/// Estimates, per neighbouring route in ascending route order, the most
+/// profitable commodity to buy at `current` and sell at the far hub, filling
+/// the hold's free capacity. Prices use the same quotes and fee math as
+/// `execute_trade`.
+pub fn route_profit_estimates(
+    current: HubId,
+    econ: &EconState,
+    rp: &Rulepack,
+    cargo: &Cargo,
+) -> anyhow::Result<Vec<RouteProfit>> {
+    let config = TradingConfig::global();
+    ensure!(config.fee_bp >= 0, "negative trade fees unsupported");
+    let catalog = CommodityCatalog::global();
+
+    let mut routes = StaticWorldIndex::neighbors(current);
+    routes.sort_by_key(|route| route.0);
+
+    let mut estimates = Vec::with_capacity(routes.len());
+    for route in routes {
+        let Some(destination) = route_destination::<StaticWorldIndex>(route, current) else {
+            continue;
+        };
+        let mut best = RouteProfit {
+            route,
+            destination,
+            commodity: None,
+            units: 0,
+            margin_cents: MoneyCents::ZERO,
+        };
+        for spec in catalog.list() {
+            let units = max_capacity_units(cargo, spec.id)?;
+            if units == 0 {
+                continue;
+            }
+            let buy = i128::from(
+                price_view_with(&catalog, current, spec.id, econ, rp)
+                    .price_cents
+                    .as_i64(),
+            );
+            let sell = i128::from(
+                price_view_with(&catalog, destination, spec.id, econ, rp)
+                    .price_cents
+                    .as_i64(),
+            );
+            let units_i = i128::from(units);
+            let cost = buy * units_i + config.fee_cents(buy * units_i);
+            let proceeds = sell * units_i - config.fee_cents(sell * units_i);
+            let margin = proceeds - cost;
+            if margin > i128::from(best.margin_cents.as_i64()) {
+                best.commodity = Some(spec.id);
+                best.units = units;
+                best.margin_cents = MoneyCents::from_i128_clamped(margin);
+            }
+        }
+        estimates.push(best);
+    }
+    Ok(estimates)
+}
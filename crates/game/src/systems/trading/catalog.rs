@@ -0,0 +1,220 @@
+//! Versioned commodity catalog installation and hot reload.
+//!
+//! A [`CatalogHandle`] owns the installed catalog; readers take an `Arc`
+//! snapshot once and keep using it even if a newer catalog is installed
+//! meanwhile. [`CommodityCatalog::global`] resolves through the handle scoped
+//! to the current thread, or the process-wide one, so tests that need their
+//! own catalog can run in parallel without clobbering each other.
+
+use std::cell::RefCell;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, OnceLock, RwLock};
+use std::time::{Duration, SystemTime};
+
+use anyhow::anyhow;
+use bevy::prelude::*;
+
+use crate::systems::command_queue::CommandQueue;
+use crate::systems::director::config::DirectorCfg;
+use crate::systems::director::DirectorConfigResource;
+use crate::systems::economy::CommodityId;
+
+use super::types::CommodityCatalog;
+
+static PROCESS_CATALOG: OnceLock<CatalogHandle> = OnceLock::new();
+
+thread_local! {
+    static SCOPED_CATALOG: RefCell<Option<CatalogHandle>> = const { RefCell::new(None) };
+}
+
+/// Bumped on every install into a [`CatalogHandle`]; zero means nothing has
+/// been installed yet.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CatalogVersion(pub u64);
+
+#[derive(Debug, Default)]
+struct CatalogSlot {
+    version: CatalogVersion,
+    catalog: Option<Arc<CommodityCatalog>>,
+}
+
+/// Shared, swappable commodity catalog. Clones refer to the same slot.
+#[derive(Resource, Debug, Clone, Default)]
+pub struct CatalogHandle {
+    slot: Arc<RwLock<CatalogSlot>>,
+}
+
+impl CatalogHandle {
+    /// A handle with `catalog` installed as version 1.
+    pub fn new(catalog: CommodityCatalog) -> Self {
+        let handle = Self::default();
+        handle.install(catalog);
+        handle
+    }
+
+    /// The handle [`CommodityCatalog::global`] reads on this thread.
+    pub fn global() -> Self {
+        SCOPED_CATALOG
+            .with(|scoped| scoped.borrow().clone())
+            .unwrap_or_else(|| PROCESS_CATALOG.get_or_init(Self::default).clone())
+    }
+
+    /// Makes this handle the one [`CatalogHandle::global`] returns on the
+    /// current thread until the guard drops.
+    pub fn scope(&self) -> CatalogScope {
+        let previous = SCOPED_CATALOG.with(|scoped| scoped.replace(Some(self.clone())));
+        CatalogScope { previous }
+    }
+
+    /// The installed catalog.
+    ///
+    /// # Panics
+    /// When nothing has been installed yet.
+    pub fn current(&self) -> Arc<CommodityCatalog> {
+        self.snapshot()
+            .1
+            .expect("commodity catalog not installed before trade execution")
+    }
+
+    pub fn version(&self) -> CatalogVersion {
+        self.snapshot().0
+    }
+
+    /// The installed version and catalog, read together.
+    pub fn snapshot(&self) -> (CatalogVersion, Option<Arc<CommodityCatalog>>) {
+        let slot = self
+            .slot
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        (slot.version, slot.catalog.clone())
+    }
+
+    /// Replaces the catalog. Snapshots taken earlier keep the old one.
+    pub fn install(&self, catalog: CommodityCatalog) -> CatalogVersion {
+        let mut slot = self
+            .slot
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        slot.version = CatalogVersion(slot.version.0 + 1);
+        slot.catalog = Some(Arc::new(catalog));
+        slot.version
+    }
+}
+
+/// Restores the previously scoped handle on drop.
+#[must_use = "the scope ends when the guard is dropped"]
+pub struct CatalogScope {
+    previous: Option<CatalogHandle>,
+}
+
+impl Drop for CatalogScope {
+    fn drop(&mut self) {
+        let previous = self.previous.take();
+        SCOPED_CATALOG.with(|scoped| *scoped.borrow_mut() = previous);
+    }
+}
+
+/// Commodities the director config refers to that `catalog` lacks.
+pub fn missing_references(catalog: &CommodityCatalog, cfg: &DirectorCfg) -> Vec<CommodityId> {
+    let mut missing: Vec<CommodityId> = [&cfg.rewards.success, &cfg.rewards.failure]
+        .into_iter()
+        .flat_map(|table| &table.entries)
+        .filter_map(|entry| entry.commodity.map(CommodityId))
+        .filter(|id| catalog.get(*id).is_none())
+        .collect();
+    missing.sort_by_key(|id| id.0);
+    missing.dedup();
+    missing
+}
+
+/// Loads and validates `path`, checks the director config's references against
+/// it and installs it into `handle`, emitting `commodity_catalog_hash`. On
+/// any error the installed catalog is left as it was.
+pub fn reload_commodities(
+    handle: &CatalogHandle,
+    path: &Path,
+    cfg: Option<&DirectorCfg>,
+    queue: &mut CommandQueue,
+) -> anyhow::Result<CatalogVersion> {
+    let catalog = CommodityCatalog::load_from_path(path)?;
+    if let Some(cfg) = cfg {
+        let missing = missing_references(&catalog, cfg);
+        if !missing.is_empty() {
+            let ids: Vec<String> = missing.iter().map(|id| id.0.to_string()).collect();
+            return Err(anyhow!(
+                "{} drops commodities the director config rewards: {}",
+                path.display(),
+                ids.join(", ")
+            ));
+        }
+    }
+    let hash = catalog.content_hash();
+    let version = handle.install(catalog);
+    // The meter carries the low 32 bits of the hash.
+    queue.meter("commodity_catalog_hash", hash as u32 as i32);
+    Ok(version)
+}
+
+/// Polls the commodities file and reloads it when its modification time
+/// changes.
+#[derive(Resource, Debug)]
+pub struct CatalogWatcher {
+    path: PathBuf,
+    modified: Option<SystemTime>,
+    poll: Timer,
+}
+
+impl CatalogWatcher {
+    pub fn new(path: PathBuf) -> Self {
+        let modified = modified_at(&path);
+        Self {
+            path,
+            modified,
+            poll: Timer::new(Duration::from_secs(1), TimerMode::Repeating),
+        }
+    }
+}
+
+fn modified_at(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path)
+        .and_then(|meta| meta.modified())
+        .ok()
+}
+
+fn watch_commodities(
+    time: Res<Time>,
+    mut watcher: ResMut<CatalogWatcher>,
+    handle: Res<CatalogHandle>,
+    director_cfg: Option<Res<DirectorConfigResource>>,
+    mut queue: ResMut<CommandQueue>,
+) {
+    if !watcher.poll.tick(time.delta()).just_finished() {
+        return;
+    }
+    let modified = modified_at(&watcher.path);
+    if modified == watcher.modified {
+        return;
+    }
+    watcher.modified = modified;
+    let cfg = director_cfg.as_ref().map(|cfg| &cfg.0);
+    match reload_commodities(&handle, &watcher.path, cfg, &mut queue) {
+        Ok(version) => info!(
+            "reloaded {} as catalog version {}",
+            watcher.path.display(),
+            version.0
+        ),
+        Err(err) => warn!("keeping the current commodity catalog: {err:#}"),
+    }
+}
+
+/// Reloads the commodity catalog when its file changes on disk.
+pub struct CatalogWatchPlugin {
+    pub path: PathBuf,
+}
+
+impl Plugin for CatalogWatchPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(CatalogWatcher::new(self.path.clone()))
+            .add_systems(Update, watch_commodities);
+    }
+}
@@ -1,9 +1,10 @@
-use anyhow::{anyhow, ensure};
+use anyhow::{anyhow, bail, ensure};
 
-use crate::systems::economy::{CommodityId, EconState, HubId, MoneyCents, Rulepack};
-use crate::systems::trading::inventory::Cargo;
-use crate::systems::trading::pricing_vm::price_view;
-use crate::systems::trading::types::{CommodityCatalog, CommoditySpec, TradingConfig};
+use crate::systems::economy::{CommodityId, EconState, EconomyDay, HubId, MoneyCents, Rulepack};
+use crate::systems::trading::inventory::{Cargo, CargoLot};
+use crate::systems::trading::ledger::{LedgerSource, WalletAccess};
+use crate::systems::trading::pricing_vm::{preview_trade_with, price_view};
+use crate::systems::trading::types::{CommodityCatalog, TradingConfig};
 
 #[derive(Debug, Clone, Copy)]
 pub struct TradeTx {
@@ -27,73 +28,57 @@ pub struct TradeResult {
     pub total_cents: MoneyCents,
 }
 
+/// Applies `tx` to the cargo and wallet and records its demand pressure on
+/// `econ`. The wallet change is journaled as [`LedgerSource::Trade`]. The
+/// whole trade is priced against one catalog snapshot, and the fee is rounded
+/// to the cent per [`TradingConfig::rounding`].
 pub fn execute_trade(
     tx: &TradeTx,
-    econ: &EconState,
+    econ: &mut EconState,
     cargo: &mut Cargo,
-    wallet: &mut MoneyCents,
+    wallet: &mut WalletAccess<'_>,
     rp: &Rulepack,
 ) -> anyhow::Result<TradeResult> {
-    ensure!(tx.units > 0, "trade requires at least one unit");
-
     let catalog = CommodityCatalog::global();
-    let spec = catalog
-        .get(tx.com)
-        .ok_or_else(|| anyhow!("unknown commodity {:?}", tx.com))?;
-    let config = TradingConfig::global();
-    ensure!(config.fee_bp >= 0, "negative trade fees unsupported");
-
-    if matches!(tx.kind, TradeKind::Sell) {
-        let stored = cargo.units(tx.com);
-        ensure!(stored >= tx.units, "insufficient units to sell");
-    }
-
-    if matches!(tx.kind, TradeKind::Buy) {
-        ensure_cargo_capacity(cargo, spec, tx.units, &catalog)?;
+    let preview = preview_trade_with(&catalog, tx, econ, rp, cargo, wallet.balance());
+    if let Some(reason) = preview.limit {
+        bail!(reason);
     }
 
-    let view = price_view(tx.hub, tx.com, econ, rp);
-    let unit_price = view.price_cents;
-    let subtotal_i128 = i128::from(unit_price.as_i64()) * i128::from(tx.units);
-    let subtotal = MoneyCents::from_i128_clamped(subtotal_i128);
-
-    let fee_i128 = subtotal_i128 * i128::from(config.fee_bp) / 10_000;
-    let fee_cents = MoneyCents::from_i128_clamped(fee_i128);
-
-    let result = match tx.kind {
+    let units = i32::try_from(tx.units).unwrap_or(i32::MAX);
+    let signed_units = match tx.kind {
         TradeKind::Buy => {
-            let total_i128 = subtotal_i128 + fee_i128;
-            let total = MoneyCents::from_i128_clamped(total_i128);
-            ensure!(
-                wallet.as_i64() >= total.as_i64(),
-                "insufficient wallet balance"
-            );
-            apply_buy(cargo, tx.com, tx.units)?;
-            *wallet = wallet.saturating_sub(total);
-            TradeResult {
-                unit_price,
-                subtotal,
-                fee_cents,
-                total_cents: total,
-            }
+            apply_buy(cargo, tx.com, tx.units, econ.day)?;
+            units
         }
         TradeKind::Sell => {
-            let net_i128 = subtotal_i128 - fee_i128;
-            let net = MoneyCents::from_i128_clamped(net_i128);
             apply_sell(cargo, tx.com, tx.units);
-            *wallet = wallet.saturating_add(net);
-            TradeResult {
-                unit_price,
-                subtotal,
-                fee_cents,
-                total_cents: MoneyCents::from_i128_clamped(-net_i128),
-            }
+            debug_assert!(
+                cargo.items.values().all(|&held| held > 0),
+                "sell left a zero-unit cargo entry"
+            );
+            -units
         }
     };
+    econ.apply_trade_pressure(tx.hub, tx.com, signed_units);
+    let balance = match tx.kind {
+        TradeKind::Buy => wallet.debit(LedgerSource::Trade, preview.total_cents),
+        TradeKind::Sell => wallet.credit(
+            LedgerSource::Trade,
+            preview.subtotal.saturating_sub(preview.fee),
+        ),
+    };
+    debug_assert_eq!(balance, preview.wallet_after);
+    let result = TradeResult {
+        unit_price: preview.unit_price,
+        subtotal: preview.subtotal,
+        fee_cents: preview.fee,
+        total_cents: preview.total_cents,
+    };
 
     #[cfg(feature = "m3_logs")]
     {
-        if let Err(err) = crate::logs::trading::log_trade(tx, &result, *wallet) {
+        if let Err(err) = crate::logs::trading::log_trade(tx, &result, wallet.balance()) {
             log::warn!("failed to log trade: {err}");
         }
     }
@@ -101,12 +86,122 @@ pub fn execute_trade(
     Ok(result)
 }
 
-fn ensure_cargo_capacity(
+/// Undoes the trade [`execute_trade`] just ran, given the inverse of its
+/// `tx`, the `result` it returned and the commodity's cargo lots from before
+/// it ran. Nothing is re-priced: the wallet moves back by exactly what the
+/// trade moved, so an undone buy refunds its fee and an undone sale charges
+/// none. Cargo is put back lot for lot and the trade's demand pressure is
+/// withdrawn.
+pub fn reverse_trade(
+    inverse: &TradeTx,
+    result: &TradeResult,
+    lots_before: &[CargoLot],
+    econ: &mut EconState,
+    cargo: &mut Cargo,
+    wallet: &mut WalletAccess<'_>,
+) -> anyhow::Result<()> {
+    let held = cargo.units(inverse.com);
+    let units = i32::try_from(inverse.units).unwrap_or(i32::MAX);
+    match inverse.kind {
+        TradeKind::Sell => {
+            ensure!(
+                held >= inverse.units,
+                "cannot undo a buy of {} units of {:?} with {held} held",
+                inverse.units,
+                inverse.com
+            );
+            cargo.restore(inverse.com, held - inverse.units, lots_before);
+            econ.apply_trade_pressure(inverse.hub, inverse.com, -units);
+            wallet.credit(
+                LedgerSource::Trade,
+                result.subtotal.saturating_add(result.fee_cents),
+            );
+        }
+        TradeKind::Buy => {
+            let proceeds = result.subtotal.saturating_sub(result.fee_cents);
+            ensure!(
+                wallet.balance() >= proceeds,
+                "wallet cannot cover undoing a sale of {proceeds:?}"
+            );
+            let before = held
+                .checked_add(inverse.units)
+                .ok_or_else(|| anyhow!("cargo units overflow for commodity {:?}", inverse.com))?;
+            cargo.restore(inverse.com, before, lots_before);
+            econ.apply_trade_pressure(inverse.hub, inverse.com, units);
+            wallet.debit(LedgerSource::Trade, proceeds);
+        }
+    }
+    Ok(())
+}
+
+/// Largest buy quantity of `com` at `hub` that fits both the remaining cargo
+/// capacity and `wallet` once fees are included.
+pub fn max_affordable_units(
+    hub: HubId,
+    com: CommodityId,
+    econ: &EconState,
+    cargo: &Cargo,
+    wallet: MoneyCents,
+    rp: &Rulepack,
+) -> anyhow::Result<u32> {
+    let config = TradingConfig::global();
+    let fee_bp = i128::from(config.fee_bp);
+    ensure!(fee_bp >= 0, "negative trade fees unsupported");
+    let capacity_units = max_capacity_units(cargo, com)?;
+
+    let unit_price = i128::from(price_view(hub, com, econ, rp).price_cents.as_i64());
+    let budget = i128::from(wallet.as_i64()).max(0);
+    let total_for = |units: i128| {
+        let subtotal = unit_price * units;
+        subtotal + config.fee_cents(subtotal)
+    };
+    let wallet_units = if unit_price <= 0 {
+        i128::from(u32::MAX)
+    } else {
+        // The fee rounds to whole cents, so the estimate can be off by a
+        // unit or two in either direction; settle it exactly.
+        let mut units = budget * 10_000 / (unit_price * (10_000 + fee_bp));
+        while total_for(units + 1) <= budget {
+            units += 1;
+        }
+        while units > 0 && total_for(units) > budget {
+            units -= 1;
+        }
+        units
+    };
+
+    let max_units = i128::from(capacity_units)
+        .min(wallet_units)
+        .clamp(0, i128::from(u32::MAX));
+    Ok(max_units as u32)
+}
+
+/// Units of `com` that still fit in the cargo hold's mass and volume limits.
+pub fn max_capacity_units(cargo: &Cargo, com: CommodityId) -> anyhow::Result<u32> {
+    let catalog = CommodityCatalog::global();
+    let spec = catalog
+        .get(com)
+        .ok_or_else(|| anyhow!("unknown commodity {:?}", com))?;
+    let (used_mass, used_volume) = cargo_usage(cargo, &catalog)?;
+    let fit = |capacity: u32, used: u128, per_unit: u16| -> u128 {
+        let free = u128::from(capacity).saturating_sub(used);
+        match per_unit {
+            0 => u128::from(u32::MAX),
+            per_unit => free / u128::from(per_unit),
+        }
+    };
+    let units = fit(cargo.capacity_mass_kg, used_mass, spec.mass_kg).min(fit(
+        cargo.capacity_volume_l,
+        used_volume,
+        spec.volume_l,
+    ));
+    Ok(u32::try_from(units).unwrap_or(u32::MAX))
+}
+
+pub(crate) fn cargo_usage(
     cargo: &Cargo,
-    spec: &CommoditySpec,
-    units: u32,
     catalog: &CommodityCatalog,
-) -> anyhow::Result<()> {
+) -> anyhow::Result<(u128, u128)> {
     let mut total_mass: u128 = 0;
     let mut total_volume: u128 = 0;
     for (id, &held_units) in &cargo.items {
@@ -120,41 +215,20 @@ fn ensure_cargo_capacity(
             .checked_add(u128::from(held_spec.volume_l) * u128::from(held_units))
             .ok_or_else(|| anyhow!("cargo volume overflow"))?;
     }
-
-    let added_mass = u128::from(spec.mass_kg) * u128::from(units);
-    let added_volume = u128::from(spec.volume_l) * u128::from(units);
-
-    let projected_mass = total_mass
-        .checked_add(added_mass)
-        .ok_or_else(|| anyhow!("cargo mass overflow"))?;
-    let projected_volume = total_volume
-        .checked_add(added_volume)
-        .ok_or_else(|| anyhow!("cargo volume overflow"))?;
-
-    ensure!(
-        projected_mass <= u128::from(cargo.capacity_mass_kg),
-        "cargo mass capacity exceeded"
-    );
-    ensure!(
-        projected_volume <= u128::from(cargo.capacity_volume_l),
-        "cargo volume capacity exceeded"
-    );
-    Ok(())
+    Ok((total_mass, total_volume))
 }
 
-fn apply_buy(cargo: &mut Cargo, com: CommodityId, units: u32) -> anyhow::Result<()> {
-    let entry = cargo.items.entry(com).or_insert(0);
-    *entry = entry
-        .checked_add(units)
-        .ok_or_else(|| anyhow!("cargo units overflow for commodity {:?}", com))?;
-    Ok(())
+fn apply_buy(
+    cargo: &mut Cargo,
+    com: CommodityId,
+    units: u32,
+    day: EconomyDay,
+) -> anyhow::Result<()> {
+    cargo
+        .load(com, units, day)
+        .ok_or_else(|| anyhow!("cargo units overflow for commodity {:?}", com))
 }
 
 fn apply_sell(cargo: &mut Cargo, com: CommodityId, units: u32) {
-    if let Some(entry) = cargo.items.get_mut(&com) {
-        *entry -= units;
-        if *entry == 0 {
-            cargo.items.remove(&com);
-        }
-    }
+    cargo.unload(com, units);
 }
@@ -3,21 +3,150 @@ use std::collections::HashMap;
 use bevy::prelude::Resource;
 use serde::{Deserialize, Serialize};
 
-use crate::systems::economy::CommodityId;
+use crate::systems::economy::order::sorted_commodities;
+use crate::systems::economy::{CommodityId, EconomyDay};
+
+use super::types::CommodityCatalog;
 
 #[derive(Debug, Default, Resource, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct Cargo {
     pub capacity_mass_kg: u32,
     pub capacity_volume_l: u32,
     pub items: HashMap<CommodityId, u32>,
+    /// Units per commodity bucketed by the day they were loaded, oldest first.
+    /// Units in `items` without a lot are adopted as loaded on the next
+    /// [`Cargo::age`] call.
+    #[serde(default)]
+    pub lots: HashMap<CommodityId, Vec<CargoLot>>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub struct CargoLot {
+    pub loaded_day: EconomyDay,
+    pub units: u32,
 }
 
 impl Cargo {
     pub fn clear(&mut self) {
         self.items.clear();
+        self.lots.clear();
     }
 
     pub fn units(&self, com: CommodityId) -> u32 {
         self.items.get(&com).copied().unwrap_or_default()
     }
+
+    /// Stores `units` of `com` loaded on `day`.
+    pub fn load(&mut self, com: CommodityId, units: u32, day: EconomyDay) -> Option<()> {
+        let entry = self.items.entry(com).or_insert(0);
+        *entry = entry.checked_add(units)?;
+        let lots = self.lots.entry(com).or_default();
+        match lots.last_mut() {
+            Some(last) if last.loaded_day == day => last.units += units,
+            _ => lots.push(CargoLot {
+                loaded_day: day,
+                units,
+            }),
+        }
+        Some(())
+    }
+
+    /// Removes up to `units` of `com`, oldest lots first.
+    pub fn unload(&mut self, com: CommodityId, units: u32) {
+        let Some(entry) = self.items.get_mut(&com) else {
+            return;
+        };
+        *entry = entry.saturating_sub(units);
+        if *entry == 0 {
+            self.items.remove(&com);
+        }
+        self.sync_lots();
+    }
+
+    /// Drops commodities held at zero units, along with their lots.
+    /// Idempotent; saves and trades never leave zero entries behind.
+    pub fn compact(&mut self) {
+        self.items.retain(|_, units| *units > 0);
+        self.sync_lots();
+    }
+
+    /// Puts `com` back to exactly `units` held in `lots`, as captured before
+    /// a trade that is being undone.
+    pub fn restore(&mut self, com: CommodityId, units: u32, lots: &[CargoLot]) {
+        if units == 0 {
+            self.items.remove(&com);
+        } else {
+            self.items.insert(com, units);
+        }
+        if lots.is_empty() {
+            self.lots.remove(&com);
+        } else {
+            self.lots.insert(com, lots.to_vec());
+        }
+    }
+
+    /// Trims lots so each commodity's buckets never hold more than `items`,
+    /// dropping the oldest units first.
+    pub fn sync_lots(&mut self) {
+        let items = &self.items;
+        self.lots.retain(|com, lots| {
+            let held = items.get(com).copied().unwrap_or_default();
+            let mut excess = lots
+                .iter()
+                .map(|lot| u64::from(lot.units))
+                .sum::<u64>()
+                .saturating_sub(u64::from(held));
+            for lot in lots.iter_mut() {
+                if excess == 0 {
+                    break;
+                }
+                let take = u32::try_from(excess.min(u64::from(lot.units))).unwrap_or(lot.units);
+                lot.units -= take;
+                excess -= u64::from(take);
+            }
+            lots.retain(|lot| lot.units > 0);
+            !lots.is_empty()
+        });
+    }
+
+    /// Removes every unit held longer than its commodity's shelf life as of
+    /// `today` and returns the losses sorted by commodity id. Commodities
+    /// without a shelf life (or missing from `catalog`) never spoil.
+    pub fn age(
+        &mut self,
+        today: EconomyDay,
+        catalog: &CommodityCatalog,
+    ) -> Vec<(CommodityId, u32)> {
+        self.sync_lots();
+        let mut spoiled = Vec::new();
+        for com in sorted_commodities(&self.items) {
+            let held = self.units(com);
+            let lots = self.lots.entry(com).or_default();
+            let tracked: u32 = lots.iter().map(|lot| lot.units).sum();
+            if tracked < held {
+                lots.push(CargoLot {
+                    loaded_day: today,
+                    units: held - tracked,
+                });
+                lots.sort_by_key(|lot| lot.loaded_day.0);
+            }
+
+            let Some(shelf_life) = catalog.get(com).and_then(|spec| spec.shelf_life_days) else {
+                continue;
+            };
+            let mut lost = 0u32;
+            lots.retain(|lot| {
+                let fresh = today.0.saturating_sub(lot.loaded_day.0) <= shelf_life;
+                if !fresh {
+                    lost += lot.units;
+                }
+                fresh
+            });
+            if lost > 0 {
+                spoiled.push((com, lost));
+                self.unload(com, lost);
+            }
+        }
+        spoiled
+    }
 }
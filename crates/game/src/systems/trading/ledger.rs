@@ -0,0 +1,187 @@
+//! Player wallet and the journal of every change made to it.
+//!
+//! The balance lives behind [`Wallet`], which has no public mutators; changes
+//! go through a [`WalletAccess`] that also appends a [`LedgerEntry`] to the
+//! session's [`LedgerJournal`]. In debug builds [`reconcile_ledger`] checks
+//! each fixed step that the balance moved by exactly what was journaled.
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::app_state::AppState;
+use crate::systems::command_queue::CommandQueue;
+use crate::systems::economy::MoneyCents;
+
+/// The player's balance. Read it with [`Wallet::balance`]; change it through
+/// a [`WalletAccess`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Wallet(MoneyCents);
+
+impl Wallet {
+    /// A wallet opened with `balance`, as when a save is loaded.
+    pub fn new(balance: MoneyCents) -> Self {
+        Self(balance)
+    }
+
+    pub fn balance(&self) -> MoneyCents {
+        self.0
+    }
+
+    /// Overwrites the balance without journaling it, to exercise
+    /// [`reconcile_ledger`].
+    #[cfg(test)]
+    pub(crate) fn bypass_journal(&mut self, balance: MoneyCents) {
+        self.0 = balance;
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LedgerSource {
+    Trade,
+    Toll,
+    Insurance,
+    ContractReward,
+    Repayment,
+    Interest,
+    Other,
+}
+
+/// One wallet change. `amount` is what the balance actually moved by, so a
+/// saturated credit records less than was asked for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LedgerEntry {
+    pub tick: u32,
+    pub source: LedgerSource,
+    pub amount: MoneyCents,
+}
+
+/// Every wallet change this session. Nothing here is saved.
+#[derive(Resource, Debug, Clone, Default)]
+pub struct LedgerJournal {
+    entries: Vec<LedgerEntry>,
+    /// Balance at the last reconciliation; `None` until the first one.
+    reconciled: Option<MoneyCents>,
+    /// Net of the entries made since the last reconciliation.
+    unreconciled: i64,
+}
+
+impl LedgerJournal {
+    pub fn entries(&self) -> &[LedgerEntry] {
+        &self.entries
+    }
+
+    /// Net of every entry.
+    pub fn net(&self) -> i64 {
+        self.entries
+            .iter()
+            .map(|entry| entry.amount.as_i64())
+            .fold(0, i64::saturating_add)
+    }
+
+    /// Net of the entries from `source`.
+    pub fn net_for(&self, source: LedgerSource) -> i64 {
+        self.entries
+            .iter()
+            .filter(|entry| entry.source == source)
+            .map(|entry| entry.amount.as_i64())
+            .fold(0, i64::saturating_add)
+    }
+
+    fn record(&mut self, tick: u32, source: LedgerSource, amount: MoneyCents) {
+        self.unreconciled = self.unreconciled.saturating_add(amount.as_i64());
+        self.entries.push(LedgerEntry {
+            tick,
+            source,
+            amount,
+        });
+    }
+
+    /// How far `balance` has moved since the last reconciliation beyond what
+    /// was journaled, then takes `balance` as the new baseline. The first
+    /// call only sets the baseline.
+    pub fn reconcile(&mut self, balance: MoneyCents) -> i64 {
+        let drift = match self.reconciled {
+            Some(previous) => balance
+                .as_i64()
+                .saturating_sub(previous.as_i64())
+                .saturating_sub(self.unreconciled),
+            None => 0,
+        };
+        self.reconciled = Some(balance);
+        self.unreconciled = 0;
+        drift
+    }
+
+    /// Forgets the baseline after the wallet was replaced wholesale, as when
+    /// a checkpoint is restored.
+    pub fn rebase(&mut self) {
+        self.reconciled = None;
+        self.unreconciled = 0;
+    }
+}
+
+/// Mutable access to a [`Wallet`] that journals every change at `tick`.
+pub struct WalletAccess<'a> {
+    wallet: &'a mut Wallet,
+    journal: &'a mut LedgerJournal,
+    tick: u32,
+}
+
+impl<'a> WalletAccess<'a> {
+    pub fn new(wallet: &'a mut Wallet, journal: &'a mut LedgerJournal, tick: u32) -> Self {
+        Self {
+            wallet,
+            journal,
+            tick,
+        }
+    }
+
+    pub fn balance(&self) -> MoneyCents {
+        self.wallet.balance()
+    }
+
+    /// Adds `amount`, saturating, and returns the new balance.
+    pub fn credit(&mut self, source: LedgerSource, amount: MoneyCents) -> MoneyCents {
+        let after = self.wallet.0.saturating_add(amount);
+        self.settle(source, after)
+    }
+
+    /// Subtracts `amount`, saturating, and returns the new balance.
+    pub fn debit(&mut self, source: LedgerSource, amount: MoneyCents) -> MoneyCents {
+        let after = self.wallet.0.saturating_sub(amount);
+        self.settle(source, after)
+    }
+
+    fn settle(&mut self, source: LedgerSource, after: MoneyCents) -> MoneyCents {
+        let moved = after.as_i64().saturating_sub(self.wallet.0.as_i64());
+        if moved != 0 {
+            self.wallet.0 = after;
+            self.journal.record(self.tick, source, MoneyCents(moved));
+        }
+        after
+    }
+}
+
+/// Compares the wallet's movement this step with the journal. Clean steps
+/// emit nothing, so debug and release records stay identical; drift emits
+/// `ledger_drift_cents` and fails deterministic builds outright.
+pub fn reconcile_ledger(
+    app_state: Option<Res<AppState>>,
+    mut journal: ResMut<LedgerJournal>,
+    mut queue: ResMut<CommandQueue>,
+) {
+    let Some(app_state) = app_state else {
+        return;
+    };
+    let drift = journal.reconcile(app_state.wallet.balance());
+    if drift == 0 {
+        return;
+    }
+    queue.meter(
+        "ledger_drift_cents",
+        drift.clamp(i64::from(i32::MIN), i64::from(i32::MAX)) as i32,
+    );
+    #[cfg(feature = "deterministic")]
+    panic!("wallet drifted {drift} cents from its ledger journal");
+}
@@ -1,24 +1,61 @@
+pub mod analysis;
+pub mod catalog;
 pub mod engine;
 pub mod inventory;
+pub mod ledger;
 pub mod pricing_vm;
+pub mod routes;
 pub mod types;
 
 #[cfg(test)]
 #[path = "tests/accounting_identity.rs"]
 mod accounting_identity;
 #[cfg(test)]
+#[path = "tests/analysis.rs"]
+mod analysis_tests;
+#[cfg(test)]
 #[path = "tests/capacity_enforcement.rs"]
 mod capacity_enforcement;
 #[cfg(test)]
+#[path = "tests/cargo_compaction.rs"]
+mod cargo_compaction;
+#[cfg(test)]
+#[path = "tests/catalog_reload.rs"]
+mod catalog_reload;
+#[cfg(test)]
+#[path = "tests/catalog_validation.rs"]
+mod catalog_validation;
+#[cfg(test)]
+#[path = "tests/fee_rounding.rs"]
+mod fee_rounding;
+#[cfg(test)]
+#[path = "tests/ledger.rs"]
+mod ledger_tests;
+#[cfg(test)]
 #[path = "tests/price_constancy.rs"]
 mod price_constancy;
 #[cfg(test)]
 #[path = "tests/pricing_vm_rounding.rs"]
 mod pricing_vm_rounding;
+#[cfg(test)]
+#[path = "tests/reverse_trade.rs"]
+mod reverse_trade_tests;
+
+#[cfg(test)]
+#[path = "tests/routes.rs"]
+mod routes_tests;
+#[cfg(test)]
+#[path = "tests/spoilage.rs"]
+mod spoilage;
+#[cfg(test)]
+#[path = "tests/trade_preview.rs"]
+mod trade_preview;
 
 use anyhow::anyhow;
 use bevy::prelude::*;
 
+use self::catalog::CatalogHandle;
+use self::ledger::LedgerJournal;
 use self::types::{CommodityCatalog, TradingConfig};
 
 pub struct TradingPlugin;
@@ -26,34 +63,46 @@ pub struct TradingPlugin;
 impl Plugin for TradingPlugin {
     fn build(&self, app: &mut App) {
         let commodities = load_default_commodities().expect("failed to load default commodities");
-        CommodityCatalog::install_global(commodities.clone());
-        app.insert_resource(commodities);
+        let handle = CatalogHandle::global();
+        handle.install(commodities);
+        app.insert_resource(handle);
 
         let config = load_default_trading_config().expect("failed to load trading config");
         TradingConfig::install_global(config.clone());
         app.insert_resource(config);
+
+        app.init_resource::<LedgerJournal>();
+        #[cfg(debug_assertions)]
+        app.add_systems(
+            FixedUpdate,
+            ledger::reconcile_ledger.after(crate::scheduling::sets::DETTEROT_Cleanup),
+        );
     }
 }
 
-fn load_default_commodities() -> anyhow::Result<CommodityCatalog> {
-    let workspace_path = std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
-        .join("..")
-        .join("..")
-        .join("assets/trading/commodities.toml");
-    let search_paths = [
-        std::path::Path::new("assets/trading/commodities.toml"),
-        workspace_path.as_path(),
-    ];
-    for path in search_paths {
-        if path.exists() {
-            return CommodityCatalog::load_from_path(path);
-        }
+pub(crate) fn load_default_commodities() -> anyhow::Result<CommodityCatalog> {
+    let path = default_commodities_path();
+    if path.exists() {
+        return CommodityCatalog::load_from_path(&path);
     }
-    let last = workspace_path.display();
+    let last = path.display();
     Err(anyhow!("missing commodities asset at {last}"))
 }
 
-fn load_default_trading_config() -> anyhow::Result<TradingConfig> {
+/// The commodity catalog the plugin loads: `assets/` under the working
+/// directory when present, otherwise the workspace copy.
+pub(crate) fn default_commodities_path() -> std::path::PathBuf {
+    let local = std::path::Path::new("assets/trading/commodities.toml");
+    if local.exists() {
+        return local.to_path_buf();
+    }
+    std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("..")
+        .join("..")
+        .join("assets/trading/commodities.toml")
+}
+
+pub(crate) fn load_default_trading_config() -> anyhow::Result<TradingConfig> {
     let workspace_path = std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
         .join("..")
         .join("..")
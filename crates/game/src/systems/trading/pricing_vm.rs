@@ -1,9 +1,16 @@
+use std::fmt;
+
 use crate::systems::economy::{
-    basis::BasisDrivers, compute_price, BasisBp, CommodityId, EconState, HubId, MoneyCents,
-    Rulepack, Weather,
+    basis::BasisDrivers, compute_price, BasisBp, ClampSide, CommodityId, EconState, HubId,
+    MoneyCents, Rulepack, Weather,
 };
+use crate::systems::trading::catalog::CatalogHandle;
+use crate::systems::trading::engine::{cargo_usage, TradeKind, TradeTx};
+use crate::systems::trading::inventory::Cargo;
+use crate::systems::trading::types::{CommodityCatalog, TradingConfig};
 
-/// Base price in cents used for quote construction.
+/// Base price in cents for commodities without their own `base_price_cents`,
+/// or when no catalog is installed.
 pub const DEFAULT_QUOTE_BASE: MoneyCents = MoneyCents(12_345);
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -19,10 +26,38 @@ pub struct PriceView {
     pub di_bp: BasisBp,
     pub basis_bp: BasisBp,
     pub price_cents: MoneyCents,
+    /// Set when the multiplier clamp moved `price_cents`.
+    pub clamped: Option<ClampSide>,
     pub drivers: TradingDrivers,
 }
 
+/// Quote for `com` at `hub`, from its base price in the installed catalog.
 pub fn price_view(hub: HubId, com: CommodityId, econ: &EconState, rp: &Rulepack) -> PriceView {
+    let base = CatalogHandle::global()
+        .snapshot()
+        .1
+        .map_or(DEFAULT_QUOTE_BASE, |catalog| catalog.base_price(com));
+    quote(base, hub, com, econ, rp)
+}
+
+/// [`price_view`] against a catalog snapshot the caller already holds.
+pub fn price_view_with(
+    catalog: &CommodityCatalog,
+    hub: HubId,
+    com: CommodityId,
+    econ: &EconState,
+    rp: &Rulepack,
+) -> PriceView {
+    quote(catalog.base_price(com), hub, com, econ, rp)
+}
+
+fn quote(
+    base: MoneyCents,
+    hub: HubId,
+    com: CommodityId,
+    econ: &EconState,
+    rp: &Rulepack,
+) -> PriceView {
     let di_bp = econ.di_bp.get(&com).copied().unwrap_or(BasisBp(0));
     let basis_bp = econ
         .basis_bp
@@ -30,7 +65,13 @@ pub fn price_view(hub: HubId, com: CommodityId, econ: &EconState, rp: &Rulepack)
         .copied()
         .unwrap_or(BasisBp(0));
 
-    let price_cents = compute_price(DEFAULT_QUOTE_BASE, di_bp, basis_bp, &rp.pricing);
+    let computation = compute_price(
+        base,
+        di_bp,
+        basis_bp,
+        &rp.pricing,
+        rp.pricing.override_for(com),
+    );
     let drivers_snapshot = econ
         .basis_drivers
         .get(&hub)
@@ -51,7 +92,207 @@ pub fn price_view(hub: HubId, com: CommodityId, econ: &EconState, rp: &Rulepack)
     PriceView {
         di_bp,
         basis_bp,
-        price_cents,
+        price_cents: computation.price,
+        clamped: computation.clamped,
         drivers,
     }
 }
+
+/// Why a previewed trade would be rejected by
+/// [`execute_trade`](crate::systems::trading::engine::execute_trade).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LimitReason {
+    NoUnits,
+    UnknownCommodity(CommodityId),
+    NegativeFee,
+    InsufficientUnits { held: u32 },
+    MassCapacity,
+    VolumeCapacity,
+    UnitOverflow,
+    InsufficientFunds,
+}
+
+impl fmt::Display for LimitReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LimitReason::NoUnits => write!(f, "trade requires at least one unit"),
+            LimitReason::UnknownCommodity(com) => write!(f, "unknown commodity {com:?}"),
+            LimitReason::NegativeFee => write!(f, "negative trade fees unsupported"),
+            LimitReason::InsufficientUnits { .. } => write!(f, "insufficient units to sell"),
+            LimitReason::MassCapacity => write!(f, "cargo mass capacity exceeded"),
+            LimitReason::VolumeCapacity => write!(f, "cargo volume capacity exceeded"),
+            LimitReason::UnitOverflow => write!(f, "cargo units overflow"),
+            LimitReason::InsufficientFunds => write!(f, "insufficient wallet balance"),
+        }
+    }
+}
+
+/// Hold usage once a previewed trade settles.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct CargoPreview {
+    /// Units of the traded commodity held afterwards.
+    pub units_after: u32,
+    pub mass_kg_after: u128,
+    pub volume_l_after: u128,
+    pub capacity_mass_kg: u32,
+    pub capacity_volume_l: u32,
+}
+
+/// Projected outcome of a trade. When `limit` is set the trade would be
+/// rejected and `wallet_after`/`cargo_after` describe the untouched state;
+/// the price fields are still filled whenever the commodity is known.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TradePreview {
+    pub unit_price: MoneyCents,
+    pub subtotal: MoneyCents,
+    pub fee: MoneyCents,
+    /// Signed like [`TradeResult::total_cents`](crate::systems::trading::engine::TradeResult):
+    /// positive for money paid, negative for money received.
+    pub total_cents: MoneyCents,
+    pub wallet_after: MoneyCents,
+    pub cargo_after: CargoPreview,
+    pub limit: Option<LimitReason>,
+}
+
+/// Prices `tx` against the current state without mutating anything. The
+/// numbers and limit checks are the ones `execute_trade` applies.
+pub fn preview_trade(
+    tx: &TradeTx,
+    econ: &EconState,
+    rp: &Rulepack,
+    cargo: &Cargo,
+    wallet: MoneyCents,
+) -> TradePreview {
+    preview_trade_with(&CommodityCatalog::global(), tx, econ, rp, cargo, wallet)
+}
+
+/// [`preview_trade`] against a catalog snapshot the caller already holds.
+pub fn preview_trade_with(
+    catalog: &CommodityCatalog,
+    tx: &TradeTx,
+    econ: &EconState,
+    rp: &Rulepack,
+    cargo: &Cargo,
+    wallet: MoneyCents,
+) -> TradePreview {
+    let held = cargo.units(tx.com);
+    let usage = cargo_usage(cargo, catalog);
+    let (mass_before, volume_before) = match usage {
+        Ok(usage) => usage,
+        Err(_) => (0, 0),
+    };
+    let mut preview = TradePreview {
+        unit_price: MoneyCents::ZERO,
+        subtotal: MoneyCents::ZERO,
+        fee: MoneyCents::ZERO,
+        total_cents: MoneyCents::ZERO,
+        wallet_after: wallet,
+        cargo_after: CargoPreview {
+            units_after: held,
+            mass_kg_after: mass_before,
+            volume_l_after: volume_before,
+            capacity_mass_kg: cargo.capacity_mass_kg,
+            capacity_volume_l: cargo.capacity_volume_l,
+        },
+        limit: None,
+    };
+
+    let Some(spec) = catalog.get(tx.com) else {
+        preview.limit = Some(if tx.units == 0 {
+            LimitReason::NoUnits
+        } else {
+            LimitReason::UnknownCommodity(tx.com)
+        });
+        return preview;
+    };
+
+    let config = TradingConfig::global();
+    let fee_bp = config.fee_bp;
+    let unit_price = price_view_with(catalog, tx.hub, tx.com, econ, rp).price_cents;
+    let subtotal_i128 = i128::from(unit_price.as_i64()) * i128::from(tx.units);
+    let fee_i128 = config.fee_cents(subtotal_i128);
+    let total_i128 = match tx.kind {
+        TradeKind::Buy => subtotal_i128 + fee_i128,
+        TradeKind::Sell => -(subtotal_i128 - fee_i128),
+    };
+    preview.unit_price = unit_price;
+    preview.subtotal = MoneyCents::from_i128_clamped(subtotal_i128);
+    preview.fee = MoneyCents::from_i128_clamped(fee_i128);
+    preview.total_cents = MoneyCents::from_i128_clamped(total_i128);
+
+    let mass_delta = u128::from(spec.mass_kg) * u128::from(tx.units);
+    let volume_delta = u128::from(spec.volume_l) * u128::from(tx.units);
+    let limit = if tx.units == 0 {
+        Some(LimitReason::NoUnits)
+    } else if fee_bp < 0 {
+        Some(LimitReason::NegativeFee)
+    } else {
+        match tx.kind {
+            TradeKind::Sell if held < tx.units => Some(LimitReason::InsufficientUnits { held }),
+            TradeKind::Sell => None,
+            TradeKind::Buy => match usage {
+                Err(_) => Some(
+                    cargo
+                        .items
+                        .keys()
+                        .find(|id| catalog.get(**id).is_none())
+                        .map_or(LimitReason::MassCapacity, |id| {
+                            LimitReason::UnknownCommodity(*id)
+                        }),
+                ),
+                Ok(_) => buy_limit(
+                    cargo,
+                    held,
+                    tx.units,
+                    mass_before.checked_add(mass_delta),
+                    volume_before.checked_add(volume_delta),
+                    wallet,
+                    preview.total_cents,
+                ),
+            },
+        }
+    };
+    if limit.is_some() {
+        preview.limit = limit;
+        return preview;
+    }
+
+    match tx.kind {
+        TradeKind::Buy => {
+            preview.wallet_after = wallet.saturating_sub(preview.total_cents);
+            preview.cargo_after.units_after = held + tx.units;
+            preview.cargo_after.mass_kg_after += mass_delta;
+            preview.cargo_after.volume_l_after += volume_delta;
+        }
+        TradeKind::Sell => {
+            let net = MoneyCents::from_i128_clamped(subtotal_i128 - fee_i128);
+            preview.wallet_after = wallet.saturating_add(net);
+            preview.cargo_after.units_after = held - tx.units;
+            preview.cargo_after.mass_kg_after = mass_before.saturating_sub(mass_delta);
+            preview.cargo_after.volume_l_after = volume_before.saturating_sub(volume_delta);
+        }
+    }
+    preview
+}
+
+fn buy_limit(
+    cargo: &Cargo,
+    held: u32,
+    units: u32,
+    mass_after: Option<u128>,
+    volume_after: Option<u128>,
+    wallet: MoneyCents,
+    total: MoneyCents,
+) -> Option<LimitReason> {
+    if mass_after.is_none_or(|mass| mass > u128::from(cargo.capacity_mass_kg)) {
+        Some(LimitReason::MassCapacity)
+    } else if volume_after.is_none_or(|volume| volume > u128::from(cargo.capacity_volume_l)) {
+        Some(LimitReason::VolumeCapacity)
+    } else if wallet.as_i64() < total.as_i64() {
+        Some(LimitReason::InsufficientFunds)
+    } else if held.checked_add(units).is_none() {
+        Some(LimitReason::UnitOverflow)
+    } else {
+        None
+    }
+}
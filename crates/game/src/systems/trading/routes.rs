@@ -0,0 +1,50 @@
+use std::collections::HashSet;
+
+use crate::systems::economy::{CommodityId, EconState, HubId, MoneyCents, RouteId, Rulepack};
+use crate::systems::trading::engine::{TradeKind, TradeTx};
+use crate::systems::trading::inventory::Cargo;
+use crate::systems::trading::pricing_vm::preview_trade;
+use crate::world::index::{route_destination, WorldIndex};
+
+/// Neighbouring hub paying the most for one unit of `com`, with the net
+/// proceeds after fees. Routes in `closed_routes` are skipped; equal
+/// proceeds go to the lowest hub id. `None` when no open neighbour quotes
+/// the commodity.
+pub fn best_sell_hub<I: WorldIndex>(
+    com: CommodityId,
+    from: HubId,
+    econ: &EconState,
+    rp: &Rulepack,
+    closed_routes: &HashSet<RouteId>,
+) -> Option<(HubId, MoneyCents)> {
+    let mut cargo = Cargo::default();
+    cargo.items.insert(com, 1);
+
+    let mut best: Option<(HubId, MoneyCents)> = None;
+    for route in I::neighbors(from) {
+        if closed_routes.contains(&route) {
+            continue;
+        }
+        let Some(hub) = route_destination::<I>(route, from) else {
+            continue;
+        };
+        let tx = TradeTx {
+            hub,
+            com,
+            units: 1,
+            kind: TradeKind::Sell,
+        };
+        let preview = preview_trade(&tx, econ, rp, &cargo, MoneyCents::ZERO);
+        if preview.limit.is_some() {
+            continue;
+        }
+        let proceeds = preview.wallet_after;
+        let better = best.is_none_or(|(best_hub, best_proceeds)| {
+            proceeds > best_proceeds || (proceeds == best_proceeds && hub < best_hub)
+        });
+        if better {
+            best = Some((hub, proceeds));
+        }
+    }
+    best
+}
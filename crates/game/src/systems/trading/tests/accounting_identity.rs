@@ -2,6 +2,7 @@ use crate::systems::economy::rulepack::load_rulepack;
 use crate::systems::economy::{BasisBp, CommodityId, EconState, HubId, MoneyCents};
 use crate::systems::trading::engine::{execute_trade, TradeKind, TradeTx};
 use crate::systems::trading::inventory::Cargo;
+use crate::systems::trading::ledger::{LedgerJournal, Wallet, WalletAccess};
 use crate::systems::trading::types::{CommodityCatalog, TradingConfig};
 use std::path::PathBuf;
 
@@ -18,7 +19,10 @@ fn load_catalog() -> CommodityCatalog {
 fn install_globals() {
     let catalog = load_catalog();
     CommodityCatalog::install_global(catalog);
-    TradingConfig::install_global(TradingConfig { fee_bp: 75 });
+    TradingConfig::install_global(TradingConfig {
+        fee_bp: 75,
+        ..TradingConfig::default()
+    });
 }
 
 fn load_rulepack_fixture() -> crate::systems::economy::Rulepack {
@@ -38,13 +42,16 @@ fn setup_state() -> EconState {
 fn wallet_delta_matches_identity() {
     install_globals();
     let rp = load_rulepack_fixture();
-    let econ = setup_state();
+    let mut econ = setup_state();
     let mut cargo = Cargo {
         capacity_mass_kg: 1_000,
         capacity_volume_l: 1_000,
         items: Default::default(),
+        lots: Default::default(),
     };
-    let mut wallet = MoneyCents(50_000);
+    let mut balance = Wallet::new(MoneyCents(50_000));
+    let mut journal = LedgerJournal::default();
+    let mut wallet = WalletAccess::new(&mut balance, &mut journal, 0);
 
     let buy = TradeTx {
         hub: HubId(1),
@@ -52,7 +59,7 @@ fn wallet_delta_matches_identity() {
         units: 2,
         kind: TradeKind::Buy,
     };
-    let buy_result = execute_trade(&buy, &econ, &mut cargo, &mut wallet, &rp).expect("buy");
+    let buy_result = execute_trade(&buy, &mut econ, &mut cargo, &mut wallet, &rp).expect("buy");
 
     let sell = TradeTx {
         hub: HubId(1),
@@ -60,9 +67,9 @@ fn wallet_delta_matches_identity() {
         units: 1,
         kind: TradeKind::Sell,
     };
-    let sell_result = execute_trade(&sell, &econ, &mut cargo, &mut wallet, &rp).expect("sell");
+    let sell_result = execute_trade(&sell, &mut econ, &mut cargo, &mut wallet, &rp).expect("sell");
 
-    let wallet_delta = wallet.as_i64() - 50_000;
+    let wallet_delta = wallet.balance().as_i64() - 50_000;
     let cost = buy_result.subtotal.as_i64();
     let proceeds = sell_result.subtotal.as_i64();
     let fees = buy_result.fee_cents.as_i64() + sell_result.fee_cents.as_i64();
@@ -0,0 +1,102 @@
+use crate::systems::economy::rulepack::load_rulepack;
+use crate::systems::economy::{
+    compute_price, BasisBp, CommodityId, EconState, HubId, MoneyCents, RouteId,
+};
+use crate::systems::trading::analysis::route_profit_estimates;
+use crate::systems::trading::inventory::Cargo;
+use crate::systems::trading::types::{CommodityCatalog, TradingConfig};
+use std::path::PathBuf;
+
+fn asset_path(relative: &str) -> PathBuf {
+    let manifest = env!("CARGO_MANIFEST_DIR");
+    PathBuf::from(manifest).join("..").join("..").join(relative)
+}
+
+fn install_globals() {
+    let path = asset_path("assets/trading/commodities.toml");
+    let catalog = CommodityCatalog::load_from_path(path.as_path()).expect("catalog");
+    CommodityCatalog::install_global(catalog);
+    TradingConfig::install_global(TradingConfig {
+        fee_bp: 75,
+        ..TradingConfig::default()
+    });
+}
+
+fn load_rulepack_fixture() -> crate::systems::economy::Rulepack {
+    let path = asset_path("assets/rulepacks/day_001.toml");
+    load_rulepack(path.to_str().expect("utf-8 path")).expect("rulepack")
+}
+
+fn hold(mass_kg: u32, volume_l: u32) -> Cargo {
+    Cargo {
+        capacity_mass_kg: mass_kg,
+        capacity_volume_l: volume_l,
+        ..Cargo::default()
+    }
+}
+
+#[test]
+fn scripted_spread_picks_best_commodity() {
+    install_globals();
+    let rp = load_rulepack_fixture();
+    let mut econ = EconState::default();
+    // Spice is cheap at hub 1 and dear at hub 2; grain runs the other way.
+    econ.basis_bp
+        .insert((HubId(1), CommodityId(3)), BasisBp(-400));
+    econ.basis_bp
+        .insert((HubId(2), CommodityId(3)), BasisBp(600));
+    econ.basis_bp
+        .insert((HubId(1), CommodityId(1)), BasisBp(300));
+
+    let estimates =
+        route_profit_estimates(HubId(1), &econ, &rp, &hold(100, 100)).expect("estimates");
+    let to_hub_2 = estimates
+        .iter()
+        .find(|estimate| estimate.route == RouteId(1))
+        .expect("route 1 estimate");
+    assert_eq!(to_hub_2.destination, HubId(2));
+    assert_eq!(to_hub_2.commodity, Some(CommodityId(3)));
+    // Spice is 2 kg / 1 l, so mass caps the hold at 50 units.
+    assert_eq!(to_hub_2.units, 50);
+
+    let base = CommodityCatalog::global().base_price(CommodityId(3));
+    let buy = i128::from(
+        compute_price(base, BasisBp(0), BasisBp(-400), &rp.pricing, None)
+            .price
+            .as_i64(),
+    );
+    let sell = i128::from(
+        compute_price(base, BasisBp(0), BasisBp(600), &rp.pricing, None)
+            .price
+            .as_i64(),
+    );
+    let cost = buy * 50 + buy * 50 * 75 / 10_000;
+    let proceeds = sell * 50 - sell * 50 * 75 / 10_000;
+    assert_eq!(to_hub_2.margin_cents, MoneyCents((proceeds - cost) as i64));
+
+    // Hub 3 quotes spice at par, so the same haul earns less there.
+    let to_hub_3 = estimates
+        .iter()
+        .find(|estimate| estimate.route == RouteId(3))
+        .expect("route 3 estimate");
+    assert_eq!(to_hub_3.destination, HubId(3));
+    assert_eq!(to_hub_3.commodity, Some(CommodityId(3)));
+    assert!(to_hub_3.margin_cents.as_i64() < to_hub_2.margin_cents.as_i64());
+}
+
+#[test]
+fn zero_capacity_yields_zero_estimates() {
+    install_globals();
+    let rp = load_rulepack_fixture();
+    let mut econ = EconState::default();
+    econ.basis_bp
+        .insert((HubId(2), CommodityId(3)), BasisBp(600));
+
+    let estimates = route_profit_estimates(HubId(1), &econ, &rp, &hold(0, 0)).expect("estimates");
+    assert!(!estimates.is_empty());
+    for estimate in estimates {
+        assert_eq!(estimate.units, 0);
+        assert_eq!(estimate.commodity, None);
+        assert_eq!(estimate.margin_cents, MoneyCents::ZERO);
+    }
+}
@@ -2,6 +2,7 @@ use crate::systems::economy::rulepack::load_rulepack;
 use crate::systems::economy::{BasisBp, CommodityId, EconState, HubId, MoneyCents};
 use crate::systems::trading::engine::{execute_trade, TradeKind, TradeTx};
 use crate::systems::trading::inventory::Cargo;
+use crate::systems::trading::ledger::{LedgerJournal, Wallet, WalletAccess};
 use crate::systems::trading::types::{CommodityCatalog, TradingConfig};
 use std::path::PathBuf;
 
@@ -18,7 +19,10 @@ fn load_catalog() -> CommodityCatalog {
 fn install_globals() {
     let catalog = load_catalog();
     CommodityCatalog::install_global(catalog);
-    TradingConfig::install_global(TradingConfig { fee_bp: 75 });
+    TradingConfig::install_global(TradingConfig {
+        fee_bp: 75,
+        ..TradingConfig::default()
+    });
 }
 
 fn load_rulepack_fixture() -> crate::systems::economy::Rulepack {
@@ -38,13 +42,16 @@ fn setup_state() -> EconState {
 fn buy_respects_mass_and_volume_caps() {
     install_globals();
     let rp = load_rulepack_fixture();
-    let econ = setup_state();
+    let mut econ = setup_state();
     let mut cargo = Cargo {
         capacity_mass_kg: 15,
         capacity_volume_l: 15,
         items: Default::default(),
+        lots: Default::default(),
     };
-    let mut wallet = MoneyCents(100_000);
+    let mut balance = Wallet::new(MoneyCents(100_000));
+    let mut journal = LedgerJournal::default();
+    let mut wallet = WalletAccess::new(&mut balance, &mut journal, 0);
 
     let buy = TradeTx {
         hub: HubId(1),
@@ -52,15 +59,15 @@ fn buy_respects_mass_and_volume_caps() {
         units: 1,
         kind: TradeKind::Buy,
     };
-    execute_trade(&buy, &econ, &mut cargo, &mut wallet, &rp).expect("initial buy");
+    execute_trade(&buy, &mut econ, &mut cargo, &mut wallet, &rp).expect("initial buy");
 
-    let err = execute_trade(&buy, &econ, &mut cargo, &mut wallet, &rp).expect_err("capacity");
+    let err = execute_trade(&buy, &mut econ, &mut cargo, &mut wallet, &rp).expect_err("capacity");
     assert!(format!("{err}").contains("capacity"));
 
     let sell = TradeTx {
         kind: TradeKind::Sell,
         ..buy
     };
-    execute_trade(&sell, &econ, &mut cargo, &mut wallet, &rp).expect("sell");
+    execute_trade(&sell, &mut econ, &mut cargo, &mut wallet, &rp).expect("sell");
     assert_eq!(cargo.units(CommodityId(1)), 0);
 }
@@ -0,0 +1,112 @@
+use crate::app_state::AppState;
+use crate::systems::economy::rulepack::load_rulepack;
+use crate::systems::economy::{BasisBp, CommodityId, EconState, EconomyDay, HubId, MoneyCents};
+use crate::systems::save::{app_state_from_snapshot, snapshot_from_app_state};
+use crate::systems::trading::engine::{execute_trade, TradeKind, TradeTx};
+use crate::systems::trading::inventory::{Cargo, CargoLot};
+use crate::systems::trading::ledger::{LedgerJournal, Wallet, WalletAccess};
+use crate::systems::trading::types::{CommodityCatalog, TradingConfig};
+use std::path::PathBuf;
+
+const GRAIN: CommodityId = CommodityId(1);
+const ORE: CommodityId = CommodityId(2);
+
+fn asset_path(relative: &str) -> PathBuf {
+    let manifest = env!("CARGO_MANIFEST_DIR");
+    PathBuf::from(manifest).join("..").join("..").join(relative)
+}
+
+fn install_globals() {
+    let path = asset_path("assets/trading/commodities.toml");
+    let catalog = CommodityCatalog::load_from_path(path.as_path()).expect("catalog");
+    CommodityCatalog::install_global(catalog);
+    TradingConfig::install_global(TradingConfig {
+        fee_bp: 75,
+        ..TradingConfig::default()
+    });
+}
+
+#[test]
+fn selling_the_last_units_removes_the_entry() {
+    install_globals();
+    let rp = load_rulepack(
+        asset_path("assets/rulepacks/day_001.toml")
+            .to_str()
+            .expect("utf-8 path"),
+    )
+    .expect("rulepack");
+    let mut econ = EconState {
+        day: EconomyDay(3),
+        ..EconState::default()
+    };
+    econ.di_bp.insert(GRAIN, BasisBp(250));
+    econ.basis_bp.insert((HubId(1), GRAIN), BasisBp(150));
+    let mut cargo = Cargo {
+        capacity_mass_kg: 1_000,
+        capacity_volume_l: 1_000,
+        ..Cargo::default()
+    };
+    cargo.load(GRAIN, 4, EconomyDay(1)).expect("load");
+    cargo.load(GRAIN, 2, EconomyDay(2)).expect("load");
+    let mut balance = Wallet::new(MoneyCents(10_000));
+    let mut journal = LedgerJournal::default();
+    let mut wallet = WalletAccess::new(&mut balance, &mut journal, 0);
+
+    let tx = TradeTx {
+        hub: HubId(1),
+        com: GRAIN,
+        units: 6,
+        kind: TradeKind::Sell,
+    };
+    execute_trade(&tx, &mut econ, &mut cargo, &mut wallet, &rp).expect("sell");
+
+    assert!(!cargo.items.contains_key(&GRAIN));
+    assert!(!cargo.lots.contains_key(&GRAIN));
+}
+
+#[test]
+fn compact_prunes_zero_entries_and_is_idempotent() {
+    let mut cargo = Cargo::default();
+    cargo.load(GRAIN, 3, EconomyDay(1)).expect("load");
+    cargo.items.insert(ORE, 0);
+    cargo.lots.insert(
+        ORE,
+        vec![CargoLot {
+            loaded_day: EconomyDay(1),
+            units: 2,
+        }],
+    );
+
+    cargo.compact();
+    let once = cargo.clone();
+    cargo.compact();
+
+    assert_eq!(cargo, once);
+    assert_eq!(cargo.items.len(), 1);
+    assert_eq!(cargo.units(GRAIN), 3);
+    assert!(!cargo.lots.contains_key(&ORE));
+}
+
+#[test]
+fn saves_never_hold_zero_entries() {
+    let mut state = AppState::default();
+    state.cargo.load(GRAIN, 3, EconomyDay(1)).expect("load");
+    state.cargo.items.insert(ORE, 0);
+
+    let snapshot = snapshot_from_app_state(&state);
+    let saved: Vec<CommodityId> = snapshot
+        .cargo
+        .items
+        .iter()
+        .map(|item| item.commodity)
+        .collect();
+    assert_eq!(saved, vec![GRAIN]);
+
+    let mut zeroed = snapshot.clone();
+    if let Some(item) = zeroed.cargo.items.first_mut() {
+        item.units = 0;
+    }
+    let restored = app_state_from_snapshot(zeroed);
+    assert!(restored.cargo.items.is_empty());
+    assert!(restored.cargo.lots.is_empty());
+}
@@ -0,0 +1,206 @@
+use crate::systems::command_queue::CommandQueue;
+use crate::systems::director::config::load_director_cfg;
+use crate::systems::director::director_cfg_path;
+use crate::systems::economy::rulepack::load_rulepack;
+use crate::systems::economy::{CommodityId, EconState, HubId, MoneyCents};
+use crate::systems::gameplay::rewards::RewardEntry;
+use crate::systems::trading::catalog::{reload_commodities, CatalogHandle, CatalogVersion};
+use crate::systems::trading::engine::{TradeKind, TradeTx};
+use crate::systems::trading::inventory::Cargo;
+use crate::systems::trading::pricing_vm::preview_trade;
+use crate::systems::trading::types::{Commodities, CommodityCatalog, CommoditySpec, TradingConfig};
+use repro::CommandKind;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Barrier};
+
+fn asset_path(relative: &str) -> PathBuf {
+    let manifest = env!("CARGO_MANIFEST_DIR");
+    PathBuf::from(manifest).join("..").join("..").join(relative)
+}
+
+fn catalog(mass_kg: u16, extra: usize) -> CommodityCatalog {
+    let mut list = vec![CommoditySpec {
+        id: CommodityId(1),
+        name: "grain".to_string(),
+        mass_kg,
+        volume_l: 1,
+        shelf_life_days: None,
+        base_price_cents: None,
+    }];
+    for idx in 0..extra {
+        let id = 10 + idx as u16;
+        list.push(CommoditySpec {
+            id: CommodityId(id),
+            name: format!("extra-{id}"),
+            mass_kg: 1,
+            volume_l: 1,
+            shelf_life_days: None,
+            base_price_cents: None,
+        });
+    }
+    Commodities { list }.into()
+}
+
+#[test]
+fn snapshots_outlive_later_installs() {
+    let handle = CatalogHandle::new(catalog(10, 0));
+    let _scope = handle.scope();
+    assert_eq!(handle.version(), CatalogVersion(1));
+
+    let before = CommodityCatalog::global();
+    assert_eq!(handle.install(catalog(20, 0)), CatalogVersion(2));
+    let after = CommodityCatalog::global();
+
+    assert_eq!(
+        before.get(CommodityId(1)).map(|spec| spec.mass_kg),
+        Some(10)
+    );
+    assert_eq!(after.get(CommodityId(1)).map(|spec| spec.mass_kg), Some(20));
+    assert_ne!(before.content_hash(), after.content_hash());
+}
+
+#[test]
+fn previews_see_one_catalog_while_installs_race() {
+    TradingConfig::install_global(TradingConfig {
+        fee_bp: 75,
+        ..TradingConfig::default()
+    });
+    let rp = load_rulepack(
+        asset_path("assets/rulepacks/day_001.toml")
+            .to_str()
+            .expect("utf-8 path"),
+    )
+    .expect("rulepack");
+    let handle = CatalogHandle::new(catalog(10, 0));
+    let _scope = handle.scope();
+
+    let done = Arc::new(AtomicBool::new(false));
+    let installer = {
+        let handle = handle.clone();
+        let done = Arc::clone(&done);
+        std::thread::spawn(move || {
+            let mut heavy = false;
+            while !done.load(Ordering::Relaxed) {
+                heavy = !heavy;
+                handle.install(catalog(if heavy { 20 } else { 10 }, 0));
+            }
+        })
+    };
+
+    let mut cargo = Cargo {
+        capacity_mass_kg: 100_000,
+        capacity_volume_l: 100_000,
+        ..Cargo::default()
+    };
+    cargo.items.insert(CommodityId(1), 3);
+    let tx = TradeTx {
+        hub: HubId(1),
+        com: CommodityId(1),
+        units: 2,
+        kind: TradeKind::Buy,
+    };
+    let econ = EconState::default();
+    for _ in 0..500 {
+        let preview = preview_trade(&tx, &econ, &rp, &cargo, MoneyCents(i64::MAX / 2));
+        let mass = preview.cargo_after.mass_kg_after;
+        assert!(mass == 5 * 10 || mass == 5 * 20, "mixed catalogs: {mass}");
+    }
+    done.store(true, Ordering::Relaxed);
+    installer.join().expect("installer thread");
+}
+
+#[test]
+fn failed_reloads_keep_the_active_catalog() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let path = dir.path().join("commodities.toml");
+    let handle = CatalogHandle::new(catalog(10, 0));
+    let active = handle.current();
+    let mut queue = CommandQueue::default();
+
+    std::fs::write(&path, "list = [ { id = 1, name = \"grain\" ").expect("write");
+    reload_commodities(&handle, &path, None, &mut queue).expect_err("truncated toml");
+
+    std::fs::write(
+        &path,
+        "list = [\n  { id = 1, name = \"grain\", mass_kg = 10, volume_l = 8 },\n  { id = 1, name = \"ore\", mass_kg = 25, volume_l = 15 },\n]\n",
+    )
+    .expect("write");
+    let err = reload_commodities(&handle, &path, None, &mut queue).expect_err("duplicate id");
+    assert!(format!("{err:#}").contains("listed twice"), "{err:#}");
+
+    std::fs::write(
+        &path,
+        "list = [ { id = 1, name = \"grain\", mass_kg = 10, volume_l = 8, base_price_cents = 0 } ]\n",
+    )
+    .expect("write");
+    let err = reload_commodities(&handle, &path, None, &mut queue).expect_err("zero base price");
+    assert!(format!("{err:#}").contains("base_price_cents"), "{err:#}");
+
+    let mut cfg =
+        load_director_cfg(director_cfg_path().to_str().expect("cfg path")).expect("director cfg");
+    cfg.rewards.success.entries = vec![RewardEntry {
+        weight: 1,
+        cents: 0,
+        commodity: Some(3),
+        units: 5,
+    }];
+    std::fs::write(
+        &path,
+        "list = [ { id = 1, name = \"grain\", mass_kg = 10, volume_l = 8 } ]\n",
+    )
+    .expect("write");
+    let err =
+        reload_commodities(&handle, &path, Some(&cfg), &mut queue).expect_err("dangling reward");
+    assert!(format!("{err:#}").contains('3'), "{err:#}");
+
+    assert_eq!(handle.version(), CatalogVersion(1));
+    assert!(Arc::ptr_eq(&active, &handle.current()));
+    assert!(queue.drain().is_empty());
+
+    let version = reload_commodities(
+        &handle,
+        &asset_path("assets/trading/commodities.toml"),
+        Some(&cfg),
+        &mut queue,
+    )
+    .expect("valid reload");
+    assert_eq!(version, CatalogVersion(2));
+    let hash = handle.current().content_hash();
+    let meters: Vec<(String, i32)> = queue
+        .drain()
+        .into_iter()
+        .filter_map(|command| match command.kind {
+            CommandKind::Meter(meter) => Some((meter.key.to_string(), meter.value)),
+            CommandKind::Spawn(_) => None,
+        })
+        .collect();
+    assert_eq!(
+        meters,
+        vec![("commodity_catalog_hash".to_string(), hash as u32 as i32)]
+    );
+}
+
+#[test]
+fn scoped_handles_isolate_parallel_threads() {
+    let barrier = Arc::new(Barrier::new(2));
+    let workers: Vec<_> = [2usize, 5]
+        .into_iter()
+        .map(|extra| {
+            let barrier = Arc::clone(&barrier);
+            std::thread::spawn(move || {
+                let handle = CatalogHandle::new(catalog(10, extra));
+                let _scope = handle.scope();
+                barrier.wait();
+                CommodityCatalog::install_global(catalog(10, extra + 1));
+                barrier.wait();
+                CommodityCatalog::global().list().len()
+            })
+        })
+        .collect();
+    let lens: Vec<usize> = workers
+        .into_iter()
+        .map(|worker| worker.join().expect("worker"))
+        .collect();
+    assert_eq!(lens, vec![4, 7]);
+}
@@ -0,0 +1,68 @@
+use crate::systems::economy::CommodityId;
+use crate::systems::trading::types::{CatalogError, Commodities, CommodityCatalog};
+use std::path::PathBuf;
+
+fn asset_path(relative: &str) -> PathBuf {
+    let manifest = env!("CARGO_MANIFEST_DIR");
+    PathBuf::from(manifest).join("..").join("..").join(relative)
+}
+
+fn load_err(raw: &str) -> CatalogError {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let path = dir.path().join("commodities.toml");
+    std::fs::write(&path, raw).expect("write");
+    let err = CommodityCatalog::load_from_path(&path).expect_err("invalid catalog");
+    err.downcast_ref::<CatalogError>()
+        .cloned()
+        .unwrap_or_else(|| panic!("not a catalog error: {err:#}"))
+}
+
+#[test]
+fn duplicate_ids_are_rejected_at_load() {
+    let err = load_err(
+        "list = [\n  { id = 1, name = \"grain\", mass_kg = 10, volume_l = 8 },\n  { id = 2, name = \"ore\", mass_kg = 25, volume_l = 15 },\n  { id = 1, name = \"spice\", mass_kg = 2, volume_l = 1 },\n]\n",
+    );
+    assert_eq!(err, CatalogError::DuplicateId(CommodityId(1)));
+}
+
+#[test]
+fn zero_volume_is_rejected_at_load() {
+    let err = load_err(
+        "list = [\n  { id = 1, name = \"grain\", mass_kg = 10, volume_l = 8 },\n  { id = 2, name = \"ore\", mass_kg = 25, volume_l = 0 },\n]\n",
+    );
+    assert_eq!(err, CatalogError::ZeroVolume(CommodityId(2)));
+}
+
+#[test]
+fn other_malformed_specs_report_their_own_variant() {
+    let cases = [
+        ("list = []\n", CatalogError::Empty),
+        (
+            "list = [ { id = 3, name = \"\", mass_kg = 1, volume_l = 1 } ]\n",
+            CatalogError::EmptyName(CommodityId(3)),
+        ),
+        (
+            "list = [\n  { id = 1, name = \"grain\", mass_kg = 1, volume_l = 1 },\n  { id = 2, name = \"grain\", mass_kg = 1, volume_l = 1 },\n]\n",
+            CatalogError::DuplicateName("grain".to_string()),
+        ),
+        (
+            "list = [ { id = 4, name = \"ore\", mass_kg = 0, volume_l = 1 } ]\n",
+            CatalogError::ZeroMass(CommodityId(4)),
+        ),
+        (
+            "list = [ { id = 5, name = \"spice\", mass_kg = 1, volume_l = 1, base_price_cents = -1 } ]\n",
+            CatalogError::NonPositiveBasePrice(CommodityId(5)),
+        ),
+    ];
+    for (raw, expected) in cases {
+        assert_eq!(load_err(raw), expected, "{raw}");
+    }
+}
+
+#[test]
+fn shipped_catalog_passes_validation() {
+    let raw = std::fs::read_to_string(asset_path("assets/trading/commodities.toml"))
+        .expect("read catalog");
+    let parsed: Commodities = toml::from_str(&raw).expect("parse catalog");
+    assert_eq!(parsed.validate(), Ok(()));
+}
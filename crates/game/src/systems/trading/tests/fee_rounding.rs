@@ -0,0 +1,136 @@
+use crate::systems::economy::rulepack::load_rulepack;
+use crate::systems::economy::{BasisBp, CommodityId, EconState, HubId, MoneyCents};
+use crate::systems::trading::engine::{execute_trade, TradeKind, TradeTx};
+use crate::systems::trading::inventory::Cargo;
+use crate::systems::trading::ledger::{LedgerJournal, Wallet, WalletAccess};
+use crate::systems::trading::pricing_vm::price_view;
+use crate::systems::trading::types::{CommodityCatalog, RoundingMode, TradingConfig};
+use std::path::PathBuf;
+
+const MODES: [RoundingMode; 3] = [
+    RoundingMode::BankersHalfEven,
+    RoundingMode::Floor,
+    RoundingMode::Ceil,
+];
+
+fn asset_path(relative: &str) -> PathBuf {
+    let manifest = env!("CARGO_MANIFEST_DIR");
+    PathBuf::from(manifest).join("..").join("..").join(relative)
+}
+
+fn config(fee_bp: i32, rounding: RoundingMode) -> TradingConfig {
+    TradingConfig {
+        fee_bp,
+        rounding,
+        ..TradingConfig::default()
+    }
+}
+
+#[test]
+fn half_cent_fees_follow_the_documented_rounding() {
+    // 75 bp of 200 is 1.5 cents and of 600 is 4.5 cents.
+    let expected = [
+        (RoundingMode::BankersHalfEven, 2, 4),
+        (RoundingMode::Floor, 1, 4),
+        (RoundingMode::Ceil, 2, 5),
+    ];
+    for (mode, odd, even) in expected {
+        let config = config(75, mode);
+        assert_eq!(config.fee_cents(200), odd, "{mode:?}");
+        assert_eq!(config.fee_cents(600), even, "{mode:?}");
+        // Whole-cent fees are left alone.
+        assert_eq!(config.fee_cents(400), 3, "{mode:?}");
+    }
+}
+
+#[test]
+fn rounding_defaults_to_floor() {
+    let parsed: TradingConfig = toml::from_str("fee_bp = 75").expect("config");
+    assert_eq!(parsed.rounding, RoundingMode::Floor);
+    let parsed: TradingConfig =
+        toml::from_str("fee_bp = 75\nrounding = \"BankersHalfEven\"").expect("config");
+    assert_eq!(parsed.rounding, RoundingMode::BankersHalfEven);
+    toml::from_str::<TradingConfig>("fee_bp = 75\nrounding = \"Nearest\"")
+        .expect_err("unknown rounding mode");
+}
+
+#[test]
+fn half_cent_trades_keep_the_accounting_identity() {
+    let catalog =
+        CommodityCatalog::load_from_path(asset_path("assets/trading/commodities.toml").as_path())
+            .expect("catalog");
+    CommodityCatalog::install_global(catalog);
+    let rp = load_rulepack(
+        asset_path("assets/rulepacks/day_001.toml")
+            .to_str()
+            .expect("utf-8 path"),
+    )
+    .expect("rulepack");
+    let mut econ = EconState::default();
+    econ.di_bp.insert(CommodityId(1), BasisBp(250));
+    econ.basis_bp
+        .insert((HubId(1), CommodityId(1)), BasisBp(150));
+
+    // Find a unit count and fee whose exact value ends in half a cent.
+    let price = i128::from(
+        price_view(HubId(1), CommodityId(1), &econ, &rp)
+            .price_cents
+            .as_i64(),
+    );
+    let (units, fee_bp) = (1..=20u32)
+        .flat_map(|units| (1..=500).map(move |fee_bp| (units, fee_bp)))
+        .find(|&(units, fee_bp)| {
+            (price * i128::from(units) * i128::from(fee_bp)).rem_euclid(10_000) == 5_000
+        })
+        .expect("a half-cent fee");
+    let subtotal = price * i128::from(units);
+    let floor = subtotal * i128::from(fee_bp) / 10_000;
+
+    for mode in MODES {
+        let _scope = TradingConfig::scope(config(fee_bp, mode));
+        let expected_fee = match mode {
+            RoundingMode::BankersHalfEven => floor + (floor & 1),
+            RoundingMode::Floor => floor,
+            RoundingMode::Ceil => floor + 1,
+        };
+
+        let mut econ = econ.clone();
+        let mut cargo = Cargo {
+            capacity_mass_kg: 100_000,
+            capacity_volume_l: 100_000,
+            ..Cargo::default()
+        };
+        let mut balance = Wallet::new(MoneyCents(1_000_000));
+        let mut journal = LedgerJournal::default();
+        let mut wallet = WalletAccess::new(&mut balance, &mut journal, 0);
+
+        let buy = TradeTx {
+            hub: HubId(1),
+            com: CommodityId(1),
+            units,
+            kind: TradeKind::Buy,
+        };
+        let bought = execute_trade(&buy, &mut econ, &mut cargo, &mut wallet, &rp).expect("buy");
+        assert_eq!(
+            i128::from(bought.fee_cents.as_i64()),
+            expected_fee,
+            "{mode:?}"
+        );
+
+        let sell = TradeTx {
+            kind: TradeKind::Sell,
+            ..buy
+        };
+        let sold = execute_trade(&sell, &mut econ, &mut cargo, &mut wallet, &rp).expect("sell");
+
+        let wallet_delta = wallet.balance().as_i64() - 1_000_000;
+        let fees = bought.fee_cents.as_i64() + sold.fee_cents.as_i64();
+        assert_eq!(
+            wallet_delta,
+            -bought.subtotal.as_i64() + sold.subtotal.as_i64() - fees,
+            "{mode:?}"
+        );
+        assert_eq!(journal.net(), wallet_delta, "{mode:?}");
+        assert_eq!(cargo.units(CommodityId(1)), 0, "{mode:?}");
+    }
+}
@@ -0,0 +1,192 @@
+use crate::app_state::AppState;
+use crate::systems::command_queue::CommandQueue;
+use crate::systems::economy::rulepack::load_rulepack;
+use crate::systems::economy::{BasisBp, CommodityId, EconState, HubId, MoneyCents};
+use crate::systems::trading::engine::{execute_trade, TradeKind, TradeTx};
+use crate::systems::trading::inventory::Cargo;
+use crate::systems::trading::ledger::{
+    reconcile_ledger, LedgerJournal, LedgerSource, Wallet, WalletAccess,
+};
+use crate::systems::trading::pricing_vm::LimitReason;
+use crate::systems::trading::types::{CommodityCatalog, TradingConfig};
+use bevy::ecs::system::IntoSystem;
+use bevy::prelude::*;
+use repro::CommandKind;
+use std::path::PathBuf;
+
+fn asset_path(relative: &str) -> PathBuf {
+    let manifest = env!("CARGO_MANIFEST_DIR");
+    PathBuf::from(manifest).join("..").join("..").join(relative)
+}
+
+fn install_globals() {
+    let path = asset_path("assets/trading/commodities.toml");
+    let catalog = CommodityCatalog::load_from_path(path.as_path()).expect("catalog");
+    CommodityCatalog::install_global(catalog);
+    TradingConfig::install_global(TradingConfig {
+        fee_bp: 75,
+        ..TradingConfig::default()
+    });
+}
+
+fn load_rulepack_fixture() -> crate::systems::economy::Rulepack {
+    let path = asset_path("assets/rulepacks/day_001.toml");
+    load_rulepack(path.to_str().expect("utf-8 path")).expect("rulepack")
+}
+
+fn trading_state() -> AppState {
+    let mut econ = EconState::default();
+    econ.di_bp.insert(CommodityId(1), BasisBp(250));
+    econ.basis_bp
+        .insert((HubId(1), CommodityId(1)), BasisBp(150));
+    AppState {
+        econ,
+        cargo: Cargo {
+            capacity_mass_kg: 1_000,
+            capacity_volume_l: 1_000,
+            ..Cargo::default()
+        },
+        wallet: Wallet::new(MoneyCents(50_000)),
+        ..AppState::default()
+    }
+}
+
+fn tx(units: u32, kind: TradeKind) -> TradeTx {
+    TradeTx {
+        hub: HubId(1),
+        com: CommodityId(1),
+        units,
+        kind,
+    }
+}
+
+fn drift_meters(world: &mut World) -> Vec<i32> {
+    world
+        .resource_mut::<CommandQueue>()
+        .drain()
+        .into_iter()
+        .filter_map(|command| match command.kind {
+            CommandKind::Meter(meter) if meter.key == "ledger_drift_cents" => Some(meter.value),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn scripted_trades_match_journal_totals() {
+    install_globals();
+    let rp = load_rulepack_fixture();
+    let mut state = trading_state();
+    let mut journal = LedgerJournal::default();
+    journal.reconcile(state.wallet.balance());
+
+    let script = [
+        (2, TradeKind::Buy),
+        (1, TradeKind::Sell),
+        (1, TradeKind::Buy),
+        (2, TradeKind::Sell),
+    ];
+    let mut expected = 0;
+    for (tick, (units, kind)) in (0u32..).zip(script) {
+        let AppState {
+            econ,
+            cargo,
+            wallet,
+            ..
+        } = &mut state;
+        let mut wallet = WalletAccess::new(wallet, &mut journal, tick);
+        let result = execute_trade(&tx(units, kind), econ, cargo, &mut wallet, &rp).expect("trade");
+        expected += match kind {
+            TradeKind::Buy => -result.total_cents.as_i64(),
+            TradeKind::Sell => result.subtotal.as_i64() - result.fee_cents.as_i64(),
+        };
+    }
+
+    let ticks: Vec<u32> = journal.entries().iter().map(|entry| entry.tick).collect();
+    assert_eq!(ticks, vec![0, 1, 2, 3]);
+    assert_eq!(journal.net(), expected);
+    assert_eq!(journal.net_for(LedgerSource::Trade), expected);
+    assert_eq!(journal.net_for(LedgerSource::Toll), 0);
+    assert_eq!(state.wallet.balance().as_i64() - 50_000, expected);
+    assert_eq!(journal.reconcile(state.wallet.balance()), 0);
+}
+
+#[test]
+fn rejected_trade_journals_nothing() {
+    install_globals();
+    let rp = load_rulepack_fixture();
+    let mut state = trading_state();
+    let mut journal = LedgerJournal::default();
+    let AppState {
+        econ,
+        cargo,
+        wallet,
+        ..
+    } = &mut state;
+    let mut wallet = WalletAccess::new(wallet, &mut journal, 0);
+    execute_trade(&tx(1, TradeKind::Sell), econ, cargo, &mut wallet, &rp)
+        .expect_err("nothing to sell");
+    assert!(journal.entries().is_empty());
+}
+
+#[test]
+fn buy_beyond_the_wallet_is_rejected() {
+    install_globals();
+    let rp = load_rulepack_fixture();
+    let mut state = AppState {
+        wallet: Wallet::new(MoneyCents(1)),
+        ..trading_state()
+    };
+    let mut journal = LedgerJournal::default();
+    let AppState {
+        econ,
+        cargo,
+        wallet,
+        ..
+    } = &mut state;
+    let mut access = WalletAccess::new(wallet, &mut journal, 0);
+    let err = execute_trade(&tx(1, TradeKind::Buy), econ, cargo, &mut access, &rp)
+        .expect_err("wallet cannot cover the buy");
+    assert_eq!(
+        err.downcast_ref::<LimitReason>(),
+        Some(&LimitReason::InsufficientFunds)
+    );
+    assert_eq!(wallet.balance(), MoneyCents(1));
+    assert_eq!(cargo.units(CommodityId(1)), 0);
+    assert!(journal.entries().is_empty());
+}
+
+#[test]
+#[cfg_attr(feature = "deterministic", should_panic(expected = "drifted"))]
+fn bypassed_mutation_trips_reconciliation() {
+    let mut world = World::new();
+    world.insert_resource(trading_state());
+    world.init_resource::<LedgerJournal>();
+    world.init_resource::<CommandQueue>();
+    let mut system = IntoSystem::into_system(reconcile_ledger);
+    system.initialize(&mut world);
+
+    // The first pass only takes the baseline.
+    let _ = system.run((), &mut world);
+    {
+        let tick = world.resource::<CommandQueue>().current_tick();
+        world.resource_scope(|world, mut journal: Mut<LedgerJournal>| {
+            let mut state = world.resource_mut::<AppState>();
+            WalletAccess::new(&mut state.wallet, &mut journal, tick)
+                .debit(LedgerSource::Toll, MoneyCents(1_200));
+        });
+    }
+    let _ = system.run((), &mut world);
+    assert!(drift_meters(&mut world).is_empty());
+
+    world
+        .resource_mut::<AppState>()
+        .wallet
+        .bypass_journal(MoneyCents(60_000));
+    let _ = system.run((), &mut world);
+    assert_eq!(drift_meters(&mut world), vec![60_000 - 48_800]);
+
+    // The drift is reported once; the next step starts from the new balance.
+    let _ = system.run((), &mut world);
+    assert!(drift_meters(&mut world).is_empty());
+}
@@ -4,6 +4,7 @@ use crate::systems::economy::rulepack::load_rulepack;
 use crate::systems::economy::{BasisBp, CommodityId, EconState, HubId, MoneyCents};
 use crate::systems::trading::engine::{execute_trade, TradeKind, TradeTx};
 use crate::systems::trading::inventory::Cargo;
+use crate::systems::trading::ledger::{LedgerJournal, Wallet, WalletAccess};
 use crate::systems::trading::pricing_vm::price_view;
 use crate::systems::trading::types::{CommodityCatalog, TradingConfig};
 
@@ -20,7 +21,10 @@ fn load_catalog() -> CommodityCatalog {
 fn install_globals() {
     let catalog = load_catalog();
     CommodityCatalog::install_global(catalog);
-    TradingConfig::install_global(TradingConfig { fee_bp: 75 });
+    TradingConfig::install_global(TradingConfig {
+        fee_bp: 75,
+        ..TradingConfig::default()
+    });
 }
 
 fn load_rulepack_fixture() -> crate::systems::economy::Rulepack {
@@ -45,8 +49,11 @@ fn price_stable_within_day() {
         capacity_mass_kg: 1_000,
         capacity_volume_l: 1_000,
         items: Default::default(),
+        lots: Default::default(),
     };
-    let mut wallet = MoneyCents(100_000);
+    let mut balance = Wallet::new(MoneyCents(100_000));
+    let mut journal = LedgerJournal::default();
+    let mut wallet = WalletAccess::new(&mut balance, &mut journal, 0);
 
     let baseline = price_view(HubId(1), CommodityId(1), &econ, &rp).price_cents;
 
@@ -56,7 +63,7 @@ fn price_stable_within_day() {
         units: 1,
         kind: TradeKind::Buy,
     };
-    execute_trade(&buy, &econ, &mut cargo, &mut wallet, &rp).expect("buy");
+    execute_trade(&buy, &mut econ, &mut cargo, &mut wallet, &rp).expect("buy");
 
     let after_buy = price_view(HubId(1), CommodityId(1), &econ, &rp).price_cents;
     assert_eq!(after_buy, baseline);
@@ -1,8 +1,12 @@
 use std::path::PathBuf;
 
 use crate::systems::economy::rulepack::load_rulepack;
-use crate::systems::economy::{compute_price, BasisBp, CommodityId, EconState, HubId, Rulepack};
-use crate::systems::trading::pricing_vm::{price_view, DEFAULT_QUOTE_BASE};
+use crate::systems::economy::{
+    compute_price, BasisBp, CommodityId, EconState, HubId, MoneyCents, Rulepack,
+};
+use crate::systems::trading::catalog::CatalogHandle;
+use crate::systems::trading::pricing_vm::{price_view, price_view_with, DEFAULT_QUOTE_BASE};
+use crate::systems::trading::types::{Commodities, CommodityCatalog, CommoditySpec};
 
 fn workspace_path(relative: &str) -> PathBuf {
     let manifest = env!("CARGO_MANIFEST_DIR");
@@ -16,6 +20,21 @@ fn setup_state(di: BasisBp, basis: BasisBp) -> EconState {
     state
 }
 
+fn priced_catalog(bases: &[(u16, Option<i64>)]) -> CommodityCatalog {
+    let list = bases
+        .iter()
+        .map(|&(id, base_price_cents)| CommoditySpec {
+            id: CommodityId(id),
+            name: format!("commodity-{id}"),
+            mass_kg: 1,
+            volume_l: 1,
+            shelf_life_days: None,
+            base_price_cents,
+        })
+        .collect();
+    Commodities { list }.into()
+}
+
 fn load_rulepack_fixture() -> Rulepack {
     let path = workspace_path("assets/rulepacks/day_001.toml");
     load_rulepack(path.to_str().expect("utf-8 path")).expect("rulepack")
@@ -30,7 +49,8 @@ fn view_matches_compute_price_across_range() {
             let basis_bp = BasisBp(basis);
             let econ = setup_state(di_bp, basis_bp);
             let view = price_view(HubId(1), CommodityId(1), &econ, &rp);
-            let expected = compute_price(DEFAULT_QUOTE_BASE, di_bp, basis_bp, &rp.pricing);
+            let expected =
+                compute_price(DEFAULT_QUOTE_BASE, di_bp, basis_bp, &rp.pricing, None).price;
             assert_eq!(view.price_cents, expected, "di={di} basis={basis}");
         }
     }
@@ -69,7 +89,7 @@ fn ties_round_to_even_cent() {
     let econ = setup_state(di_bp, basis_bp);
     let rp = load_rulepack_fixture();
     let view = price_view(HubId(1), CommodityId(1), &econ, &rp);
-    let expected = compute_price(DEFAULT_QUOTE_BASE, di_bp, basis_bp, &rp.pricing);
+    let expected = compute_price(DEFAULT_QUOTE_BASE, di_bp, basis_bp, &rp.pricing, None).price;
     assert_eq!(view.price_cents, expected);
     assert_eq!(
         view.price_cents.as_i64() % 2,
@@ -86,7 +106,7 @@ fn floor_applied_to_final_price() {
     let basis_bp = BasisBp(1_111);
     let econ = setup_state(di_bp, basis_bp);
     let view = price_view(HubId(1), CommodityId(1), &econ, &rp);
-    let expected = compute_price(DEFAULT_QUOTE_BASE, di_bp, basis_bp, &rp.pricing);
+    let expected = compute_price(DEFAULT_QUOTE_BASE, di_bp, basis_bp, &rp.pricing, None).price;
     assert_eq!(view.price_cents, expected);
     // Floor ensures we never exceed theoretical value even if rounding nudges up
     let base_cents = DEFAULT_QUOTE_BASE.as_i64();
@@ -103,3 +123,62 @@ fn floor_applied_to_final_price() {
     let floored = theoretical / 10;
     assert!(i64::from(view.price_cents) <= floored as i64);
 }
+
+#[test]
+fn commodity_bases_scale_prices_proportionally() {
+    let rp = load_rulepack_fixture();
+    let catalog = priced_catalog(&[(1, Some(10_000)), (2, Some(30_000)), (3, None)]);
+    let handle = CatalogHandle::new(catalog.clone());
+    let _scope = handle.scope();
+    for di in (-1_200..=1_200).step_by(150) {
+        for basis in (-1_200..=1_200).step_by(150) {
+            let mut econ = setup_state(BasisBp(di), BasisBp(basis));
+            econ.di_bp.insert(CommodityId(2), BasisBp(di));
+            econ.basis_bp
+                .insert((HubId(1), CommodityId(2)), BasisBp(basis));
+            let grain = price_view(HubId(1), CommodityId(1), &econ, &rp).price_cents;
+            let dear = price_view(HubId(1), CommodityId(2), &econ, &rp).price_cents;
+            assert_eq!(dear.as_i64(), grain.as_i64() * 3, "di={di} basis={basis}");
+            let held = price_view_with(&catalog, HubId(1), CommodityId(2), &econ, &rp);
+            assert_eq!(held.price_cents, dear);
+        }
+    }
+
+    // Specs without a base, and unknown ids, fall back to the shared base.
+    let econ = setup_state(BasisBp(0), BasisBp(0));
+    for id in [3, 9] {
+        let view = price_view(HubId(1), CommodityId(id), &econ, &rp);
+        assert_eq!(view.price_cents, DEFAULT_QUOTE_BASE, "commodity {id}");
+    }
+}
+
+#[test]
+fn odd_bases_stay_in_whole_cents_through_the_multiplier() {
+    let rp = load_rulepack_fixture();
+    let base = MoneyCents(24_001);
+    let catalog = priced_catalog(&[(1, Some(base.as_i64()))]);
+    for di in (-4_000..=4_000).step_by(125) {
+        for basis in (-4_000..=4_000).step_by(125) {
+            let di_bp = BasisBp(di);
+            let basis_bp = BasisBp(basis);
+            let econ = setup_state(di_bp, basis_bp);
+            let view = price_view_with(&catalog, HubId(1), CommodityId(1), &econ, &rp);
+            assert_eq!(
+                view.price_cents,
+                compute_price(base, di_bp, basis_bp, &rp.pricing, None).price,
+                "di={di} basis={basis}"
+            );
+
+            // Within a cent of the exact product, in either direction.
+            let drivers = i64::from(di) + i64::from(basis);
+            let multiplier = 10_000
+                + drivers.clamp(
+                    i64::from(rp.pricing.min_multiplier_bp),
+                    i64::from(rp.pricing.max_multiplier_bp),
+                );
+            let exact = i128::from(base.as_i64()) * i128::from(multiplier);
+            let quoted = i128::from(view.price_cents.as_i64()) * 10_000;
+            assert!((quoted - exact).abs() < 10_000, "di={di} basis={basis}");
+        }
+    }
+}
@@ -0,0 +1,137 @@
+use crate::systems::economy::rulepack::load_rulepack;
+use crate::systems::economy::{BasisBp, CommodityId, EconState, EconomyDay, HubId, MoneyCents};
+use crate::systems::trading::engine::{execute_trade, reverse_trade, TradeKind, TradeTx};
+use crate::systems::trading::inventory::Cargo;
+use crate::systems::trading::ledger::{LedgerJournal, LedgerSource, Wallet, WalletAccess};
+use crate::systems::trading::types::{CommodityCatalog, TradingConfig};
+use std::path::PathBuf;
+
+fn asset_path(relative: &str) -> PathBuf {
+    let manifest = env!("CARGO_MANIFEST_DIR");
+    PathBuf::from(manifest).join("..").join("..").join(relative)
+}
+
+fn install_globals() {
+    let path = asset_path("assets/trading/commodities.toml");
+    let catalog = CommodityCatalog::load_from_path(path.as_path()).expect("catalog");
+    CommodityCatalog::install_global(catalog);
+    TradingConfig::install_global(TradingConfig {
+        fee_bp: 75,
+        ..TradingConfig::default()
+    });
+}
+
+fn load_rulepack_fixture() -> crate::systems::economy::Rulepack {
+    let path = asset_path("assets/rulepacks/day_001.toml");
+    load_rulepack(path.to_str().expect("utf-8 path")).expect("rulepack")
+}
+
+fn setup() -> (EconState, Cargo) {
+    let mut econ = EconState {
+        day: EconomyDay(3),
+        ..EconState::default()
+    };
+    econ.di_bp.insert(CommodityId(1), BasisBp(250));
+    econ.basis_bp
+        .insert((HubId(1), CommodityId(1)), BasisBp(150));
+    let mut cargo = Cargo {
+        capacity_mass_kg: 1_000,
+        capacity_volume_l: 1_000,
+        ..Cargo::default()
+    };
+    // Older lots, so an undone buy must not trim them and an undone sale
+    // must bring back their original days.
+    cargo.load(CommodityId(1), 4, EconomyDay(1)).expect("load");
+    cargo.load(CommodityId(1), 3, EconomyDay(2)).expect("load");
+    econ.apply_trade_pressure(HubId(1), CommodityId(1), 5);
+    (econ, cargo)
+}
+
+fn round_trip(kind: TradeKind, inverse_kind: TradeKind) {
+    install_globals();
+    let rp = load_rulepack_fixture();
+    let (mut econ, mut cargo) = setup();
+    let pressure_before = econ.trade_pressure(HubId(1), CommodityId(1));
+    let cargo_before = cargo.clone();
+    let mut balance = Wallet::new(MoneyCents(5_000_000));
+    let mut journal = LedgerJournal::default();
+    let mut wallet = WalletAccess::new(&mut balance, &mut journal, 0);
+
+    let tx = TradeTx {
+        hub: HubId(1),
+        com: CommodityId(1),
+        units: 5,
+        kind,
+    };
+    let lots_before = cargo.lots[&CommodityId(1)].clone();
+    let result = execute_trade(&tx, &mut econ, &mut cargo, &mut wallet, &rp).expect("trade");
+    assert_ne!(wallet.balance(), MoneyCents(5_000_000));
+    assert!(result.fee_cents > MoneyCents::ZERO);
+
+    let inverse = TradeTx {
+        kind: inverse_kind,
+        ..tx
+    };
+    reverse_trade(
+        &inverse,
+        &result,
+        &lots_before,
+        &mut econ,
+        &mut cargo,
+        &mut wallet,
+    )
+    .expect("reverse");
+
+    assert_eq!(wallet.balance(), MoneyCents(5_000_000));
+    assert_eq!(cargo, cargo_before);
+    assert_eq!(
+        econ.trade_pressure(HubId(1), CommodityId(1)),
+        pressure_before
+    );
+    assert_eq!(journal.net_for(LedgerSource::Trade), 0);
+}
+
+#[test]
+fn reversing_a_buy_is_the_identity() {
+    round_trip(TradeKind::Buy, TradeKind::Sell);
+}
+
+#[test]
+fn reversing_a_sale_is_the_identity() {
+    round_trip(TradeKind::Sell, TradeKind::Buy);
+}
+
+#[test]
+fn reversing_a_sale_needs_the_proceeds_in_the_wallet() {
+    install_globals();
+    let rp = load_rulepack_fixture();
+    let (mut econ, mut cargo) = setup();
+    let mut balance = Wallet::new(MoneyCents(0));
+    let mut journal = LedgerJournal::default();
+    let mut wallet = WalletAccess::new(&mut balance, &mut journal, 0);
+    let tx = TradeTx {
+        hub: HubId(1),
+        com: CommodityId(1),
+        units: 2,
+        kind: TradeKind::Sell,
+    };
+    let lots_before = cargo.lots[&CommodityId(1)].clone();
+    let result = execute_trade(&tx, &mut econ, &mut cargo, &mut wallet, &rp).expect("sell");
+    wallet.debit(LedgerSource::Trade, MoneyCents(1));
+    let cargo_after = cargo.clone();
+
+    let inverse = TradeTx {
+        kind: TradeKind::Buy,
+        ..tx
+    };
+    reverse_trade(
+        &inverse,
+        &result,
+        &lots_before,
+        &mut econ,
+        &mut cargo,
+        &mut wallet,
+    )
+    .expect_err("short wallet");
+    assert_eq!(cargo, cargo_after);
+}
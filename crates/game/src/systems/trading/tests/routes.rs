@@ -0,0 +1,125 @@
+use std::collections::HashSet;
+use std::path::PathBuf;
+
+use smallvec::SmallVec;
+
+use crate::systems::economy::rulepack::load_rulepack;
+use crate::systems::economy::{
+    BasisBp, CommodityId, EconState, HubId, MoneyCents, RouteId, Rulepack, Weather,
+};
+use crate::systems::trading::engine::{TradeKind, TradeTx};
+use crate::systems::trading::inventory::Cargo;
+use crate::systems::trading::pricing_vm::preview_trade;
+use crate::systems::trading::routes::best_sell_hub;
+use crate::systems::trading::types::{CommodityCatalog, TradingConfig};
+use crate::world::index::WorldIndex;
+
+/// Hub 1 links to hubs 2, 3, 4 and 5 over routes 12, 13, 14 and 15.
+struct StarIndex;
+
+impl WorldIndex for StarIndex {
+    fn neighbors(hub: HubId) -> SmallVec<[RouteId; 6]> {
+        match hub.0 {
+            1 => [12, 13, 14, 15].into_iter().map(RouteId).collect(),
+            2..=5 => [RouteId(10 + hub.0)].into_iter().collect(),
+            _ => SmallVec::new(),
+        }
+    }
+
+    fn route_weather(_route: RouteId) -> Weather {
+        Weather::Clear
+    }
+
+    fn route_endpoints(route: RouteId) -> Option<(HubId, HubId)> {
+        (12..=15)
+            .contains(&route.0)
+            .then(|| (HubId(1), HubId(route.0 - 10)))
+    }
+
+    fn route_distance(_route: RouteId) -> u32 {
+        0
+    }
+
+    fn route_toll(_route: RouteId) -> MoneyCents {
+        MoneyCents::ZERO
+    }
+}
+
+fn asset_path(relative: &str) -> PathBuf {
+    let manifest = env!("CARGO_MANIFEST_DIR");
+    PathBuf::from(manifest).join("..").join("..").join(relative)
+}
+
+fn install_globals() {
+    let path = asset_path("assets/trading/commodities.toml");
+    let catalog = CommodityCatalog::load_from_path(path.as_path()).expect("catalog");
+    CommodityCatalog::install_global(catalog);
+    TradingConfig::install_global(TradingConfig {
+        fee_bp: 75,
+        ..TradingConfig::default()
+    });
+}
+
+fn load_rulepack_fixture() -> Rulepack {
+    let path = asset_path("assets/rulepacks/day_001.toml");
+    load_rulepack(path.to_str().expect("utf-8 path")).expect("rulepack")
+}
+
+fn econ_with_basis(basis: &[(u16, i32)]) -> EconState {
+    let mut econ = EconState::default();
+    econ.di_bp.insert(CommodityId(1), BasisBp(120));
+    for &(hub, bp) in basis {
+        econ.basis_bp
+            .insert((HubId(hub), CommodityId(1)), BasisBp(bp));
+    }
+    econ
+}
+
+fn sell_one(hub: HubId, econ: &EconState, rp: &Rulepack) -> MoneyCents {
+    let mut cargo = Cargo::default();
+    cargo.items.insert(CommodityId(1), 1);
+    let tx = TradeTx {
+        hub,
+        com: CommodityId(1),
+        units: 1,
+        kind: TradeKind::Sell,
+    };
+    preview_trade(&tx, econ, rp, &cargo, MoneyCents::ZERO).wallet_after
+}
+
+#[test]
+fn picks_highest_open_neighbour_net_of_fees() {
+    install_globals();
+    let rp = load_rulepack_fixture();
+    let econ = econ_with_basis(&[(2, -40), (3, 90), (4, 400), (5, 10)]);
+    let closed: HashSet<RouteId> = [RouteId(14)].into_iter().collect();
+
+    let best = best_sell_hub::<StarIndex>(CommodityId(1), HubId(1), &econ, &rp, &closed);
+    assert_eq!(best, Some((HubId(3), sell_one(HubId(3), &econ, &rp))));
+    let (_, proceeds) = best.unwrap();
+    assert!(proceeds > sell_one(HubId(5), &econ, &rp));
+
+    let open = best_sell_hub::<StarIndex>(CommodityId(1), HubId(1), &econ, &rp, &HashSet::new());
+    assert_eq!(open.map(|(hub, _)| hub), Some(HubId(4)));
+}
+
+#[test]
+fn ties_go_to_the_lowest_hub_and_unknown_goods_have_no_buyer() {
+    install_globals();
+    let rp = load_rulepack_fixture();
+    let econ = econ_with_basis(&[(2, 0), (3, 250), (4, 0), (5, 250)]);
+    let closed = HashSet::new();
+
+    let best = best_sell_hub::<StarIndex>(CommodityId(1), HubId(1), &econ, &rp, &closed);
+    assert_eq!(best.map(|(hub, _)| hub), Some(HubId(3)));
+
+    assert_eq!(
+        best_sell_hub::<StarIndex>(CommodityId(999), HubId(1), &econ, &rp, &closed),
+        None
+    );
+    let all_closed: HashSet<RouteId> = (12..=15).map(RouteId).collect();
+    assert_eq!(
+        best_sell_hub::<StarIndex>(CommodityId(1), HubId(1), &econ, &rp, &all_closed),
+        None
+    );
+}
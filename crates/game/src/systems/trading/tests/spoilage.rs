@@ -0,0 +1,81 @@
+use crate::systems::economy::{CommodityId, EconomyDay};
+use crate::systems::trading::inventory::{Cargo, CargoLot};
+use crate::systems::trading::types::{Commodities, CommodityCatalog};
+
+fn catalog() -> CommodityCatalog {
+    let raw = r#"
+        list = [
+            { id = 1, name = "grain", mass_kg = 10, volume_l = 8, shelf_life_days = 3 },
+            { id = 2, name = "ore", mass_kg = 25, volume_l = 15 },
+        ]
+    "#;
+    toml::from_str::<Commodities>(raw).expect("catalog").into()
+}
+
+const GRAIN: CommodityId = CommodityId(1);
+const ORE: CommodityId = CommodityId(2);
+
+#[test]
+fn units_past_shelf_life_are_removed_and_reported() {
+    let catalog = catalog();
+    let mut cargo = Cargo::default();
+    cargo.load(GRAIN, 5, EconomyDay(1)).expect("load");
+    cargo.load(GRAIN, 2, EconomyDay(3)).expect("load");
+
+    assert!(cargo.age(EconomyDay(4), &catalog).is_empty());
+    assert_eq!(cargo.units(GRAIN), 7);
+
+    assert_eq!(cargo.age(EconomyDay(5), &catalog), vec![(GRAIN, 5)]);
+    assert_eq!(cargo.units(GRAIN), 2);
+    assert_eq!(
+        cargo.lots[&GRAIN],
+        vec![CargoLot {
+            loaded_day: EconomyDay(3),
+            units: 2,
+        }]
+    );
+
+    assert_eq!(cargo.age(EconomyDay(7), &catalog), vec![(GRAIN, 2)]);
+    assert_eq!(cargo.units(GRAIN), 0);
+    assert!(!cargo.items.contains_key(&GRAIN));
+    assert!(!cargo.lots.contains_key(&GRAIN));
+}
+
+#[test]
+fn non_perishables_are_untouched() {
+    let catalog = catalog();
+    let mut cargo = Cargo::default();
+    cargo.load(ORE, 9, EconomyDay(0)).expect("load");
+
+    assert!(cargo.age(EconomyDay(10_000), &catalog).is_empty());
+    assert_eq!(cargo.units(ORE), 9);
+}
+
+#[test]
+fn selling_drains_oldest_lots_first() {
+    let catalog = catalog();
+    let mut cargo = Cargo::default();
+    cargo.load(GRAIN, 4, EconomyDay(1)).expect("load");
+    cargo.load(GRAIN, 4, EconomyDay(4)).expect("load");
+    cargo.unload(GRAIN, 5);
+
+    assert_eq!(
+        cargo.lots[&GRAIN],
+        vec![CargoLot {
+            loaded_day: EconomyDay(4),
+            units: 3,
+        }]
+    );
+    assert!(cargo.age(EconomyDay(6), &catalog).is_empty());
+}
+
+#[test]
+fn untracked_units_start_ageing_on_first_pass() {
+    let catalog = catalog();
+    let mut cargo = Cargo::default();
+    cargo.items.insert(GRAIN, 6);
+
+    assert!(cargo.age(EconomyDay(10), &catalog).is_empty());
+    assert_eq!(cargo.age(EconomyDay(14), &catalog), vec![(GRAIN, 6)]);
+    assert_eq!(cargo.units(GRAIN), 0);
+}
@@ -0,0 +1,168 @@
+use std::path::PathBuf;
+
+use crate::systems::economy::rulepack::load_rulepack;
+use crate::systems::economy::{BasisBp, CommodityId, EconState, HubId, MoneyCents, Rulepack};
+use crate::systems::trading::engine::{execute_trade, TradeKind, TradeTx};
+use crate::systems::trading::inventory::Cargo;
+use crate::systems::trading::ledger::{LedgerJournal, Wallet, WalletAccess};
+use crate::systems::trading::pricing_vm::{preview_trade, LimitReason};
+use crate::systems::trading::types::{CommodityCatalog, TradingConfig};
+
+fn asset_path(relative: &str) -> PathBuf {
+    let manifest = env!("CARGO_MANIFEST_DIR");
+    PathBuf::from(manifest).join("..").join("..").join(relative)
+}
+
+fn install_globals() {
+    let path = asset_path("assets/trading/commodities.toml");
+    let catalog = CommodityCatalog::load_from_path(path.as_path()).expect("catalog");
+    CommodityCatalog::install_global(catalog);
+    TradingConfig::install_global(TradingConfig {
+        fee_bp: 75,
+        ..TradingConfig::default()
+    });
+}
+
+fn load_rulepack_fixture() -> Rulepack {
+    let path = asset_path("assets/rulepacks/day_001.toml");
+    load_rulepack(path.to_str().expect("utf-8 path")).expect("rulepack")
+}
+
+fn setup_state() -> EconState {
+    let mut econ = EconState::default();
+    econ.di_bp.insert(CommodityId(1), BasisBp(180));
+    econ.basis_bp
+        .insert((HubId(1), CommodityId(1)), BasisBp(-35));
+    econ
+}
+
+fn starting_cargo() -> Cargo {
+    let mut cargo = Cargo {
+        capacity_mass_kg: 100,
+        capacity_volume_l: 100,
+        ..Default::default()
+    };
+    cargo.items.insert(CommodityId(1), 3);
+    cargo
+}
+
+/// Previews `tx`, runs it for real on clones, and checks both agree.
+fn assert_preview_matches(tx: TradeTx, econ: &EconState, rp: &Rulepack, wallet: MoneyCents) {
+    let cargo = starting_cargo();
+    let preview = preview_trade(&tx, econ, rp, &cargo, wallet);
+
+    let mut econ_after = econ.clone();
+    let mut cargo_after = cargo.clone();
+    let mut wallet_after = Wallet::new(wallet);
+    let mut journal = LedgerJournal::default();
+    let result = execute_trade(
+        &tx,
+        &mut econ_after,
+        &mut cargo_after,
+        &mut WalletAccess::new(&mut wallet_after, &mut journal, 0),
+        rp,
+    );
+    assert_eq!(cargo, starting_cargo(), "preview must not mutate cargo");
+
+    match result {
+        Ok(result) => {
+            assert_eq!(preview.limit, None, "{tx:?}");
+            assert_eq!(preview.unit_price, result.unit_price);
+            assert_eq!(preview.subtotal, result.subtotal);
+            assert_eq!(preview.fee, result.fee_cents);
+            assert_eq!(preview.total_cents, result.total_cents);
+            assert_eq!(preview.wallet_after, wallet_after.balance());
+            assert_eq!(
+                preview.cargo_after.units_after,
+                cargo_after.units(tx.com),
+                "{tx:?}"
+            );
+            let mass: u128 = cargo_after
+                .items
+                .values()
+                .map(|units| u128::from(*units) * 10)
+                .sum();
+            assert_eq!(preview.cargo_after.mass_kg_after, mass);
+        }
+        Err(err) => {
+            let reason = preview.limit.expect("rejected trade must carry a limit");
+            assert_eq!(err.to_string(), reason.to_string());
+            assert_eq!(wallet_after.balance(), wallet);
+            assert!(journal.entries().is_empty());
+            assert_eq!(cargo_after, cargo);
+            assert_eq!(preview.wallet_after, wallet);
+            assert_eq!(preview.cargo_after.units_after, 3);
+        }
+    }
+}
+
+#[test]
+fn preview_matches_execute_trade() {
+    install_globals();
+    let rp = load_rulepack_fixture();
+    let econ = setup_state();
+    let wallet = MoneyCents(50_000);
+
+    for units in [0, 1, 2, 5, 7, 8, 40] {
+        for kind in [TradeKind::Buy, TradeKind::Sell] {
+            let tx = TradeTx {
+                hub: HubId(1),
+                com: CommodityId(1),
+                units,
+                kind,
+            };
+            assert_preview_matches(tx, &econ, &rp, wallet);
+        }
+    }
+    // Wallet too small for three units but big enough for one.
+    let tx = TradeTx {
+        hub: HubId(1),
+        com: CommodityId(1),
+        units: 3,
+        kind: TradeKind::Buy,
+    };
+    assert_preview_matches(tx, &econ, &rp, MoneyCents(20_000));
+}
+
+#[test]
+fn preview_reports_limit_reasons() {
+    install_globals();
+    let rp = load_rulepack_fixture();
+    let econ = setup_state();
+    let cargo = starting_cargo();
+    let buy = TradeTx {
+        hub: HubId(1),
+        com: CommodityId(1),
+        units: 8,
+        kind: TradeKind::Buy,
+    };
+
+    let over_capacity = preview_trade(&buy, &econ, &rp, &cargo, MoneyCents(1_000_000));
+    assert_eq!(over_capacity.limit, Some(LimitReason::MassCapacity));
+    assert!(
+        over_capacity.total_cents.as_i64() > 0,
+        "cost is still quoted"
+    );
+    assert_eq!(over_capacity.wallet_after, MoneyCents(1_000_000));
+
+    let sell = TradeTx {
+        kind: TradeKind::Sell,
+        units: 4,
+        ..buy
+    };
+    let preview = preview_trade(&sell, &econ, &rp, &cargo, MoneyCents(0));
+    assert_eq!(
+        preview.limit,
+        Some(LimitReason::InsufficientUnits { held: 3 })
+    );
+
+    let unknown = TradeTx {
+        com: CommodityId(99),
+        ..buy
+    };
+    let preview = preview_trade(&unknown, &econ, &rp, &cargo, MoneyCents(0));
+    assert_eq!(
+        preview.limit,
+        Some(LimitReason::UnknownCommodity(CommodityId(99)))
+    );
+}
@@ -1,15 +1,24 @@
-use std::collections::HashMap;
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
 use std::path::Path;
-use std::sync::{Arc, Mutex, OnceLock};
+use std::sync::{Arc, Mutex, Once, OnceLock};
 
 use anyhow::Context;
 use bevy::prelude::Resource;
 use serde::Deserialize;
+use thiserror::Error;
 
-use crate::systems::economy::CommodityId;
+use crate::systems::economy::{CommodityId, MoneyCents};
+
+use super::catalog::CatalogHandle;
+use super::pricing_vm::DEFAULT_QUOTE_BASE;
 
-static GLOBAL_CATALOG: OnceLock<Mutex<Arc<CommodityCatalog>>> = OnceLock::new();
 static GLOBAL_TRADING_CONFIG: OnceLock<Mutex<Arc<TradingConfig>>> = OnceLock::new();
+static MISSING_BASE_PRICE_WARNING: Once = Once::new();
+
+thread_local! {
+    static SCOPED_TRADING_CONFIG: RefCell<Option<Arc<TradingConfig>>> = const { RefCell::new(None) };
+}
 
 #[derive(Debug, Clone, Deserialize)]
 #[serde(deny_unknown_fields)]
@@ -18,6 +27,19 @@ pub struct CommoditySpec {
     pub name: String,
     pub mass_kg: u16,
     pub volume_l: u16,
+    /// Days a unit keeps in the hold before it spoils; `None` never spoils.
+    #[serde(default)]
+    pub shelf_life_days: Option<u32>,
+    /// Quote price before DI and basis. Optional only while older catalogs
+    /// are phased out; specs without it price from [`DEFAULT_QUOTE_BASE`].
+    #[serde(default)]
+    pub base_price_cents: Option<i64>,
+}
+
+impl CommoditySpec {
+    pub fn base_price(&self) -> MoneyCents {
+        self.base_price_cents.map_or(DEFAULT_QUOTE_BASE, MoneyCents)
+    }
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -26,6 +48,89 @@ pub struct Commodities {
     pub list: Vec<CommoditySpec>,
 }
 
+/// Why [`Commodities::validate`] rejected a catalog. Reports the first
+/// offending spec in list order.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum CatalogError {
+    #[error("catalog lists no commodities")]
+    Empty,
+    #[error("commodity id {} is listed twice", .0.0)]
+    DuplicateId(CommodityId),
+    #[error("commodity {} has an empty name", .0.0)]
+    EmptyName(CommodityId),
+    #[error("commodity name {0:?} is listed twice")]
+    DuplicateName(String),
+    #[error("commodity {} needs a positive mass_kg", .0.0)]
+    ZeroMass(CommodityId),
+    #[error("commodity {} needs a positive volume_l", .0.0)]
+    ZeroVolume(CommodityId),
+    #[error("commodity {} needs a positive base_price_cents", .0.0)]
+    NonPositiveBasePrice(CommodityId),
+}
+
+impl Commodities {
+    /// Rejects empty catalogs, repeated ids or names, empty names, zero mass
+    /// or volume, and non-positive base prices.
+    pub fn validate(&self) -> Result<(), CatalogError> {
+        if self.list.is_empty() {
+            return Err(CatalogError::Empty);
+        }
+        let mut ids = HashSet::new();
+        let mut names = HashSet::new();
+        for spec in &self.list {
+            if !ids.insert(spec.id) {
+                return Err(CatalogError::DuplicateId(spec.id));
+            }
+            if spec.name.is_empty() {
+                return Err(CatalogError::EmptyName(spec.id));
+            }
+            if !names.insert(spec.name.as_str()) {
+                return Err(CatalogError::DuplicateName(spec.name.clone()));
+            }
+            if spec.mass_kg == 0 {
+                return Err(CatalogError::ZeroMass(spec.id));
+            }
+            if spec.volume_l == 0 {
+                return Err(CatalogError::ZeroVolume(spec.id));
+            }
+            if spec.base_price_cents.is_some_and(|cents| cents <= 0) {
+                return Err(CatalogError::NonPositiveBasePrice(spec.id));
+            }
+        }
+        Ok(())
+    }
+
+    /// Base price of `id`, or [`DEFAULT_QUOTE_BASE`] if it is not listed or
+    /// has no `base_price_cents`.
+    pub fn base_price(&self, id: CommodityId) -> MoneyCents {
+        self.list
+            .iter()
+            .find(|spec| spec.id == id)
+            .map_or(DEFAULT_QUOTE_BASE, CommoditySpec::base_price)
+    }
+
+    /// Logs, once per process, the specs still missing `base_price_cents`.
+    pub(crate) fn warn_missing_base_prices(&self, path: &Path) {
+        let missing: Vec<&str> = self
+            .list
+            .iter()
+            .filter(|spec| spec.base_price_cents.is_none())
+            .map(|spec| spec.name.as_str())
+            .collect();
+        if missing.is_empty() {
+            return;
+        }
+        MISSING_BASE_PRICE_WARNING.call_once(|| {
+            log::warn!(
+                "{} has no base_price_cents for {}; pricing them from the shared {}-cent base",
+                path.display(),
+                missing.join(", "),
+                DEFAULT_QUOTE_BASE.as_i64()
+            );
+        });
+    }
+}
+
 #[derive(Debug, Clone, Resource)]
 pub struct CommodityCatalog {
     list: Vec<CommoditySpec>,
@@ -51,6 +156,10 @@ impl CommodityCatalog {
             std::fs::read_to_string(path).with_context(|| format!("reading {}", path.display()))?;
         let parsed: Commodities =
             toml::from_str(&raw).with_context(|| format!("parsing {}", path.display()))?;
+        parsed
+            .validate()
+            .with_context(|| format!("validating {}", path.display()))?;
+        parsed.warn_missing_base_prices(path);
         Ok(parsed.into())
     }
 
@@ -61,29 +170,102 @@ impl CommodityCatalog {
     pub fn get(&self, id: CommodityId) -> Option<&CommoditySpec> {
         self.by_id.get(&id)
     }
+
+    /// Base price of `id`, or [`DEFAULT_QUOTE_BASE`] if it is not in the
+    /// catalog or has no `base_price_cents`.
+    pub fn base_price(&self, id: CommodityId) -> MoneyCents {
+        self.get(id)
+            .map_or(DEFAULT_QUOTE_BASE, CommoditySpec::base_price)
+    }
+
+    /// Hash of every spec in list order, for telling catalogs apart.
+    pub fn content_hash(&self) -> u64 {
+        let mut bytes = Vec::new();
+        for spec in &self.list {
+            bytes.extend_from_slice(&spec.id.0.to_le_bytes());
+            bytes.extend_from_slice(&(spec.name.len() as u64).to_le_bytes());
+            bytes.extend_from_slice(spec.name.as_bytes());
+            bytes.extend_from_slice(&spec.mass_kg.to_le_bytes());
+            bytes.extend_from_slice(&spec.volume_l.to_le_bytes());
+            bytes.extend_from_slice(&spec.shelf_life_days.unwrap_or(u32::MAX).to_le_bytes());
+            bytes.extend_from_slice(&spec.base_price_cents.unwrap_or(-1).to_le_bytes());
+        }
+        wyhash::wyhash(&bytes, 0)
+    }
 }
 
 impl CommodityCatalog {
+    /// Installs `catalog` into [`CatalogHandle::global`].
     pub fn install_global(catalog: CommodityCatalog) {
-        let lock = GLOBAL_CATALOG.get_or_init(|| Mutex::new(Arc::new(catalog.clone())));
-        let mut guard = lock.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
-        *guard = Arc::new(catalog);
+        CatalogHandle::global().install(catalog);
     }
 
+    /// Snapshot of the catalog installed in [`CatalogHandle::global`].
     pub fn global() -> Arc<CommodityCatalog> {
-        GLOBAL_CATALOG
-            .get()
-            .expect("commodity catalog not installed before trade execution")
-            .lock()
-            .unwrap_or_else(|poisoned| poisoned.into_inner())
-            .clone()
+        CatalogHandle::global().current()
+    }
+}
+
+/// How a trade fee that lands between two cents is rounded.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+pub enum RoundingMode {
+    /// Nearest cent, ties to the even cent: 1.5 -> 2, 4.5 -> 4.
+    BankersHalfEven,
+    /// Down to the cent below: 1.5 -> 1, 4.5 -> 4.
+    #[default]
+    Floor,
+    /// Up to the cent above: 1.5 -> 2, 4.5 -> 5.
+    Ceil,
+}
+
+impl RoundingMode {
+    /// `numerator / denominator` rounded to a whole number under this mode.
+    /// `denominator` must be positive.
+    pub fn divide(self, numerator: i128, denominator: i128) -> i128 {
+        let floor = numerator.div_euclid(denominator);
+        let remainder = numerator.rem_euclid(denominator);
+        if remainder == 0 {
+            return floor;
+        }
+        let round_up = match self {
+            Self::Floor => false,
+            Self::Ceil => true,
+            Self::BankersHalfEven => match (remainder * 2).cmp(&denominator) {
+                std::cmp::Ordering::Less => false,
+                std::cmp::Ordering::Greater => true,
+                std::cmp::Ordering::Equal => floor & 1 != 0,
+            },
+        };
+        floor + i128::from(round_up)
     }
 }
 
-#[derive(Debug, Clone, Deserialize, Resource)]
+#[derive(Debug, Clone, Default, Deserialize, Resource)]
 #[serde(deny_unknown_fields)]
 pub struct TradingConfig {
     pub fee_bp: i32,
+    /// Rounding for fees that land on a fraction of a cent.
+    #[serde(default)]
+    pub rounding: RoundingMode,
+    /// Trades costing or paying more than this ask for confirmation first.
+    #[serde(default)]
+    pub confirm_over_cents: Option<i64>,
+    /// Trades of more units than this ask for confirmation first.
+    #[serde(default)]
+    pub confirm_over_units: Option<u32>,
+}
+
+/// Restores the previously scoped trading config on drop.
+#[must_use = "the scope ends when the guard is dropped"]
+pub struct TradingConfigScope {
+    previous: Option<Arc<TradingConfig>>,
+}
+
+impl Drop for TradingConfigScope {
+    fn drop(&mut self) {
+        let previous = self.previous.take();
+        SCOPED_TRADING_CONFIG.with(|scoped| *scoped.borrow_mut() = previous);
+    }
 }
 
 impl TradingConfig {
@@ -99,7 +281,17 @@ impl TradingConfig {
         *guard = Arc::new(config);
     }
 
+    /// Makes `config` the one [`TradingConfig::global`] returns on the current
+    /// thread until the guard drops.
+    pub fn scope(config: TradingConfig) -> TradingConfigScope {
+        let previous = SCOPED_TRADING_CONFIG.with(|scoped| scoped.replace(Some(Arc::new(config))));
+        TradingConfigScope { previous }
+    }
+
     pub fn global() -> Arc<TradingConfig> {
+        if let Some(config) = SCOPED_TRADING_CONFIG.with(|scoped| scoped.borrow().clone()) {
+            return config;
+        }
         GLOBAL_TRADING_CONFIG
             .get()
             .expect("trading config not installed before trade execution")
@@ -107,6 +299,20 @@ impl TradingConfig {
             .unwrap_or_else(|poisoned| poisoned.into_inner())
             .clone()
     }
+
+    /// Whether a trade of `units` moving `total` through the wallet is large
+    /// enough to need confirmation.
+    pub fn needs_confirmation(&self, units: u32, total: MoneyCents) -> bool {
+        self.confirm_over_cents
+            .is_some_and(|limit| total.as_i64().saturating_abs() > limit)
+            || self.confirm_over_units.is_some_and(|limit| units > limit)
+    }
+
+    /// Fee in cents on `subtotal`, rounded under [`TradingConfig::rounding`].
+    pub fn fee_cents(&self, subtotal: i128) -> i128 {
+        self.rounding
+            .divide(subtotal * i128::from(self.fee_bp), 10_000)
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,215 @@
+use std::fmt::Write as _;
+use std::time::Duration;
+
+use bevy::prelude::*;
+use bevy::text::{Font, TextColor, TextFont};
+
+use crate::systems::director::{
+    danger_diff_sign, DirectorState, EconIntent, MissionRuntime, Outcome, PauseEvent, SpawnMemory,
+    Stance, ToolSlot, WheelChange, WheelEvent,
+};
+use crate::ui::styles::{COLOR_BG, COLOR_TEXT_PRIMARY};
+
+/// How often the overlay text is rebuilt by default.
+pub const DEFAULT_OVERLAY_REFRESH: Duration = Duration::from_millis(250);
+
+/// Debug panel showing what the director is doing: leg tick and status,
+/// danger trend, the last spawn budget, mission progress and pending econ
+/// deltas, plus the wheel and pause settings. Its systems only read director
+/// resources and wheel/pause events and never touch the command queue, so
+/// enabling it cannot change a recorded trace.
+pub struct DirectorDebugOverlayPlugin {
+    pub refresh: Duration,
+}
+
+impl Default for DirectorDebugOverlayPlugin {
+    fn default() -> Self {
+        Self {
+            refresh: DEFAULT_OVERLAY_REFRESH,
+        }
+    }
+}
+
+impl Plugin for DirectorDebugOverlayPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(OverlayRefresh {
+            interval: self.refresh,
+            since_last: self.refresh,
+        })
+        .init_resource::<WheelReadout>()
+        .add_systems(Startup, spawn_director_overlay)
+        .add_systems(
+            Update,
+            (track_wheel_events, refresh_director_overlay).chain(),
+        );
+    }
+}
+
+#[derive(Resource, Debug, Clone, Copy)]
+struct OverlayRefresh {
+    interval: Duration,
+    since_last: Duration,
+}
+
+#[derive(Component)]
+pub struct DirectorOverlayText;
+
+/// The wheel and pause settings as last announced by [`WheelEvent`] and
+/// [`PauseEvent`], so the overlay never polls the director resources for them.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct WheelReadout {
+    pub stance: Stance,
+    pub tool: ToolSlot,
+    pub overwatch: bool,
+    pub move_mode: bool,
+    pub slowmo: bool,
+    pub hard_paused: bool,
+}
+
+impl WheelReadout {
+    pub fn apply_wheel(&mut self, event: &WheelEvent) {
+        match event.change {
+            WheelChange::Stance { new, .. } => self.stance = new,
+            WheelChange::Tool { new, .. } => self.tool = new,
+            WheelChange::Overwatch { new, .. } => self.overwatch = new,
+            WheelChange::MoveMode { new, .. } => self.move_mode = new,
+            WheelChange::Slowmo { new, .. } => self.slowmo = new,
+        }
+    }
+
+    pub fn apply_pause(&mut self, event: &PauseEvent) {
+        self.hard_paused = event.new;
+    }
+}
+
+fn track_wheel_events(
+    mut wheel_events: MessageReader<WheelEvent>,
+    mut pause_events: MessageReader<PauseEvent>,
+    mut readout: ResMut<WheelReadout>,
+) {
+    for event in wheel_events.read() {
+        readout.apply_wheel(event);
+    }
+    for event in pause_events.read() {
+        readout.apply_pause(event);
+    }
+}
+
+/// Renders the overlay body from director resources.
+pub fn overlay_text(
+    state: &DirectorState,
+    memory: &SpawnMemory,
+    runtime: &MissionRuntime,
+    econ: &EconIntent,
+    wheel: &WheelReadout,
+) -> String {
+    let trend = match danger_diff_sign(state.current_danger_score, state.prior_danger_score) {
+        1 => "↑",
+        -1 => "↓",
+        _ => "→",
+    };
+    let mut text = format!(
+        "tick {}  {:?}\ndanger {} {trend}\n",
+        state.leg_tick, state.status, state.current_danger_score
+    );
+    match memory.last_budget {
+        Some(budget) => {
+            let _ = writeln!(
+                text,
+                "budget {} enemies, {} obstacles",
+                budget.enemies, budget.obstacles
+            );
+        }
+        None => text.push_str("budget -\n"),
+    }
+    for (name, progress) in runtime.progress() {
+        let outcome = match progress.outcome {
+            None => "running",
+            Some(Outcome::Success) => "success",
+            Some(Outcome::Failure) => "failure",
+        };
+        let _ = writeln!(
+            text,
+            "{name} {}/{} {outcome}",
+            progress.elapsed, progress.target
+        );
+    }
+    let _ = writeln!(
+        text,
+        "pending pp {:+} basis {:+} bp",
+        econ.pending_pp_delta, econ.pending_basis_overlay_bp
+    );
+    let flag = |on: bool| if on { "on" } else { "off" };
+    let _ = write!(
+        text,
+        "wheel {:?}/{:?} overwatch {} move {} slowmo {}{}",
+        wheel.stance,
+        wheel.tool,
+        flag(wheel.overwatch),
+        flag(wheel.move_mode),
+        flag(wheel.slowmo),
+        if wheel.hard_paused { "  PAUSED" } else { "" }
+    );
+    text
+}
+
+fn spawn_director_overlay(mut commands: Commands, asset_server: Option<Res<AssetServer>>) {
+    let font = asset_server
+        .as_ref()
+        .map(|server| server.load::<Font>("fonts/inter-regular.ttf"))
+        .unwrap_or_default();
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                left: Val::Px(24.0),
+                bottom: Val::Px(24.0),
+                padding: UiRect::axes(Val::Px(12.0), Val::Px(8.0)),
+                ..default()
+            },
+            BackgroundColor(COLOR_BG),
+            BorderRadius::all(Val::Px(8.0)),
+            Name::new("DirectorDebugOverlay"),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(""),
+                TextFont {
+                    font,
+                    font_size: 13.0,
+                    ..default()
+                },
+                TextColor(COLOR_TEXT_PRIMARY),
+                DirectorOverlayText,
+            ));
+        });
+}
+
+#[allow(clippy::too_many_arguments)]
+fn refresh_director_overlay(
+    time: Res<Time>,
+    mut refresh: ResMut<OverlayRefresh>,
+    state: Option<Res<DirectorState>>,
+    memory: Option<Res<SpawnMemory>>,
+    runtime: Option<Res<MissionRuntime>>,
+    econ: Option<Res<EconIntent>>,
+    wheel: Res<WheelReadout>,
+    mut texts: Query<&mut Text, With<DirectorOverlayText>>,
+) {
+    refresh.since_last = refresh.since_last.saturating_add(time.delta());
+    if refresh.since_last < refresh.interval {
+        return;
+    }
+    refresh.since_last = Duration::ZERO;
+
+    let (Some(state), Some(memory), Some(runtime), Some(econ)) = (state, memory, runtime, econ)
+    else {
+        return;
+    };
+    let body = overlay_text(&state, &memory, &runtime, &econ, &wheel);
+    for mut text in &mut texts {
+        if text.0 != body {
+            text.0.clone_from(&body);
+        }
+    }
+}
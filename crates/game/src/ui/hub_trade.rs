@@ -1,4 +1,5 @@
 use std::collections::HashMap;
+use std::sync::Arc;
 
 use anyhow::Result;
 use bevy::ecs::hierarchy::ChildSpawnerCommands;
@@ -9,19 +10,29 @@ use bevy::ui::UiRect;
 use crate::app_state::AppState;
 use crate::systems::command_queue::CommandQueue;
 use crate::systems::economy::{
-    BasisBp, CommodityId, EconState, EconomyDay, HubId, MoneyCents, Rulepack,
+    BasisBp, ClampSide, CommodityId, EconState, EconomyDay, HubId, MoneyCents, Rulepack,
 };
-use crate::systems::trading::engine::{execute_trade, TradeKind, TradeResult, TradeTx};
-use crate::systems::trading::inventory::Cargo;
-use crate::systems::trading::pricing_vm::{price_view, TradingDrivers};
+use crate::systems::trading::engine::{
+    execute_trade, max_affordable_units, reverse_trade, TradeKind, TradeResult, TradeTx,
+};
+use crate::systems::trading::inventory::{Cargo, CargoLot};
+use crate::systems::trading::ledger::{LedgerJournal, WalletAccess};
+use crate::systems::trading::pricing_vm::{preview_trade_with, price_view_with, TradingDrivers};
 use crate::systems::trading::types::{CommodityCatalog, TradingConfig};
+use crate::ui::i18n::{tr, Strings};
 use crate::ui::styles::{
-    COLOR_ACCENT_NEG, COLOR_ACCENT_POS, COLOR_BG, COLOR_TEXT_PRIMARY, COLOR_TEXT_SECONDARY,
+    COLOR_ACCENT_NEG, COLOR_ACCENT_POS, COLOR_BG, COLOR_FOCUS, COLOR_TEXT_PRIMARY,
+    COLOR_TEXT_SECONDARY,
 };
 
 type ButtonInteractionFilter = (Changed<Interaction>, With<Button>);
 type StepperInteraction<'w> = (&'w Interaction, &'w StepperButton);
-type TradeInteraction<'w> = (&'w Interaction, &'w TradeButton);
+type TradeInteraction<'w> = (Entity, &'w Interaction);
+type TradeButtonFilter = (
+    Changed<Interaction>,
+    Or<(With<TradeButton>, With<BulkTradeButton>)>,
+);
+type RebuiltRowFilter = Or<(With<CommodityRowUi>, With<ManifestEntry>)>;
 type UiTextParamSet<'w, 's> = ParamSet<
     'w,
     's,
@@ -29,6 +40,8 @@ type UiTextParamSet<'w, 's> = ParamSet<
         Query<'w, 's, &'static mut Text, With<TickerText>>,
         Query<'w, 's, &'static mut Text, With<WalletText>>,
         Query<'w, 's, &'static mut Text, With<CargoSummaryText>>,
+        Query<'w, 's, (&'static mut Text, &'static mut TextColor), With<ManifestMassText>>,
+        Query<'w, 's, (&'static mut Text, &'static mut TextColor), With<ManifestVolumeText>>,
     ),
 >;
 
@@ -37,6 +50,137 @@ struct UiTextQueries<'w, 's> {
     sets: UiTextParamSet<'w, 's>,
 }
 
+/// Everything a trade button press touches, shared by pointer and keyboard
+/// activation.
+#[derive(SystemParam)]
+struct TradeContext<'w, 's> {
+    buttons: Query<
+        'w,
+        's,
+        (
+            Option<&'static TradeButton>,
+            Option<&'static BulkTradeButton>,
+        ),
+    >,
+    model: ResMut<'w, HubTradeUiModel>,
+    ui_state: ResMut<'w, HubTradeUiState>,
+    queue: ResMut<'w, CommandQueue>,
+    app_state: ResMut<'w, AppState>,
+    journal: ResMut<'w, LedgerJournal>,
+    rp: Res<'w, Rulepack>,
+    pending: ResMut<'w, PendingConfirmation>,
+    undo: ResMut<'w, UndoStack>,
+    price_cache: ResMut<'w, PriceViewCache>,
+}
+
+/// A trade over the confirmation thresholds, held until the player confirms
+/// or cancels it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PendingTrade {
+    pub commodity: CommodityId,
+    pub kind: TradeKind,
+    /// Buy max / sell all rather than the stepper amount.
+    pub bulk: bool,
+    pub units: u32,
+    pub total_cents: MoneyCents,
+}
+
+/// The trade awaiting confirmation, if any. Trade buttons do nothing while
+/// one is pending.
+#[derive(Resource, Debug, Default)]
+pub struct PendingConfirmation {
+    trade: Option<PendingTrade>,
+}
+
+impl PendingConfirmation {
+    pub fn trade(&self) -> Option<PendingTrade> {
+        self.trade
+    }
+}
+
+/// The last trade, kept so it can be undone at the same prices.
+#[derive(Debug, Clone)]
+pub struct UndoEntry {
+    pub inverse: TradeTx,
+    pub result: TradeResult,
+    pub lots_before: Vec<CargoLot>,
+    pub day: EconomyDay,
+}
+
+/// One level of trade undo. A new trade replaces the entry, and it lapses
+/// once the player is at another hub or the day has moved on.
+#[derive(Resource, Debug, Default)]
+pub struct UndoStack {
+    entry: Option<UndoEntry>,
+}
+
+impl UndoStack {
+    pub fn entry(&self) -> Option<&UndoEntry> {
+        self.entry.as_ref()
+    }
+}
+
+/// A keyboard-focusable widget, named by what it does rather than by its
+/// entity so focus survives row rebuilds.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FocusTarget {
+    Stepper {
+        commodity: CommodityId,
+        delta: i32,
+    },
+    Trade {
+        commodity: CommodityId,
+        kind: TradeKind,
+    },
+}
+
+/// The focused hub trade widget. `entity` is re-resolved from `target`
+/// whenever the rows are rebuilt.
+#[derive(Resource, Debug, Default)]
+pub struct FocusRing {
+    target: Option<FocusTarget>,
+    entity: Option<Entity>,
+}
+
+impl FocusRing {
+    pub fn target(&self) -> Option<FocusTarget> {
+        self.target
+    }
+
+    pub fn entity(&self) -> Option<Entity> {
+        self.entity
+    }
+}
+
+/// Tab order for `view`: each commodity row top to bottom, and within a row
+/// stepper −, stepper +, Buy, Sell.
+pub fn focus_order(view: &HubTradeView) -> Vec<FocusTarget> {
+    view.commodities
+        .iter()
+        .flat_map(|row| {
+            let commodity = row.id;
+            [
+                FocusTarget::Stepper {
+                    commodity,
+                    delta: -1,
+                },
+                FocusTarget::Stepper {
+                    commodity,
+                    delta: 1,
+                },
+                FocusTarget::Trade {
+                    commodity,
+                    kind: TradeKind::Buy,
+                },
+                FocusTarget::Trade {
+                    commodity,
+                    kind: TradeKind::Sell,
+                },
+            ]
+        })
+        .collect()
+}
+
 #[derive(Resource, Default)]
 pub struct HubTradeUiState {
     pub last_view: Option<HubTradeView>,
@@ -55,11 +199,28 @@ impl Plugin for HubTradePlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<HubTradeUiState>()
             .init_resource::<HubTradeUiModel>()
+            .init_resource::<LedgerJournal>()
+            .init_resource::<Strings>()
+            .init_resource::<FocusRing>()
+            .init_resource::<PendingConfirmation>()
+            .init_resource::<UndoStack>()
+            .init_resource::<PriceViewCache>()
             .add_systems(Update, refresh_hub_trade_view)
             .add_systems(Startup, setup_hub_trade_ui)
             .add_systems(Update, apply_hub_trade_view)
             .add_systems(Update, handle_stepper_buttons)
-            .add_systems(Update, handle_trade_buttons);
+            .add_systems(Update, handle_manifest_toggle)
+            .add_systems(Update, handle_trade_buttons)
+            .add_systems(Update, handle_focus_keys)
+            .add_systems(Update, handle_confirmation_buttons)
+            .add_systems(Update, handle_undo_button)
+            .add_systems(Update, sync_confirmation_modal)
+            .add_systems(
+                Update,
+                apply_focus_highlight
+                    .after(apply_hub_trade_view)
+                    .after(handle_focus_keys),
+            );
     }
 }
 
@@ -105,19 +266,28 @@ pub struct CommodityRow {
     pub di_bp: BasisBp,
     pub basis_bp: BasisBp,
     pub price_cents: MoneyCents,
+    pub clamped: Option<ClampSide>,
     pub drivers: TradingDrivers,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct CargoItemRow {
     pub commodity: CommodityId,
+    pub name: String,
     pub units: u32,
+    pub unit_mass_kg: u16,
+    pub unit_volume_l: u16,
+    /// What selling every unit at this hub would pay, after the fee; zero
+    /// when the sale would be rejected.
+    pub sell_value_cents: MoneyCents,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct CargoSummary {
     pub capacity_mass_kg: u32,
     pub capacity_volume_l: u32,
+    pub mass_kg: u64,
+    pub volume_l: u64,
     pub items: Vec<CargoItemRow>,
 }
 
@@ -133,55 +303,147 @@ pub struct HubTradeView {
     pub fee_bp: i32,
 }
 
+/// Commodity rows of [`HubTradeView`], which only move when prices do.
+/// Entries are keyed by hub and day and dropped together once the economy's
+/// [`revision`](EconState::revision) changes or another catalog is installed.
+/// Nothing here is saved.
+#[derive(Resource, Debug, Default)]
+pub struct PriceViewCache {
+    revision: u64,
+    catalog: Option<Arc<CommodityCatalog>>,
+    rows: HashMap<(HubId, EconomyDay), Arc<[CommodityRow]>>,
+    hits: u64,
+    misses: u64,
+}
+
+impl PriceViewCache {
+    /// Rebuilds served from the cache.
+    pub fn hits(&self) -> u64 {
+        self.hits
+    }
+
+    /// Rebuilds that had to price every commodity.
+    pub fn misses(&self) -> u64 {
+        self.misses
+    }
+
+    /// Drops every entry, e.g. after the rulepack is swapped.
+    pub fn clear(&mut self) {
+        self.rows.clear();
+        self.catalog = None;
+    }
+
+    fn rows(
+        &mut self,
+        catalog: &Arc<CommodityCatalog>,
+        hub: HubId,
+        econ: &EconState,
+        rp: &Rulepack,
+    ) -> Arc<[CommodityRow]> {
+        let same_catalog = self
+            .catalog
+            .as_ref()
+            .is_some_and(|cached| Arc::ptr_eq(cached, catalog));
+        if self.revision != econ.revision || !same_catalog {
+            self.rows.clear();
+            self.revision = econ.revision;
+            self.catalog = Some(Arc::clone(catalog));
+        }
+        if let Some(rows) = self.rows.get(&(hub, econ.day)) {
+            self.hits += 1;
+            return Arc::clone(rows);
+        }
+        self.misses += 1;
+        let rows: Arc<[CommodityRow]> = price_rows(catalog, hub, econ, rp).into();
+        self.rows.insert((hub, econ.day), Arc::clone(&rows));
+        rows
+    }
+}
+
+fn price_rows(
+    catalog: &CommodityCatalog,
+    hub: HubId,
+    econ: &EconState,
+    rp: &Rulepack,
+) -> Vec<CommodityRow> {
+    catalog
+        .list()
+        .iter()
+        .map(|spec| {
+            let view = price_view_with(catalog, hub, spec.id, econ, rp);
+            CommodityRow {
+                id: spec.id,
+                name: spec.name.clone(),
+                di_bp: view.di_bp,
+                basis_bp: view.basis_bp,
+                price_cents: view.price_cents,
+                clamped: view.clamped,
+                drivers: view.drivers,
+            }
+        })
+        .collect()
+}
+
+/// Builds the hub view from scratch; see [`build_view_cached`].
 pub fn build_view(
     hub: HubId,
     econ: &EconState,
     rp: &Rulepack,
     cargo: &Cargo,
     wallet: MoneyCents,
+) -> HubTradeView {
+    build_view_cached(hub, econ, rp, cargo, wallet, &mut PriceViewCache::default())
+}
+
+/// Builds the hub view, taking commodity prices from `cache` when they are
+/// still current. Only the cargo and wallet parts are recomputed then.
+pub fn build_view_cached(
+    hub: HubId,
+    econ: &EconState,
+    rp: &Rulepack,
+    cargo: &Cargo,
+    wallet: MoneyCents,
+    cache: &mut PriceViewCache,
 ) -> HubTradeView {
     let catalog = CommodityCatalog::global();
-    let di_cfg = &rp.di;
-    let basis_cfg = &rp.basis;
-    let mut di_total: i64 = 0;
-    let mut commodities: Vec<CommodityRow> = Vec::with_capacity(catalog.list().len());
-    let mut clamp_hit = false;
-    if econ.di_overlay_bp.abs() >= rp.di.per_day_clamp_bp
-        || econ.di_overlay_bp <= rp.di.overlay_min_bp
-        || econ.di_overlay_bp >= rp.di.overlay_max_bp
-    {
-        clamp_hit = true;
-    }
-    for spec in catalog.list() {
-        let view = price_view(hub, spec.id, econ, rp);
-        di_total += i64::from(view.di_bp.0);
-        if view.di_bp.0 <= di_cfg.absolute_min_bp || view.di_bp.0 >= di_cfg.absolute_max_bp {
-            clamp_hit = true;
-        }
-        if view.basis_bp.0 <= basis_cfg.absolute_min_bp
-            || view.basis_bp.0 >= basis_cfg.absolute_max_bp
-        {
-            clamp_hit = true;
-        }
-        commodities.push(CommodityRow {
-            id: spec.id,
-            name: spec.name.clone(),
-            di_bp: view.di_bp,
-            basis_bp: view.basis_bp,
-            price_cents: view.price_cents,
-            drivers: view.drivers,
-        });
-    }
+    let commodities = cache.rows(&catalog, hub, econ, rp).to_vec();
+    let di_total: i64 = commodities.iter().map(|row| i64::from(row.di_bp.0)).sum();
+    let clamp_hit = commodities.iter().any(|row| row.clamped.is_some());
 
     let mut cargo_items: Vec<CargoItemRow> = cargo
         .items
         .iter()
-        .map(|(commodity, units)| CargoItemRow {
-            commodity: *commodity,
-            units: *units,
+        .map(|(commodity, units)| {
+            let spec = catalog.get(*commodity);
+            let tx = TradeTx {
+                hub,
+                com: *commodity,
+                units: *units,
+                kind: TradeKind::Sell,
+            };
+            let preview = preview_trade_with(&catalog, &tx, econ, rp, cargo, wallet);
+            CargoItemRow {
+                commodity: *commodity,
+                name: spec.map_or_else(|| format!("#{}", commodity.0), |spec| spec.name.clone()),
+                units: *units,
+                unit_mass_kg: spec.map_or(0, |spec| spec.mass_kg),
+                unit_volume_l: spec.map_or(0, |spec| spec.volume_l),
+                sell_value_cents: match preview.limit {
+                    None => MoneyCents(preview.total_cents.as_i64().saturating_neg()),
+                    Some(_) => MoneyCents::ZERO,
+                },
+            }
         })
         .collect();
     cargo_items.sort_by_key(|row| row.commodity.0);
+    let (mass_kg, volume_l) = cargo_items
+        .iter()
+        .fold((0u64, 0u64), |(mass, volume), row| {
+            (
+                mass.saturating_add(u64::from(row.unit_mass_kg) * u64::from(row.units)),
+                volume.saturating_add(u64::from(row.unit_volume_l) * u64::from(row.units)),
+            )
+        });
 
     let fee_bp = TradingConfig::global().fee_bp;
     let di_bp = if commodities.is_empty() {
@@ -200,6 +462,8 @@ pub fn build_view(
         cargo: CargoSummary {
             capacity_mass_kg: cargo.capacity_mass_kg,
             capacity_volume_l: cargo.capacity_volume_l,
+            mass_kg,
+            volume_l,
             items: cargo_items,
         },
         wallet_cents: wallet,
@@ -213,9 +477,9 @@ impl HubTradeActions {
     pub fn buy(
         queue: &mut CommandQueue,
         tx: TradeTx,
-        econ: &EconState,
+        econ: &mut EconState,
         cargo: &mut Cargo,
-        wallet: &mut MoneyCents,
+        wallet: &mut WalletAccess<'_>,
         rp: &Rulepack,
     ) -> Result<TradeResult> {
         debug_assert!(matches!(tx.kind, TradeKind::Buy));
@@ -226,47 +490,118 @@ impl HubTradeActions {
     pub fn sell(
         queue: &mut CommandQueue,
         tx: TradeTx,
-        econ: &EconState,
+        econ: &mut EconState,
         cargo: &mut Cargo,
-        wallet: &mut MoneyCents,
+        wallet: &mut WalletAccess<'_>,
         rp: &Rulepack,
     ) -> Result<TradeResult> {
         debug_assert!(matches!(tx.kind, TradeKind::Sell));
         queue.meter_units("ui_click_sell", tx.units);
         execute_trade(&tx, econ, cargo, wallet, rp)
     }
+
+    /// Every unit of `com` currently held.
+    pub fn max_sellable(cargo: &Cargo, com: CommodityId) -> u32 {
+        cargo.units(com)
+    }
+
+    /// Largest purchase of `com` that fits the wallet (fees included) and the
+    /// remaining cargo capacity.
+    pub fn max_affordable(
+        hub: HubId,
+        com: CommodityId,
+        econ: &EconState,
+        cargo: &Cargo,
+        wallet: MoneyCents,
+        rp: &Rulepack,
+    ) -> Result<u32> {
+        max_affordable_units(hub, com, econ, cargo, wallet, rp)
+    }
+
+    pub fn sell_all(
+        queue: &mut CommandQueue,
+        hub: HubId,
+        com: CommodityId,
+        econ: &mut EconState,
+        cargo: &mut Cargo,
+        wallet: &mut WalletAccess<'_>,
+        rp: &Rulepack,
+    ) -> Result<TradeResult> {
+        let units = Self::max_sellable(cargo, com);
+        queue.meter_units("ui_click_sell_all", units);
+        let tx = TradeTx {
+            hub,
+            com,
+            units,
+            kind: TradeKind::Sell,
+        };
+        execute_trade(&tx, econ, cargo, wallet, rp)
+    }
+
+    pub fn buy_max(
+        queue: &mut CommandQueue,
+        hub: HubId,
+        com: CommodityId,
+        econ: &mut EconState,
+        cargo: &mut Cargo,
+        wallet: &mut WalletAccess<'_>,
+        rp: &Rulepack,
+    ) -> Result<TradeResult> {
+        let units = Self::max_affordable(hub, com, econ, cargo, wallet.balance(), rp)?;
+        queue.meter_units("ui_click_buy_max", units);
+        let tx = TradeTx {
+            hub,
+            com,
+            units,
+            kind: TradeKind::Buy,
+        };
+        execute_trade(&tx, econ, cargo, wallet, rp)
+    }
 }
 
-fn ticker_line(view: &HubTradeView) -> String {
+fn ticker_line(view: &HubTradeView, strings: &Strings) -> String {
     let mut parts = vec![
-        format!("Hub {}", view.hub.0),
-        format!("Day {}", view.day.0),
-        format!("Avg ΔDI {}bp", view.di_bp.0),
+        tr!(strings, "hub_trade.ticker_hub", hub = view.hub.0),
+        tr!(strings, "hub_trade.ticker_day", day = view.day.0),
+        tr!(strings, "hub_trade.ticker_di", di = view.di_bp.0),
     ];
     if view.clamp_hit {
-        parts.push("Clamp active".to_string());
+        parts.push(tr!(strings, "hub_trade.ticker_clamp"));
     }
-    parts.join(" • ")
+    parts.join(strings.get("hub_trade.ticker_separator"))
 }
 
-fn wallet_line(view: &HubTradeView) -> String {
-    format!(
-        "Wallet: {}¢ (fee {}bp)",
-        view.wallet_cents.as_i64(),
-        view.fee_bp
+fn wallet_line(view: &HubTradeView, strings: &Strings) -> String {
+    tr!(
+        strings,
+        "hub_trade.wallet_line",
+        cents = view.wallet_cents.as_i64(),
+        fee = view.fee_bp,
     )
 }
 
-fn cargo_line(view: &HubTradeView) -> String {
+fn cargo_line(view: &HubTradeView, strings: &Strings) -> String {
     let total_units: u32 = view.cargo.items.iter().map(|row| row.units).sum();
-    format!(
-        "Cargo: {} units • {}kg / {}L",
-        total_units, view.cargo.capacity_mass_kg, view.cargo.capacity_volume_l
+    tr!(
+        strings,
+        "hub_trade.cargo_line",
+        units = total_units,
+        mass = view.cargo.capacity_mass_kg,
+        volume = view.cargo.capacity_volume_l,
     )
 }
 
-fn format_price(cents: MoneyCents) -> String {
-    format!("{}¢", cents.as_i64())
+/// Warning colour once a hold is more than 90% full.
+fn capacity_color(used: u64, capacity: u32) -> Color {
+    if used.saturating_mul(10) > u64::from(capacity) * 9 {
+        COLOR_ACCENT_NEG
+    } else {
+        COLOR_TEXT_SECONDARY
+    }
+}
+
+fn format_price(cents: MoneyCents, strings: &Strings) -> String {
+    tr!(strings, "hub_trade.price", cents = cents.as_i64())
 }
 
 #[derive(Component)]
@@ -295,6 +630,14 @@ pub struct TradeButton {
     kind: TradeKind,
 }
 
+/// "Buy max" / "Sell all" button: trades the largest valid quantity instead
+/// of the stepper amount.
+#[derive(Component, Clone, Copy)]
+pub struct BulkTradeButton {
+    commodity: CommodityId,
+    kind: TradeKind,
+}
+
 impl StepperButton {
     pub fn commodity(&self) -> CommodityId {
         self.commodity
@@ -315,6 +658,66 @@ impl TradeButton {
     }
 }
 
+impl BulkTradeButton {
+    pub fn commodity(&self) -> CommodityId {
+        self.commodity
+    }
+
+    pub fn kind(&self) -> TradeKind {
+        self.kind
+    }
+}
+
+/// One commodity row of the cargo manifest panel.
+#[derive(Component, Clone, Copy)]
+pub struct ManifestRow {
+    commodity: CommodityId,
+}
+
+impl ManifestRow {
+    pub fn commodity(&self) -> CommodityId {
+        self.commodity
+    }
+}
+
+/// Confirm or cancel button on the large-trade confirmation modal.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfirmationButton {
+    Confirm,
+    Cancel,
+}
+
+/// Undoes the last trade.
+#[derive(Component, Clone, Copy)]
+pub struct UndoTradeButton;
+
+#[derive(Component)]
+struct ConfirmationModal;
+
+#[derive(Component)]
+struct ConfirmationPrompt;
+
+/// Marks the manifest's "sell all" buttons, which also carry a
+/// [`BulkTradeButton`].
+#[derive(Component, Clone, Copy)]
+pub struct ManifestSellAllButton;
+
+#[derive(Component)]
+struct ManifestToggle;
+
+#[derive(Component)]
+struct ManifestList;
+
+/// Anything the manifest rebuilds from the view.
+#[derive(Component)]
+struct ManifestEntry;
+
+#[derive(Component)]
+struct ManifestMassText;
+
+#[derive(Component)]
+struct ManifestVolumeText;
+
 #[derive(Component)]
 struct TickerText;
 
@@ -324,13 +727,22 @@ struct WalletText;
 #[derive(Component)]
 struct CargoSummaryText;
 
-fn setup_hub_trade_ui(mut commands: Commands) {
-    let (ticker_text, ticker_font, ticker_color) =
-        text_components("Awaiting market data", 18.0, COLOR_TEXT_PRIMARY);
-    let (wallet_text, wallet_font, wallet_color) =
-        text_components("Wallet: --", 16.0, COLOR_TEXT_PRIMARY);
-    let (cargo_text, cargo_font, cargo_color) =
-        text_components("Cargo: --", 14.0, COLOR_TEXT_SECONDARY);
+fn setup_hub_trade_ui(mut commands: Commands, strings: Res<Strings>) {
+    let (ticker_text, ticker_font, ticker_color) = text_components(
+        tr!(strings, "hub_trade.awaiting_data"),
+        18.0,
+        COLOR_TEXT_PRIMARY,
+    );
+    let (wallet_text, wallet_font, wallet_color) = text_components(
+        tr!(strings, "hub_trade.wallet_placeholder"),
+        16.0,
+        COLOR_TEXT_PRIMARY,
+    );
+    let (cargo_text, cargo_font, cargo_color) = text_components(
+        tr!(strings, "hub_trade.cargo_placeholder"),
+        14.0,
+        COLOR_TEXT_SECONDARY,
+    );
 
     commands
         .spawn((
@@ -365,6 +777,101 @@ fn setup_hub_trade_ui(mut commands: Commands) {
                 .with_children(|panel| {
                     panel.spawn((WalletText, wallet_text, wallet_font, wallet_color));
                     panel.spawn((CargoSummaryText, cargo_text, cargo_font, cargo_color));
+                    spawn_undo_button(panel, &strings);
+                    spawn_manifest_panel(panel, &strings);
+                });
+
+            spawn_confirmation_modal(root, &strings);
+        });
+}
+
+fn spawn_undo_button(parent: &mut ChildSpawnerCommands, strings: &Strings) {
+    parent
+        .spawn((
+            UndoTradeButton,
+            Button,
+            Node {
+                padding: UiRect::axes(Val::Px(8.0), Val::Px(4.0)),
+                align_self: AlignSelf::FlexStart,
+                ..Default::default()
+            },
+            BackgroundColor(COLOR_TEXT_SECONDARY.with_alpha(0.25)),
+        ))
+        .with_children(|button| {
+            let (text, font, color) =
+                text_components(tr!(strings, "hub_trade.undo"), 14.0, COLOR_TEXT_PRIMARY);
+            button.spawn((text, font, color));
+        });
+}
+
+fn spawn_confirmation_modal(parent: &mut ChildSpawnerCommands, strings: &Strings) {
+    parent
+        .spawn((
+            ConfirmationModal,
+            Node {
+                display: Display::None,
+                position_type: PositionType::Absolute,
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..Default::default()
+            },
+            BackgroundColor(COLOR_BG.with_alpha(0.8)),
+        ))
+        .with_children(|overlay| {
+            overlay
+                .spawn((
+                    Node {
+                        flex_direction: FlexDirection::Column,
+                        row_gap: Val::Px(8.0),
+                        padding: UiRect::all(Val::Px(16.0)),
+                        ..Default::default()
+                    },
+                    BackgroundColor(COLOR_BG),
+                ))
+                .with_children(|dialog| {
+                    let (text, font, color) = text_components("", 16.0, COLOR_TEXT_PRIMARY);
+                    dialog.spawn((ConfirmationPrompt, text, font, color));
+                    dialog
+                        .spawn(Node {
+                            flex_direction: FlexDirection::Row,
+                            column_gap: Val::Px(8.0),
+                            ..Default::default()
+                        })
+                        .with_children(|buttons| {
+                            for (choice, key, color) in [
+                                (
+                                    ConfirmationButton::Confirm,
+                                    "hub_trade.confirm",
+                                    COLOR_ACCENT_POS,
+                                ),
+                                (
+                                    ConfirmationButton::Cancel,
+                                    "hub_trade.cancel",
+                                    COLOR_TEXT_SECONDARY,
+                                ),
+                            ] {
+                                buttons
+                                    .spawn((
+                                        choice,
+                                        Button,
+                                        Node {
+                                            padding: UiRect::axes(Val::Px(12.0), Val::Px(6.0)),
+                                            ..Default::default()
+                                        },
+                                        BackgroundColor(color.with_alpha(0.85)),
+                                    ))
+                                    .with_children(|button| {
+                                        let (text, font, color) = text_components(
+                                            strings.get(key),
+                                            14.0,
+                                            COLOR_TEXT_PRIMARY,
+                                        );
+                                        button.spawn((text, font, color));
+                                    });
+                            }
+                        });
                 });
         });
 }
@@ -373,18 +880,23 @@ fn refresh_hub_trade_view(
     app_state: Res<AppState>,
     rp: Res<Rulepack>,
     mut model: ResMut<HubTradeUiModel>,
+    mut price_cache: ResMut<PriceViewCache>,
 ) {
-    if !app_state.is_changed() && model.view().is_some() {
+    if rp.is_changed() {
+        price_cache.clear();
+    }
+    if !app_state.is_changed() && !rp.is_changed() && model.view().is_some() {
         return;
     }
 
     let hub = app_state.last_hub;
-    let view = build_view(
+    let view = build_view_cached(
         hub,
         &app_state.econ,
         rp.as_ref(),
         &app_state.cargo,
-        app_state.wallet,
+        app_state.wallet.balance(),
+        &mut price_cache,
     );
 
     if model
@@ -398,13 +910,16 @@ fn refresh_hub_trade_view(
     model.set_view(view);
 }
 
+#[allow(clippy::too_many_arguments)]
 fn apply_hub_trade_view(
     mut commands: Commands,
     mut model: ResMut<HubTradeUiModel>,
     mut ui_state: ResMut<HubTradeUiState>,
+    strings: Res<Strings>,
     mut text_queries: UiTextQueries,
     table_query: Query<Entity, With<CommodityTableRoot>>,
-    existing_rows: Query<Entity, With<CommodityRowUi>>,
+    existing_rows: Query<Entity, RebuiltRowFilter>,
+    manifest_query: Query<Entity, With<ManifestList>>,
     children_query: Query<&Children>,
 ) {
     let Some(table_entity) = table_query.iter().next() else {
@@ -421,13 +936,31 @@ fn apply_hub_trade_view(
     ui_state.remember(view.clone());
 
     if let Some(mut ticker) = text_queries.sets.p0().iter_mut().next() {
-        ticker.0 = ticker_line(&view);
+        ticker.0 = ticker_line(&view, &strings);
     }
     if let Some(mut wallet_text) = text_queries.sets.p1().iter_mut().next() {
-        wallet_text.0 = wallet_line(&view);
+        wallet_text.0 = wallet_line(&view, &strings);
     }
     if let Some(mut cargo_text) = text_queries.sets.p2().iter_mut().next() {
-        cargo_text.0 = cargo_line(&view);
+        cargo_text.0 = cargo_line(&view, &strings);
+    }
+    if let Some((mut text, mut color)) = text_queries.sets.p3().iter_mut().next() {
+        text.0 = tr!(
+            strings,
+            "hub_trade.manifest_mass",
+            used = view.cargo.mass_kg,
+            capacity = view.cargo.capacity_mass_kg,
+        );
+        color.0 = capacity_color(view.cargo.mass_kg, view.cargo.capacity_mass_kg);
+    }
+    if let Some((mut text, mut color)) = text_queries.sets.p4().iter_mut().next() {
+        text.0 = tr!(
+            strings,
+            "hub_trade.manifest_volume",
+            used = view.cargo.volume_l,
+            capacity = view.cargo.capacity_volume_l,
+        );
+        color.0 = capacity_color(view.cargo.volume_l, view.cargo.capacity_volume_l);
     }
 
     for entity in existing_rows.iter() {
@@ -438,9 +971,45 @@ fn apply_hub_trade_view(
     commands.entity(table_entity).with_children(|table| {
         for row in &view.commodities {
             let units = units_snapshot.get(&row.id).copied().unwrap_or(1);
-            spawn_commodity_row(table, row, units);
+            spawn_commodity_row(table, row, units, &strings);
         }
     });
+
+    if let Some(manifest_entity) = manifest_query.iter().next() {
+        commands.entity(manifest_entity).with_children(|list| {
+            if view.cargo.items.is_empty() {
+                let (text, font, color) = text_components(
+                    tr!(strings, "hub_trade.manifest_empty"),
+                    14.0,
+                    COLOR_TEXT_SECONDARY,
+                );
+                list.spawn((ManifestEntry, text, font, color));
+            }
+            for item in &view.cargo.items {
+                spawn_manifest_row(list, item, &strings);
+            }
+        });
+    }
+}
+
+/// Shows or hides the manifest list; the totals stay visible.
+fn handle_manifest_toggle(
+    interactions: Query<&Interaction, (Changed<Interaction>, With<ManifestToggle>)>,
+    mut lists: Query<&mut Node, With<ManifestList>>,
+) {
+    let presses = interactions
+        .iter()
+        .filter(|interaction| **interaction == Interaction::Pressed)
+        .count();
+    if presses % 2 == 0 {
+        return;
+    }
+    for mut node in lists.iter_mut() {
+        node.display = match node.display {
+            Display::None => Display::Flex,
+            _ => Display::None,
+        };
+    }
 }
 
 fn handle_stepper_buttons(
@@ -453,100 +1022,432 @@ fn handle_stepper_buttons(
         return;
     }
 
-    let mut updates: Vec<(CommodityId, u32)> = Vec::new();
     for (interaction, button) in interactions.iter_mut() {
-        if *interaction != Interaction::Pressed {
-            continue;
-        }
-        let current = model.units_for(button.commodity);
-        let updated = if button.delta < 0 {
-            current.saturating_sub(button.delta.unsigned_abs())
-        } else {
-            current.saturating_add(button.delta as u32)
-        };
-        if updated == current {
-            continue;
+        if *interaction == Interaction::Pressed {
+            adjust_stepper(
+                button.commodity,
+                button.delta,
+                &mut model,
+                &mut queue,
+                &mut texts,
+            );
         }
+    }
+}
 
-        model.set_units(button.commodity, updated);
-        let diff_i64 = updated as i64 - current as i64;
-        let diff = diff_i64.clamp(i32::MIN as i64, i32::MAX as i64) as i32;
-        queue.meter("ui_stepper_delta", diff);
-        updates.push((button.commodity, updated));
+fn adjust_stepper(
+    commodity: CommodityId,
+    delta: i32,
+    model: &mut HubTradeUiModel,
+    queue: &mut CommandQueue,
+    texts: &mut Query<(&mut Text, &StepperValueText)>,
+) {
+    let current = model.units_for(commodity);
+    let updated = if delta < 0 {
+        current.saturating_sub(delta.unsigned_abs())
+    } else {
+        current.saturating_add(delta as u32)
+    };
+    if updated == current {
+        return;
     }
 
-    for (commodity, value) in updates {
-        for (mut text, marker) in texts.iter_mut() {
-            if marker.commodity == commodity {
-                text.0 = value.to_string();
-            }
+    model.set_units(commodity, updated);
+    let diff_i64 = updated as i64 - current as i64;
+    let diff = diff_i64.clamp(i32::MIN as i64, i32::MAX as i64) as i32;
+    queue.meter("ui_stepper_delta", diff);
+    for (mut text, marker) in texts.iter_mut() {
+        if marker.commodity == commodity {
+            text.0 = updated.to_string();
         }
     }
 }
 
 fn handle_trade_buttons(
-    mut interactions: Query<TradeInteraction<'_>, ButtonInteractionFilter>,
-    mut model: ResMut<HubTradeUiModel>,
-    mut ui_state: ResMut<HubTradeUiState>,
-    mut queue: ResMut<CommandQueue>,
-    mut app_state: ResMut<AppState>,
-    rp: Res<Rulepack>,
+    interactions: Query<TradeInteraction<'_>, TradeButtonFilter>,
+    mut ctx: TradeContext,
 ) {
-    let Some(view) = model.view().cloned() else {
-        return;
+    let pressed: Vec<Entity> = interactions
+        .iter()
+        .filter(|(_, interaction)| **interaction == Interaction::Pressed)
+        .map(|(entity, _)| entity)
+        .collect();
+    for entity in pressed {
+        activate_trade_button(entity, &mut ctx);
+    }
+}
+
+/// Runs the trade behind a [`TradeButton`] or [`BulkTradeButton`] entity,
+/// exactly as a click on it would. Trades over the confirmation thresholds
+/// wait in [`PendingConfirmation`] instead. Returns whether a trade executed.
+fn activate_trade_button(entity: Entity, ctx: &mut TradeContext) -> bool {
+    let (commodity, kind, bulk) = match ctx.buttons.get(entity) {
+        Ok((Some(button), _)) => (button.commodity, button.kind, false),
+        Ok((None, Some(button))) => (button.commodity, button.kind, true),
+        _ => return false,
+    };
+    if ctx.pending.trade.is_some() {
+        return false;
+    }
+    let Some(view) = ctx.model.view() else {
+        return false;
     };
 
-    let mut triggered: Vec<TradeButton> = Vec::new();
-    for (interaction, button) in interactions.iter_mut() {
-        if *interaction == Interaction::Pressed {
-            triggered.push(*button);
+    let units = if bulk {
+        let AppState {
+            econ,
+            cargo,
+            wallet,
+            ..
+        } = &*ctx.app_state;
+        match kind {
+            TradeKind::Sell => HubTradeActions::max_sellable(cargo, commodity),
+            TradeKind::Buy => HubTradeActions::max_affordable(
+                view.hub,
+                commodity,
+                econ,
+                cargo,
+                wallet.balance(),
+                ctx.rp.as_ref(),
+            )
+            .unwrap_or(0),
+        }
+    } else {
+        ctx.model.units_for(commodity)
+    };
+    let tx = TradeTx {
+        hub: view.hub,
+        com: commodity,
+        units,
+        kind,
+    };
+    let catalog = CommodityCatalog::global();
+    let preview = preview_trade_with(
+        &catalog,
+        &tx,
+        &ctx.app_state.econ,
+        ctx.rp.as_ref(),
+        &ctx.app_state.cargo,
+        ctx.app_state.wallet.balance(),
+    );
+    if preview.limit.is_none()
+        && TradingConfig::global().needs_confirmation(units, preview.total_cents)
+    {
+        ctx.pending.trade = Some(PendingTrade {
+            commodity,
+            kind,
+            bulk,
+            units,
+            total_cents: MoneyCents(preview.total_cents.as_i64().saturating_abs()),
+        });
+        return false;
+    }
+    run_trade(ctx, commodity, kind, bulk)
+}
+
+/// Executes a trade through [`HubTradeActions`], keeps its inverse on the
+/// [`UndoStack`] and refreshes the view.
+fn run_trade(ctx: &mut TradeContext, commodity: CommodityId, kind: TradeKind, bulk: bool) -> bool {
+    let Some(view) = ctx.model.view().cloned() else {
+        return false;
+    };
+    let units = ctx.model.units_for(commodity);
+    if !bulk && units == 0 {
+        return false;
+    }
+    let tx = TradeTx {
+        hub: view.hub,
+        com: commodity,
+        units,
+        kind,
+    };
+    let held_before = ctx.app_state.cargo.units(commodity);
+    let lots_before = ctx
+        .app_state
+        .cargo
+        .lots
+        .get(&commodity)
+        .cloned()
+        .unwrap_or_default();
+    let result = {
+        let AppState {
+            econ,
+            cargo,
+            wallet,
+            ..
+        } = &mut *ctx.app_state;
+        let queue = ctx.queue.as_mut();
+        let wallet = &mut WalletAccess::new(wallet, &mut ctx.journal, queue.current_tick());
+        let rp = ctx.rp.as_ref();
+        match (kind, bulk) {
+            (TradeKind::Buy, false) => HubTradeActions::buy(queue, tx, econ, cargo, wallet, rp),
+            (TradeKind::Sell, false) => HubTradeActions::sell(queue, tx, econ, cargo, wallet, rp),
+            (TradeKind::Buy, true) => {
+                HubTradeActions::buy_max(queue, view.hub, commodity, econ, cargo, wallet, rp)
+            }
+            (TradeKind::Sell, true) => {
+                HubTradeActions::sell_all(queue, view.hub, commodity, econ, cargo, wallet, rp)
+            }
+        }
+    };
+
+    match result {
+        Ok(result) => {
+            let traded = held_before.abs_diff(ctx.app_state.cargo.units(commodity));
+            ctx.undo.entry = (traded > 0).then(|| UndoEntry {
+                inverse: TradeTx {
+                    hub: view.hub,
+                    com: commodity,
+                    units: traded,
+                    kind: match kind {
+                        TradeKind::Buy => TradeKind::Sell,
+                        TradeKind::Sell => TradeKind::Buy,
+                    },
+                },
+                result,
+                lots_before,
+                day: ctx.app_state.econ.day,
+            });
+            refresh_after_trade(ctx, view.hub);
+            true
+        }
+        Err(err) => {
+            warn!("failed to execute trade: {err:?}");
+            false
         }
     }
+}
+
+fn refresh_after_trade(ctx: &mut TradeContext, hub: HubId) {
+    let new_view = build_view_cached(
+        hub,
+        &ctx.app_state.econ,
+        ctx.rp.as_ref(),
+        &ctx.app_state.cargo,
+        ctx.app_state.wallet.balance(),
+        &mut ctx.price_cache,
+    );
+    ctx.model.set_view(new_view.clone());
+    ctx.ui_state.remember(new_view);
+}
 
-    for button in triggered {
-        let units = model.units_for(button.commodity);
-        if units == 0 {
-            continue;
+/// Reverses the last trade if it happened at this hub today. Returns whether
+/// anything was undone.
+fn undo_last_trade(ctx: &mut TradeContext) -> bool {
+    let Some(entry) = ctx.undo.entry.take() else {
+        return false;
+    };
+    if entry.inverse.hub != ctx.app_state.last_hub || entry.day != ctx.app_state.econ.day {
+        return false;
+    }
+    let reversed = {
+        let AppState {
+            econ,
+            cargo,
+            wallet,
+            ..
+        } = &mut *ctx.app_state;
+        let wallet = &mut WalletAccess::new(wallet, &mut ctx.journal, ctx.queue.current_tick());
+        reverse_trade(
+            &entry.inverse,
+            &entry.result,
+            &entry.lots_before,
+            econ,
+            cargo,
+            wallet,
+        )
+    };
+    match reversed {
+        Ok(()) => {
+            ctx.queue.meter_units("trade_undone", entry.inverse.units);
+            refresh_after_trade(ctx, entry.inverse.hub);
+            true
         }
-        let tx = TradeTx {
-            hub: view.hub,
-            com: button.commodity,
-            units,
-            kind: button.kind,
+        Err(err) => {
+            warn!("failed to undo trade: {err:?}");
+            false
+        }
+    }
+}
+
+fn handle_confirmation_buttons(
+    interactions: Query<(&Interaction, &ConfirmationButton), ButtonInteractionFilter>,
+    mut ctx: TradeContext,
+) {
+    let choices: Vec<ConfirmationButton> = interactions
+        .iter()
+        .filter(|(interaction, _)| **interaction == Interaction::Pressed)
+        .map(|(_, choice)| *choice)
+        .collect();
+    for choice in choices {
+        let Some(trade) = ctx.pending.trade.take() else {
+            return;
         };
-        let result = {
-            let AppState {
-                econ,
-                cargo,
-                wallet,
-                ..
-            } = &mut *app_state;
-            match button.kind {
-                TradeKind::Buy => {
-                    HubTradeActions::buy(queue.as_mut(), tx, &*econ, cargo, wallet, rp.as_ref())
-                }
-                TradeKind::Sell => {
-                    HubTradeActions::sell(queue.as_mut(), tx, &*econ, cargo, wallet, rp.as_ref())
-                }
+        match choice {
+            ConfirmationButton::Confirm => {
+                ctx.queue.meter_units("trade_confirmed", trade.units);
+                run_trade(&mut ctx, trade.commodity, trade.kind, trade.bulk);
             }
+            ConfirmationButton::Cancel => {
+                ctx.queue.meter_units("trade_cancelled", trade.units);
+            }
+        }
+    }
+}
+
+fn handle_undo_button(
+    interactions: Query<&Interaction, (Changed<Interaction>, With<UndoTradeButton>)>,
+    mut ctx: TradeContext,
+) {
+    let presses = interactions
+        .iter()
+        .filter(|interaction| **interaction == Interaction::Pressed)
+        .count();
+    for _ in 0..presses {
+        undo_last_trade(&mut ctx);
+    }
+}
+
+/// Shows the confirmation modal while a trade is pending.
+fn sync_confirmation_modal(
+    pending: Res<PendingConfirmation>,
+    model: Res<HubTradeUiModel>,
+    strings: Res<Strings>,
+    mut modals: Query<&mut Node, With<ConfirmationModal>>,
+    mut prompts: Query<&mut Text, With<ConfirmationPrompt>>,
+) {
+    if !pending.is_changed() {
+        return;
+    }
+    let display = match pending.trade {
+        Some(_) => Display::Flex,
+        None => Display::None,
+    };
+    for mut node in modals.iter_mut() {
+        node.display = display;
+    }
+    let Some(trade) = pending.trade else {
+        return;
+    };
+    let name = model
+        .view()
+        .and_then(|view| {
+            view.commodities
+                .iter()
+                .find(|row| row.id == trade.commodity)
+        })
+        .map_or_else(|| format!("#{}", trade.commodity.0), |row| row.name.clone());
+    let action = match trade.kind {
+        TradeKind::Buy => strings.get("hub_trade.buy"),
+        TradeKind::Sell => strings.get("hub_trade.sell"),
+    };
+    for mut text in prompts.iter_mut() {
+        text.0 = tr!(
+            strings,
+            "hub_trade.confirm_prompt",
+            action = action,
+            units = trade.units,
+            name = name,
+            cents = trade.total_cents.as_i64(),
+        );
+    }
+}
+
+/// Tab / Shift-Tab move focus along [`focus_order`], arrow keys step the
+/// focused row's units, and Enter activates the focused widget.
+fn handle_focus_keys(
+    keys: Option<Res<ButtonInput<KeyCode>>>,
+    mut ring: ResMut<FocusRing>,
+    mut ctx: TradeContext,
+    widgets: Query<(Entity, &FocusTarget)>,
+    mut texts: Query<(&mut Text, &StepperValueText)>,
+) {
+    let Some(keys) = keys else {
+        return;
+    };
+    let Some(view) = ctx.model.view() else {
+        return;
+    };
+
+    if keys.just_pressed(KeyCode::Tab) {
+        let order = focus_order(view);
+        let backwards = keys.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]);
+        let current = ring
+            .target
+            .and_then(|target| order.iter().position(|entry| *entry == target));
+        let next = match (current, backwards) {
+            (Some(index), false) => Some((index + 1) % order.len()),
+            (Some(index), true) => Some((index + order.len() - 1) % order.len()),
+            (None, false) => (!order.is_empty()).then_some(0),
+            (None, true) => order.len().checked_sub(1),
         };
+        ring.target = next.map(|index| order[index]);
+    }
+    ring.entity = ring.target.and_then(|target| {
+        widgets
+            .iter()
+            .find(|(_, widget)| **widget == target)
+            .map(|(entity, _)| entity)
+    });
 
-        match result {
-            Ok(_) => {
-                let new_view = build_view(
-                    view.hub,
-                    &app_state.econ,
-                    rp.as_ref(),
-                    &app_state.cargo,
-                    app_state.wallet,
-                );
-                model.set_view(new_view.clone());
-                ui_state.remember(new_view);
+    match ring.target {
+        Some(FocusTarget::Stepper { commodity, delta }) => {
+            let step = if keys.any_just_pressed([KeyCode::ArrowUp, KeyCode::ArrowRight]) {
+                1
+            } else if keys.any_just_pressed([KeyCode::ArrowDown, KeyCode::ArrowLeft]) {
+                -1
+            } else if keys.just_pressed(KeyCode::Enter) {
+                delta
+            } else {
+                0
+            };
+            if step != 0 {
+                adjust_stepper(commodity, step, &mut ctx.model, &mut ctx.queue, &mut texts);
             }
-            Err(err) => {
-                warn!("failed to execute trade: {err:?}");
+        }
+        Some(FocusTarget::Trade { .. }) if keys.just_pressed(KeyCode::Enter) => {
+            if let Some(entity) = ring.entity {
+                activate_trade_button(entity, &mut ctx);
             }
         }
+        Some(FocusTarget::Trade { .. }) | None => {}
+    }
+}
+
+/// Re-resolves the focused entity after rebuilds and swaps its background
+/// to the focus colour.
+fn apply_focus_highlight(
+    mut ring: ResMut<FocusRing>,
+    mut widgets: Query<(Entity, &FocusTarget, &mut BackgroundColor)>,
+) {
+    let mut focused = None;
+    for (entity, target, mut background) in widgets.iter_mut() {
+        let color = if ring.target == Some(*target) {
+            focused = Some(entity);
+            COLOR_FOCUS
+        } else {
+            widget_color(*target)
+        };
+        if background.0 != color {
+            background.0 = color;
+        }
+    }
+    if ring.entity != focused {
+        ring.entity = focused;
+    }
+}
+
+fn widget_color(target: FocusTarget) -> Color {
+    match target {
+        FocusTarget::Stepper { .. } => COLOR_TEXT_SECONDARY.with_alpha(0.25),
+        FocusTarget::Trade { kind, .. } => trade_color(kind).with_alpha(0.85),
+    }
+}
+
+fn trade_color(kind: TradeKind) -> Color {
+    match kind {
+        TradeKind::Buy => COLOR_ACCENT_POS,
+        TradeKind::Sell => COLOR_ACCENT_NEG,
     }
 }
 
@@ -559,7 +1460,12 @@ fn despawn_recursive(commands: &mut Commands, entity: Entity, children_query: &Q
     commands.entity(entity).despawn();
 }
 
-fn spawn_commodity_row(parent: &mut ChildSpawnerCommands, row: &CommodityRow, units: u32) {
+fn spawn_commodity_row(
+    parent: &mut ChildSpawnerCommands,
+    row: &CommodityRow,
+    units: u32,
+    strings: &Strings,
+) {
     parent
         .spawn((
             CommodityRowUi,
@@ -577,8 +1483,11 @@ fn spawn_commodity_row(parent: &mut ChildSpawnerCommands, row: &CommodityRow, un
                 text_components(row.name.clone(), 16.0, COLOR_TEXT_PRIMARY);
             row_node.spawn((name_text, name_font, name_color));
 
-            let (price_text, price_font, price_color) =
-                text_components(format_price(row.price_cents), 14.0, COLOR_TEXT_SECONDARY);
+            let (price_text, price_font, price_color) = text_components(
+                format_price(row.price_cents, strings),
+                14.0,
+                COLOR_TEXT_SECONDARY,
+            );
             row_node.spawn((price_text, price_font, price_color));
 
             let (units_text, units_font, units_color) =
@@ -590,10 +1499,130 @@ fn spawn_commodity_row(parent: &mut ChildSpawnerCommands, row: &CommodityRow, un
                 units_color,
             ));
 
-            spawn_stepper_button(row_node, row.id, -1, "−");
-            spawn_stepper_button(row_node, row.id, 1, "+");
-            spawn_trade_button(row_node, row.id, TradeKind::Buy, "Buy");
-            spawn_trade_button(row_node, row.id, TradeKind::Sell, "Sell");
+            spawn_stepper_button(row_node, row.id, -1, strings.get("hub_trade.stepper_down"));
+            spawn_stepper_button(row_node, row.id, 1, strings.get("hub_trade.stepper_up"));
+            spawn_trade_button(
+                row_node,
+                row.id,
+                TradeKind::Buy,
+                strings.get("hub_trade.buy"),
+            );
+            spawn_trade_button(
+                row_node,
+                row.id,
+                TradeKind::Sell,
+                strings.get("hub_trade.sell"),
+            );
+            spawn_bulk_trade_button(
+                row_node,
+                row.id,
+                TradeKind::Buy,
+                strings.get("hub_trade.buy_max"),
+            );
+            spawn_bulk_trade_button(
+                row_node,
+                row.id,
+                TradeKind::Sell,
+                strings.get("hub_trade.sell_all"),
+            );
+        });
+}
+
+fn spawn_manifest_panel(parent: &mut ChildSpawnerCommands, strings: &Strings) {
+    parent
+        .spawn((
+            ManifestToggle,
+            Button,
+            Node {
+                padding: UiRect::axes(Val::Px(8.0), Val::Px(4.0)),
+                align_self: AlignSelf::FlexStart,
+                ..Default::default()
+            },
+            BackgroundColor(COLOR_TEXT_SECONDARY.with_alpha(0.25)),
+        ))
+        .with_children(|button| {
+            let (text, font, color) = text_components(
+                tr!(strings, "hub_trade.manifest_toggle"),
+                14.0,
+                COLOR_TEXT_PRIMARY,
+            );
+            button.spawn((text, font, color));
+        });
+
+    parent.spawn((
+        ManifestList,
+        Node {
+            flex_direction: FlexDirection::Column,
+            row_gap: Val::Px(2.0),
+            ..Default::default()
+        },
+    ));
+
+    let (mass_text, mass_font, mass_color) = text_components("", 14.0, COLOR_TEXT_SECONDARY);
+    parent.spawn((ManifestMassText, mass_text, mass_font, mass_color));
+    let (volume_text, volume_font, volume_color) = text_components("", 14.0, COLOR_TEXT_SECONDARY);
+    parent.spawn((ManifestVolumeText, volume_text, volume_font, volume_color));
+}
+
+fn spawn_manifest_row(parent: &mut ChildSpawnerCommands, item: &CargoItemRow, strings: &Strings) {
+    parent
+        .spawn((
+            ManifestEntry,
+            ManifestRow {
+                commodity: item.commodity,
+            },
+            Node {
+                flex_direction: FlexDirection::Row,
+                align_items: AlignItems::Center,
+                column_gap: Val::Px(8.0),
+                padding: UiRect::axes(Val::Px(8.0), Val::Px(2.0)),
+                ..Default::default()
+            },
+        ))
+        .with_children(|row_node| {
+            let (name_text, name_font, name_color) =
+                text_components(item.name.clone(), 14.0, COLOR_TEXT_PRIMARY);
+            row_node.spawn((name_text, name_font, name_color));
+
+            let (units_text, units_font, units_color) = text_components(
+                tr!(strings, "hub_trade.manifest_units", units = item.units),
+                14.0,
+                COLOR_TEXT_PRIMARY,
+            );
+            row_node.spawn((units_text, units_font, units_color));
+
+            let (size_text, size_font, size_color) = text_components(
+                tr!(
+                    strings,
+                    "hub_trade.manifest_unit_size",
+                    mass = item.unit_mass_kg,
+                    volume = item.unit_volume_l,
+                ),
+                14.0,
+                COLOR_TEXT_SECONDARY,
+            );
+            row_node.spawn((size_text, size_font, size_color));
+
+            let (value_text, value_font, value_color) = text_components(
+                format_price(item.sell_value_cents, strings),
+                14.0,
+                COLOR_TEXT_SECONDARY,
+            );
+            row_node.spawn((value_text, value_font, value_color));
+
+            row_node
+                .spawn((
+                    ManifestSellAllButton,
+                    bulk_trade_button_bundle(item.commodity, TradeKind::Sell),
+                ))
+                .with_children(|button| {
+                    let (text, font, color) = text_components(
+                        strings.get("hub_trade.sell_all"),
+                        14.0,
+                        COLOR_TEXT_PRIMARY,
+                    );
+                    button.spawn((text, font, color));
+                });
         });
 }
 
@@ -603,9 +1632,11 @@ fn spawn_stepper_button(
     delta: i32,
     label: &str,
 ) {
+    let target = FocusTarget::Stepper { commodity, delta };
     parent
         .spawn((
             StepperButton { commodity, delta },
+            target,
             Button,
             Node {
                 padding: UiRect::all(Val::Px(6.0)),
@@ -614,7 +1645,7 @@ fn spawn_stepper_button(
                 align_items: AlignItems::Center,
                 ..Default::default()
             },
-            BackgroundColor(COLOR_TEXT_SECONDARY.with_alpha(0.25)),
+            BackgroundColor(widget_color(target)),
         ))
         .with_children(|button| {
             let (text, font, color) = text_components(label, 14.0, COLOR_TEXT_PRIMARY);
@@ -628,13 +1659,11 @@ fn spawn_trade_button(
     kind: TradeKind,
     label: &str,
 ) {
-    let color = match kind {
-        TradeKind::Buy => COLOR_ACCENT_POS,
-        TradeKind::Sell => COLOR_ACCENT_NEG,
-    };
+    let target = FocusTarget::Trade { commodity, kind };
     parent
         .spawn((
             TradeButton { commodity, kind },
+            target,
             Button,
             Node {
                 padding: UiRect::axes(Val::Px(12.0), Val::Px(6.0)),
@@ -642,7 +1671,7 @@ fn spawn_trade_button(
                 align_items: AlignItems::Center,
                 ..Default::default()
             },
-            BackgroundColor(color.with_alpha(0.85)),
+            BackgroundColor(widget_color(target)),
         ))
         .with_children(|button| {
             let (text, font, color) = text_components(label, 14.0, COLOR_TEXT_PRIMARY);
@@ -650,6 +1679,34 @@ fn spawn_trade_button(
         });
 }
 
+fn bulk_trade_button_bundle(commodity: CommodityId, kind: TradeKind) -> impl Bundle {
+    (
+        BulkTradeButton { commodity, kind },
+        Button,
+        Node {
+            padding: UiRect::axes(Val::Px(12.0), Val::Px(6.0)),
+            justify_content: JustifyContent::Center,
+            align_items: AlignItems::Center,
+            ..Default::default()
+        },
+        BackgroundColor(trade_color(kind).with_alpha(0.55)),
+    )
+}
+
+fn spawn_bulk_trade_button(
+    parent: &mut ChildSpawnerCommands,
+    commodity: CommodityId,
+    kind: TradeKind,
+    label: &str,
+) {
+    parent
+        .spawn(bulk_trade_button_bundle(commodity, kind))
+        .with_children(|button| {
+            let (text, font, color) = text_components(label, 14.0, COLOR_TEXT_PRIMARY);
+            button.spawn((text, font, color));
+        });
+}
+
 fn text_components(
     value: impl Into<String>,
     size: f32,
@@ -665,10 +1722,11 @@ fn text_components(
     )
 }
 
+/// Copies the last view's hub and cargo back into `app`. The wallet is left
+/// alone: trades already moved it through the ledger.
 pub fn persist_on_exit(state: &HubTradeUiState, app: &mut AppState) {
     if let Some(view) = &state.last_view {
         app.last_hub = view.hub;
-        app.wallet = view.wallet_cents;
         app.cargo.capacity_mass_kg = view.cargo.capacity_mass_kg;
         app.cargo.capacity_volume_l = view.cargo.capacity_volume_l;
         app.cargo.items = view
@@ -677,5 +1735,6 @@ pub fn persist_on_exit(state: &HubTradeUiState, app: &mut AppState) {
             .iter()
             .map(|row| (row.commodity, row.units))
             .collect();
+        app.cargo.sync_lots();
     }
 }
@@ -0,0 +1,296 @@
+//! UI strings by language, loaded from `assets/i18n/<lang>.toml`.
+//!
+//! Only presentation text goes through here. Numbers are formatted as ASCII
+//! integers before they are substituted, and localized text must never be
+//! placed in a command, so records replay identically under any `--lang`.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use anyhow::{bail, Context, Result};
+use bevy::prelude::*;
+
+/// Language used when `--lang` is not given.
+pub const DEFAULT_LANG: &str = "en";
+
+/// Every key a strings file may define.
+pub const KEYS: &[&str] = &[
+    "hub_trade.awaiting_data",
+    "hub_trade.buy",
+    "hub_trade.buy_max",
+    "hub_trade.cancel",
+    "hub_trade.cargo_line",
+    "hub_trade.cargo_placeholder",
+    "hub_trade.confirm",
+    "hub_trade.confirm_prompt",
+    "hub_trade.manifest_empty",
+    "hub_trade.manifest_mass",
+    "hub_trade.manifest_toggle",
+    "hub_trade.manifest_unit_size",
+    "hub_trade.manifest_units",
+    "hub_trade.manifest_volume",
+    "hub_trade.price",
+    "hub_trade.sell",
+    "hub_trade.sell_all",
+    "hub_trade.stepper_down",
+    "hub_trade.stepper_up",
+    "hub_trade.ticker_clamp",
+    "hub_trade.ticker_day",
+    "hub_trade.ticker_di",
+    "hub_trade.ticker_hub",
+    "hub_trade.ticker_separator",
+    "hub_trade.undo",
+    "hub_trade.wallet_line",
+    "hub_trade.wallet_placeholder",
+    "post_leg_report.continue",
+    "post_leg_report.danger",
+    "post_leg_report.intents",
+    "post_leg_report.leg",
+    "post_leg_report.mission_failure",
+    "post_leg_report.mission_pending",
+    "post_leg_report.mission_success",
+    "post_leg_report.missions",
+    "post_leg_report.outcome_failure",
+    "post_leg_report.outcome_success",
+    "post_leg_report.paused",
+    "post_leg_report.spawns",
+    "post_leg_report.title",
+    "route_planner.closed",
+    "route_planner.danger",
+    "route_planner.no_routes",
+    "route_planner.profit",
+    "route_planner.profit_unknown",
+    "route_planner.route",
+    "route_planner.rumor",
+    "route_planner.title",
+    "route_planner.weather",
+    "rumor.fog",
+    "rumor.patrol",
+    "rumor.wind",
+    "rumor_icon.fog",
+    "rumor_icon.patrol",
+    "rumor_icon.wind",
+    "weather.clear",
+    "weather.fog",
+    "weather.rains",
+    "weather.windy",
+    "weather_icon.clear",
+    "weather_icon.fog",
+    "weather_icon.rains",
+    "weather_icon.windy",
+];
+
+/// Path of the strings file for `lang`.
+pub fn strings_path(lang: &str) -> PathBuf {
+    crate::workspace_asset(&format!("assets/i18n/{lang}.toml"))
+}
+
+/// The UI strings for one language. Keys the file leaves out render as the
+/// key itself, with a warning the first time each is asked for.
+#[derive(Resource, Debug, Clone)]
+pub struct Strings {
+    lang: String,
+    entries: BTreeMap<String, String>,
+    warned: Arc<Mutex<BTreeSet<String>>>,
+}
+
+impl Strings {
+    /// Loads `assets/i18n/<lang>.toml`.
+    pub fn load(lang: &str) -> Result<Self> {
+        Self::load_from_path(lang, &strings_path(lang))
+    }
+
+    pub fn load_from_path(lang: &str, path: &Path) -> Result<Self> {
+        let text = fs::read_to_string(path)
+            .with_context(|| format!("reading {lang} strings from {}", path.display()))?;
+        Self::from_toml_str(lang, &text)
+            .with_context(|| format!("loading {lang} strings from {}", path.display()))
+    }
+
+    /// Parses a strings file. Tables nest into dotted keys; every leaf must
+    /// be a string and every key one of [`KEYS`].
+    pub fn from_toml_str(lang: &str, text: &str) -> Result<Self> {
+        let table: toml::Table = toml::from_str(text)?;
+        let mut entries = BTreeMap::new();
+        flatten("", &table, &mut entries)?;
+        if let Some(unknown) = entries.keys().find(|key| !KEYS.contains(&key.as_str())) {
+            bail!("unknown string key `{unknown}`");
+        }
+        Ok(Self {
+            lang: lang.to_string(),
+            entries,
+            warned: Arc::default(),
+        })
+    }
+
+    pub fn lang(&self) -> &str {
+        &self.lang
+    }
+
+    /// The string for `key`, or `key` itself when this language lacks it.
+    pub fn get<'a>(&'a self, key: &'a str) -> &'a str {
+        match self.entries.get(key) {
+            Some(value) => value,
+            None => {
+                self.warn_missing(key);
+                key
+            }
+        }
+    }
+
+    /// The string for `key` with each `{name}` replaced by its argument.
+    /// Placeholders without an argument are left as written.
+    pub fn format(&self, key: &str, args: &[(&str, &dyn fmt::Display)]) -> String {
+        let template = self.get(key);
+        let mut out = String::with_capacity(template.len());
+        let mut rest = template;
+        while let Some(open) = rest.find('{') {
+            out.push_str(&rest[..open]);
+            let tail = &rest[open..];
+            let arg = tail.find('}').and_then(|close| {
+                let name = &tail[1..close];
+                args.iter()
+                    .find(|(arg, _)| *arg == name)
+                    .map(|(_, value)| (close, value))
+            });
+            match arg {
+                Some((close, value)) => {
+                    out.push_str(&value.to_string());
+                    rest = &tail[close + 1..];
+                }
+                None => {
+                    out.push('{');
+                    rest = &tail[1..];
+                }
+            }
+        }
+        out.push_str(rest);
+        out
+    }
+
+    fn warn_missing(&self, key: &str) {
+        let mut warned = self
+            .warned
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        if warned.insert(key.to_string()) {
+            warn!("no {} string for `{key}`; showing the key", self.lang);
+        }
+    }
+}
+
+impl FromWorld for Strings {
+    fn from_world(_world: &mut World) -> Self {
+        Strings::load(DEFAULT_LANG).expect("failed to load the bundled UI strings")
+    }
+}
+
+fn flatten(prefix: &str, table: &toml::Table, out: &mut BTreeMap<String, String>) -> Result<()> {
+    for (name, value) in table {
+        let key = if prefix.is_empty() {
+            name.clone()
+        } else {
+            format!("{prefix}.{name}")
+        };
+        match value {
+            toml::Value::String(text) => {
+                out.insert(key, text.clone());
+            }
+            toml::Value::Table(inner) => flatten(&key, inner, out)?,
+            other => bail!("string key `{key}` holds a {}", other.type_str()),
+        }
+    }
+    Ok(())
+}
+
+/// Looks up `key` in `strings`, substituting `name = value` arguments:
+/// `tr!(strings, "route_planner.route", route = 4)`.
+macro_rules! tr {
+    ($strings:expr, $key:expr $(,)?) => {
+        $strings.get($key).to_string()
+    };
+    ($strings:expr, $key:expr, $($name:ident = $value:expr),+ $(,)?) => {
+        $strings.format(
+            $key,
+            &[$((stringify!($name), &$value as &dyn ::std::fmt::Display)),+],
+        )
+    };
+}
+pub(crate) use tr;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bundled_languages_define_every_key() {
+        for lang in [DEFAULT_LANG, "xx"] {
+            let strings = Strings::load(lang).expect("bundled strings");
+            for key in KEYS {
+                assert!(strings.entries.contains_key(*key), "{lang} lacks {key}");
+            }
+        }
+    }
+
+    #[test]
+    fn languages_render_different_labels() {
+        let en = Strings::load(DEFAULT_LANG).expect("en");
+        let xx = Strings::load("xx").expect("xx");
+        assert_eq!(tr!(en, "route_planner.title"), "Route Planner");
+        assert_ne!(
+            tr!(en, "route_planner.title"),
+            tr!(xx, "route_planner.title")
+        );
+        assert_eq!(tr!(en, "route_planner.route", route = 4), "Route 4");
+        assert_ne!(
+            tr!(en, "hub_trade.wallet_line", cents = 1_250, fee = 75),
+            tr!(xx, "hub_trade.wallet_line", cents = 1_250, fee = 75)
+        );
+    }
+
+    #[test]
+    fn missing_keys_fall_back_to_the_key() {
+        let strings = Strings::from_toml_str("xx", "[route_planner]\ntitle = \"Planner\"\n")
+            .expect("partial strings");
+        assert_eq!(tr!(strings, "route_planner.title"), "Planner");
+        assert_eq!(tr!(strings, "route_planner.closed"), "route_planner.closed");
+        assert_eq!(
+            tr!(strings, "route_planner.route", route = 4),
+            "route_planner.route"
+        );
+        assert_eq!(strings.warned.lock().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn substitution_leaves_unknown_placeholders() {
+        let strings = Strings::from_toml_str(
+            "xx",
+            "[route_planner]\nrumor = \"{icon} {name} {confidence}% {other}\"\n",
+        )
+        .expect("strings");
+        assert_eq!(
+            tr!(
+                strings,
+                "route_planner.rumor",
+                icon = "*",
+                name = "Fog",
+                confidence = 40
+            ),
+            "* Fog 40% {other}"
+        );
+    }
+
+    #[test]
+    fn strict_loading_rejects_bad_files() {
+        let unknown = Strings::from_toml_str("xx", "[route_planner]\ntitel = \"Planner\"\n")
+            .expect_err("typo key");
+        assert!(unknown.to_string().contains("route_planner.titel"));
+        let number = Strings::from_toml_str("xx", "[route_planner]\ntitle = 4\n")
+            .expect_err("non-string value");
+        assert!(number.to_string().contains("route_planner.title"));
+        assert!(Strings::load("zz").is_err());
+    }
+}
@@ -1,3 +1,6 @@
+pub mod director_overlay;
 pub mod hub_trade;
+pub mod i18n;
+pub mod post_leg_report;
 pub mod route_planner;
 pub mod styles;
@@ -0,0 +1,560 @@
+//! End-of-leg recap: danger change, mission outcomes, pending econ intents,
+//! spawn totals and pause time, shown full-screen once the director reports
+//! [`LegStatus::Completed`]. Continue dismisses it back to the hub screen and
+//! rolls the economy over to the next day.
+
+use bevy::prelude::*;
+
+use crate::app_state::AppState;
+use crate::systems::director::{
+    DirectorState, EconIntent, LegStatus, MissionOutcome, MissionOutcomes, Outcome, SpawnMemory,
+};
+use crate::systems::economy::{
+    accrue_spoilage_rot, step_economy_day_with_events, EconStepScope, MarketEvent, MarketEvents,
+    RouteId, Rulepack, RulepackSchedule, RulepackSource,
+};
+use crate::systems::trading::catalog::CatalogHandle;
+use crate::systems::trading::types::CommodityCatalog;
+use crate::ui::i18n::{tr, Strings};
+use crate::ui::styles::{
+    COLOR_ACCENT_NEG, COLOR_ACCENT_POS, COLOR_BG, COLOR_TEXT_PRIMARY, COLOR_TEXT_SECONDARY,
+};
+
+/// Everything the report screen shows for one finished leg.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LegReport {
+    pub link_id: RouteId,
+    pub day: u32,
+    /// `None` when the leg had not completed when the report was built.
+    pub outcome: Option<Outcome>,
+    pub leg_ticks: u32,
+    pub start_danger: i32,
+    pub end_danger: i32,
+    pub danger_delta: i32,
+    /// One row per catalog mission, in catalog order.
+    pub missions: Vec<MissionOutcome>,
+    pub pending_pp_delta: i32,
+    pub pending_basis_overlay_bp: i32,
+    /// Enemies spawned over the leg.
+    pub spawned: u64,
+    /// Spawns still alive when the leg ended.
+    pub active_spawns: u32,
+    pub paused_ticks: u32,
+}
+
+/// Gathers the report for the leg `state` describes. `intent` is the leg's
+/// pending econ intent; the live [`EconIntent`] is drained every tick, so
+/// callers normally pass [`MissionOutcomes::intent`].
+pub fn build_leg_report(
+    state: &DirectorState,
+    outcomes: &MissionOutcomes,
+    intent: &EconIntent,
+    memory: &SpawnMemory,
+) -> LegReport {
+    let outcome = match state.status {
+        LegStatus::Completed(outcome) => Some(outcome),
+        _ => None,
+    };
+    LegReport {
+        link_id: state.link_id,
+        day: state.day,
+        outcome,
+        leg_ticks: state.leg_tick,
+        start_danger: state.leg_start_danger_score,
+        end_danger: state.current_danger_score,
+        danger_delta: state
+            .current_danger_score
+            .saturating_sub(state.leg_start_danger_score),
+        missions: outcomes.rows().to_vec(),
+        pending_pp_delta: i32::from(intent.pending_pp_delta),
+        pending_basis_overlay_bp: i32::from(intent.pending_basis_overlay_bp),
+        spawned: memory.spawn_counter,
+        active_spawns: u32::try_from(memory.active_spawns.len()).unwrap_or(u32::MAX),
+        paused_ticks: state.paused_ticks,
+    }
+}
+
+/// The report's body lines, in display order.
+pub fn report_lines(report: &LegReport, strings: &Strings) -> Vec<String> {
+    let outcome = match report.outcome {
+        Some(Outcome::Success) | None => tr!(strings, "post_leg_report.outcome_success"),
+        Some(Outcome::Failure) => tr!(strings, "post_leg_report.outcome_failure"),
+    };
+    let mut lines = vec![
+        tr!(
+            strings,
+            "post_leg_report.leg",
+            route = report.link_id.0,
+            day = report.day,
+            outcome = outcome
+        ),
+        tr!(
+            strings,
+            "post_leg_report.danger",
+            start = report.start_danger,
+            end = report.end_danger,
+            delta = signed(report.danger_delta)
+        ),
+        tr!(strings, "post_leg_report.missions"),
+    ];
+    lines.extend(report.missions.iter().map(|row| mission_line(row, strings)));
+    lines.push(tr!(
+        strings,
+        "post_leg_report.intents",
+        pp = signed(report.pending_pp_delta),
+        basis = signed(report.pending_basis_overlay_bp)
+    ));
+    lines.push(tr!(
+        strings,
+        "post_leg_report.spawns",
+        spawned = report.spawned,
+        active = report.active_spawns
+    ));
+    lines.push(tr!(
+        strings,
+        "post_leg_report.paused",
+        ticks = report.paused_ticks
+    ));
+    lines
+}
+
+fn mission_line(row: &MissionOutcome, strings: &Strings) -> String {
+    let key = match row.outcome {
+        Some(Outcome::Success) => "post_leg_report.mission_success",
+        Some(Outcome::Failure) => "post_leg_report.mission_failure",
+        None => return tr!(strings, "post_leg_report.mission_pending", name = row.name),
+    };
+    tr!(
+        strings,
+        key,
+        name = row.name,
+        pp = signed(i32::from(row.pp_delta)),
+        basis = signed(i32::from(row.basis_bp_overlay))
+    )
+}
+
+/// `+3`, `-2` or `0`.
+fn signed(value: i32) -> String {
+    if value == 0 {
+        "0".to_string()
+    } else {
+        format!("{value:+}")
+    }
+}
+
+/// The report on screen, if any, and how many day rollovers Continue has
+/// triggered.
+#[derive(Resource, Debug, Default)]
+pub struct PostLegReportState {
+    report: Option<LegReport>,
+    /// Set once the current completed leg has been reported, so dismissing
+    /// the report does not reopen it.
+    captured: bool,
+    rollovers: u32,
+}
+
+impl PostLegReportState {
+    pub fn report(&self) -> Option<&LegReport> {
+        self.report.as_ref()
+    }
+
+    pub fn rollovers(&self) -> u32 {
+        self.rollovers
+    }
+}
+
+#[derive(Component)]
+struct PostLegReportRoot;
+
+#[derive(Component)]
+struct PostLegReportLines;
+
+#[derive(Component)]
+struct PostLegReportLine;
+
+/// The report's Continue button.
+#[derive(Component, Debug, Clone, Copy)]
+pub struct PostLegContinueButton;
+
+pub struct PostLegReportPlugin;
+
+impl Plugin for PostLegReportPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<PostLegReportState>()
+            .init_resource::<Strings>()
+            .add_systems(Startup, spawn_post_leg_report)
+            .add_systems(
+                Update,
+                (
+                    capture_leg_report,
+                    handle_continue_button,
+                    sync_post_leg_report,
+                )
+                    .chain(),
+            );
+    }
+}
+
+fn spawn_post_leg_report(mut commands: Commands, strings: Res<Strings>) {
+    commands
+        .spawn((
+            PostLegReportRoot,
+            Node {
+                display: Display::None,
+                position_type: PositionType::Absolute,
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                flex_direction: FlexDirection::Column,
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                row_gap: Val::Px(12.0),
+                ..default()
+            },
+            BackgroundColor(COLOR_BG),
+            GlobalZIndex(1),
+            Name::new("PostLegReport"),
+        ))
+        .with_children(|root| {
+            root.spawn((
+                Text::new(tr!(strings, "post_leg_report.title")),
+                TextFont {
+                    font_size: 24.0,
+                    ..default()
+                },
+                TextColor(COLOR_TEXT_PRIMARY),
+            ));
+            root.spawn((
+                PostLegReportLines,
+                Node {
+                    flex_direction: FlexDirection::Column,
+                    row_gap: Val::Px(6.0),
+                    ..default()
+                },
+            ));
+            root.spawn((
+                PostLegContinueButton,
+                Button,
+                Node {
+                    padding: UiRect::axes(Val::Px(16.0), Val::Px(8.0)),
+                    ..default()
+                },
+                BackgroundColor(COLOR_ACCENT_POS.with_alpha(0.85)),
+            ))
+            .with_children(|button| {
+                button.spawn((
+                    Text::new(tr!(strings, "post_leg_report.continue")),
+                    TextFont {
+                        font_size: 16.0,
+                        ..default()
+                    },
+                    TextColor(COLOR_TEXT_PRIMARY),
+                ));
+            });
+        });
+}
+
+fn capture_leg_report(
+    director: Option<Res<DirectorState>>,
+    outcomes: Option<Res<MissionOutcomes>>,
+    memory: Option<Res<SpawnMemory>>,
+    mut report: ResMut<PostLegReportState>,
+) {
+    let Some(director) = director else {
+        return;
+    };
+    if !matches!(director.status, LegStatus::Completed(_)) {
+        if report.captured {
+            report.captured = false;
+        }
+        return;
+    }
+    if report.captured {
+        return;
+    }
+    let outcomes = outcomes.map(|res| res.clone()).unwrap_or_default();
+    let memory = memory.map(|res| res.clone()).unwrap_or_default();
+    report.report = Some(build_leg_report(
+        &director,
+        &outcomes,
+        &outcomes.intent(),
+        &memory,
+    ));
+    report.captured = true;
+}
+
+fn handle_continue_button(
+    buttons: Query<&Interaction, (Changed<Interaction>, With<PostLegContinueButton>)>,
+    mut report: ResMut<PostLegReportState>,
+    app_state: Option<ResMut<AppState>>,
+    schedule: Option<Res<RulepackSchedule>>,
+    rulepack: Option<Res<Rulepack>>,
+    events: Option<Res<MarketEvents>>,
+    catalog: Option<Res<CatalogHandle>>,
+) {
+    if !buttons
+        .iter()
+        .any(|interaction| *interaction == Interaction::Pressed)
+    {
+        return;
+    }
+    if report.report.take().is_none() {
+        return;
+    }
+    let Some(mut app_state) = app_state else {
+        return;
+    };
+    let events = events.as_deref().map_or(&[][..], MarketEvents::events);
+    let catalog = catalog.and_then(|handle| handle.snapshot().1);
+    let catalog = catalog.as_deref();
+    let rolled = match (schedule.as_deref(), rulepack.as_deref()) {
+        (Some(schedule), _) => roll_over_day(&mut app_state, schedule, events, catalog),
+        (None, Some(rulepack)) => roll_over_day(&mut app_state, rulepack, events, catalog),
+        (None, None) => {
+            warn!("no rulepack installed; skipping the post-leg day rollover");
+            false
+        }
+    };
+    if rolled {
+        report.rollovers = report.rollovers.saturating_add(1);
+    }
+}
+
+/// Steps the economy one day at the current hub, applying the market events
+/// scheduled for it, then ages the cargo into the new day. Spoiled units
+/// accrue as ROT, which the next rollover converts to debt. Autosave sees
+/// the new day and writes its day-rollover checkpoint.
+fn roll_over_day<R: RulepackSource + ?Sized>(
+    app_state: &mut AppState,
+    rules: &R,
+    events: &[MarketEvent],
+    catalog: Option<&CommodityCatalog>,
+) -> bool {
+    let AppState {
+        world_seed,
+        econ_version,
+        last_hub,
+        econ,
+        cargo,
+        ..
+    } = app_state;
+    let before = econ.day;
+    step_economy_day_with_events(
+        rules,
+        *world_seed,
+        *econ_version,
+        *last_hub,
+        econ,
+        EconStepScope::GlobalAndHub,
+        events,
+    );
+    if econ.day <= before {
+        return false;
+    }
+    if let Some(catalog) = catalog {
+        let spoiled = cargo.age(econ.day, catalog);
+        econ.rot_u16 =
+            accrue_spoilage_rot(econ.rot_u16, &spoiled, &rules.rulepack_for(econ.day).rot);
+    }
+    true
+}
+
+fn sync_post_leg_report(
+    mut commands: Commands,
+    report: Res<PostLegReportState>,
+    strings: Res<Strings>,
+    mut roots: Query<&mut Node, With<PostLegReportRoot>>,
+    lines: Query<Entity, With<PostLegReportLines>>,
+    existing: Query<Entity, With<PostLegReportLine>>,
+) {
+    if !report.is_changed() && !strings.is_changed() {
+        return;
+    }
+    let display = match report.report {
+        Some(_) => Display::Flex,
+        None => Display::None,
+    };
+    for mut node in roots.iter_mut() {
+        node.display = display;
+    }
+    for entity in existing.iter() {
+        commands.entity(entity).despawn();
+    }
+    let (Some(leg), Some(container)) = (report.report.as_ref(), lines.iter().next()) else {
+        return;
+    };
+    let danger_color = match leg.danger_delta {
+        delta if delta > 0 => COLOR_ACCENT_NEG,
+        delta if delta < 0 => COLOR_ACCENT_POS,
+        _ => COLOR_TEXT_SECONDARY,
+    };
+    commands.entity(container).with_children(|parent| {
+        for (index, line) in report_lines(leg, &strings).into_iter().enumerate() {
+            let color = if index == 1 {
+                danger_color
+            } else {
+                COLOR_TEXT_PRIMARY
+            };
+            parent.spawn((
+                PostLegReportLine,
+                Text::new(line),
+                TextFont {
+                    font_size: 16.0,
+                    ..default()
+                },
+                TextColor(color),
+            ));
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::CliOptions;
+    use crate::systems::director::MissionResult;
+    use crate::{leg_context_from_options, leg_meters, start_leg_app, step_leg_app};
+    use clap::Parser;
+
+    const LEG_MINUTES: u32 = 2;
+
+    fn seeded_options() -> CliOptions {
+        let minutes = LEG_MINUTES.to_string();
+        CliOptions::try_parse_from([
+            "game",
+            "--mode",
+            "record",
+            "--headless",
+            "--mission-minutes",
+            minutes.as_str(),
+        ])
+        .expect("cli options")
+    }
+
+    /// Runs the seeded headless leg until it completes and returns the
+    /// report built from its final resources.
+    fn seeded_leg_report(options: &CliOptions) -> LegReport {
+        crate::world::index::ensure_world_index().expect("world index");
+        let meters = leg_meters(options).expect("meters");
+        let mut app = start_leg_app(options, leg_context_from_options(options).expect("context"));
+        for _ in 0..=LEG_MINUTES * 60 {
+            step_leg_app(&mut app, options, meters).expect("step");
+            if matches!(
+                app.world().resource::<DirectorState>().status,
+                LegStatus::Completed(_)
+            ) {
+                break;
+            }
+        }
+        let world = app.world();
+        let outcomes = world.resource::<MissionOutcomes>();
+        build_leg_report(
+            world.resource::<DirectorState>(),
+            outcomes,
+            &outcomes.intent(),
+            world.resource::<SpawnMemory>(),
+        )
+    }
+
+    fn row(name: &str, outcome: Option<(Outcome, i16, i16, u32)>) -> MissionOutcome {
+        MissionOutcome {
+            name: name.to_string(),
+            outcome: outcome.map(|(outcome, ..)| outcome),
+            pp_delta: outcome.map_or(0, |(_, pp, _, _)| pp),
+            basis_bp_overlay: outcome.map_or(0, |(_, _, basis, _)| basis),
+            resolve_tick: outcome.map(|(.., tick)| tick),
+        }
+    }
+
+    #[test]
+    fn seeded_leg_report_matches_expected_values() {
+        let options = seeded_options();
+        let report = seeded_leg_report(&options);
+        assert_eq!(
+            report,
+            LegReport {
+                link_id: RouteId(11),
+                day: 3,
+                outcome: Some(Outcome::Success),
+                leg_ticks: 120,
+                start_danger: 0,
+                end_danger: 16_000,
+                danger_delta: 16_000,
+                missions: vec![
+                    row("anchor_audit", Some((Outcome::Success, -3, 0, 103))),
+                    row("break_chain", Some((Outcome::Success, -8, 0, 6))),
+                    row("rain_flag", Some((Outcome::Failure, 4, 10, 89))),
+                    row("sourvault", None),
+                    row("wayleave", None),
+                ],
+                pending_pp_delta: -7,
+                pending_basis_overlay_bp: 10,
+                spawned: 13,
+                active_spawns: 13,
+                paused_ticks: 0,
+            }
+        );
+
+        let (_, summary) = crate::runtime::fast_forward(&options).expect("fast forward");
+        assert_eq!(summary.pp_delta, report.pending_pp_delta);
+        assert_eq!(summary.final_danger, report.end_danger);
+    }
+
+    #[test]
+    fn mission_rows_keep_catalog_order_and_sum_intents() {
+        let cfg = crate::systems::director::config::MissionCfg::default();
+        let catalog = vec![
+            ("wayleave".to_string(), cfg.clone()),
+            ("rain_flag".to_string(), cfg),
+        ];
+        let mut outcomes = MissionOutcomes::for_catalog(&catalog);
+        outcomes.record(
+            "rain_flag",
+            MissionResult::Success {
+                pp_delta: 5,
+                basis_bp_overlay: -3,
+            },
+            40,
+        );
+        outcomes.record(
+            "wayleave",
+            MissionResult::Fail {
+                pp_delta: -2,
+                basis_bp_overlay: 7,
+            },
+            90,
+        );
+        let state = DirectorState {
+            status: LegStatus::Completed(Outcome::Success),
+            leg_start_danger_score: 10,
+            current_danger_score: 4,
+            paused_ticks: 12,
+            ..Default::default()
+        };
+        let memory = SpawnMemory {
+            spawn_counter: 9,
+            active_spawns: vec![7, 8],
+            ..Default::default()
+        };
+        let report = build_leg_report(&state, &outcomes, &outcomes.intent(), &memory);
+        let names: Vec<&str> = report
+            .missions
+            .iter()
+            .map(|row| row.name.as_str())
+            .collect();
+        assert_eq!(names, ["wayleave", "rain_flag"]);
+        assert_eq!(report.danger_delta, -6);
+        assert_eq!(
+            (report.pending_pp_delta, report.pending_basis_overlay_bp),
+            (3, 4)
+        );
+        assert_eq!((report.spawned, report.active_spawns), (9, 2));
+
+        let strings = Strings::load(crate::ui::i18n::DEFAULT_LANG).expect("strings");
+        let lines = report_lines(&report, &strings);
+        assert_eq!(lines[1], "Danger 10 → 4 (-6)");
+        assert_eq!(lines[3], "wayleave: failed, PP -2, basis +7bp");
+        assert_eq!(lines[4], "rain_flag: success, PP +5, basis -3bp");
+        assert_eq!(lines[5], "Pending PP +3 • basis +4bp");
+        assert_eq!(lines[7], "Paused 12 ticks");
+    }
+}
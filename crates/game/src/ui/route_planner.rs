@@ -1,17 +1,74 @@
+use bevy::ecs::system::SystemParam;
 use bevy::prelude::*;
 use bevy::text::{Font, TextColor, TextFont};
 use smallvec::SmallVec;
 
 use crate::app_state::AppState;
-use crate::systems::economy::{HubId, RouteId, Weather};
+use crate::cli::{
+    DEFAULT_CADENCE_PER_MIN, DEFAULT_DENSITY_PER_10K, DEFAULT_MISSION_MINUTES,
+    DEFAULT_PLAYER_RATING,
+};
+use crate::systems::director::config::DirectorCfg;
+use crate::systems::director::{compute_spawn_budget, danger_score, DirectorConfigResource};
+use crate::systems::economy::{EconomyDay, HubId, MoneyCents, Pp, RouteId, Rulepack, Weather};
+use crate::systems::trading::analysis::{route_profit_estimates, RouteProfit};
+use crate::systems::trading::catalog::CatalogHandle;
+use crate::systems::trading::inventory::Cargo;
+use crate::ui::i18n::{tr, Strings};
 use crate::ui::styles::{
-    COLOR_ACCENT_NEG, COLOR_ACCENT_POS, COLOR_BG, COLOR_TEXT_PRIMARY, COLOR_TEXT_SECONDARY,
+    COLOR_ACCENT_NEG, COLOR_ACCENT_POS, COLOR_BG, COLOR_TEXT_DISABLED, COLOR_TEXT_PRIMARY,
+    COLOR_TEXT_SECONDARY,
 };
+use crate::world::closures::RouteClosures;
 use crate::world::index::{deterministic_rumor, RumorKind, StaticWorldIndex, WorldIndex};
 
 #[derive(Resource, Default)]
 pub struct RoutePlannerState {
     pub last_forecast: Vec<RouteForecast>,
+    pub profits: ProfitCache,
+}
+
+/// Route profit estimates for one hub and hold. Prices only move at day
+/// rollover, so entries stay valid until the day, hub or cargo changes.
+#[derive(Debug, Clone, Default)]
+pub struct ProfitCache {
+    key: Option<(EconomyDay, HubId, Cargo)>,
+    estimates: Vec<RouteProfit>,
+}
+
+impl ProfitCache {
+    pub fn estimates(&self) -> &[RouteProfit] {
+        &self.estimates
+    }
+
+    pub fn estimate(&self, route: RouteId) -> Option<&RouteProfit> {
+        self.estimates
+            .iter()
+            .find(|estimate| estimate.route == route)
+    }
+
+    /// Recomputes the estimates when the cache key changed; returns whether
+    /// the stored estimates differ from before.
+    fn refresh(&mut self, app_state: &AppState, rp: &Rulepack) -> bool {
+        let key = (
+            app_state.econ.day,
+            app_state.last_hub,
+            app_state.cargo.clone(),
+        );
+        if self.key.as_ref() == Some(&key) {
+            return false;
+        }
+        let estimates =
+            route_profit_estimates(app_state.last_hub, &app_state.econ, rp, &app_state.cargo)
+                .unwrap_or_else(|err| {
+                    warn!("route profit estimates unavailable: {err:#}");
+                    Vec::new()
+                });
+        self.key = Some(key);
+        let changed = self.estimates != estimates;
+        self.estimates = estimates;
+        changed
+    }
 }
 
 pub struct RoutePlannerPlugin;
@@ -19,6 +76,7 @@ pub struct RoutePlannerPlugin;
 impl Plugin for RoutePlannerPlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<RoutePlannerState>()
+            .init_resource::<Strings>()
             .add_systems(Startup, spawn_route_planner_panel)
             .add_systems(Update, sync_route_planner_ui);
     }
@@ -29,17 +87,45 @@ pub struct RouteForecast {
     pub route: RouteId,
     pub weather: Weather,
     pub rumor: (RumorKind, u8),
+    pub estimated_danger: i32,
+    /// Closed by a [`RouteClosures`] entry on the forecast day.
+    pub closed: bool,
+}
+
+/// Danger the director would report at the start of a fresh leg on a route
+/// with `weather`, using the default mission parameters.
+pub fn estimate_route_danger(pp: Pp, weather: Weather, cfg: &DirectorCfg) -> i32 {
+    let budget = compute_spawn_budget(pp, weather, None, cfg);
+    danger_score(
+        &budget,
+        DEFAULT_MISSION_MINUTES,
+        DEFAULT_DENSITY_PER_10K,
+        DEFAULT_CADENCE_PER_MIN,
+        DEFAULT_PLAYER_RATING,
+    )
 }
 
-pub fn build_forecast(seed: u64, hub: HubId) -> Vec<RouteForecast> {
+pub fn build_forecast(
+    seed: u64,
+    hub: HubId,
+    pp: Pp,
+    day: EconomyDay,
+    closures: &RouteClosures,
+    cfg: &DirectorCfg,
+) -> Vec<RouteForecast> {
     let mut neighbors: SmallVec<[RouteId; 6]> = StaticWorldIndex::neighbors(hub);
     neighbors.sort_by_key(|route| route.0);
     neighbors
         .into_iter()
-        .map(|route| RouteForecast {
-            route,
-            weather: StaticWorldIndex::route_weather(route),
-            rumor: deterministic_rumor(seed, route),
+        .map(|route| {
+            let weather = StaticWorldIndex::route_weather(route);
+            RouteForecast {
+                route,
+                weather,
+                rumor: deterministic_rumor(seed, route),
+                estimated_danger: estimate_route_danger(pp, weather, cfg),
+                closed: closures.is_closed(route, day),
+            }
         })
         .collect()
 }
@@ -65,9 +151,19 @@ struct WeatherLabel;
 #[derive(Component)]
 struct RumorLabel;
 
+#[derive(Component)]
+struct DangerLabel;
+
+#[derive(Component)]
+struct ProfitLabel;
+
+#[derive(Component)]
+struct ClosedLabel;
+
 fn spawn_route_planner_panel(
     mut commands: Commands,
     asset_server: Option<Res<AssetServer>>,
+    strings: Res<Strings>,
     existing: Query<Entity, With<RoutePlannerRoot>>,
 ) {
     if existing.iter().next().is_some() {
@@ -100,7 +196,11 @@ fn spawn_route_planner_panel(
             Name::new("RoutePlannerPanel"),
         ))
         .with_children(|parent| {
-            parent.spawn((Text::new("Route Planner"), title_font, title_color));
+            parent.spawn((
+                Text::new(tr!(strings, "route_planner.title")),
+                title_font,
+                title_color,
+            ));
             parent.spawn((
                 Node {
                     flex_direction: FlexDirection::Column,
@@ -112,37 +212,74 @@ fn spawn_route_planner_panel(
         });
 }
 
+#[derive(SystemParam)]
+struct ForecastInputs<'w> {
+    director_cfg: Option<Res<'w, DirectorConfigResource>>,
+    rulepack: Option<Res<'w, Rulepack>>,
+    catalog: Option<Res<'w, CatalogHandle>>,
+    closures: Option<Res<'w, RouteClosures>>,
+}
+
+#[allow(clippy::too_many_arguments)]
 fn sync_route_planner_ui(
     mut commands: Commands,
     app_state: Option<Res<AppState>>,
+    inputs: ForecastInputs,
     planner_state: Option<ResMut<RoutePlannerState>>,
-    rows: Query<(Entity, &Children), With<RoutePlannerRows>>,
+    rows: Query<(Entity, Option<&Children>), With<RoutePlannerRows>>,
     hierarchy: Query<&Children>,
     asset_server: Option<Res<AssetServer>>,
+    strings: Res<Strings>,
 ) {
     let Some(app_state) = app_state else {
         return;
     };
+    let Some(director_cfg) = inputs.director_cfg.as_ref() else {
+        return;
+    };
     let Some(mut planner_state) = planner_state else {
         return;
     };
-    let Some((rows_entity, child_entities)) = rows
-        .iter()
-        .next()
-        .map(|(entity, children)| (entity, children.iter().collect::<Vec<Entity>>()))
-    else {
+    let Some((rows_entity, child_entities)) = rows.iter().next().map(|(entity, children)| {
+        let children = children.map(|children| children.iter().collect::<Vec<Entity>>());
+        (entity, children.unwrap_or_default())
+    }) else {
         return;
     };
 
-    if !app_state.is_changed() && !planner_state.last_forecast.is_empty() {
+    let closures_changed = inputs
+        .closures
+        .as_ref()
+        .is_some_and(|closures| closures.is_changed());
+    if !app_state.is_changed()
+        && !director_cfg.is_changed()
+        && !strings.is_changed()
+        && !closures_changed
+        && !planner_state.last_forecast.is_empty()
+    {
         return;
     }
 
-    let forecast = build_forecast(app_state.world_seed, app_state.last_hub);
-    if planner_state.last_forecast == forecast {
+    let no_closures = RouteClosures::default();
+    let forecast = build_forecast(
+        app_state.world_seed,
+        app_state.last_hub,
+        app_state.econ.pp,
+        app_state.econ.day,
+        inputs.closures.as_deref().unwrap_or(&no_closures),
+        &director_cfg.0,
+    );
+    // Estimates need the trading globals, which TradingPlugin installs along
+    // with the catalog handle.
+    let profits_changed = match (inputs.rulepack.as_ref(), inputs.catalog.as_ref()) {
+        (Some(rp), Some(_)) => planner_state.profits.refresh(&app_state, rp),
+        _ => false,
+    };
+    if planner_state.last_forecast == forecast && !profits_changed {
         return;
     }
     planner_state.last_forecast = forecast.clone();
+    let profits = planner_state.profits.clone();
 
     if !child_entities.is_empty() {
         let mut to_remove = Vec::new();
@@ -165,7 +302,7 @@ fn sync_route_planner_ui(
     commands.entity(rows_entity).with_children(|parent| {
         if forecast.is_empty() {
             parent.spawn((
-                Text::new("No routes available"),
+                Text::new(tr!(strings, "route_planner.no_routes")),
                 body_font.clone(),
                 TextColor(COLOR_TEXT_SECONDARY),
             ));
@@ -176,6 +313,7 @@ fn sync_route_planner_ui(
             let route = entry.route;
             let weather = entry.weather;
             let (rumor_kind, confidence) = entry.rumor;
+            let closed = entry.closed;
             parent
                 .spawn((
                     Node {
@@ -190,71 +328,133 @@ fn sync_route_planner_ui(
                 ))
                 .with_children(|row| {
                     row.spawn((
-                        Text::new(route_label(route)),
+                        Text::new(route_label(route, &strings)),
                         body_font.clone(),
-                        TextColor(COLOR_TEXT_PRIMARY),
+                        TextColor(row_color(closed, COLOR_TEXT_PRIMARY)),
                         RouteLabel,
                     ));
                     row.spawn((
-                        Text::new(weather_display(weather)),
+                        Text::new(weather_display(weather, &strings)),
                         body_font.clone(),
-                        TextColor(COLOR_TEXT_SECONDARY),
+                        TextColor(row_color(closed, COLOR_TEXT_SECONDARY)),
                         WeatherLabel,
                     ));
                     row.spawn((
-                        Text::new(rumor_display(rumor_kind, confidence)),
+                        Text::new(profit_display(profits.estimate(route), &strings)),
                         body_font.clone(),
-                        TextColor(rumor_color(rumor_kind)),
+                        TextColor(row_color(closed, profit_color(profits.estimate(route)))),
+                        ProfitLabel,
+                    ));
+                    row.spawn((
+                        Text::new(rumor_display(rumor_kind, confidence, &strings)),
+                        body_font.clone(),
+                        TextColor(row_color(closed, rumor_color(rumor_kind))),
                         RumorLabel,
                     ));
+                    row.spawn((
+                        Text::new(danger_display(entry.estimated_danger, &strings)),
+                        body_font.clone(),
+                        TextColor(row_color(closed, COLOR_TEXT_SECONDARY)),
+                        DangerLabel,
+                    ));
+                    if closed {
+                        row.spawn((
+                            Text::new(tr!(strings, "route_planner.closed")),
+                            body_font.clone(),
+                            TextColor(COLOR_ACCENT_NEG),
+                            ClosedLabel,
+                        ));
+                    }
                 });
         }
     });
 }
 
-fn route_label(route: RouteId) -> String {
-    format!("Route {}", route.0)
+fn route_label(route: RouteId, strings: &Strings) -> String {
+    tr!(strings, "route_planner.route", route = route.0)
+}
+
+fn weather_display(weather: Weather, strings: &Strings) -> String {
+    tr!(
+        strings,
+        "route_planner.weather",
+        icon = strings.get(weather_icon_key(weather)),
+        name = strings.get(weather_name_key(weather)),
+    )
+}
+
+fn rumor_display(kind: RumorKind, confidence: u8, strings: &Strings) -> String {
+    tr!(
+        strings,
+        "route_planner.rumor",
+        icon = strings.get(rumor_icon_key(kind)),
+        name = strings.get(rumor_name_key(kind)),
+        confidence = confidence,
+    )
 }
 
-fn weather_display(weather: Weather) -> String {
-    format!("{} {}", weather_icon(weather), weather_name(weather))
+fn profit_display(estimate: Option<&RouteProfit>, strings: &Strings) -> String {
+    match estimate {
+        Some(estimate) => tr!(
+            strings,
+            "route_planner.profit",
+            margin = format!("{:+}", estimate.margin_cents.as_i64()),
+        ),
+        None => tr!(strings, "route_planner.profit_unknown"),
+    }
 }
 
-fn rumor_display(kind: RumorKind, confidence: u8) -> String {
-    format!("{} {} {confidence}%", rumor_icon(kind), rumor_name(kind))
+fn profit_color(estimate: Option<&RouteProfit>) -> Color {
+    match estimate {
+        Some(estimate) if estimate.margin_cents > MoneyCents::ZERO => COLOR_ACCENT_POS,
+        _ => COLOR_TEXT_SECONDARY,
+    }
 }
 
-fn weather_icon(weather: Weather) -> &'static str {
+/// Greys out every label on a closed route's row.
+fn row_color(closed: bool, color: Color) -> Color {
+    if closed {
+        COLOR_TEXT_DISABLED
+    } else {
+        color
+    }
+}
+
+fn danger_display(danger: i32, strings: &Strings) -> String {
+    tr!(strings, "route_planner.danger", danger = danger)
+}
+
+fn weather_icon_key(weather: Weather) -> &'static str {
     match weather {
-        Weather::Clear => "☀",
-        Weather::Rains => "🌧",
-        Weather::Fog => "🌫",
-        Weather::Windy => "💨",
+        Weather::Clear => "weather_icon.clear",
+        Weather::Rains => "weather_icon.rains",
+        Weather::Fog => "weather_icon.fog",
+        Weather::Windy => "weather_icon.windy",
     }
 }
 
-fn weather_name(weather: Weather) -> &'static str {
+fn weather_name_key(weather: Weather) -> &'static str {
     match weather {
-        Weather::Clear => "Clear",
-        Weather::Rains => "Rains",
-        Weather::Fog => "Fog",
-        Weather::Windy => "Windy",
+        Weather::Clear => "weather.clear",
+        Weather::Rains => "weather.rains",
+        Weather::Fog => "weather.fog",
+        Weather::Windy => "weather.windy",
     }
 }
 
-fn rumor_icon(kind: RumorKind) -> &'static str {
+fn rumor_icon_key(kind: RumorKind) -> &'static str {
     match kind {
-        RumorKind::Wind => "🌀",
-        RumorKind::Fog => "🌁",
-        RumorKind::Patrol => "🚨",
+        RumorKind::Wind => "rumor_icon.wind",
+        RumorKind::Fog => "rumor_icon.fog",
+        RumorKind::Patrol => "rumor_icon.patrol",
     }
 }
 
-fn rumor_name(kind: RumorKind) -> &'static str {
+fn rumor_name_key(kind: RumorKind) -> &'static str {
     match kind {
-        RumorKind::Wind => "Wind",
-        RumorKind::Fog => "Fog",
-        RumorKind::Patrol => "Patrol",
+        RumorKind::Wind => "rumor.wind",
+        RumorKind::Fog => "rumor.fog",
+        RumorKind::Patrol => "rumor.patrol",
     }
 }
 
@@ -283,20 +483,32 @@ fn load_font(asset_server: Option<&AssetServer>, path: &'static str) -> Handle<F
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::systems::director::config::load_director_cfg;
+    use crate::systems::director::director_cfg_path;
+    use crate::ui::i18n::DEFAULT_LANG;
     use bevy::app::App;
     use bevy::prelude::{Children, Text};
     use bevy::MinimalPlugins;
 
     #[test]
     fn planner_ui_matches_forecast_for_fixed_seed() {
+        for lang in [DEFAULT_LANG, "xx"] {
+            planner_ui_matches_forecast_in(lang);
+        }
+    }
+
+    fn planner_ui_matches_forecast_in(lang: &str) {
+        let strings = Strings::load(lang).expect("strings");
         let mut app = App::new();
         app.add_plugins(MinimalPlugins);
+        app.insert_resource(strings.clone());
 
         app.init_resource::<AppState>();
+        app.insert_resource(DirectorConfigResource(test_cfg()));
         {
             let mut state = app.world_mut().resource_mut::<AppState>();
             state.world_seed = 0xDEADBEEFCAFEBABE;
-            state.last_hub = HubId(7);
+            state.last_hub = HubId(2);
         }
 
         app.add_plugins(RoutePlannerPlugin);
@@ -305,7 +517,15 @@ mod tests {
         app.update();
 
         let state = app.world().resource::<AppState>().clone();
-        let expected = build_forecast(state.world_seed, state.last_hub);
+        let cfg = test_cfg();
+        let expected = build_forecast(
+            state.world_seed,
+            state.last_hub,
+            state.econ.pp,
+            state.econ.day,
+            &RouteClosures::default(),
+            &cfg,
+        );
 
         let last_forecast = {
             let planner_state = app.world().resource::<RoutePlannerState>();
@@ -333,13 +553,166 @@ mod tests {
         let mut expected_sorted = expected.clone();
         expected_sorted.sort_by_key(|entry| entry.route.0);
 
+        assert!(!expected_sorted.is_empty());
         assert_eq!(rows.len(), expected_sorted.len());
         for (actual, target) in rows.iter().zip(expected_sorted.iter()) {
             let (_, labels) = actual;
-            assert_eq!(labels.len(), 3);
-            assert_eq!(labels[0], route_label(target.route));
-            assert_eq!(labels[1], weather_display(target.weather));
-            assert_eq!(labels[2], rumor_display(target.rumor.0, target.rumor.1));
+            assert_eq!(labels.len(), 5);
+            assert_eq!(labels[0], route_label(target.route, &strings));
+            assert_eq!(labels[1], weather_display(target.weather, &strings));
+            assert_eq!(labels[2], profit_display(None, &strings));
+            assert_eq!(
+                labels[3],
+                rumor_display(target.rumor.0, target.rumor.1, &strings)
+            );
+            assert_eq!(labels[4], danger_display(target.estimated_danger, &strings));
+        }
+    }
+
+    #[test]
+    fn forecast_danger_matches_director_scoring() {
+        let cfg = test_cfg();
+        let pp = Pp(120);
+        let forecast = build_forecast(
+            0xDEADBEEFCAFEBABE,
+            HubId(2),
+            pp,
+            EconomyDay(0),
+            &RouteClosures::default(),
+            &cfg,
+        );
+        assert!(!forecast.is_empty());
+
+        for entry in &forecast {
+            let budget = compute_spawn_budget(pp, entry.weather, None, &cfg);
+            let expected = danger_score(
+                &budget,
+                DEFAULT_MISSION_MINUTES,
+                DEFAULT_DENSITY_PER_10K,
+                DEFAULT_CADENCE_PER_MIN,
+                DEFAULT_PLAYER_RATING,
+            );
+            assert_eq!(entry.estimated_danger, expected, "route {}", entry.route.0);
+        }
+
+        let danger_for = |weather: Weather| {
+            forecast
+                .iter()
+                .find(|entry| entry.weather == weather)
+                .map(|entry| entry.estimated_danger)
+                .unwrap_or_else(|| panic!("hub 2 should have a {weather:?} route"))
+        };
+        assert!(danger_for(Weather::Fog) > danger_for(Weather::Clear));
+    }
+
+    #[test]
+    fn planner_ui_shows_cached_profit_estimates() {
+        use crate::systems::economy::rulepack::load_rulepack;
+        use crate::systems::economy::{BasisBp, CommodityId};
+        use crate::systems::trading::TradingPlugin;
+
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins);
+        app.add_plugins(TradingPlugin);
+        let rp_path = std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("../../assets/rulepacks/day_001.toml");
+        app.insert_resource(
+            load_rulepack(rp_path.to_str().expect("rulepack path")).expect("rulepack"),
+        );
+        app.insert_resource(DirectorConfigResource(test_cfg()));
+        let mut state = AppState {
+            last_hub: HubId(1),
+            cargo: Cargo {
+                capacity_mass_kg: 100,
+                capacity_volume_l: 100,
+                ..Cargo::default()
+            },
+            ..AppState::default()
+        };
+        state
+            .econ
+            .basis_bp
+            .insert((HubId(2), CommodityId(3)), BasisBp(600));
+        app.insert_resource(state);
+        app.add_plugins(RoutePlannerPlugin);
+
+        app.update();
+        app.update();
+
+        let profits = app.world().resource::<RoutePlannerState>().profits.clone();
+        let to_hub_2 = profits.estimate(RouteId(1)).expect("route 1 estimate");
+        assert_eq!(to_hub_2.commodity, Some(CommodityId(3)));
+        assert!(to_hub_2.margin_cents > MoneyCents::ZERO);
+
+        let world = app.world_mut();
+        let mut row_query = world.query::<(&ForecastRow, &Children)>();
+        let mut text_query = world.query::<&Text>();
+        let (_, children) = row_query
+            .iter(world)
+            .find(|(row, _)| row.route == RouteId(1))
+            .expect("route 1 row");
+        let labels: Vec<String> = children
+            .iter()
+            .filter_map(|child| text_query.get(world, child).ok())
+            .map(|text| text.0.clone())
+            .collect();
+        let strings = Strings::load(DEFAULT_LANG).expect("strings");
+        assert_eq!(labels[2], profit_display(Some(to_hub_2), &strings));
+    }
+
+    #[test]
+    fn closed_routes_render_greyed_out() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins);
+        app.insert_resource(DirectorConfigResource(test_cfg()));
+        let mut state = AppState {
+            last_hub: HubId(2),
+            ..AppState::default()
+        };
+        state.econ.day = EconomyDay(4);
+        app.insert_resource(state);
+        let mut closures = RouteClosures::default();
+        closures.close(RouteId(2), EconomyDay(6));
+        app.insert_resource(closures);
+        app.add_plugins(RoutePlannerPlugin);
+
+        app.update();
+        app.update();
+
+        let forecast = app
+            .world()
+            .resource::<RoutePlannerState>()
+            .last_forecast
+            .clone();
+        let closed: Vec<RouteId> = forecast
+            .iter()
+            .filter(|entry| entry.closed)
+            .map(|entry| entry.route)
+            .collect();
+        assert_eq!(closed, vec![RouteId(2)]);
+
+        let world = app.world_mut();
+        let mut row_query = world.query::<(&ForecastRow, &Children)>();
+        let mut label_query = world.query::<(&Text, &TextColor)>();
+        for (row, children) in row_query.iter(world) {
+            let labels: Vec<(String, Color)> = children
+                .iter()
+                .filter_map(|child| label_query.get(world, child).ok())
+                .map(|(text, color)| (text.0.clone(), color.0))
+                .collect();
+            if row.route == RouteId(2) {
+                assert_eq!(labels.len(), 6);
+                assert_eq!(labels[0].1, COLOR_TEXT_DISABLED);
+                assert_eq!(labels[5].0, "Closed");
+            } else {
+                assert_eq!(labels.len(), 5);
+                assert_eq!(labels[0].1, COLOR_TEXT_PRIMARY);
+            }
         }
     }
+
+    fn test_cfg() -> DirectorCfg {
+        let path = director_cfg_path();
+        load_director_cfg(path.to_str().expect("cfg path")).expect("director config")
+    }
 }
@@ -5,3 +5,5 @@ pub const COLOR_ACCENT_POS: Color = Color::srgb(0.29, 0.71, 0.31);
 pub const COLOR_ACCENT_NEG: Color = Color::srgb(0.82, 0.24, 0.24);
 pub const COLOR_TEXT_PRIMARY: Color = Color::srgb(0.92, 0.94, 0.96);
 pub const COLOR_TEXT_SECONDARY: Color = Color::srgb(0.62, 0.66, 0.70);
+pub const COLOR_TEXT_DISABLED: Color = Color::srgb(0.38, 0.41, 0.45);
+pub const COLOR_FOCUS: Color = Color::srgb(0.95, 0.78, 0.25);
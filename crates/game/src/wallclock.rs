@@ -0,0 +1,20 @@
+//! The game crate's only wall-clock reads. Soak leg timings, bench
+//! throughput and tick profiles are measured here; none of them reach the
+//! simulation or the command stream. `ci/grep_banned_random.sh` allowlists
+//! this file and no other.
+
+use std::time::{Duration, Instant};
+
+/// Wall-clock time since [`Stopwatch::start`].
+#[derive(Debug, Clone, Copy)]
+pub struct Stopwatch(Instant);
+
+impl Stopwatch {
+    pub fn start() -> Self {
+        Self(Instant::now())
+    }
+
+    pub fn elapsed(&self) -> Duration {
+        self.0.elapsed()
+    }
+}
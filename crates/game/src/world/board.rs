@@ -0,0 +1,174 @@
+use serde::{Deserialize, Serialize};
+
+/// Default edge length, in cells, of a leg board.
+pub const BOARD_SIZE: u32 = 64;
+/// Default cell edge length in millimetres.
+pub const DEFAULT_CELL_MM: u32 = 1_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct Point {
+    pub x: i32,
+    pub y: i32,
+}
+
+impl Point {
+    pub const fn new(x: i32, y: i32) -> Self {
+        Self { x, y }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CoverKind {
+    Rock,
+    Crate,
+    Brush,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Cell {
+    #[default]
+    Open,
+    Wall,
+    Cover(CoverKind),
+}
+
+impl Cell {
+    /// Walls block movement; cover slows it but stays walkable.
+    pub fn is_walkable(self) -> bool {
+        !matches!(self, Cell::Wall)
+    }
+}
+
+/// Axis-aligned cell rectangle, inclusive of `(x, y)` and exclusive of
+/// `(x + w, y + h)`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Rect {
+    pub x: i32,
+    pub y: i32,
+    pub w: u32,
+    pub h: u32,
+}
+
+impl Rect {
+    pub fn contains(&self, p: Point) -> bool {
+        let dx = i64::from(p.x) - i64::from(self.x);
+        let dy = i64::from(p.y) - i64::from(self.y);
+        dx >= 0 && dy >= 0 && dx < i64::from(self.w) && dy < i64::from(self.h)
+    }
+
+    /// Cells covered by the rectangle in row-major order.
+    pub fn cells(&self) -> impl Iterator<Item = Point> + '_ {
+        (0..self.h as i32).flat_map(move |dy| {
+            (0..self.w as i32).map(move |dx| Point::new(self.x + dx, self.y + dy))
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ZoneKind {
+    Evac,
+    Objective,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Zone {
+    pub kind: ZoneKind,
+    pub rect: Rect,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SpawnPoints {
+    pub player: Vec<Point>,
+    pub enemy: Vec<Point>,
+}
+
+/// Tile layout of a leg: a row-major grid of cells plus spawn points and
+/// zones, all in cell coordinates.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Board {
+    pub width: u32,
+    pub height: u32,
+    pub cell_mm: u32,
+    pub cells: Vec<Cell>,
+    pub spawns: SpawnPoints,
+    pub zones: Vec<Zone>,
+    /// Attempts [`generate_board`](super::boardgen::generate_board) threw
+    /// away before this board validated. Part of [`board_hash`].
+    #[serde(default)]
+    pub retries: u32,
+}
+
+impl Board {
+    /// An all-open board with no spawns or zones.
+    pub fn empty(width: u32, height: u32, cell_mm: u32) -> Self {
+        Self {
+            width,
+            height,
+            cell_mm,
+            cells: vec![Cell::Open; (width as usize) * (height as usize)],
+            spawns: SpawnPoints::default(),
+            zones: Vec::new(),
+            retries: 0,
+        }
+    }
+
+    pub fn in_bounds(&self, p: Point) -> bool {
+        p.x >= 0 && p.y >= 0 && (p.x as u32) < self.width && (p.y as u32) < self.height
+    }
+
+    pub fn index(&self, p: Point) -> Option<usize> {
+        self.in_bounds(p)
+            .then(|| p.y as usize * self.width as usize + p.x as usize)
+    }
+
+    pub fn point(&self, index: usize) -> Point {
+        let width = self.width as usize;
+        Point::new((index % width) as i32, (index / width) as i32)
+    }
+
+    pub fn cell(&self, p: Point) -> Option<Cell> {
+        self.index(p).map(|idx| self.cells[idx])
+    }
+
+    pub fn set_cell(&mut self, p: Point, cell: Cell) {
+        if let Some(idx) = self.index(p) {
+            self.cells[idx] = cell;
+        }
+    }
+
+    pub fn is_walkable(&self, p: Point) -> bool {
+        self.cell(p).is_some_and(Cell::is_walkable)
+    }
+
+    /// Centre of cell `p` in millimetres along the board's x and y axes.
+    pub fn cell_center_mm(&self, p: Point) -> (i32, i32) {
+        let cell = self.cell_mm as i32;
+        (p.x * cell + cell / 2, p.y * cell + cell / 2)
+    }
+
+    /// World position, in millimetres, for the `n`-th enemy spawned on this
+    /// board. Enemies cycle through `spawns.enemy` in order; the board's y
+    /// axis maps to world z at ground level.
+    pub fn enemy_spawn_mm(&self, n: u64) -> Option<[i32; 3]> {
+        let count = self.spawns.enemy.len() as u64;
+        if count == 0 {
+            return None;
+        }
+        let cell = self.spawns.enemy[(n % count) as usize];
+        let (x_mm, z_mm) = self.cell_center_mm(cell);
+        Some([x_mm, 0, z_mm])
+    }
+
+    pub fn evac_zones(&self) -> impl Iterator<Item = &Zone> {
+        self.zones.iter().filter(|zone| zone.kind == ZoneKind::Evac)
+    }
+}
+
+/// Identity hash of a board: blake3 over its canonical JSON encoding.
+pub fn board_hash(board: &Board) -> Result<String, repro::CanonicalJsonError> {
+    let bytes = repro::canonical_json_bytes(board)?;
+    Ok(blake3::hash(&bytes).to_hex().to_string())
+}
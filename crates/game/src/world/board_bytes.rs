@@ -0,0 +1,269 @@
+//! Compact binary encoding of a [`Board`].
+//!
+//! Layout, all integers little-endian:
+//!
+//! ```text
+//! magic   b"DTBD"
+//! version u16
+//! width, height, cell_mm, retries               u32 each
+//! cells   u32 count, then one tag byte per cell
+//! player  u32 count, then (x i32, y i32) per point
+//! enemy   u32 count, then (x i32, y i32) per point
+//! zones   u32 count, then (kind u8, x i32, y i32, w u32, h u32) per zone
+//! ```
+//!
+//! The canonical-JSON [`board_hash`] stays the board's identity; the binary
+//! form is only a faster container for it.
+
+use thiserror::Error;
+
+use super::board::{board_hash, Board, Cell, CoverKind, Point, Rect, SpawnPoints, Zone, ZoneKind};
+
+pub const BOARD_MAGIC: [u8; 4] = *b"DTBD";
+/// Bumped whenever the layout changes; older files are rejected.
+pub const BOARD_BYTES_VERSION: u16 = 2;
+
+/// Magic, version, the three dimensions, the retry count and the four
+/// section counts.
+const HEADER_LEN: usize = 38;
+const POINT_LEN: usize = 8;
+const ZONE_LEN: usize = 17;
+
+#[derive(Debug, Error)]
+pub enum BoardBytesError {
+    #[error("not a board file: bad magic")]
+    BadMagic,
+    #[error("board file version {found} is not supported (expected {BOARD_BYTES_VERSION})")]
+    UnsupportedVersion { found: u16 },
+    #[error("board file truncated at byte {offset}")]
+    Truncated { offset: usize },
+    #[error("invalid {what} tag {tag} at byte {offset}")]
+    InvalidTag {
+        what: &'static str,
+        tag: u8,
+        offset: usize,
+    },
+    #[error("{count} cells do not fill a {width}x{height} board")]
+    CellCountMismatch {
+        width: u32,
+        height: u32,
+        count: usize,
+    },
+    #[error("{count} trailing bytes after the board")]
+    TrailingBytes { count: usize },
+    #[error(transparent)]
+    Hash(#[from] repro::CanonicalJsonError),
+}
+
+impl Board {
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(
+            HEADER_LEN
+                + self.cells.len()
+                + (self.spawns.player.len() + self.spawns.enemy.len()) * POINT_LEN
+                + self.zones.len() * ZONE_LEN,
+        );
+        out.extend_from_slice(&BOARD_MAGIC);
+        out.extend_from_slice(&BOARD_BYTES_VERSION.to_le_bytes());
+        for value in [self.width, self.height, self.cell_mm, self.retries] {
+            out.extend_from_slice(&value.to_le_bytes());
+        }
+        put_len(&mut out, self.cells.len());
+        out.extend(self.cells.iter().map(|&cell| cell_tag(cell)));
+        for points in [&self.spawns.player, &self.spawns.enemy] {
+            put_len(&mut out, points.len());
+            for point in points {
+                out.extend_from_slice(&point.x.to_le_bytes());
+                out.extend_from_slice(&point.y.to_le_bytes());
+            }
+        }
+        put_len(&mut out, self.zones.len());
+        for zone in &self.zones {
+            out.push(match zone.kind {
+                ZoneKind::Evac => 0,
+                ZoneKind::Objective => 1,
+            });
+            out.extend_from_slice(&zone.rect.x.to_le_bytes());
+            out.extend_from_slice(&zone.rect.y.to_le_bytes());
+            out.extend_from_slice(&zone.rect.w.to_le_bytes());
+            out.extend_from_slice(&zone.rect.h.to_le_bytes());
+        }
+        out
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, BoardBytesError> {
+        let mut reader = Reader { bytes, offset: 0 };
+        if reader.take(BOARD_MAGIC.len())? != BOARD_MAGIC {
+            return Err(BoardBytesError::BadMagic);
+        }
+        let version = reader.u16()?;
+        if version != BOARD_BYTES_VERSION {
+            return Err(BoardBytesError::UnsupportedVersion { found: version });
+        }
+        let width = reader.u32()?;
+        let height = reader.u32()?;
+        let cell_mm = reader.u32()?;
+        let retries = reader.u32()?;
+
+        let count = reader.count(1)?;
+        if count as u64 != u64::from(width) * u64::from(height) {
+            return Err(BoardBytesError::CellCountMismatch {
+                width,
+                height,
+                count,
+            });
+        }
+        let start = reader.offset;
+        let cells = reader
+            .take(count)?
+            .iter()
+            .enumerate()
+            .map(|(idx, &tag)| cell_from_tag(tag, start + idx))
+            .collect::<Result<_, _>>()?;
+        let player = reader.points()?;
+        let enemy = reader.points()?;
+
+        let count = reader.count(ZONE_LEN)?;
+        let mut zones = Vec::with_capacity(count);
+        for _ in 0..count {
+            let offset = reader.offset;
+            let kind = match reader.u8()? {
+                0 => ZoneKind::Evac,
+                1 => ZoneKind::Objective,
+                tag => {
+                    return Err(BoardBytesError::InvalidTag {
+                        what: "zone",
+                        tag,
+                        offset,
+                    })
+                }
+            };
+            let rect = Rect {
+                x: reader.i32()?,
+                y: reader.i32()?,
+                w: reader.u32()?,
+                h: reader.u32()?,
+            };
+            zones.push(Zone { kind, rect });
+        }
+
+        let trailing = bytes.len() - reader.offset;
+        if trailing != 0 {
+            return Err(BoardBytesError::TrailingBytes { count: trailing });
+        }
+        Ok(Self {
+            width,
+            height,
+            cell_mm,
+            cells,
+            spawns: SpawnPoints { player, enemy },
+            zones,
+            retries,
+        })
+    }
+}
+
+/// Decodes a binary board and returns its canonical-JSON [`board_hash`], so
+/// a `.bin` can be checked against the `.hash` written beside it.
+pub fn board_hash_from_bytes(bytes: &[u8]) -> Result<String, BoardBytesError> {
+    Ok(board_hash(&Board::from_bytes(bytes)?)?)
+}
+
+fn put_len(out: &mut Vec<u8>, len: usize) {
+    let len = u32::try_from(len).expect("board section longer than u32::MAX");
+    out.extend_from_slice(&len.to_le_bytes());
+}
+
+fn cell_tag(cell: Cell) -> u8 {
+    match cell {
+        Cell::Open => 0,
+        Cell::Wall => 1,
+        Cell::Cover(CoverKind::Rock) => 2,
+        Cell::Cover(CoverKind::Crate) => 3,
+        Cell::Cover(CoverKind::Brush) => 4,
+    }
+}
+
+fn cell_from_tag(tag: u8, offset: usize) -> Result<Cell, BoardBytesError> {
+    Ok(match tag {
+        0 => Cell::Open,
+        1 => Cell::Wall,
+        2 => Cell::Cover(CoverKind::Rock),
+        3 => Cell::Cover(CoverKind::Crate),
+        4 => Cell::Cover(CoverKind::Brush),
+        tag => {
+            return Err(BoardBytesError::InvalidTag {
+                what: "cell",
+                tag,
+                offset,
+            })
+        }
+    })
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+    offset: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], BoardBytesError> {
+        let slice = self
+            .offset
+            .checked_add(len)
+            .and_then(|end| self.bytes.get(self.offset..end))
+            .ok_or(BoardBytesError::Truncated {
+                offset: self.bytes.len(),
+            })?;
+        self.offset += len;
+        Ok(slice)
+    }
+
+    fn array<const N: usize>(&mut self) -> Result<[u8; N], BoardBytesError> {
+        let mut out = [0u8; N];
+        out.copy_from_slice(self.take(N)?);
+        Ok(out)
+    }
+
+    fn u8(&mut self) -> Result<u8, BoardBytesError> {
+        Ok(self.array::<1>()?[0])
+    }
+
+    fn u16(&mut self) -> Result<u16, BoardBytesError> {
+        Ok(u16::from_le_bytes(self.array()?))
+    }
+
+    fn u32(&mut self) -> Result<u32, BoardBytesError> {
+        Ok(u32::from_le_bytes(self.array()?))
+    }
+
+    fn i32(&mut self) -> Result<i32, BoardBytesError> {
+        Ok(i32::from_le_bytes(self.array()?))
+    }
+
+    /// Reads a section count, rejecting counts the remaining bytes cannot
+    /// hold before anything is allocated for them.
+    fn count(&mut self, item_len: usize) -> Result<usize, BoardBytesError> {
+        let count = self.u32()? as usize;
+        let remaining = self.bytes.len() - self.offset;
+        if count > remaining / item_len {
+            return Err(BoardBytesError::Truncated {
+                offset: self.bytes.len(),
+            });
+        }
+        Ok(count)
+    }
+
+    fn points(&mut self) -> Result<Vec<Point>, BoardBytesError> {
+        let count = self.count(POINT_LEN)?;
+        let mut points = Vec::with_capacity(count);
+        for _ in 0..count {
+            points.push(Point::new(self.i32()?, self.i32()?));
+        }
+        Ok(points)
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/board_bytes.rs"]
+mod tests;
@@ -0,0 +1,104 @@
+use super::board::{Board, Cell, CoverKind, Point};
+use crate::systems::economy::Weather;
+
+/// Seed used by [`has_los`] when the caller has no leg seed to hand.
+pub const DEFAULT_LOS_SEED: u64 = 0;
+
+/// Sight range, in metres, for each weather condition.
+pub fn weather_los_m(weather: Weather) -> u32 {
+    match weather {
+        Weather::Clear => 60,
+        Weather::Windy => 48,
+        Weather::Rains => 36,
+        Weather::Fog => 20,
+    }
+}
+
+/// Chance, in basis points, that a cover cell on the sight line blocks it.
+pub fn cover_block_bp(kind: CoverKind) -> u32 {
+    match kind {
+        CoverKind::Rock => 7_500,
+        CoverKind::Crate => 5_000,
+        CoverKind::Brush => 2_500,
+    }
+}
+
+pub fn has_los(board: &Board, a: Point, b: Point, max_range_m: u32, cell_mm: u32) -> bool {
+    has_los_seeded(board, a, b, max_range_m, cell_mm, DEFAULT_LOS_SEED)
+}
+
+/// Line of sight between the centres of cells `a` and `b`. Walls on the
+/// Bresenham line always block; cover blocks when a draw keyed on `seed`, the
+/// endpoints and the cell falls under [`cover_block_bp`]. The endpoints
+/// themselves never block, and the result is the same in both directions.
+pub fn has_los_seeded(
+    board: &Board,
+    a: Point,
+    b: Point,
+    max_range_m: u32,
+    cell_mm: u32,
+    seed: u64,
+) -> bool {
+    if !board.is_walkable(a) || !board.is_walkable(b) {
+        return false;
+    }
+    if !within_range(a, b, max_range_m, cell_mm) {
+        return false;
+    }
+    let (from, to) = if a <= b { (a, b) } else { (b, a) };
+    line_cells(from, to)
+        .into_iter()
+        .filter(|&p| p != from && p != to)
+        .all(|p| match board.cell(p) {
+            Some(Cell::Open) => true,
+            Some(Cell::Wall) | None => false,
+            Some(Cell::Cover(kind)) => cover_draw_bp(seed, from, to, p) >= cover_block_bp(kind),
+        })
+}
+
+fn within_range(a: Point, b: Point, max_range_m: u32, cell_mm: u32) -> bool {
+    let dx = i128::from(a.x) - i128::from(b.x);
+    let dy = i128::from(a.y) - i128::from(b.y);
+    let cell = i128::from(cell_mm);
+    let range_mm = i128::from(max_range_m) * 1_000;
+    (dx * dx + dy * dy) * cell * cell <= range_mm * range_mm
+}
+
+/// Cells on the Bresenham line from `a` to `b`, inclusive of both ends.
+pub fn line_cells(a: Point, b: Point) -> Vec<Point> {
+    let dx = (b.x - a.x).abs();
+    let dy = -(b.y - a.y).abs();
+    let sx = if a.x < b.x { 1 } else { -1 };
+    let sy = if a.y < b.y { 1 } else { -1 };
+    let mut err = dx + dy;
+    let mut p = a;
+    let mut cells = vec![p];
+    while p != b {
+        let e2 = 2 * err;
+        if e2 >= dy {
+            err += dy;
+            p.x += sx;
+        }
+        if e2 <= dx {
+            err += dx;
+            p.y += sy;
+        }
+        cells.push(p);
+    }
+    cells
+}
+
+fn cover_draw_bp(seed: u64, from: Point, to: Point, cell: Point) -> u32 {
+    let mut key = [0u8; 24];
+    for (chunk, value) in key
+        .chunks_exact_mut(4)
+        .zip([from.x, from.y, to.x, to.y, cell.x, cell.y])
+    {
+        chunk.copy_from_slice(&value.to_le_bytes());
+    }
+    (wyhash::wyhash(&key, seed) % 10_000) as u32
+}
+
+#[cfg(test)]
+#[path = "tests/board_los.rs"]
+mod board_los_tests;
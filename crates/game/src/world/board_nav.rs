@@ -0,0 +1,175 @@
+//! Step costs and the grid searches built on them. [`path_cost`] on a
+//! [`Board`], [`NavGrid`](super::nav::NavGrid) paths and the reachability
+//! checks in [`validate_board`](super::boardcheck::validate_board) all run
+//! through the one A* and flood fill below.
+
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, VecDeque};
+
+use fixedbitset::FixedBitSet;
+
+use super::board::{Board, Cell, Point};
+
+/// Cost of one orthogonal step onto an open cell.
+pub const STEP_COST: u32 = 10;
+/// Extra cost for stepping onto a cover cell.
+pub const COVER_PENALTY: u32 = 15;
+
+/// Cost of entering `cell`, or `None` for walls.
+pub fn enter_cost(cell: Cell) -> Option<u32> {
+    match cell {
+        Cell::Open => Some(STEP_COST),
+        Cell::Cover(_) => Some(STEP_COST + COVER_PENALTY),
+        Cell::Wall => None,
+    }
+}
+
+/// A row-major grid the searches can walk.
+pub(crate) trait CostGrid {
+    fn width(&self) -> u32;
+    fn height(&self) -> u32;
+    /// Cost of entering the cell at `index`, or `None` when it blocks.
+    fn cost_at(&self, index: usize) -> Option<u32>;
+
+    fn index(&self, p: Point) -> Option<usize> {
+        let in_bounds =
+            p.x >= 0 && p.y >= 0 && (p.x as u32) < self.width() && (p.y as u32) < self.height();
+        in_bounds.then(|| p.y as usize * self.width() as usize + p.x as usize)
+    }
+
+    fn point(&self, index: usize) -> Point {
+        let width = self.width() as usize;
+        Point::new((index % width) as i32, (index / width) as i32)
+    }
+
+    fn len(&self) -> usize {
+        self.width() as usize * self.height() as usize
+    }
+}
+
+impl CostGrid for Board {
+    fn width(&self) -> u32 {
+        self.width
+    }
+
+    fn height(&self) -> u32 {
+        self.height
+    }
+
+    fn cost_at(&self, index: usize) -> Option<u32> {
+        self.cells.get(index).copied().and_then(enter_cost)
+    }
+}
+
+/// Result of one [`search`].
+pub(crate) struct Search {
+    /// Cost and cells of the cheapest path, both ends included.
+    pub path: Option<(u32, Vec<Point>)>,
+    /// Cells expanded; each is expanded at most once.
+    pub expanded: usize,
+}
+
+/// Neighbours of `p` in the fixed order every search visits them.
+fn neighbors(p: Point) -> [Point; 4] {
+    [
+        Point::new(p.x + 1, p.y),
+        Point::new(p.x - 1, p.y),
+        Point::new(p.x, p.y + 1),
+        Point::new(p.x, p.y - 1),
+    ]
+}
+
+/// 4-connected A* from `from` to `to` with a Manhattan heuristic. Ties in
+/// the open set are broken by lower cell index, so the same grid always
+/// yields the same path.
+pub(crate) fn search(grid: &impl CostGrid, from: Point, to: Point) -> Search {
+    let mut result = Search {
+        path: None,
+        expanded: 0,
+    };
+    let open_cell = |p: Point| grid.index(p).filter(|&idx| grid.cost_at(idx).is_some());
+    let (Some(start), Some(goal)) = (open_cell(from), open_cell(to)) else {
+        return result;
+    };
+
+    let heuristic = |p: Point| (p.x.abs_diff(to.x) + p.y.abs_diff(to.y)) * STEP_COST;
+    let len = grid.len();
+    let mut best = vec![u32::MAX; len];
+    let mut parent = vec![usize::MAX; len];
+    let mut closed = FixedBitSet::with_capacity(len);
+    let mut open = BinaryHeap::new();
+    best[start] = 0;
+    open.push(Reverse((heuristic(from), start)));
+
+    while let Some(Reverse((_, idx))) = open.pop() {
+        if idx == goal {
+            let mut path = vec![grid.point(idx)];
+            let mut cursor = idx;
+            while cursor != start {
+                cursor = parent[cursor];
+                path.push(grid.point(cursor));
+            }
+            path.reverse();
+            result.path = Some((best[idx], path));
+            return result;
+        }
+        if closed.put(idx) {
+            continue;
+        }
+        result.expanded += 1;
+        for next in neighbors(grid.point(idx)) {
+            let Some(next_idx) = grid.index(next) else {
+                continue;
+            };
+            if closed[next_idx] {
+                continue;
+            }
+            let Some(step) = grid.cost_at(next_idx) else {
+                continue;
+            };
+            let cost = best[idx] + step;
+            if cost < best[next_idx] {
+                best[next_idx] = cost;
+                parent[next_idx] = idx;
+                open.push(Reverse((cost + heuristic(next), next_idx)));
+            }
+        }
+    }
+    result
+}
+
+/// Cells reachable from `from` over passable cells, as a row-major bitset.
+pub(crate) fn flood_fill(grid: &impl CostGrid, from: Point) -> FixedBitSet {
+    let mut visited = FixedBitSet::with_capacity(grid.len());
+    let Some(start) = grid.index(from).filter(|&idx| grid.cost_at(idx).is_some()) else {
+        return visited;
+    };
+    visited.insert(start);
+    let mut frontier = VecDeque::from([from]);
+    while let Some(p) = frontier.pop_front() {
+        for next in neighbors(p) {
+            let Some(idx) = grid.index(next) else {
+                continue;
+            };
+            if grid.cost_at(idx).is_none() || visited.put(idx) {
+                continue;
+            }
+            frontier.push_back(next);
+        }
+    }
+    visited
+}
+
+/// Cheapest traversal cost from `from` to `to` by the A* above. Returns
+/// `None` when either end is out of bounds or a wall, when `to` cannot be
+/// reached, or when the board's cells do not fill `width * height`.
+pub fn path_cost(board: &Board, from: Point, to: Point) -> Option<u32> {
+    if board.cells.len() != CostGrid::len(board) {
+        return None;
+    }
+    search(board, from, to).path.map(|(cost, _)| cost)
+}
+
+#[cfg(test)]
+#[path = "tests/board_nav.rs"]
+mod board_nav_tests;
@@ -0,0 +1,235 @@
+//! Per-style board generation parameters.
+//!
+//! Each named style in `assets/world/board_styles.toml` sets how many wall
+//! runs, how much cover and how many zones
+//! [`generate_board`](super::boardgen::generate_board) lays out. Styles the
+//! file does not name, and the reserved `legacy` style, use
+//! [`BoardStyleParams::LEGACY`].
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+use anyhow::{bail, Context};
+use serde::Deserialize;
+
+use super::board::CoverKind;
+
+/// Style name that always resolves to [`BoardStyleParams::LEGACY`].
+pub const LEGACY_STYLE: &str = "legacy";
+/// Wall runs drawn for every board; a style places the first `wall_runs`.
+pub const MAX_WALL_RUNS: u32 = 32;
+/// Zones drawn for every board; a style keeps the first `zones`.
+pub const MAX_ZONES: u32 = 4;
+
+static SHIPPED: OnceLock<Result<BoardStyles, String>> = OnceLock::new();
+
+/// Inclusive `[min, max]` range of a count.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(from = "[u32; 2]")]
+pub struct CountRange {
+    pub min: u32,
+    pub max: u32,
+}
+
+impl CountRange {
+    pub const fn new(min: u32, max: u32) -> Self {
+        Self { min, max }
+    }
+
+    pub fn contains(&self, value: u32) -> bool {
+        (self.min..=self.max).contains(&value)
+    }
+
+    /// Maps `roll` onto the range; every value is reachable.
+    pub fn pick(&self, roll: u64) -> u32 {
+        let span = u64::from(self.max - self.min) + 1;
+        self.min + (roll % span) as u32
+    }
+}
+
+impl From<[u32; 2]> for CountRange {
+    fn from([min, max]: [u32; 2]) -> Self {
+        Self { min, max }
+    }
+}
+
+/// Relative weights of each cover kind.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CoverWeights {
+    #[serde(default)]
+    pub rock: u32,
+    #[serde(default, rename = "crate")]
+    pub crate_: u32,
+    #[serde(default)]
+    pub brush: u32,
+}
+
+impl CoverWeights {
+    pub fn total(&self) -> u64 {
+        u64::from(self.rock) + u64::from(self.crate_) + u64::from(self.brush)
+    }
+
+    /// The kind `roll` lands on, scanning rock, crate, brush.
+    pub fn choose(&self, roll: u64) -> CoverKind {
+        let mut pick = roll % self.total().max(1);
+        for (kind, weight) in [
+            (CoverKind::Rock, self.rock),
+            (CoverKind::Crate, self.crate_),
+            (CoverKind::Brush, self.brush),
+        ] {
+            if pick < u64::from(weight) {
+                return kind;
+            }
+            pick -= u64::from(weight);
+        }
+        CoverKind::Brush
+    }
+}
+
+/// Generation parameters for one board style.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct BoardStyleParams {
+    /// Wall runs placed, at most [`MAX_WALL_RUNS`].
+    pub wall_runs: CountRange,
+    /// Share of the cells left open after walls, zones and spawns that
+    /// become cover, in percent.
+    pub cover_pct: CountRange,
+    pub cover_weights: CoverWeights,
+    /// Zones placed, the first an evac zone; between 1 and [`MAX_ZONES`].
+    pub zones: CountRange,
+}
+
+impl BoardStyleParams {
+    /// The recipe every board used before styles had their own parameters.
+    pub const LEGACY: Self = Self {
+        wall_runs: CountRange::new(6, 10),
+        cover_pct: CountRange::new(8, 12),
+        cover_weights: CoverWeights {
+            rock: 5,
+            crate_: 3,
+            brush: 2,
+        },
+        zones: CountRange::new(2, 3),
+    };
+
+    pub fn validate(&self) -> anyhow::Result<()> {
+        for (name, range) in [
+            ("wall_runs", self.wall_runs),
+            ("cover_pct", self.cover_pct),
+            ("zones", self.zones),
+        ] {
+            if range.min > range.max {
+                bail!("{name} range [{}, {}] is reversed", range.min, range.max);
+            }
+        }
+        if self.wall_runs.max > MAX_WALL_RUNS {
+            bail!(
+                "wall_runs max {} exceeds {MAX_WALL_RUNS}",
+                self.wall_runs.max
+            );
+        }
+        if self.cover_pct.max > 100 {
+            bail!("cover_pct max {} exceeds 100", self.cover_pct.max);
+        }
+        if self.zones.min == 0 || self.zones.max > MAX_ZONES {
+            bail!(
+                "zones range [{}, {}] must lie within [1, {MAX_ZONES}]",
+                self.zones.min,
+                self.zones.max
+            );
+        }
+        if self.cover_weights.total() == 0 {
+            bail!("cover_weights are all zero");
+        }
+        Ok(())
+    }
+}
+
+/// Named style parameters from `assets/world/board_styles.toml`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BoardStyles {
+    styles: BTreeMap<String, BoardStyleParams>,
+}
+
+impl BoardStyles {
+    pub fn load_from_path(path: &Path) -> anyhow::Result<Self> {
+        let raw =
+            std::fs::read_to_string(path).with_context(|| format!("reading {}", path.display()))?;
+        Self::from_toml_str(&raw).with_context(|| format!("parsing {}", path.display()))
+    }
+
+    /// Parses a style table, rejecting unknown keys, invalid ranges and any
+    /// attempt to redefine `legacy`.
+    pub fn from_toml_str(raw: &str) -> anyhow::Result<Self> {
+        let file: BoardStylesFile = toml::from_str(raw)?;
+        for (name, params) in &file.styles {
+            if name == LEGACY_STYLE {
+                bail!("style `{LEGACY_STYLE}` is built in and cannot be redefined");
+            }
+            params
+                .validate()
+                .with_context(|| format!("style `{name}`"))?;
+        }
+        Ok(Self {
+            styles: file.styles,
+        })
+    }
+
+    /// The table in `assets/world/board_styles.toml`.
+    ///
+    /// # Panics
+    /// When the asset is missing or invalid.
+    pub fn shipped() -> &'static BoardStyles {
+        let loaded = SHIPPED.get_or_init(|| {
+            board_styles_path()
+                .and_then(|path| Self::load_from_path(&path))
+                .map_err(|err| format!("{err:#}"))
+        });
+        match loaded {
+            Ok(styles) => styles,
+            Err(err) => panic!("failed to load board styles: {err}"),
+        }
+    }
+
+    /// Parameters for `style`, or [`BoardStyleParams::LEGACY`] when the
+    /// table does not name it.
+    pub fn params(&self, style: &str) -> &BoardStyleParams {
+        self.styles.get(style).unwrap_or(&BoardStyleParams::LEGACY)
+    }
+
+    /// Named styles in ascending order, `legacy` excluded.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.styles.keys().map(String::as_str)
+    }
+}
+
+/// Path of the shipped style table, relative to the working directory when
+/// present and to the workspace otherwise.
+pub fn board_styles_path() -> anyhow::Result<PathBuf> {
+    let primary = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("..")
+        .join("..")
+        .join("assets/world/board_styles.toml");
+    let search_paths = [
+        Path::new("assets/world/board_styles.toml"),
+        primary.as_path(),
+    ];
+    for path in search_paths {
+        if path.exists() {
+            return Ok(path.to_path_buf());
+        }
+    }
+    Err(anyhow::anyhow!(
+        "missing board styles at {}",
+        primary.display()
+    ))
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct BoardStylesFile {
+    styles: BTreeMap<String, BoardStyleParams>,
+}
@@ -0,0 +1,118 @@
+use super::board::{Board, Point};
+use super::board_nav::flood_fill;
+
+/// A layout problem that would make a leg unwinnable or malformed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BoardDefect {
+    /// The cell grid does not match `width * height`.
+    CellCountMismatch {
+        expected: usize,
+        actual: usize,
+    },
+    NoPlayerSpawns,
+    NoEvacZones,
+    /// A spawn lies outside the board or on a wall cell.
+    PlayerSpawnBlocked(Point),
+    EnemySpawnBlocked(Point),
+    /// A zone extends past the board edge.
+    ZoneOutOfBounds {
+        zone: usize,
+    },
+    /// A zone covers at least one wall cell; `cell` is the first in row order.
+    ZoneOverlapsWall {
+        zone: usize,
+        cell: Point,
+    },
+    /// No evac cell is reachable from this player spawn.
+    EvacUnreachable {
+        spawn: Point,
+    },
+    /// Fewer than half of the enemy spawns are reachable from this player
+    /// spawn.
+    EnemiesUnreachable {
+        spawn: Point,
+        reachable: usize,
+        total: usize,
+    },
+}
+
+/// Checks that every player spawn can walk to an evac zone and to at least
+/// half of the enemy spawns, and that no zone sits on a wall. Defects are
+/// reported in a fixed order so results are stable across runs.
+pub fn validate_board(board: &Board) -> Result<(), Vec<BoardDefect>> {
+    let mut defects = Vec::new();
+
+    let expected = board.width as usize * board.height as usize;
+    if board.cells.len() != expected {
+        return Err(vec![BoardDefect::CellCountMismatch {
+            expected,
+            actual: board.cells.len(),
+        }]);
+    }
+
+    if board.spawns.player.is_empty() {
+        defects.push(BoardDefect::NoPlayerSpawns);
+    }
+    if board.evac_zones().next().is_none() {
+        defects.push(BoardDefect::NoEvacZones);
+    }
+    for &spawn in &board.spawns.player {
+        if !board.is_walkable(spawn) {
+            defects.push(BoardDefect::PlayerSpawnBlocked(spawn));
+        }
+    }
+    for &spawn in &board.spawns.enemy {
+        if !board.is_walkable(spawn) {
+            defects.push(BoardDefect::EnemySpawnBlocked(spawn));
+        }
+    }
+    for (idx, zone) in board.zones.iter().enumerate() {
+        if zone.rect.cells().any(|cell| !board.in_bounds(cell)) {
+            defects.push(BoardDefect::ZoneOutOfBounds { zone: idx });
+            continue;
+        }
+        if let Some(cell) = zone.rect.cells().find(|&cell| !board.is_walkable(cell)) {
+            defects.push(BoardDefect::ZoneOverlapsWall { zone: idx, cell });
+        }
+    }
+
+    for &spawn in &board.spawns.player {
+        if !board.is_walkable(spawn) {
+            continue;
+        }
+        let reachable = flood_fill(board, spawn);
+        let reaches = |p: Point| board.index(p).is_some_and(|idx| reachable[idx]);
+
+        let evac_reachable = board
+            .evac_zones()
+            .any(|zone| zone.rect.cells().any(&reaches));
+        if board.evac_zones().next().is_some() && !evac_reachable {
+            defects.push(BoardDefect::EvacUnreachable { spawn });
+        }
+
+        let total = board.spawns.enemy.len();
+        let reached = board
+            .spawns
+            .enemy
+            .iter()
+            .filter(|&&enemy| reaches(enemy))
+            .count();
+        if reached * 2 < total {
+            defects.push(BoardDefect::EnemiesUnreachable {
+                spawn,
+                reachable: reached,
+                total,
+            });
+        }
+    }
+
+    if defects.is_empty() {
+        Ok(())
+    } else {
+        Err(defects)
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/boardcheck.rs"]
+mod boardcheck_tests;
@@ -0,0 +1,207 @@
+//! Board generation from a seed and a style, plus text views for checking a
+//! generated board by eye.
+//!
+//! Every board draws the same number of values in the same order, whatever
+//! its style's parameters: three counts, [`MAX_WALL_RUNS`] wall runs,
+//! [`MAX_ZONES`] zones, the spawns, then one value per cell for cover. A
+//! style only decides how many of the drawn runs and zones are kept and how
+//! many cells become cover, so changing one style never moves another's
+//! boards.
+
+use super::board::{
+    Board, Cell, CoverKind, Point, Rect, Zone, ZoneKind, BOARD_SIZE, DEFAULT_CELL_MM,
+};
+use super::board_styles::{BoardStyles, MAX_WALL_RUNS, MAX_ZONES};
+use super::boardcheck::{validate_board, BoardDefect};
+use super::index::splitmix64;
+
+/// Attempts [`generate_board`] makes before giving up on a seed.
+pub const MAX_BOARD_RETRIES: u32 = 8;
+/// Player spawns on every generated board, all in the west band.
+pub const PLAYER_SPAWNS: u32 = 2;
+/// Enemy spawns on every generated board.
+pub const ENEMY_SPAWNS: u32 = 4;
+
+const MIN_RUN_LEN: u64 = 3;
+const MAX_RUN_LEN: u64 = 12;
+const ZONE_EDGE: u32 = 4;
+
+/// Generates the board for `seed` in `style`, using `styles` for its
+/// parameters. Each attempt is checked with [`validate_board`]; a failed
+/// attempt is retried with the retry count appended to the seed hash, up to
+/// [`MAX_BOARD_RETRIES`] times, and the last attempt's defects are returned
+/// when every one fails.
+pub fn generate_board(
+    seed: u64,
+    style: &str,
+    styles: &BoardStyles,
+) -> Result<Board, Vec<BoardDefect>> {
+    let mut defects = Vec::new();
+    for retry in 0..=MAX_BOARD_RETRIES {
+        let board = build_board(seed, style, styles, retry);
+        match validate_board(&board) {
+            Ok(()) => return Ok(board),
+            Err(found) => defects = found,
+        }
+    }
+    Err(defects)
+}
+
+fn board_state(seed: u64, style: &str, retry: u32) -> u64 {
+    let mut key = b"board:".to_vec();
+    key.extend_from_slice(style.as_bytes());
+    if retry > 0 {
+        key.extend_from_slice(&retry.to_le_bytes());
+    }
+    wyhash::wyhash(&key, seed)
+}
+
+fn build_board(seed: u64, style: &str, styles: &BoardStyles, retry: u32) -> Board {
+    let params = styles.params(style);
+    let mut state = board_state(seed, style, retry);
+    let mut board = Board::empty(BOARD_SIZE, BOARD_SIZE, DEFAULT_CELL_MM);
+    board.retries = retry;
+    let size = u64::from(BOARD_SIZE);
+
+    let wall_runs = params.wall_runs.pick(splitmix64(&mut state));
+    let cover_pct = params.cover_pct.pick(splitmix64(&mut state));
+    let zone_count = params.zones.pick(splitmix64(&mut state));
+
+    for run in 0..MAX_WALL_RUNS {
+        let roll = splitmix64(&mut state);
+        if run >= wall_runs {
+            continue;
+        }
+        let start = Point::new((roll % size) as i32, ((roll >> 8) % size) as i32);
+        let len = MIN_RUN_LEN + (roll >> 16) % (MAX_RUN_LEN - MIN_RUN_LEN + 1);
+        let vertical = (roll >> 24) & 1 == 1;
+        for step in 0..len as i32 {
+            let point = if vertical {
+                Point::new(start.x, start.y + step)
+            } else {
+                Point::new(start.x + step, start.y)
+            };
+            board.set_cell(point, Cell::Wall);
+        }
+    }
+
+    // The evac zone sits in the east band, objectives in the middle.
+    let span = u64::from(BOARD_SIZE - ZONE_EDGE);
+    for zone in 0..MAX_ZONES {
+        let roll = splitmix64(&mut state);
+        if zone >= zone_count {
+            continue;
+        }
+        let (kind, x) = if zone == 0 {
+            (ZoneKind::Evac, 52 + (roll % 8) as i32)
+        } else {
+            (ZoneKind::Objective, 16 + (roll % 28) as i32)
+        };
+        let rect = Rect {
+            x,
+            y: 1 + ((roll >> 16) % (span - 1)) as i32,
+            w: ZONE_EDGE,
+            h: ZONE_EDGE,
+        };
+        for point in rect.cells() {
+            board.set_cell(point, Cell::Open);
+        }
+        board.zones.push(Zone { kind, rect });
+    }
+
+    // Player spawns sit in the west band, enemies from the middle east.
+    for spawn in 0..PLAYER_SPAWNS + ENEMY_SPAWNS {
+        let roll = splitmix64(&mut state);
+        let y = 1 + (roll % (size - 2)) as i32;
+        let (points, x) = if spawn < PLAYER_SPAWNS {
+            (&mut board.spawns.player, 1 + ((roll >> 16) % 4) as i32)
+        } else {
+            (&mut board.spawns.enemy, 20 + ((roll >> 16) % 40) as i32)
+        };
+        points.push(Point::new(x, y));
+        board.set_cell(Point::new(x, y), Cell::Open);
+    }
+
+    // One key per cell; the open, unreserved cells with the lowest keys
+    // become cover, so the cover count is exact for the drawn percentage.
+    let keys: Vec<u64> = (0..board.cells.len())
+        .map(|_| splitmix64(&mut state))
+        .collect();
+    let mut candidates: Vec<usize> = (0..board.cells.len())
+        .filter(|&idx| board.cells[idx] == Cell::Open && !is_reserved(&board, board.point(idx)))
+        .collect();
+    let cover = candidates.len() * cover_pct as usize / 100;
+    candidates.sort_by_key(|&idx| (keys[idx] as u32, idx));
+    for &idx in &candidates[..cover] {
+        board.cells[idx] = Cell::Cover(params.cover_weights.choose(keys[idx] >> 32));
+    }
+    board
+}
+
+fn is_reserved(board: &Board, point: Point) -> bool {
+    board.zones.iter().any(|zone| zone.rect.contains(point))
+        || board.spawns.player.contains(&point)
+        || board.spawns.enemy.contains(&point)
+}
+
+/// Renders `board` one text row per board row, `y = 0` first, each row
+/// newline-terminated. Glyphs, highest precedence first:
+///
+/// | glyph | meaning |
+/// |-------|---------|
+/// | `@` | player spawn |
+/// | `&` | enemy spawn |
+/// | `#` | wall |
+/// | `r` `c` `b` | rock, crate and brush cover |
+/// | `+` | open cell in an evac zone |
+/// | `*` | open cell in an objective zone |
+/// | `.` | open |
+///
+/// Spawns and zone cells outside the board are skipped.
+pub fn ascii_preview(board: &Board) -> String {
+    let mut grid: Vec<u8> = board
+        .cells
+        .iter()
+        .map(|cell| match cell {
+            Cell::Open => b'.',
+            Cell::Wall => b'#',
+            Cell::Cover(CoverKind::Rock) => b'r',
+            Cell::Cover(CoverKind::Crate) => b'c',
+            Cell::Cover(CoverKind::Brush) => b'b',
+        })
+        .collect();
+    grid.resize(board.width as usize * board.height as usize, b'.');
+
+    for zone in &board.zones {
+        let glyph = match zone.kind {
+            ZoneKind::Evac => b'+',
+            ZoneKind::Objective => b'*',
+        };
+        for point in zone.rect.cells() {
+            if let Some(idx) = board.index(point) {
+                if grid[idx] == b'.' {
+                    grid[idx] = glyph;
+                }
+            }
+        }
+    }
+    for (points, glyph) in [(&board.spawns.enemy, b'&'), (&board.spawns.player, b'@')] {
+        for &point in points {
+            if let Some(idx) = board.index(point) {
+                grid[idx] = glyph;
+            }
+        }
+    }
+
+    let width = board.width.max(1) as usize;
+    let mut out = String::with_capacity(grid.len() + board.height as usize);
+    for row in grid.chunks(width) {
+        out.extend(row.iter().map(|&glyph| char::from(glyph)));
+        out.push('\n');
+    }
+    out
+}
+
+#[cfg(test)]
+#[path = "tests/boardgen.rs"]
+mod tests;
@@ -0,0 +1,80 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use bevy::prelude::Resource;
+use serde::{Deserialize, Serialize};
+
+use crate::systems::economy::{EconomyDay, HubId, RouteId};
+
+use super::index::WorldIndex;
+
+/// Routes closed until a given economy day. A route is closed on every day
+/// before its `until` day and reopens on that day.
+#[derive(Debug, Default, Resource, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct RouteClosures {
+    until: BTreeMap<u16, EconomyDay>,
+}
+
+impl RouteClosures {
+    /// Closes `route` until `until_day`. An existing longer closure is kept.
+    /// Returns `true` when this extends or creates the closure.
+    pub fn close(&mut self, route: RouteId, until_day: EconomyDay) -> bool {
+        let entry = self.until.entry(route.0).or_insert(EconomyDay(0));
+        if until_day.0 > entry.0 {
+            *entry = until_day;
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn is_closed(&self, route: RouteId, day: EconomyDay) -> bool {
+        self.until
+            .get(&route.0)
+            .is_some_and(|until| day.0 < until.0)
+    }
+
+    /// Drops every closure that has reopened by `day`.
+    pub fn expire(&mut self, day: EconomyDay) {
+        self.until.retain(|_, until| day.0 < until.0);
+    }
+
+    /// `(route, until_day)` pairs in route order, including expired entries
+    /// that have not been pruned yet.
+    pub fn entries(&self) -> impl Iterator<Item = (RouteId, EconomyDay)> + '_ {
+        self.until
+            .iter()
+            .map(|(route, until)| (RouteId(*route), *until))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.until.is_empty()
+    }
+
+    /// Routes closed on `day`, in the shape `start_leg_for_route` expects.
+    pub fn closed_on(&self, day: EconomyDay) -> HashSet<RouteId> {
+        self.entries()
+            .filter(|(_, until)| day.0 < until.0)
+            .map(|(route, _)| route)
+            .collect()
+    }
+
+    /// Number of closed routes touching each hub on `day`, for the basis
+    /// `closed_routes` driver.
+    pub fn closed_counts_by_hub<I: WorldIndex>(&self, day: EconomyDay) -> HashMap<HubId, u8> {
+        let mut counts: HashMap<HubId, u8> = HashMap::new();
+        for route in self.closed_on(day) {
+            let Some((from, to)) = I::route_endpoints(route) else {
+                continue;
+            };
+            for hub in [from, to] {
+                let count = counts.entry(hub).or_default();
+                *count = count.saturating_add(1);
+            }
+        }
+        counts
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/closures.rs"]
+mod closures_tests;
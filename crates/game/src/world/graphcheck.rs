@@ -0,0 +1,176 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap, VecDeque};
+use std::fmt;
+
+use crate::systems::economy::{HubId, RouteId};
+
+use super::index::WorldGraphData;
+
+/// Most routes a hub may join; matches the inline capacity of the neighbor
+/// lists handed out by [`super::index::WorldIndex::neighbors`].
+pub const MAX_HUB_ROUTES: usize = 6;
+
+/// A world graph problem that would otherwise surface as a panic or silently
+/// dropped data once systems start querying the index.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GraphDefect {
+    /// Two routes share the same id; the later one would shadow the first.
+    DuplicateRouteId(RouteId),
+    /// A route endpoint is missing from the declared hub list.
+    UnknownHub { route: RouteId, hub: HubId },
+    /// A second route joins a hub pair that `first` already links.
+    DuplicateLink {
+        hubs: (HubId, HubId),
+        first: RouteId,
+        duplicate: RouteId,
+    },
+    /// The world index still loads, keeping the hub's lowest
+    /// [`MAX_HUB_ROUTES`] route ids and flagging it through
+    /// [`super::index::WorldIndex::neighbor_overflow`].
+    TooManyRoutes { hub: HubId, count: usize },
+    /// Hubs cut off from the largest component, in ascending id order.
+    Disconnected { hubs: Vec<HubId> },
+}
+
+impl fmt::Display for GraphDefect {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GraphDefect::DuplicateRouteId(route) => write!(f, "route {} defined twice", route.0),
+            GraphDefect::UnknownHub { route, hub } => {
+                write!(f, "route {} references unknown hub {}", route.0, hub.0)
+            }
+            GraphDefect::DuplicateLink {
+                hubs,
+                first,
+                duplicate,
+            } => write!(
+                f,
+                "route {} duplicates route {} between hubs {} and {}",
+                duplicate.0, first.0, hubs.0 .0, hubs.1 .0
+            ),
+            GraphDefect::TooManyRoutes { hub, count } => write!(
+                f,
+                "hub {} joins {count} routes (limit {MAX_HUB_ROUTES})",
+                hub.0
+            ),
+            GraphDefect::Disconnected { hubs } => {
+                let ids: Vec<String> = hubs.iter().map(|hub| hub.0.to_string()).collect();
+                write!(f, "hubs [{}] are disconnected", ids.join(", "))
+            }
+        }
+    }
+}
+
+/// Checks route ids, endpoints, link uniqueness, per-hub route counts and
+/// connectivity. Defects are reported grouped by kind in id order so the
+/// report is stable across runs.
+pub fn validate_world_graph(graph: &WorldGraphData) -> Result<(), Vec<GraphDefect>> {
+    let mut defects = Vec::new();
+
+    let mut seen_ids = BTreeSet::new();
+    for route in &graph.routes {
+        if !seen_ids.insert(route.id.0) {
+            defects.push(GraphDefect::DuplicateRouteId(route.id));
+        }
+    }
+
+    let hubs: BTreeSet<u16> = graph.hub_ids().iter().map(|hub| hub.0).collect();
+
+    let mut valid_routes = Vec::new();
+    for route in &graph.routes {
+        let mut known = true;
+        for hub in [route.from, route.to] {
+            if !hubs.contains(&hub.0) {
+                defects.push(GraphDefect::UnknownHub {
+                    route: route.id,
+                    hub,
+                });
+                known = false;
+            }
+        }
+        if known {
+            valid_routes.push(route);
+        }
+    }
+
+    let mut links: HashMap<(u16, u16), RouteId> = HashMap::new();
+    for route in &valid_routes {
+        let pair = (route.from.0.min(route.to.0), route.from.0.max(route.to.0));
+        if let Some(&first) = links.get(&pair) {
+            defects.push(GraphDefect::DuplicateLink {
+                hubs: (HubId(pair.0), HubId(pair.1)),
+                first,
+                duplicate: route.id,
+            });
+        } else {
+            links.insert(pair, route.id);
+        }
+    }
+
+    let mut adjacency: BTreeMap<u16, Vec<u16>> =
+        hubs.iter().map(|&hub| (hub, Vec::new())).collect();
+    let mut route_counts: BTreeMap<u16, usize> = BTreeMap::new();
+    for route in &valid_routes {
+        for (hub, other) in [(route.from.0, route.to.0), (route.to.0, route.from.0)] {
+            adjacency.entry(hub).or_default().push(other);
+            *route_counts.entry(hub).or_default() += 1;
+        }
+    }
+    for (&hub, &count) in &route_counts {
+        if count > MAX_HUB_ROUTES {
+            defects.push(GraphDefect::TooManyRoutes {
+                hub: HubId(hub),
+                count,
+            });
+        }
+    }
+
+    let components = components(&adjacency);
+    if let Some(main) = components
+        .iter()
+        .enumerate()
+        .max_by_key(|(idx, component)| (component.len(), std::cmp::Reverse(*idx)))
+        .map(|(idx, _)| idx)
+    {
+        for (idx, component) in components.into_iter().enumerate() {
+            if idx != main {
+                defects.push(GraphDefect::Disconnected {
+                    hubs: component.into_iter().map(HubId).collect(),
+                });
+            }
+        }
+    }
+
+    if defects.is_empty() {
+        Ok(())
+    } else {
+        Err(defects)
+    }
+}
+
+/// Connected components ordered by their smallest hub id, each sorted.
+fn components(adjacency: &BTreeMap<u16, Vec<u16>>) -> Vec<Vec<u16>> {
+    let mut visited = BTreeSet::new();
+    let mut out = Vec::new();
+    for &start in adjacency.keys() {
+        if !visited.insert(start) {
+            continue;
+        }
+        let mut component = vec![start];
+        let mut frontier = VecDeque::from([start]);
+        while let Some(hub) = frontier.pop_front() {
+            for &next in adjacency.get(&hub).into_iter().flatten() {
+                if visited.insert(next) {
+                    component.push(next);
+                    frontier.push_back(next);
+                }
+            }
+        }
+        component.sort_unstable();
+        out.push(component);
+    }
+    out
+}
+
+#[cfg(test)]
+#[path = "tests/graphcheck.rs"]
+mod graphcheck_tests;
@@ -1,29 +1,56 @@
-use std::collections::HashMap;
-use std::path::Path;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::path::{Path, PathBuf};
 use std::sync::OnceLock;
 
 use anyhow::Context;
 use serde::Deserialize;
 use smallvec::SmallVec;
 
-use crate::systems::economy::{HubId, RouteId, Weather};
+use crate::systems::economy::{HubId, MoneyCents, RouteId, Weather};
 
-static ROUTES: OnceLock<RoutesData> = OnceLock::new();
+use super::graphcheck::{validate_world_graph, GraphDefect, MAX_HUB_ROUTES};
+use super::weather::next_weather;
+
+static ROUTES: OnceLock<Result<RoutesData, String>> = OnceLock::new();
 
 pub trait WorldIndex {
+    /// Routes leaving `hub`, ascending by id and capped at
+    /// [`MAX_HUB_ROUTES`].
     fn neighbors(hub: HubId) -> SmallVec<[RouteId; 6]>;
+    /// Whether `hub` joins more routes than [`WorldIndex::neighbors`] lists;
+    /// the routes past the cap are the highest ids.
+    fn neighbor_overflow(_hub: HubId) -> bool {
+        false
+    }
     fn route_weather(route: RouteId) -> Weather;
+    /// Weather on `route` on `day`: [`WorldIndex::route_weather`] on day 0,
+    /// then one [`next_weather`] step per day.
+    fn route_weather_on_day(route: RouteId, seed: u64, day: u32) -> Weather {
+        (1..=day).fold(Self::route_weather(route), |weather, day| {
+            next_weather(weather, seed, route, day)
+        })
+    }
+    /// The `(from, to)` hubs joined by `route`, as listed in the world asset.
+    fn route_endpoints(route: RouteId) -> Option<(HubId, HubId)>;
+    /// Travel distance of `route`; zero when the asset leaves it unset.
+    fn route_distance(route: RouteId) -> u32;
+    /// Toll charged for starting a leg on `route`; zero when unset.
+    fn route_toll(route: RouteId) -> MoneyCents;
+    /// Display name of `hub` from the world asset, if it has one.
+    fn hub_name(_hub: HubId) -> Option<&'static str> {
+        None
+    }
 }
 
 pub struct StaticWorldIndex;
 
 impl WorldIndex for StaticWorldIndex {
     fn neighbors(hub: HubId) -> SmallVec<[RouteId; 6]> {
-        ensure_loaded()
-            .neighbors
-            .get(&hub)
-            .cloned()
-            .unwrap_or_default()
+        ensure_loaded().neighbors_of(hub)
+    }
+
+    fn neighbor_overflow(hub: HubId) -> bool {
+        ensure_loaded().overflow.contains(&hub)
     }
 
     fn route_weather(route: RouteId) -> Weather {
@@ -33,6 +60,42 @@ impl WorldIndex for StaticWorldIndex {
             .copied()
             .unwrap_or(Weather::Clear)
     }
+
+    fn route_endpoints(route: RouteId) -> Option<(HubId, HubId)> {
+        ensure_loaded().endpoints.get(&route).copied()
+    }
+
+    fn route_distance(route: RouteId) -> u32 {
+        ensure_loaded()
+            .costs
+            .get(&route)
+            .map(|cost| cost.distance_km)
+            .unwrap_or_default()
+    }
+
+    fn route_toll(route: RouteId) -> MoneyCents {
+        ensure_loaded()
+            .costs
+            .get(&route)
+            .map(|cost| MoneyCents(cost.toll_cents))
+            .unwrap_or_default()
+    }
+
+    fn hub_name(hub: HubId) -> Option<&'static str> {
+        ensure_loaded().hub_names.get(&hub).map(String::as_str)
+    }
+}
+
+/// The hub at the far end of `route` when leaving from `hub`.
+pub fn route_destination<I: WorldIndex>(route: RouteId, hub: HubId) -> Option<HubId> {
+    let (from, to) = I::route_endpoints(route)?;
+    if from == hub {
+        Some(to)
+    } else if to == hub {
+        Some(from)
+    } else {
+        None
+    }
 }
 
 pub fn deterministic_rumor(seed: u64, route: RouteId) -> (RumorKind, u8) {
@@ -56,7 +119,7 @@ pub enum RumorKind {
     Patrol,
 }
 
-fn splitmix64(state: &mut u64) -> u64 {
+pub(crate) fn splitmix64(state: &mut u64) -> u64 {
     *state = state.wrapping_add(0x9E3779B97F4A7C15);
     let mut z = *state;
     z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
@@ -66,29 +129,100 @@ fn splitmix64(state: &mut u64) -> u64 {
 
 struct RoutesData {
     neighbors: HashMap<HubId, SmallVec<[RouteId; 6]>>,
+    /// Hubs whose neighbor lists were cut to [`MAX_HUB_ROUTES`].
+    overflow: BTreeSet<HubId>,
     weather: HashMap<RouteId, Weather>,
+    endpoints: HashMap<RouteId, (HubId, HubId)>,
+    costs: HashMap<RouteId, RouteCost>,
+    hub_names: BTreeMap<HubId, String>,
+}
+
+impl RoutesData {
+    fn neighbors_of(&self, hub: HubId) -> SmallVec<[RouteId; 6]> {
+        self.neighbors.get(&hub).cloned().unwrap_or_default()
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct RouteCost {
+    distance_km: u32,
+    toll_cents: i64,
+}
+
+/// The raw hub/route graph as written in the world asset.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct WorldGraphData {
+    /// Every hub in the world. When omitted, the hubs referenced by routes
+    /// are taken as the full set.
+    #[serde(default)]
+    pub hubs: Vec<HubId>,
+    /// Display names; hubs without one show their id.
+    #[serde(default)]
+    pub hub_names: Vec<HubName>,
+    pub routes: Vec<RouteSpec>,
+}
+
+impl WorldGraphData {
+    /// Every hub id, ascending: `hubs` when listed, else the route endpoints.
+    pub fn hub_ids(&self) -> Vec<HubId> {
+        let mut ids: Vec<HubId> = if self.hubs.is_empty() {
+            self.routes
+                .iter()
+                .flat_map(|route| [route.from, route.to])
+                .collect()
+        } else {
+            self.hubs.clone()
+        };
+        ids.sort_unstable_by_key(|hub| hub.0);
+        ids.dedup();
+        ids
+    }
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Deserialize)]
 #[serde(deny_unknown_fields)]
-struct RoutesConfig {
-    routes: Vec<RouteSpec>,
+pub struct HubName {
+    pub hub: HubId,
+    pub name: String,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Deserialize)]
 #[serde(deny_unknown_fields)]
-struct RouteSpec {
-    id: RouteId,
-    from: HubId,
-    to: HubId,
-    weather: Weather,
+pub struct RouteSpec {
+    pub id: RouteId,
+    pub from: HubId,
+    pub to: HubId,
+    pub weather: Weather,
+    #[serde(default)]
+    pub distance_km: u32,
+    #[serde(default)]
+    pub toll_cents: i64,
+}
+
+fn load_once() -> &'static Result<RoutesData, String> {
+    ROUTES.get_or_init(|| load_routes().map_err(|err| format!("{err:#}")))
 }
 
 fn ensure_loaded() -> &'static RoutesData {
-    ROUTES.get_or_init(|| load_routes().expect("failed to load world index"))
+    match load_once() {
+        Ok(data) => data,
+        Err(err) => panic!("failed to load world index: {err}"),
+    }
 }
 
-fn load_routes() -> anyhow::Result<RoutesData> {
+/// Loads and validates the world index, surfacing graph defects as an error
+/// instead of a panic on first use. Call once at startup.
+pub fn ensure_world_index() -> anyhow::Result<()> {
+    load_once()
+        .as_ref()
+        .map(|_| ())
+        .map_err(|err| anyhow::anyhow!("failed to load world index: {err}"))
+}
+
+/// Path of the shipped world asset, relative to the working directory when
+/// present and to the workspace otherwise.
+pub fn world_graph_path() -> anyhow::Result<PathBuf> {
     let manifest = env!("CARGO_MANIFEST_DIR");
     let primary = Path::new(manifest)
         .join("..")
@@ -97,7 +231,7 @@ fn load_routes() -> anyhow::Result<RoutesData> {
     let search_paths = [Path::new("assets/world/hubs_min.toml"), primary.as_path()];
     for path in search_paths {
         if path.exists() {
-            return parse_routes(path);
+            return Ok(path.to_path_buf());
         }
     }
     Err(anyhow::anyhow!(
@@ -106,26 +240,87 @@ fn load_routes() -> anyhow::Result<RoutesData> {
     ))
 }
 
-fn parse_routes(path: &Path) -> anyhow::Result<RoutesData> {
+pub fn load_world_graph_data(path: &Path) -> anyhow::Result<WorldGraphData> {
     let raw =
         std::fs::read_to_string(path).with_context(|| format!("reading {}", path.display()))?;
-    let config: RoutesConfig =
-        toml::from_str(&raw).with_context(|| format!("parsing {}", path.display()))?;
+    toml::from_str(&raw).with_context(|| format!("parsing {}", path.display()))
+}
 
-    let mut neighbors: HashMap<HubId, SmallVec<[RouteId; 6]>> = HashMap::new();
+fn load_routes() -> anyhow::Result<RoutesData> {
+    parse_routes(&world_graph_path()?)
+}
+
+fn parse_routes(path: &Path) -> anyhow::Result<RoutesData> {
+    let config = load_world_graph_data(path)?;
+    if let Err(defects) = validate_world_graph(&config) {
+        let (over_cap, fatal): (Vec<_>, Vec<_>) = defects
+            .iter()
+            .partition(|defect| matches!(defect, GraphDefect::TooManyRoutes { .. }));
+        if !fatal.is_empty() {
+            let report: Vec<String> = fatal.iter().map(ToString::to_string).collect();
+            anyhow::bail!(
+                "invalid world graph {}: {}",
+                path.display(),
+                report.join("; ")
+            );
+        }
+        let report: Vec<String> = over_cap.iter().map(ToString::to_string).collect();
+        log::warn!(
+            "world graph {}: {}; keeping the lowest route ids",
+            path.display(),
+            report.join("; ")
+        );
+    }
+    Ok(build_routes(&config))
+}
+
+/// Indexes the graph's routes. Neighbor lists are sorted by route id and cut
+/// to the lowest [`MAX_HUB_ROUTES`]; hubs that lose routes are recorded as
+/// overflowing.
+fn build_routes(config: &WorldGraphData) -> RoutesData {
+    let mut all_neighbors: HashMap<HubId, Vec<RouteId>> = HashMap::new();
     let mut weather = HashMap::new();
+    let mut endpoints = HashMap::new();
+    let mut costs = HashMap::new();
     for route in &config.routes {
-        neighbors.entry(route.from).or_default().push(route.id);
-        neighbors.entry(route.to).or_default().push(route.id);
+        all_neighbors.entry(route.from).or_default().push(route.id);
+        all_neighbors.entry(route.to).or_default().push(route.id);
         weather.insert(route.id, route.weather);
+        endpoints.insert(route.id, (route.from, route.to));
+        costs.insert(
+            route.id,
+            RouteCost {
+                distance_km: route.distance_km,
+                toll_cents: route.toll_cents,
+            },
+        );
     }
 
-    for list in neighbors.values_mut() {
-        list.sort_by_key(|id| id.0);
-        list.truncate(6);
-    }
+    let mut overflow = BTreeSet::new();
+    let neighbors = all_neighbors
+        .into_iter()
+        .map(|(hub, mut list)| {
+            list.sort_by_key(|id| id.0);
+            if list.len() > MAX_HUB_ROUTES {
+                overflow.insert(hub);
+                list.truncate(MAX_HUB_ROUTES);
+            }
+            (hub, list.into_iter().collect())
+        })
+        .collect();
 
-    Ok(RoutesData { neighbors, weather })
+    RoutesData {
+        neighbors,
+        overflow,
+        weather,
+        endpoints,
+        costs,
+        hub_names: config
+            .hub_names
+            .iter()
+            .map(|entry| (entry.hub, entry.name.clone()))
+            .collect(),
+    }
 }
 
 #[cfg(test)]
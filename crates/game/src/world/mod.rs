@@ -1 +1,12 @@
+pub mod board;
+pub mod board_bytes;
+pub mod board_los;
+pub mod board_nav;
+pub mod board_styles;
+pub mod boardcheck;
+pub mod boardgen;
+pub mod closures;
+pub mod graphcheck;
 pub mod index;
+pub mod nav;
+pub mod weather;
@@ -0,0 +1,101 @@
+use fixedbitset::FixedBitSet;
+
+use super::board::{Board, Cell, Point};
+use super::board_nav::{self, CostGrid, COVER_PENALTY, STEP_COST};
+
+/// Walkability snapshot of a [`Board`] for AI and reachability queries.
+/// Walls block movement; cover stays walkable but is flagged so paths pay
+/// [`COVER_PENALTY`] to cross it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NavGrid {
+    width: u32,
+    height: u32,
+    cell_mm: u32,
+    walkable: FixedBitSet,
+    cover: FixedBitSet,
+}
+
+impl NavGrid {
+    pub fn from_board(board: &Board) -> Self {
+        let len = board.width as usize * board.height as usize;
+        let mut walkable = FixedBitSet::with_capacity(len);
+        let mut cover = FixedBitSet::with_capacity(len);
+        for (idx, cell) in board.cells.iter().take(len).enumerate() {
+            walkable.set(idx, cell.is_walkable());
+            cover.set(idx, matches!(cell, Cell::Cover(_)));
+        }
+        Self {
+            width: board.width,
+            height: board.height,
+            cell_mm: board.cell_mm,
+            walkable,
+            cover,
+        }
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    pub fn cell_mm(&self) -> u32 {
+        self.cell_mm
+    }
+
+    /// Cell containing the millimetre position, or `None` off the board.
+    pub fn mm_to_cell(&self, x_mm: i32, y_mm: i32) -> Option<Point> {
+        let cell = self.cell_mm.max(1) as i32;
+        let p = Point::new(x_mm.div_euclid(cell), y_mm.div_euclid(cell));
+        self.in_bounds(p).then_some(p)
+    }
+
+    pub fn in_bounds(&self, p: Point) -> bool {
+        p.x >= 0 && p.y >= 0 && (p.x as u32) < self.width && (p.y as u32) < self.height
+    }
+
+    pub fn is_walkable(&self, p: Point) -> bool {
+        self.index(p).is_some_and(|idx| self.walkable[idx])
+    }
+
+    pub fn is_cover(&self, p: Point) -> bool {
+        self.index(p).is_some_and(|idx| self.cover[idx])
+    }
+
+    /// 4-connected A* from `from` to `to`, inclusive of both ends, with the
+    /// step costs and tie-breaking of [`path_cost`](board_nav::path_cost).
+    pub fn find_path(&self, from: Point, to: Point) -> Option<Vec<Point>> {
+        board_nav::search(self, from, to).path.map(|(_, path)| path)
+    }
+
+    /// Cells reachable from `from` over walkable tiles, as a row-major bitset.
+    pub fn reachable(&self, from: Point) -> FixedBitSet {
+        board_nav::flood_fill(self, from)
+    }
+}
+
+impl CostGrid for NavGrid {
+    fn width(&self) -> u32 {
+        self.width
+    }
+
+    fn height(&self) -> u32 {
+        self.height
+    }
+
+    fn cost_at(&self, index: usize) -> Option<u32> {
+        self.walkable[index].then(|| {
+            if self.cover[index] {
+                STEP_COST + COVER_PENALTY
+            } else {
+                STEP_COST
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/nav.rs"]
+mod nav_tests;
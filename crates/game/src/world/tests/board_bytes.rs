@@ -0,0 +1,145 @@
+use crate::world::board::{
+    board_hash, Board, Cell, CoverKind, Point, Rect, Zone, ZoneKind, BOARD_SIZE, DEFAULT_CELL_MM,
+};
+use crate::world::board_bytes::{board_hash_from_bytes, BoardBytesError, BOARD_BYTES_VERSION};
+
+fn sample_board() -> Board {
+    let mut board = Board::empty(BOARD_SIZE, BOARD_SIZE, DEFAULT_CELL_MM);
+    board.retries = 3;
+    board
+        .spawns
+        .player
+        .extend([Point::new(2, 2), Point::new(3, 2)]);
+    board
+        .spawns
+        .enemy
+        .extend([Point::new(40, 40), Point::new(60, 5), Point::new(-1, 7)]);
+    for x in 0..BOARD_SIZE as i32 {
+        board.set_cell(Point::new(x, 20), Cell::Wall);
+    }
+    board.set_cell(Point::new(5, 5), Cell::Cover(CoverKind::Rock));
+    board.set_cell(Point::new(6, 5), Cell::Cover(CoverKind::Crate));
+    board.set_cell(Point::new(7, 5), Cell::Cover(CoverKind::Brush));
+    board.zones.extend([
+        Zone {
+            kind: ZoneKind::Evac,
+            rect: Rect {
+                x: 58,
+                y: 58,
+                w: 4,
+                h: 4,
+            },
+        },
+        Zone {
+            kind: ZoneKind::Objective,
+            rect: Rect {
+                x: -3,
+                y: 30,
+                w: 2,
+                h: 9,
+            },
+        },
+    ]);
+    board
+}
+
+#[test]
+fn binary_round_trip_matches_json() {
+    let board = sample_board();
+    let json = serde_json::to_string_pretty(&board).expect("json");
+    let from_json: Board = serde_json::from_str(&json).expect("parse json");
+
+    let bytes = board.to_bytes();
+    assert!(bytes.len() * 4 < json.len());
+    let decoded = Board::from_bytes(&bytes).expect("decode");
+    assert_eq!(decoded, board);
+    assert_eq!(decoded, from_json);
+    assert_eq!(decoded.to_bytes(), bytes);
+}
+
+#[test]
+fn binary_hash_matches_canonical_json_hash() {
+    let board = sample_board();
+    assert_eq!(
+        board_hash_from_bytes(&board.to_bytes()).expect("hash"),
+        board_hash(&board).expect("hash")
+    );
+}
+
+#[test]
+fn truncated_bytes_are_rejected() {
+    let bytes = sample_board().to_bytes();
+    for len in [0, 3, 6, 40, bytes.len() / 2, bytes.len() - 1] {
+        assert!(
+            matches!(
+                Board::from_bytes(&bytes[..len]),
+                Err(BoardBytesError::Truncated { .. })
+            ),
+            "len {len}"
+        );
+    }
+}
+
+#[test]
+fn corrupt_headers_and_tags_are_rejected() {
+    let bytes = sample_board().to_bytes();
+
+    let mut bad_magic = bytes.clone();
+    bad_magic[0] = b'X';
+    assert!(matches!(
+        Board::from_bytes(&bad_magic),
+        Err(BoardBytesError::BadMagic)
+    ));
+
+    let mut future = bytes.clone();
+    future[4..6].copy_from_slice(&(BOARD_BYTES_VERSION + 1).to_le_bytes());
+    assert!(matches!(
+        Board::from_bytes(&future),
+        Err(BoardBytesError::UnsupportedVersion { found }) if found == BOARD_BYTES_VERSION + 1
+    ));
+
+    let mut bad_cell = bytes.clone();
+    bad_cell[26] = 9;
+    assert!(matches!(
+        Board::from_bytes(&bad_cell),
+        Err(BoardBytesError::InvalidTag {
+            what: "cell",
+            tag: 9,
+            offset: 26
+        })
+    ));
+
+    let mut huge_count = bytes.clone();
+    huge_count[22..26].copy_from_slice(&u32::MAX.to_le_bytes());
+    assert!(matches!(
+        Board::from_bytes(&huge_count),
+        Err(BoardBytesError::Truncated { .. })
+    ));
+
+    let mut trailing = bytes.clone();
+    trailing.push(0);
+    assert!(matches!(
+        Board::from_bytes(&trailing),
+        Err(BoardBytesError::TrailingBytes { count: 1 })
+    ));
+}
+
+#[test]
+fn cell_count_must_match_dimensions() {
+    let bytes = sample_board().to_bytes();
+
+    let mut wide = bytes.clone();
+    wide[6..10].copy_from_slice(&(BOARD_SIZE + 1).to_le_bytes());
+    assert!(matches!(
+        Board::from_bytes(&wide),
+        Err(BoardBytesError::CellCountMismatch { width, height, count })
+            if width == BOARD_SIZE + 1 && height == BOARD_SIZE && count == (BOARD_SIZE * BOARD_SIZE) as usize
+    ));
+
+    let mut flat = bytes;
+    flat[10..14].copy_from_slice(&0u32.to_le_bytes());
+    assert!(matches!(
+        Board::from_bytes(&flat),
+        Err(BoardBytesError::CellCountMismatch { height: 0, .. })
+    ));
+}
@@ -0,0 +1,113 @@
+use crate::systems::economy::Weather;
+use crate::world::board::{Board, Cell, CoverKind, Point, DEFAULT_CELL_MM};
+use crate::world::board_los::{has_los, has_los_seeded, line_cells, weather_los_m};
+
+fn board() -> Board {
+    Board::empty(16, 16, DEFAULT_CELL_MM)
+}
+
+#[test]
+fn clear_line_within_range_is_visible() {
+    let board = board();
+    let range = weather_los_m(Weather::Clear);
+    assert!(has_los(
+        &board,
+        Point::new(1, 1),
+        Point::new(12, 7),
+        range,
+        DEFAULT_CELL_MM
+    ));
+    assert!(has_los(
+        &board,
+        Point::new(12, 7),
+        Point::new(1, 1),
+        range,
+        DEFAULT_CELL_MM
+    ));
+}
+
+#[test]
+fn wall_on_line_blocks_sight() {
+    let mut board = board();
+    board.set_cell(Point::new(6, 4), Cell::Wall);
+    let range = weather_los_m(Weather::Clear);
+    assert!(!has_los(
+        &board,
+        Point::new(2, 4),
+        Point::new(10, 4),
+        range,
+        DEFAULT_CELL_MM
+    ));
+    assert!(has_los(
+        &board,
+        Point::new(2, 5),
+        Point::new(10, 5),
+        range,
+        DEFAULT_CELL_MM
+    ));
+}
+
+#[test]
+fn target_beyond_range_is_hidden() {
+    let board = board();
+    // 15 cells of 1 m apart: visible at 15 m, hidden at 14 m.
+    assert!(has_los(
+        &board,
+        Point::new(0, 0),
+        Point::new(15, 0),
+        15,
+        DEFAULT_CELL_MM
+    ));
+    assert!(!has_los(
+        &board,
+        Point::new(0, 0),
+        Point::new(15, 0),
+        14,
+        DEFAULT_CELL_MM
+    ));
+    assert!(!has_los(
+        &board,
+        Point::new(0, 0),
+        Point::new(15, 0),
+        weather_los_m(Weather::Fog),
+        2_000
+    ));
+}
+
+#[test]
+fn cover_draws_are_seeded_and_symmetric() {
+    let mut board = board();
+    for y in 0..16 {
+        board.set_cell(Point::new(8, y), Cell::Cover(CoverKind::Crate));
+    }
+    let range = weather_los_m(Weather::Clear);
+    let mut blocked = 0;
+    let mut visible = 0;
+    for seed in 0..64u64 {
+        let a = Point::new(2, (seed % 16) as i32);
+        let b = Point::new(14, 15 - (seed % 16) as i32);
+        let forward = has_los_seeded(&board, a, b, range, DEFAULT_CELL_MM, seed);
+        assert_eq!(
+            forward,
+            has_los_seeded(&board, a, b, range, DEFAULT_CELL_MM, seed)
+        );
+        assert_eq!(
+            forward,
+            has_los_seeded(&board, b, a, range, DEFAULT_CELL_MM, seed)
+        );
+        if forward {
+            visible += 1;
+        } else {
+            blocked += 1;
+        }
+    }
+    assert!(blocked > 0 && visible > 0);
+}
+
+#[test]
+fn bresenham_line_includes_both_ends() {
+    let cells = line_cells(Point::new(0, 0), Point::new(4, 2));
+    assert_eq!(cells.first(), Some(&Point::new(0, 0)));
+    assert_eq!(cells.last(), Some(&Point::new(4, 2)));
+    assert_eq!(cells.len(), 5);
+}
@@ -0,0 +1,77 @@
+use crate::world::board::{Board, Cell, CoverKind, Point, BOARD_SIZE, DEFAULT_CELL_MM};
+use crate::world::board_nav::{path_cost, COVER_PENALTY, STEP_COST};
+
+fn small_board() -> Board {
+    Board::empty(8, 8, DEFAULT_CELL_MM)
+}
+
+#[test]
+fn straight_path_costs_one_step_per_cell() {
+    let board = small_board();
+    assert_eq!(
+        path_cost(&board, Point::new(0, 3), Point::new(7, 3)),
+        Some(7 * STEP_COST)
+    );
+    assert_eq!(
+        path_cost(&board, Point::new(2, 2), Point::new(2, 2)),
+        Some(0)
+    );
+}
+
+#[test]
+fn cover_adds_fixed_penalty() {
+    let mut board = small_board();
+    for y in 0..8 {
+        board.set_cell(Point::new(4, y), Cell::Cover(CoverKind::Brush));
+    }
+    assert_eq!(
+        path_cost(&board, Point::new(0, 3), Point::new(7, 3)),
+        Some(7 * STEP_COST + COVER_PENALTY)
+    );
+
+    // Skirting through a gap one row over costs two extra steps, which is
+    // dearer than pushing through the brush.
+    board.set_cell(Point::new(4, 2), Cell::Open);
+    assert_eq!(
+        path_cost(&board, Point::new(0, 3), Point::new(7, 3)),
+        Some((7 * STEP_COST + COVER_PENALTY).min(9 * STEP_COST))
+    );
+}
+
+#[test]
+fn wall_forces_detour() {
+    let mut board = small_board();
+    for y in 0..7 {
+        board.set_cell(Point::new(4, y), Cell::Wall);
+    }
+    // The only gap is at (4, 7): down four rows, across, and back up.
+    assert_eq!(
+        path_cost(&board, Point::new(0, 3), Point::new(7, 3)),
+        Some(15 * STEP_COST)
+    );
+}
+
+#[test]
+fn unreachable_or_invalid_endpoints_return_none() {
+    let mut board = small_board();
+    for y in 0..8 {
+        board.set_cell(Point::new(4, y), Cell::Wall);
+    }
+    assert_eq!(path_cost(&board, Point::new(0, 3), Point::new(7, 3)), None);
+    assert_eq!(path_cost(&board, Point::new(0, 3), Point::new(4, 3)), None);
+    assert_eq!(path_cost(&board, Point::new(-1, 0), Point::new(2, 2)), None);
+    assert_eq!(path_cost(&board, Point::new(0, 0), Point::new(8, 0)), None);
+}
+
+#[test]
+fn full_size_board_cost_is_stable() {
+    let mut board = Board::empty(BOARD_SIZE, BOARD_SIZE, DEFAULT_CELL_MM);
+    for x in 0..(BOARD_SIZE as i32 - 1) {
+        board.set_cell(Point::new(x, 32), Cell::Wall);
+    }
+    let from = Point::new(0, 0);
+    let to = Point::new(0, BOARD_SIZE as i32 - 1);
+    let first = path_cost(&board, from, to);
+    assert_eq!(first, Some((63 + 63 + 63) * STEP_COST));
+    assert_eq!(path_cost(&board, from, to), first);
+}
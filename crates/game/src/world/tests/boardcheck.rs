@@ -0,0 +1,107 @@
+use crate::world::board::{
+    board_hash, Board, Cell, CoverKind, Point, Rect, Zone, ZoneKind, BOARD_SIZE, DEFAULT_CELL_MM,
+};
+use crate::world::boardcheck::{validate_board, BoardDefect};
+
+fn open_board() -> Board {
+    let mut board = Board::empty(BOARD_SIZE, BOARD_SIZE, DEFAULT_CELL_MM);
+    board.spawns.player.push(Point::new(2, 2));
+    board
+        .spawns
+        .enemy
+        .extend([Point::new(40, 40), Point::new(60, 5)]);
+    board.zones.push(Zone {
+        kind: ZoneKind::Evac,
+        rect: Rect {
+            x: 58,
+            y: 58,
+            w: 4,
+            h: 4,
+        },
+    });
+    board
+}
+
+#[test]
+fn open_board_with_cover_validates() {
+    let mut board = open_board();
+    for x in 0..BOARD_SIZE as i32 {
+        board.set_cell(Point::new(x, 20), Cell::Cover(CoverKind::Brush));
+    }
+    assert_eq!(validate_board(&board), Ok(()));
+}
+
+#[test]
+fn enclosed_spawn_and_walled_evac_are_reported() {
+    let mut board = open_board();
+    for (x, y) in [
+        (1, 1),
+        (2, 1),
+        (3, 1),
+        (1, 2),
+        (3, 2),
+        (1, 3),
+        (2, 3),
+        (3, 3),
+    ] {
+        board.set_cell(Point::new(x, y), Cell::Wall);
+    }
+    board.set_cell(Point::new(59, 60), Cell::Wall);
+    board.set_cell(Point::new(60, 5), Cell::Wall);
+
+    let defects = validate_board(&board).expect_err("board should be rejected");
+    assert_eq!(
+        defects,
+        vec![
+            BoardDefect::EnemySpawnBlocked(Point::new(60, 5)),
+            BoardDefect::ZoneOverlapsWall {
+                zone: 0,
+                cell: Point::new(59, 60),
+            },
+            BoardDefect::EvacUnreachable {
+                spawn: Point::new(2, 2),
+            },
+            BoardDefect::EnemiesUnreachable {
+                spawn: Point::new(2, 2),
+                reachable: 0,
+                total: 2,
+            },
+        ]
+    );
+}
+
+#[test]
+fn missing_spawns_zones_and_bounds_are_reported() {
+    let mut board = Board::empty(8, 8, DEFAULT_CELL_MM);
+    board.zones.push(Zone {
+        kind: ZoneKind::Objective,
+        rect: Rect {
+            x: 6,
+            y: 6,
+            w: 4,
+            h: 1,
+        },
+    });
+    board.spawns.enemy.push(Point::new(9, 0));
+
+    let defects = validate_board(&board).expect_err("board should be rejected");
+    assert_eq!(
+        defects,
+        vec![
+            BoardDefect::NoPlayerSpawns,
+            BoardDefect::NoEvacZones,
+            BoardDefect::EnemySpawnBlocked(Point::new(9, 0)),
+            BoardDefect::ZoneOutOfBounds { zone: 0 },
+        ]
+    );
+}
+
+#[test]
+fn board_hash_tracks_layout() {
+    let board = open_board();
+    let mut walled = board.clone();
+    walled.set_cell(Point::new(10, 10), Cell::Wall);
+    let hash = board_hash(&board).expect("hash");
+    assert_eq!(hash, board_hash(&board.clone()).expect("hash"));
+    assert_ne!(hash, board_hash(&walled).expect("hash"));
+}
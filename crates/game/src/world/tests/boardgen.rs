@@ -0,0 +1,234 @@
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use crate::world::board::{
+    board_hash, Board, Cell, CoverKind, Point, Rect, Zone, ZoneKind, BOARD_SIZE, DEFAULT_CELL_MM,
+};
+use crate::world::board_styles::{BoardStyleParams, BoardStyles, LEGACY_STYLE};
+use crate::world::boardcheck::validate_board;
+use crate::world::boardgen::{ascii_preview, generate_board, MAX_BOARD_RETRIES};
+
+const GOLDEN_SEEDS: [u64; 4] = [1, 7, 0xD7E7, 0xDEAD_BEEF];
+
+fn golden_path() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../../repro/golden/boards.json")
+}
+
+/// Style name to seed to board hash, for `legacy` and every shipped style.
+fn board_hashes() -> BTreeMap<String, BTreeMap<String, String>> {
+    let styles = BoardStyles::shipped();
+    std::iter::once(LEGACY_STYLE)
+        .chain(styles.names())
+        .map(|style| {
+            let hashes = GOLDEN_SEEDS
+                .iter()
+                .map(|&seed| {
+                    let board = generate_board(seed, style, styles).expect("valid board");
+                    (seed.to_string(), board_hash(&board).expect("hash"))
+                })
+                .collect();
+            (style.to_string(), hashes)
+        })
+        .collect()
+}
+
+/// Seeds listed in `repro/seeds.toml`.
+fn listed_seeds() -> Vec<u64> {
+    #[derive(serde::Deserialize)]
+    #[serde(deny_unknown_fields)]
+    struct SeedsFile {
+        seeds: Vec<String>,
+    }
+
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../../repro/seeds.toml");
+    let raw = std::fs::read_to_string(path).expect("read repro/seeds.toml");
+    let file: SeedsFile = toml::from_str(&raw).expect("parse repro/seeds.toml");
+    file.seeds
+        .iter()
+        .map(|seed| {
+            let hex = seed.strip_prefix("0x").expect("hex seed");
+            u64::from_str_radix(hex, 16).expect("hex seed")
+        })
+        .collect()
+}
+
+fn count_cells(board: &Board, wanted: fn(Cell) -> bool) -> usize {
+    board.cells.iter().filter(|&&cell| wanted(cell)).count()
+}
+
+#[test]
+fn tiny_board_renders_each_glyph_in_place() {
+    let mut board = Board::empty(6, 4, DEFAULT_CELL_MM);
+    board.set_cell(Point::new(0, 0), Cell::Wall);
+    board.set_cell(Point::new(1, 0), Cell::Cover(CoverKind::Rock));
+    board.set_cell(Point::new(2, 0), Cell::Cover(CoverKind::Crate));
+    board.set_cell(Point::new(3, 0), Cell::Cover(CoverKind::Brush));
+    board.set_cell(Point::new(5, 3), Cell::Wall);
+    board.spawns.player.push(Point::new(0, 3));
+    board
+        .spawns
+        .enemy
+        .extend([Point::new(5, 0), Point::new(9, 9)]);
+    board.zones.push(Zone {
+        kind: ZoneKind::Evac,
+        rect: Rect {
+            x: 4,
+            y: 2,
+            w: 3,
+            h: 2,
+        },
+    });
+    board.zones.push(Zone {
+        kind: ZoneKind::Objective,
+        rect: Rect {
+            x: 0,
+            y: 1,
+            w: 2,
+            h: 1,
+        },
+    });
+
+    assert_eq!(
+        ascii_preview(&board),
+        "#rcb.&\n\
+         **....\n\
+         ....++\n\
+         @...+#\n"
+    );
+}
+
+#[test]
+fn full_board_is_a_newline_terminated_square() {
+    let preview = ascii_preview(&Board::empty(BOARD_SIZE, BOARD_SIZE, DEFAULT_CELL_MM));
+    let rows: Vec<&str> = preview.split_terminator('\n').collect();
+    assert_eq!(rows.len(), BOARD_SIZE as usize);
+    assert!(rows.iter().all(|row| row.len() == BOARD_SIZE as usize));
+    assert!(preview.ends_with('\n'));
+}
+
+#[test]
+fn shipped_styles_load_strictly() {
+    let styles = BoardStyles::shipped();
+    assert_eq!(
+        styles.names().collect::<Vec<_>>(),
+        ["coast", "ridge", "wetland"]
+    );
+
+    let valid = "[styles.dunes]\nwall_runs = [1, 2]\ncover_pct = [0, 5]\nzones = [1, 1]\n\
+                 cover_weights = { rock = 1 }\n";
+    assert!(BoardStyles::from_toml_str(valid).is_ok());
+    for bad in [
+        valid.replace("zones", "zone_count"),
+        valid.replace("dunes", LEGACY_STYLE),
+        valid.replace("[1, 2]", "[3, 2]"),
+        valid.replace("[1, 2]", "[1, 33]"),
+        valid.replace("[0, 5]", "[0, 101]"),
+        valid.replace("zones = [1, 1]", "zones = [0, 1]"),
+        valid.replace("rock = 1", "rock = 0"),
+        valid.replace("rock = 1", "sand = 1"),
+    ] {
+        assert!(BoardStyles::from_toml_str(&bad).is_err(), "{bad}");
+    }
+}
+
+#[test]
+fn unknown_styles_use_the_legacy_recipe() {
+    let styles = BoardStyles::shipped();
+    assert_eq!(*styles.params("tundra"), BoardStyleParams::LEGACY);
+    assert_eq!(*styles.params(LEGACY_STYLE), BoardStyleParams::LEGACY);
+    assert_eq!(
+        generate_board(7, LEGACY_STYLE, styles),
+        generate_board(7, LEGACY_STYLE, &BoardStyles::default())
+    );
+}
+
+#[test]
+fn styles_from_one_seed_differ_within_their_ranges() {
+    let styles = BoardStyles::shipped();
+    for seed in GOLDEN_SEEDS {
+        let mut walls = BTreeMap::new();
+        let mut boards = Vec::new();
+        for style in styles.names() {
+            let params = styles.params(style);
+            let board = generate_board(seed, style, styles).expect("valid board");
+            assert_eq!(validate_board(&board), Ok(()));
+            assert!(params.zones.contains(board.zones.len() as u32), "{style}");
+
+            let wall_cells = count_cells(&board, |cell| cell == Cell::Wall);
+            assert!(wall_cells <= params.wall_runs.max as usize * 12, "{style}");
+            let cover = count_cells(&board, |cell| matches!(cell, Cell::Cover(_)));
+            let reserved = board
+                .zones
+                .iter()
+                .flat_map(|zone| zone.rect.cells())
+                .chain(board.spawns.player.iter().copied())
+                .chain(board.spawns.enemy.iter().copied())
+                .filter_map(|point| board.index(point))
+                .collect::<std::collections::BTreeSet<_>>();
+            let candidates = board.cells.len() - wall_cells - reserved.len();
+            assert!(
+                cover >= candidates * params.cover_pct.min as usize / 100
+                    && cover <= candidates * params.cover_pct.max as usize / 100,
+                "{style}: {cover} cover of {candidates}"
+            );
+
+            walls.insert(style, wall_cells);
+            boards.push(board);
+        }
+        assert!(walls["ridge"] > walls["coast"], "seed {seed}: {walls:?}");
+        assert_ne!(boards[0], boards[1]);
+        assert_ne!(boards[1], boards[2]);
+        assert_ne!(boards[0], boards[2]);
+    }
+}
+
+#[test]
+fn wetland_cover_is_mostly_brush() {
+    let styles = BoardStyles::shipped();
+    let board = generate_board(0xD7E7, "wetland", styles).expect("valid board");
+    let brush = count_cells(&board, |cell| cell == Cell::Cover(CoverKind::Brush));
+    let cover = count_cells(&board, |cell| matches!(cell, Cell::Cover(_)));
+    assert!(brush * 2 > cover, "{brush} brush of {cover} cover");
+}
+
+#[test]
+fn listed_seeds_validate_in_every_style() {
+    let styles = BoardStyles::shipped();
+    let seeds = listed_seeds();
+    assert!(seeds.contains(&0xD7E7), "{seeds:?}");
+    for style in std::iter::once(LEGACY_STYLE).chain(styles.names()) {
+        for &seed in &seeds {
+            let board = generate_board(seed, style, styles)
+                .unwrap_or_else(|defects| panic!("{style} {seed:#x}: {defects:?}"));
+            assert!(board.retries <= MAX_BOARD_RETRIES);
+            assert_eq!(validate_board(&board), Ok(()), "{style} {seed:#x}");
+        }
+    }
+}
+
+#[test]
+fn retry_count_is_part_of_the_hash() {
+    let board = generate_board(7, LEGACY_STYLE, BoardStyles::shipped()).expect("valid board");
+    let mut retried = board.clone();
+    retried.retries += 1;
+    assert_ne!(
+        board_hash(&board).expect("hash"),
+        board_hash(&retried).expect("hash")
+    );
+}
+
+#[test]
+fn generated_boards_match_goldens() {
+    let raw = std::fs::read_to_string(golden_path()).expect("read board goldens");
+    let golden: BTreeMap<String, BTreeMap<String, String>> =
+        serde_json::from_str(&raw).expect("parse board goldens");
+    assert_eq!(board_hashes(), golden);
+}
+
+#[test]
+#[ignore = "rewrites repro/golden/boards.json"]
+fn generate_golden_boards() {
+    let mut json = serde_json::to_string_pretty(&board_hashes()).expect("encode");
+    json.push('\n');
+    std::fs::write(golden_path(), json).expect("write board goldens");
+}
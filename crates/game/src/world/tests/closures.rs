@@ -0,0 +1,49 @@
+use crate::systems::economy::{EconomyDay, HubId, RouteId};
+use crate::world::closures::RouteClosures;
+use crate::world::index::StaticWorldIndex;
+
+#[test]
+fn closure_reopens_on_until_day() {
+    let mut closures = RouteClosures::default();
+    assert!(closures.close(RouteId(2), EconomyDay(7)));
+    assert!(closures.is_closed(RouteId(2), EconomyDay(4)));
+    assert!(closures.is_closed(RouteId(2), EconomyDay(6)));
+    assert!(!closures.is_closed(RouteId(2), EconomyDay(7)));
+    assert!(!closures.is_closed(RouteId(1), EconomyDay(4)));
+
+    closures.expire(EconomyDay(6));
+    assert!(!closures.is_empty());
+    closures.expire(EconomyDay(7));
+    assert!(closures.is_empty());
+}
+
+#[test]
+fn shorter_closure_does_not_shorten_longer_one() {
+    let mut closures = RouteClosures::default();
+    assert!(closures.close(RouteId(3), EconomyDay(10)));
+    assert!(!closures.close(RouteId(3), EconomyDay(8)));
+    assert!(closures.is_closed(RouteId(3), EconomyDay(9)));
+    assert!(closures.close(RouteId(3), EconomyDay(12)));
+    assert_eq!(
+        closures.entries().collect::<Vec<_>>(),
+        vec![(RouteId(3), EconomyDay(12))]
+    );
+}
+
+#[test]
+fn closed_counts_touch_both_endpoints() {
+    let mut closures = RouteClosures::default();
+    closures.close(RouteId(1), EconomyDay(5));
+    closures.close(RouteId(2), EconomyDay(5));
+    closures.close(RouteId(4), EconomyDay(2));
+
+    let counts = closures.closed_counts_by_hub::<StaticWorldIndex>(EconomyDay(3));
+    assert_eq!(counts.get(&HubId(1)), Some(&1));
+    assert_eq!(counts.get(&HubId(2)), Some(&2));
+    assert_eq!(counts.get(&HubId(3)), Some(&1));
+    assert_eq!(counts.get(&HubId(4)), None);
+    assert_eq!(
+        closures.closed_on(EconomyDay(3)),
+        [RouteId(1), RouteId(2)].into_iter().collect()
+    );
+}
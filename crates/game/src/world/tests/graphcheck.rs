@@ -0,0 +1,93 @@
+use std::path::PathBuf;
+
+use crate::systems::economy::{HubId, RouteId};
+use crate::world::graphcheck::{validate_world_graph, GraphDefect};
+use crate::world::index::{load_world_graph_data, WorldGraphData};
+
+fn asset_path(relative: &str) -> PathBuf {
+    let manifest = env!("CARGO_MANIFEST_DIR");
+    PathBuf::from(manifest).join("..").join("..").join(relative)
+}
+
+fn graph(hubs: &[u16], routes: &[(u16, u16, u16)]) -> WorldGraphData {
+    let mut raw = format!("hubs = {hubs:?}\n");
+    for (id, from, to) in routes {
+        raw.push_str(&format!(
+            "[[routes]]\nid = {id}\nfrom = {from}\nto = {to}\nweather = \"Clear\"\n"
+        ));
+    }
+    toml::from_str(&raw).expect("fixture graph")
+}
+
+#[test]
+fn shipped_graph_validates() {
+    let path = asset_path("assets/world/hubs_min.toml");
+    let data = load_world_graph_data(&path).expect("world graph");
+    assert_eq!(validate_world_graph(&data), Ok(()));
+}
+
+#[test]
+fn unknown_hub_references_are_reported() {
+    let data = graph(&[1, 2], &[(1, 1, 2), (2, 2, 9)]);
+    assert_eq!(
+        validate_world_graph(&data),
+        Err(vec![GraphDefect::UnknownHub {
+            route: RouteId(2),
+            hub: HubId(9),
+        }])
+    );
+}
+
+#[test]
+fn hubs_over_route_limit_are_errors() {
+    let routes: Vec<(u16, u16, u16)> = (2..=8).map(|hub| (hub, 1, hub)).collect();
+    let hubs: Vec<u16> = (1..=8).collect();
+    let defects = validate_world_graph(&graph(&hubs, &routes)).expect_err("too many routes");
+    assert_eq!(
+        defects,
+        vec![GraphDefect::TooManyRoutes {
+            hub: HubId(1),
+            count: 7,
+        }]
+    );
+}
+
+#[test]
+fn smaller_components_are_listed() {
+    let data = graph(&[1, 2, 3, 4, 5, 6], &[(1, 1, 2), (2, 2, 3), (3, 4, 5)]);
+    assert_eq!(
+        validate_world_graph(&data),
+        Err(vec![
+            GraphDefect::Disconnected {
+                hubs: vec![HubId(4), HubId(5)],
+            },
+            GraphDefect::Disconnected {
+                hubs: vec![HubId(6)],
+            },
+        ])
+    );
+}
+
+#[test]
+fn duplicate_links_and_ids_are_reported() {
+    let data = graph(&[1, 2, 3], &[(1, 1, 2), (2, 2, 1), (3, 2, 3), (3, 1, 3)]);
+    assert_eq!(
+        validate_world_graph(&data),
+        Err(vec![
+            GraphDefect::DuplicateRouteId(RouteId(3)),
+            GraphDefect::DuplicateLink {
+                hubs: (HubId(1), HubId(2)),
+                first: RouteId(1),
+                duplicate: RouteId(2),
+            },
+        ])
+    );
+}
+
+#[test]
+fn defects_render_readably() {
+    let defect = GraphDefect::Disconnected {
+        hubs: vec![HubId(4), HubId(5)],
+    };
+    assert_eq!(defect.to_string(), "hubs [4, 5] are disconnected");
+}
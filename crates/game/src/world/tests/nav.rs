@@ -0,0 +1,134 @@
+use crate::systems::director::rng::DetRng;
+use crate::world::board::{Board, Cell, CoverKind, Point, BOARD_SIZE, DEFAULT_CELL_MM};
+use crate::world::board_nav::{enter_cost, path_cost, search, Search};
+use crate::world::nav::NavGrid;
+
+/// Open 64×64 board with scattered walls and cover drawn from `seed`.
+fn seeded_board(seed: u64) -> Board {
+    let mut board = Board::empty(BOARD_SIZE, BOARD_SIZE, DEFAULT_CELL_MM);
+    let mut rng = DetRng::from_seed(seed);
+    for idx in 0..board.cells.len() {
+        board.cells[idx] = match rng.range_u32(0, 99) {
+            0..=21 => Cell::Wall,
+            22..=29 => Cell::Cover(CoverKind::Brush),
+            _ => Cell::Open,
+        };
+    }
+    board.set_cell(Point::new(0, 0), Cell::Open);
+    board.set_cell(Point::new(63, 63), Cell::Open);
+    board
+}
+
+#[test]
+fn straight_corridor_path_visits_every_cell() {
+    let mut board = Board::empty(8, 3, DEFAULT_CELL_MM);
+    for x in 0..8 {
+        board.set_cell(Point::new(x, 0), Cell::Wall);
+        board.set_cell(Point::new(x, 2), Cell::Wall);
+    }
+    let grid = NavGrid::from_board(&board);
+    let path = grid
+        .find_path(Point::new(0, 1), Point::new(7, 1))
+        .expect("corridor path");
+    assert_eq!(path.len(), 8);
+    assert_eq!(path.first(), Some(&Point::new(0, 1)));
+    assert_eq!(path.last(), Some(&Point::new(7, 1)));
+    assert!(path.iter().all(|p| p.y == 1));
+}
+
+#[test]
+fn walled_off_target_has_no_path() {
+    let mut board = Board::empty(8, 8, DEFAULT_CELL_MM);
+    for (x, y) in [(5, 4), (7, 4), (6, 3), (6, 5)] {
+        board.set_cell(Point::new(x, y), Cell::Wall);
+    }
+    let grid = NavGrid::from_board(&board);
+    assert_eq!(grid.find_path(Point::new(0, 0), Point::new(6, 4)), None);
+    assert!(!grid.reachable(Point::new(0, 0))[4 * 8 + 6]);
+    assert_eq!(grid.reachable(Point::new(6, 4)).count_ones(..), 1);
+}
+
+#[test]
+fn cover_is_walkable_but_flagged() {
+    let mut board = Board::empty(4, 1, DEFAULT_CELL_MM);
+    board.set_cell(Point::new(2, 0), Cell::Cover(CoverKind::Rock));
+    let grid = NavGrid::from_board(&board);
+    assert!(grid.is_walkable(Point::new(2, 0)));
+    assert!(grid.is_cover(Point::new(2, 0)));
+    assert_eq!(
+        grid.find_path(Point::new(0, 0), Point::new(3, 0))
+            .map(|path| path.len()),
+        Some(4)
+    );
+}
+
+#[test]
+fn mm_conversion_round_trips_cell_centres() {
+    let board = Board::empty(BOARD_SIZE, BOARD_SIZE, 500);
+    let grid = NavGrid::from_board(&board);
+    let cell = Point::new(12, 40);
+    let (x_mm, y_mm) = board.cell_center_mm(cell);
+    assert_eq!((x_mm, y_mm), (6_250, 20_250));
+    assert_eq!(grid.mm_to_cell(x_mm, y_mm), Some(cell));
+    assert_eq!(grid.mm_to_cell(-1, 0), None);
+    assert_eq!(grid.mm_to_cell(32_000, 0), None);
+}
+
+#[test]
+fn seeded_board_paths_are_identical_across_runs() {
+    for seed in [0x5EED_0001_u64, 0x5EED_0002, 0xD7E7_2024_0001_0001] {
+        let run = || {
+            let grid = NavGrid::from_board(&seeded_board(seed));
+            let path = grid.find_path(Point::new(0, 0), Point::new(63, 63));
+            let reachable: Vec<usize> = grid.reachable(Point::new(0, 0)).ones().collect();
+            serde_json::to_vec(&(path, reachable)).expect("encode")
+        };
+        assert_eq!(run(), run(), "seed {seed:#x}");
+    }
+}
+
+#[test]
+fn full_board_path_expands_each_cell_at_most_once() {
+    let mut board = Board::empty(BOARD_SIZE, BOARD_SIZE, DEFAULT_CELL_MM);
+    // Serpentine walls force the search across most of the board.
+    for row in (4..BOARD_SIZE as i32).step_by(8) {
+        let gap = if (row / 8) % 2 == 0 { 63 } else { 0 };
+        for x in 0..BOARD_SIZE as i32 {
+            if x != gap {
+                board.set_cell(Point::new(x, row), Cell::Wall);
+            }
+        }
+    }
+    let grid = NavGrid::from_board(&board);
+    let walkable = board.cells.iter().filter(|cell| cell.is_walkable()).count();
+    let Search { path, expanded } = search(&grid, Point::new(0, 0), Point::new(0, 63));
+    assert!(path.is_some());
+    // The walls leave no shortcut, so most of the board is searched, but
+    // never a cell twice.
+    assert!(expanded > walkable / 2, "{expanded} of {walkable}");
+    assert!(expanded <= walkable, "{expanded} of {walkable}");
+}
+
+#[test]
+fn nav_paths_cost_what_board_path_cost_reports() {
+    let mut routed = 0;
+    for seed in [0x5EED_0001_u64, 0x5EED_0002, 0xD7E7_2024_0001_0001] {
+        let board = seeded_board(seed);
+        let (from, to) = (Point::new(0, 0), Point::new(63, 63));
+        let path = NavGrid::from_board(&board).find_path(from, to);
+        routed += usize::from(path.is_some());
+        let walked = path.as_ref().map(|path| {
+            path.iter()
+                .skip(1)
+                .map(|&p| enter_cost(board.cell(p).expect("on board")).expect("walkable"))
+                .sum::<u32>()
+        });
+        assert_eq!(walked, path_cost(&board, from, to), "seed {seed:#x}");
+        assert_eq!(
+            search(&board, from, to).path.map(|(_, path)| path),
+            path,
+            "seed {seed:#x}"
+        );
+    }
+    assert!(routed > 0, "no seeded board had a path");
+}
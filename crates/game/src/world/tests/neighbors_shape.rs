@@ -2,7 +2,7 @@ use std::collections::HashSet;
 use std::path::PathBuf;
 
 use crate::systems::economy::{HubId, RouteId};
-use crate::world::index::{StaticWorldIndex, WorldIndex};
+use crate::world::index::{parse_routes, StaticWorldIndex, WorldIndex};
 use serde::Deserialize;
 
 fn asset_path(relative: &str) -> PathBuf {
@@ -30,7 +30,10 @@ fn neighbor_lists_are_bounded() {
 
     let mut hubs: HashSet<HubId> = HashSet::new();
     for route in &cfg.routes {
-        let _ = route.id;
+        assert_eq!(
+            StaticWorldIndex::route_endpoints(route.id),
+            Some((route.from, route.to))
+        );
         hubs.insert(route.from);
         hubs.insert(route.to);
     }
@@ -38,8 +41,29 @@ fn neighbor_lists_are_bounded() {
     for hub in hubs {
         let neighbors = StaticWorldIndex::neighbors(hub);
         assert!(neighbors.len() <= 6);
+        assert!(!StaticWorldIndex::neighbor_overflow(hub));
         for route in neighbors {
             assert_ne!(route.0, 0);
         }
     }
 }
+
+#[test]
+fn overflowing_hub_keeps_its_lowest_route_ids() {
+    let mut raw = String::new();
+    for id in [18, 11, 15, 12, 17, 13, 16, 14] {
+        raw.push_str(&format!(
+            "[[routes]]\nid = {id}\nfrom = 1\nto = {id}\nweather = \"Clear\"\n\n"
+        ));
+    }
+    let dir = tempfile::tempdir().expect("tempdir");
+    let path = dir.path().join("star.toml");
+    std::fs::write(&path, raw).expect("write graph");
+
+    let data = parse_routes(&path).expect("over-cap hubs still load");
+    let kept: Vec<u16> = data.neighbors_of(HubId(1)).iter().map(|id| id.0).collect();
+    assert_eq!(kept, vec![11, 12, 13, 14, 15, 16]);
+    assert!(data.overflow.contains(&HubId(1)));
+    assert_eq!(data.neighbors_of(HubId(18)).as_slice(), &[RouteId(18)]);
+    assert!(!data.overflow.contains(&HubId(18)));
+}
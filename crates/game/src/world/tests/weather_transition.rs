@@ -0,0 +1,89 @@
+use crate::systems::economy::{RouteId, Weather};
+use crate::world::index::{StaticWorldIndex, WorldIndex};
+use crate::world::weather::{next_weather, WeatherTransition, WEATHER_ORDER};
+
+fn forcing_clear_to_fog() -> WeatherTransition {
+    WeatherTransition::new([
+        [0, 0, 10_000, 0],
+        [10_000, 0, 0, 0],
+        [0, 10_000, 0, 0],
+        [0, 0, 0, 10_000],
+    ])
+    .expect("matrix")
+}
+
+#[test]
+fn transitions_repeat_for_the_same_inputs() {
+    for route in [RouteId(1), RouteId(4)] {
+        let first: Vec<Weather> = (1..=60)
+            .map(|day| StaticWorldIndex::route_weather_on_day(route, 0xD7E7, day))
+            .collect();
+        let second: Vec<Weather> = (1..=60)
+            .map(|day| StaticWorldIndex::route_weather_on_day(route, 0xD7E7, day))
+            .collect();
+        assert_eq!(first, second);
+    }
+    assert_eq!(
+        next_weather(Weather::Fog, 7, RouteId(2), 9),
+        next_weather(Weather::Fog, 7, RouteId(2), 9)
+    );
+}
+
+#[test]
+fn sampling_respects_the_matrix() {
+    let matrix = forcing_clear_to_fog();
+    for seed in 0..50 {
+        for day in 1..20 {
+            assert_eq!(
+                matrix.next(Weather::Clear, seed, RouteId(3), day),
+                Weather::Fog
+            );
+            assert_eq!(
+                matrix.next(Weather::Fog, seed, RouteId(3), day),
+                Weather::Rains
+            );
+        }
+    }
+
+    let split = WeatherTransition::new([[5_000, 5_000, 0, 0]; 4]).expect("matrix");
+    let mut seen = [false; 4];
+    for seed in 0..400 {
+        let next = split.next(Weather::Windy, seed, RouteId(1), 1);
+        let index = WEATHER_ORDER.iter().position(|w| *w == next).unwrap();
+        seen[index] = true;
+    }
+    assert_eq!(seen, [true, true, false, false]);
+}
+
+#[test]
+fn day_zero_matches_the_static_weather() {
+    let shipped = WeatherTransition::shipped();
+    for route in (1..=6).map(RouteId) {
+        let day_zero = StaticWorldIndex::route_weather(route);
+        assert_eq!(
+            StaticWorldIndex::route_weather_on_day(route, 42, 0),
+            day_zero
+        );
+        assert_eq!(
+            StaticWorldIndex::route_weather_on_day(route, 42, 1),
+            shipped.next(day_zero, 42, route, 1)
+        );
+    }
+}
+
+#[test]
+fn rows_must_sum_to_ten_thousand() {
+    let err = WeatherTransition::new([[2_500; 4], [2_500; 4], [9_999, 0, 0, 0], [2_500; 4]])
+        .expect_err("short row");
+    assert!(err.to_string().contains("Fog"), "{err}");
+    let err = WeatherTransition::from_toml_str(
+        "[transitions.Clear]\nClear = 10000\n[transitions.Rains]\nRains = 10000\n\
+         [transitions.Fog]\nFog = 10000\n[transitions.Windy]\nWindy = 10000\nSnow = 1\n",
+    )
+    .expect_err("unknown weather");
+    assert!(format!("{err:#}").contains("Snow"), "{err:#}");
+    assert_eq!(
+        WeatherTransition::shipped().bp(Weather::Fog, Weather::Rains),
+        3_500
+    );
+}
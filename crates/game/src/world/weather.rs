@@ -0,0 +1,169 @@
+//! Day-to-day weather transitions for routes.
+//!
+//! A route's weather on day 0 is the one listed in the world asset; each
+//! later day samples a row of the transition matrix in
+//! `assets/world/weather.toml`, seeded by the world seed, route and day.
+
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+use anyhow::{bail, Context};
+use serde::Deserialize;
+
+use crate::systems::economy::{RouteId, Weather};
+
+use super::index::splitmix64;
+
+/// Row and column order of [`WeatherTransition`] matrices.
+pub const WEATHER_ORDER: [Weather; 4] =
+    [Weather::Clear, Weather::Rains, Weather::Fog, Weather::Windy];
+
+/// Basis points every matrix row must sum to.
+pub const ROW_TOTAL_BP: u32 = 10_000;
+
+static SHIPPED: OnceLock<Result<WeatherTransition, String>> = OnceLock::new();
+
+/// Chance, in basis points, of each weather tomorrow given today's. Rows and
+/// columns follow [`WEATHER_ORDER`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WeatherTransition {
+    rows: [[u32; 4]; 4],
+}
+
+impl WeatherTransition {
+    /// Checks that every row sums to [`ROW_TOTAL_BP`].
+    pub fn new(rows: [[u32; 4]; 4]) -> anyhow::Result<Self> {
+        for (from, row) in WEATHER_ORDER.iter().zip(&rows) {
+            let total: u64 = row.iter().map(|bp| u64::from(*bp)).sum();
+            if total != u64::from(ROW_TOTAL_BP) {
+                bail!("{from:?} transitions sum to {total}bp, expected {ROW_TOTAL_BP}bp");
+            }
+        }
+        Ok(Self { rows })
+    }
+
+    pub fn load_from_path(path: &Path) -> anyhow::Result<Self> {
+        let raw =
+            std::fs::read_to_string(path).with_context(|| format!("reading {}", path.display()))?;
+        Self::from_toml_str(&raw).with_context(|| format!("parsing {}", path.display()))
+    }
+
+    pub fn from_toml_str(raw: &str) -> anyhow::Result<Self> {
+        let file: WeatherFile = toml::from_str(raw)?;
+        let t = file.transitions;
+        Self::new([t.clear.bp(), t.rains.bp(), t.fog.bp(), t.windy.bp()])
+    }
+
+    /// The matrix in `assets/world/weather.toml`.
+    ///
+    /// # Panics
+    /// When the asset is missing or invalid.
+    pub fn shipped() -> &'static WeatherTransition {
+        let loaded = SHIPPED.get_or_init(|| {
+            weather_path()
+                .and_then(|path| Self::load_from_path(&path))
+                .map_err(|err| format!("{err:#}"))
+        });
+        match loaded {
+            Ok(matrix) => matrix,
+            Err(err) => panic!("failed to load weather transitions: {err}"),
+        }
+    }
+
+    /// Chance of moving from `from` to `to`, in basis points.
+    pub fn bp(&self, from: Weather, to: Weather) -> u32 {
+        self.rows[weather_index(from)][weather_index(to)]
+    }
+
+    /// Tomorrow's weather on `route` when it is `current` on the day before
+    /// `day`. The same inputs always give the same weather.
+    pub fn next(&self, current: Weather, seed: u64, route: RouteId, day: u32) -> Weather {
+        let mut key = [0u8; 13];
+        key[..7].copy_from_slice(b"weather");
+        key[7..9].copy_from_slice(&route.0.to_le_bytes());
+        key[9..].copy_from_slice(&day.to_le_bytes());
+        let mut state = wyhash::wyhash(&key, seed);
+        let roll = (splitmix64(&mut state) % u64::from(ROW_TOTAL_BP)) as u32;
+
+        let row = &self.rows[weather_index(current)];
+        let mut cumulative = 0u32;
+        for (weather, bp) in WEATHER_ORDER.iter().zip(row) {
+            cumulative += bp;
+            if roll < cumulative {
+                return *weather;
+            }
+        }
+        current
+    }
+}
+
+/// Samples the shipped matrix; see [`WeatherTransition::next`].
+pub fn next_weather(current: Weather, seed: u64, route: RouteId, day: u32) -> Weather {
+    WeatherTransition::shipped().next(current, seed, route, day)
+}
+
+/// Path of the shipped transition matrix, relative to the working directory
+/// when present and to the workspace otherwise.
+pub fn weather_path() -> anyhow::Result<PathBuf> {
+    let primary = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("..")
+        .join("..")
+        .join("assets/world/weather.toml");
+    let search_paths = [Path::new("assets/world/weather.toml"), primary.as_path()];
+    for path in search_paths {
+        if path.exists() {
+            return Ok(path.to_path_buf());
+        }
+    }
+    Err(anyhow::anyhow!(
+        "missing weather transitions at {}",
+        primary.display()
+    ))
+}
+
+fn weather_index(weather: Weather) -> usize {
+    match weather {
+        Weather::Clear => 0,
+        Weather::Rains => 1,
+        Weather::Fog => 2,
+        Weather::Windy => 3,
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct WeatherFile {
+    transitions: TransitionRows,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "PascalCase")]
+struct TransitionRows {
+    clear: TransitionRow,
+    rains: TransitionRow,
+    fog: TransitionRow,
+    windy: TransitionRow,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "PascalCase")]
+struct TransitionRow {
+    #[serde(default)]
+    clear: u32,
+    #[serde(default)]
+    rains: u32,
+    #[serde(default)]
+    fog: u32,
+    #[serde(default)]
+    windy: u32,
+}
+
+impl TransitionRow {
+    fn bp(&self) -> [u32; 4] {
+        [self.clear, self.rains, self.fog, self.windy]
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/weather_transition.rs"]
+mod weather_transition;
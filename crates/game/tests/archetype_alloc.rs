@@ -0,0 +1,65 @@
+//! Archetype lookups run for every spawn, so they must not allocate. This
+//! lives in its own test binary because it installs a counting allocator;
+//! with `count_allocations` the game's own counter is used instead.
+
+#[cfg(not(feature = "count_allocations"))]
+use std::alloc::{GlobalAlloc, Layout, System};
+#[cfg(not(feature = "count_allocations"))]
+use std::cell::Cell;
+
+use game::systems::director::archetypes::{archetypes_path, ArchetypeCatalog};
+
+#[cfg(not(feature = "count_allocations"))]
+struct CountingAlloc;
+
+#[cfg(not(feature = "count_allocations"))]
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+#[cfg(not(feature = "count_allocations"))]
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.with(|count| count.set(count.get() + 1));
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) }
+    }
+}
+
+#[cfg(not(feature = "count_allocations"))]
+#[global_allocator]
+static GLOBAL: CountingAlloc = CountingAlloc;
+
+#[cfg(not(feature = "count_allocations"))]
+fn allocations() -> usize {
+    ALLOCATIONS.with(Cell::get)
+}
+
+#[cfg(feature = "count_allocations")]
+fn allocations() -> usize {
+    game::bench::allocation_count().map_or(0, |count| count as usize)
+}
+
+#[test]
+fn archetype_lookups_do_not_allocate() {
+    let catalog = ArchetypeCatalog::load_from_path(&archetypes_path()).expect("catalog");
+    let kinds = ["bandit", "scout", "brute", "missing"];
+
+    let before = allocations();
+    let mut threat = 0u32;
+    for _ in 0..1_000 {
+        for kind in kinds {
+            threat = threat.wrapping_add(catalog.threat(kind));
+            if let Some(archetype) = catalog.get(kind) {
+                threat = threat.wrapping_add(archetype.hp);
+            }
+        }
+    }
+    let after = allocations();
+
+    assert!(threat > 0);
+    assert_eq!(after - before, 0, "archetype lookups allocated");
+}
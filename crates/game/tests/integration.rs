@@ -1,11 +1,39 @@
+#[path = "integration/audio_cues.rs"]
+mod audio_cues;
+#[path = "integration/autosave_record.rs"]
+mod autosave_record;
+#[path = "integration/bench.rs"]
+mod bench;
 #[path = "integration/buy_sell_flow_headless.rs"]
 mod buy_sell_flow_headless;
+#[path = "integration/campaign_chain.rs"]
+mod campaign_chain;
+#[path = "integration/checkpoint_replay.rs"]
+mod checkpoint_replay;
 #[path = "integration/danger_sign.rs"]
 mod danger_sign;
 #[path = "integration/director_config_strict.rs"]
 mod director_config_strict;
+#[path = "integration/director_overlay.rs"]
+mod director_overlay;
+#[path = "integration/fast_forward.rs"]
+mod fast_forward;
+#[path = "integration/fixed_dt.rs"]
+mod fixed_dt;
+#[path = "integration/golden_manifest.rs"]
+mod golden_manifest;
+#[path = "integration/headless_runner.rs"]
+mod headless_runner;
 #[path = "integration/hub_trade_ui.rs"]
 mod hub_trade_ui;
+#[path = "integration/idle_coalescing.rs"]
+mod idle_coalescing;
+#[path = "integration/leg_length.rs"]
+mod leg_length;
+#[path = "integration/leg_phases.rs"]
+mod leg_phases;
+#[path = "integration/meter_manifest.rs"]
+mod meter_manifest;
 #[path = "integration/migrate_roundtrip.rs"]
 mod migrate_roundtrip;
 #[path = "integration/migrate_v1_to_v11.rs"]
@@ -16,19 +44,67 @@ mod missions_cfg_link;
 mod missions_determinism;
 #[path = "integration/physics_step.rs"]
 mod physics_step;
+#[path = "integration/post_leg_report.rs"]
+mod post_leg_report;
+#[path = "integration/price_view_cache.rs"]
+mod price_view_cache;
+#[path = "integration/record_annotations.rs"]
+mod record_annotations;
+#[path = "integration/record_provenance.rs"]
+mod record_provenance;
+#[path = "integration/record_schema.rs"]
+mod record_schema;
 #[path = "integration/replay_golden.rs"]
 mod replay_golden;
+#[path = "integration/replay_mismatch_context.rs"]
+mod replay_mismatch_context;
+#[path = "integration/route_closures.rs"]
+mod route_closures;
+#[path = "integration/rulepack_schedule.rs"]
+mod rulepack_schedule;
+#[path = "integration/save_backup_recovery.rs"]
+mod save_backup_recovery;
 #[path = "integration/save_load_integration.rs"]
 mod save_load_integration;
+#[path = "integration/save_slots.rs"]
+mod save_slots;
+#[path = "integration/schedule_contract.rs"]
+mod schedule_contract;
 #[path = "integration/schedule_order.rs"]
 mod schedule_order;
+#[path = "integration/self_test.rs"]
+mod self_test;
 #[path = "integration/serde_v11_roundtrip.rs"]
 mod serde_v11_roundtrip;
+#[path = "integration/serde_v12_roundtrip.rs"]
+mod serde_v12_roundtrip;
+#[path = "integration/serde_v13_roundtrip.rs"]
+mod serde_v13_roundtrip;
+#[path = "integration/serde_v14_roundtrip.rs"]
+mod serde_v14_roundtrip;
+#[path = "integration/serde_v15_roundtrip.rs"]
+mod serde_v15_roundtrip;
+#[path = "integration/serde_v16_roundtrip.rs"]
+mod serde_v16_roundtrip;
+#[path = "integration/serde_v17_roundtrip.rs"]
+mod serde_v17_roundtrip;
+#[path = "integration/serde_v18_roundtrip.rs"]
+mod serde_v18_roundtrip;
+#[path = "integration/soak.rs"]
+mod soak;
+#[path = "integration/spawn_board_placement.rs"]
+mod spawn_board_placement;
 #[path = "integration/spawn_monotone.rs"]
 mod spawn_monotone;
 #[path = "integration/spawn_type_determinism.rs"]
 mod spawn_type_determinism;
+#[path = "integration/tick_profile.rs"]
+mod tick_profile;
+#[path = "integration/trading_session.rs"]
+mod trading_session;
 #[path = "integration/ui_vm_shape.rs"]
 mod ui_vm_shape;
 #[path = "integration/wheel_state_transitions.rs"]
 mod wheel_state_transitions;
+#[path = "integration/world_seed_phrase.rs"]
+mod world_seed_phrase;
@@ -0,0 +1,172 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+use game::systems::audio_cues::{
+    cues_path, route_audio_cues_to, AudioBackend, AudioCueRouter, CueTable, CueTableError,
+};
+use game::systems::command_queue::CommandQueue;
+use game::systems::telemetry::meter_registry;
+use repro::Command;
+
+/// Records every clip it is asked to play.
+#[derive(Resource, Default)]
+struct MockAudio {
+    played: Vec<String>,
+}
+
+impl AudioBackend for MockAudio {
+    fn play(&mut self, sound: &str) {
+        self.played.push(sound.to_owned());
+    }
+}
+
+const CUES: &str = r#"
+default_cooldown_ms = 100
+
+[[cue]]
+meter = "trade_confirmed"
+sound = "audio/trade.ogg"
+
+[[cue]]
+meter = "mission_result"
+sound = "audio/mission_won.ogg"
+equals = 1
+cooldown_ms = 0
+
+[[cue]]
+meter = "mission_result"
+sound = "audio/mission_lost.ogg"
+cooldown_ms = 0
+
+[[cue]]
+meter = "danger_diff"
+sound = "audio/danger.ogg"
+min = 1
+cooldown_ms = 1000
+"#;
+
+fn table() -> CueTable {
+    CueTable::parse(CUES, meter_registry().expect("meter manifest")).expect("cue table")
+}
+
+fn ms(millis: u64) -> Duration {
+    Duration::from_millis(millis)
+}
+
+#[test]
+fn scripted_meters_trigger_cues_and_respect_cooldowns() {
+    let mut router = AudioCueRouter::new(table());
+    let mut audio = MockAudio::default();
+    let script = [
+        (0, vec![Command::meter_at(0, "trade_confirmed", 4)]),
+        // Inside the 100 ms default cooldown.
+        (50, vec![Command::meter_at(1, "trade_confirmed", 2)]),
+        (
+            120,
+            vec![
+                Command::meter_at(2, "trade_confirmed", 1),
+                Command::meter_at(2, "mission_result", 1),
+                Command::meter_at(2, "mission_result", 0),
+                // Unmapped meters and values outside the predicate stay quiet.
+                Command::meter_at(2, "danger_score", 40),
+                Command::meter_at(2, "danger_diff", -1),
+            ],
+        ),
+        (130, vec![Command::meter_at(3, "danger_diff", 1)]),
+        (900, vec![Command::meter_at(4, "danger_diff", 1)]),
+        (1_130, vec![Command::meter_at(5, "danger_diff", 1)]),
+    ];
+    for (at, commands) in &script {
+        router.route(commands, ms(*at), &mut audio);
+    }
+    assert_eq!(
+        audio.played,
+        [
+            "audio/trade.ogg",
+            "audio/trade.ogg",
+            "audio/mission_won.ogg",
+            "audio/mission_lost.ogg",
+            "audio/danger.ogg",
+            "audio/danger.ogg",
+        ]
+    );
+}
+
+#[test]
+fn router_system_reads_the_queue_without_changing_it() {
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .insert_resource(CommandQueue::authoritative())
+        .insert_resource(AudioCueRouter::new(table()))
+        .init_resource::<MockAudio>()
+        .add_systems(Update, route_audio_cues_to::<MockAudio>);
+
+    let queued = vec![
+        Command::meter_at(7, "mission_result", 0),
+        Command::meter_at(7, "danger_score", 12),
+    ];
+    app.world_mut().resource_mut::<CommandQueue>().buf = queued.clone();
+    app.update();
+    // Commands still waiting to be drained do not play twice.
+    app.update();
+    assert_eq!(app.world().resource::<CommandQueue>().buf, queued);
+    assert_eq!(
+        app.world().resource::<MockAudio>().played,
+        ["audio/mission_lost.ogg"]
+    );
+
+    // After a drain, new meters are routed from the front again.
+    let drained = app.world_mut().resource_mut::<CommandQueue>().drain();
+    assert_eq!(drained, queued);
+    app.world_mut()
+        .resource_mut::<CommandQueue>()
+        .buf
+        .push(Command::meter_at(8, "mission_result", 1));
+    app.update();
+    assert_eq!(
+        app.world().resource::<MockAudio>().played,
+        ["audio/mission_lost.ogg", "audio/mission_won.ogg"]
+    );
+}
+
+#[test]
+fn cue_files_are_parsed_strictly() {
+    let meters = meter_registry().expect("meter manifest");
+    let err = CueTable::parse(
+        "[[cue]]\nmeter = \"trade_confirmed\"\nsound = \"a.ogg\"\nvolume = 3\n",
+        meters,
+    )
+    .expect_err("unknown field");
+    assert!(matches!(err, CueTableError::Parse(_)), "{err}");
+
+    let err = CueTable::parse(
+        "[[cue]]\nmeter = \"trade_confirmd\"\nsound = \"a.ogg\"\n",
+        meters,
+    )
+    .expect_err("typo in meter");
+    assert!(
+        matches!(err, CueTableError::UnknownMeter { index: 0, .. }),
+        "{err}"
+    );
+
+    let err = CueTable::parse(
+        "[[cue]]\nmeter = \"danger_diff\"\nsound = \"a.ogg\"\nmin = 2\nmax = 1\n",
+        meters,
+    )
+    .expect_err("empty range");
+    assert!(matches!(err, CueTableError::EmptyRange { .. }), "{err}");
+
+    let err = CueTable::parse("[[cue]]\nmeter = \"danger_diff\"\nsound = \"\"\n", meters)
+        .expect_err("empty sound");
+    assert!(
+        matches!(err, CueTableError::EmptyField { field: "sound", .. }),
+        "{err}"
+    );
+}
+
+#[test]
+fn shipped_cues_load() {
+    let table = CueTable::load(&cues_path()).expect("assets/audio/cues.toml");
+    assert!(table.cue_for("trade_confirmed", 1).is_some());
+    assert!(table.cue_for("danger_diff", 0).is_none());
+}
@@ -0,0 +1,76 @@
+use std::fs;
+use std::path::Path;
+
+use clap::Parser;
+use game::app_state::AppState;
+use game::cli::CliOptions;
+use game::systems::save::{app_state_from_snapshot, backup_path, load, snapshot_from_app_state};
+use tempfile::tempdir;
+
+fn record_options(io: &Path, autosave_dir: Option<&Path>) -> CliOptions {
+    let mut args = vec![
+        "game".to_string(),
+        "--mode".to_string(),
+        "record".to_string(),
+        "--headless".to_string(),
+        "--mission-minutes".to_string(),
+        "1".to_string(),
+        "--io".to_string(),
+        io.display().to_string(),
+    ];
+    if let Some(dir) = autosave_dir {
+        args.push("--autosave-dir".to_string());
+        args.push(dir.display().to_string());
+    }
+    CliOptions::try_parse_from(args).expect("cli options")
+}
+
+#[test]
+fn completed_leg_writes_single_autosave() {
+    let dir = tempdir().expect("tempdir");
+    let autosave_dir = dir.path().join("autosave");
+    let record_path = dir.path().join("with_autosave.json");
+    game::run_with_options(record_options(&record_path, Some(&autosave_dir))).expect("record run");
+
+    let slot = autosave_dir.join("autosave.json");
+    assert!(slot.exists(), "leg completion should autosave");
+    assert!(
+        !backup_path(&slot, 0).exists(),
+        "exactly one autosave write expected"
+    );
+    let saved = load(&slot).expect("load autosave");
+    // The only changes are the finished leg's visit to its route and its
+    // entry in the mission history.
+    assert_eq!(saved.visited_links.len(), 1);
+    assert_eq!(saved.visited_links[0].count, 1);
+    assert_eq!(saved.mission_history.len(), 1);
+    let mut expected = AppState::default();
+    expected.record_visit(saved.visited_links[0].route);
+    expected.mission_history = app_state_from_snapshot(saved.clone()).mission_history;
+    assert_eq!(saved, snapshot_from_app_state(&expected));
+
+    let plain_path = dir.path().join("plain.json");
+    game::run_with_options(record_options(&plain_path, None)).expect("plain record run");
+    assert_eq!(
+        fs::read(&record_path).expect("record"),
+        fs::read(&plain_path).expect("plain record"),
+        "autosave must not perturb the command stream"
+    );
+}
+
+#[test]
+fn no_autosave_without_flag() {
+    let dir = tempdir().expect("tempdir");
+    let record_path = dir.path().join("record.json");
+    game::run_with_options(record_options(&record_path, None)).expect("record run");
+
+    let entries: Vec<_> = fs::read_dir(dir.path())
+        .expect("read dir")
+        .map(|entry| entry.expect("entry").file_name())
+        .collect();
+    assert_eq!(
+        entries.len(),
+        2,
+        "only record and hash expected: {entries:?}"
+    );
+}
@@ -0,0 +1,52 @@
+use game::bench::{bench_leg, bench_leg_with, BENCH_WORLD_SEED};
+use game::cli::{CliOptions, Mode};
+use game::systems::director::LegContext;
+use game::systems::economy::{Pp, RouteId};
+
+/// Generous enough for an unoptimised build on a loaded CI runner.
+const MIN_TICKS_PER_SEC: u64 = 100;
+
+fn context() -> LegContext {
+    LegContext::builder()
+        .world_seed(BENCH_WORLD_SEED)
+        .link_id(RouteId(11))
+        .day(3)
+        .pp(Pp(120))
+        .density_per_10k(5)
+        .cadence_per_min(3)
+        .mission_minutes(2)
+        .player_rating(50)
+        .build()
+        .expect("context")
+}
+
+#[test]
+fn report_is_populated_and_clears_the_floor() {
+    let report = bench_leg(context(), 600).expect("bench");
+    assert_eq!(report.world_seed, BENCH_WORLD_SEED);
+    assert_eq!(report.ticks, 600);
+    assert_eq!(report.warmup_ticks, 0);
+    assert!(report.wall_nanos > 0);
+    assert!(report.commands > 0);
+    assert!(report.peak_queue_len > 0);
+    assert!(report.peak_queue_len as u64 <= report.commands);
+    assert_eq!(
+        report.allocations.is_some(),
+        cfg!(feature = "count_allocations")
+    );
+    assert!(
+        report.ticks_per_sec >= MIN_TICKS_PER_SEC,
+        "{} ticks/s",
+        report.ticks_per_sec
+    );
+}
+
+#[test]
+fn same_seed_simulates_the_same_commands() {
+    let options = CliOptions::for_mode(Mode::Bench);
+    let first = bench_leg_with(&options, context(), 30, 300).expect("first run");
+    let second = bench_leg_with(&options, context(), 30, 300).expect("second run");
+    assert_eq!(first.warmup_ticks, 30);
+    assert_eq!(first.commands, second.commands);
+    assert_eq!(first.peak_queue_len, second.peak_queue_len);
+}
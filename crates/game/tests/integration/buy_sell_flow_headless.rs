@@ -5,6 +5,7 @@ use game::systems::economy::rulepack::load_rulepack;
 use game::systems::economy::{BasisBp, CommodityId, EconState, HubId, MoneyCents};
 use game::systems::trading::engine::{TradeKind, TradeTx};
 use game::systems::trading::inventory::Cargo;
+use game::systems::trading::ledger::{LedgerJournal, LedgerSource, Wallet, WalletAccess};
 use game::systems::trading::types::{CommodityCatalog, TradingConfig};
 use game::ui::hub_trade::HubTradeActions;
 use repro::CommandKind;
@@ -18,7 +19,10 @@ fn install_globals() {
     let catalog_path = asset_path("assets/trading/commodities.toml");
     let catalog = CommodityCatalog::load_from_path(catalog_path.as_path()).expect("catalog");
     CommodityCatalog::install_global(catalog);
-    TradingConfig::install_global(TradingConfig { fee_bp: 75 });
+    TradingConfig::install_global(TradingConfig {
+        fee_bp: 75,
+        ..TradingConfig::default()
+    });
 }
 
 fn load_rulepack_fixture() -> game::systems::economy::Rulepack {
@@ -39,10 +43,13 @@ fn buy_then_sell_updates_state() {
         capacity_mass_kg: 500,
         capacity_volume_l: 500,
         items: Default::default(),
+        lots: Default::default(),
     };
-    let mut wallet = MoneyCents(100_000);
+    let mut balance = Wallet::new(MoneyCents(100_000));
+    let mut journal = LedgerJournal::default();
     let mut queue = CommandQueue::default();
     queue.begin_tick(0);
+    let mut wallet = WalletAccess::new(&mut balance, &mut journal, 0);
 
     let buy = TradeTx {
         hub: HubId(1),
@@ -50,7 +57,7 @@ fn buy_then_sell_updates_state() {
         units: 1,
         kind: TradeKind::Buy,
     };
-    let buy_result = HubTradeActions::buy(&mut queue, buy, &econ, &mut cargo, &mut wallet, &rp)
+    let buy_result = HubTradeActions::buy(&mut queue, buy, &mut econ, &mut cargo, &mut wallet, &rp)
         .expect("buy result");
     assert!(buy_result.total_cents.as_i64() > 0);
     assert_eq!(cargo.units(CommodityId(1)), 1);
@@ -61,8 +68,9 @@ fn buy_then_sell_updates_state() {
         units: 1,
         kind: TradeKind::Sell,
     };
-    let sell_result = HubTradeActions::sell(&mut queue, sell, &econ, &mut cargo, &mut wallet, &rp)
-        .expect("sell result");
+    let sell_result =
+        HubTradeActions::sell(&mut queue, sell, &mut econ, &mut cargo, &mut wallet, &rp)
+            .expect("sell result");
     assert!(sell_result.total_cents.as_i64() < 0);
     assert_eq!(cargo.units(CommodityId(1)), 0);
 
@@ -76,4 +84,56 @@ fn buy_then_sell_updates_state() {
         .collect();
     assert!(meters.iter().any(|(key, _)| key == "ui_click_buy"));
     assert!(meters.iter().any(|(key, _)| key == "ui_click_sell"));
+    assert_eq!(
+        journal.net_for(LedgerSource::Trade),
+        -buy_result.total_cents.as_i64() - sell_result.total_cents.as_i64()
+    );
+    assert_eq!(balance.balance().as_i64() - 100_000, journal.net());
+}
+
+#[test]
+fn max_affordable_respects_wallet_including_fees() {
+    install_globals();
+    let rp = load_rulepack_fixture();
+    let mut econ = EconState::default();
+    let cargo = Cargo {
+        capacity_mass_kg: 100_000,
+        capacity_volume_l: 100_000,
+        items: Default::default(),
+        lots: Default::default(),
+    };
+    let unit_price =
+        game::systems::trading::pricing_vm::price_view(HubId(1), CommodityId(3), &econ, &rp)
+            .price_cents
+            .as_i64();
+    let cost = |units: i64| {
+        let subtotal = unit_price * units;
+        subtotal + subtotal * 75 / 10_000
+    };
+    let wallet = MoneyCents(cost(12) + unit_price / 2);
+
+    let units =
+        HubTradeActions::max_affordable(HubId(1), CommodityId(3), &econ, &cargo, wallet, &rp)
+            .expect("max affordable");
+    assert_eq!(units, 12);
+
+    let mut cargo = cargo;
+    let mut balance = Wallet::new(wallet);
+    let mut journal = LedgerJournal::default();
+    let mut wallet = WalletAccess::new(&mut balance, &mut journal, 0);
+    let mut queue = CommandQueue::default();
+    queue.begin_tick(0);
+    HubTradeActions::buy_max(
+        &mut queue,
+        HubId(1),
+        CommodityId(3),
+        &mut econ,
+        &mut cargo,
+        &mut wallet,
+        &rp,
+    )
+    .expect("buy max");
+    assert_eq!(cargo.units(CommodityId(3)), 12);
+    let left = wallet.balance().as_i64();
+    assert!(left >= 0 && left < cost(1));
 }
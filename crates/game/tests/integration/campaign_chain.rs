@@ -0,0 +1,89 @@
+use game::cli::{CliOptions, Mode};
+use game::record_leg;
+use game::systems::director::campaign::{next_leg_context, CampaignState};
+use game::systems::director::{DirectorState, LegContext};
+use game::systems::economy::{Pp, RouteId};
+use repro::hash_record;
+
+const LEGS: u16 = 3;
+const PP_STEP: u16 = 300;
+const TICKS: u32 = 240;
+
+fn first_leg(options: &CliOptions) -> LegContext {
+    LegContext {
+        world_seed: options.world_seed(),
+        link_id: RouteId(options.link_id()),
+        day: options.day(),
+        weather: options.weather(),
+        pp: Pp(200),
+        density_per_10k: options.density_per_10k(),
+        cadence_per_min: options.cadence_per_min(),
+        mission_minutes: options.mission_minutes(),
+        player_rating: options.player_rating(),
+        multiplayer: false,
+        prior_danger_score: None,
+        basis_overlay_bp_total: 0,
+    }
+}
+
+/// Runs `LEGS` chained legs with PP growing by `PP_STEP` each leg.
+fn run_campaign() -> Vec<(LegContext, DirectorState, String)> {
+    let mut options = CliOptions::for_mode(Mode::Record);
+    options.headless = true;
+    let mut campaign = CampaignState::default();
+    let mut base = first_leg(&options);
+    let mut legs = Vec::new();
+    for leg in 0..LEGS {
+        let context = campaign.leg_context(&base);
+        let (record, state) = record_leg(&options, context, TICKS).expect("record leg");
+        legs.push((context, state.clone(), hash_record(&record).expect("hash")));
+        assert_eq!(campaign.leg_index, u32::from(leg));
+        campaign.finish_leg(&state);
+        base.day += 1;
+        base.pp = Pp(base.pp.0 + PP_STEP);
+    }
+    legs
+}
+
+#[test]
+fn chained_legs_carry_danger_and_ramp_up() {
+    let legs = run_campaign();
+    assert_eq!(legs[0].0.prior_danger_score, None);
+    for pair in legs.windows(2) {
+        let (_, prev_state, _) = &pair[0];
+        let (context, state, _) = &pair[1];
+        assert_eq!(
+            context.prior_danger_score,
+            Some(prev_state.current_danger_score)
+        );
+        assert_eq!(
+            context.basis_overlay_bp_total,
+            prev_state.basis_overlay_bp_total
+        );
+        assert_eq!(
+            state.leg_start_danger_score,
+            prev_state.current_danger_score
+        );
+        assert!(
+            state.current_danger_score > prev_state.current_danger_score,
+            "danger should climb: {} then {}",
+            prev_state.current_danger_score,
+            state.current_danger_score
+        );
+
+        let direct = next_leg_context(prev_state, context);
+        assert_eq!(direct.prior_danger_score, context.prior_danger_score);
+        assert_eq!(
+            direct.basis_overlay_bp_total,
+            context.basis_overlay_bp_total
+        );
+    }
+}
+
+#[test]
+fn chained_legs_are_deterministic() {
+    let first: Vec<String> = run_campaign().into_iter().map(|leg| leg.2).collect();
+    let second: Vec<String> = run_campaign().into_iter().map(|leg| leg.2).collect();
+    assert_eq!(first, second);
+    assert_eq!(first.len(), usize::from(LEGS));
+}
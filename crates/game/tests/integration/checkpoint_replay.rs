@@ -0,0 +1,112 @@
+use clap::Parser;
+use game::checkpoint::{record_leg_with_checkpoint, replay_from_checkpoint, Checkpoint};
+use game::cli::CliOptions;
+use game::systems::director::{LegContext, LegStatus};
+use game::systems::economy::{Pp, RouteId, Weather};
+use repro::{canonical_json_bytes, Record};
+
+const TICKS: u32 = 300;
+
+fn record_options() -> CliOptions {
+    CliOptions::try_parse_from([
+        "game",
+        "--mode",
+        "record",
+        "--headless",
+        "--world-seed",
+        "0xC4EC",
+        "--link-id",
+        "3",
+        "--weather",
+        "fog",
+        "--mission-minutes",
+        "4",
+    ])
+    .expect("cli options")
+}
+
+fn leg_context(options: &CliOptions) -> LegContext {
+    LegContext {
+        world_seed: options.world_seed(),
+        link_id: RouteId(options.link_id()),
+        day: options.day(),
+        weather: Weather::Fog,
+        pp: Pp(options.pp()),
+        density_per_10k: options.density_per_10k(),
+        cadence_per_min: options.cadence_per_min(),
+        mission_minutes: options.mission_minutes(),
+        player_rating: options.player_rating(),
+        ..LegContext::default()
+    }
+}
+
+fn record_with_checkpoint(at_step: u32) -> (Record, Checkpoint) {
+    let options = record_options();
+    let (record, state, checkpoint) =
+        record_leg_with_checkpoint(&options, leg_context(&options), TICKS, at_step)
+            .expect("record leg");
+    assert!(matches!(state.status, LegStatus::Completed(_)));
+    (record, checkpoint)
+}
+
+#[test]
+fn mid_leg_checkpoint_replays_the_same_tail() {
+    let (record, checkpoint) = record_with_checkpoint(100);
+    assert_eq!(checkpoint.step, 100);
+    assert_eq!(checkpoint.director.leg_tick, 100);
+    let tail = &record.commands[checkpoint.command_index..];
+    assert!(tail.iter().all(|command| command.t >= 100));
+    assert!(record.commands[..checkpoint.command_index]
+        .iter()
+        .all(|command| command.t < 100));
+    assert!(
+        tail.iter().any(|command| matches!(
+            &command.kind,
+            repro::CommandKind::Meter(meter) if meter.key == "mission_result"
+        )),
+        "missions resolve after the checkpoint"
+    );
+
+    let replay =
+        replay_from_checkpoint(&record_options(), &record, &checkpoint, false).expect("replay");
+    assert!(replay.is_match(), "{:?}", replay.mismatches);
+    assert_eq!(replay.start, checkpoint.command_index);
+    assert_eq!(replay.commands, tail);
+
+    // The serialized checkpoint carries everything the tail needs.
+    let bytes = canonical_json_bytes(&checkpoint).expect("checkpoint json");
+    let parsed: Checkpoint = serde_json::from_slice(&bytes).expect("parse checkpoint");
+    let reparsed =
+        replay_from_checkpoint(&record_options(), &record, &parsed, false).expect("replay");
+    assert_eq!(reparsed, replay);
+}
+
+#[test]
+fn checkpoint_at_step_zero_replays_the_whole_leg() {
+    let (record, checkpoint) = record_with_checkpoint(0);
+    assert_eq!(checkpoint.command_index, 0);
+    assert!(!checkpoint.queued.is_empty(), "startup primes the wheel");
+    let replay =
+        replay_from_checkpoint(&record_options(), &record, &checkpoint, false).expect("replay");
+    assert!(replay.is_match(), "{:?}", replay.mismatches);
+    assert_eq!(replay.commands, record.commands);
+}
+
+#[test]
+fn tampered_checkpoint_reports_the_divergence() {
+    let (record, mut checkpoint) = record_with_checkpoint(100);
+    checkpoint.econ_intent.pending_pp_delta = 7;
+
+    let stopped =
+        replay_from_checkpoint(&record_options(), &record, &checkpoint, false).expect("replay");
+    assert_eq!(stopped.mismatches, vec![checkpoint.command_index]);
+    assert!(stopped.commands.iter().all(|command| command.t == 100));
+
+    let full =
+        replay_from_checkpoint(&record_options(), &record, &checkpoint, true).expect("replay");
+    assert_eq!(full.mismatches.first(), stopped.mismatches.first());
+    assert_eq!(
+        full.commands.len(),
+        record.commands.len() - checkpoint.command_index + 1
+    );
+}
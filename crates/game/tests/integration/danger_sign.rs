@@ -10,3 +10,33 @@ fn danger_diff_matches_sign() {
     assert_eq!(danger_diff_sign(danger_low, danger_high), -1);
     assert_eq!(danger_diff_sign(danger_low, danger_low), 0);
 }
+
+#[test]
+fn danger_score_saturates_at_extreme_inputs() {
+    let budget = SpawnBudget::new(u32::MAX, 0);
+    for rating in [0, 50, 100] {
+        assert_eq!(
+            danger_score(&budget, u32::MAX, u32::MAX, u32::MAX, rating),
+            i32::MAX
+        );
+        assert_eq!(
+            danger_score(&SpawnBudget::new(0, 0), 0, u32::MAX, 0, rating),
+            i32::MAX
+        );
+        assert_eq!(
+            danger_score(&SpawnBudget::new(0, 0), 0, 0, u32::MAX, rating),
+            i32::MAX
+        );
+    }
+
+    // 300 * 7_158_278 = 2_147_483_400 fits in i32 at a neutral rating; the
+    // old i32 sum wrapped just past it once density was added.
+    let neutral = danger_score(&SpawnBudget::new(0, 0), 0, 0, 7_158_278, 50);
+    assert_eq!(neutral, 2_147_483_400);
+    let over = danger_score(&SpawnBudget::new(0, 0), 0, 1, 7_158_278, 50);
+    assert_eq!(over, i32::MAX);
+    // A low rating scales an oversized raw sum back into range.
+    let scaled = danger_score(&SpawnBudget::new(0, 0), 0, 1, 7_158_278, 0);
+    assert_eq!(scaled, 1_717_987_040);
+    assert_eq!(danger_diff_sign(over, neutral), 1);
+}
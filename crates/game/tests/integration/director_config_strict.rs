@@ -31,3 +31,57 @@ basis_bp_fail = 0
     let result = load_director_cfg(path.to_str().expect("path"));
     assert!(result.is_err(), "unknown fields should error");
 }
+
+fn config_with_weather_weight(weight: &str) -> String {
+    format!(
+        r#"
+[spawn]
+base = 1
+alpha_pp_per_100 = 0
+growth_cap_per_leg = 1
+clamp_min = 0
+clamp_max = 10
+
+[spawn.beta_weather]
+Clear = 0
+
+[weather_types.Clear]
+bandit = 1000
+scout = {weight}
+
+[missions.alpha]
+pp_success = 0
+pp_fail = 0
+basis_bp_success = 0
+basis_bp_fail = 0
+"#
+    )
+}
+
+fn load_with_weight(weight: &str) -> anyhow::Result<game::systems::director::config::DirectorCfg> {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let path = dir.path().join("weights.toml");
+    fs::write(&path, config_with_weather_weight(weight)).expect("write config");
+    load_director_cfg(path.to_str().expect("path"))
+}
+
+#[test]
+fn director_config_accepts_integer_spawn_weights() {
+    load_with_weight("500").expect("integer weights load");
+}
+
+#[cfg(not(feature = "deterministic"))]
+#[test]
+fn director_config_still_reads_float_spawn_weights() {
+    load_with_weight("0.5").expect("legacy float weights load");
+}
+
+#[cfg(feature = "deterministic")]
+#[test]
+fn deterministic_builds_reject_float_spawn_weights() {
+    let err = load_with_weight("0.5").expect_err("float weight rejected");
+    assert!(
+        format!("{err:#}").contains("weather_types.Clear.scout"),
+        "{err:#}"
+    );
+}
@@ -0,0 +1,143 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+use bevy::time::{Fixed, Time as BevyTime};
+
+use game::scheduling;
+use game::systems::command_queue::CommandQueue;
+use game::systems::director::input::{WheelInputAction, WheelInputQueue};
+use game::systems::director::{
+    DirectorPlugin, DirectorState, LegContext, MissionRuntime, Outcome, Stance,
+};
+use game::systems::economy::{Pp, RouteId, Weather};
+use game::ui::director_overlay::{DirectorDebugOverlayPlugin, DirectorOverlayText};
+use repro::Command;
+
+fn leg_app(with_overlay: bool) -> App {
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins);
+    scheduling::configure(&mut app);
+    {
+        let mut fixed = app.world_mut().resource_mut::<BevyTime<Fixed>>();
+        *fixed = BevyTime::<Fixed>::from_hz(60.0);
+    }
+    app.init_resource::<CommandQueue>();
+    app.insert_resource(LegContext {
+        world_seed: 0xD7E7_2024_0001_0001,
+        link_id: RouteId(2),
+        day: 5,
+        weather: Weather::Fog,
+        pp: Pp(600),
+        density_per_10k: 6,
+        cadence_per_min: 4,
+        mission_minutes: 4,
+        player_rating: 50,
+        ..Default::default()
+    });
+    app.add_plugins(DirectorPlugin);
+    if with_overlay {
+        app.add_plugins(DirectorDebugOverlayPlugin {
+            refresh: Duration::ZERO,
+        });
+    }
+    app.finish();
+    app.update();
+    app
+}
+
+/// Runs `ticks` fixed steps, running `Update` after each so the overlay
+/// refreshes every tick, and returns the drained command trace. Apps without
+/// the overlay have no `Update` systems, so the schedule may be missing.
+fn run_ticks(app: &mut App, ticks: u32) -> Vec<Command> {
+    let mut trace = Vec::new();
+    for _ in 0..ticks {
+        let tick = app.world().resource::<DirectorState>().leg_tick;
+        let world = app.world_mut();
+        world.resource_mut::<CommandQueue>().begin_tick(tick);
+        world.run_schedule(FixedUpdate);
+        let _ = world.try_run_schedule(Update);
+        trace.extend(world.resource_mut::<CommandQueue>().drain());
+    }
+    trace
+}
+
+fn overlay_text(app: &mut App) -> String {
+    let world = app.world_mut();
+    let mut query = world.query_filtered::<&Text, With<DirectorOverlayText>>();
+    query.single(world).expect("overlay text").0.clone()
+}
+
+#[test]
+fn overlay_does_not_change_the_command_trace() {
+    let mut plain = leg_app(false);
+    let mut overlay = leg_app(true);
+    let plain_trace = run_ticks(&mut plain, 260);
+    let overlay_trace = run_ticks(&mut overlay, 260);
+
+    assert!(!plain_trace.is_empty());
+    assert_eq!(plain_trace, overlay_trace);
+
+    let text = overlay_text(&mut overlay);
+    assert!(text.starts_with("tick "), "{text}");
+    assert!(text.contains("rain_flag"), "{text}");
+    assert!(text.contains("pending pp"), "{text}");
+}
+
+#[test]
+fn mission_progress_is_sensible_mid_leg() {
+    let mut app = leg_app(true);
+    run_ticks(&mut app, 60);
+
+    let progress = app.world().resource::<MissionRuntime>().progress();
+    let names: Vec<_> = progress.iter().map(|(name, _)| *name).collect();
+    assert_eq!(
+        names,
+        [
+            "rain_flag",
+            "sourvault",
+            "break_chain",
+            "wayleave",
+            "anchor_audit"
+        ]
+    );
+    for (name, entry) in progress {
+        if name == "break_chain" {
+            continue;
+        }
+        assert_eq!(entry.elapsed, 60, "{name}");
+        assert!(entry.target > entry.elapsed, "{name}");
+        assert_eq!(entry.outcome, None, "{name}");
+    }
+    let (_, chain) = progress[2];
+    assert!(chain.elapsed <= chain.target);
+
+    run_ticks(&mut app, 200);
+    for (name, entry) in app.world().resource::<MissionRuntime>().progress() {
+        assert!(entry.outcome.is_some(), "{name} should resolve by tick 260");
+        assert!(entry.elapsed >= entry.target || entry.outcome == Some(Outcome::Success));
+    }
+    assert!(overlay_text(&mut app).contains("break_chain"));
+}
+
+#[test]
+fn wheel_line_follows_wheel_and_pause_events() {
+    let mut app = leg_app(true);
+    run_ticks(&mut app, 1);
+    let text = overlay_text(&mut app);
+    assert!(
+        text.ends_with("wheel Brace/A overwatch off move off slowmo off"),
+        "{text}"
+    );
+
+    app.world_mut().resource_mut::<WheelInputQueue>().extend([
+        WheelInputAction::SetStance(Stance::Vault),
+        WheelInputAction::SetOverwatch(true),
+        WheelInputAction::SetHardPause(true),
+    ]);
+    run_ticks(&mut app, 1);
+    let text = overlay_text(&mut app);
+    assert!(
+        text.ends_with("wheel Vault/A overwatch on move off slowmo off  PAUSED"),
+        "{text}"
+    );
+}
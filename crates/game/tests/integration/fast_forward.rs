@@ -0,0 +1,38 @@
+use clap::Parser;
+use game::cli::CliOptions;
+use game::runtime::{fast_forward, record_leg, LegSummary};
+use game::systems::director::LegStatus;
+
+fn headless_options(mission_minutes: &str) -> CliOptions {
+    CliOptions::try_parse_from([
+        "game",
+        "--mode",
+        "record",
+        "--headless",
+        "--mission-minutes",
+        mission_minutes,
+    ])
+    .expect("cli options")
+}
+
+#[test]
+fn fast_forward_matches_recorded_leg() {
+    for minutes in ["1", "8"] {
+        let options = headless_options(minutes);
+        let (commands, recorded) = record_leg(&options).expect("record leg");
+        let (state, summary) = fast_forward(&options).expect("fast forward");
+
+        let mut expected = LegSummary::default();
+        expected.observe(&commands);
+
+        assert_eq!(state.leg_tick, recorded.leg_tick, "minutes={minutes}");
+        assert_eq!(state.status, recorded.status, "minutes={minutes}");
+        assert_eq!(summary.spawn_count_sum, expected.spawn_count_sum);
+        assert_eq!(summary.spawns, expected.spawns);
+        assert_eq!(summary.pp_delta, expected.pp_delta);
+        assert_eq!(summary.final_danger, recorded.current_danger_score);
+    }
+
+    let (state, _) = fast_forward(&headless_options("1")).expect("fast forward");
+    assert!(matches!(state.status, LegStatus::Completed(_)));
+}
@@ -0,0 +1,97 @@
+use clap::Parser;
+use game::cli::{
+    check_fixed_dt, safe_fixed_dts, CliOptions, FixedDtError, FixedDtStatus, TOLERATED_RATES_HZ,
+};
+
+/// 1/7 s, the nearest `f64`.
+const ONE_SEVENTH: f64 = 0.142_857_142_857_142_85;
+
+fn options(dt: &str) -> CliOptions {
+    CliOptions::try_parse_from(["game", "--mode", "record", "--fixed-dt", dt]).expect("cli options")
+}
+
+#[test]
+fn thirty_hz_is_inexact_but_tolerated() {
+    let check = options("0.03333333333333333")
+        .validate_fixed_dt()
+        .expect("30 Hz is tolerated");
+    assert_eq!(check.status, FixedDtStatus::Tolerated { hz: 30 });
+    assert_eq!(check.nanos, 33_333_333);
+
+    // CI's replay step passes the period truncated to ten digits; it rounds
+    // to the same nanosecond and is tolerated in every build.
+    let truncated = options("0.0333333333")
+        .validate_fixed_dt()
+        .expect("truncated 30 Hz is tolerated");
+    assert_eq!(truncated, check);
+
+    // The default step is the same 30 Hz period.
+    let default = CliOptions::try_parse_from(["game"]).expect("cli options");
+    assert_eq!(
+        default.validate_fixed_dt().expect("default").status,
+        FixedDtStatus::Tolerated { hz: 30 }
+    );
+
+    // The step the physics tests drive is the 60 Hz period.
+    assert_eq!(
+        check_fixed_dt(f64::from_bits(0x3F91_1111_1111_1111))
+            .expect("60 Hz")
+            .status,
+        FixedDtStatus::Tolerated { hz: 60 }
+    );
+}
+
+#[test]
+fn whole_nanosecond_steps_pass() {
+    for dt in ["0.02", "0.015625", "0.004"] {
+        let check = options(dt).validate_fixed_dt().expect("exact step");
+        assert_eq!(check.status, FixedDtStatus::Exact, "{dt}");
+    }
+    assert_eq!(check_fixed_dt(0.02).expect("50 Hz").nanos, 20_000_000);
+}
+
+#[test]
+fn odd_steps_are_inexact() {
+    let check = check_fixed_dt(ONE_SEVENTH).expect("1/7 s");
+    assert_eq!(check.status, FixedDtStatus::Inexact);
+    assert_eq!(check.nanos, 142_857_143);
+
+    let result = options(&ONE_SEVENTH.to_string()).validate_fixed_dt();
+    if cfg!(feature = "deterministic") {
+        assert!(matches!(
+            result,
+            Err(FixedDtError::Inexact {
+                nanos: 142_857_143,
+                ..
+            })
+        ));
+    } else {
+        assert_eq!(result.expect("warned only"), check);
+    }
+}
+
+#[test]
+fn non_positive_steps_are_rejected() {
+    for dt in [0.0, -0.5, f64::NAN, 1e-12] {
+        assert!(
+            matches!(check_fixed_dt(dt), Err(FixedDtError::NotPositive(_))),
+            "{dt}"
+        );
+    }
+}
+
+#[test]
+fn safe_list_covers_the_tolerated_rates() {
+    let safe = safe_fixed_dts();
+    for hz in TOLERATED_RATES_HZ {
+        assert!(safe.iter().any(|(rate, _, _)| *rate == hz), "{hz} Hz");
+    }
+    for (hz, dt, check) in &safe {
+        assert_ne!(check.status, FixedDtStatus::Inexact, "{hz} Hz");
+        // Every listed value is accepted back as `--fixed-dt`.
+        let parsed = options(&dt.to_string())
+            .validate_fixed_dt()
+            .expect("listed step");
+        assert_eq!(parsed, *check, "{hz} Hz");
+    }
+}
@@ -0,0 +1,109 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use game::runtime::{
+    update_goldens, verify_golden_cases, verify_goldens, GoldenManifest, GoldenStatus,
+    GOLDEN_MANIFEST,
+};
+
+fn workspace(relative: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("../..")
+        .join(relative)
+}
+
+/// A one-case manifest in `dir` around a copy of `leg_seed_01`.
+fn single_case(dir: &Path, expected_hash: &str, extra: &str) -> PathBuf {
+    fs::copy(
+        workspace("repro/records/leg_seed_01.json"),
+        dir.join("leg.json"),
+    )
+    .expect("copy record");
+    let manifest = dir.join("manifest.toml");
+    fs::write(
+        &manifest,
+        format!(
+            "[[case]]\nname = \"leg\"\nrecord = \"leg.json\"\nexpected_hash = \"{expected_hash}\"\n{extra}"
+        ),
+    )
+    .expect("write manifest");
+    manifest
+}
+
+fn golden_hash() -> String {
+    fs::read_to_string(workspace("repro/records/leg_seed_01.hash"))
+        .expect("read hash")
+        .trim()
+        .to_owned()
+}
+
+#[test]
+fn ci_tagged_goldens_verify() {
+    let manifest = workspace(GOLDEN_MANIFEST);
+    let ci_cases = GoldenManifest::load(&manifest)
+        .expect("manifest")
+        .cases
+        .iter()
+        .filter(|case| case.ci)
+        .count();
+    assert!(ci_cases > 0);
+
+    let report = verify_golden_cases(&manifest, |case| case.ci).expect("verify");
+    assert_eq!(report.cases.len(), ci_cases);
+    for case in &report.cases {
+        assert_eq!(case.status, GoldenStatus::Pass, "{}", case.name);
+    }
+    assert!(report.passed());
+}
+
+#[test]
+fn wrong_hash_fails_with_the_actual_hash() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let manifest = single_case(dir.path(), "deadbeef", "");
+    let report = verify_goldens(&manifest).expect("verify");
+    assert!(!report.passed());
+    assert_eq!(report.cases[0].name, "leg");
+    assert_eq!(
+        report.cases[0].status,
+        GoldenStatus::Mismatch {
+            expected: "deadbeef".into()
+        }
+    );
+    assert_eq!(report.cases[0].actual_hash, golden_hash());
+
+    let manifest = single_case(dir.path(), "deadbeef", "tolerated_mismatch = true\n");
+    let report = verify_goldens(&manifest).expect("verify");
+    assert!(report.passed());
+    assert!(matches!(
+        report.cases[0].status,
+        GoldenStatus::Tolerated { .. }
+    ));
+}
+
+#[test]
+fn update_rewrites_hashes_and_keeps_record_bytes() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let manifest = single_case(dir.path(), "deadbeef", "ci = true\n");
+
+    let updated = update_goldens(&manifest).expect("update");
+    assert_eq!(updated.cases[0].expected_hash, Some(golden_hash()));
+    assert!(updated.cases[0].ci);
+    assert_eq!(GoldenManifest::load(&manifest).expect("reload"), updated);
+    assert_eq!(
+        fs::read(dir.path().join("leg.json")).expect("record"),
+        fs::read(workspace("repro/records/leg_seed_01.json")).expect("golden")
+    );
+    assert_eq!(
+        fs::read_to_string(dir.path().join("leg.hash"))
+            .expect("hash")
+            .trim(),
+        golden_hash()
+    );
+    assert!(verify_goldens(&manifest).expect("verify").passed());
+    let leftovers: Vec<_> = fs::read_dir(dir.path())
+        .expect("dir")
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.path().extension().is_some_and(|ext| ext == "tmp"))
+        .collect();
+    assert!(leftovers.is_empty());
+}
@@ -0,0 +1,48 @@
+use clap::Parser;
+use game::cli::CliOptions;
+use game::runtime::{record_leg, HeadlessRunner};
+use game::systems::director::LegStatus;
+
+fn headless_options() -> CliOptions {
+    CliOptions::try_parse_from([
+        "game",
+        "--mode",
+        "record",
+        "--headless",
+        "--mission-minutes",
+        "1",
+    ])
+    .expect("cli options")
+}
+
+#[test]
+fn split_steps_match_a_single_recorded_leg() {
+    let options = headless_options();
+    let (expected, recorded) = record_leg(&options).expect("record leg");
+
+    let mut runner = HeadlessRunner::from_options(&options).expect("runner");
+    let first = runner.step(45).expect("first step");
+    assert!(!first.completed);
+    assert_eq!(runner.ticks_run(), 45);
+    let second = runner.step(75).expect("second step");
+    assert!(second.completed);
+    assert_eq!(runner.ticks_run(), 120);
+
+    let mut commands = first.commands;
+    commands.extend(second.commands);
+    assert_eq!(commands, expected);
+
+    let (state, _) = runner.finish().expect("finish");
+    assert_eq!(state.leg_tick, recorded.leg_tick);
+    assert_eq!(state.status, recorded.status);
+    assert!(matches!(state.status, LegStatus::Completed(_)));
+}
+
+#[test]
+fn zero_budget_runs_nothing() {
+    let mut runner = HeadlessRunner::from_options(&headless_options()).expect("runner");
+    let outcome = runner.step(0).expect("empty step");
+    assert!(outcome.commands.is_empty());
+    assert!(!outcome.completed);
+    assert_eq!(runner.director().leg_tick, 0);
+}
@@ -1,6 +1,7 @@
 use std::path::PathBuf;
 
 use bevy::app::App;
+use bevy::ecs::schedule::ExecutorKind;
 use bevy::prelude::*;
 use game::app_state::AppState;
 use game::systems::command_queue::CommandQueue;
@@ -8,10 +9,15 @@ use game::systems::economy::rulepack::load_rulepack;
 use game::systems::economy::{CommodityId, HubId, MoneyCents, Rulepack};
 use game::systems::trading::engine::TradeKind;
 use game::systems::trading::inventory::Cargo;
+use game::systems::trading::ledger::{LedgerJournal, LedgerSource, Wallet};
+use game::systems::trading::pricing_vm::price_view;
 use game::systems::trading::types::{CommodityCatalog, TradingConfig};
 use game::ui::hub_trade::{
-    HubTradePlugin, HubTradeUiModel, HubTradeUiState, StepperButton, TradeButton,
+    focus_order, BulkTradeButton, ConfirmationButton, FocusRing, FocusTarget, HubTradePlugin,
+    HubTradeUiModel, HubTradeUiState, ManifestRow, ManifestSellAllButton, PendingConfirmation,
+    StepperButton, TradeButton, UndoStack, UndoTradeButton,
 };
+use game::ui::styles::COLOR_FOCUS;
 use repro::CommandKind;
 
 fn asset_path(relative: &str) -> PathBuf {
@@ -23,7 +29,10 @@ fn install_globals() {
     let catalog_path = asset_path("assets/trading/commodities.toml");
     let catalog = CommodityCatalog::load_from_path(catalog_path.as_path()).expect("catalog");
     CommodityCatalog::install_global(catalog);
-    TradingConfig::install_global(TradingConfig { fee_bp: 75 });
+    TradingConfig::install_global(TradingConfig {
+        fee_bp: 75,
+        ..TradingConfig::default()
+    });
 }
 
 fn load_rulepack_fixture() -> Rulepack {
@@ -81,7 +90,7 @@ fn stepper_buttons_update_units_and_meter_queue() {
     let rp = load_rulepack_fixture();
 
     let app_state = AppState {
-        wallet: MoneyCents(500_000),
+        wallet: Wallet::new(MoneyCents(500_000)),
         last_hub: HubId(1),
         cargo: Cargo {
             capacity_mass_kg: 1_000,
@@ -153,7 +162,7 @@ fn trade_buttons_execute_actions_and_refresh_view() {
     let rp = load_rulepack_fixture();
 
     let app_state = AppState {
-        wallet: MoneyCents(600_000),
+        wallet: Wallet::new(MoneyCents(600_000)),
         last_hub: HubId(1),
         cargo: Cargo {
             capacity_mass_kg: 1_000,
@@ -235,7 +244,7 @@ fn trade_buttons_execute_actions_and_refresh_view() {
             .get(&target_commodity)
             .copied()
             .unwrap_or(0);
-        (state.wallet, held)
+        (state.wallet.balance(), held)
     };
     assert!(
         cargo_units >= 1,
@@ -266,3 +275,603 @@ fn trade_buttons_execute_actions_and_refresh_view() {
         "remembered view should track wallet"
     );
 }
+
+fn collect_bulk_buttons(app: &mut App) -> Vec<(Entity, CommodityId, TradeKind)> {
+    let world = app.world_mut();
+    let mut query = world.query::<(Entity, &BulkTradeButton)>();
+    query
+        .iter(&*world)
+        .map(|(entity, button)| (entity, button.commodity(), button.kind()))
+        .collect()
+}
+
+fn press_bulk_button(app: &mut App, commodity: CommodityId, kind: TradeKind) {
+    let entity = collect_bulk_buttons(app)
+        .into_iter()
+        .find(|(_, com, button_kind)| *com == commodity && *button_kind == kind)
+        .map(|(entity, _, _)| entity)
+        .expect("bulk trade button");
+    app.world_mut()
+        .entity_mut(entity)
+        .insert(Interaction::Pressed);
+    warm_up_hub_trade_ui(app);
+}
+
+#[test]
+fn sell_all_empties_slot_in_one_click() {
+    install_globals();
+    let rp = load_rulepack_fixture();
+    let commodity = CommodityId(1);
+    let starting_wallet = MoneyCents(10_000);
+
+    let mut cargo = Cargo {
+        capacity_mass_kg: 1_000,
+        capacity_volume_l: 1_000,
+        ..Default::default()
+    };
+    cargo.items.insert(commodity, 17);
+    let app_state = AppState {
+        wallet: Wallet::new(starting_wallet),
+        last_hub: HubId(1),
+        cargo,
+        ..Default::default()
+    };
+    let unit_price = price_view(HubId(1), commodity, &app_state.econ, &rp).price_cents;
+
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins);
+    app.add_plugins(HubTradePlugin);
+    app.world_mut().insert_resource(CommandQueue::default());
+    app.world_mut().insert_resource(rp);
+    app.world_mut().insert_resource(app_state);
+    {
+        let mut queue = app.world_mut().resource_mut::<CommandQueue>();
+        queue.begin_tick(0);
+    }
+    warm_up_hub_trade_ui(&mut app);
+
+    press_bulk_button(&mut app, commodity, TradeKind::Sell);
+
+    let state = app.world().resource::<AppState>();
+    assert_eq!(state.cargo.units(commodity), 0, "slot should be empty");
+    let subtotal = unit_price.as_i64() * 17;
+    let fee = subtotal * 75 / 10_000;
+    assert_eq!(
+        state.wallet.balance(),
+        MoneyCents(starting_wallet.as_i64() + subtotal - fee)
+    );
+    let journal = app.world().resource::<LedgerJournal>();
+    assert_eq!(journal.net_for(LedgerSource::Trade), subtotal - fee);
+
+    let queue = app.world().resource::<CommandQueue>();
+    assert!(queue.buf.iter().any(|cmd| match &cmd.kind {
+        CommandKind::Meter(m) => m.key == "ui_click_sell_all" && m.value == 17,
+        _ => false,
+    }));
+    assert!(!queue.buf.iter().any(|cmd| match &cmd.kind {
+        CommandKind::Meter(m) => m.key == "ui_click_sell",
+        _ => false,
+    }));
+}
+
+#[test]
+fn buy_max_stops_at_wallet_and_capacity() {
+    install_globals();
+    let rp = load_rulepack_fixture();
+    let commodity = CommodityId(1);
+
+    let app_state = AppState {
+        wallet: Wallet::new(MoneyCents(1_000_000)),
+        last_hub: HubId(1),
+        cargo: Cargo {
+            capacity_mass_kg: 95,
+            capacity_volume_l: 1_000,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins);
+    app.add_plugins(HubTradePlugin);
+    app.world_mut().insert_resource(CommandQueue::default());
+    app.world_mut().insert_resource(rp);
+    app.world_mut().insert_resource(app_state);
+    {
+        let mut queue = app.world_mut().resource_mut::<CommandQueue>();
+        queue.begin_tick(0);
+    }
+    warm_up_hub_trade_ui(&mut app);
+
+    press_bulk_button(&mut app, commodity, TradeKind::Buy);
+
+    let state = app.world().resource::<AppState>();
+    // Grain weighs 10kg, so 95kg of capacity fits nine units.
+    assert_eq!(state.cargo.units(commodity), 9);
+    let queue = app.world().resource::<CommandQueue>();
+    assert!(queue.buf.iter().any(|cmd| match &cmd.kind {
+        CommandKind::Meter(m) => m.key == "ui_click_buy_max" && m.value == 9,
+        _ => false,
+    }));
+}
+
+fn manifest_rows(app: &mut App) -> Vec<(CommodityId, Vec<String>)> {
+    let world = app.world_mut();
+    let mut rows = world.query::<(&ManifestRow, &Children)>();
+    let mut texts = world.query::<&Text>();
+    let mut out: Vec<(CommodityId, Vec<String>)> = rows
+        .iter(&*world)
+        .map(|(row, children)| {
+            let labels = children
+                .iter()
+                .filter_map(|child| texts.get(&*world, child).ok())
+                .map(|text| text.0.clone())
+                .collect();
+            (row.commodity(), labels)
+        })
+        .collect();
+    out.sort_by_key(|(commodity, _)| commodity.0);
+    out
+}
+
+fn press_trade_button(app: &mut App, commodity: CommodityId, kind: TradeKind) {
+    let entity = collect_trade_buttons(app)
+        .into_iter()
+        .find(|(_, com, button_kind)| *com == commodity && *button_kind == kind)
+        .map(|(entity, _, _)| entity)
+        .expect("trade button");
+    app.world_mut()
+        .entity_mut(entity)
+        .insert(Interaction::Pressed);
+    warm_up_hub_trade_ui(app);
+}
+
+#[test]
+fn manifest_lists_cargo_and_sells_rows() {
+    install_globals();
+    let rp = load_rulepack_fixture();
+    let grain = CommodityId(1);
+    let other = CommodityId(2);
+
+    let app_state = AppState {
+        wallet: Wallet::new(MoneyCents(500_000)),
+        last_hub: HubId(1),
+        cargo: Cargo {
+            capacity_mass_kg: 1_000,
+            capacity_volume_l: 1_000,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let unit_price = price_view(HubId(1), grain, &app_state.econ, &rp)
+        .price_cents
+        .as_i64();
+    let sell_value = unit_price - unit_price * 75 / 10_000;
+
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins);
+    app.add_plugins(HubTradePlugin);
+    app.world_mut().insert_resource(CommandQueue::default());
+    app.world_mut().insert_resource(rp);
+    app.world_mut().insert_resource(app_state);
+    {
+        let mut queue = app.world_mut().resource_mut::<CommandQueue>();
+        queue.begin_tick(0);
+    }
+    warm_up_hub_trade_ui(&mut app);
+    assert!(manifest_rows(&mut app).is_empty());
+
+    press_trade_button(&mut app, grain, TradeKind::Buy);
+    press_trade_button(&mut app, other, TradeKind::Buy);
+
+    let catalog = CommodityCatalog::global();
+    let view = app
+        .world()
+        .resource::<HubTradeUiModel>()
+        .view()
+        .cloned()
+        .expect("view");
+    let mut expected_mass = 0u64;
+    let mut expected_volume = 0u64;
+    for commodity in [grain, other] {
+        let spec = catalog.get(commodity).expect("catalog entry");
+        expected_mass += u64::from(spec.mass_kg);
+        expected_volume += u64::from(spec.volume_l);
+    }
+    assert_eq!(view.cargo.mass_kg, expected_mass);
+    assert_eq!(view.cargo.volume_l, expected_volume);
+
+    let rows = manifest_rows(&mut app);
+    assert_eq!(
+        rows.iter()
+            .map(|(commodity, _)| *commodity)
+            .collect::<Vec<_>>(),
+        vec![grain, other]
+    );
+    let grain_item = view
+        .cargo
+        .items
+        .iter()
+        .find(|item| item.commodity == grain)
+        .cloned()
+        .expect("grain in view");
+    assert_eq!(grain_item.sell_value_cents, MoneyCents(sell_value));
+    let grain_spec = catalog.get(grain).expect("grain spec");
+    assert_eq!(
+        rows[0].1,
+        vec![
+            grain_spec.name.clone(),
+            "×1".to_string(),
+            format!("{}kg / {}L each", grain_spec.mass_kg, grain_spec.volume_l),
+            format!("{sell_value}¢"),
+        ]
+    );
+    let totals = {
+        let world = app.world_mut();
+        let mut texts = world.query::<&Text>();
+        texts
+            .iter(&*world)
+            .map(|text| text.0.clone())
+            .filter(|text| text.starts_with("Mass ") || text.starts_with("Volume "))
+            .collect::<Vec<_>>()
+    };
+    assert!(
+        totals.contains(&format!("Mass {expected_mass} / 1000kg")),
+        "{totals:?}"
+    );
+    assert!(
+        totals.contains(&format!("Volume {expected_volume} / 1000L")),
+        "{totals:?}"
+    );
+
+    let wallet_before = app.world().resource::<AppState>().wallet.balance();
+    let sell_all = {
+        let world = app.world_mut();
+        let mut query =
+            world.query_filtered::<(Entity, &BulkTradeButton), With<ManifestSellAllButton>>();
+        query
+            .iter(&*world)
+            .find(|(_, button)| button.commodity() == grain)
+            .map(|(entity, _)| entity)
+            .expect("manifest sell all")
+    };
+    app.world_mut()
+        .entity_mut(sell_all)
+        .insert(Interaction::Pressed);
+    warm_up_hub_trade_ui(&mut app);
+
+    let state = app.world().resource::<AppState>();
+    assert_eq!(state.cargo.units(grain), 0);
+    assert_eq!(state.cargo.units(other), 1);
+    assert_eq!(
+        state.wallet.balance(),
+        MoneyCents(wallet_before.as_i64() + sell_value)
+    );
+    let queue = app.world().resource::<CommandQueue>();
+    assert!(queue.buf.iter().any(|cmd| match &cmd.kind {
+        CommandKind::Meter(m) => m.key == "ui_click_sell_all" && m.value == 1,
+        _ => false,
+    }));
+    let rows = manifest_rows(&mut app);
+    assert_eq!(
+        rows.iter()
+            .map(|(commodity, _)| *commodity)
+            .collect::<Vec<_>>(),
+        vec![other]
+    );
+
+    // Selling from the main table refreshes the manifest too.
+    press_trade_button(&mut app, other, TradeKind::Sell);
+    assert!(manifest_rows(&mut app).is_empty());
+}
+
+fn trade_app(wallet: MoneyCents) -> App {
+    install_globals();
+    let app_state = AppState {
+        wallet: Wallet::new(wallet),
+        last_hub: HubId(1),
+        cargo: Cargo {
+            capacity_mass_kg: 1_000,
+            capacity_volume_l: 1_000,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins);
+    app.add_plugins(HubTradePlugin);
+    app.world_mut().insert_resource(CommandQueue::default());
+    app.world_mut().insert_resource(load_rulepack_fixture());
+    app.world_mut().insert_resource(app_state);
+    app.world_mut()
+        .insert_resource(ButtonInput::<KeyCode>::default());
+    app.world_mut().resource_mut::<CommandQueue>().begin_tick(0);
+    warm_up_hub_trade_ui(&mut app);
+    app
+}
+
+fn tap(app: &mut App, held: &[KeyCode], key: KeyCode) {
+    {
+        let mut input = app.world_mut().resource_mut::<ButtonInput<KeyCode>>();
+        for modifier in held {
+            input.press(*modifier);
+        }
+        input.press(key);
+    }
+    app.update();
+    {
+        let mut input = app.world_mut().resource_mut::<ButtonInput<KeyCode>>();
+        input.release_all();
+        input.clear();
+    }
+    warm_up_hub_trade_ui(app);
+}
+
+fn meters(app: &App) -> Vec<(String, i32)> {
+    app.world()
+        .resource::<CommandQueue>()
+        .buf
+        .iter()
+        .filter_map(|cmd| match &cmd.kind {
+            CommandKind::Meter(m) => Some((m.key.to_string(), m.value)),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn keyboard_buy_matches_pointer_buy() {
+    let first = {
+        let app = trade_app(MoneyCents(600_000));
+        let model = app.world().resource::<HubTradeUiModel>();
+        model.view().expect("view").commodities[0].id
+    };
+
+    let mut pointer = trade_app(MoneyCents(600_000));
+    let plus = collect_stepper_buttons(&mut pointer)
+        .into_iter()
+        .find(|(_, commodity, delta)| *commodity == first && *delta > 0)
+        .map(|(entity, _, _)| entity)
+        .expect("plus button");
+    pointer
+        .world_mut()
+        .entity_mut(plus)
+        .insert(Interaction::Pressed);
+    warm_up_hub_trade_ui(&mut pointer);
+    press_trade_button(&mut pointer, first, TradeKind::Buy);
+
+    let mut keyboard = trade_app(MoneyCents(600_000));
+    let order = {
+        let model = keyboard.world().resource::<HubTradeUiModel>();
+        focus_order(model.view().expect("view"))
+    };
+    assert_eq!(
+        order[..4],
+        [
+            FocusTarget::Stepper {
+                commodity: first,
+                delta: -1
+            },
+            FocusTarget::Stepper {
+                commodity: first,
+                delta: 1
+            },
+            FocusTarget::Trade {
+                commodity: first,
+                kind: TradeKind::Buy
+            },
+            FocusTarget::Trade {
+                commodity: first,
+                kind: TradeKind::Sell
+            },
+        ]
+    );
+
+    // Shift-Tab from nothing wraps to the last widget; Tab wraps back.
+    tap(&mut keyboard, &[KeyCode::ShiftLeft], KeyCode::Tab);
+    assert_eq!(
+        keyboard.world().resource::<FocusRing>().target(),
+        order.last().copied()
+    );
+    tap(&mut keyboard, &[], KeyCode::Tab);
+    assert_eq!(
+        keyboard.world().resource::<FocusRing>().target(),
+        Some(order[0])
+    );
+
+    tap(&mut keyboard, &[], KeyCode::ArrowUp);
+    tap(&mut keyboard, &[], KeyCode::Tab);
+    tap(&mut keyboard, &[], KeyCode::Tab);
+    let focused = keyboard
+        .world()
+        .resource::<FocusRing>()
+        .entity()
+        .expect("focused entity");
+    assert_eq!(
+        keyboard
+            .world()
+            .get::<BackgroundColor>(focused)
+            .map(|bg| bg.0),
+        Some(COLOR_FOCUS)
+    );
+    tap(&mut keyboard, &[], KeyCode::Enter);
+
+    assert_eq!(meters(&keyboard), meters(&pointer));
+    assert!(meters(&keyboard).contains(&("ui_click_buy".to_string(), 2)));
+    let wallet = |app: &App| app.world().resource::<AppState>().wallet.balance();
+    assert_eq!(wallet(&keyboard), wallet(&pointer));
+    assert!(wallet(&keyboard) < MoneyCents(600_000));
+    assert_eq!(
+        keyboard.world().resource::<AppState>().cargo.units(first),
+        2
+    );
+
+    // Focus survives the rebuild the trade triggered.
+    let focused_after = keyboard
+        .world()
+        .resource::<FocusRing>()
+        .entity()
+        .expect("focus after rebuild");
+    assert_ne!(focused_after, focused);
+    assert_eq!(
+        keyboard.world().get::<FocusTarget>(focused_after).copied(),
+        Some(order[2])
+    );
+}
+
+fn press<C: Component>(app: &mut App, matches: impl Fn(&C) -> bool) {
+    let entity = {
+        let world = app.world_mut();
+        let mut query = world.query::<(Entity, &C)>();
+        query
+            .iter(&*world)
+            .find(|(_, component)| matches(component))
+            .map(|(entity, _)| entity)
+            .expect("button")
+    };
+    app.world_mut()
+        .entity_mut(entity)
+        .insert(Interaction::Pressed);
+    warm_up_hub_trade_ui(app);
+}
+
+/// A trade app whose systems run on this thread, so a scoped trading config
+/// reaches them.
+fn gated_trade_app() -> App {
+    let mut app = trade_app(MoneyCents(600_000));
+    app.edit_schedule(Update, |schedule| {
+        schedule.set_executor_kind(ExecutorKind::SingleThreaded);
+    });
+    app
+}
+
+fn gated_config() -> TradingConfig {
+    TradingConfig {
+        fee_bp: 75,
+        confirm_over_units: Some(3),
+        ..TradingConfig::default()
+    }
+}
+
+fn set_stepper(app: &mut App, commodity: CommodityId, units: u32) {
+    while app
+        .world()
+        .resource::<HubTradeUiModel>()
+        .units_for(commodity)
+        < units
+    {
+        press::<StepperButton>(app, |button| {
+            button.commodity() == commodity && button.delta() > 0
+        });
+    }
+}
+
+#[test]
+fn confirmation_thresholds() {
+    let config = TradingConfig {
+        confirm_over_cents: Some(1_000),
+        confirm_over_units: Some(10),
+        ..TradingConfig::default()
+    };
+    assert!(!config.needs_confirmation(10, MoneyCents(1_000)));
+    assert!(config.needs_confirmation(11, MoneyCents(1)));
+    assert!(config.needs_confirmation(1, MoneyCents(1_001)));
+    assert!(config.needs_confirmation(1, MoneyCents(-1_001)));
+    assert!(!TradingConfig::default().needs_confirmation(u32::MAX, MoneyCents(i64::MAX)));
+}
+
+#[test]
+fn large_trades_wait_for_confirmation() {
+    let _config = TradingConfig::scope(gated_config());
+    let mut app = gated_trade_app();
+    let commodity = CommodityId(1);
+
+    // At the threshold the trade goes straight through.
+    set_stepper(&mut app, commodity, 3);
+    press_trade_button(&mut app, commodity, TradeKind::Buy);
+    assert!(app
+        .world()
+        .resource::<PendingConfirmation>()
+        .trade()
+        .is_none());
+    assert_eq!(app.world().resource::<AppState>().cargo.units(commodity), 3);
+
+    set_stepper(&mut app, commodity, 4);
+    let before = app.world().resource::<AppState>().clone();
+    press_trade_button(&mut app, commodity, TradeKind::Buy);
+    let pending = app
+        .world()
+        .resource::<PendingConfirmation>()
+        .trade()
+        .expect("pending trade");
+    assert_eq!(pending.units, 4);
+    assert_eq!(pending.kind, TradeKind::Buy);
+    assert!(pending.total_cents > MoneyCents::ZERO);
+    assert_eq!(app.world().resource::<AppState>().cargo, before.cargo);
+
+    // Trade buttons are inert while the modal is up.
+    press_trade_button(&mut app, commodity, TradeKind::Sell);
+    assert_eq!(app.world().resource::<AppState>().cargo, before.cargo);
+
+    press::<ConfirmationButton>(&mut app, |button| *button == ConfirmationButton::Cancel);
+    let state = app.world().resource::<AppState>();
+    assert_eq!(state.cargo, before.cargo);
+    assert_eq!(state.wallet.balance(), before.wallet.balance());
+    assert!(app
+        .world()
+        .resource::<PendingConfirmation>()
+        .trade()
+        .is_none());
+    assert!(meters(&app).contains(&("trade_cancelled".to_string(), 4)));
+    assert!(!meters(&app).contains(&("ui_click_buy".to_string(), 4)));
+
+    set_stepper(&mut app, commodity, 4);
+    press_trade_button(&mut app, commodity, TradeKind::Buy);
+    press::<ConfirmationButton>(&mut app, |button| *button == ConfirmationButton::Confirm);
+    let state = app.world().resource::<AppState>();
+    assert_eq!(state.cargo.units(commodity), 7);
+    assert_eq!(
+        state.wallet.balance(),
+        MoneyCents(before.wallet.balance().as_i64() - pending.total_cents.as_i64())
+    );
+    assert!(meters(&app).contains(&("trade_confirmed".to_string(), 4)));
+    assert!(meters(&app).contains(&("ui_click_buy".to_string(), 4)));
+}
+
+#[test]
+fn undo_restores_the_last_trade_exactly() {
+    let _config = TradingConfig::scope(gated_config());
+    let mut app = gated_trade_app();
+    let commodity = CommodityId(1);
+
+    set_stepper(&mut app, commodity, 3);
+    press_trade_button(&mut app, commodity, TradeKind::Buy);
+    let before_sale = app.world().resource::<AppState>().clone();
+    let journal_before = app
+        .world()
+        .resource::<LedgerJournal>()
+        .net_for(LedgerSource::Trade);
+
+    set_stepper(&mut app, commodity, 2);
+    press_trade_button(&mut app, commodity, TradeKind::Sell);
+    assert_eq!(app.world().resource::<AppState>().cargo.units(commodity), 1);
+    assert!(app.world().resource::<UndoStack>().entry().is_some());
+
+    press::<UndoTradeButton>(&mut app, |_| true);
+    let state = app.world().resource::<AppState>();
+    assert_eq!(state.cargo, before_sale.cargo);
+    assert_eq!(state.wallet.balance(), before_sale.wallet.balance());
+    assert_eq!(
+        app.world()
+            .resource::<LedgerJournal>()
+            .net_for(LedgerSource::Trade),
+        journal_before
+    );
+    assert!(meters(&app).contains(&("trade_undone".to_string(), 2)));
+    assert!(app.world().resource::<UndoStack>().entry().is_none());
+
+    // Depth one: the buy before the sale stays done.
+    press::<UndoTradeButton>(&mut app, |_| true);
+    assert_eq!(app.world().resource::<AppState>().cargo.units(commodity), 3);
+}
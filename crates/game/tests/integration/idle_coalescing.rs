@@ -0,0 +1,66 @@
+use std::fs;
+use std::path::Path;
+
+use clap::Parser;
+use game::cli::CliOptions;
+use game::runtime::{leg_idle_spans, record_leg};
+use repro::{from_canonical_json_bytes, hash_record, Record};
+use tempfile::tempdir;
+
+fn record_args(io: &Path, coalesce: Option<&str>) -> CliOptions {
+    let mut args = vec![
+        "game".to_string(),
+        "--mode".to_string(),
+        "record".to_string(),
+        "--headless".to_string(),
+        "--io".to_string(),
+        io.display().to_string(),
+    ];
+    if let Some(min_ticks) = coalesce {
+        args.push("--coalesce-idle".to_string());
+        args.push(min_ticks.to_string());
+    }
+    CliOptions::try_parse_from(args).expect("cli options")
+}
+
+fn read_record(path: &Path) -> Record {
+    from_canonical_json_bytes(&fs::read(path).expect("read record")).expect("parse record")
+}
+
+#[test]
+fn idle_stretch_coalesces_and_hash_is_unchanged() {
+    let dir = tempdir().expect("tempdir");
+    let plain_path = dir.path().join("plain.json");
+    let coalesced_path = dir.path().join("coalesced.json");
+    game::run_with_options(record_args(&plain_path, None)).expect("plain record");
+    game::run_with_options(record_args(&coalesced_path, Some("30"))).expect("coalesced record");
+
+    let plain = read_record(&plain_path);
+    let coalesced = read_record(&coalesced_path);
+    assert!(plain.meta.idle_spans.is_empty());
+    assert!(
+        coalesced
+            .meta
+            .idle_spans
+            .iter()
+            .any(|span| span.until - span.from >= 60),
+        "{:?}",
+        coalesced.meta.idle_spans
+    );
+    assert!(coalesced.idle_spans_consistent());
+    assert_eq!(coalesced.commands, plain.commands);
+    assert_eq!(
+        hash_record(&coalesced).expect("hash"),
+        hash_record(&plain).expect("hash")
+    );
+    assert_eq!(
+        fs::read_to_string(coalesced_path.with_extension("hash")).expect("hash file"),
+        fs::read_to_string(plain_path.with_extension("hash")).expect("hash file")
+    );
+
+    let (commands, state) = record_leg(&record_args(&plain_path, None)).expect("record leg");
+    assert_eq!(
+        leg_idle_spans(&commands, &state, 30),
+        coalesced.meta.idle_spans
+    );
+}
@@ -0,0 +1,112 @@
+use std::fs;
+use std::path::Path;
+
+use clap::Parser;
+use game::cli::{CliOptions, Mode};
+use game::runtime::{record_leg, StopCondition, DEFAULT_LEG_TICKS, DEFAULT_MAX_LEG_TICKS};
+use game::systems::director::LegStatus;
+use repro::strict::ParseMode;
+use repro::{canonical_json_bytes, Record};
+use tempfile::tempdir;
+
+fn options(args: &[&str]) -> CliOptions {
+    let mut argv = vec!["game", "--mode", "record", "--headless"];
+    argv.extend_from_slice(args);
+    CliOptions::try_parse_from(argv).expect("cli options")
+}
+
+fn replay(path: &Path) -> anyhow::Result<()> {
+    let mut options = CliOptions::for_mode(Mode::Replay);
+    options.headless = true;
+    options.continue_after_mismatch = false;
+    options.io = Some(path.to_str().expect("record path").to_string());
+    game::run_with_options(options)
+}
+
+#[test]
+fn stop_condition_follows_the_cli() {
+    assert_eq!(
+        options(&[]).stop_condition(),
+        StopCondition::Ticks(DEFAULT_LEG_TICKS)
+    );
+    assert_eq!(
+        options(&["--ticks", "300"]).stop_condition(),
+        StopCondition::Ticks(300)
+    );
+    assert_eq!(
+        options(&["--run-to-completion"]).stop_condition(),
+        StopCondition::Completion {
+            max_ticks: DEFAULT_MAX_LEG_TICKS
+        }
+    );
+    assert_eq!(
+        options(&["--run-to-completion", "--ticks", "90"]).stop_condition(),
+        StopCondition::Completion { max_ticks: 90 }
+    );
+}
+
+#[test]
+fn run_to_completion_record_replays_through_the_lib_path() {
+    let dir = tempdir().expect("tempdir");
+    let path = dir.path().join("leg.json");
+    let record_options = options(&[
+        "--mission-minutes",
+        "3",
+        "--run-to-completion",
+        "--io",
+        path.to_str().expect("record path"),
+    ]);
+    game::run_with_options(record_options.clone()).expect("record run");
+    let record = Record::from_json_bytes(&fs::read(&path).expect("read record"), ParseMode::Strict)
+        .expect("parse record");
+
+    // Three minutes run well past the old fixed 120 steps.
+    let last_tick = record.commands.last().expect("commands").t;
+    assert!(
+        last_tick > DEFAULT_LEG_TICKS,
+        "last command at tick {last_tick}"
+    );
+    let (_, state) = record_leg(&record_options).expect("record leg");
+    assert!(matches!(state.status, LegStatus::Completed(_)));
+    assert!(state.leg_tick >= 180, "leg tick {}", state.leg_tick);
+
+    replay(&path).expect("replay matches");
+
+    // Replay runs through the last recorded tick, so dropping one of its
+    // commands is caught.
+    let mut truncated = record;
+    truncated.commands.pop();
+    let truncated_path = dir.path().join("truncated.json");
+    fs::write(
+        &truncated_path,
+        canonical_json_bytes(&truncated).expect("truncated bytes"),
+    )
+    .expect("write truncated");
+    let err = replay(&truncated_path).expect_err("length mismatch");
+    assert!(err.to_string().contains("length mismatch"), "{err}");
+}
+
+#[test]
+fn early_exit_never_exceeds_the_cap() {
+    // An eight-minute leg cannot complete within 50 steps.
+    let capped = options(&["--run-to-completion", "--ticks", "50"]);
+    let (commands, state) = record_leg(&capped).expect("capped leg");
+    assert_eq!(state.status, LegStatus::Running);
+    assert_eq!(state.leg_tick, 50);
+    assert!(commands.iter().all(|command| command.t < 50));
+
+    // A one-minute leg stops as soon as it completes, well under the cap.
+    let short = options(&["--mission-minutes", "1", "--run-to-completion"]);
+    let (commands, state) = record_leg(&short).expect("short leg");
+    assert!(matches!(state.status, LegStatus::Completed(_)));
+    assert!(state.leg_tick <= 61, "leg tick {}", state.leg_tick);
+    let last = commands.last().expect("commands").t;
+    assert!(last <= state.leg_tick);
+
+    // The same leg over a fixed count keeps stepping after completion
+    // without emitting anything new.
+    let fixed = options(&["--mission-minutes", "1", "--ticks", "200"]);
+    let (fixed_commands, fixed_state) = record_leg(&fixed).expect("fixed leg");
+    assert_eq!(fixed_commands, commands);
+    assert_eq!(fixed_state.leg_tick, state.leg_tick);
+}
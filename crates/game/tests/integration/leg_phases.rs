@@ -0,0 +1,132 @@
+use std::path::Path;
+
+use bevy::prelude::*;
+use bevy::time::{Fixed, Time as BevyTime};
+
+use game::scheduling;
+use game::systems::command_queue::CommandQueue;
+use game::systems::director::config::load_director_cfg;
+use game::systems::director::{
+    DirectorConfigResource, DirectorPlugin, DirectorState, LegClock, LegContext, Phase, SpawnMemory,
+};
+use game::systems::economy::{Pp, RouteId, Weather};
+use repro::CommandKind;
+
+const TICKS: u32 = 300;
+
+struct LegTrace {
+    phases: Vec<Phase>,
+    budgets: Vec<u32>,
+    meters: Vec<(u32, String, i32)>,
+}
+
+fn run_leg(night_aggression: bool) -> LegTrace {
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins);
+    scheduling::configure(&mut app);
+    {
+        let mut fixed = app.world_mut().resource_mut::<BevyTime<Fixed>>();
+        *fixed = BevyTime::<Fixed>::from_hz(60.0);
+    }
+    app.init_resource::<CommandQueue>();
+    app.insert_resource(LegContext {
+        world_seed: 0xD7E7_2024_0001_0001,
+        link_id: RouteId(3),
+        day: 4,
+        weather: Weather::Clear,
+        pp: Pp(400),
+        density_per_10k: 5,
+        cadence_per_min: 3,
+        mission_minutes: 5,
+        player_rating: 50,
+        ..Default::default()
+    });
+    app.add_plugins(DirectorPlugin);
+
+    let cfg_path = Path::new(env!("CARGO_MANIFEST_DIR")).join("../../assets/director/m2.toml");
+    let mut cfg = load_director_cfg(cfg_path.to_str().expect("cfg path")).expect("director cfg");
+    cfg.phases.minutes_per_phase = 1;
+    cfg.phases.night_aggression = night_aggression;
+    cfg.phases.night_enemy_bonus_pct = 50;
+    app.insert_resource(DirectorConfigResource(cfg));
+    app.finish();
+    app.update();
+
+    let mut trace = LegTrace {
+        phases: Vec::new(),
+        budgets: Vec::new(),
+        meters: Vec::new(),
+    };
+    for _ in 0..TICKS {
+        let tick = app.world().resource::<DirectorState>().leg_tick;
+        let world = app.world_mut();
+        world.resource_mut::<CommandQueue>().begin_tick(tick);
+        world.run_schedule(FixedUpdate);
+        for command in world.resource_mut::<CommandQueue>().drain() {
+            if let CommandKind::Meter(meter) = command.kind {
+                trace
+                    .meters
+                    .push((tick, meter.key.to_string(), meter.value));
+            }
+        }
+        trace.phases.push(world.resource::<LegClock>().phase);
+        let budget = world
+            .resource::<SpawnMemory>()
+            .last_budget
+            .expect("budget computed");
+        trace.budgets.push(budget.enemies);
+    }
+    trace
+}
+
+#[test]
+fn phases_change_on_exact_ticks_with_meters() {
+    let trace = run_leg(false);
+    for (tick, phase) in trace.phases.iter().enumerate() {
+        let expected = match tick / 60 % 4 {
+            0 => Phase::Dawn,
+            1 => Phase::Day,
+            2 => Phase::Dusk,
+            _ => Phase::Night,
+        };
+        assert_eq!(*phase, expected, "tick {tick}");
+    }
+
+    let changes: Vec<(u32, i32)> = trace
+        .meters
+        .iter()
+        .filter(|(_, key, _)| key == "phase_changed")
+        .map(|(tick, _, value)| (*tick, *value))
+        .collect();
+    assert_eq!(changes, vec![(60, 1), (120, 2), (180, 3), (240, 0)]);
+
+    let los: Vec<i32> = trace
+        .meters
+        .iter()
+        .filter(|(_, key, _)| key == "los_m")
+        .map(|(_, _, value)| *value)
+        .collect();
+    // Clear weather is 60 m, scaled by the m2.toml phase percentages.
+    assert_eq!(los, vec![60, 42, 24, 48]);
+}
+
+#[test]
+fn night_bonus_only_raises_budgets_at_night() {
+    let calm = run_leg(false);
+    let aggressive = run_leg(true);
+    assert_eq!(calm.phases, aggressive.phases);
+
+    let mut night_ticks = 0;
+    for (tick, phase) in calm.phases.iter().enumerate() {
+        if *phase == Phase::Night {
+            night_ticks += 1;
+            assert!(
+                aggressive.budgets[tick] > calm.budgets[tick],
+                "tick {tick} should carry the night bonus"
+            );
+        } else {
+            assert_eq!(aggressive.budgets[tick], calm.budgets[tick], "tick {tick}");
+        }
+    }
+    assert_eq!(night_ticks, 60);
+}
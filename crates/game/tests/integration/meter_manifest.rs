@@ -0,0 +1,70 @@
+use std::fs;
+use std::path::Path;
+
+use clap::Parser;
+use game::cli::CliOptions;
+use game::runtime::record_leg;
+use game::systems::telemetry::{aggregate_meters, meter_registry};
+
+/// Meter keys passed as string literals to `CommandQueue::meter` or
+/// `meter_units` anywhere under `dir`.
+fn emitted_keys(dir: &Path, keys: &mut Vec<(String, String)>) {
+    for entry in fs::read_dir(dir).expect("read src dir") {
+        let path = entry.expect("dir entry").path();
+        if path.is_dir() {
+            emitted_keys(&path, keys);
+            continue;
+        }
+        if path.extension().is_none_or(|ext| ext != "rs") {
+            continue;
+        }
+        let source = fs::read_to_string(&path).expect("read source");
+        for call in ["meter(", "meter_units("] {
+            for (at, _) in source.match_indices(call) {
+                let rest = source[at + call.len()..].trim_start();
+                let Some(literal) = rest.strip_prefix('"') else {
+                    continue;
+                };
+                let key = &literal[..literal.find('"').expect("closing quote")];
+                keys.push((key.to_string(), path.display().to_string()));
+            }
+        }
+    }
+}
+
+#[test]
+fn every_emitted_meter_key_is_in_the_manifest() {
+    let registry = meter_registry().expect("meter manifest");
+    let mut keys = Vec::new();
+    emitted_keys(
+        &Path::new(env!("CARGO_MANIFEST_DIR")).join("src"),
+        &mut keys,
+    );
+    assert!(keys.len() > 20, "scan found only {} keys", keys.len());
+    let missing: Vec<_> = keys
+        .iter()
+        .filter(|(key, _)| registry.policy(key).is_none())
+        .collect();
+    assert!(
+        missing.is_empty(),
+        "keys missing from manifest: {missing:?}"
+    );
+}
+
+#[test]
+fn recorded_leg_passes_validation_and_aggregates() {
+    let options = CliOptions::try_parse_from(["game", "--mode", "record", "--headless"])
+        .expect("cli options");
+    let (commands, state) = record_leg(&options).expect("record leg");
+    let registry = meter_registry().expect("meter manifest");
+    for tick in commands.chunk_by(|a, b| a.t == b.t) {
+        registry.validate_tick(tick).expect("valid tick");
+    }
+
+    let totals = aggregate_meters(&commands);
+    assert_eq!(
+        totals.get("danger_score").copied(),
+        Some(i64::from(state.current_danger_score))
+    );
+    assert!(totals.get("spawn_count").copied().unwrap_or_default() > 0);
+}
@@ -9,7 +9,7 @@ use tempfile::tempdir;
 fn migrate_then_persist_is_idempotent() {
     let raw_v1 = include_str!("../goldens/save_v1_roundtrip.json");
     let value: Value = serde_json::from_str(raw_v1).expect("parse v1 value");
-    let migrated = migrate_to_latest(value).expect("migrate to v1.1");
+    let migrated = migrate_to_latest(value).expect("migrate to latest");
 
     let app_state = app_state_from_snapshot(migrated.clone());
     let dir = tempdir().expect("tempdir");
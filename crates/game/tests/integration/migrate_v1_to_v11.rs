@@ -1,6 +1,9 @@
 use game::systems::economy::MoneyCents;
 use game::systems::migrations::migrate_to_latest;
-use game::systems::save::{v1_1::migrate_v1_to_v11, CargoSave, SaveV1};
+use game::systems::save::{
+    v1_1::migrate_v1_to_v11, CargoSave, SaveV1, SaveV12, SaveV13, SaveV14, SaveV15, SaveV16,
+    SaveV17, SaveV18,
+};
 use serde_json::Value;
 
 #[test]
@@ -27,9 +30,16 @@ fn migration_preserves_econ_fields_and_sets_defaults() {
     assert_eq!(migrated.last_hub, Default::default());
     assert_eq!(migrated.cargo, CargoSave::default());
     assert_eq!(migrated.wallet_cents, MoneyCents::ZERO);
+    assert!(migrated.price_history.is_empty());
+    assert!(migrated.route_closures.is_empty());
 
     let manual = migrate_v1_to_v11(original.clone());
-    assert_eq!(migrated, manual);
+    assert_eq!(
+        migrated,
+        SaveV18::from(SaveV17::from(SaveV16::from(SaveV15::from(SaveV14::from(
+            SaveV13::from(SaveV12::from(manual.clone()))
+        )))))
+    );
 
     // Ensure econ bytes stable by comparing serialized slices
     let original_econ = serde_json::to_string_pretty(&original).expect("serialize v1");
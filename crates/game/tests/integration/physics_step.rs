@@ -7,7 +7,7 @@ use game::scheduling;
 use game::systems::command_queue::CommandQueue;
 use game::systems::director::{
     DirectorPlugin, DirectorState, LegContext, LegStatus, Outcome, Physics, PhysicsBackend,
-    SubstepCount, WheelState,
+    SlowmoAccumulator, SubstepCount, WheelState,
 };
 use game::systems::economy::{Pp, RouteId, Weather};
 use repro::Command;
@@ -193,6 +193,60 @@ fn physics_step_slowmo_preserves_fixed_cadence() {
     assert_eq!(slowmo_fixed, baseline_fixed);
 }
 
+#[test]
+fn physics_step_slowmo_advances_closed_form_time() {
+    let mut app = build_director_app();
+    app.world_mut().resource_mut::<LegContext>().mission_minutes = 30;
+    step_once(&mut app);
+    let base = app.world().resource::<Time<Fixed>>().timestep();
+    let physics_before = app.world().resource::<Time<Physics>>().elapsed();
+
+    app.world_mut()
+        .resource_scope(|world, mut queue: Mut<CommandQueue>| {
+            world
+                .resource_mut::<WheelState>()
+                .set_slowmo(&mut queue, true);
+        });
+    for _ in 0..1000 {
+        step_once(&mut app);
+    }
+
+    let expected_ns = base.as_nanos() * 1000 * 4 / 5;
+    let slowmo = *app.world().resource::<SlowmoAccumulator>();
+    assert_eq!(u128::from(slowmo.elapsed_ns), expected_ns);
+    let physics_after = app.world().resource::<Time<Physics>>().elapsed();
+    #[cfg(not(feature = "avian_physics"))]
+    assert_eq!(
+        (physics_after - physics_before).as_nanos(),
+        base.as_nanos() * (expected_ns / base.as_nanos())
+    );
+    #[cfg(feature = "avian_physics")]
+    let _ = (physics_before, physics_after);
+
+    // Ending the leg meters the slow-mo total, including this last tick.
+    app.world_mut().resource_mut::<LegContext>().mission_minutes = 1;
+    let commands = step_once_collect(&mut app);
+    let elapsed_ns = app.world().resource::<SlowmoAccumulator>().elapsed_ns;
+    assert!(matches!(
+        app.world().resource::<DirectorState>().status,
+        LegStatus::Completed(_)
+    ));
+    let meter = |key: &str| {
+        commands.iter().find_map(|command| match &command.kind {
+            repro::CommandKind::Meter(meter) if meter.key == key => Some(meter.value),
+            _ => None,
+        })
+    };
+    assert_eq!(
+        meter("slowmo_elapsed_s"),
+        Some((elapsed_ns / 1_000_000_000) as i32)
+    );
+    assert_eq!(
+        meter("slowmo_elapsed_subsec_ns"),
+        Some((elapsed_ns % 1_000_000_000) as i32)
+    );
+}
+
 #[cfg(feature = "deterministic")]
 #[test]
 fn physics_step_deterministic_under_feature() {
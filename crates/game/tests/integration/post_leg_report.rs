@@ -0,0 +1,200 @@
+use std::path::PathBuf;
+
+use bevy::app::App;
+use bevy::prelude::*;
+use game::app_state::AppState;
+use game::systems::director::{DirectorState, LegStatus, Outcome};
+use game::systems::economy::rulepack::load_rulepack;
+use game::systems::economy::{
+    step_economy_day, BasisBp, CommodityId, EconStepScope, EconomyDay, HubId, MarketEvent,
+    MarketEvents,
+};
+use game::systems::save::{AutosavePlugin, AutosaveTracker, AutosaveTrigger};
+use game::systems::trading::catalog::CatalogHandle;
+use game::systems::trading::types::CommodityCatalog;
+use game::ui::post_leg_report::{PostLegContinueButton, PostLegReportPlugin, PostLegReportState};
+use tempfile::tempdir;
+
+fn asset_path(relative: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("..")
+        .join("..")
+        .join(relative)
+}
+
+fn press_continue(app: &mut App) {
+    let button = {
+        let world = app.world_mut();
+        let mut query = world.query_filtered::<Entity, With<PostLegContinueButton>>();
+        query.iter(&*world).next().expect("continue button")
+    };
+    app.world_mut()
+        .entity_mut(button)
+        .insert(Interaction::Pressed);
+    app.update();
+}
+
+#[test]
+fn continue_fires_exactly_one_day_rollover() {
+    let dir = tempdir().expect("tempdir");
+    let rulepack = load_rulepack(
+        asset_path("assets/rulepacks/day_001.toml")
+            .to_str()
+            .expect("utf-8 path"),
+    )
+    .expect("rulepack");
+
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins);
+    app.add_plugins((PostLegReportPlugin, AutosavePlugin::new(dir.path())));
+    app.insert_resource(rulepack);
+    app.insert_resource(AppState {
+        last_hub: HubId(1),
+        ..Default::default()
+    });
+    app.insert_resource(DirectorState {
+        status: LegStatus::Running,
+        ..Default::default()
+    });
+    app.update();
+    assert!(app
+        .world()
+        .resource::<PostLegReportState>()
+        .report()
+        .is_none());
+
+    app.world_mut().resource_mut::<DirectorState>().status = LegStatus::Completed(Outcome::Success);
+    app.update();
+    let report = app
+        .world()
+        .resource::<PostLegReportState>()
+        .report()
+        .cloned()
+        .expect("report shown on completion");
+    assert_eq!(report.outcome, Some(Outcome::Success));
+    assert_eq!(
+        app.world().resource::<AutosaveTracker>().last_trigger,
+        Some(AutosaveTrigger::LegCompleted)
+    );
+
+    press_continue(&mut app);
+    for _ in 0..3 {
+        app.update();
+    }
+    press_continue(&mut app);
+
+    let state = app.world().resource::<PostLegReportState>();
+    assert!(state.report().is_none(), "continue dismisses the report");
+    assert_eq!(state.rollovers(), 1);
+    assert_eq!(app.world().resource::<AppState>().econ.day, EconomyDay(1));
+    let tracker = app.world().resource::<AutosaveTracker>();
+    assert_eq!(tracker.last_trigger, Some(AutosaveTrigger::DayRollover));
+    assert_eq!(tracker.writes, 2);
+}
+
+#[test]
+fn continue_applies_market_events_for_the_stepped_day() {
+    let rulepack = load_rulepack(
+        asset_path("assets/rulepacks/day_001.toml")
+            .to_str()
+            .expect("utf-8 path"),
+    )
+    .expect("rulepack");
+    let mut start = AppState {
+        last_hub: HubId(1),
+        ..Default::default()
+    };
+    start.econ.di_bp.insert(CommodityId(1), BasisBp(0));
+    let shock = MarketEvent {
+        day: EconomyDay(0),
+        hub: Some(HubId(1)),
+        commodity: CommodityId(1),
+        di_overlay_bp: 0,
+        basis_overlay_bp: 400,
+    };
+
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins);
+    app.add_plugins(PostLegReportPlugin);
+    app.insert_resource(rulepack.clone());
+    app.insert_resource(MarketEvents(vec![shock]));
+    app.insert_resource(start.clone());
+    app.insert_resource(DirectorState {
+        status: LegStatus::Completed(Outcome::Success),
+        ..Default::default()
+    });
+    app.update();
+    press_continue(&mut app);
+
+    let mut plain = start.econ;
+    step_economy_day(
+        &rulepack,
+        start.world_seed,
+        start.econ_version,
+        HubId(1),
+        &mut plain,
+        EconStepScope::GlobalAndHub,
+    );
+    let key = (HubId(1), CommodityId(1));
+    let shocked = app.world().resource::<AppState>().econ.basis_bp[&key];
+    let unshocked = plain.basis_bp[&key];
+    assert!(
+        shocked.0 > unshocked.0,
+        "event basis {} vs plain {}",
+        shocked.0,
+        unshocked.0
+    );
+}
+
+#[test]
+fn continue_spoils_stale_cargo_into_rot() {
+    let rulepack = load_rulepack(
+        asset_path("assets/rulepacks/day_001.toml")
+            .to_str()
+            .expect("utf-8 path"),
+    )
+    .expect("rulepack");
+    let catalog = CommodityCatalog::load_from_path(&asset_path("assets/trading/commodities.toml"))
+        .expect("commodities");
+    let (grain, ore) = (CommodityId(1), CommodityId(2));
+    let mut start = AppState {
+        last_hub: HubId(1),
+        ..Default::default()
+    };
+    start.econ.day = EconomyDay(14);
+    start.cargo.load(grain, 10, EconomyDay(0)).expect("load");
+    start.cargo.load(grain, 3, EconomyDay(10)).expect("load");
+    start.cargo.load(ore, 5, EconomyDay(0)).expect("load");
+
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins);
+    app.add_plugins(PostLegReportPlugin);
+    app.insert_resource(rulepack.clone());
+    app.insert_resource(CatalogHandle::new(catalog));
+    app.insert_resource(start.clone());
+    app.insert_resource(DirectorState {
+        status: LegStatus::Completed(Outcome::Success),
+        ..Default::default()
+    });
+    app.update();
+    press_continue(&mut app);
+
+    let mut plain = start.econ;
+    step_economy_day(
+        &rulepack,
+        start.world_seed,
+        start.econ_version,
+        HubId(1),
+        &mut plain,
+        EconStepScope::GlobalAndHub,
+    );
+    let state = app.world().resource::<AppState>();
+    assert_eq!(state.econ.day, EconomyDay(15));
+    assert_eq!(
+        state.cargo.units(grain),
+        3,
+        "day-0 grain is past its shelf life"
+    );
+    assert_eq!(state.cargo.units(ore), 5, "ore never spoils");
+    assert_eq!(state.econ.rot_u16, plain.rot_u16 + 10);
+}
@@ -0,0 +1,72 @@
+use std::path::PathBuf;
+
+use game::systems::economy::rulepack::load_rulepack;
+use game::systems::economy::{
+    step_economy_day, BasisBp, CommodityId, EconState, EconStepScope, HubId, MoneyCents, Pp,
+};
+use game::systems::trading::catalog::{CatalogHandle, CatalogScope};
+use game::systems::trading::inventory::Cargo;
+use game::systems::trading::types::{CommodityCatalog, TradingConfig};
+use game::ui::hub_trade::{build_view, build_view_cached, PriceViewCache};
+
+fn asset_path(relative: &str) -> PathBuf {
+    let manifest = env!("CARGO_MANIFEST_DIR");
+    PathBuf::from(manifest).join("..").join("..").join(relative)
+}
+
+/// Installs a thread-scoped catalog so parallel tests replacing the global
+/// one cannot invalidate the cache mid-test.
+fn scoped_catalog() -> CatalogScope {
+    let catalog_path = asset_path("assets/trading/commodities.toml");
+    let catalog = CommodityCatalog::load_from_path(catalog_path.as_path()).expect("catalog");
+    TradingConfig::install_global(TradingConfig {
+        fee_bp: 75,
+        ..TradingConfig::default()
+    });
+    CatalogHandle::new(catalog).scope()
+}
+
+#[test]
+fn same_day_rebuilds_hit_the_cache_and_a_day_step_invalidates_it() {
+    let _catalog = scoped_catalog();
+    let path = asset_path("assets/rulepacks/day_001.toml");
+    let rp = load_rulepack(path.to_str().expect("utf-8 path")).expect("rulepack");
+    let mut econ = EconState {
+        pp: Pp(420),
+        ..EconState::default()
+    };
+    econ.di_bp.insert(CommodityId(1), BasisBp(120));
+    econ.basis_bp
+        .insert((HubId(1), CommodityId(1)), BasisBp(45));
+    let mut cargo = Cargo {
+        capacity_mass_kg: 2_000,
+        capacity_volume_l: 1_500,
+        ..Cargo::default()
+    };
+    let mut cache = PriceViewCache::default();
+
+    let first = build_view_cached(HubId(1), &econ, &rp, &cargo, MoneyCents(5_000), &mut cache);
+    for wallet in 0..1_000 {
+        let view = build_view_cached(HubId(1), &econ, &rp, &cargo, MoneyCents(wallet), &mut cache);
+        assert_eq!(view.commodities, first.commodities);
+        assert_eq!(view.wallet_cents, MoneyCents(wallet));
+    }
+    assert_eq!(cache.misses(), 1);
+    assert_eq!(cache.hits(), 1_000);
+
+    cargo.items.insert(CommodityId(1), 2);
+    let with_cargo = build_view_cached(HubId(1), &econ, &rp, &cargo, MoneyCents(5_000), &mut cache);
+    assert_eq!(with_cargo.cargo.items.len(), 1, "cargo is never cached");
+    assert_eq!(cache.misses(), 1);
+
+    let revision = econ.revision;
+    step_economy_day(&rp, 7, 1, HubId(1), &mut econ, EconStepScope::GlobalAndHub);
+    assert_ne!(econ.revision, revision);
+    let stepped = build_view_cached(HubId(1), &econ, &rp, &cargo, MoneyCents(5_000), &mut cache);
+    assert_eq!(cache.misses(), 2);
+    assert_eq!(
+        stepped,
+        build_view(HubId(1), &econ, &rp, &cargo, MoneyCents(5_000))
+    );
+    assert_ne!(stepped.commodities, first.commodities);
+}
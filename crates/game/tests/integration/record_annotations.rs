@@ -0,0 +1,59 @@
+use std::fs;
+use std::path::Path;
+
+use clap::Parser;
+use game::cli::{CliOptions, Mode};
+use repro::{from_canonical_json_bytes, hash_record, Record};
+use tempfile::tempdir;
+
+fn record_to(path: &Path, annotations: &[&str]) -> Record {
+    let mut args = vec![
+        "game",
+        "--mode",
+        "record",
+        "--headless",
+        "--mission-minutes",
+        "1",
+        "--io",
+        path.to_str().expect("record path"),
+    ];
+    for annotation in annotations {
+        args.extend(["--annotate", annotation]);
+    }
+    let options = CliOptions::try_parse_from(args).expect("cli options");
+    game::run_with_options(options).expect("record run");
+    from_canonical_json_bytes(&fs::read(path).expect("read record")).expect("parse record")
+}
+
+#[test]
+fn cli_annotations_land_in_the_record_without_touching_its_hash() {
+    let dir = tempdir().expect("tempdir");
+    let plain = record_to(&dir.path().join("plain.json"), &[]);
+    let annotated_path = dir.path().join("annotated.json");
+    let annotated = record_to(
+        &annotated_path,
+        &["t=40,label=spike,note=felt wrong", "t=5,label=start"],
+    );
+
+    let labels: Vec<(u32, &str)> = annotated
+        .annotations
+        .iter()
+        .map(|annotation| (annotation.t, annotation.label.as_str()))
+        .collect();
+    assert_eq!(labels, [(5, "start"), (40, "spike")]);
+    assert_eq!(
+        annotated.annotation("spike").map(|a| a.note.as_str()),
+        Some("felt wrong")
+    );
+    assert!(plain.annotations.is_empty());
+    assert_eq!(
+        hash_record(&annotated).expect("hash"),
+        hash_record(&plain).expect("hash")
+    );
+
+    let mut options = CliOptions::for_mode(Mode::Replay);
+    options.headless = true;
+    options.continue_after_mismatch = false;
+    options.io = Some(annotated_path.to_str().expect("record path").to_string());
+    game::run_with_options(options).expect("annotated record replays cleanly");
+}
@@ -0,0 +1,102 @@
+use std::fs;
+use std::path::Path;
+
+use clap::Parser;
+use game::cli::{CliOptions, Mode};
+use game::provenance::{AssetDrift, AssetHashes};
+use game::runtime::mismatch_context_path;
+use repro::{canonical_json_bytes, from_canonical_json_bytes, Record, RECORD_SCHEMA};
+use tempfile::tempdir;
+
+fn record_to(path: &Path) -> Record {
+    let options = CliOptions::try_parse_from([
+        "game",
+        "--mode",
+        "record",
+        "--headless",
+        "--mission-minutes",
+        "1",
+        "--io",
+        path.to_str().expect("record path"),
+    ])
+    .expect("cli options");
+    game::run_with_options(options).expect("record run");
+    from_canonical_json_bytes(&fs::read(path).expect("read record")).expect("parse record")
+}
+
+fn write_record(path: &Path, record: &Record) {
+    fs::write(path, canonical_json_bytes(record).expect("bytes")).expect("write record");
+}
+
+fn replay(path: &Path) -> anyhow::Result<()> {
+    let mut options = CliOptions::for_mode(Mode::Replay);
+    options.headless = true;
+    options.continue_after_mismatch = false;
+    options.io = Some(path.to_str().expect("record path").to_string());
+    game::run_with_options(options)
+}
+
+#[test]
+fn records_pin_asset_hashes() {
+    let dir = tempdir().expect("tempdir");
+    let path = dir.path().join("leg.json");
+    let record = record_to(&path);
+
+    let current = AssetHashes::current().expect("asset hashes");
+    assert_eq!(record.meta.schema, RECORD_SCHEMA);
+    assert_eq!(record.meta.fixed_dt_ns, Some(33_333_333));
+    assert_eq!(record.meta.rulepack_hash, Some(current.rulepack));
+    assert_eq!(record.meta.director_cfg_hash, Some(current.director_cfg));
+    assert_eq!(record.meta.commodities_hash, Some(current.commodities));
+    replay(&path).expect("unchanged assets replay cleanly");
+}
+
+#[test]
+fn drifted_asset_fails_before_simulating() {
+    let dir = tempdir().expect("tempdir");
+    let path = dir.path().join("leg.json");
+    let mut record = record_to(&path);
+    // Desynchronise the commands too: a simulated run would report a
+    // command mismatch and write a context file instead.
+    record.commands.truncate(1);
+
+    for (asset, edit) in [
+        (
+            "rulepack",
+            (|r: &mut Record| r.meta.rulepack_hash = Some("0".into())) as fn(&mut Record),
+        ),
+        ("director config", |r| {
+            r.meta.director_cfg_hash = Some("0".into())
+        }),
+        ("commodities", |r| {
+            r.meta.commodities_hash = Some("0".into())
+        }),
+    ] {
+        let mut drifted = record.clone();
+        edit(&mut drifted);
+        write_record(&path, &drifted);
+
+        let err = replay(&path).expect_err("drifted asset must fail");
+        let drift = err.downcast_ref::<AssetDrift>().expect("asset drift error");
+        assert_eq!(drift.asset, asset);
+        assert_eq!(drift.recorded, "0");
+        assert!(err.to_string().starts_with("asset drift"), "{err}");
+        assert!(!mismatch_context_path(&path).exists());
+    }
+}
+
+#[test]
+fn legacy_records_without_hashes_still_replay() {
+    let dir = tempdir().expect("tempdir");
+    let path = dir.path().join("leg.json");
+    let mut record = record_to(&path);
+    record.meta.schema = 1;
+    record.meta.rulepack_hash = None;
+    record.meta.director_cfg_hash = None;
+    record.meta.commodities_hash = None;
+    write_record(&path, &record);
+
+    let bytes = fs::read(&path).expect("legacy bytes");
+    assert!(!String::from_utf8_lossy(&bytes).contains("_hash"));
+    replay(&path).expect("legacy record replays");
+}
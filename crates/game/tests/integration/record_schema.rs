@@ -0,0 +1,78 @@
+use std::fs;
+use std::path::Path;
+
+use clap::Parser;
+use game::cli::{CliOptions, Mode};
+use repro::strict::ParseMode;
+use repro::{hash_record, CommandSchema, Record};
+use serde_json::Value;
+use tempfile::tempdir;
+
+fn record_to(path: &Path, schema: &str) -> Record {
+    let options = CliOptions::try_parse_from([
+        "game",
+        "--mode",
+        "record",
+        "--headless",
+        "--mission-minutes",
+        "1",
+        "--record-schema",
+        schema,
+        "--io",
+        path.to_str().expect("record path"),
+    ])
+    .expect("cli options");
+    game::run_with_options(options).expect("record run");
+    Record::from_json_bytes(&fs::read(path).expect("read record"), ParseMode::Strict)
+        .expect("parse record")
+}
+
+fn replay(path: &Path) -> anyhow::Result<()> {
+    let mut options = CliOptions::for_mode(Mode::Replay);
+    options.headless = true;
+    options.continue_after_mismatch = false;
+    options.io = Some(path.to_str().expect("record path").to_string());
+    game::run_with_options(options)
+}
+
+fn written_hash(path: &Path) -> String {
+    fs::read_to_string(path.with_extension("hash"))
+        .expect("read hash")
+        .trim()
+        .to_owned()
+}
+
+#[test]
+fn schema_two_records_group_by_tick_and_replay() {
+    let dir = tempdir().expect("tempdir");
+    let v1_path = dir.path().join("leg_v1.json");
+    let v2_path = dir.path().join("leg_v2.json");
+    let v1 = record_to(&v1_path, "1");
+    let v2 = record_to(&v2_path, "2");
+
+    assert_eq!(v1.meta.command_schema, CommandSchema::PerCommand);
+    assert_eq!(v2.meta.command_schema, CommandSchema::ByTick);
+    assert_eq!(v1.commands, v2.commands);
+    assert!(!v2.commands.is_empty());
+
+    let raw: Value = serde_json::from_slice(&fs::read(&v2_path).expect("read v2")).expect("json");
+    let groups = raw["commands"].as_array().expect("command groups");
+    assert_eq!(groups.len(), v2.ticks().count());
+    assert!(groups.iter().all(|group| group["cmds"].is_array()));
+    assert!(
+        fs::metadata(&v2_path).expect("v2 size").len()
+            < fs::metadata(&v1_path).expect("v1 size").len()
+    );
+
+    assert_eq!(written_hash(&v2_path), hash_record(&v2).expect("hash v2"));
+    assert_ne!(written_hash(&v1_path), written_hash(&v2_path));
+
+    replay(&v2_path).expect("schema 2 record replays against the live sim");
+    replay(&v1_path).expect("schema 1 record still replays");
+}
+
+#[test]
+fn unknown_record_schema_is_rejected() {
+    let err = CliOptions::try_parse_from(["game", "--record-schema", "3"]).expect_err("schema 3");
+    assert!(err.to_string().contains("expected 1 or 2"), "{err}");
+}
@@ -0,0 +1,115 @@
+use std::fs;
+use std::path::Path;
+
+use clap::Parser;
+use game::cli::{CliOptions, Mode};
+use game::runtime::{mismatch_context_path, MISMATCH_WINDOW};
+use repro::{canonical_json_bytes, from_canonical_json_bytes, Command, CommandKind, Record};
+use serde_json::Value;
+use tempfile::tempdir;
+
+fn record_to(path: &Path) -> Record {
+    let options = CliOptions::try_parse_from([
+        "game",
+        "--mode",
+        "record",
+        "--headless",
+        "--mission-minutes",
+        "1",
+        "--io",
+        path.to_str().expect("record path"),
+    ])
+    .expect("cli options");
+    game::run_with_options(options).expect("record run");
+    from_canonical_json_bytes(&fs::read(path).expect("read record")).expect("parse record")
+}
+
+fn tamper(command: &mut Command) {
+    match &mut command.kind {
+        CommandKind::Meter(meter) => meter.value += 1,
+        CommandKind::Spawn(spawn) => spawn.x_mm += 1,
+    }
+}
+
+fn replay(path: &Path, continue_after: bool) -> anyhow::Result<()> {
+    let mut options = CliOptions::for_mode(Mode::Replay);
+    options.headless = true;
+    options.continue_after_mismatch = continue_after;
+    options.io = Some(path.to_str().expect("record path").to_string());
+    game::run_with_options(options)
+}
+
+fn as_json(commands: &[Command]) -> Value {
+    serde_json::to_value(commands).expect("commands json")
+}
+
+#[test]
+fn mismatch_writes_neighbouring_commands_and_live_snapshot() {
+    let dir = tempdir().expect("tempdir");
+    let path = dir.path().join("leg.json");
+    let original = record_to(&path);
+    let idx = original.commands.len() / 2;
+    assert!(idx > MISMATCH_WINDOW);
+
+    let mut tampered = original.clone();
+    tamper(&mut tampered.commands[idx]);
+    fs::write(&path, canonical_json_bytes(&tampered).expect("bytes")).expect("write record");
+
+    let err = replay(&path, false).expect_err("tampered record must mismatch");
+    assert!(
+        err.to_string()
+            .starts_with(&format!("replay mismatch at command {idx}")),
+        "{err}"
+    );
+
+    let context_path = mismatch_context_path(&path);
+    assert_eq!(context_path, dir.path().join("leg.mismatch.json"));
+    let context: Value = serde_json::from_slice(&fs::read(&context_path).expect("context file"))
+        .expect("context json");
+
+    let tick = original.commands[idx].t;
+    let window = idx - MISMATCH_WINDOW..idx + MISMATCH_WINDOW + 1;
+    assert_eq!(context["index"], idx);
+    assert_eq!(context["tick"], tick);
+    assert_eq!(context["window_start"], idx - MISMATCH_WINDOW);
+    assert_eq!(context["meta"]["day"], original.meta.day);
+    assert_eq!(
+        context["expected"],
+        as_json(&tampered.commands[window.clone()])
+    );
+    assert_eq!(context["actual"], as_json(&original.commands[window]));
+    assert_eq!(context["snapshot"]["director"]["leg_tick"], tick);
+    assert!(context["snapshot"]["wheel"].is_object());
+    assert!(context["snapshot"]["econ_intent"].is_object());
+    assert!(context["snapshot"]["spawn_memory"].is_object());
+}
+
+#[test]
+fn continue_after_mismatch_collects_every_context() {
+    let dir = tempdir().expect("tempdir");
+    let path = dir.path().join("leg.json");
+    let original = record_to(&path);
+    let picks = [3, original.commands.len() / 3, original.commands.len() - 2];
+
+    let mut tampered = original.clone();
+    for idx in picks {
+        tamper(&mut tampered.commands[idx]);
+    }
+    fs::write(&path, canonical_json_bytes(&tampered).expect("bytes")).expect("write record");
+
+    replay(&path, true).expect("continue-after-mismatch replays to the end");
+
+    let contexts: Value =
+        serde_json::from_slice(&fs::read(mismatch_context_path(&path)).expect("context file"))
+            .expect("context json");
+    let contexts = contexts.as_array().expect("array of contexts");
+    assert_eq!(contexts.len(), picks.len());
+    for (context, idx) in contexts.iter().zip(picks) {
+        assert_eq!(context["index"], idx);
+        assert_eq!(
+            context["snapshot"]["director"]["leg_tick"],
+            original.commands[idx].t
+        );
+    }
+    assert_eq!(contexts[0]["window_start"], 0);
+}
@@ -0,0 +1,115 @@
+use std::path::Path;
+
+use bevy::prelude::*;
+use bevy::time::{Fixed, Time as BevyTime};
+
+use game::app_state::AppState;
+use game::scheduling;
+use game::systems::command_queue::CommandQueue;
+use game::systems::director::config::{load_director_cfg, ClosureCfg};
+use game::systems::director::{
+    DirectorConfigResource, DirectorPlugin, DirectorState, LegContext, LegStatus,
+};
+use game::systems::economy::{EconomyDay, Pp, RouteId, Weather};
+use game::world::closures::RouteClosures;
+use game::world::index::{StaticWorldIndex, WorldIndex};
+use repro::CommandKind;
+
+const LINK: RouteId = RouteId(4);
+const DAY: u32 = 6;
+
+fn run_leg(closures: ClosureCfg) -> (App, Vec<(String, i32)>) {
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins);
+    scheduling::configure(&mut app);
+    {
+        let mut fixed = app.world_mut().resource_mut::<BevyTime<Fixed>>();
+        *fixed = BevyTime::<Fixed>::from_hz(60.0);
+    }
+    app.init_resource::<CommandQueue>();
+    app.init_resource::<AppState>();
+    app.insert_resource(LegContext {
+        world_seed: 0xD7E7_2024_0001_0001,
+        link_id: LINK,
+        day: DAY,
+        weather: Weather::Rains,
+        pp: Pp(9_000),
+        density_per_10k: 8,
+        cadence_per_min: 4,
+        mission_minutes: 1,
+        player_rating: 50,
+        ..Default::default()
+    });
+    app.add_plugins(DirectorPlugin);
+
+    let cfg_path = Path::new(env!("CARGO_MANIFEST_DIR")).join("../../assets/director/m2.toml");
+    let mut cfg = load_director_cfg(cfg_path.to_str().expect("cfg path")).expect("director cfg");
+    cfg.closures = closures;
+    app.insert_resource(DirectorConfigResource(cfg));
+    app.finish();
+    app.update();
+
+    let mut meters = Vec::new();
+    for _ in 0..200 {
+        let tick = app.world().resource::<DirectorState>().leg_tick;
+        let world = app.world_mut();
+        world.resource_mut::<CommandQueue>().begin_tick(tick);
+        world.run_schedule(FixedUpdate);
+        for command in world.resource_mut::<CommandQueue>().drain() {
+            if let CommandKind::Meter(meter) = command.kind {
+                meters.push((meter.key.to_string(), meter.value));
+            }
+        }
+        if matches!(
+            app.world().resource::<DirectorState>().status,
+            LegStatus::Completed(_)
+        ) {
+            break;
+        }
+    }
+    assert!(matches!(
+        app.world().resource::<DirectorState>().status,
+        LegStatus::Completed(_)
+    ));
+    (app, meters)
+}
+
+#[test]
+fn dangerous_leg_closes_its_route_and_persists_to_app_state() {
+    let (app, meters) = run_leg(ClosureCfg {
+        danger_delta_threshold: 0,
+        closed_days: 2,
+    });
+    assert!(app.world().resource::<DirectorState>().current_danger_score > 0);
+
+    let closures = app.world().resource::<RouteClosures>();
+    assert!(closures.is_closed(LINK, EconomyDay(DAY)));
+    assert!(closures.is_closed(LINK, EconomyDay(DAY + 1)));
+    assert!(!closures.is_closed(LINK, EconomyDay(DAY + 2)));
+    assert_eq!(
+        closures.entries().collect::<Vec<_>>(),
+        vec![(LINK, EconomyDay(DAY + 2))]
+    );
+    assert_eq!(
+        meters
+            .iter()
+            .filter(|(key, _)| key == "route_closed")
+            .collect::<Vec<_>>(),
+        vec![&("route_closed".to_string(), i32::from(LINK.0))]
+    );
+    let app_state = app.world().resource::<AppState>();
+    assert_eq!(app_state.route_closures, *closures);
+    let (from, to) = StaticWorldIndex::route_endpoints(LINK).expect("link endpoints");
+    assert_eq!(app_state.econ.closed_routes.get(&from), Some(&1));
+    assert_eq!(app_state.econ.closed_routes.get(&to), Some(&1));
+}
+
+#[test]
+fn calm_leg_leaves_route_open() {
+    let (app, meters) = run_leg(ClosureCfg {
+        danger_delta_threshold: i32::MAX,
+        closed_days: 2,
+    });
+    assert!(app.world().resource::<RouteClosures>().is_empty());
+    assert!(meters.iter().all(|(key, _)| key != "route_closed"));
+}
@@ -0,0 +1,73 @@
+use std::fs;
+use std::path::Path;
+
+use clap::Parser;
+use game::cli::{CliOptions, Mode};
+use game::provenance::{file_hash, AssetDrift};
+use repro::{canonical_json_bytes, from_canonical_json_bytes, Record};
+use tempfile::tempdir;
+
+fn workspace_path(relative: &str) -> std::path::PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("../..")
+        .join(relative)
+}
+
+fn record_day(path: &Path, day: u32) -> Record {
+    let options = CliOptions::try_parse_from([
+        "game",
+        "--mode",
+        "record",
+        "--headless",
+        "--mission-minutes",
+        "1",
+        "--day",
+        &day.to_string(),
+        "--io",
+        path.to_str().expect("record path"),
+    ])
+    .expect("cli options");
+    game::run_with_options(options).expect("record run");
+    from_canonical_json_bytes(&fs::read(path).expect("read record")).expect("parse record")
+}
+
+fn replay(path: &Path) -> anyhow::Result<()> {
+    let mut options = CliOptions::for_mode(Mode::Replay);
+    options.headless = true;
+    options.continue_after_mismatch = false;
+    options.io = Some(path.to_str().expect("record path").to_string());
+    game::run_with_options(options)
+}
+
+#[test]
+fn records_pin_the_rulepack_scheduled_for_their_day() {
+    let dir = tempdir().expect("tempdir");
+    for (day, file) in [
+        (5, "assets/rulepacks/day_001.toml"),
+        (15, "assets/rulepacks/day_010.toml"),
+    ] {
+        let path = dir.path().join(format!("day_{day}.json"));
+        let record = record_day(&path, day);
+        assert_eq!(record.meta.rulepack, file);
+        assert_eq!(
+            record.meta.rulepack_hash,
+            Some(file_hash(&workspace_path(file)).expect("rulepack hash"))
+        );
+        replay(&path).expect("replay re-resolves the recorded rulepack");
+    }
+}
+
+#[test]
+fn replay_loads_the_recorded_file_not_the_schedule() {
+    let dir = tempdir().expect("tempdir");
+    let path = dir.path().join("leg.json");
+    let mut record = record_day(&path, 15);
+
+    // Pointing the record at the day 1 pack makes the replay hash that file,
+    // which no longer matches the pinned day 10 hash.
+    record.meta.rulepack = "assets/rulepacks/day_001.toml".into();
+    fs::write(&path, canonical_json_bytes(&record).expect("bytes")).expect("write record");
+    let err = replay(&path).expect_err("swapped rulepack must fail");
+    let drift = err.downcast_ref::<AssetDrift>().expect("asset drift error");
+    assert_eq!(drift.asset, "rulepack");
+}
@@ -0,0 +1,129 @@
+use std::fs;
+use std::path::Path;
+
+use game::app_state::AppState;
+use game::systems::economy::{EconomyDay, HubId, MoneyCents};
+use game::systems::save::{
+    backup_path, load, load_report, save, save_with_options, snapshot_from_app_state, verify,
+    SaveOptions, SaveV18, SchemaVersion,
+};
+use game::systems::trading::ledger::Wallet;
+use tempfile::tempdir;
+
+fn snapshot_for_day(day: u32) -> SaveV18 {
+    let mut state = AppState::default();
+    state.econ.day = EconomyDay(day);
+    state.last_hub = HubId(2);
+    state.wallet = Wallet::new(MoneyCents(1_000 + i64::from(day)));
+    snapshot_from_app_state(&state)
+}
+
+fn saved_day(path: &Path) -> u32 {
+    let raw = fs::read_to_string(path).expect("read save");
+    let value: serde_json::Value = serde_json::from_str(&raw).expect("json");
+    value["day"].as_u64().expect("day") as u32
+}
+
+#[test]
+fn truncated_primary_recovers_from_backup() {
+    let dir = tempdir().expect("tempdir");
+    let path = dir.path().join("slot.json");
+
+    save(&path, &snapshot_for_day(1)).expect("first save");
+    save(&path, &snapshot_for_day(2)).expect("second save");
+
+    let raw = fs::read(&path).expect("primary bytes");
+    fs::write(&path, &raw[..raw.len() / 2]).expect("truncate primary");
+
+    let report = load_report(&path).expect("recovered");
+    assert_eq!(report.recovered_from, Some(backup_path(&path, 0)));
+    assert_eq!(report.snapshot, snapshot_for_day(1));
+    assert_eq!(load(&path).expect("load"), snapshot_for_day(1));
+}
+
+#[test]
+fn corrupt_primary_without_backup_returns_original_error() {
+    let dir = tempdir().expect("tempdir");
+    let path = dir.path().join("slot.json");
+    fs::write(&path, "{\"econ_version\":").expect("write corrupt");
+
+    let err = load_report(&path).expect_err("no backup to recover from");
+    assert!(matches!(err, game::systems::save::SaveError::Serde(_)));
+}
+
+#[test]
+fn save_renames_temp_file_into_place() {
+    let dir = tempdir().expect("tempdir");
+    let path = dir.path().join("slot.json");
+
+    save(&path, &snapshot_for_day(4)).expect("save");
+
+    assert!(path.exists());
+    assert!(!dir.path().join("slot.json.tmp").exists());
+    assert!(
+        !backup_path(&path, 0).exists(),
+        "first save has nothing to back up"
+    );
+    assert_eq!(verify(&path).expect("verify"), SchemaVersion::V18);
+}
+
+#[test]
+fn partial_temp_file_from_a_crash_leaves_the_previous_save_loadable() {
+    let dir = tempdir().expect("tempdir");
+    let path = dir.path().join("slot.json");
+    save(&path, &snapshot_for_day(1)).expect("first save");
+
+    // A save of day 2 that crashed halfway through writing its temp file.
+    let full = serde_json::to_vec_pretty(&snapshot_for_day(2)).expect("json");
+    let tmp = dir.path().join("slot.json.tmp");
+    fs::write(&tmp, &full[..full.len() / 2]).expect("partial tmp");
+
+    let report = load_report(&path).expect("previous save");
+    assert_eq!(report.snapshot, snapshot_for_day(1));
+    assert_eq!(report.recovered_from, None);
+
+    // The next save replaces the stale temp file rather than appending to it.
+    save(&path, &snapshot_for_day(3)).expect("save over stale tmp");
+    assert!(!tmp.exists());
+    assert_eq!(load(&path).expect("load"), snapshot_for_day(3));
+    assert_eq!(saved_day(&backup_path(&path, 0)), 1);
+}
+
+#[test]
+fn repeated_saves_rotate_backups() {
+    let dir = tempdir().expect("tempdir");
+    let path = dir.path().join("slot.json");
+    let options = SaveOptions { backup_depth: 2 };
+
+    for day in 1..=4 {
+        save_with_options(&path, &snapshot_for_day(day), &options).expect("save");
+    }
+
+    assert_eq!(saved_day(&path), 4);
+    assert_eq!(saved_day(&backup_path(&path, 0)), 3);
+    assert_eq!(saved_day(&backup_path(&path, 1)), 2);
+    assert!(!backup_path(&path, 2).exists());
+}
+
+#[test]
+fn default_depth_keeps_single_backup() {
+    let dir = tempdir().expect("tempdir");
+    let path = dir.path().join("slot.json");
+
+    for day in 1..=3 {
+        save(&path, &snapshot_for_day(day)).expect("save");
+    }
+
+    assert_eq!(saved_day(&backup_path(&path, 0)), 2);
+    assert!(!backup_path(&path, 1).exists());
+}
+
+#[test]
+fn verify_detects_v1_payloads() {
+    let dir = tempdir().expect("tempdir");
+    let path = dir.path().join("legacy.json");
+    let golden = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/goldens/save_v1_roundtrip.json");
+    fs::copy(golden, &path).expect("copy golden");
+
+    assert_eq!(verify(&path).expect("verify"), SchemaVersion::V1);
+}
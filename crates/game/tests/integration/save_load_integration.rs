@@ -1,18 +1,21 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::path::PathBuf;
 
 use game::app_state::AppState;
 use game::systems::command_queue::CommandQueue;
+use game::systems::director::{MissionHistory, MissionHistoryEntry, Outcome};
 use game::systems::economy::rulepack::load_rulepack;
 use game::systems::economy::state::RngCursor;
 use game::systems::economy::{
-    BasisBp, CommodityId, EconState, HubId, MoneyCents, PendingPlanting, Pp,
+    BasisBp, CommodityId, EconState, EconomyDay, HubId, MoneyCents, PendingPlanting, Pp, RouteId,
 };
 use game::systems::save::{load_app_state, save_app_state, snapshot_from_app_state, InventorySlot};
 use game::systems::trading::engine::{TradeKind, TradeTx};
 use game::systems::trading::inventory::Cargo;
+use game::systems::trading::ledger::{LedgerJournal, Wallet, WalletAccess};
 use game::systems::trading::types::{CommodityCatalog, TradingConfig};
 use game::ui::hub_trade::{build_view, persist_on_exit, HubTradeActions, HubTradeUiState};
+use game::world::closures::RouteClosures;
 use tempfile::tempdir;
 
 fn asset_path(relative: &str) -> PathBuf {
@@ -24,7 +27,10 @@ fn install_globals() {
     let catalog_path = asset_path("assets/trading/commodities.toml");
     let catalog = CommodityCatalog::load_from_path(catalog_path.as_path()).expect("catalog");
     CommodityCatalog::install_global(catalog);
-    TradingConfig::install_global(TradingConfig { fee_bp: 75 });
+    TradingConfig::install_global(TradingConfig {
+        fee_bp: 75,
+        ..TradingConfig::default()
+    });
 }
 
 fn load_rulepack_fixture() -> game::systems::economy::Rulepack {
@@ -33,8 +39,12 @@ fn load_rulepack_fixture() -> game::systems::economy::Rulepack {
 }
 
 fn sample_app_state() -> AppState {
+    let mut closures = RouteClosures::default();
+    closures.close(RouteId(2), EconomyDay(6));
+    closures.close(RouteId(4), EconomyDay(5));
+
     let econ = EconState {
-        day: game::systems::economy::EconomyDay(3),
+        day: EconomyDay(3),
         di_bp: HashMap::from([
             (CommodityId(1), BasisBp(120)),
             (CommodityId(2), BasisBp(-80)),
@@ -68,12 +78,22 @@ fn sample_app_state() -> AppState {
             capacity_mass_kg: 2_000,
             capacity_volume_l: 1_500,
             items: HashMap::new(),
+            lots: HashMap::new(),
         },
         rng_cursors: vec![RngCursor {
             label: "di".to_string(),
             draws: 24,
         }],
-        wallet: MoneyCents(100_000),
+        wallet: Wallet::new(MoneyCents(200_000)),
+        route_closures: closures,
+        visited_links: BTreeMap::from([(RouteId(2), 3), (RouteId(4), 1)]),
+        mission_history: MissionHistory::from_legs(
+            8,
+            [vec![MissionHistoryEntry {
+                name: "rain_flag".to_string(),
+                outcome: Outcome::Failure,
+            }]],
+        ),
     }
 }
 
@@ -82,6 +102,7 @@ fn save_load_roundtrip_preserves_state() {
     install_globals();
     let rp = load_rulepack_fixture();
     let mut app_state = sample_app_state();
+    let mut journal = LedgerJournal::default();
     let mut queue = CommandQueue::default();
     queue.begin_tick(0);
 
@@ -96,9 +117,9 @@ fn save_load_roundtrip_preserves_state() {
     HubTradeActions::buy(
         &mut queue,
         buy_spice,
-        &app_state.econ,
+        &mut app_state.econ,
         &mut app_state.cargo,
-        &mut app_state.wallet,
+        &mut WalletAccess::new(&mut app_state.wallet, &mut journal, 0),
         &rp,
     )
     .expect("buy spice");
@@ -112,9 +133,9 @@ fn save_load_roundtrip_preserves_state() {
     HubTradeActions::buy(
         &mut queue,
         buy_grain,
-        &app_state.econ,
+        &mut app_state.econ,
         &mut app_state.cargo,
-        &mut app_state.wallet,
+        &mut WalletAccess::new(&mut app_state.wallet, &mut journal, 0),
         &rp,
     )
     .expect("buy grain");
@@ -128,9 +149,9 @@ fn save_load_roundtrip_preserves_state() {
     HubTradeActions::sell(
         &mut queue,
         sell_spice,
-        &app_state.econ,
+        &mut app_state.econ,
         &mut app_state.cargo,
-        &mut app_state.wallet,
+        &mut WalletAccess::new(&mut app_state.wallet, &mut journal, 0),
         &rp,
     )
     .expect("sell spice");
@@ -140,7 +161,7 @@ fn save_load_roundtrip_preserves_state() {
         &app_state.econ,
         &rp,
         &app_state.cargo,
-        app_state.wallet,
+        app_state.wallet.balance(),
     );
     ui_state.remember(view);
     persist_on_exit(&ui_state, &mut app_state);
@@ -154,4 +175,7 @@ fn save_load_roundtrip_preserves_state() {
 
     let snapshot = snapshot_from_app_state(&loaded);
     assert_eq!(snapshot.day, app_state.econ.day);
+    assert_eq!(snapshot.route_closures.len(), 2);
+    assert!(loaded.route_closures.is_closed(RouteId(2), EconomyDay(5)));
+    assert_eq!(loaded.most_visited(), Some((RouteId(2), 3)));
 }
@@ -0,0 +1,123 @@
+use std::fs;
+
+use game::app_state::AppState;
+use game::systems::economy::{EconomyDay, HubId, MoneyCents};
+use game::systems::save::{backup_path, SaveError, SaveSlotManager, SchemaVersion, SlotInfo};
+use game::systems::trading::ledger::Wallet;
+use tempfile::tempdir;
+
+fn info_for<'a>(slots: &'a [SlotInfo], slot: &str) -> &'a SlotInfo {
+    slots
+        .iter()
+        .find(|info| info.slot == slot)
+        .unwrap_or_else(|| panic!("missing slot {slot}"))
+}
+
+#[test]
+fn list_reads_metadata_across_schema_versions() {
+    let dir = tempdir().expect("tempdir");
+    let manager = SaveSlotManager::new(dir.path());
+    fs::write(
+        dir.path().join("legacy.json"),
+        include_str!("../goldens/save_v1_roundtrip.json"),
+    )
+    .expect("write v1");
+    fs::write(
+        dir.path().join("cargo_era.json"),
+        include_str!("../goldens/save_v11_roundtrip.json"),
+    )
+    .expect("write v1.1");
+    fs::write(
+        dir.path().join("current.json"),
+        include_str!("../goldens/save_v18_roundtrip.json"),
+    )
+    .expect("write v1.8");
+    fs::write(dir.path().join("current.json.bak"), "{}").expect("write backup");
+    fs::write(dir.path().join("notes.txt"), "ignored").expect("write stray file");
+
+    let slots = manager.list().expect("list slots");
+    let ids: Vec<_> = slots.iter().map(|info| info.slot.as_str()).collect();
+    assert_eq!(ids, ["cargo_era", "current", "legacy"]);
+
+    let legacy = info_for(&slots, "legacy");
+    assert_eq!(legacy.schema, SchemaVersion::V1);
+    assert_eq!(legacy.wallet_cents, MoneyCents::ZERO);
+    assert_eq!(legacy.last_hub, HubId(0));
+    // Hub 0 is not in the world asset, so it keeps its id.
+    assert_eq!(legacy.last_hub_name, "Hub 0");
+
+    let cargo_era = info_for(&slots, "cargo_era");
+    assert_eq!(cargo_era.schema, SchemaVersion::V11);
+    assert_eq!(cargo_era.day, EconomyDay(3));
+    assert_eq!(cargo_era.wallet_cents, MoneyCents(37_217));
+    assert_eq!(cargo_era.last_hub, HubId(2));
+    assert_eq!(cargo_era.last_hub_name, "Brinemarch");
+
+    let current = info_for(&slots, "current");
+    assert_eq!(current.schema, SchemaVersion::V18);
+    assert_eq!(current.wallet_cents, MoneyCents(37_217));
+    assert!(current.modified.is_some());
+}
+
+#[test]
+fn list_tolerates_unknown_top_level_fields() {
+    let dir = tempdir().expect("tempdir");
+    let manager = SaveSlotManager::new(dir.path());
+    let mut value: serde_json::Value =
+        serde_json::from_str(include_str!("../goldens/save_v13_roundtrip.json")).expect("json");
+    value["future_field"] = serde_json::json!({ "nested": true });
+    fs::write(dir.path().join("newer.json"), value.to_string()).expect("write");
+
+    let slots = manager.list().expect("list slots");
+    assert_eq!(slots.len(), 1);
+    assert_eq!(slots[0].day, EconomyDay(3));
+}
+
+#[test]
+fn invalid_slot_ids_are_rejected() {
+    let dir = tempdir().expect("tempdir");
+    let manager = SaveSlotManager::new(dir.path());
+    let state = AppState::default();
+    let too_long = "a".repeat(33);
+    for slot in [
+        "",
+        "../escape",
+        "Upper",
+        "with space",
+        "a/b",
+        too_long.as_str(),
+    ] {
+        let err = manager
+            .save_to_slot(slot, &state)
+            .expect_err("invalid slot should be rejected");
+        assert!(matches!(err, SaveError::InvalidSlot(_)), "{slot:?}: {err}");
+        assert!(matches!(
+            manager.delete_slot(slot),
+            Err(SaveError::InvalidSlot(_))
+        ));
+    }
+    assert!(manager.slot_path(&"z".repeat(32)).is_ok());
+}
+
+#[test]
+fn save_load_and_delete_slot() {
+    let dir = tempdir().expect("tempdir");
+    let manager = SaveSlotManager::new(dir.path().join("saves"));
+    let mut state = AppState {
+        wallet: Wallet::new(MoneyCents(1_250)),
+        ..Default::default()
+    };
+    manager.save_to_slot("slot-1", &state).expect("first save");
+    state.wallet = Wallet::new(MoneyCents(2_500));
+    manager.save_to_slot("slot-1", &state).expect("second save");
+
+    let path = manager.slot_path("slot-1").expect("path");
+    assert!(path.exists());
+    assert!(backup_path(&path, 0).exists());
+    assert_eq!(manager.load_slot("slot-1").expect("load"), state);
+
+    manager.delete_slot("slot-1").expect("delete");
+    assert!(!path.exists());
+    assert!(!backup_path(&path, 0).exists());
+    assert!(manager.list().expect("list").is_empty());
+}
@@ -0,0 +1,136 @@
+use bevy::prelude::*;
+
+use game::scheduling::{self, assert_contract, contract_violations, scheduled_systems, sets};
+use game::systems::command_queue::CommandQueue;
+use game::systems::director::DirectorPlugin;
+
+fn build_director_app() -> App {
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins);
+    scheduling::configure(&mut app);
+    app.init_resource::<CommandQueue>();
+    app.add_plugins(DirectorPlugin);
+    app.finish();
+    app.update();
+    app
+}
+
+fn dummy() {}
+
+fn queue_writer(mut queue: ResMut<CommandQueue>) {
+    queue.meter("dummy", 1);
+}
+
+fn has_violation(app: &App, needles: &[&str]) -> bool {
+    contract_violations(app)
+        .iter()
+        .any(|violation| needles.iter().all(|needle| violation.contains(needle)))
+}
+
+#[test]
+fn director_app_holds_the_contract() {
+    let mut app = build_director_app();
+    assert_contract(&app);
+    app.world_mut().run_schedule(FixedUpdate);
+    assert_contract(&app);
+}
+
+#[test]
+fn director_systems_sit_in_exactly_one_set() {
+    let app = build_director_app();
+    let director: Vec<_> = scheduled_systems(&app)
+        .into_iter()
+        .filter(|system| system.name.contains("::director::"))
+        .collect();
+    assert!(director.len() >= 8, "{director:?}");
+    for system in &director {
+        assert_eq!(system.sets.len(), 1, "{system:?}");
+    }
+    let physics = director
+        .iter()
+        .find(|system| system.name.ends_with("physics_step"))
+        .expect("physics_step");
+    assert_eq!(physics.sets, vec!["DETTEROT_PhysicsStep"]);
+}
+
+#[test]
+fn mis_ordered_system_is_caught() {
+    let mut app = build_director_app();
+    app.add_systems(
+        FixedUpdate,
+        queue_writer
+            .in_set(sets::DETTEROT_Spawns)
+            .before(sets::DETTEROT_Director),
+    );
+    assert!(
+        has_violation(
+            &app,
+            &["queue_writer", "DETTEROT_Spawns", "DETTEROT_Director"]
+        ),
+        "{:?}",
+        contract_violations(&app)
+    );
+}
+
+#[test]
+fn systems_outside_or_across_sets_are_caught() {
+    let mut app = build_director_app();
+    app.add_systems(FixedUpdate, queue_writer);
+    app.add_systems(
+        FixedUpdate,
+        dummy
+            .in_set(sets::DETTEROT_Director)
+            .in_set(sets::DETTEROT_Cleanup),
+    );
+    assert!(has_violation(&app, &["queue_writer", "unordered"]));
+    assert!(has_violation(&app, &["dummy", "several pipeline sets"]));
+
+    // Ordering against a set is enough for systems kept outside them.
+    let mut app = build_director_app();
+    app.add_systems(FixedUpdate, dummy.after(sets::DETTEROT_Cleanup));
+    assert_contract(&app);
+}
+
+#[test]
+fn unchained_sets_are_caught() {
+    let mut app = App::new();
+    app.configure_sets(
+        FixedUpdate,
+        (
+            sets::DETTEROT_Input,
+            sets::DETTEROT_Director,
+            sets::DETTEROT_Missions,
+        )
+            .chain(),
+    );
+    app.configure_sets(
+        FixedUpdate,
+        (
+            sets::DETTEROT_Spawns,
+            sets::DETTEROT_PhysicsStep,
+            sets::DETTEROT_Cleanup,
+        )
+            .chain(),
+    );
+    assert_eq!(
+        contract_violations(&app),
+        vec!["`DETTEROT_Missions` is not ordered before `DETTEROT_Spawns`".to_string()]
+    );
+}
+
+#[test]
+#[should_panic(expected = "schedule contract violated")]
+fn assert_contract_panics_on_violations() {
+    let mut app = build_director_app();
+    app.add_systems(FixedUpdate, queue_writer);
+    assert_contract(&app);
+}
+
+#[cfg(feature = "deterministic")]
+#[test]
+#[should_panic(expected = "ambigu")]
+fn ambiguous_director_systems_fail_the_build() {
+    let mut app = build_director_app();
+    app.add_systems(FixedUpdate, queue_writer.in_set(sets::DETTEROT_Director));
+    app.world_mut().run_schedule(FixedUpdate);
+}
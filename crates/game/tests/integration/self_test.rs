@@ -0,0 +1,34 @@
+#![cfg(feature = "deterministic")]
+
+use clap::Parser;
+use game::cli::{CliOptions, Mode};
+use game::selftest::run_self_test;
+
+fn self_test_options(seed: &str) -> CliOptions {
+    CliOptions::try_parse_from([
+        "game",
+        "--mode",
+        "self-test",
+        "--world-seed",
+        seed,
+        "--link-id",
+        "3",
+        "--weather",
+        "fog",
+    ])
+    .expect("cli options")
+}
+
+#[test]
+fn self_test_passes_under_deterministic_feature() {
+    let options = self_test_options("0x5EED");
+    assert_eq!(options.mode(), Mode::SelfTest);
+    let report = run_self_test(&options, 240).expect("self-test");
+    assert!(report.is_pass(), "{report}");
+    assert_eq!(report.replay_mismatch, None);
+    assert!(report.to_string().ends_with("PASS"), "{report}");
+
+    let other = run_self_test(&self_test_options("0x5EEE"), 240).expect("self-test");
+    assert!(other.is_pass(), "{other}");
+    assert_ne!(other.first_hash, report.first_hash, "world seed is honored");
+}
@@ -3,7 +3,8 @@ use game::systems::economy::{
     BasisBp, CommodityId, EconomyDay, HubId, MoneyCents, PendingPlanting, Pp,
 };
 use game::systems::save::{
-    load, save, BasisSave, CargoItemSave, CargoSave, CommoditySave, InventorySlot, SaveV11,
+    load, BasisSave, CargoItemSave, CargoSave, CommoditySave, InventorySlot, SaveV11, SaveV12,
+    SaveV13, SaveV14, SaveV15, SaveV16, SaveV17, SaveV18,
 };
 use std::fs;
 use tempfile::tempdir;
@@ -66,16 +67,23 @@ fn sample_save() -> SaveV11 {
 }
 
 #[test]
-fn save_roundtrip_is_byte_identical() {
+fn v11_golden_parses_and_migrates() {
+    let golden = include_str!("../goldens/save_v11_roundtrip.json");
+    let parsed: SaveV11 = serde_json::from_str(golden).expect("parse v1.1 golden");
+    assert_eq!(parsed, sample_save());
+
     let dir = tempdir().expect("temp dir");
     let path = dir.path().join("save_v11.json");
-    let snapshot = sample_save();
-    save(&path, &snapshot).expect("write save");
-    let written = fs::read_to_string(&path).expect("read save");
-    let golden = include_str!("../goldens/save_v11_roundtrip.json");
-    assert_eq!(written, golden);
+    fs::write(&path, golden).expect("write golden");
     let loaded = load(&path).expect("load save");
-    assert_eq!(loaded, snapshot);
+    assert_eq!(
+        loaded,
+        SaveV18::from(SaveV17::from(SaveV16::from(SaveV15::from(SaveV14::from(
+            SaveV13::from(SaveV12::from(sample_save()))
+        )))))
+    );
+    assert!(loaded.price_history.is_empty());
+    assert!(loaded.route_closures.is_empty());
 }
 
 #[test]
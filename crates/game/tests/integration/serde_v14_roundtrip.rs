@@ -0,0 +1,136 @@
+use game::systems::economy::state::{RngCursor, TRADE_PRESSURE_LIMIT};
+use game::systems::economy::{
+    BasisBp, CommodityId, EconomyDay, HubId, MoneyCents, PendingPlanting, Pp, RouteId,
+};
+use game::systems::save::{
+    app_state_from_snapshot, load, save, snapshot_from_app_state, BasisSave, CargoItemSave,
+    CargoSave, CommoditySave, InventorySlot, PriceHistorySave, RouteClosureSave, SaveV14, SaveV15,
+    SaveV16, SaveV17, SaveV18, TradePressureSave,
+};
+use std::fs;
+use tempfile::tempdir;
+
+fn sample_save() -> SaveV14 {
+    SaveV14 {
+        econ_version: 7,
+        world_seed: 42,
+        day: EconomyDay(3),
+        last_hub: HubId(2),
+        di: vec![
+            CommoditySave {
+                commodity: CommodityId(1),
+                value: BasisBp(125),
+            },
+            CommoditySave {
+                commodity: CommodityId(2),
+                value: BasisBp(-45),
+            },
+        ],
+        di_overlay_bp: 120,
+        basis: vec![BasisSave {
+            hub: HubId(1),
+            commodity: CommodityId(1),
+            value: BasisBp(15),
+        }],
+        pp: Pp(5_100),
+        rot: 12,
+        debt_cents: MoneyCents(4_200),
+        inventory: vec![InventorySlot {
+            commodity: CommodityId(9),
+            amount: 33,
+        }],
+        wallet_cents: MoneyCents(37_217),
+        cargo: CargoSave {
+            capacity_mass_kg: 2_000,
+            capacity_volume_l: 1_500,
+            items: vec![CargoItemSave {
+                commodity: CommodityId(1),
+                units: 7,
+            }],
+        },
+        pending_planting: vec![PendingPlanting {
+            hub: HubId(1),
+            size: 4,
+            age_days: 2,
+        }],
+        rng_cursors: vec![RngCursor {
+            label: "di".to_string(),
+            draws: 24,
+        }],
+        price_history: vec![
+            PriceHistorySave {
+                hub: HubId(1),
+                commodity: CommodityId(1),
+                samples: vec![BasisBp(110), BasisBp(125), BasisBp(140)],
+            },
+            PriceHistorySave {
+                hub: HubId(1),
+                commodity: CommodityId(2),
+                samples: vec![BasisBp(-30), BasisBp(-45)],
+            },
+        ],
+        route_closures: vec![
+            RouteClosureSave {
+                route: RouteId(1),
+                until_day: EconomyDay(5),
+            },
+            RouteClosureSave {
+                route: RouteId(3),
+                until_day: EconomyDay(4),
+            },
+        ],
+        trade_pressure: vec![
+            TradePressureSave {
+                hub: HubId(1),
+                commodity: CommodityId(1),
+                units: 240,
+                priced: 300,
+            },
+            TradePressureSave {
+                hub: HubId(2),
+                commodity: CommodityId(2),
+                units: -35,
+                priced: 0,
+            },
+        ],
+    }
+}
+
+#[test]
+fn v14_golden_parses_and_migrates() {
+    let golden = include_str!("../goldens/save_v14_roundtrip.json");
+    let parsed: SaveV14 = serde_json::from_str(golden).expect("parse v1.4 golden");
+    assert_eq!(parsed, sample_save());
+
+    let dir = tempdir().expect("temp dir");
+    let path = dir.path().join("save_v14.json");
+    fs::write(&path, golden).expect("write golden");
+    let loaded = load(&path).expect("load save");
+    assert_eq!(
+        loaded,
+        SaveV18::from(SaveV17::from(SaveV16::from(SaveV15::from(sample_save()))))
+    );
+    assert!(loaded.visited_links.is_empty());
+}
+
+#[test]
+fn trade_pressure_roundtrips_through_app_state() {
+    let snapshot = SaveV18::from(SaveV17::from(SaveV16::from(SaveV15::from(sample_save()))));
+    let state = app_state_from_snapshot(snapshot.clone());
+    assert_eq!(state.econ.trade_pressure(HubId(1), CommodityId(1)), 240);
+    assert_eq!(state.econ.trade_pressure(HubId(2), CommodityId(2)), -35);
+    assert_eq!(state.econ.trade_pressure(HubId(2), CommodityId(1)), 0);
+    assert_eq!(snapshot_from_app_state(&state), snapshot);
+}
+
+#[test]
+fn oversized_pressure_is_bounded_on_save() {
+    let dir = tempdir().expect("temp dir");
+    let path = dir.path().join("save_v14.json");
+    let mut snapshot = SaveV18::from(SaveV17::from(SaveV16::from(SaveV15::from(sample_save()))));
+    snapshot.trade_pressure[0].units = i32::MAX;
+    save(&path, &snapshot).expect("write save");
+
+    let loaded = load(&path).expect("load save");
+    assert_eq!(loaded.trade_pressure[0].units, TRADE_PRESSURE_LIMIT);
+}
@@ -0,0 +1,154 @@
+use game::systems::economy::state::RngCursor;
+use game::systems::economy::{
+    BasisBp, CommodityId, EconomyDay, HubId, MoneyCents, PendingPlanting, Pp, RouteId,
+};
+use game::systems::save::{
+    app_state_from_snapshot, load, save, snapshot_from_app_state, BasisSave, CargoItemSave,
+    CargoSave, CommoditySave, InventorySlot, PriceHistorySave, RouteClosureSave, RouteVisitSave,
+    SaveV15, SaveV16, SaveV17, SaveV18, TradePressureSave,
+};
+use std::fs;
+use tempfile::tempdir;
+
+fn sample_save() -> SaveV15 {
+    SaveV15 {
+        econ_version: 7,
+        world_seed: 42,
+        day: EconomyDay(3),
+        last_hub: HubId(2),
+        di: vec![
+            CommoditySave {
+                commodity: CommodityId(1),
+                value: BasisBp(125),
+            },
+            CommoditySave {
+                commodity: CommodityId(2),
+                value: BasisBp(-45),
+            },
+        ],
+        di_overlay_bp: 120,
+        basis: vec![BasisSave {
+            hub: HubId(1),
+            commodity: CommodityId(1),
+            value: BasisBp(15),
+        }],
+        pp: Pp(5_100),
+        rot: 12,
+        debt_cents: MoneyCents(4_200),
+        inventory: vec![InventorySlot {
+            commodity: CommodityId(9),
+            amount: 33,
+        }],
+        wallet_cents: MoneyCents(37_217),
+        cargo: CargoSave {
+            capacity_mass_kg: 2_000,
+            capacity_volume_l: 1_500,
+            items: vec![CargoItemSave {
+                commodity: CommodityId(1),
+                units: 7,
+            }],
+        },
+        pending_planting: vec![PendingPlanting {
+            hub: HubId(1),
+            size: 4,
+            age_days: 2,
+        }],
+        rng_cursors: vec![RngCursor {
+            label: "di".to_string(),
+            draws: 24,
+        }],
+        price_history: vec![
+            PriceHistorySave {
+                hub: HubId(1),
+                commodity: CommodityId(1),
+                samples: vec![BasisBp(110), BasisBp(125), BasisBp(140)],
+            },
+            PriceHistorySave {
+                hub: HubId(1),
+                commodity: CommodityId(2),
+                samples: vec![BasisBp(-30), BasisBp(-45)],
+            },
+        ],
+        route_closures: vec![
+            RouteClosureSave {
+                route: RouteId(1),
+                until_day: EconomyDay(5),
+            },
+            RouteClosureSave {
+                route: RouteId(3),
+                until_day: EconomyDay(4),
+            },
+        ],
+        trade_pressure: vec![
+            TradePressureSave {
+                hub: HubId(1),
+                commodity: CommodityId(1),
+                units: 240,
+                priced: 300,
+            },
+            TradePressureSave {
+                hub: HubId(2),
+                commodity: CommodityId(2),
+                units: -35,
+                priced: 0,
+            },
+        ],
+        visited_links: vec![
+            RouteVisitSave {
+                route: RouteId(1),
+                count: 4,
+            },
+            RouteVisitSave {
+                route: RouteId(3),
+                count: 4,
+            },
+            RouteVisitSave {
+                route: RouteId(6),
+                count: 1,
+            },
+        ],
+    }
+}
+
+#[test]
+fn v15_golden_parses_and_migrates() {
+    let golden = include_str!("../goldens/save_v15_roundtrip.json");
+    let parsed: SaveV15 = serde_json::from_str(golden).expect("parse v1.5 golden");
+    assert_eq!(parsed, sample_save());
+
+    let dir = tempdir().expect("temp dir");
+    let path = dir.path().join("save_v15.json");
+    fs::write(&path, golden).expect("write golden");
+    let loaded = load(&path).expect("load save");
+    assert_eq!(
+        loaded,
+        SaveV18::from(SaveV17::from(SaveV16::from(sample_save())))
+    );
+    assert!(loaded.mission_history.is_empty());
+}
+
+#[test]
+fn visited_links_roundtrip_through_app_state() {
+    let snapshot = SaveV18::from(SaveV17::from(SaveV16::from(sample_save())));
+    let state = app_state_from_snapshot(snapshot.clone());
+    assert_eq!(state.route_visit_count(RouteId(1)), 4);
+    assert_eq!(state.route_visit_count(RouteId(6)), 1);
+    assert_eq!(state.route_visit_count(RouteId(2)), 0);
+    assert_eq!(state.most_visited(), Some((RouteId(1), 4)));
+    assert_eq!(snapshot_from_app_state(&state), snapshot);
+}
+
+#[test]
+fn visited_links_are_sorted_on_save() {
+    let dir = tempdir().expect("temp dir");
+    let path = dir.path().join("save_v15.json");
+    let mut snapshot = SaveV18::from(SaveV17::from(SaveV16::from(sample_save())));
+    snapshot.visited_links.reverse();
+    save(&path, &snapshot).expect("write save");
+
+    let loaded = load(&path).expect("load save");
+    assert_eq!(
+        loaded,
+        SaveV18::from(SaveV17::from(SaveV16::from(sample_save())))
+    );
+}
@@ -0,0 +1,171 @@
+use game::systems::director::{MissionHistory, Outcome};
+use game::systems::economy::state::RngCursor;
+use game::systems::economy::{
+    BasisBp, CommodityId, EconomyDay, HubId, MoneyCents, PendingPlanting, Pp, RouteId,
+};
+use game::systems::save::{
+    app_state_from_snapshot, load, snapshot_from_app_state, BasisSave, CargoItemSave, CargoSave,
+    CommoditySave, InventorySlot, MissionHistoryLegSave, MissionResultSave, PriceHistorySave,
+    RouteClosureSave, RouteVisitSave, SaveV16, SaveV17, SaveV18, TradePressureSave,
+};
+use std::fs;
+use tempfile::tempdir;
+
+fn sample_save() -> SaveV16 {
+    SaveV16 {
+        econ_version: 7,
+        world_seed: 42,
+        day: EconomyDay(3),
+        last_hub: HubId(2),
+        di: vec![
+            CommoditySave {
+                commodity: CommodityId(1),
+                value: BasisBp(125),
+            },
+            CommoditySave {
+                commodity: CommodityId(2),
+                value: BasisBp(-45),
+            },
+        ],
+        di_overlay_bp: 120,
+        basis: vec![BasisSave {
+            hub: HubId(1),
+            commodity: CommodityId(1),
+            value: BasisBp(15),
+        }],
+        pp: Pp(5_100),
+        rot: 12,
+        debt_cents: MoneyCents(4_200),
+        inventory: vec![InventorySlot {
+            commodity: CommodityId(9),
+            amount: 33,
+        }],
+        wallet_cents: MoneyCents(37_217),
+        cargo: CargoSave {
+            capacity_mass_kg: 2_000,
+            capacity_volume_l: 1_500,
+            items: vec![CargoItemSave {
+                commodity: CommodityId(1),
+                units: 7,
+            }],
+        },
+        pending_planting: vec![PendingPlanting {
+            hub: HubId(1),
+            size: 4,
+            age_days: 2,
+        }],
+        rng_cursors: vec![RngCursor {
+            label: "di".to_string(),
+            draws: 24,
+        }],
+        price_history: vec![
+            PriceHistorySave {
+                hub: HubId(1),
+                commodity: CommodityId(1),
+                samples: vec![BasisBp(110), BasisBp(125), BasisBp(140)],
+            },
+            PriceHistorySave {
+                hub: HubId(1),
+                commodity: CommodityId(2),
+                samples: vec![BasisBp(-30), BasisBp(-45)],
+            },
+        ],
+        route_closures: vec![
+            RouteClosureSave {
+                route: RouteId(1),
+                until_day: EconomyDay(5),
+            },
+            RouteClosureSave {
+                route: RouteId(3),
+                until_day: EconomyDay(4),
+            },
+        ],
+        trade_pressure: vec![
+            TradePressureSave {
+                hub: HubId(1),
+                commodity: CommodityId(1),
+                units: 240,
+                priced: 300,
+            },
+            TradePressureSave {
+                hub: HubId(2),
+                commodity: CommodityId(2),
+                units: -35,
+                priced: 0,
+            },
+        ],
+        visited_links: vec![
+            RouteVisitSave {
+                route: RouteId(1),
+                count: 4,
+            },
+            RouteVisitSave {
+                route: RouteId(3),
+                count: 4,
+            },
+            RouteVisitSave {
+                route: RouteId(6),
+                count: 1,
+            },
+        ],
+        mission_history: vec![
+            MissionHistoryLegSave {
+                missions: vec![
+                    MissionResultSave {
+                        name: "rain_flag".to_string(),
+                        outcome: Outcome::Success,
+                    },
+                    MissionResultSave {
+                        name: "sourvault".to_string(),
+                        outcome: Outcome::Failure,
+                    },
+                ],
+            },
+            MissionHistoryLegSave {
+                missions: Vec::new(),
+            },
+            MissionHistoryLegSave {
+                missions: vec![MissionResultSave {
+                    name: "wayleave".to_string(),
+                    outcome: Outcome::Failure,
+                }],
+            },
+        ],
+    }
+}
+
+#[test]
+fn v16_golden_parses_and_migrates() {
+    let golden = include_str!("../goldens/save_v16_roundtrip.json");
+    let parsed: SaveV16 = serde_json::from_str(golden).expect("parse v1.6 golden");
+    assert_eq!(parsed, sample_save());
+
+    let dir = tempdir().expect("temp dir");
+    let path = dir.path().join("save_v16.json");
+    fs::write(&path, golden).expect("write golden");
+    let loaded = load(&path).expect("load save");
+    assert_eq!(loaded, SaveV18::from(SaveV17::from(sample_save())));
+    assert!(loaded.stock_offsets.is_empty());
+}
+
+#[test]
+fn mission_history_roundtrips_through_app_state() {
+    let snapshot = SaveV18::from(SaveV17::from(sample_save()));
+    let state = app_state_from_snapshot(snapshot.clone());
+    let history = &state.mission_history;
+    assert_eq!(history.len(), 3);
+    assert_eq!(history.streak(), Some((Outcome::Failure, 1)));
+    let first = history.legs().next().expect("oldest leg");
+    assert_eq!(first[0].name, "rain_flag");
+    assert_eq!(first[1].outcome, Outcome::Failure);
+    assert_eq!(snapshot_from_app_state(&state), snapshot);
+}
+
+#[test]
+fn empty_history_saves_as_empty_list() {
+    let mut snapshot = SaveV18::from(SaveV17::from(sample_save()));
+    snapshot.mission_history.clear();
+    let state = app_state_from_snapshot(snapshot.clone());
+    assert_eq!(state.mission_history, MissionHistory::default());
+    assert_eq!(snapshot_from_app_state(&state), snapshot);
+}
@@ -0,0 +1,226 @@
+use game::systems::director::Outcome;
+use game::systems::economy::state::RngCursor;
+use game::systems::economy::{
+    BasisBp, CommodityId, EconomyDay, HubId, MoneyCents, PendingPlanting, Pp, RouteId,
+};
+use game::systems::save::{
+    app_state_from_snapshot, load, save, snapshot_from_app_state, verify, BasisSave, CargoItemSave,
+    CargoLotSave, CargoSave, CommoditySave, InventorySlot, MissionHistoryLegSave,
+    MissionResultSave, PriceHistorySave, RouteClosureSave, RouteVisitSave, SaveV18, SchemaVersion,
+    StockOffsetSave, TradePressureSave,
+};
+use std::fs;
+use tempfile::tempdir;
+
+fn sample_save() -> SaveV18 {
+    SaveV18 {
+        econ_version: 7,
+        world_seed: 42,
+        day: EconomyDay(3),
+        last_hub: HubId(2),
+        di: vec![
+            CommoditySave {
+                commodity: CommodityId(1),
+                value: BasisBp(125),
+            },
+            CommoditySave {
+                commodity: CommodityId(2),
+                value: BasisBp(-45),
+            },
+        ],
+        di_overlay_bp: 120,
+        basis: vec![BasisSave {
+            hub: HubId(1),
+            commodity: CommodityId(1),
+            value: BasisBp(15),
+        }],
+        pp: Pp(5_100),
+        rot: 12,
+        debt_cents: MoneyCents(4_200),
+        inventory: vec![InventorySlot {
+            commodity: CommodityId(9),
+            amount: 33,
+        }],
+        wallet_cents: MoneyCents(37_217),
+        cargo: CargoSave {
+            capacity_mass_kg: 2_000,
+            capacity_volume_l: 1_500,
+            items: vec![CargoItemSave {
+                commodity: CommodityId(1),
+                units: 7,
+            }],
+        },
+        pending_planting: vec![PendingPlanting {
+            hub: HubId(1),
+            size: 4,
+            age_days: 2,
+        }],
+        rng_cursors: vec![RngCursor {
+            label: "di".to_string(),
+            draws: 24,
+        }],
+        price_history: vec![
+            PriceHistorySave {
+                hub: HubId(1),
+                commodity: CommodityId(1),
+                samples: vec![BasisBp(110), BasisBp(125), BasisBp(140)],
+            },
+            PriceHistorySave {
+                hub: HubId(1),
+                commodity: CommodityId(2),
+                samples: vec![BasisBp(-30), BasisBp(-45)],
+            },
+        ],
+        route_closures: vec![
+            RouteClosureSave {
+                route: RouteId(1),
+                until_day: EconomyDay(5),
+            },
+            RouteClosureSave {
+                route: RouteId(3),
+                until_day: EconomyDay(4),
+            },
+        ],
+        trade_pressure: vec![
+            TradePressureSave {
+                hub: HubId(1),
+                commodity: CommodityId(1),
+                units: 240,
+                priced: 300,
+            },
+            TradePressureSave {
+                hub: HubId(2),
+                commodity: CommodityId(2),
+                units: -35,
+                priced: 0,
+            },
+        ],
+        visited_links: vec![
+            RouteVisitSave {
+                route: RouteId(1),
+                count: 4,
+            },
+            RouteVisitSave {
+                route: RouteId(3),
+                count: 4,
+            },
+            RouteVisitSave {
+                route: RouteId(6),
+                count: 1,
+            },
+        ],
+        mission_history: vec![
+            MissionHistoryLegSave {
+                missions: vec![
+                    MissionResultSave {
+                        name: "rain_flag".to_string(),
+                        outcome: Outcome::Success,
+                    },
+                    MissionResultSave {
+                        name: "sourvault".to_string(),
+                        outcome: Outcome::Failure,
+                    },
+                ],
+            },
+            MissionHistoryLegSave {
+                missions: Vec::new(),
+            },
+            MissionHistoryLegSave {
+                missions: vec![MissionResultSave {
+                    name: "wayleave".to_string(),
+                    outcome: Outcome::Failure,
+                }],
+            },
+        ],
+        stock_offsets: vec![
+            StockOffsetSave {
+                hub: HubId(1),
+                commodity: CommodityId(1),
+                units: -140,
+            },
+            StockOffsetSave {
+                hub: HubId(2),
+                commodity: CommodityId(2),
+                units: 35,
+            },
+        ],
+        cargo_lots: vec![
+            CargoLotSave {
+                commodity: CommodityId(1),
+                loaded_day: EconomyDay(1),
+                units: 3,
+            },
+            CargoLotSave {
+                commodity: CommodityId(1),
+                loaded_day: EconomyDay(3),
+                units: 4,
+            },
+        ],
+    }
+}
+
+#[test]
+fn save_roundtrip_is_byte_identical() {
+    let dir = tempdir().expect("temp dir");
+    let path = dir.path().join("save_v18.json");
+    let snapshot = sample_save();
+    save(&path, &snapshot).expect("write save");
+    let written = fs::read_to_string(&path).expect("read save");
+    let golden = include_str!("../goldens/save_v18_roundtrip.json");
+    assert_eq!(written, golden);
+    assert_eq!(verify(&path).expect("verify"), SchemaVersion::V18);
+    let loaded = load(&path).expect("load save");
+    assert_eq!(loaded, snapshot);
+}
+
+#[test]
+fn stock_offsets_roundtrip_through_app_state() {
+    let snapshot = sample_save();
+    let state = app_state_from_snapshot(snapshot.clone());
+    assert_eq!(state.econ.stock_offset(HubId(1), CommodityId(1)), -140);
+    assert_eq!(state.econ.stock_offset(HubId(2), CommodityId(2)), 35);
+    assert_eq!(state.econ.stock_offset(HubId(2), CommodityId(1)), 0);
+    assert_eq!(snapshot_from_app_state(&state), snapshot);
+}
+
+#[test]
+fn stock_offsets_are_sorted_and_drop_equilibrium_on_load() {
+    let dir = tempdir().expect("temp dir");
+    let path = dir.path().join("save_v18.json");
+    let mut snapshot = sample_save();
+    snapshot.stock_offsets.reverse();
+    snapshot.stock_offsets.push(StockOffsetSave {
+        hub: HubId(3),
+        commodity: CommodityId(1),
+        units: 0,
+    });
+    save(&path, &snapshot).expect("write save");
+
+    let loaded = load(&path).expect("load save");
+    let state = app_state_from_snapshot(loaded);
+    assert_eq!(snapshot_from_app_state(&state), sample_save());
+}
+
+#[test]
+fn cargo_lots_roundtrip_through_app_state() {
+    let snapshot = sample_save();
+    let state = app_state_from_snapshot(snapshot.clone());
+    let lots = &state.cargo.lots[&CommodityId(1)];
+    assert_eq!(lots.len(), 2);
+    assert_eq!(lots[0].loaded_day, EconomyDay(1));
+    assert_eq!(lots[1].units, 4);
+    assert_eq!(snapshot_from_app_state(&state), snapshot);
+}
+
+#[test]
+fn cargo_lots_beyond_held_units_are_trimmed_on_load() {
+    let mut snapshot = sample_save();
+    snapshot.cargo_lots.reverse();
+    snapshot.cargo_lots.push(CargoLotSave {
+        commodity: CommodityId(5),
+        loaded_day: EconomyDay(2),
+        units: 9,
+    });
+    let state = app_state_from_snapshot(snapshot);
+    assert_eq!(snapshot_from_app_state(&state), sample_save());
+}
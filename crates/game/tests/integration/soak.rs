@@ -0,0 +1,22 @@
+use game::soak::{run_soak, SoakConfig};
+
+#[test]
+fn small_soak_reports_stable_seeds() {
+    let cfg = SoakConfig {
+        seeds: 0..2,
+        legs: 1,
+        ticks: 240,
+        threads: 2,
+    };
+    let report = run_soak(&cfg).expect("soak");
+    assert_eq!(report.seeds.len(), 2);
+    assert_eq!(report.seeds[0].seed, 0);
+    assert_eq!(report.seeds[1].seed, 1);
+    assert!(report.is_stable(), "{report}");
+    assert!(report.unstable_seeds().is_empty());
+    assert_eq!(report.leg_timings().len(), 4);
+    assert!(report.total_commands() > 0);
+    assert!(report.leg_percentile(50) <= report.leg_percentile(95));
+    let summary = report.to_string();
+    assert!(summary.contains("all 2 seeds stable"), "{summary}");
+}
@@ -0,0 +1,78 @@
+use bevy::prelude::*;
+use bevy::time::{Fixed, Time as BevyTime};
+
+use game::scheduling;
+use game::systems::command_queue::CommandQueue;
+use game::systems::director::{DirectorPlugin, DirectorState, LegBoard, LegContext};
+use game::systems::economy::{Pp, RouteId, Weather};
+use game::world::board::{Board, Point, BOARD_SIZE, DEFAULT_CELL_MM};
+use repro::CommandKind;
+
+fn leg_board() -> Board {
+    let mut board = Board::empty(BOARD_SIZE, BOARD_SIZE, DEFAULT_CELL_MM);
+    board.spawns.player.push(Point::new(2, 2));
+    board
+        .spawns
+        .enemy
+        .extend([Point::new(40, 12), Point::new(55, 30), Point::new(20, 61)]);
+    board
+}
+
+fn collect_spawns(board: Board) -> Vec<[i32; 3]> {
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins);
+    scheduling::configure(&mut app);
+    {
+        let mut fixed = app.world_mut().resource_mut::<BevyTime<Fixed>>();
+        *fixed = BevyTime::<Fixed>::from_hz(60.0);
+    }
+    app.init_resource::<CommandQueue>();
+    app.insert_resource(LegContext {
+        world_seed: 0xD7E7_2024_0001_0001,
+        link_id: RouteId(11),
+        day: 3,
+        weather: Weather::Fog,
+        pp: Pp(120),
+        density_per_10k: 5,
+        cadence_per_min: 3,
+        mission_minutes: 8,
+        player_rating: 50,
+        ..Default::default()
+    });
+    app.insert_resource(LegBoard(board));
+    app.add_plugins(DirectorPlugin);
+    app.finish();
+    app.update();
+
+    let mut spawns = Vec::new();
+    for _ in 0..30 {
+        let tick = app.world().resource::<DirectorState>().leg_tick;
+        let world = app.world_mut();
+        world.resource_mut::<CommandQueue>().begin_tick(tick);
+        world.run_schedule(FixedUpdate);
+        for command in world.resource_mut::<CommandQueue>().drain() {
+            if let CommandKind::Spawn(spawn) = command.kind {
+                spawns.push([spawn.x_mm, spawn.y_mm, spawn.z_mm]);
+            }
+        }
+    }
+    spawns
+}
+
+#[test]
+fn spawns_land_on_board_enemy_cells_in_order() {
+    let board = leg_board();
+    let spawns = collect_spawns(board.clone());
+    assert!(
+        spawns.len() > board.spawns.enemy.len(),
+        "expected spawns to cycle"
+    );
+
+    for (n, position) in spawns.iter().enumerate() {
+        let cell = board.spawns.enemy[n % board.spawns.enemy.len()];
+        let (x_mm, z_mm) = board.cell_center_mm(cell);
+        assert_eq!(*position, [x_mm, 0, z_mm], "spawn {n}");
+    }
+
+    assert_eq!(spawns, collect_spawns(board));
+}
@@ -1,19 +1,35 @@
 use game::systems::director::compute_spawn_budget;
-use game::systems::director::config::load_director_cfg;
+use game::systems::director::config::{load_director_cfg, DirectorCfg};
 use game::systems::economy::{Pp, Weather};
+use std::collections::HashMap;
 use std::path::Path;
 
+fn load_cfg() -> DirectorCfg {
+    let cfg_path = Path::new(env!("CARGO_MANIFEST_DIR")).join("../../assets/director/m2.toml");
+    load_director_cfg(cfg_path.to_str().expect("cfg path")).expect("load config")
+}
+
 #[test]
 fn spawn_budget_monotonic_with_pp() {
-    let cfg_path = Path::new(env!("CARGO_MANIFEST_DIR")).join("../../assets/director/m2.toml");
-    let cfg = load_director_cfg(cfg_path.to_str().expect("cfg path")).expect("load config");
+    assert_monotonic(&load_cfg());
+}
+
+#[test]
+fn spawn_budget_monotonic_with_weather_clamps() {
+    let mut cfg = load_cfg();
+    cfg.spawn.weather_clamp =
+        HashMap::from([("Fog".to_string(), (6, 64)), ("Windy".to_string(), (1, 14))]);
+    assert_monotonic(&cfg);
+}
+
+fn assert_monotonic(cfg: &DirectorCfg) {
     let weathers = [Weather::Clear, Weather::Rains, Weather::Fog, Weather::Windy];
 
     for weather in weathers {
         let mut prior = None;
-        for band in 0..=4 {
+        for band in 0..=12 {
             let pp = Pp((band * 100) as u16);
-            let budget = compute_spawn_budget(pp, weather, prior, &cfg);
+            let budget = compute_spawn_budget(pp, weather, prior, cfg);
             if let Some(prev) = prior {
                 assert!(
                     budget.enemies >= prev,
@@ -0,0 +1,80 @@
+use std::fs;
+use std::path::Path;
+use std::time::Instant;
+
+use clap::Parser;
+use game::cli::CliOptions;
+use game::profiling::{profile_path, PROFILED_SETS};
+use game::runtime::record_leg;
+use repro::Command;
+use serde_json::Value;
+use tempfile::tempdir;
+
+fn options(io: Option<&Path>, profile: bool) -> CliOptions {
+    let mut args = vec![
+        "game".to_string(),
+        "--mode".to_string(),
+        "record".to_string(),
+        "--headless".to_string(),
+    ];
+    if let Some(io) = io {
+        args.push("--io".to_string());
+        args.push(io.display().to_string());
+    }
+    if profile {
+        args.push("--profile-ticks".to_string());
+    }
+    CliOptions::try_parse_from(args).expect("cli options")
+}
+
+fn assert_no_profile_commands(plain: &[Command], profiled: &[Command]) {
+    assert_eq!(plain, profiled, "profiling changed the command trace");
+}
+
+#[test]
+fn profiling_leaves_the_command_trace_alone() {
+    let (plain, plain_state) = record_leg(&options(None, false)).expect("plain leg");
+    let (profiled, profiled_state) = record_leg(&options(None, true)).expect("profiled leg");
+    assert!(!plain.is_empty());
+    assert_no_profile_commands(&plain, &profiled);
+    assert_eq!(plain_state.leg_tick, profiled_state.leg_tick);
+}
+
+#[test]
+fn profile_file_covers_every_set() {
+    let dir = tempdir().expect("tempdir");
+    let record_path = dir.path().join("leg.json");
+    let started = Instant::now();
+    game::run_with_options(options(Some(&record_path), true)).expect("record");
+    let elapsed_us = started.elapsed().as_micros() as u64;
+
+    let profile: Value =
+        serde_json::from_slice(&fs::read(profile_path(&record_path)).expect("profile file"))
+            .expect("profile json");
+    assert_eq!(profile["ticks"], 120);
+    let sets = profile["sets"].as_array().expect("sets");
+    let names: Vec<&str> = sets
+        .iter()
+        .map(|set| set["set"].as_str().expect("set name"))
+        .collect();
+    assert_eq!(names, PROFILED_SETS);
+
+    let total: u64 = sets
+        .iter()
+        .map(|set| set["total_us"].as_u64().expect("total"))
+        .sum();
+    let wall = profile["wall_us"].as_u64().expect("wall");
+    // Set spans tile each tick, so they add up to the profiled wall time
+    // less per-set rounding, and never exceed the whole run.
+    assert!(total <= wall, "{total} > {wall}");
+    assert!(wall - total <= sets.len() as u64 * 120, "{total} vs {wall}");
+    assert!(wall <= elapsed_us, "{wall} > {elapsed_us}");
+
+    let repeat = record_path.with_file_name("plain.json");
+    game::run_with_options(options(Some(&repeat), false)).expect("plain record");
+    assert!(!profile_path(&repeat).exists());
+    assert_eq!(
+        fs::read(record_path.with_extension("hash")).expect("hash"),
+        fs::read(repeat.with_extension("hash")).expect("hash")
+    );
+}
@@ -0,0 +1,91 @@
+use std::fs;
+use std::path::PathBuf;
+
+use game::runtime::record_trading_session;
+use game::systems::economy::{CommodityId, HubId};
+use game::systems::trading::engine::{TradeKind, TradeTx};
+use repro::{canonical_json_bytes, hash_record, CommandKind, Record};
+
+const SEED: u64 = 0x7EAD_2024_0000_0001;
+const DAYS: u32 = 4;
+
+fn tx(hub: u16, com: u16, units: u32, kind: TradeKind) -> TradeTx {
+    TradeTx {
+        hub: HubId(hub),
+        com: CommodityId(com),
+        units,
+        kind,
+    }
+}
+
+/// Eight trades over four days, two per day. The ore sale asks for more than
+/// the hold carries and is rejected.
+fn script() -> Vec<TradeTx> {
+    vec![
+        tx(1, 1, 10, TradeKind::Buy),
+        tx(1, 3, 5, TradeKind::Buy),
+        tx(2, 1, 4, TradeKind::Sell),
+        tx(2, 2, 20, TradeKind::Buy),
+        tx(3, 3, 5, TradeKind::Sell),
+        tx(3, 2, 50, TradeKind::Sell),
+        tx(4, 1, 6, TradeKind::Sell),
+        tx(4, 3, 3, TradeKind::Buy),
+    ]
+}
+
+fn meter_values<'a>(record: &'a Record, key: &'a str) -> impl Iterator<Item = (u32, i32)> + 'a {
+    record
+        .commands
+        .iter()
+        .filter_map(move |command| match &command.kind {
+            CommandKind::Meter(meter) if meter.key == key => Some((command.t, meter.value)),
+            _ => None,
+        })
+}
+
+fn golden_path(ext: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("../..")
+        .join(format!("repro/trading/trading_session.{ext}"))
+}
+
+#[test]
+fn trading_session_is_deterministic() {
+    let script = script();
+    let first = record_trading_session(&script, DAYS, SEED).expect("first session");
+    let second = record_trading_session(&script, DAYS, SEED).expect("second session");
+    assert_eq!(first, second);
+    assert_eq!(first.meta.day, DAYS);
+
+    let days: Vec<_> = meter_values(&first, "econ_day").collect();
+    assert_eq!(days, [(0, 1), (1, 2), (2, 3), (3, 4)]);
+    let rejected: Vec<_> = meter_values(&first, "trade_rejected").collect();
+    assert_eq!(rejected, [(2, 1)]);
+    let clicks = meter_values(&first, "ui_click_buy").count()
+        + meter_values(&first, "ui_click_sell").count();
+    assert_eq!(clicks, script.len());
+    assert_eq!(meter_values(&first, "trade_total_cents").count(), 7);
+
+    let other = record_trading_session(&script, DAYS, SEED + 1).expect("other seed");
+    assert_ne!(
+        hash_record(&first).expect("hash"),
+        hash_record(&other).expect("hash")
+    );
+}
+
+#[test]
+fn trading_session_matches_golden() {
+    let record = record_trading_session(&script(), DAYS, SEED).expect("session");
+    let json = String::from_utf8(canonical_json_bytes(&record).expect("canonical json"))
+        .expect("utf-8 record");
+    let hash = hash_record(&record).expect("record hash");
+    if std::env::var_os("UPDATE_TRADING_GOLDENS").is_some() {
+        fs::write(golden_path("json"), &json).expect("write json");
+        fs::write(golden_path("hash"), format!("{hash}\n")).expect("write hash");
+        return;
+    }
+    let golden_json = fs::read_to_string(golden_path("json")).expect("read json");
+    assert_eq!(json, golden_json, "trading session record drifted");
+    let golden_hash = fs::read_to_string(golden_path("hash")).expect("read hash");
+    assert_eq!(hash, golden_hash.trim_end(), "trading session hash drifted");
+}
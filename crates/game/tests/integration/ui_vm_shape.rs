@@ -1,7 +1,9 @@
 use std::path::PathBuf;
 
 use game::systems::economy::rulepack::load_rulepack;
-use game::systems::economy::{BasisBp, CommodityId, EconState, HubId, MoneyCents};
+use game::systems::economy::{
+    compute_price, BasisBp, ClampSide, CommodityId, EconState, HubId, MoneyCents, PricingOverride,
+};
 use game::systems::trading::inventory::Cargo;
 use game::systems::trading::types::{CommodityCatalog, TradingConfig};
 use game::ui::hub_trade::build_view;
@@ -15,7 +17,10 @@ fn install_globals() {
     let catalog_path = asset_path("assets/trading/commodities.toml");
     let catalog = CommodityCatalog::load_from_path(catalog_path.as_path()).expect("catalog");
     CommodityCatalog::install_global(catalog);
-    TradingConfig::install_global(TradingConfig { fee_bp: 75 });
+    TradingConfig::install_global(TradingConfig {
+        fee_bp: 75,
+        ..TradingConfig::default()
+    });
 }
 
 fn load_rulepack_fixture() -> game::systems::economy::Rulepack {
@@ -39,6 +44,7 @@ fn view_contains_expected_rows() {
         capacity_mass_kg: 2_000,
         capacity_volume_l: 1_500,
         items: Default::default(),
+        lots: Default::default(),
     };
 
     let wallet = MoneyCents(12_345);
@@ -71,9 +77,20 @@ fn view_contains_expected_rows() {
 }
 
 #[test]
-fn clamp_flag_trips_at_limits() {
+fn clamp_flag_follows_the_price_computation() {
     install_globals();
-    let rp = load_rulepack_fixture();
+    let mut rp = load_rulepack_fixture();
+    rp.pricing.overrides.insert(
+        CommodityId(2),
+        PricingOverride {
+            min_multiplier_bp: -1_000,
+            max_multiplier_bp: 2_000,
+        },
+    );
+    let cargo = Cargo::default();
+
+    // DI and basis both pinned at their own limits, but their sum sits
+    // exactly on the global multiplier bound: nothing is clamped.
     let mut econ = EconState::default();
     econ.di_bp
         .insert(CommodityId(1), BasisBp(rp.di.absolute_max_bp));
@@ -81,9 +98,34 @@ fn clamp_flag_trips_at_limits() {
         (HubId(1), CommodityId(1)),
         BasisBp(rp.basis.absolute_max_bp),
     );
+    let view = build_view(HubId(1), &econ, &rp, &cargo, MoneyCents::ZERO);
+    assert!(view.commodities.iter().all(|row| row.clamped.is_none()));
+    assert!(!view.clamp_hit);
 
-    let cargo = Cargo::default();
-
+    // Commodity 2 clamps at its override while commodity 1, at the same
+    // drivers, stays inside the global bounds.
+    let mut econ = EconState::default();
+    econ.di_bp.insert(CommodityId(1), BasisBp(2_500));
+    econ.di_bp.insert(CommodityId(2), BasisBp(2_500));
     let view = build_view(HubId(1), &econ, &rp, &cargo, MoneyCents::ZERO);
+    let clamped: Vec<(CommodityId, Option<ClampSide>)> = view
+        .commodities
+        .iter()
+        .map(|row| (row.id, row.clamped))
+        .collect();
+    assert!(clamped.contains(&(CommodityId(1), None)));
+    assert!(clamped.contains(&(CommodityId(2), Some(ClampSide::Max))));
     assert!(view.clamp_hit);
+
+    for row in &view.commodities {
+        let computation = compute_price(
+            CommodityCatalog::global().base_price(row.id),
+            row.di_bp,
+            row.basis_bp,
+            &rp.pricing,
+            rp.pricing.override_for(row.id),
+        );
+        assert_eq!(row.price_cents, computation.price);
+        assert_eq!(row.clamped, computation.clamped);
+    }
 }
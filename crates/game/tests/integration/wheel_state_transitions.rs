@@ -5,7 +5,9 @@ use game::scheduling;
 use game::scheduling::sets;
 use game::systems::command_queue::CommandQueue;
 use game::systems::director::input::{apply_wheel_inputs, WheelInputAction, WheelInputQueue};
-use game::systems::director::pause_wheel::{PauseState, Stance, ToolSlot, WheelState};
+use game::systems::director::pause_wheel::{
+    PauseEvent, PauseState, Stance, ToolSlot, WheelChange, WheelEvent, WheelState,
+};
 use game::systems::director::{DirectorPlugin, DirectorState, LegContext};
 use game::systems::economy::{Pp, RouteId, Weather};
 use repro::Command;
@@ -51,6 +53,8 @@ fn queued_input_updates_states_and_emits_meters() {
     app.init_resource::<WheelState>();
     app.init_resource::<PauseState>();
     app.init_resource::<WheelInputQueue>();
+    app.add_message::<WheelEvent>();
+    app.add_message::<PauseEvent>();
     app.insert_resource(LegContext {
         multiplayer: false,
         ..Default::default()
@@ -97,6 +101,8 @@ fn multiplayer_input_ignores_hard_pause_and_slowmo_requests() {
     app.init_resource::<WheelState>();
     app.init_resource::<PauseState>();
     app.init_resource::<WheelInputQueue>();
+    app.add_message::<WheelEvent>();
+    app.add_message::<PauseEvent>();
     app.insert_resource(LegContext {
         multiplayer: true,
         ..Default::default()
@@ -131,6 +137,8 @@ fn keyboard_input_updates_wheel_state() {
     app.init_resource::<WheelState>();
     app.init_resource::<PauseState>();
     app.init_resource::<WheelInputQueue>();
+    app.add_message::<WheelEvent>();
+    app.add_message::<PauseEvent>();
     app.insert_resource(LegContext {
         multiplayer: false,
         ..Default::default()
@@ -259,3 +267,88 @@ fn hard_pause_freezes_ticks_and_commands() {
     }));
     assert!(app.world().resource::<DirectorState>().leg_tick > paused_tick);
 }
+
+fn wheel_meters(commands: &[Command]) -> Vec<Command> {
+    commands
+        .iter()
+        .filter(|command| {
+            matches!(&command.kind, repro::CommandKind::Meter(meter) if meter.key.starts_with("wheel_"))
+        })
+        .cloned()
+        .collect()
+}
+
+fn drain_messages<M: Message>(app: &mut App) -> Vec<M> {
+    app.world_mut()
+        .resource_mut::<Messages<M>>()
+        .drain()
+        .collect()
+}
+
+#[test]
+fn one_transition_sends_one_event_matching_its_meter() {
+    let mut app = build_director_app_for_pause_tests();
+    let _ = step_director(&mut app);
+    let tick = app.world().resource::<DirectorState>().leg_tick;
+    app.world_mut().resource_mut::<WheelInputQueue>().extend([
+        WheelInputAction::SetStance(Stance::Vault),
+        // Repeats of the current value are not transitions.
+        WheelInputAction::SetStance(Stance::Vault),
+        WheelInputAction::SetOverwatch(false),
+        WheelInputAction::SetHardPause(true),
+        WheelInputAction::SetHardPause(true),
+    ]);
+    let commands = step_director(&mut app);
+
+    let wheel_events = drain_messages::<WheelEvent>(&mut app);
+    assert_eq!(
+        wheel_events,
+        [WheelEvent {
+            tick,
+            change: WheelChange::Stance {
+                old: Stance::Brace,
+                new: Stance::Vault,
+            },
+        }]
+    );
+    let pause_events = drain_messages::<PauseEvent>(&mut app);
+    assert_eq!(
+        pause_events,
+        [PauseEvent {
+            tick,
+            old: false,
+            new: true,
+        }]
+    );
+
+    let (key, value) = wheel_events[0].change.meter();
+    assert_eq!(
+        wheel_meters(&commands),
+        [
+            Command::meter_at(wheel_events[0].tick, key, value),
+            Command::meter_at(
+                pause_events[0].tick,
+                "wheel_hard_pause",
+                i32::from(pause_events[0].new)
+            ),
+        ]
+    );
+}
+
+#[test]
+fn suppressed_multiplayer_actions_send_no_event_or_meter() {
+    let mut app = build_director_app_for_pause_tests();
+    app.world_mut().resource_mut::<LegContext>().multiplayer = true;
+    let _ = step_director(&mut app);
+    app.world_mut().resource_mut::<WheelInputQueue>().extend([
+        WheelInputAction::SetSlowmo(true),
+        WheelInputAction::SetHardPause(true),
+    ]);
+    let commands = step_director(&mut app);
+
+    assert!(wheel_meters(&commands).is_empty());
+    assert!(drain_messages::<WheelEvent>(&mut app).is_empty());
+    assert!(drain_messages::<PauseEvent>(&mut app).is_empty());
+    assert!(!app.world().resource::<WheelState>().slowmo_enabled);
+    assert!(!app.world().resource::<PauseState>().hard_paused_sp);
+}
@@ -0,0 +1,68 @@
+use clap::Parser;
+use game::cli::{seed_from_phrase, CliOptions};
+use game::record_leg;
+use game::systems::director::LegContext;
+
+fn seed_arg(seed: &str) -> Result<u64, clap::Error> {
+    CliOptions::try_parse_from([
+        "game",
+        "--mode",
+        "record",
+        "--headless",
+        "--world-seed",
+        seed,
+    ])
+    .map(|options| options.world_seed())
+}
+
+#[test]
+fn phrases_map_to_stable_seeds() {
+    assert_eq!(seed_from_phrase("bluefox"), seed_from_phrase("bluefox"));
+    assert_ne!(seed_from_phrase("bluefox"), seed_from_phrase("redfox"));
+    assert_ne!(seed_from_phrase("bluefox"), seed_from_phrase("Bluefox"));
+
+    assert_eq!(
+        seed_arg("bluefox").expect("phrase"),
+        seed_from_phrase("bluefox")
+    );
+    assert_eq!(
+        seed_arg(" bluefox ").expect("phrase"),
+        seed_from_phrase("bluefox")
+    );
+}
+
+#[test]
+fn numeric_seeds_are_unchanged() {
+    assert_eq!(seed_arg("42").expect("decimal"), 42);
+    assert_eq!(seed_arg("0x2A").expect("hex"), 42);
+    assert_eq!(
+        seed_arg("0xD7E7202400010001").expect("hex"),
+        0xD7E7_2024_0001_0001
+    );
+    assert!(seed_arg("0xZZ").is_err());
+    assert!(seed_arg("99999999999999999999").is_err());
+    assert!(seed_arg("").is_err());
+}
+
+#[test]
+fn records_store_the_resolved_seed() {
+    let options = CliOptions::try_parse_from([
+        "game",
+        "--mode",
+        "record",
+        "--headless",
+        "--world-seed",
+        "bluefox",
+    ])
+    .expect("cli options");
+    let context = LegContext::builder()
+        .world_seed(options.world_seed())
+        .mission_minutes(1)
+        .build()
+        .expect("context");
+    let (record, _) = record_leg(&options, context, 4).expect("record leg");
+    assert_eq!(
+        record.meta.world_seed,
+        format!("0x{:016X}", seed_from_phrase("bluefox"))
+    );
+}
@@ -0,0 +1,49 @@
+//! Meter keys are emitted thousands of times per leg, so literal keys must
+//! not allocate per command. Needs the `count_allocations` allocator and
+//! lives in its own test binary so no other test allocates alongside it.
+#![cfg(feature = "count_allocations")]
+
+use game::bench::allocation_count;
+use game::systems::command_queue::CommandQueue;
+use repro::{Command, CommandKind, MeterCommand};
+
+const BURST: i32 = 10_000;
+
+fn allocations() -> u64 {
+    allocation_count().expect("count_allocations is enabled")
+}
+
+#[test]
+fn literal_meter_keys_do_not_allocate_per_command() {
+    let mut queue = CommandQueue::authoritative();
+    let before = allocations();
+    for value in 0..BURST {
+        queue.meter("danger_score", value);
+    }
+    let interned = allocations() - before;
+    let drained = queue.drain();
+
+    // The same burst with a freshly allocated key per command, as meter keys
+    // were stored before `MeterKey`.
+    let before = allocations();
+    let mut owned = Vec::new();
+    for value in 0..BURST {
+        owned.push(("danger_score".to_owned(), value));
+    }
+    let owned_keys = allocations() - before;
+
+    assert_eq!(drained.len(), owned.len());
+    assert!(matches!(
+        &drained[0],
+        Command {
+            kind: CommandKind::Meter(MeterCommand { key, value: 0 }),
+            ..
+        } if key == "danger_score"
+    ));
+    assert!(owned_keys >= BURST as u64);
+    // Only the queue's buffer growth remains.
+    assert!(
+        interned * 100 < owned_keys,
+        "{interned} allocations for {BURST} meters (owned keys: {owned_keys})"
+    );
+}
@@ -11,6 +11,7 @@ use game::systems::economy::{
 };
 use game::systems::trading::engine::{TradeKind, TradeResult, TradeTx};
 use game::systems::trading::inventory::Cargo;
+use game::systems::trading::ledger::{LedgerJournal, Wallet, WalletAccess};
 use game::systems::trading::types::{CommodityCatalog, TradingConfig};
 use game::ui::hub_trade::{build_view, persist_on_exit, HubTradeActions, HubTradeUiState};
 use repro::CommandKind;
@@ -49,7 +50,10 @@ fn install_globals() {
     let catalog_path = asset_path("assets/trading/commodities.toml");
     let catalog = CommodityCatalog::load_from_path(catalog_path.as_path()).expect("catalog");
     CommodityCatalog::install_global(catalog);
-    TradingConfig::install_global(TradingConfig { fee_bp: 75 });
+    TradingConfig::install_global(TradingConfig {
+        fee_bp: 75,
+        ..TradingConfig::default()
+    });
 }
 
 fn load_rulepack_fixture() -> game::systems::economy::Rulepack {
@@ -59,7 +63,8 @@ fn load_rulepack_fixture() -> game::systems::economy::Rulepack {
 
 fn scripted_snapshot(seed: u64, rp: &game::systems::economy::Rulepack) -> (String, String) {
     let mut app_state = seeded_app_state(seed);
-    let starting_wallet = app_state.wallet.as_i64();
+    let starting_wallet = app_state.wallet.balance().as_i64();
+    let mut journal = LedgerJournal::default();
     let mut queue = CommandQueue::default();
     queue.begin_tick(0);
     let mut ui_state = HubTradeUiState::default();
@@ -73,9 +78,9 @@ fn scripted_snapshot(seed: u64, rp: &game::systems::economy::Rulepack) -> (Strin
         let result = HubTradeActions::buy(
             &mut queue,
             *tx,
-            &app_state.econ,
+            &mut app_state.econ,
             &mut app_state.cargo,
-            &mut app_state.wallet,
+            &mut WalletAccess::new(&mut app_state.wallet, &mut journal, 0),
             rp,
         )
         .expect("buy commodity");
@@ -90,6 +95,7 @@ fn scripted_snapshot(seed: u64, rp: &game::systems::economy::Rulepack) -> (Strin
         &mut app_state.econ,
         EconStepScope::GlobalAndHub,
     );
+    delta.meter_price_clamps(&mut queue);
     let clamp_hit = !delta.clamps_hit.is_empty();
 
     let sell_tx = TradeTx {
@@ -101,21 +107,32 @@ fn scripted_snapshot(seed: u64, rp: &game::systems::economy::Rulepack) -> (Strin
     let sell_result = HubTradeActions::sell(
         &mut queue,
         sell_tx,
-        &app_state.econ,
+        &mut app_state.econ,
         &mut app_state.cargo,
-        &mut app_state.wallet,
+        &mut WalletAccess::new(&mut app_state.wallet, &mut journal, 0),
         rp,
     )
     .expect("sell commodity");
 
-    let view = build_view(HUB, &app_state.econ, rp, &app_state.cargo, app_state.wallet);
+    let view = build_view(
+        HUB,
+        &app_state.econ,
+        rp,
+        &app_state.cargo,
+        app_state.wallet.balance(),
+    );
     ui_state.remember(view);
     persist_on_exit(&ui_state, &mut app_state);
 
-    let wallet_delta = app_state.wallet.as_i64() - starting_wallet;
+    let wallet_delta = app_state.wallet.balance().as_i64() - starting_wallet;
     let identity_rhs = -buy_subtotals + sell_result.subtotal.as_i64()
         - (buy_fees + sell_result.fee_cents.as_i64());
     assert_eq!(wallet_delta, identity_rhs, "wallet identity must hold");
+    assert_eq!(
+        journal.net(),
+        wallet_delta,
+        "journal must cover every trade"
+    );
 
     let meters = meters_from_queue(&mut queue);
 
@@ -125,7 +142,7 @@ fn scripted_snapshot(seed: u64, rp: &game::systems::economy::Rulepack) -> (Strin
         hub: HUB.0,
         day: app_state.econ.day.0,
         clamp_hit: clamp_hit as u8,
-        wallet_cents: app_state.wallet.as_i64(),
+        wallet_cents: app_state.wallet.balance().as_i64(),
         cargo_units: cargo_units(&app_state.cargo),
         di_bp: basis_list(&app_state.econ.di_bp),
         basis_bp: basis_for_hub(&app_state.econ.basis_bp),
@@ -191,12 +208,14 @@ fn seeded_app_state(seed: u64) -> AppState {
             capacity_mass_kg: 600,
             capacity_volume_l: 400,
             items: HashMap::from([(CommodityId(2), 1 + seed as u32)]),
+            ..Default::default()
         },
         rng_cursors: vec![RngCursor {
             label: "di".to_string(),
             draws: 12 + seed as u32,
         }],
-        wallet: MoneyCents(200_000 + (seed as i64) * 1_000),
+        wallet: Wallet::new(MoneyCents(200_000 + (seed as i64) * 1_000)),
+        ..Default::default()
     }
 }
 
@@ -0,0 +1,236 @@
+//! On-disk layouts of a record's commands.
+//!
+//! Schema 1 writes one object per command, each carrying its own tick:
+//!
+//! ```text
+//! "commands": [{"t": 5, "Meter": {..}}, {"t": 5, "Spawn": {..}}, ..]
+//! ```
+//!
+//! Schema 2 writes one object per tick that emitted anything, ticks strictly
+//! increasing:
+//!
+//! ```text
+//! "commands": [{"t": 5, "cmds": [{"Meter": {..}}, {"Spawn": {..}}]}, ..]
+//! ```
+//!
+//! Both read back into the same flat `Vec<Command>`. The hash covers the
+//! layout the meta names, so the same commands hash differently under each.
+
+use std::fmt;
+
+use serde::de::Error as _;
+use serde::ser::{Error as _, SerializeSeq};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::Value;
+
+use crate::{Annotation, Command, CommandKind, InputEvent, Record, RecordMeta};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[serde(into = "u32", try_from = "u32")]
+pub enum CommandSchema {
+    /// Schema 1: every command repeats its tick.
+    #[default]
+    PerCommand,
+    /// Schema 2: commands grouped under their tick.
+    ByTick,
+}
+
+impl CommandSchema {
+    pub fn number(self) -> u32 {
+        match self {
+            Self::PerCommand => 1,
+            Self::ByTick => 2,
+        }
+    }
+
+    pub fn is_per_command(&self) -> bool {
+        *self == Self::PerCommand
+    }
+}
+
+impl From<CommandSchema> for u32 {
+    fn from(schema: CommandSchema) -> Self {
+        schema.number()
+    }
+}
+
+impl TryFrom<u32> for CommandSchema {
+    type Error = String;
+
+    fn try_from(number: u32) -> Result<Self, Self::Error> {
+        match number {
+            1 => Ok(Self::PerCommand),
+            2 => Ok(Self::ByTick),
+            other => Err(format!("unknown command schema {other} (expected 1 or 2)")),
+        }
+    }
+}
+
+impl fmt::Display for CommandSchema {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.number())
+    }
+}
+
+/// One tick's commands, borrowed from a record in emission order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TickCommands<'a> {
+    pub t: u32,
+    pub commands: &'a [Command],
+}
+
+impl Record {
+    /// Runs of consecutive commands sharing a tick, without copying them.
+    /// Ticks strictly increase when the record came from a recorder or a
+    /// schema 2 file.
+    pub fn ticks(&self) -> impl Iterator<Item = TickCommands<'_>> {
+        self.commands
+            .chunk_by(|a, b| a.t == b.t)
+            .map(|commands| TickCommands {
+                t: commands[0].t,
+                commands,
+            })
+    }
+
+    pub(crate) fn commands_view(&self) -> CommandsView<'_> {
+        CommandsView {
+            record: self,
+            schema: self.meta.command_schema,
+        }
+    }
+}
+
+/// `commands` as written under `schema`.
+pub(crate) struct CommandsView<'a> {
+    record: &'a Record,
+    schema: CommandSchema,
+}
+
+impl Serialize for CommandsView<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self.schema {
+            CommandSchema::PerCommand => self.record.commands.serialize(serializer),
+            CommandSchema::ByTick => {
+                let mut seq = serializer.serialize_seq(None)?;
+                let mut previous: Option<u32> = None;
+                for tick in self.record.ticks() {
+                    if previous.is_some_and(|previous| tick.t <= previous) {
+                        return Err(S::Error::custom(format!(
+                            "tick {} follows tick {}; schema 2 needs tick-ordered commands",
+                            tick.t,
+                            previous.unwrap_or_default()
+                        )));
+                    }
+                    previous = Some(tick.t);
+                    seq.serialize_element(&TickGroupView(tick))?;
+                }
+                seq.end()
+            }
+        }
+    }
+}
+
+struct TickGroupView<'a>(TickCommands<'a>);
+
+impl Serialize for TickGroupView<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeMap;
+
+        struct Kinds<'a>(&'a [Command]);
+
+        impl Serialize for Kinds<'_> {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.collect_seq(self.0.iter().map(|command| &command.kind))
+            }
+        }
+
+        let mut map = serializer.serialize_map(Some(2))?;
+        map.serialize_entry("t", &self.0.t)?;
+        map.serialize_entry("cmds", &Kinds(self.0.commands))?;
+        map.end()
+    }
+}
+
+#[derive(Deserialize)]
+struct TickGroup {
+    t: u32,
+    cmds: Vec<CommandKind>,
+}
+
+/// Flattens schema 2 tick groups, rejecting ticks that do not strictly
+/// increase.
+fn commands_by_tick(groups: Vec<Value>) -> Result<Vec<Command>, String> {
+    let mut commands = Vec::with_capacity(groups.len());
+    let mut previous: Option<u32> = None;
+    for (idx, group) in groups.into_iter().enumerate() {
+        let group: TickGroup =
+            serde_json::from_value(group).map_err(|err| format!("commands[{idx}]: {err}"))?;
+        if let Some(previous) = previous.filter(|&previous| group.t <= previous) {
+            let message = tick_order_message(group.t, previous);
+            return Err(format!("commands[{idx}].t: {message}"));
+        }
+        previous = Some(group.t);
+        commands.extend(
+            group
+                .cmds
+                .into_iter()
+                .map(|kind| Command { t: group.t, kind }),
+        );
+    }
+    Ok(commands)
+}
+
+pub(crate) fn tick_order_message(t: u32, previous: u32) -> String {
+    format!("tick {t} does not follow tick {previous}; ticks must strictly increase")
+}
+
+impl Serialize for Record {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        #[derive(Serialize)]
+        struct RecordView<'a> {
+            meta: &'a RecordMeta,
+            commands: CommandsView<'a>,
+            inputs: &'a [InputEvent],
+            #[serde(skip_serializing_if = "<[Annotation]>::is_empty")]
+            annotations: &'a [Annotation],
+        }
+        RecordView {
+            meta: &self.meta,
+            commands: self.commands_view(),
+            inputs: &self.inputs,
+            annotations: &self.annotations,
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Record {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        struct RecordWire {
+            meta: RecordMeta,
+            #[serde(default)]
+            commands: Vec<Value>,
+            #[serde(default)]
+            inputs: Vec<InputEvent>,
+            #[serde(default)]
+            annotations: Vec<Annotation>,
+        }
+        let wire = RecordWire::deserialize(deserializer)?;
+        let commands = match wire.meta.command_schema {
+            CommandSchema::PerCommand => wire
+                .commands
+                .into_iter()
+                .map(serde_json::from_value)
+                .collect::<Result<_, _>>()
+                .map_err(D::Error::custom)?,
+            CommandSchema::ByTick => commands_by_tick(wire.commands).map_err(D::Error::custom)?,
+        };
+        Ok(Self {
+            meta: wire.meta,
+            commands,
+            inputs: wire.inputs,
+            annotations: wire.annotations,
+        })
+    }
+}
@@ -0,0 +1,46 @@
+//! What a record actually exercises.
+//!
+//! A golden that replays cleanly may still never spawn anything or resolve a
+//! mission; this summary makes that visible so suites can assert on it.
+
+use std::collections::BTreeSet;
+
+use crate::{CommandKind, Record};
+
+/// Command counts, distinct meter keys and tick extent of one record.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Coverage {
+    pub spawns: usize,
+    pub meters: usize,
+    pub meter_keys: BTreeSet<String>,
+    /// Ticks of the first and last command, if there are any.
+    pub first_tick: Option<u32>,
+    pub last_tick: Option<u32>,
+}
+
+impl Coverage {
+    pub fn has_spawn(&self) -> bool {
+        self.spawns > 0
+    }
+
+    pub fn commands(&self) -> usize {
+        self.spawns + self.meters
+    }
+}
+
+/// Counts every command in `record` by kind.
+pub fn summarize(record: &Record) -> Coverage {
+    let mut coverage = Coverage::default();
+    for command in &record.commands {
+        match &command.kind {
+            CommandKind::Spawn(_) => coverage.spawns += 1,
+            CommandKind::Meter(meter) => {
+                coverage.meters += 1;
+                coverage.meter_keys.insert(meter.key.to_string());
+            }
+        }
+        coverage.first_tick = Some(coverage.first_tick.map_or(command.t, |t| t.min(command.t)));
+        coverage.last_tick = Some(coverage.last_tick.map_or(command.t, |t| t.max(command.t)));
+    }
+    coverage
+}
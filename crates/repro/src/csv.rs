@@ -0,0 +1,68 @@
+//! Spreadsheet export of a record's command timeline.
+//!
+//! One row per command under a fixed header; columns that do not apply to a
+//! variant stay empty. Output depends only on the command list, so the same
+//! record always exports the same bytes. Export only; there is no reader.
+
+use std::io::{self, Write};
+
+use crate::{Command, CommandKind, Record};
+
+/// Column layout shared by every export.
+pub const CSV_HEADER: &str = "t,kind,name,value,x_mm,y_mm,z_mm";
+
+impl Record {
+    /// Writes every command as a CSV row. Spawns put their kind in `name`
+    /// and fill the position columns; meters fill `name` and `value`.
+    pub fn to_csv<W: Write>(&self, writer: W) -> io::Result<()> {
+        write_rows(writer, self.commands.iter())
+    }
+
+    /// Like [`Record::to_csv`], keeping only meter commands.
+    pub fn meters_to_csv<W: Write>(&self, writer: W) -> io::Result<()> {
+        write_rows(
+            writer,
+            self.commands
+                .iter()
+                .filter(|command| matches!(command.kind, CommandKind::Meter(_))),
+        )
+    }
+}
+
+fn write_rows<'a, W: Write>(
+    mut writer: W,
+    commands: impl Iterator<Item = &'a Command>,
+) -> io::Result<()> {
+    writeln!(writer, "{CSV_HEADER}")?;
+    for command in commands {
+        match &command.kind {
+            CommandKind::Meter(meter) => writeln!(
+                writer,
+                "{},Meter,{},{},,,",
+                command.t,
+                quote(&meter.key),
+                meter.value
+            )?,
+            CommandKind::Spawn(spawn) => writeln!(
+                writer,
+                "{},Spawn,{},,{},{},{}",
+                command.t,
+                quote(&spawn.kind),
+                spawn.x_mm,
+                spawn.y_mm,
+                spawn.z_mm
+            )?,
+        }
+    }
+    writer.flush()
+}
+
+/// Quotes a field when it holds a delimiter, quote or line break, doubling
+/// any embedded quotes.
+fn quote(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_owned()
+    }
+}
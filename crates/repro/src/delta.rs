@@ -0,0 +1,183 @@
+//! Compact record encoding that stores, per tick, only the meters whose value
+//! changed since they were last emitted plus the full spawn list.
+//!
+//! Each tick keeps a slot layout (meter key or spawn, in emission order) so
+//! the canonical command list can be rebuilt exactly. The layout is omitted
+//! when it matches the previous tick's, which is the common case for
+//! director output.
+
+use std::collections::HashMap;
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    hash_record, Annotation, CanonicalJsonError, Command, CommandKind, InputEvent, MeterCommand,
+    MeterKey, Record, RecordMeta, SpawnCommand,
+};
+
+/// Delta encoding error.
+#[derive(Debug)]
+pub enum DeltaError {
+    /// A meter slot has no recorded change and no earlier value to inherit.
+    MissingBaseline {
+        t: u32,
+        key: String,
+    },
+    /// A change or spawn refers to a slot the tick layout does not have.
+    SlotMismatch {
+        t: u32,
+    },
+    Hash(CanonicalJsonError),
+}
+
+impl fmt::Display for DeltaError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingBaseline { t, key } => {
+                write!(f, "tick {t}: meter {key} has no earlier value")
+            }
+            Self::SlotMismatch { t } => write!(f, "tick {t}: changes do not match the layout"),
+            Self::Hash(err) => write!(f, "{err}"),
+        }
+    }
+}
+
+impl std::error::Error for DeltaError {}
+
+impl From<CanonicalJsonError> for DeltaError {
+    fn from(value: CanonicalJsonError) -> Self {
+        Self::Hash(value)
+    }
+}
+
+/// One run of commands sharing a tick.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DeltaTick {
+    pub t: u32,
+    /// Emission order: `Some(key)` for a meter, `None` for the next spawn.
+    /// Absent when identical to the previous tick's layout.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub layout: Option<Vec<Option<MeterKey>>>,
+    /// `(slot, value)` for meters whose value differs from the last value
+    /// emitted under the same key.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub changed: Vec<(u32, i32)>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub spawns: Vec<SpawnCommand>,
+}
+
+/// Delta-encoded counterpart of [`Record`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub struct DeltaRecord {
+    pub meta: RecordMeta,
+    #[serde(default)]
+    pub ticks: Vec<DeltaTick>,
+    #[serde(default)]
+    pub inputs: Vec<InputEvent>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub annotations: Vec<Annotation>,
+}
+
+impl DeltaRecord {
+    pub fn from_record(record: &Record) -> Self {
+        let mut ticks: Vec<DeltaTick> = Vec::new();
+        let mut last_values: HashMap<&str, i32> = HashMap::new();
+        let mut last_layout: Option<Vec<Option<MeterKey>>> = None;
+
+        for run in record.commands.chunk_by(|a, b| a.t == b.t) {
+            let mut layout = Vec::with_capacity(run.len());
+            let mut changed = Vec::new();
+            let mut spawns = Vec::new();
+            for (slot, command) in run.iter().enumerate() {
+                match &command.kind {
+                    CommandKind::Meter(meter) => {
+                        layout.push(Some(meter.key.clone()));
+                        let previous = last_values.insert(&meter.key, meter.value);
+                        if previous != Some(meter.value) {
+                            changed.push((slot as u32, meter.value));
+                        }
+                    }
+                    CommandKind::Spawn(spawn) => {
+                        layout.push(None);
+                        spawns.push(spawn.clone());
+                    }
+                }
+            }
+            let layout = if last_layout.as_ref() == Some(&layout) {
+                None
+            } else {
+                last_layout = Some(layout.clone());
+                Some(layout)
+            };
+            ticks.push(DeltaTick {
+                t: run[0].t,
+                layout,
+                changed,
+                spawns,
+            });
+        }
+
+        Self {
+            meta: record.meta.clone(),
+            ticks,
+            inputs: record.inputs.clone(),
+            annotations: record.annotations.clone(),
+        }
+    }
+
+    /// Rebuilds the canonical record, command for command.
+    pub fn to_record(&self) -> Result<Record, DeltaError> {
+        let mut commands = Vec::new();
+        let mut last_values: HashMap<MeterKey, i32> = HashMap::new();
+        let mut layout: &[Option<MeterKey>] = &[];
+
+        for tick in &self.ticks {
+            let t = tick.t;
+            if let Some(next) = &tick.layout {
+                layout = next;
+            }
+            let mut changed = tick.changed.iter().peekable();
+            let mut spawns = tick.spawns.iter();
+            for (slot, entry) in layout.iter().enumerate() {
+                let kind = match entry {
+                    Some(key) => {
+                        let value = match changed.next_if(|(at, _)| *at as usize == slot) {
+                            Some(&(_, value)) => value,
+                            None => *last_values.get(key).ok_or_else(|| {
+                                DeltaError::MissingBaseline {
+                                    t,
+                                    key: key.to_string(),
+                                }
+                            })?,
+                        };
+                        last_values.insert(key.clone(), value);
+                        CommandKind::Meter(MeterCommand {
+                            key: key.clone(),
+                            value,
+                        })
+                    }
+                    None => CommandKind::Spawn(
+                        spawns.next().ok_or(DeltaError::SlotMismatch { t })?.clone(),
+                    ),
+                };
+                commands.push(Command { t, kind });
+            }
+            if changed.next().is_some() || spawns.next().is_some() {
+                return Err(DeltaError::SlotMismatch { t });
+            }
+        }
+
+        Ok(Record {
+            meta: self.meta.clone(),
+            commands,
+            inputs: self.inputs.clone(),
+            annotations: self.annotations.clone(),
+        })
+    }
+
+    /// Same digest as [`hash_record`] on the rebuilt record.
+    pub fn hash(&self) -> Result<String, DeltaError> {
+        Ok(hash_record(&self.to_record()?)?)
+    }
+}
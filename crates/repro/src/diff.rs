@@ -0,0 +1,36 @@
+//! Aggregate comparison of two records' meters.
+//!
+//! Hash checks stop at the first divergent command; these helpers instead
+//! total every meter key so the net effect of a change (more danger, fewer
+//! spawns) can be read off directly.
+
+use std::collections::BTreeMap;
+
+use crate::{CommandKind, Record};
+
+/// Sum of every meter value in `record`, by key.
+pub fn aggregate_meters(record: &Record) -> BTreeMap<String, i64> {
+    let mut totals = BTreeMap::new();
+    for command in &record.commands {
+        if let CommandKind::Meter(meter) = &command.kind {
+            *totals.entry(meter.key.to_string()).or_insert(0i64) += i64::from(meter.value);
+        }
+    }
+    totals
+}
+
+/// Meter keys whose totals differ between `a` and `b`, with `(a, b)` sums.
+/// A key missing from one record counts as zero there.
+pub fn compare_aggregates(a: &Record, b: &Record) -> BTreeMap<String, (i64, i64)> {
+    let a = aggregate_meters(a);
+    let b = aggregate_meters(b);
+    let mut diffs = BTreeMap::new();
+    for key in a.keys().chain(b.keys()) {
+        let left = a.get(key).copied().unwrap_or(0);
+        let right = b.get(key).copied().unwrap_or(0);
+        if left != right {
+            diffs.insert(key.clone(), (left, right));
+        }
+    }
+    diffs
+}
@@ -0,0 +1,175 @@
+//! Deterministic single-step mutations of record JSON, for checking that
+//! the parser rejects malformed records with an error instead of panicking.
+//!
+//! [`mutate`] serializes a record and applies exactly one change chosen by
+//! the seed: drop an object member, swap a value for one of another JSON
+//! type, or swap two elements of an array. The same record and seed always
+//! give the same JSON.
+
+use serde_json::{Map, Value};
+
+use crate::Record;
+
+/// One mutation [`mutate`] can apply.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mutation {
+    /// Removes a member from an object.
+    DropField,
+    /// Replaces a value with one of a different JSON type.
+    FlipType,
+    /// Swaps two elements of an array with at least two.
+    ReorderArray,
+}
+
+const MUTATIONS: [Mutation; 3] = [
+    Mutation::DropField,
+    Mutation::FlipType,
+    Mutation::ReorderArray,
+];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Step {
+    Key(String),
+    Index(usize),
+}
+
+/// `record` as JSON with one mutation picked by `seed`.
+///
+/// Panics if `record` does not serialize, which only happens for a schema 2
+/// record whose commands are out of tick order.
+pub fn mutate(record: &Record, seed: u64) -> Value {
+    mutate_with(record, seed).0
+}
+
+/// Like [`mutate`], also returning which mutation was applied. A mutation
+/// with nowhere to apply falls back to [`Mutation::FlipType`].
+pub fn mutate_with(record: &Record, seed: u64) -> (Value, Mutation) {
+    let mut value = serde_json::to_value(record).expect("record serializes");
+    crate::canonicalize_value(&mut value);
+    let mut rng = SplitMix64(seed);
+    let mut paths = Vec::new();
+    collect_paths(&value, &mut Vec::new(), &mut paths);
+
+    let wanted = MUTATIONS[rng.below(MUTATIONS.len())];
+    let candidates: Vec<&Vec<Step>> = paths
+        .iter()
+        .filter(|path| applies(&value, path, wanted))
+        .collect();
+    let (mutation, candidates) = if candidates.is_empty() {
+        let fallback = paths.iter().filter(|path| !path.is_empty()).collect();
+        (Mutation::FlipType, fallback)
+    } else {
+        (wanted, candidates)
+    };
+    let path = candidates[rng.below(candidates.len())].clone();
+    apply(&mut value, &path, mutation, &mut rng);
+    (value, mutation)
+}
+
+/// Every node below `value`, parents before children, in key and index
+/// order.
+fn collect_paths(value: &Value, prefix: &mut Vec<Step>, out: &mut Vec<Vec<Step>>) {
+    out.push(prefix.clone());
+    match value {
+        Value::Object(map) => {
+            for (key, child) in map {
+                prefix.push(Step::Key(key.clone()));
+                collect_paths(child, prefix, out);
+                prefix.pop();
+            }
+        }
+        Value::Array(items) => {
+            for (idx, child) in items.iter().enumerate() {
+                prefix.push(Step::Index(idx));
+                collect_paths(child, prefix, out);
+                prefix.pop();
+            }
+        }
+        _ => {}
+    }
+}
+
+fn applies(root: &Value, path: &[Step], mutation: Mutation) -> bool {
+    match mutation {
+        Mutation::DropField => matches!(path.last(), Some(Step::Key(_))),
+        Mutation::FlipType => !path.is_empty(),
+        Mutation::ReorderArray => {
+            matches!(node(root, path), Some(Value::Array(items)) if items.len() >= 2)
+        }
+    }
+}
+
+fn node<'a>(root: &'a Value, path: &[Step]) -> Option<&'a Value> {
+    path.iter().try_fold(root, |value, step| match step {
+        Step::Key(key) => value.get(key),
+        Step::Index(idx) => value.get(idx),
+    })
+}
+
+fn node_mut<'a>(root: &'a mut Value, path: &[Step]) -> Option<&'a mut Value> {
+    path.iter().try_fold(root, |value, step| match step {
+        Step::Key(key) => value.get_mut(key),
+        Step::Index(idx) => value.get_mut(idx),
+    })
+}
+
+fn apply(root: &mut Value, path: &[Step], mutation: Mutation, rng: &mut SplitMix64) {
+    match mutation {
+        Mutation::DropField => {
+            let Some((Step::Key(key), parent)) = path.split_last() else {
+                return;
+            };
+            if let Some(Value::Object(map)) = node_mut(root, parent) {
+                map.remove(key);
+            }
+        }
+        Mutation::FlipType => {
+            if let Some(value) = node_mut(root, path) {
+                *value = flipped(value);
+            }
+        }
+        Mutation::ReorderArray => {
+            if let Some(Value::Array(items)) = node_mut(root, path) {
+                let first = rng.below(items.len());
+                let second = (first + 1 + rng.below(items.len() - 1)) % items.len();
+                items.swap(first, second);
+            }
+        }
+    }
+}
+
+/// `value` recast as another JSON type, keeping what content it can.
+fn flipped(value: &Value) -> Value {
+    match value {
+        Value::Null => Value::Bool(false),
+        Value::Bool(flag) => Value::from(u8::from(*flag)),
+        Value::Number(number) => Value::String(number.to_string()),
+        Value::String(text) => Value::from(text.len()),
+        Value::Array(items) => Value::Object(
+            items
+                .iter()
+                .enumerate()
+                .map(|(idx, item)| (idx.to_string(), item.clone()))
+                .collect::<Map<_, _>>(),
+        ),
+        Value::Object(map) => Value::Array(map.values().cloned().collect()),
+    }
+}
+
+/// SplitMix64: small, seedable and stable across platforms and releases.
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform in `0..bound`; `bound` must be non-zero.
+    fn below(&mut self, bound: usize) -> usize {
+        ((u128::from(self.next()) * bound as u128) >> 64) as usize
+    }
+}
@@ -1,5 +1,18 @@
-use std::collections::BTreeMap;
+mod command_schema;
+pub mod coverage;
+pub mod csv;
+pub mod delta;
+pub mod diff;
+pub mod fuzz;
+mod meter_key;
+pub mod strict;
+
+pub use command_schema::{CommandSchema, TickCommands};
+pub use meter_key::MeterKey;
+
+use std::collections::{BTreeMap, BTreeSet};
 use std::fmt;
+use std::ops::RangeInclusive;
 
 use blake3::Hasher;
 use serde::de::DeserializeOwned;
@@ -89,11 +102,11 @@ impl Command {
         }
     }
 
-    pub fn meter_at(t: u32, key: &str, value: i32) -> Self {
+    pub fn meter_at(t: u32, key: impl Into<MeterKey>, value: i32) -> Self {
         Self {
             t,
             kind: CommandKind::Meter(MeterCommand {
-                key: key.to_owned(),
+                key: key.into(),
                 value,
             }),
         }
@@ -116,7 +129,7 @@ pub struct SpawnCommand {
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct MeterCommand {
-    pub key: String,
+    pub key: MeterKey,
     pub value: i32,
 }
 
@@ -127,10 +140,7 @@ impl Serialize for Command {
     {
         let mut map = serializer.serialize_map(Some(2))?;
         map.serialize_entry("t", &self.t)?;
-        match &self.kind {
-            CommandKind::Spawn(cmd) => map.serialize_entry("Spawn", cmd)?,
-            CommandKind::Meter(cmd) => map.serialize_entry("Meter", cmd)?,
-        }
+        self.kind.serialize_entry(&mut map)?;
         map.end()
     }
 }
@@ -147,30 +157,7 @@ impl<'de> Deserialize<'de> for Command {
                     .remove("t")
                     .ok_or_else(|| serde::de::Error::custom("missing command tick"))?;
                 let t: u32 = serde_json::from_value(t_value).map_err(serde::de::Error::custom)?;
-
-                if map.len() != 1 {
-                    return Err(serde::de::Error::custom("expected single command variant"));
-                }
-
-                let (key, value) = map.into_iter().next().unwrap();
-                let kind = match key.as_str() {
-                    "Spawn" => {
-                        let cmd: SpawnCommand =
-                            serde_json::from_value(value).map_err(serde::de::Error::custom)?;
-                        CommandKind::Spawn(cmd)
-                    }
-                    "Meter" => {
-                        let cmd: MeterCommand =
-                            serde_json::from_value(value).map_err(serde::de::Error::custom)?;
-                        CommandKind::Meter(cmd)
-                    }
-                    other => {
-                        return Err(serde::de::Error::custom(format!(
-                            "unknown command type: {other}"
-                        )))
-                    }
-                };
-
+                let kind = CommandKind::from_map(map).map_err(serde::de::Error::custom)?;
                 Ok(Command { t, kind })
             }
             _ => Err(serde::de::Error::custom("expected object for command")),
@@ -178,6 +165,57 @@ impl<'de> Deserialize<'de> for Command {
     }
 }
 
+impl CommandKind {
+    fn serialize_entry<M: SerializeMap>(&self, map: &mut M) -> Result<(), M::Error> {
+        match self {
+            Self::Spawn(cmd) => map.serialize_entry("Spawn", cmd),
+            Self::Meter(cmd) => map.serialize_entry("Meter", cmd),
+        }
+    }
+
+    /// Parses the single `{"Spawn": ..}` or `{"Meter": ..}` entry left once
+    /// any tick has been taken out.
+    fn from_map(map: serde_json::Map<String, Value>) -> Result<Self, String> {
+        if map.len() != 1 {
+            return Err("expected single command variant".to_owned());
+        }
+        let (key, value) = map.into_iter().next().unwrap();
+        match key.as_str() {
+            "Spawn" => serde_json::from_value(value)
+                .map(CommandKind::Spawn)
+                .map_err(|err| err.to_string()),
+            "Meter" => serde_json::from_value(value)
+                .map(CommandKind::Meter)
+                .map_err(|err| err.to_string()),
+            other => Err(format!("unknown command type: {other}")),
+        }
+    }
+}
+
+/// A command without its tick, as written inside a schema 2 tick group.
+impl Serialize for CommandKind {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut map = serializer.serialize_map(Some(1))?;
+        self.serialize_entry(&mut map)?;
+        map.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for CommandKind {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        match Value::deserialize(deserializer)? {
+            Value::Object(map) => Self::from_map(map).map_err(serde::de::Error::custom),
+            _ => Err(serde::de::Error::custom("expected object for command")),
+        }
+    }
+}
+
 /// Player input captured alongside authoritative commands (not hashed).
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
 pub struct InputEvent {
@@ -185,6 +223,33 @@ pub struct InputEvent {
     pub input: String,
 }
 
+/// A named marker on a tick, for finding a moment in a record later (not
+/// hashed).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub struct Annotation {
+    pub t: u32,
+    pub label: String,
+    #[serde(default)]
+    pub note: String,
+}
+
+/// Record schema written by current recorders. Schema 2 added the asset
+/// content hashes to [`RecordMeta`]; schema 3 added `fixed_dt_ns`. How the
+/// commands themselves are laid out is versioned separately, by
+/// [`CommandSchema`].
+pub const RECORD_SCHEMA: u32 = 3;
+
+const NANOS_PER_SEC: u64 = 1_000_000_000;
+
+/// `fixed_dt` seconds as whole nanoseconds, rounded to the nearest. Zero for
+/// non-finite or non-positive steps.
+pub fn fixed_dt_nanos(fixed_dt: f64) -> u64 {
+    if !fixed_dt.is_finite() || fixed_dt <= 0.0 {
+        return 0;
+    }
+    (fixed_dt * NANOS_PER_SEC as f64).round() as u64
+}
+
 /// Metadata recorded for a deterministic leg.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
 pub struct RecordMeta {
@@ -208,6 +273,52 @@ pub struct RecordMeta {
     pub player_rating: u8,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub prior_danger_score: Option<i32>,
+    /// BLAKE3 of the rulepack, director config and commodity catalog files
+    /// the leg ran against. Absent on schema 1 records.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rulepack_hash: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub director_cfg_hash: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub commodities_hash: Option<String>,
+    /// Stretches of ticks that emitted no commands, noted by recorders that
+    /// coalesce idle time. Descriptive only; not part of the record hash.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub idle_spans: Vec<IdleSpan>,
+    /// Fixed simulation step the leg ran at, in nanoseconds. Absent before
+    /// schema 3.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fixed_dt_ns: Option<u64>,
+    /// Layout of the record's `commands`. Written only for schema 2, so
+    /// schema 1 records are unchanged.
+    #[serde(default, skip_serializing_if = "CommandSchema::is_per_command")]
+    pub command_schema: CommandSchema,
+}
+
+/// Ticks `from..until` in which the leg emitted no commands.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IdleSpan {
+    pub from: u32,
+    pub until: u32,
+}
+
+/// Maximal runs of at least `min_ticks` ticks in `0..end_tick` with no
+/// command, in tick order.
+pub fn idle_spans(commands: &[Command], end_tick: u32, min_ticks: u32) -> Vec<IdleSpan> {
+    let busy: BTreeSet<u32> = commands
+        .iter()
+        .map(|command| command.t)
+        .filter(|&t| t < end_tick)
+        .collect();
+    let mut spans = Vec::new();
+    let mut from = 0;
+    for t in busy.into_iter().chain(std::iter::once(end_tick)) {
+        if t - from >= min_ticks.max(1) {
+            spans.push(IdleSpan { from, until: t });
+        }
+        from = t.saturating_add(1);
+    }
+    spans
 }
 
 #[derive(Serialize)]
@@ -218,6 +329,14 @@ struct RecordMetaHashView<'a> {
     rulepack: &'a str,
     weather: &'a str,
     rng_salt: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    rulepack_hash: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    director_cfg_hash: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    commodities_hash: Option<&'a str>,
+    #[serde(skip_serializing_if = "CommandSchema::is_per_command")]
+    command_schema: CommandSchema,
 }
 
 impl RecordMeta {
@@ -229,31 +348,99 @@ impl RecordMeta {
             rulepack: &self.rulepack,
             weather: &self.weather,
             rng_salt: &self.rng_salt,
+            rulepack_hash: self.rulepack_hash.as_deref(),
+            director_cfg_hash: self.director_cfg_hash.as_deref(),
+            commodities_hash: self.commodities_hash.as_deref(),
+            command_schema: self.command_schema,
         }
     }
 }
 
-/// Canonical record encompassing commands and auxiliary inputs.
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+/// Canonical record encompassing commands and auxiliary inputs. Commands are
+/// held flat in memory whichever [`CommandSchema`] the meta names; the
+/// schema only decides how they are written and hashed.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
 pub struct Record {
     pub meta: RecordMeta,
-    #[serde(default)]
     pub commands: Vec<Command>,
-    #[serde(default)]
     pub inputs: Vec<InputEvent>,
+    pub annotations: Vec<Annotation>,
 }
 
 impl Record {
-    /// Returns canonical bytes for the hash-relevant view of the record.
+    /// Marks tick `t` with `label`. Annotations stay in tick order; ties
+    /// keep the order they were added in.
+    pub fn add_annotation(&mut self, t: u32, label: impl Into<String>, note: impl Into<String>) {
+        let at = self.annotations.partition_point(|existing| existing.t <= t);
+        self.annotations.insert(
+            at,
+            Annotation {
+                t,
+                label: label.into(),
+                note: note.into(),
+            },
+        );
+    }
+
+    /// The first annotation labelled `label`.
+    pub fn annotation(&self, label: &str) -> Option<&Annotation> {
+        self.annotations
+            .iter()
+            .find(|annotation| annotation.label == label)
+    }
+
+    /// Ticks within `radius` of the first annotation labelled `label`.
+    pub fn annotation_window(&self, label: &str, radius: u32) -> Option<RangeInclusive<u32>> {
+        let t = self.annotation(label)?.t;
+        Some(t.saturating_sub(radius)..=t.saturating_add(radius))
+    }
+
+    /// Each command with its simulated time in seconds at `fixed_dt` seconds
+    /// per tick. Ticks are scaled in whole nanoseconds and only the result is
+    /// a float, so a step that is not a whole number of nanoseconds drifts by
+    /// under a nanosecond per tick.
+    pub fn command_time_secs(&self, fixed_dt: f64) -> Vec<(f64, &Command)> {
+        self.command_time_secs_at(fixed_dt_nanos(fixed_dt))
+    }
+
+    /// [`command_time_secs`](Self::command_time_secs) at the step stored in
+    /// the record, if it has one.
+    pub fn recorded_command_time_secs(&self) -> Option<Vec<(f64, &Command)>> {
+        Some(self.command_time_secs_at(self.meta.fixed_dt_ns?))
+    }
+
+    fn command_time_secs_at(&self, dt_ns: u64) -> Vec<(f64, &Command)> {
+        self.commands
+            .iter()
+            .map(|command| {
+                let elapsed_ns = u128::from(command.t) * u128::from(dt_ns);
+                (elapsed_ns as f64 / NANOS_PER_SEC as f64, command)
+            })
+            .collect()
+    }
+
+    /// True when no command falls inside one of the meta's idle spans.
+    pub fn idle_spans_consistent(&self) -> bool {
+        self.meta.idle_spans.iter().all(|span| {
+            span.from <= span.until
+                && !self
+                    .commands
+                    .iter()
+                    .any(|command| (span.from..span.until).contains(&command.t))
+        })
+    }
+
+    /// Returns canonical bytes for the hash-relevant view of the record,
+    /// with the commands in the layout its meta names.
     fn hash_view_bytes(&self) -> Result<Vec<u8>, CanonicalJsonError> {
         #[derive(Serialize)]
         struct HashView<'a> {
             meta: RecordMetaHashView<'a>,
-            commands: &'a [Command],
+            commands: command_schema::CommandsView<'a>,
         }
         canonical_json_bytes(&HashView {
             meta: self.meta.hash_view(),
-            commands: &self.commands,
+            commands: self.commands_view(),
         })
     }
 }
@@ -287,12 +474,14 @@ mod tests {
                 mission_minutes: 8,
                 player_rating: 50,
                 prior_danger_score: None,
+                ..RecordMeta::default()
             },
             commands: vec![Command::meter_at(0, "danger_score", 42)],
             inputs: vec![InputEvent {
                 t: 7,
                 input: "KeyDown(Q)".into(),
             }],
+            annotations: Vec::new(),
         };
         let bytes = canonical_json_bytes(&record).unwrap();
         let parsed: Record = from_canonical_json_bytes(&bytes).unwrap();
@@ -316,6 +505,7 @@ mod tests {
                 mission_minutes: 9,
                 player_rating: 60,
                 prior_danger_score: None,
+                ..RecordMeta::default()
             },
             ..Record::default()
         };
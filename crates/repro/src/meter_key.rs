@@ -0,0 +1,187 @@
+//! Meter keys without a heap allocation per command.
+//!
+//! The same few dozen keys are emitted thousands of times per leg, so a
+//! [`MeterKey`] is either a `&'static str` literal or a shared `Arc<str>`
+//! from a process-wide interner. Either way it compares, hashes, orders and
+//! serializes by its text alone, so records are byte-identical to keys held
+//! as `String`.
+
+use std::borrow::Borrow;
+use std::cmp::Ordering;
+use std::collections::HashSet;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::ops::Deref;
+use std::sync::{Arc, Mutex, OnceLock};
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+#[derive(Clone)]
+pub struct MeterKey(Repr);
+
+#[derive(Clone)]
+enum Repr {
+    Static(&'static str),
+    Interned(Arc<str>),
+}
+
+fn interner() -> &'static Mutex<HashSet<Arc<str>>> {
+    static INTERNER: OnceLock<Mutex<HashSet<Arc<str>>>> = OnceLock::new();
+    INTERNER.get_or_init(Default::default)
+}
+
+impl MeterKey {
+    /// A key backed by a literal; never allocates.
+    pub const fn from_static(key: &'static str) -> Self {
+        Self(Repr::Static(key))
+    }
+
+    /// Shares one allocation per distinct key across the process; only the
+    /// first sighting of a key allocates.
+    pub fn intern(key: &str) -> Self {
+        let mut keys = interner().lock().unwrap_or_else(|err| err.into_inner());
+        if let Some(shared) = keys.get(key) {
+            return Self(Repr::Interned(Arc::clone(shared)));
+        }
+        let shared: Arc<str> = Arc::from(key);
+        keys.insert(Arc::clone(&shared));
+        Self(Repr::Interned(shared))
+    }
+
+    pub fn as_str(&self) -> &str {
+        match &self.0 {
+            Repr::Static(key) => key,
+            Repr::Interned(key) => key,
+        }
+    }
+}
+
+impl From<&'static str> for MeterKey {
+    fn from(key: &'static str) -> Self {
+        Self::from_static(key)
+    }
+}
+
+impl From<String> for MeterKey {
+    fn from(key: String) -> Self {
+        Self::intern(&key)
+    }
+}
+
+impl From<&String> for MeterKey {
+    fn from(key: &String) -> Self {
+        Self::intern(key)
+    }
+}
+
+impl Deref for MeterKey {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl AsRef<str> for MeterKey {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl Borrow<str> for MeterKey {
+    fn borrow(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl PartialEq for MeterKey {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+
+impl Eq for MeterKey {}
+
+impl PartialEq<str> for MeterKey {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl PartialEq<&str> for MeterKey {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+impl Hash for MeterKey {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_str().hash(state);
+    }
+}
+
+impl PartialOrd for MeterKey {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for MeterKey {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.as_str().cmp(other.as_str())
+    }
+}
+
+impl fmt::Debug for MeterKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.as_str(), f)
+    }
+}
+
+impl fmt::Display for MeterKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl Serialize for MeterKey {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for MeterKey {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let key = String::deserialize(deserializer)?;
+        Ok(Self::from(key))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn static_and_interned_keys_compare_by_text() {
+        let literal = MeterKey::from("danger_score");
+        let interned = MeterKey::intern("danger_score");
+        assert_eq!(literal, interned);
+        assert_eq!(interned, "danger_score");
+        assert_eq!(literal.cmp(&interned), Ordering::Equal);
+
+        let mut keys = HashSet::new();
+        keys.insert(literal);
+        assert!(keys.contains(&interned));
+        assert!(keys.contains("danger_score"));
+    }
+
+    #[test]
+    fn interning_shares_one_allocation() {
+        let a = MeterKey::intern(&String::from("wheel_stance"));
+        let b = MeterKey::intern("wheel_stance");
+        match (&a.0, &b.0) {
+            (Repr::Interned(a), Repr::Interned(b)) => assert!(Arc::ptr_eq(a, b)),
+            _ => panic!("expected interned keys"),
+        }
+    }
+}
@@ -0,0 +1,525 @@
+//! Record parsing with an opt-in strict mode.
+//!
+//! Lenient parsing is plain serde: missing pacing fields default to zero and
+//! unknown keys are ignored, which keeps old and hand-made files loading.
+//! When serde does reject a lenient record, the offending element is located
+//! afterwards so the error still names a path. Strict parsing first walks the
+//! JSON and rejects anything the recorder would not have written, naming the
+//! offending path.
+
+use std::fmt;
+
+use serde::Deserialize;
+use serde_json::{Map, Value};
+
+use crate::command_schema::tick_order_message;
+use crate::{
+    Annotation, CanonicalJsonError, Command, CommandKind, CommandSchema, InputEvent, Record,
+    RecordMeta,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ParseMode {
+    /// Missing, unknown, mistyped and out-of-range fields are errors.
+    #[default]
+    Strict,
+    /// Serde defaults apply and unknown keys are ignored.
+    Lenient,
+}
+
+/// Highest valid `meta.player_rating`.
+pub const MAX_PLAYER_RATING: u64 = 100;
+
+#[derive(Debug)]
+pub enum RecordParseError {
+    Json(CanonicalJsonError),
+    UnknownField {
+        path: String,
+    },
+    MissingField {
+        path: String,
+    },
+    WrongType {
+        path: String,
+        expected: &'static str,
+    },
+    OutOfRange {
+        path: String,
+        max: u64,
+    },
+    /// A lenient parse that serde rejected, with the element it failed on.
+    Invalid {
+        path: String,
+        message: String,
+    },
+}
+
+impl RecordParseError {
+    /// One-based line and column of a JSON syntax error; `None` for errors
+    /// found after the JSON itself parsed.
+    pub fn position(&self) -> Option<(usize, usize)> {
+        match self {
+            Self::Json(CanonicalJsonError::Serialize(err)) if err.line() > 0 => {
+                Some((err.line(), err.column()))
+            }
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for RecordParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Json(err) => write!(f, "{err}"),
+            Self::UnknownField { path } => write!(f, "unknown field `{path}`"),
+            Self::MissingField { path } => write!(f, "missing field `{path}`"),
+            Self::WrongType { path, expected } => write!(f, "`{path}` must be {expected}"),
+            Self::OutOfRange { path, max } => write!(f, "`{path}` must be at most {max}"),
+            Self::Invalid { path, message } => write!(f, "`{path}`: {message}"),
+        }
+    }
+}
+
+impl std::error::Error for RecordParseError {}
+
+impl From<CanonicalJsonError> for RecordParseError {
+    fn from(value: CanonicalJsonError) -> Self {
+        Self::Json(value)
+    }
+}
+
+impl From<serde_json::Error> for RecordParseError {
+    fn from(value: serde_json::Error) -> Self {
+        Self::Json(value.into())
+    }
+}
+
+impl Record {
+    /// Parses record JSON, validating it field by field in
+    /// [`ParseMode::Strict`].
+    pub fn from_json_bytes(bytes: &[u8], mode: ParseMode) -> Result<Self, RecordParseError> {
+        Self::from_json_value(serde_json::from_slice(bytes)?, mode)
+    }
+
+    /// Parses an already decoded record, as [`Record::from_json_bytes`] does
+    /// once the JSON itself has parsed.
+    pub fn from_json_value(mut value: Value, mode: ParseMode) -> Result<Self, RecordParseError> {
+        match mode {
+            ParseMode::Lenient => {
+                crate::canonicalize_value(&mut value);
+                Record::deserialize(&value).map_err(|err| locate_lenient_error(&value, err))
+            }
+            ParseMode::Strict => {
+                check_record(&value)?;
+                Ok(serde_json::from_value(value)?)
+            }
+        }
+    }
+}
+
+/// Finds the first meta, command or input serde rejects and names the field
+/// within it. Falls back to the bare serde error when no element fails on
+/// its own.
+fn locate_lenient_error(value: &Value, err: serde_json::Error) -> RecordParseError {
+    if let Some(meta) = value.get("meta") {
+        if let Err(err) = RecordMeta::deserialize(meta) {
+            return invalid(meta, "meta", META_FIELDS, &err);
+        }
+    }
+    if let Some(Value::Array(commands)) = value.get("commands") {
+        if by_tick(value) {
+            if let Some(err) = locate_tick_group_error(commands) {
+                return err;
+            }
+        } else {
+            for (idx, command) in commands.iter().enumerate() {
+                if let Err(err) = Command::deserialize(command) {
+                    return invalid_command(command, &format!("commands[{idx}]"), &err);
+                }
+            }
+        }
+    }
+    if let Some(Value::Array(inputs)) = value.get("inputs") {
+        for (idx, input) in inputs.iter().enumerate() {
+            if let Err(err) = InputEvent::deserialize(input) {
+                return invalid(input, &format!("inputs[{idx}]"), INPUT_FIELDS, &err);
+            }
+        }
+    }
+    if let Some(Value::Array(annotations)) = value.get("annotations") {
+        for (idx, annotation) in annotations.iter().enumerate() {
+            if let Err(err) = Annotation::deserialize(annotation) {
+                let path = format!("annotations[{idx}]");
+                return invalid(annotation, &path, ANNOTATION_FIELDS, &err);
+            }
+        }
+    }
+    RecordParseError::Json(err.into())
+}
+
+/// True when the record's meta names the schema 2 command layout.
+fn by_tick(record: &Value) -> bool {
+    record
+        .get("meta")
+        .and_then(|meta| meta.get("command_schema"))
+        .and_then(Value::as_u64)
+        == Some(u64::from(CommandSchema::ByTick.number()))
+}
+
+fn locate_tick_group_error(groups: &[Value]) -> Option<RecordParseError> {
+    let mut previous: Option<u32> = None;
+    for (idx, group) in groups.iter().enumerate() {
+        let path = format!("commands[{idx}]");
+        let Some(object) = group.as_object() else {
+            return Some(invalid_message(&path, "expected object for tick group"));
+        };
+        let t = match object.get("t").map(u32::deserialize) {
+            Some(Ok(t)) => t,
+            Some(Err(err)) => return Some(invalid(group, &join(&path, "t"), &[], &err)),
+            None => return Some(invalid_message(&join(&path, "t"), "missing tick")),
+        };
+        if let Some(previous) = previous.filter(|&previous| t <= previous) {
+            return Some(invalid_message(
+                &join(&path, "t"),
+                &tick_order_message(t, previous),
+            ));
+        }
+        previous = Some(t);
+        let Some(Value::Array(cmds)) = object.get("cmds") else {
+            return Some(invalid_message(
+                &join(&path, "cmds"),
+                "expected an array of commands",
+            ));
+        };
+        for (slot, command) in cmds.iter().enumerate() {
+            if let Err(err) = CommandKind::deserialize(command) {
+                let path = format!("{path}.cmds[{slot}]");
+                return Some(invalid_command_body(command, &path, &err));
+            }
+        }
+    }
+    None
+}
+
+fn invalid_message(path: &str, message: &str) -> RecordParseError {
+    RecordParseError::Invalid {
+        path: path.to_owned(),
+        message: message.to_owned(),
+    }
+}
+
+fn invalid_command(value: &Value, path: &str, err: &serde_json::Error) -> RecordParseError {
+    let Some(command) = value.as_object() else {
+        return invalid(value, path, &[], err);
+    };
+    match command.get("t") {
+        Some(t) if check_kind(t, path, U32).is_err() => {
+            return invalid(value, &join(path, "t"), &[], err);
+        }
+        None => return invalid(value, &join(path, "t"), &[], err),
+        _ => {}
+    }
+    invalid_command_body(value, path, err)
+}
+
+/// Names the field within a command's `Spawn` or `Meter` body serde
+/// rejected.
+fn invalid_command_body(value: &Value, path: &str, err: &serde_json::Error) -> RecordParseError {
+    let Some(command) = value.as_object() else {
+        return invalid(value, path, &[], err);
+    };
+    let body = |key: &str| command.get(key).map(|body| (body, join(path, key)));
+    match (body("Spawn"), body("Meter")) {
+        (Some((body, path)), None) => invalid(body, &path, SPAWN_FIELDS, err),
+        (None, Some((body, path))) => invalid(body, &path, METER_FIELDS, err),
+        _ => invalid(value, path, &[], err),
+    }
+}
+
+/// Names the first of `fields` that is mistyped, or missing where serde
+/// complained about it, under `path`.
+fn invalid(
+    value: &Value,
+    path: &str,
+    fields: &[Field],
+    err: &serde_json::Error,
+) -> RecordParseError {
+    let message = err.to_string();
+    let field = value.as_object().and_then(|object| {
+        fields.iter().find(|field| match object.get(field.name) {
+            None => message.contains(&format!("`{}`", field.name)),
+            Some(Value::Null) if !field.required => false,
+            Some(value) => check_kind(value, path, field.kind).is_err(),
+        })
+    });
+    RecordParseError::Invalid {
+        path: field.map_or_else(|| path.to_owned(), |field| join(path, field.name)),
+        message,
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Kind {
+    Str,
+    Uint(u64),
+    Int32,
+    IdleSpans,
+    CommandSchema,
+}
+
+struct Field {
+    name: &'static str,
+    kind: Kind,
+    /// Optional fields may also be `null`.
+    required: bool,
+}
+
+const fn required(name: &'static str, kind: Kind) -> Field {
+    Field {
+        name,
+        kind,
+        required: true,
+    }
+}
+
+const fn optional(name: &'static str, kind: Kind) -> Field {
+    Field {
+        name,
+        kind,
+        required: false,
+    }
+}
+
+const U16: Kind = Kind::Uint(u16::MAX as u64);
+const U32: Kind = Kind::Uint(u32::MAX as u64);
+
+const META_FIELDS: &[Field] = &[
+    required("schema", U32),
+    required("world_seed", Kind::Str),
+    required("link_id", Kind::Str),
+    required("rulepack", Kind::Str),
+    required("weather", Kind::Str),
+    required("rng_salt", Kind::Str),
+    required("day", U32),
+    required("pp", U16),
+    required("density_per_10k", U32),
+    required("cadence_per_min", U32),
+    required("mission_minutes", U32),
+    required("player_rating", Kind::Uint(MAX_PLAYER_RATING)),
+    optional("prior_danger_score", Kind::Int32),
+    optional("rulepack_hash", Kind::Str),
+    optional("director_cfg_hash", Kind::Str),
+    optional("commodities_hash", Kind::Str),
+    optional("idle_spans", Kind::IdleSpans),
+    optional("fixed_dt_ns", Kind::Uint(u64::MAX)),
+    optional("command_schema", Kind::CommandSchema),
+];
+const IDLE_SPAN_FIELDS: &[Field] = &[required("from", U32), required("until", U32)];
+const SPAWN_FIELDS: &[Field] = &[
+    required("kind", Kind::Str),
+    required("x_mm", Kind::Int32),
+    required("y_mm", Kind::Int32),
+    required("z_mm", Kind::Int32),
+];
+const METER_FIELDS: &[Field] = &[required("key", Kind::Str), required("value", Kind::Int32)];
+const INPUT_FIELDS: &[Field] = &[required("t", U32), required("input", Kind::Str)];
+const ANNOTATION_FIELDS: &[Field] = &[
+    required("t", U32),
+    required("label", Kind::Str),
+    optional("note", Kind::Str),
+];
+
+fn check_record(value: &Value) -> Result<(), RecordParseError> {
+    let record = as_object(value, "record")?;
+    for key in record.keys() {
+        if !matches!(key.as_str(), "meta" | "commands" | "inputs" | "annotations") {
+            return Err(RecordParseError::UnknownField { path: key.clone() });
+        }
+    }
+    let meta = record.get("meta").ok_or_else(|| missing("", "meta"))?;
+    check_fields(meta, "meta", META_FIELDS)?;
+
+    let commands = as_array(record.get("commands"), "commands")?;
+    if by_tick(value) {
+        check_tick_groups(commands)?;
+    } else {
+        for (idx, command) in commands.iter().enumerate() {
+            check_command(command, &format!("commands[{idx}]"))?;
+        }
+    }
+    let inputs = as_array(record.get("inputs"), "inputs")?;
+    for (idx, input) in inputs.iter().enumerate() {
+        check_fields(input, &format!("inputs[{idx}]"), INPUT_FIELDS)?;
+    }
+    // Written only when non-empty.
+    let annotations = match record.get("annotations") {
+        Some(annotations) => as_array(Some(annotations), "annotations")?,
+        None => &[],
+    };
+    for (idx, annotation) in annotations.iter().enumerate() {
+        check_fields(
+            annotation,
+            &format!("annotations[{idx}]"),
+            ANNOTATION_FIELDS,
+        )?;
+    }
+    Ok(())
+}
+
+/// Schema 2 command groups: `t` and `cmds` only, ticks strictly
+/// increasing, and every grouped command without a tick of its own.
+fn check_tick_groups(groups: &[Value]) -> Result<(), RecordParseError> {
+    let mut previous: Option<u64> = None;
+    for (idx, group) in groups.iter().enumerate() {
+        let path = format!("commands[{idx}]");
+        let object = as_object(group, &path)?;
+        for key in object.keys() {
+            if !matches!(key.as_str(), "t" | "cmds") {
+                return Err(RecordParseError::UnknownField {
+                    path: join(&path, key),
+                });
+            }
+        }
+        let t = object.get("t").ok_or_else(|| missing(&path, "t"))?;
+        check_kind(t, &join(&path, "t"), U32)?;
+        let t = t.as_u64().unwrap_or_default();
+        if let Some(previous) = previous.filter(|&previous| t <= previous) {
+            return Err(invalid_message(
+                &join(&path, "t"),
+                &tick_order_message(t as u32, previous as u32),
+            ));
+        }
+        previous = Some(t);
+        let cmds = as_array(object.get("cmds"), &join(&path, "cmds"))?;
+        for (slot, command) in cmds.iter().enumerate() {
+            check_command_body(command, &format!("{path}.cmds[{slot}]"), false)?;
+        }
+    }
+    Ok(())
+}
+
+fn check_command(value: &Value, path: &str) -> Result<(), RecordParseError> {
+    check_command_body(value, path, true)
+}
+
+fn check_command_body(value: &Value, path: &str, with_tick: bool) -> Result<(), RecordParseError> {
+    let command = as_object(value, path)?;
+    if with_tick {
+        match command.get("t") {
+            Some(t) => check_kind(t, &join(path, "t"), U32)?,
+            None => return Err(missing(path, "t")),
+        }
+    }
+    for (key, body) in command {
+        match key.as_str() {
+            "t" if with_tick => {}
+            "Spawn" => check_fields(body, &join(path, key), SPAWN_FIELDS)?,
+            "Meter" => check_fields(body, &join(path, key), METER_FIELDS)?,
+            _ => {
+                return Err(RecordParseError::UnknownField {
+                    path: join(path, key),
+                })
+            }
+        }
+    }
+    Ok(())
+}
+
+fn check_fields(value: &Value, path: &str, fields: &[Field]) -> Result<(), RecordParseError> {
+    let object = as_object(value, path)?;
+    for key in object.keys() {
+        if !fields.iter().any(|field| field.name == key) {
+            return Err(RecordParseError::UnknownField {
+                path: join(path, key),
+            });
+        }
+    }
+    for field in fields {
+        match (object.get(field.name), field.required) {
+            (None, true) => return Err(missing(path, field.name)),
+            (None | Some(Value::Null), false) => {}
+            (Some(value), _) => check_kind(value, &join(path, field.name), field.kind)?,
+        }
+    }
+    Ok(())
+}
+
+fn check_kind(value: &Value, path: &str, kind: Kind) -> Result<(), RecordParseError> {
+    match kind {
+        Kind::Str if value.is_string() => Ok(()),
+        Kind::Str => Err(wrong_type(path, "a string")),
+        Kind::Uint(max) => match value.as_u64() {
+            Some(n) if n <= max => Ok(()),
+            found => match unsigned_name(max) {
+                Some(expected) => Err(wrong_type(path, expected)),
+                None if found.is_some() => Err(RecordParseError::OutOfRange {
+                    path: path.to_owned(),
+                    max,
+                }),
+                None => Err(wrong_type(path, "an unsigned integer")),
+            },
+        },
+        Kind::Int32 => match value.as_i64() {
+            Some(n) if i32::try_from(n).is_ok() => Ok(()),
+            _ => Err(wrong_type(path, "an i32")),
+        },
+        Kind::IdleSpans => {
+            let Value::Array(spans) = value else {
+                return Err(wrong_type(path, "an array"));
+            };
+            for (idx, span) in spans.iter().enumerate() {
+                check_fields(span, &format!("{path}[{idx}]"), IDLE_SPAN_FIELDS)?;
+            }
+            Ok(())
+        }
+        Kind::CommandSchema => match value.as_u64().map(u32::try_from) {
+            Some(Ok(number)) if CommandSchema::try_from(number).is_ok() => Ok(()),
+            _ => Err(wrong_type(path, "1 or 2")),
+        },
+    }
+}
+
+/// Type name for limits that are a primitive's full range; `None` for
+/// narrower domain limits such as the player rating.
+fn unsigned_name(max: u64) -> Option<&'static str> {
+    match max {
+        m if m == u64::from(u16::MAX) => Some("a u16"),
+        m if m == u64::from(u32::MAX) => Some("a u32"),
+        _ => None,
+    }
+}
+
+fn as_object<'a>(value: &'a Value, path: &str) -> Result<&'a Map<String, Value>, RecordParseError> {
+    value
+        .as_object()
+        .ok_or_else(|| wrong_type(path, "an object"))
+}
+
+fn as_array<'a>(value: Option<&'a Value>, path: &str) -> Result<&'a [Value], RecordParseError> {
+    match value {
+        None => Err(missing("", path)),
+        Some(Value::Array(items)) => Ok(items),
+        Some(_) => Err(wrong_type(path, "an array")),
+    }
+}
+
+fn join(path: &str, key: &str) -> String {
+    if path.is_empty() {
+        key.to_owned()
+    } else {
+        format!("{path}.{key}")
+    }
+}
+
+fn missing(path: &str, key: &str) -> RecordParseError {
+    RecordParseError::MissingField {
+        path: join(path, key),
+    }
+}
+
+fn wrong_type(path: &str, expected: &'static str) -> RecordParseError {
+    RecordParseError::WrongType {
+        path: path.to_owned(),
+        expected,
+    }
+}
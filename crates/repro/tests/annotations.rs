@@ -0,0 +1,74 @@
+use repro::strict::ParseMode;
+use repro::{canonical_json_bytes, hash_record, Annotation, Command, Record, RecordMeta};
+
+fn base_record() -> Record {
+    Record {
+        meta: RecordMeta {
+            schema: 2,
+            world_seed: "omega".into(),
+            link_id: "leg_01".into(),
+            rulepack: "assets/rulepack.toml".into(),
+            weather: "Clear".into(),
+            rng_salt: "salt".into(),
+            day: 3,
+            ..RecordMeta::default()
+        },
+        commands: vec![
+            Command::meter_at(0, "danger_score", 7),
+            Command::meter_at(120, "danger_score", 9),
+        ],
+        inputs: Vec::new(),
+        annotations: Vec::new(),
+    }
+}
+
+#[test]
+fn annotations_round_trip_through_strict_parse() {
+    let mut record = base_record();
+    record.add_annotation(120, "spike", "felt wrong");
+    record.add_annotation(4, "start", "");
+
+    let bytes = canonical_json_bytes(&record).expect("canonical bytes");
+    let parsed = Record::from_json_bytes(&bytes, ParseMode::Strict).expect("strict parse");
+    assert_eq!(parsed, record);
+    assert_eq!(
+        parsed.annotations,
+        vec![
+            Annotation {
+                t: 4,
+                label: "start".into(),
+                note: String::new(),
+            },
+            Annotation {
+                t: 120,
+                label: "spike".into(),
+                note: "felt wrong".into(),
+            },
+        ]
+    );
+}
+
+#[test]
+fn annotations_leave_bytes_and_hash_alone() {
+    let plain = base_record();
+    let mut annotated = plain.clone();
+    annotated.add_annotation(60, "lag", "");
+
+    let plain_json = String::from_utf8(canonical_json_bytes(&plain).expect("bytes")).expect("utf8");
+    assert!(!plain_json.contains("annotations"));
+    assert_eq!(
+        hash_record(&annotated).expect("hash"),
+        hash_record(&plain).expect("hash")
+    );
+}
+
+#[test]
+fn annotation_window_centres_on_the_label() {
+    let mut record = base_record();
+    record.add_annotation(10, "early", "");
+    record.add_annotation(100, "spike", "");
+
+    assert_eq!(record.annotation_window("spike", 30), Some(70..=130));
+    assert_eq!(record.annotation_window("early", 30), Some(0..=40));
+    assert_eq!(record.annotation_window("missing", 30), None);
+}
@@ -17,9 +17,11 @@ fn canonical_json_is_sorted() {
             mission_minutes: 12,
             player_rating: 62,
             prior_danger_score: None,
+            ..RecordMeta::default()
         },
         commands: vec![Command::meter_at(0, "danger", 1)],
         inputs: Vec::new(),
+        annotations: Vec::new(),
     };
 
     let bytes = canonical_json_bytes(&record).expect("canonical bytes");
@@ -0,0 +1,179 @@
+use repro::strict::{ParseMode, RecordParseError};
+use repro::{
+    canonical_json_bytes, from_canonical_json_bytes, hash_record, Command, CommandSchema, Record,
+    RecordMeta,
+};
+use serde_json::{json, Value};
+
+fn record(schema: CommandSchema) -> Record {
+    Record {
+        meta: RecordMeta {
+            schema: 3,
+            world_seed: "omega".into(),
+            link_id: "leg_01".into(),
+            rulepack: "assets/rulepack.toml".into(),
+            weather: "Clear".into(),
+            rng_salt: "salt".into(),
+            day: 3,
+            pp: 250,
+            mission_minutes: 6,
+            player_rating: 50,
+            fixed_dt_ns: Some(33_333_333),
+            command_schema: schema,
+            ..RecordMeta::default()
+        },
+        commands: vec![
+            Command::meter_at(0, "danger_score", 7),
+            Command::spawn_at(0, "bandit", 10, 0, -10),
+            Command::meter_at(4, "danger_score", 8),
+            Command::meter_at(9, "danger_diff", 1),
+            Command::meter_at(9, "danger_score", 9),
+        ],
+        ..Record::default()
+    }
+}
+
+fn parse(bytes: &[u8], mode: ParseMode) -> Result<Record, RecordParseError> {
+    Record::from_json_bytes(bytes, mode)
+}
+
+fn parse_value(value: &Value, mode: ParseMode) -> Result<Record, RecordParseError> {
+    parse(&canonical_json_bytes(value).expect("canonical bytes"), mode)
+}
+
+#[test]
+fn schema_two_groups_commands_under_their_tick() {
+    let value = serde_json::to_value(record(CommandSchema::ByTick)).expect("record value");
+    assert_eq!(value["meta"]["command_schema"], json!(2));
+    assert_eq!(
+        value["commands"],
+        json!([
+            { "t": 0, "cmds": [
+                { "Meter": { "key": "danger_score", "value": 7 } },
+                { "Spawn": { "kind": "bandit", "x_mm": 10, "y_mm": 0, "z_mm": -10 } },
+            ] },
+            { "t": 4, "cmds": [{ "Meter": { "key": "danger_score", "value": 8 } }] },
+            { "t": 9, "cmds": [
+                { "Meter": { "key": "danger_diff", "value": 1 } },
+                { "Meter": { "key": "danger_score", "value": 9 } },
+            ] },
+        ])
+    );
+
+    // Schema 1 files are written exactly as before.
+    let value = serde_json::to_value(record(CommandSchema::PerCommand)).expect("record value");
+    assert!(value["meta"].get("command_schema").is_none());
+    assert_eq!(value["commands"][1]["t"], json!(0));
+}
+
+#[test]
+fn both_schemas_read_back_in_every_mode() {
+    for schema in [CommandSchema::PerCommand, CommandSchema::ByTick] {
+        let original = record(schema);
+        let bytes = canonical_json_bytes(&original).expect("canonical bytes");
+        for mode in [ParseMode::Strict, ParseMode::Lenient] {
+            assert_eq!(parse(&bytes, mode).expect("parse"), original, "{schema}");
+        }
+        let parsed: Record = from_canonical_json_bytes(&bytes).expect("canonical parse");
+        assert_eq!(parsed, original);
+    }
+}
+
+#[test]
+fn rewriting_across_schemas_keeps_commands_and_changes_the_hash() {
+    let v1 = record(CommandSchema::PerCommand);
+    let mut v2 = parse(
+        &canonical_json_bytes(&v1).expect("v1 bytes"),
+        ParseMode::Strict,
+    )
+    .expect("v1 parse");
+    v2.meta.command_schema = CommandSchema::ByTick;
+    let reread = parse(
+        &canonical_json_bytes(&v2).expect("v2 bytes"),
+        ParseMode::Strict,
+    )
+    .expect("v2 parse");
+    assert_eq!(reread.commands, v1.commands);
+    assert_eq!(reread.meta.command_schema, CommandSchema::ByTick);
+
+    // The hash covers the layout, so a rewritten record gets a new one.
+    let hash_v1 = hash_record(&v1).expect("hash v1");
+    let hash_v2 = hash_record(&reread).expect("hash v2");
+    assert_ne!(hash_v1, hash_v2);
+    assert_eq!(hash_v2, hash_record(&v2).expect("hash v2 again"));
+
+    // And back again, to the original hash.
+    let mut back = reread;
+    back.meta.command_schema = CommandSchema::PerCommand;
+    assert_eq!(hash_record(&back).expect("hash back"), hash_v1);
+}
+
+#[test]
+fn grouped_ticks_must_strictly_increase() {
+    let mut value = serde_json::to_value(record(CommandSchema::ByTick)).expect("record value");
+    value["commands"][2]["t"] = json!(4);
+    for mode in [ParseMode::Strict, ParseMode::Lenient] {
+        match parse_value(&value, mode).expect_err("repeated tick") {
+            RecordParseError::Invalid { path, message } => {
+                assert_eq!(path, "commands[2].t", "{mode:?}");
+                assert!(message.contains("strictly increase"), "{message}");
+            }
+            other => panic!("{mode:?}: unexpected error {other}"),
+        }
+    }
+    value["commands"][2]["t"] = json!(1);
+    assert!(parse_value(&value, ParseMode::Strict).is_err());
+    assert!(serde_json::from_value::<Record>(value).is_err());
+
+    // Commands out of tick order cannot be written as schema 2.
+    let mut unordered = record(CommandSchema::ByTick);
+    unordered.commands.swap(0, 2);
+    assert!(canonical_json_bytes(&unordered).is_err());
+    assert!(hash_record(&unordered).is_err());
+}
+
+#[test]
+fn strict_mode_rejects_malformed_groups() {
+    let base = serde_json::to_value(record(CommandSchema::ByTick)).expect("record value");
+
+    let mut value = base.clone();
+    value["commands"][0]["cmds"][0]["t"] = json!(0);
+    match parse_value(&value, ParseMode::Strict).expect_err("tick inside a group") {
+        RecordParseError::UnknownField { path } => assert_eq!(path, "commands[0].cmds[0].t"),
+        other => panic!("unexpected error {other}"),
+    }
+
+    let mut value = base.clone();
+    value["commands"][1]["extra"] = json!(true);
+    match parse_value(&value, ParseMode::Strict).expect_err("unknown group key") {
+        RecordParseError::UnknownField { path } => assert_eq!(path, "commands[1].extra"),
+        other => panic!("unexpected error {other}"),
+    }
+
+    let mut value = base.clone();
+    value["commands"][1]["cmds"][0]["Meter"]["value"] = json!("eight");
+    for mode in [ParseMode::Strict, ParseMode::Lenient] {
+        let err = parse_value(&value, mode).expect_err("mistyped meter");
+        assert!(
+            err.to_string().contains("commands[1].cmds[0].Meter.value"),
+            "{err}"
+        );
+    }
+
+    let mut value = base;
+    value["meta"]["command_schema"] = json!(3);
+    match parse_value(&value, ParseMode::Strict).expect_err("unknown schema") {
+        RecordParseError::WrongType { path, .. } => assert_eq!(path, "meta.command_schema"),
+        other => panic!("unexpected error {other}"),
+    }
+}
+
+#[test]
+fn ticks_borrow_runs_of_commands() {
+    let record = record(CommandSchema::PerCommand);
+    let ticks: Vec<(u32, usize)> = record
+        .ticks()
+        .map(|tick| (tick.t, tick.commands.len()))
+        .collect();
+    assert_eq!(ticks, [(0, 2), (4, 1), (9, 2)]);
+}
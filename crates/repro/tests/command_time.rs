@@ -0,0 +1,52 @@
+use repro::strict::ParseMode;
+use repro::{canonical_json_bytes, fixed_dt_nanos, Command, Record, RecordMeta};
+
+fn record(fixed_dt_ns: Option<u64>) -> Record {
+    Record {
+        meta: RecordMeta {
+            fixed_dt_ns,
+            ..RecordMeta::default()
+        },
+        commands: vec![
+            Command::meter_at(0, "danger_score", 1),
+            Command::meter_at(60, "danger_score", 2),
+            Command::spawn_at(100, "bandit", 0, 0, 0),
+        ],
+        inputs: Vec::new(),
+        annotations: Vec::new(),
+    }
+}
+
+#[test]
+fn thirty_hz_tick_sixty_is_two_seconds() {
+    let dt = 1.0 / 30.0;
+    assert_eq!(fixed_dt_nanos(dt), 33_333_333);
+    let rec = record(Some(fixed_dt_nanos(dt)));
+
+    let times = rec.command_time_secs(dt);
+    assert_eq!(times.len(), 3);
+    assert_eq!(times[0].0, 0.0);
+    assert_eq!(times[1].1.t, 60);
+    // 1/30 s is not a whole number of nanoseconds; the error stays under
+    // one nanosecond per tick.
+    assert!((times[1].0 - 2.0).abs() < 60e-9, "{}", times[1].0);
+    assert_eq!(rec.recorded_command_time_secs(), Some(times));
+}
+
+#[test]
+fn whole_nanosecond_steps_are_exact() {
+    let rec = record(None);
+    let times = rec.command_time_secs(0.02);
+    assert_eq!(times[2].0, 2.0);
+    assert_eq!(rec.recorded_command_time_secs(), None);
+    assert_eq!(fixed_dt_nanos(f64::NAN), 0);
+    assert_eq!(fixed_dt_nanos(-0.5), 0);
+}
+
+#[test]
+fn fixed_dt_survives_strict_parse() {
+    let rec = record(Some(33_333_333));
+    let bytes = canonical_json_bytes(&rec).expect("bytes");
+    let parsed = Record::from_json_bytes(&bytes, ParseMode::Strict).expect("strict parse");
+    assert_eq!(parsed.meta.fixed_dt_ns, Some(33_333_333));
+}
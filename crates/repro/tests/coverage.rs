@@ -0,0 +1,51 @@
+use repro::coverage::summarize;
+use repro::{Command, Record, RecordMeta};
+
+fn record(commands: Vec<Command>) -> Record {
+    Record {
+        meta: RecordMeta::default(),
+        commands,
+        inputs: Vec::new(),
+        annotations: Vec::new(),
+    }
+}
+
+#[test]
+fn counts_kinds_keys_and_tick_extent() {
+    let rec = record(vec![
+        Command::meter_at(2, "danger_score", 100),
+        Command::spawn_at(3, "bandit", 0, 0, 0),
+        Command::meter_at(3, "spawn_count", 1),
+        Command::spawn_at(7, "scout", 10, 0, -10),
+        Command::meter_at(9, "danger_score", 120),
+        Command::meter_at(9, "mission_result", 1),
+    ]);
+    let coverage = summarize(&rec);
+
+    assert_eq!(coverage.spawns, 2);
+    assert_eq!(coverage.meters, 4);
+    assert_eq!(coverage.commands(), 6);
+    assert!(coverage.has_spawn());
+    assert_eq!(
+        coverage
+            .meter_keys
+            .iter()
+            .map(String::as_str)
+            .collect::<Vec<_>>(),
+        ["danger_score", "mission_result", "spawn_count"]
+    );
+    assert_eq!(coverage.first_tick, Some(2));
+    assert_eq!(coverage.last_tick, Some(9));
+}
+
+#[test]
+fn empty_and_meter_only_records() {
+    let empty = summarize(&record(Vec::new()));
+    assert_eq!(empty.commands(), 0);
+    assert_eq!(empty.first_tick, None);
+    assert_eq!(empty.last_tick, None);
+
+    let meters_only = summarize(&record(vec![Command::meter_at(0, "danger_score", 1)]));
+    assert!(!meters_only.has_spawn());
+    assert_eq!(meters_only.meter_keys.len(), 1);
+}
@@ -0,0 +1,61 @@
+use repro::csv::CSV_HEADER;
+use repro::{Command, Record, RecordMeta};
+
+fn record() -> Record {
+    Record {
+        meta: RecordMeta {
+            schema: 2,
+            world_seed: "omega".into(),
+            link_id: "leg_01".into(),
+            rulepack: "assets/rulepack.toml".into(),
+            weather: "Clear".into(),
+            rng_salt: "salt".into(),
+            ..RecordMeta::default()
+        },
+        commands: vec![
+            Command::meter_at(0, "danger_score", 10290),
+            Command::spawn_at(0, "bandit", 100, 0, -250),
+            Command::meter_at(3, "route,closed \"north\"", -1),
+            Command::spawn_at(7, "scout", 0, 5, 0),
+        ],
+        inputs: Vec::new(),
+        annotations: Vec::new(),
+    }
+}
+
+fn export(record: &Record, meters_only: bool) -> String {
+    let mut out = Vec::new();
+    if meters_only {
+        record.meters_to_csv(&mut out).expect("csv");
+    } else {
+        record.to_csv(&mut out).expect("csv");
+    }
+    String::from_utf8(out).expect("utf8")
+}
+
+#[test]
+fn both_variants_export_with_quoting() {
+    let record = record();
+    let csv = export(&record, false);
+    let expected = format!(
+        "{CSV_HEADER}\n\
+         0,Meter,danger_score,10290,,,\n\
+         0,Spawn,bandit,,100,0,-250\n\
+         3,Meter,\"route,closed \"\"north\"\"\",-1,,,\n\
+         7,Spawn,scout,,0,5,0\n"
+    );
+    assert_eq!(csv, expected);
+    assert_eq!(csv.lines().count(), record.commands.len() + 1);
+    assert_eq!(csv, export(&record, false));
+}
+
+#[test]
+fn meters_only_drops_spawns() {
+    let csv = export(&record(), true);
+    let rows: Vec<&str> = csv.lines().skip(1).collect();
+    assert_eq!(rows.len(), 2);
+    assert!(rows
+        .iter()
+        .all(|row| row.split(',').nth(1) == Some("Meter")));
+    assert!(csv.starts_with(CSV_HEADER));
+}
@@ -0,0 +1,146 @@
+use repro::delta::{DeltaError, DeltaRecord};
+use repro::{canonical_json_bytes, hash_record, Command, InputEvent, Record, RecordMeta};
+
+fn meta() -> RecordMeta {
+    RecordMeta {
+        schema: 1,
+        world_seed: "omega".into(),
+        link_id: "leg_07".into(),
+        rulepack: "assets/rulepack.toml".into(),
+        weather: "Rains".into(),
+        rng_salt: "salt".into(),
+        day: 6,
+        pp: 340,
+        density_per_10k: 8,
+        cadence_per_min: 4,
+        mission_minutes: 10,
+        player_rating: 55,
+        prior_danger_score: Some(12),
+        ..RecordMeta::default()
+    }
+}
+
+/// Director-like output: the same handful of meters every tick with values
+/// that rarely change, periodic spawns interleaved between meters, and the
+/// odd tick with an extra or missing meter.
+fn synthetic_record() -> Record {
+    let mut commands = Vec::new();
+    for t in 0..600u32 {
+        commands.push(Command::meter_at(t, "danger_score", 400 + (t / 120) as i32));
+        commands.push(Command::meter_at(t, "danger_diff", 1));
+        if t % 60 == 0 {
+            commands.push(Command::meter_at(t, "spawn_count", (t / 60) as i32));
+            commands.push(Command::spawn_at(t, "bandit", t as i32 * 10, 0, 500));
+            commands.push(Command::spawn_at(t, "scout", t as i32 * 10 + 100, 0, 500));
+        }
+        if t % 97 == 0 {
+            commands.push(Command::meter_at(t, "econ_pp_pending", 3));
+            commands.push(Command::meter_at(t, "econ_pp_pending", 4));
+        }
+        commands.push(Command::meter_at(t, "mission_progress", (t % 7) as i32));
+    }
+    Record {
+        meta: meta(),
+        commands,
+        inputs: vec![InputEvent {
+            t: 42,
+            input: "KeyDown(Q)".into(),
+        }],
+        annotations: Vec::new(),
+    }
+}
+
+#[test]
+fn delta_round_trip_is_lossless_and_smaller() {
+    let record = synthetic_record();
+    let delta = DeltaRecord::from_record(&record);
+
+    assert_eq!(delta.to_record().expect("decode"), record);
+    assert_eq!(
+        delta.hash().expect("delta hash"),
+        hash_record(&record).expect("record hash")
+    );
+
+    let full = canonical_json_bytes(&record).expect("record bytes").len();
+    let compact = canonical_json_bytes(&delta).expect("delta bytes").len();
+    assert!(compact * 3 < full, "delta {compact} bytes vs record {full}");
+
+    let bytes = canonical_json_bytes(&delta).expect("delta bytes");
+    let parsed: DeltaRecord = repro::from_canonical_json_bytes(&bytes).expect("parse delta");
+    assert_eq!(parsed, delta);
+    assert_eq!(parsed.to_record().expect("decode parsed"), record);
+}
+
+#[test]
+fn unchanged_ticks_store_no_meters() {
+    let record = Record {
+        meta: meta(),
+        commands: vec![
+            Command::meter_at(0, "danger_score", 5),
+            Command::meter_at(1, "danger_score", 5),
+            Command::meter_at(2, "danger_score", 6),
+        ],
+        inputs: Vec::new(),
+        annotations: Vec::new(),
+    };
+    let delta = DeltaRecord::from_record(&record);
+    assert_eq!(delta.ticks.len(), 3);
+    assert!(delta.ticks[0].layout.is_some());
+    assert!(delta.ticks[1].layout.is_none() && delta.ticks[1].changed.is_empty());
+    assert_eq!(delta.ticks[2].changed, vec![(0, 6)]);
+}
+
+#[test]
+fn empty_and_non_monotonic_records_round_trip() {
+    let empty = Record {
+        meta: meta(),
+        ..Record::default()
+    };
+    assert_eq!(
+        DeltaRecord::from_record(&empty)
+            .to_record()
+            .expect("decode"),
+        empty
+    );
+
+    let shuffled = Record {
+        meta: meta(),
+        commands: vec![
+            Command::meter_at(5, "a", 1),
+            Command::meter_at(3, "a", 1),
+            Command::spawn_at(3, "bandit", 1, 2, 3),
+            Command::meter_at(5, "a", 2),
+        ],
+        inputs: Vec::new(),
+        annotations: Vec::new(),
+    };
+    assert_eq!(
+        DeltaRecord::from_record(&shuffled)
+            .to_record()
+            .expect("decode"),
+        shuffled
+    );
+}
+
+#[test]
+fn corrupt_delta_is_rejected() {
+    let record = Record {
+        meta: meta(),
+        commands: vec![Command::meter_at(0, "danger_score", 5)],
+        inputs: Vec::new(),
+        annotations: Vec::new(),
+    };
+    let mut delta = DeltaRecord::from_record(&record);
+    delta.ticks[0].changed.clear();
+    assert!(matches!(
+        delta.to_record(),
+        Err(DeltaError::MissingBaseline { t: 0, .. })
+    ));
+
+    let mut delta = DeltaRecord::from_record(&record);
+    delta.ticks[0].changed.push((3, 9));
+    assert!(matches!(
+        delta.to_record(),
+        Err(DeltaError::SlotMismatch { t: 0 })
+    ));
+}
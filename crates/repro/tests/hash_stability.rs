@@ -17,9 +17,11 @@ fn identical_records_hash_the_same() {
             mission_minutes: 14,
             player_rating: 58,
             prior_danger_score: None,
+            ..RecordMeta::default()
         },
         commands: vec![Command::meter_at(0, "danger_score", 9001)],
         inputs: Vec::new(),
+        annotations: Vec::new(),
     };
 
     let hash_a = hash_record(&record).expect("hash");
@@ -44,9 +46,11 @@ fn hash_contract_fields_change_digest() {
             mission_minutes: 14,
             player_rating: 58,
             prior_danger_score: None,
+            ..RecordMeta::default()
         },
         commands: vec![Command::meter_at(0, "danger_score", 9001)],
         inputs: Vec::new(),
+        annotations: Vec::new(),
     };
 
     let hash_base = hash_record(&base).expect("hash");
@@ -74,6 +78,22 @@ fn hash_contract_fields_change_digest() {
     let mut changed_salt = base.clone();
     changed_salt.meta.rng_salt = "pepper".into();
     assert_ne!(hash_base, hash_record(&changed_salt).expect("hash"));
+
+    let mut pinned = base.clone();
+    pinned.meta.rulepack_hash = Some("aa".into());
+    pinned.meta.director_cfg_hash = Some("bb".into());
+    pinned.meta.commodities_hash = Some("cc".into());
+    let hash_pinned = hash_record(&pinned).expect("hash");
+    assert_ne!(hash_base, hash_pinned);
+    for edit in [
+        |meta: &mut RecordMeta| meta.rulepack_hash = Some("ab".into()),
+        |meta: &mut RecordMeta| meta.director_cfg_hash = Some("bc".into()),
+        |meta: &mut RecordMeta| meta.commodities_hash = Some("cd".into()),
+    ] {
+        let mut drifted = pinned.clone();
+        edit(&mut drifted.meta);
+        assert_ne!(hash_pinned, hash_record(&drifted).expect("hash"));
+    }
 }
 
 #[test]
@@ -93,9 +113,11 @@ fn non_contract_meta_fields_do_not_change_digest() {
             mission_minutes: 14,
             player_rating: 58,
             prior_danger_score: None,
+            ..RecordMeta::default()
         },
         commands: vec![Command::meter_at(0, "danger_score", 9001)],
         inputs: Vec::new(),
+        annotations: Vec::new(),
     };
 
     let hash_base = hash_record(&base).expect("hash");
@@ -146,9 +168,11 @@ fn inputs_are_excluded_from_digest() {
             mission_minutes: 14,
             player_rating: 58,
             prior_danger_score: None,
+            ..RecordMeta::default()
         },
         commands: vec![Command::meter_at(0, "danger_score", 9001)],
         inputs: Vec::new(),
+        annotations: Vec::new(),
     };
 
     let hash_base = hash_record(&base).expect("hash");
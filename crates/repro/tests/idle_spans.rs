@@ -0,0 +1,84 @@
+use repro::{
+    canonical_json_bytes, from_canonical_json_bytes, hash_record, idle_spans, Command, IdleSpan,
+    Record, RecordMeta,
+};
+
+fn record(commands: Vec<Command>) -> Record {
+    Record {
+        meta: RecordMeta {
+            schema: 2,
+            world_seed: "omega".into(),
+            link_id: "leg_01".into(),
+            rulepack: "assets/rulepack.toml".into(),
+            weather: "Clear".into(),
+            rng_salt: "salt".into(),
+            ..RecordMeta::default()
+        },
+        commands,
+        inputs: Vec::new(),
+        annotations: Vec::new(),
+    }
+}
+
+#[test]
+fn spans_cover_only_long_empty_stretches() {
+    let commands = vec![
+        Command::meter_at(0, "a", 1),
+        Command::meter_at(0, "b", 1),
+        Command::meter_at(3, "a", 2),
+        Command::spawn_at(40, "bandit", 0, 0, 0),
+        Command::meter_at(41, "a", 3),
+    ];
+    assert_eq!(
+        idle_spans(&commands, 120, 10),
+        vec![
+            IdleSpan { from: 4, until: 40 },
+            IdleSpan {
+                from: 42,
+                until: 120
+            },
+        ]
+    );
+    assert_eq!(
+        idle_spans(&commands, 42, 2),
+        vec![
+            IdleSpan { from: 1, until: 3 },
+            IdleSpan { from: 4, until: 40 }
+        ]
+    );
+    assert_eq!(idle_spans(&[], 5, 5), vec![IdleSpan { from: 0, until: 5 }]);
+    assert!(idle_spans(&[], 4, 5).is_empty());
+}
+
+#[test]
+fn coalesced_record_hashes_like_the_plain_one() {
+    let plain = record(vec![
+        Command::meter_at(0, "a", 1),
+        Command::meter_at(90, "a", 2),
+    ]);
+    let mut coalesced = plain.clone();
+    coalesced.meta.idle_spans = idle_spans(&coalesced.commands, 100, 20);
+    assert_eq!(
+        coalesced.meta.idle_spans,
+        vec![IdleSpan { from: 1, until: 90 }]
+    );
+    assert!(coalesced.idle_spans_consistent());
+    assert_eq!(
+        hash_record(&coalesced).unwrap(),
+        hash_record(&plain).unwrap()
+    );
+
+    let bytes = canonical_json_bytes(&coalesced).unwrap();
+    let parsed: Record = from_canonical_json_bytes(&bytes).unwrap();
+    assert_eq!(parsed, coalesced);
+    assert!(!String::from_utf8(canonical_json_bytes(&plain).unwrap())
+        .unwrap()
+        .contains("idle_spans"));
+
+    let mut bogus = coalesced.clone();
+    bogus.meta.idle_spans = vec![IdleSpan {
+        from: 80,
+        until: 95,
+    }];
+    assert!(!bogus.idle_spans_consistent());
+}
@@ -0,0 +1,58 @@
+use repro::diff::{aggregate_meters, compare_aggregates};
+use repro::{Command, Record, RecordMeta};
+
+fn record(commands: Vec<Command>) -> Record {
+    Record {
+        meta: RecordMeta::default(),
+        commands,
+        inputs: Vec::new(),
+        annotations: Vec::new(),
+    }
+}
+
+#[test]
+fn meters_sum_across_ticks() {
+    let rec = record(vec![
+        Command::meter_at(0, "danger_score", 100),
+        Command::spawn_at(0, "bandit", 0, 0, 0),
+        Command::meter_at(1, "danger_score", 250),
+        Command::meter_at(1, "spawn_count", 1),
+        Command::meter_at(2, "danger_score", -50),
+        Command::meter_at(2, "spawn_count", 1),
+        Command::meter_at(2, "big", i32::MAX),
+        Command::meter_at(3, "big", i32::MAX),
+    ]);
+    let totals = aggregate_meters(&rec);
+    assert_eq!(totals.len(), 3);
+    assert_eq!(totals["danger_score"], 300);
+    assert_eq!(totals["spawn_count"], 2);
+    assert_eq!(totals["big"], 2 * i64::from(i32::MAX));
+    assert!(aggregate_meters(&record(Vec::new())).is_empty());
+}
+
+#[test]
+fn comparison_reports_only_differing_keys() {
+    let a = record(vec![
+        Command::meter_at(0, "danger_score", 100),
+        Command::meter_at(0, "spawn_count", 2),
+        Command::meter_at(1, "same_total", 3),
+        Command::meter_at(1, "only_a", 5),
+    ]);
+    let b = record(vec![
+        Command::meter_at(0, "danger_score", 40),
+        Command::meter_at(0, "spawn_count", 1),
+        Command::meter_at(5, "spawn_count", 1),
+        Command::meter_at(2, "same_total", 1),
+        Command::meter_at(3, "same_total", 2),
+        Command::meter_at(4, "only_b", 0),
+    ]);
+    let diffs = compare_aggregates(&a, &b);
+    assert_eq!(
+        diffs.into_iter().collect::<Vec<_>>(),
+        vec![
+            ("danger_score".to_string(), (100, 40)),
+            ("only_a".to_string(), (5, 0)),
+        ]
+    );
+    assert!(compare_aggregates(&a, &a).is_empty());
+}
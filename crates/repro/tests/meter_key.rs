@@ -0,0 +1,58 @@
+use repro::strict::ParseMode;
+use repro::{canonical_json_bytes, hash_record, Command, MeterKey, Record, RecordMeta};
+
+/// Canonical bytes of `sample().commands` and the record's hash as produced
+/// when meter keys were plain `String`s.
+const STRING_KEY_BYTES: &str = concat!(
+    r#"[{"Meter":{"key":"wheel_stance","value":1},"t":0},"#,
+    r#"{"Meter":{"key":"danger_score","value":12},"t":0},"#,
+    r#"{"Spawn":{"kind":"bandit","x_mm":1000,"y_mm":0,"z_mm":-2500},"t":3},"#,
+    r#"{"Meter":{"key":"spawn_count","value":1},"t":3},"#,
+    r#"{"Meter":{"key":"danger_score","value":-4},"t":30},"#,
+    r#"{"Meter":{"key":"route_toll_cents","value":2500},"t":30}]"#,
+    "\n"
+);
+const STRING_KEY_HASH: &str = "cde8ffc6de16297721d1c85d3f69d4e0b864461f25e560c4d7e310ba34e648d4";
+
+fn sample() -> Record {
+    Record {
+        meta: RecordMeta {
+            schema: 3,
+            world_seed: "0x00000000DEADBEEF".into(),
+            link_id: "7".into(),
+            rulepack: "assets/rulepack.toml".into(),
+            weather: "Fog".into(),
+            rng_salt: "salt".into(),
+            day: 4,
+            mission_minutes: 8,
+            ..RecordMeta::default()
+        },
+        commands: vec![
+            Command::meter_at(0, "wheel_stance", 1),
+            Command::meter_at(0, "danger_score", 12),
+            Command::spawn_at(3, "bandit", 1_000, 0, -2_500),
+            Command::meter_at(3, MeterKey::intern("spawn_count"), 1),
+            Command::meter_at(30, String::from("danger_score"), -4),
+            Command::meter_at(30, "route_toll_cents", 2_500),
+        ],
+        inputs: Vec::new(),
+        annotations: Vec::new(),
+    }
+}
+
+#[test]
+fn meter_keys_serialize_like_strings() {
+    let record = sample();
+    let bytes = canonical_json_bytes(&record.commands).expect("canonical bytes");
+    assert_eq!(String::from_utf8(bytes).expect("utf8"), STRING_KEY_BYTES);
+    assert_eq!(hash_record(&record).expect("hash"), STRING_KEY_HASH);
+}
+
+#[test]
+fn parsed_keys_replay_equal_to_literals() {
+    let record = sample();
+    let bytes = canonical_json_bytes(&record).expect("canonical bytes");
+    let parsed = Record::from_json_bytes(&bytes, ParseMode::Strict).expect("strict parse");
+    assert_eq!(parsed, record);
+    assert_eq!(hash_record(&parsed).expect("hash"), STRING_KEY_HASH);
+}
@@ -0,0 +1,126 @@
+use std::collections::BTreeSet;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+use repro::fuzz::{mutate, mutate_with, Mutation};
+use repro::strict::{ParseMode, RecordParseError};
+use repro::{
+    Annotation, Command, CommandSchema, IdleSpan, InputEvent, Record, RecordMeta, RECORD_SCHEMA,
+};
+
+const MUTATIONS: u64 = 100;
+
+fn sample_record(command_schema: CommandSchema) -> Record {
+    let mut record = Record {
+        meta: RecordMeta {
+            schema: RECORD_SCHEMA,
+            world_seed: "0x00000000000000F0".into(),
+            link_id: "2".into(),
+            rulepack: "assets/rulepacks/day_001.toml".into(),
+            weather: "Fog".into(),
+            rng_salt: "salt".into(),
+            day: 5,
+            pp: 600,
+            density_per_10k: 6,
+            cadence_per_min: 4,
+            mission_minutes: 4,
+            player_rating: 50,
+            prior_danger_score: Some(-12),
+            rulepack_hash: Some("ab12".into()),
+            idle_spans: vec![IdleSpan { from: 3, until: 9 }],
+            fixed_dt_ns: Some(16_666_667),
+            command_schema,
+            ..RecordMeta::default()
+        },
+        commands: vec![
+            Command::meter_at(0, "danger_score", 7),
+            Command::spawn_at(0, "bandit", 10, 0, -10),
+            Command::meter_at(2, "ui_click_buy", 3),
+            Command::spawn_at(9, "wolf", -5, 2, 40),
+            Command::meter_at(9, "danger_diff_sign", -1),
+        ],
+        inputs: vec![
+            InputEvent {
+                t: 1,
+                input: "fire".into(),
+            },
+            InputEvent {
+                t: 2,
+                input: "brace".into(),
+            },
+        ],
+        annotations: vec![Annotation {
+            t: 2,
+            label: "ambush".into(),
+            note: "first contact".into(),
+        }],
+    };
+    record.add_annotation(9, "retreat", "");
+    record
+}
+
+/// Parses `value` in `mode`, failing the test if the parser panics.
+fn parse_without_panic(
+    value: &serde_json::Value,
+    mode: ParseMode,
+    seed: u64,
+) -> Result<Record, RecordParseError> {
+    catch_unwind(AssertUnwindSafe(|| {
+        Record::from_json_value(value.clone(), mode)
+    }))
+    .unwrap_or_else(|_| panic!("{mode:?} parse panicked on mutation {seed}: {value}"))
+}
+
+#[test]
+fn mutations_are_deterministic() {
+    let record = sample_record(CommandSchema::PerCommand);
+    let original = serde_json::to_value(&record).expect("record value");
+    let mut distinct = BTreeSet::new();
+    for seed in 0..MUTATIONS {
+        let mutated = mutate(&record, seed);
+        assert_eq!(mutated, mutate(&record, seed), "seed {seed}");
+        assert_ne!(mutated, original, "seed {seed} left the record untouched");
+        distinct.insert(mutated.to_string());
+    }
+    assert!(distinct.len() > 50, "{} distinct mutations", distinct.len());
+}
+
+#[test]
+fn mutated_records_parse_or_fail_cleanly() {
+    for schema in [CommandSchema::PerCommand, CommandSchema::ByTick] {
+        let record = sample_record(schema);
+        let mut kinds = Vec::new();
+        let (mut parsed, mut rejected) = (0, 0);
+        for seed in 0..MUTATIONS {
+            let (value, mutation) = mutate_with(&record, seed);
+            kinds.push(mutation);
+
+            match parse_without_panic(&value, ParseMode::Lenient, seed) {
+                Ok(_) => parsed += 1,
+                Err(err) => {
+                    rejected += 1;
+                    assert!(
+                        matches!(
+                            err,
+                            RecordParseError::Invalid { .. } | RecordParseError::Json(_)
+                        ),
+                        "lenient mutation {seed} gave {err:?}"
+                    );
+                    assert!(!err.to_string().is_empty());
+                }
+            }
+            // Strict parsing also names unknown, missing, mistyped and
+            // out-of-range fields; any of its errors is clean.
+            if let Err(err) = parse_without_panic(&value, ParseMode::Strict, seed) {
+                assert!(!err.to_string().is_empty(), "strict mutation {seed}");
+            }
+        }
+        for mutation in [
+            Mutation::DropField,
+            Mutation::FlipType,
+            Mutation::ReorderArray,
+        ] {
+            assert!(kinds.contains(&mutation), "{schema}: no {mutation:?}");
+        }
+        assert!(parsed > 0 && rejected > 0, "{schema}: {parsed}/{rejected}");
+    }
+}
@@ -0,0 +1,224 @@
+use std::fs;
+use std::path::Path;
+
+use repro::strict::{ParseMode, RecordParseError};
+use repro::{canonical_json_bytes, Command, Record, RecordMeta};
+use serde_json::{json, Value};
+
+fn full_record() -> Value {
+    let record = Record {
+        meta: RecordMeta {
+            schema: 2,
+            world_seed: "omega".into(),
+            link_id: "leg_01".into(),
+            rulepack: "assets/rulepack.toml".into(),
+            weather: "Clear".into(),
+            rng_salt: "salt".into(),
+            day: 3,
+            pp: 250,
+            density_per_10k: 5,
+            cadence_per_min: 3,
+            mission_minutes: 6,
+            player_rating: 50,
+            ..RecordMeta::default()
+        },
+        commands: vec![
+            Command::meter_at(0, "danger_score", 7),
+            Command::spawn_at(1, "bandit", 10, 0, -10),
+        ],
+        inputs: Vec::new(),
+        annotations: Vec::new(),
+    };
+    let mut value = serde_json::to_value(&record).expect("record value");
+    value["inputs"] = json!([{ "t": 2, "input": "fire" }]);
+    value
+}
+
+fn parse(value: &Value, mode: ParseMode) -> Result<Record, RecordParseError> {
+    let bytes = canonical_json_bytes(value).expect("canonical bytes");
+    Record::from_json_bytes(&bytes, mode)
+}
+
+fn path_of(err: &RecordParseError) -> &str {
+    match err {
+        RecordParseError::UnknownField { path }
+        | RecordParseError::MissingField { path }
+        | RecordParseError::WrongType { path, .. }
+        | RecordParseError::OutOfRange { path, .. }
+        | RecordParseError::Invalid { path, .. } => path,
+        RecordParseError::Json(err) => panic!("unexpected json error: {err}"),
+    }
+}
+
+#[test]
+fn full_record_parses_in_both_modes() {
+    let value = full_record();
+    let strict = parse(&value, ParseMode::Strict).expect("strict");
+    let lenient = parse(&value, ParseMode::Lenient).expect("lenient");
+    assert_eq!(strict, lenient);
+}
+
+#[test]
+fn unknown_meta_field_fails_only_strict() {
+    let mut value = full_record();
+    value["meta"]["foo"] = json!(1);
+    let err = parse(&value, ParseMode::Strict).unwrap_err();
+    assert!(matches!(err, RecordParseError::UnknownField { .. }));
+    assert_eq!(path_of(&err), "meta.foo");
+    assert!(err.to_string().contains("meta.foo"), "{err}");
+    assert!(parse(&value, ParseMode::Lenient).is_ok());
+}
+
+#[test]
+fn defaulted_fields_are_required_in_strict() {
+    let meta_fields = [
+        "day",
+        "pp",
+        "density_per_10k",
+        "cadence_per_min",
+        "mission_minutes",
+        "player_rating",
+    ];
+    for field in meta_fields {
+        let mut value = full_record();
+        value["meta"].as_object_mut().unwrap().remove(field);
+        let err = parse(&value, ParseMode::Strict).unwrap_err();
+        assert!(matches!(err, RecordParseError::MissingField { .. }));
+        assert_eq!(path_of(&err), format!("meta.{field}"));
+        assert!(parse(&value, ParseMode::Lenient).is_ok(), "{field}");
+    }
+    for field in ["commands", "inputs"] {
+        let mut value = full_record();
+        value.as_object_mut().unwrap().remove(field);
+        let err = parse(&value, ParseMode::Strict).unwrap_err();
+        assert_eq!(path_of(&err), field);
+        assert!(parse(&value, ParseMode::Lenient).is_ok(), "{field}");
+    }
+}
+
+#[test]
+fn wrong_types_and_ranges_name_the_path() {
+    let mut value = full_record();
+    value["meta"]["pp"] = json!("250");
+    let err = parse(&value, ParseMode::Strict).unwrap_err();
+    assert!(matches!(err, RecordParseError::WrongType { .. }));
+    assert_eq!(path_of(&err), "meta.pp");
+
+    let mut value = full_record();
+    value["meta"]["pp"] = json!(70_000);
+    assert_eq!(
+        path_of(&parse(&value, ParseMode::Strict).unwrap_err()),
+        "meta.pp"
+    );
+
+    let mut value = full_record();
+    value["meta"]["player_rating"] = json!(101);
+    let err = parse(&value, ParseMode::Strict).unwrap_err();
+    assert!(matches!(err, RecordParseError::OutOfRange { max: 100, .. }));
+    assert_eq!(path_of(&err), "meta.player_rating");
+}
+
+#[test]
+fn nested_unknown_fields_are_rejected() {
+    let mut value = full_record();
+    value["commands"][0]["Meter"]["unit"] = json!("pp");
+    assert_eq!(
+        path_of(&parse(&value, ParseMode::Strict).unwrap_err()),
+        "commands[0].Meter.unit"
+    );
+
+    let mut value = full_record();
+    value["commands"][1]["note"] = json!("x");
+    assert_eq!(
+        path_of(&parse(&value, ParseMode::Strict).unwrap_err()),
+        "commands[1].note"
+    );
+
+    let mut value = full_record();
+    value["inputs"][0]["held"] = json!(true);
+    assert_eq!(
+        path_of(&parse(&value, ParseMode::Strict).unwrap_err()),
+        "inputs[0].held"
+    );
+
+    let mut value = full_record();
+    value["meta"]["idle_spans"] = json!([{ "from": 2, "until": 9, "len": 7 }]);
+    assert_eq!(
+        path_of(&parse(&value, ParseMode::Strict).unwrap_err()),
+        "meta.idle_spans[0].len"
+    );
+}
+
+fn record_with_bad_spawn(x_mm: Option<Value>) -> Value {
+    let mut value = full_record();
+    let commands = value["commands"].as_array_mut().unwrap();
+    commands.push(json!({ "t": 4, "Meter": { "key": "danger_score", "value": 9 } }));
+    commands
+        .push(json!({ "t": 5, "Spawn": { "kind": "bandit", "x_mm": 1, "y_mm": 2, "z_mm": 3 } }));
+    let spawn = commands[3]["Spawn"].as_object_mut().unwrap();
+    match x_mm {
+        Some(x_mm) => spawn.insert("x_mm".into(), x_mm),
+        None => spawn.remove("x_mm"),
+    };
+    value
+}
+
+#[test]
+fn bad_command_fields_name_the_index_in_both_modes() {
+    for x_mm in [Some(json!("far")), Some(json!(1u64 << 40)), None] {
+        let value = record_with_bad_spawn(x_mm.clone());
+        for mode in [ParseMode::Strict, ParseMode::Lenient] {
+            let err = parse(&value, mode).unwrap_err();
+            assert_eq!(path_of(&err), "commands[3].Spawn.x_mm", "{mode:?} {x_mm:?}");
+            let message = err.to_string();
+            assert!(message.contains("commands[3]"), "{message}");
+            assert!(message.contains("x_mm"), "{message}");
+            assert_eq!(err.position(), None);
+        }
+    }
+}
+
+#[test]
+fn lenient_errors_name_meta_and_input_fields() {
+    let mut value = full_record();
+    value["meta"]["day"] = json!("three");
+    let err = parse(&value, ParseMode::Lenient).unwrap_err();
+    assert!(matches!(err, RecordParseError::Invalid { .. }));
+    assert_eq!(path_of(&err), "meta.day");
+
+    let mut value = full_record();
+    value["inputs"][0].as_object_mut().unwrap().remove("input");
+    let err = parse(&value, ParseMode::Lenient).unwrap_err();
+    assert_eq!(path_of(&err), "inputs[0].input");
+
+    let mut value = full_record();
+    value["commands"][1]["t"] = json!(-1);
+    let err = parse(&value, ParseMode::Lenient).unwrap_err();
+    assert_eq!(path_of(&err), "commands[1].t");
+}
+
+#[test]
+fn syntax_errors_report_line_and_column() {
+    let bytes = b"{\n  \"meta\": {\n    \"schema\": 2,,\n";
+    for mode in [ParseMode::Strict, ParseMode::Lenient] {
+        let err = Record::from_json_bytes(bytes, mode).unwrap_err();
+        assert!(matches!(err, RecordParseError::Json(_)), "{err}");
+        assert_eq!(err.position(), Some((3, 17)), "{err}");
+    }
+}
+
+#[test]
+fn golden_records_parse_strictly() {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("../../repro/records");
+    let mut checked = 0;
+    for entry in fs::read_dir(&dir).expect("records dir") {
+        let path = entry.expect("entry").path();
+        if path.extension().is_some_and(|ext| ext == "json") {
+            let bytes = fs::read(&path).expect("record bytes");
+            Record::from_json_bytes(&bytes, ParseMode::Strict)
+                .unwrap_or_else(|err| panic!("{}: {err}", path.display()));
+            checked += 1;
+        }
+    }
+    assert!(checked > 0);
+}
@@ -1,3 +1,7 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
+
 #[derive(Clone, Copy)]
 pub struct GenParams {
     pub seed: u64,
@@ -10,6 +14,7 @@ pub struct ChunkKey {
     pub x: i32,
     pub z: i32,
 }
+#[derive(Default)]
 pub struct MeshData {
     pub positions: Vec<[f32; 3]>,
     pub normals: Vec<[f32; 3]>,
@@ -23,21 +28,90 @@ impl WorldGen {
     pub fn new(p: GenParams) -> Self {
         Self { p }
     }
+    pub fn height_at(&self, x: f32, z: f32) -> f32 {
+        let y = (x * 0.0113).sin() * 0.6 + (z * 0.0097).cos() * 0.4;
+        y * self.p.height
+    }
+    pub fn normal_at(&self, x: f32, z: f32) -> [f32; 3] {
+        let e = self.p.scale.abs().max(f32::EPSILON);
+        let dx = (self.height_at(x + e, z) - self.height_at(x - e, z)) / (2.0 * e);
+        let dz = (self.height_at(x, z + e) - self.height_at(x, z - e)) / (2.0 * e);
+        let len = (dx * dx + 1.0 + dz * dz).sqrt();
+        [-dx / len, 1.0 / len, -dz / len]
+    }
     pub fn chunk_mesh(&self, key: ChunkKey) -> MeshData {
-        let n = self.p.chunk_size as usize;
-        let stride = self.p.scale * (self.p.chunk_size - 1) as f32;
+        self.chunk_mesh_lod(key, 0)
+    }
+    /// Meshes every chunk in `keys`, returned in `keys` order. A chunk's mesh
+    /// depends only on its key, so chunks are built across threads unless the
+    /// `deterministic` feature pins generation to the calling thread.
+    pub fn generate_chunks(&self, keys: &[ChunkKey]) -> Vec<(ChunkKey, MeshData)> {
+        if cfg!(feature = "deterministic") {
+            self.generate_chunks_serial(keys)
+        } else {
+            self.generate_chunks_parallel(keys)
+        }
+    }
+    fn generate_chunks_serial(&self, keys: &[ChunkKey]) -> Vec<(ChunkKey, MeshData)> {
+        keys.iter()
+            .map(|&key| (key, self.chunk_mesh(key)))
+            .collect()
+    }
+    fn generate_chunks_parallel(&self, keys: &[ChunkKey]) -> Vec<(ChunkKey, MeshData)> {
+        let threads = thread::available_parallelism()
+            .map_or(1, usize::from)
+            .min(keys.len());
+        if threads <= 1 {
+            return self.generate_chunks_serial(keys);
+        }
+        let next = AtomicUsize::new(0);
+        let meshes: Mutex<Vec<Option<MeshData>>> = Mutex::new(keys.iter().map(|_| None).collect());
+        thread::scope(|scope| {
+            for _ in 0..threads {
+                scope.spawn(|| loop {
+                    let idx = next.fetch_add(1, Ordering::Relaxed);
+                    let Some(&key) = keys.get(idx) else {
+                        break;
+                    };
+                    let mesh = self.chunk_mesh(key);
+                    meshes.lock().expect("chunk meshes")[idx] = Some(mesh);
+                });
+            }
+        });
+        keys.iter()
+            .zip(meshes.into_inner().expect("chunk meshes"))
+            .map(|(&key, mesh)| (key, mesh.expect("chunk worker exited early")))
+            .collect()
+    }
+    /// Samples every `2^lod`th grid point; the last row and column are always
+    /// included so chunk corners and edges line up with every other LOD.
+    /// LODs above 0 get a skirt hanging below each edge to hide T-junction
+    /// cracks against finer neighbours. Chunks smaller than 2×2 have no
+    /// quads and yield an empty mesh.
+    pub fn chunk_mesh_lod(&self, key: ChunkKey, lod: u8) -> MeshData {
+        if self.p.chunk_size < 2 {
+            return MeshData::default();
+        }
+        let last = self.p.chunk_size - 1;
+        let step = 1u32.checked_shl(u32::from(lod)).unwrap_or(u32::MAX).max(1);
+        let mut samples: Vec<u32> = (0..last).step_by(step as usize).collect();
+        samples.push(last);
+        let n = samples.len();
+        let stride = self.p.scale * last as f32;
         let base_x = key.x as f32 * stride;
         let base_z = key.z as f32 * stride;
         let mut positions = Vec::with_capacity(n * n);
+        let mut normals = Vec::with_capacity(n * n);
         let mut uvs = Vec::with_capacity(n * n);
-        let mut indices = Vec::with_capacity((n - 1) * (n - 1) * 6);
-        for j in 0..n {
-            for i in 0..n {
-                let wx = base_x + (i as f32) * self.p.scale;
-                let wz = base_z + (j as f32) * self.p.scale;
-                let y = (wx * 0.0113).sin() * 0.6 + (wz * 0.0097).cos() * 0.4;
-                positions.push([wx, y * self.p.height, wz]);
-                uvs.push([i as f32 / (n - 1) as f32, j as f32 / (n - 1) as f32]);
+        let mut indices = Vec::with_capacity(n.saturating_sub(1).pow(2) * 6);
+        let uv_div = last.max(1) as f32;
+        for &sj in &samples {
+            for &si in &samples {
+                let wx = base_x + (si as f32) * self.p.scale;
+                let wz = base_z + (sj as f32) * self.p.scale;
+                positions.push([wx, self.height_at(wx, wz), wz]);
+                normals.push(self.normal_at(wx, wz));
+                uvs.push([si as f32 / uv_div, sj as f32 / uv_div]);
             }
         }
         for j in 0..(n - 1) {
@@ -49,7 +123,27 @@ impl WorldGen {
                 indices.extend_from_slice(&[a, c, b, b, c, d]);
             }
         }
-        let normals = vec![[0.0, 1.0, 0.0]; positions.len()];
+        if lod > 0 && n > 1 {
+            let depth = self.p.scale * step as f32;
+            let mut ring: Vec<u32> = (0..n).map(|i| i as u32).collect();
+            ring.extend((1..n).map(|j| (j * n + n - 1) as u32));
+            ring.extend((0..n - 1).rev().map(|i| ((n - 1) * n + i) as u32));
+            ring.extend((1..n - 1).rev().map(|j| (j * n) as u32));
+            let skirt_base = positions.len() as u32;
+            for &top in &ring {
+                let [x, y, z] = positions[top as usize];
+                positions.push([x, y - depth, z]);
+                normals.push(normals[top as usize]);
+                uvs.push(uvs[top as usize]);
+            }
+            let count = ring.len() as u32;
+            for k in 0..count {
+                let next = (k + 1) % count;
+                let (a, b) = (ring[k as usize], ring[next as usize]);
+                let (c, d) = (skirt_base + k, skirt_base + next);
+                indices.extend_from_slice(&[a, b, c, b, d, c]);
+            }
+        }
         MeshData {
             positions,
             normals,
@@ -58,3 +152,130 @@ impl WorldGen {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn gen(height: f32) -> WorldGen {
+        gen_sized(17, height)
+    }
+
+    fn gen_sized(chunk_size: u32, height: f32) -> WorldGen {
+        WorldGen::new(GenParams {
+            seed: 7,
+            chunk_size,
+            scale: 2.0,
+            height,
+        })
+    }
+
+    fn edge_positions(mesh: &MeshData, n: usize) -> Vec<[f32; 3]> {
+        (0..n * n)
+            .filter(|idx| {
+                let (i, j) = (idx % n, idx / n);
+                i == 0 || j == 0 || i == n - 1 || j == n - 1
+            })
+            .map(|idx| mesh.positions[idx])
+            .collect()
+    }
+
+    #[test]
+    fn normals_are_unit_length_and_face_up_when_flat() {
+        let mesh = gen(8.0).chunk_mesh(ChunkKey { x: 1, z: -2 });
+        for normal in &mesh.normals {
+            let len =
+                (normal[0] * normal[0] + normal[1] * normal[1] + normal[2] * normal[2]).sqrt();
+            assert!((len - 1.0).abs() < 1e-5, "normal {normal:?}");
+            assert!(normal[1] > 0.0);
+        }
+
+        let flat = gen(0.0).chunk_mesh(ChunkKey { x: 0, z: 0 });
+        for normal in &flat.normals {
+            assert_eq!(*normal, [0.0, 1.0, 0.0]);
+        }
+    }
+
+    #[test]
+    fn lod_edges_match_finer_mesh() {
+        let wg = gen(8.0);
+        let key = ChunkKey { x: 3, z: 5 };
+        let lod0 = wg.chunk_mesh_lod(key, 0);
+        let lod1 = wg.chunk_mesh_lod(key, 1);
+        let lod0_edges = edge_positions(&lod0, 17);
+        let lod1_edges = edge_positions(&lod1, 9);
+        for pos in &lod1_edges {
+            assert!(lod0_edges.contains(pos), "LOD1 edge vertex {pos:?} missing");
+        }
+        let corners = |edges: &[[f32; 3]]| (edges[0], edges[edges.len() - 1]);
+        assert_eq!(corners(&lod0_edges), corners(&lod1_edges));
+        assert!(lod1
+            .indices
+            .iter()
+            .all(|&i| (i as usize) < lod1.positions.len()));
+    }
+
+    #[test]
+    fn height_at_matches_mesh_vertices() {
+        let wg = gen(8.0);
+        let mesh = wg.chunk_mesh(ChunkKey { x: -1, z: 2 });
+        for pos in &mesh.positions {
+            assert_eq!(pos[1], wg.height_at(pos[0], pos[2]));
+        }
+    }
+
+    #[test]
+    fn degenerate_chunk_sizes_yield_empty_meshes() {
+        for chunk_size in [0, 1] {
+            for lod in [0, 1] {
+                let mesh = gen_sized(chunk_size, 8.0).chunk_mesh_lod(ChunkKey { x: 2, z: -3 }, lod);
+                assert!(mesh.positions.is_empty(), "chunk_size {chunk_size}");
+                assert!(mesh.normals.is_empty() && mesh.uvs.is_empty());
+                assert!(mesh.indices.is_empty(), "chunk_size {chunk_size}");
+            }
+        }
+
+        let mesh = gen_sized(2, 8.0).chunk_mesh(ChunkKey { x: 2, z: -3 });
+        assert_eq!(mesh.positions.len(), 4);
+        assert_eq!(mesh.indices.len(), 6);
+        assert!(mesh.indices.iter().all(|&i| (i as usize) < 4));
+        assert!(mesh
+            .uvs
+            .iter()
+            .all(|uv| uv.iter().all(|c| (0.0..=1.0).contains(c))));
+    }
+
+    fn mesh_bytes(mesh: &MeshData) -> Vec<u8> {
+        let floats = mesh
+            .positions
+            .iter()
+            .chain(&mesh.normals)
+            .flat_map(|v| v.iter())
+            .chain(mesh.uvs.iter().flat_map(|v| v.iter()));
+        floats
+            .flat_map(|f| f.to_bits().to_le_bytes())
+            .chain(mesh.indices.iter().flat_map(|i| i.to_le_bytes()))
+            .collect()
+    }
+
+    #[test]
+    fn parallel_and_serial_chunks_are_byte_identical() {
+        let wg = gen(8.0);
+        let mut keys: Vec<ChunkKey> = (-4..4)
+            .flat_map(|z| (-4..4).map(move |x| ChunkKey { x, z }))
+            .collect();
+        keys.reverse();
+        keys.push(ChunkKey { x: 1, z: 1 });
+        let serial = wg.generate_chunks_serial(&keys);
+        let parallel = wg.generate_chunks_parallel(&keys);
+        let public = wg.generate_chunks(&keys);
+        assert_eq!(serial.len(), keys.len());
+        for (idx, key) in keys.iter().enumerate() {
+            assert!(serial[idx].0 == *key && parallel[idx].0 == *key && public[idx].0 == *key);
+            let bytes = mesh_bytes(&serial[idx].1);
+            assert_eq!(bytes, mesh_bytes(&parallel[idx].1), "chunk {idx}");
+            assert_eq!(bytes, mesh_bytes(&public[idx].1), "chunk {idx}");
+        }
+        assert!(wg.generate_chunks(&[]).is_empty());
+    }
+}
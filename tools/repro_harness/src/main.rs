@@ -1,19 +1,210 @@
 use bevy::prelude::*;
-use clap::Parser;
+use std::ops::Range;
+
+use clap::{Parser, Subcommand};
+use game::soak::{run_soak, SoakConfig};
+use repro::coverage::summarize;
+use repro::diff::compare_aggregates;
+use repro::strict::ParseMode;
 use repro::{hash_record, Record};
 
 #[derive(Parser, Debug)]
+#[command(subcommand_negates_reqs = true)]
 struct Args {
-    #[arg(long)]
-    replay: String,
+    #[arg(long, required = true)]
+    replay: Option<String>,
     #[arg(long)]
     assert_hash: Option<String>,
+    /// Compare per-key meter totals against another record.
+    #[arg(long)]
+    meter_diff: Option<String>,
+    /// Print the replay tick window around the annotation with this label.
+    #[arg(long)]
+    at_annotation: Option<String>,
+    /// Print which command kinds and meter keys the record exercises.
+    #[arg(long)]
+    coverage: bool,
+    /// Accept records with missing or unknown fields.
+    #[arg(long, global = true)]
+    lenient: bool,
+    #[command(subcommand)]
+    command: Option<Cmd>,
+}
+
+#[derive(Subcommand, Debug)]
+enum Cmd {
+    /// Writes the record's command timeline as CSV.
+    ExportCsv {
+        #[arg(long)]
+        replay: String,
+        #[arg(long)]
+        out: String,
+        /// Export only Meter commands.
+        #[arg(long)]
+        meters_only: bool,
+    },
+    /// Records every seed's campaign twice and checks the hashes agree.
+    Soak {
+        /// Seed range, e.g. `0..16`.
+        #[arg(long, value_parser = parse_seed_range, default_value = "0..16")]
+        seeds: Range<u64>,
+        #[arg(long, default_value_t = 3)]
+        legs: u32,
+        #[arg(long, default_value_t = 7200)]
+        ticks: u32,
+        /// Worker threads; 0 uses every available core.
+        #[arg(long, default_value_t = 0)]
+        threads: usize,
+    },
+}
+
+fn parse_seed_range(value: &str) -> Result<Range<u64>, String> {
+    let (start, end) = value
+        .split_once("..")
+        .ok_or_else(|| format!("expected START..END, got {value}"))?;
+    let start = start.trim().parse::<u64>().map_err(|err| err.to_string())?;
+    let end = end.trim().parse::<u64>().map_err(|err| err.to_string())?;
+    if start >= end {
+        return Err(format!("empty seed range {value}"));
+    }
+    Ok(start..end)
+}
+
+fn load_record(path: &str, lenient: bool) -> Record {
+    let data = std::fs::read(path).expect("record file");
+    let mode = if lenient {
+        ParseMode::Lenient
+    } else {
+        ParseMode::Strict
+    };
+    Record::from_json_bytes(&data, mode).unwrap_or_else(|err| {
+        eprintln!("invalid record {path}: {err}");
+        std::process::exit(1);
+    })
+}
+
+fn export_csv(record: &Record, out: &str, meters_only: bool) {
+    let file = std::fs::File::create(out).expect("csv file");
+    let writer = std::io::BufWriter::new(file);
+    let written = if meters_only {
+        record.meters_to_csv(writer)
+    } else {
+        record.to_csv(writer)
+    };
+    written.expect("write csv");
+}
+
+/// Prints every meter whose total differs; returns whether any did.
+fn report_meter_diff(record: &Record, other_path: &str, lenient: bool) -> bool {
+    let other = load_record(other_path, lenient);
+    print_annotations(&other);
+    let diffs = compare_aggregates(record, &other);
+    for (key, (ours, theirs)) in &diffs {
+        println!("{key}: {ours} vs {theirs} ({:+})", theirs - ours);
+    }
+    !diffs.is_empty()
+}
+
+fn print_coverage(record: &Record) {
+    let coverage = summarize(record);
+    let tick = |t: Option<u32>| t.map_or_else(|| "-".to_string(), |t| t.to_string());
+    println!("{:<12} {:>8}", "kind", "count");
+    println!("{:<12} {:>8}", "spawn", coverage.spawns);
+    println!("{:<12} {:>8}", "meter", coverage.meters);
+    println!("{:<12} {:>8}", "meter keys", coverage.meter_keys.len());
+    for key in &coverage.meter_keys {
+        println!("  {key}");
+    }
+    println!("{:<12} {:>8}", "first tick", tick(coverage.first_tick));
+    println!("{:<12} {:>8}", "last tick", tick(coverage.last_tick));
+    println!("{:<12} {:>8}", "any spawn", coverage.has_spawn());
+}
+
+/// Ticks either side of an annotation that `--at-annotation` replays.
+const ANNOTATION_WINDOW_TICKS: u32 = 30;
+
+fn print_annotations(record: &Record) {
+    for annotation in &record.annotations {
+        if annotation.note.is_empty() {
+            println!("annotation t={} {}", annotation.t, annotation.label);
+        } else {
+            println!(
+                "annotation t={} {}: {}",
+                annotation.t, annotation.label, annotation.note
+            );
+        }
+    }
+}
+
+/// Prints the tick window around `label` and the commands inside it;
+/// returns false when the record has no such annotation.
+fn report_annotation_window(record: &Record, label: &str) -> bool {
+    let Some(window) = record.annotation_window(label, ANNOTATION_WINDOW_TICKS) else {
+        eprintln!("no annotation labelled {label}");
+        return false;
+    };
+    println!(
+        "{label}: replay ticks {}..={}",
+        window.start(),
+        window.end()
+    );
+    for command in record
+        .commands
+        .iter()
+        .filter(|command| window.contains(&command.t))
+    {
+        println!("  t={} {:?}", command.t, command.kind);
+    }
+    true
 }
 
 fn main() {
     let args = Args::parse();
-    let data = std::fs::read_to_string(&args.replay).expect("record file");
-    let rec: Record = serde_json::from_str(&data).expect("valid record");
+    match &args.command {
+        Some(Cmd::ExportCsv {
+            replay,
+            out,
+            meters_only,
+        }) => {
+            let rec = load_record(replay, args.lenient);
+            export_csv(&rec, out, *meters_only);
+            return;
+        }
+        Some(Cmd::Soak {
+            seeds,
+            legs,
+            ticks,
+            threads,
+        }) => {
+            let cfg = SoakConfig {
+                seeds: seeds.clone(),
+                legs: *legs,
+                ticks: *ticks,
+                threads: *threads,
+            };
+            let report = run_soak(&cfg).unwrap_or_else(|err| {
+                eprintln!("soak failed: {err:#}");
+                std::process::exit(2);
+            });
+            println!("{report}");
+            if !report.is_stable() {
+                std::process::exit(1);
+            }
+            return;
+        }
+        None => {}
+    }
+    let replay = args.replay.as_deref().expect("--replay is required");
+    let rec = load_record(replay, args.lenient);
+    print_annotations(&rec);
+    if args.coverage {
+        print_coverage(&rec);
+    }
+    if let Some(label) = args.at_annotation.as_deref() {
+        if !report_annotation_window(&rec, label) {
+            std::process::exit(1);
+        }
+    }
     // For M0 we don't simulate; we just hash the record content.
     let got = hash_record(&rec).expect("hash record");
     if let Some(expected_path) = args.assert_hash {
@@ -26,6 +217,11 @@ fn main() {
             std::process::exit(1);
         }
     }
+    if let Some(other) = args.meter_diff.as_deref() {
+        if report_meter_diff(&rec, other, args.lenient) {
+            std::process::exit(1);
+        }
+    }
     // Prepare a tiny app to prove headless plugin init works (no renderer).
     let mut app = App::new();
     app.add_plugins(MinimalPlugins);